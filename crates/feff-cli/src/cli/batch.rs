@@ -0,0 +1,290 @@
+use super::CliError;
+use super::commands::plan_deck_workflow;
+use super::helpers::{current_working_dir, resolve_cli_path};
+use feff_core::domain::{FeffError, FeffErrorCategory};
+use feff_core::modules::batch::{
+    BATCH_JOB_LOG, BATCH_SUMMARY_JSON, BatchJobResult, resolve_batch_directories,
+    write_batch_summary,
+};
+use feff_core::modules::cache::{RunManifest, StageStatus, directory_label};
+use std::collections::VecDeque;
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+#[derive(clap::Args, Debug, Clone)]
+pub(super) struct BatchArgs {
+    /// Run directories, feff.inp paths or quoted glob patterns ('snapshots/*', 'runs/**/feff.inp').
+    #[arg(value_name = "PATTERN", required_unless_present = "list")]
+    patterns: Vec<String>,
+
+    /// File listing one run directory or pattern per line ('#' starts a comment).
+    #[arg(long, value_name = "FILE")]
+    list: Option<PathBuf>,
+
+    /// Number of runs executed at once (defaults to the available CPUs).
+    #[arg(long, short = 'j', value_name = "N")]
+    jobs: Option<usize>,
+
+    /// Kill a run attempt that takes longer than SECONDS.
+    #[arg(long, value_name = "SECONDS")]
+    timeout: Option<u64>,
+
+    /// Extra attempts for a run that timed out or failed with a non-input error.
+    #[arg(long, value_name = "N", default_value_t = 0)]
+    retries: u32,
+
+    /// Re-run directories whose previous run is complete.
+    #[arg(long)]
+    force: bool,
+
+    /// Directory for batch-summary.json and batch-summary.csv.
+    #[arg(long, value_name = "DIR", default_value = ".")]
+    summary_dir: PathBuf,
+}
+
+/// Runs every matched directory with `feff10-rs run` in its own process, at
+/// most `--jobs` at a time, and writes a summary of their exit codes.
+pub(super) fn run_batch_command(args: BatchArgs) -> Result<i32, CliError> {
+    let working_dir = current_working_dir().map_err(CliError::Compute)?;
+    let mut patterns = args.patterns.clone();
+    if let Some(list) = &args.list {
+        patterns.extend(read_pattern_list(&resolve_cli_path(&working_dir, list))?);
+    }
+    let directories =
+        resolve_batch_directories(&working_dir, &patterns).map_err(CliError::Compute)?;
+    let workers = match args.jobs {
+        Some(0) => {
+            return Err(CliError::Usage(
+                "Invalid job count '0'; expected a positive integer.".to_string(),
+            ));
+        }
+        Some(jobs) => jobs,
+        None => thread::available_parallelism().map_or(1, |count| count.get()),
+    };
+    let executable = std::env::current_exe().map_err(|source| {
+        CliError::Compute(FeffError::io_system(
+            "IO.BATCH_EXECUTABLE",
+            format!("failed to locate the feff10-rs executable: {}", source),
+        ))
+    })?;
+
+    println!(
+        "Running {} directories with {} workers.",
+        directories.len(),
+        workers.min(directories.len())
+    );
+    let queue = Mutex::new(directories.iter().enumerate().collect::<VecDeque<_>>());
+    let results = Mutex::new(vec![None; directories.len()]);
+    let finished = Mutex::new(0_usize);
+    thread::scope(|scope| {
+        for _ in 0..workers.min(directories.len()) {
+            scope.spawn(|| {
+                loop {
+                    let Some((index, directory)) = queue.lock().expect("batch queue").pop_front()
+                    else {
+                        break;
+                    };
+                    let result = if !args.force && run_is_complete(directory) {
+                        BatchJobResult::skipped(directory)
+                    } else {
+                        run_with_retries(&executable, directory, &args)
+                    };
+                    let mut finished = finished.lock().expect("batch progress");
+                    *finished += 1;
+                    println!(
+                        "[{}/{}] {} {}",
+                        finished,
+                        directories.len(),
+                        result.status.as_str(),
+                        directory.display()
+                    );
+                    results.lock().expect("batch results")[index] = Some(result);
+                }
+            });
+        }
+    });
+
+    let results: Vec<BatchJobResult> = results
+        .into_inner()
+        .expect("batch results")
+        .into_iter()
+        .map(|result| result.expect("every queued directory should have a result"))
+        .collect();
+    let summary_dir = resolve_cli_path(&working_dir, &args.summary_dir);
+    write_batch_summary(&summary_dir, &results).map_err(CliError::Compute)?;
+    let failed = results.iter().filter(|result| !result.succeeded()).count();
+    println!(
+        "Completed batch: {} of {} directories succeeded; summary in {}.",
+        results.len() - failed,
+        results.len(),
+        summary_dir.join(BATCH_SUMMARY_JSON).display()
+    );
+    Ok(if failed > 0 { 1 } else { 0 })
+}
+
+fn read_pattern_list(path: &Path) -> Result<Vec<String>, CliError> {
+    let content = fs::read_to_string(path).map_err(|source| {
+        CliError::Compute(FeffError::io_system(
+            "IO.BATCH_LIST",
+            format!("failed to read batch list '{}': {}", path.display(), source),
+        ))
+    })?;
+    let base = path.parent().unwrap_or(Path::new("."));
+    Ok(content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| base.join(line).to_string_lossy().into_owned())
+        .collect())
+}
+
+/// Whether every module `feff.inp` enables is fresh in the run manifest.
+fn run_is_complete(directory: &Path) -> bool {
+    let (Ok(plan), Ok(manifest)) = (plan_deck_workflow(directory), RunManifest::load(directory))
+    else {
+        return false;
+    };
+    let statuses = manifest.plan_status(directory, &plan, &directory_label(directory));
+    !statuses.is_empty()
+        && statuses
+            .iter()
+            .all(|(_, status)| *status == StageStatus::Fresh)
+}
+
+fn run_with_retries(executable: &Path, directory: &Path, args: &BatchArgs) -> BatchJobResult {
+    let started = Instant::now();
+    let log_path = directory.join(BATCH_JOB_LOG);
+    let mut attempt = 0;
+    loop {
+        attempt += 1;
+        let result = run_attempt(
+            executable,
+            directory,
+            &log_path,
+            attempt,
+            args.timeout,
+            started,
+        );
+        // Input errors fail the same way on every attempt.
+        let retryable = !result.succeeded()
+            && result.exit_code != FeffErrorCategory::InputValidationError.exit_code();
+        if !retryable || attempt > args.retries {
+            return result;
+        }
+    }
+}
+
+fn run_attempt(
+    executable: &Path,
+    directory: &Path,
+    log_path: &Path,
+    attempt: u32,
+    timeout: Option<u64>,
+    started: Instant,
+) -> BatchJobResult {
+    let elapsed = || started.elapsed().as_secs_f64();
+    let log = match open_log(log_path, attempt) {
+        Ok(log) => log,
+        Err(error) => {
+            return BatchJobResult::exited(
+                directory,
+                FeffErrorCategory::IoSystemError.exit_code(),
+                attempt,
+                elapsed(),
+                error,
+            );
+        }
+    };
+    let spawned = log.try_clone().and_then(|stderr| {
+        Command::new(executable)
+            .arg("run")
+            .arg(directory)
+            .current_dir(directory)
+            .env("NO_COLOR", "1")
+            .stdin(Stdio::null())
+            .stdout(log)
+            .stderr(stderr)
+            .spawn()
+    });
+    let mut child = match spawned {
+        Ok(child) => child,
+        Err(source) => {
+            return BatchJobResult::exited(
+                directory,
+                FeffErrorCategory::IoSystemError.exit_code(),
+                attempt,
+                elapsed(),
+                format!("failed to start run: {}", source),
+            );
+        }
+    };
+
+    let attempt_started = Instant::now();
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break Ok(status),
+            Ok(None) => {}
+            Err(source) => break Err(source),
+        }
+        if let Some(limit) = timeout
+            && attempt_started.elapsed() >= Duration::from_secs(limit)
+        {
+            let _ = child.kill();
+            let _ = child.wait();
+            return BatchJobResult::timed_out(directory, attempt, elapsed(), limit);
+        }
+        thread::sleep(POLL_INTERVAL);
+    };
+
+    match status {
+        Ok(status) => {
+            // A run killed by a signal has no exit code; report it as an
+            // internal error like any other unmapped code.
+            let exit_code = status.code().unwrap_or(-1);
+            let message = if status.success() {
+                String::new()
+            } else {
+                last_error_line(log_path).unwrap_or_else(|| status.to_string())
+            };
+            BatchJobResult::exited(directory, exit_code, attempt, elapsed(), message)
+        }
+        Err(source) => BatchJobResult::exited(
+            directory,
+            FeffErrorCategory::IoSystemError.exit_code(),
+            attempt,
+            elapsed(),
+            format!("failed to wait for run: {}", source),
+        ),
+    }
+}
+
+/// Opens the run log, truncating it on the first attempt so it only holds
+/// this batch's attempts.
+fn open_log(path: &Path, attempt: u32) -> Result<File, String> {
+    let mut options = OpenOptions::new();
+    if attempt == 1 {
+        options.write(true).create(true).truncate(true);
+    } else {
+        options.append(true).create(true);
+    }
+    let mut log = options
+        .open(path)
+        .map_err(|source| format!("failed to open run log '{}': {}", path.display(), source))?;
+    writeln!(log, "# feff10-rs batch attempt {}", attempt)
+        .map_err(|source| format!("failed to write run log '{}': {}", path.display(), source))?;
+    Ok(log)
+}
+
+fn last_error_line(log_path: &Path) -> Option<String> {
+    let log = fs::read_to_string(log_path).ok()?;
+    log.lines()
+        .rev()
+        .find_map(|line| line.find("ERROR: [").map(|start| line[start..].to_string()))
+}
@@ -1,15 +1,35 @@
 use super::CliError;
-use super::dispatch::{ModuleCommandSpec, module_command_for_module};
+use super::dispatch::{ModuleCommandSpec, module_command_for_module, module_command_spec};
 use super::helpers::*;
+use super::progress::ConsoleProgress;
 use anyhow::Context;
-use feff_core::domain::FeffError;
+use feff_core::domain::{
+    ComputeArtifact, ComputeModule, ComputeResult, ExecutionControl, ExecutionMode, FeffError,
+};
+use feff_core::modules::cache::{
+    RunManifest, StageStatus, directory_label, run_deck_directory_with, run_directory_stages,
+};
+use feff_core::modules::distributed::{DistributedJob, supports_energy_chunks};
+use feff_core::modules::ensure_runtime_engines_available;
+use feff_core::modules::multi_absorber::{
+    MultiAbsorberConfig, MultiAbsorberPlan, SiteSelection, parse_absorber_element,
+};
+use feff_core::modules::pipeline::PipelinePlan;
+use feff_core::modules::rdinp::WORKFLOW_MODULE_ORDER;
 use feff_core::modules::regression::{
     RegressionRunnerConfig, render_human_summary, run_regression,
 };
-use feff_core::modules::{runtime_compute_engine_available, runtime_engine_unavailable_error};
+use feff_core::modules::sweep::{
+    SWEEP_SUMMARY_OUTPUT, SweepParameter, SweepPlan, SweepRunStatus,
+    describe as describe_sweep_point,
+};
+use feff_core::parser::lint::{has_errors, lint_input_source};
+use feff_core::parser::writer::{FormatOptions, format_input_source};
+use std::fs;
 use std::io::ErrorKind;
 use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::sync::Arc;
 
 const STRICT_SERIAL_CHAIN_MODULES: [&str; 18] = [
     "rdinp", "dmdw", "atomic", "pot", "ldos", "screen", "crpa", "opconsat", "xsph", "fms", "mkgtr",
@@ -181,18 +201,145 @@ pub(super) struct FeffArgs {
     /// If omitted, defaults to '<workspace>/feff10/bin/Seq' when available.
     #[arg(long, value_name = "DIR")]
     strict_bin_dir: Option<PathBuf>,
+
+    /// Average spectra over absorber sites of this element (symbol or Z).
+    ///
+    /// Writes one re-centered cluster per site to 'site_NNN/', runs the Rust
+    /// runtime chain in each, and averages xmu.dat/chi.dat with site multiplicities.
+    #[arg(long, value_name = "ELEMENT", conflicts_with = "strict")]
+    average_absorber: Option<String>,
+
+    /// Run one representative per group of equivalent sites instead of every site.
+    #[arg(long, requires = "average_absorber")]
+    inequivalent_sites: bool,
+
+    /// Cluster radius in Angstrom kept around each absorber site.
+    #[arg(long, value_name = "RADIUS", requires = "average_absorber")]
+    site_cluster_radius: Option<f64>,
+
+    /// Re-run MODULE in the Rust runtime chain even when its inputs are unchanged ('all' for every module).
+    #[arg(long, value_name = "MODULE")]
+    force: Vec<String>,
+
+    /// Split energy, path and q-point loops of the Rust runtime chain across N threads.
+    #[arg(long, value_name = "N")]
+    threads: Option<usize>,
+
+    /// Split the XSPH and FMS energy grids across N worker processes.
+    #[arg(long, value_name = "N")]
+    processes: Option<usize>,
+
+    /// Command prefix that starts each worker process, e.g. 'srun -N1 -n1' (needs a shared filesystem).
+    #[arg(long, value_name = "CMD", requires = "processes")]
+    launcher: Option<String>,
+
+    /// Print energy-point, SCF and path progress while each module runs.
+    #[arg(long)]
+    progress: bool,
 }
 
 #[derive(clap::Args, Debug, Clone)]
 pub(super) struct FeffmpiArgs {
-    /// Number of MPI processes
+    /// Number of worker threads for the Rust runtime chain (MPI process count in legacy FEFF)
     #[arg(value_name = "nprocs")]
     nprocs: usize,
 
+    /// Run nprocs worker processes over the XSPH and FMS energy grids instead of threads.
+    #[arg(long)]
+    distributed: bool,
+
     #[command(flatten)]
     feff: FeffArgs,
 }
 
+#[derive(clap::Args, Debug, Clone)]
+pub(super) struct WorkerArgs {
+    /// Job directory written by the coordinator.
+    #[arg(value_name = "JOB_DIR")]
+    job_dir: PathBuf,
+
+    /// Zero-based chunk of the energy grid to compute.
+    #[arg(long, value_name = "K")]
+    part: usize,
+}
+
+#[derive(clap::Args, Debug, Clone)]
+pub(super) struct RunArgs {
+    /// Directory containing feff.inp; outputs are written next to it.
+    #[arg(value_name = "DIR", default_value = ".")]
+    directory: PathBuf,
+
+    /// Print why each module runs or is skipped instead of running them.
+    #[arg(long)]
+    explain: bool,
+
+    /// Re-run MODULE even when its inputs are unchanged ('all' for every module).
+    #[arg(long, value_name = "MODULE")]
+    force: Vec<String>,
+
+    /// Split energy, path and q-point loops across N threads.
+    #[arg(long, value_name = "N")]
+    threads: Option<usize>,
+
+    /// Split the XSPH and FMS energy grids across N worker processes.
+    #[arg(long, value_name = "N")]
+    processes: Option<usize>,
+
+    /// Command prefix that starts each worker process, e.g. 'srun -N1 -n1' (needs a shared filesystem).
+    #[arg(long, value_name = "CMD", requires = "processes")]
+    launcher: Option<String>,
+
+    /// Print energy-point, SCF and path progress while each module runs.
+    #[arg(long)]
+    progress: bool,
+}
+
+#[derive(clap::Args, Debug, Clone)]
+pub(super) struct StatusArgs {
+    /// Directory containing feff.inp and a previous run.
+    #[arg(value_name = "DIR", default_value = ".")]
+    directory: PathBuf,
+}
+
+#[derive(clap::Args, Debug, Clone)]
+pub(super) struct FmtArgs {
+    /// Input decks to format in place.
+    #[arg(value_name = "FILE", default_value = "feff.inp")]
+    files: Vec<PathBuf>,
+
+    /// Report decks that are not formatted instead of rewriting them.
+    #[arg(long)]
+    check: bool,
+
+    /// Keep ATOMS rows in their written order.
+    #[arg(long)]
+    keep_atom_order: bool,
+}
+
+#[derive(clap::Args, Debug, Clone)]
+pub(super) struct SweepArgs {
+    /// Swept variables as NAME=v1,v2,... or NAME=start:stop:step.
+    ///
+    /// Each NAME must be declared in feff.inp with '@set NAME <default>'.
+    #[arg(value_name = "NAME=VALUES", required = true)]
+    parameters: Vec<String>,
+
+    /// Directory that receives one run_NNN/ per grid point and sweep.dat.
+    #[arg(long, value_name = "DIR", default_value = "sweep")]
+    output: PathBuf,
+}
+
+#[derive(clap::Args, Debug, Clone)]
+pub(super) struct CheckArgs {
+    /// Input decks to lint.
+    #[arg(value_name = "FILE", default_value = "feff.inp")]
+    files: Vec<PathBuf>,
+
+    /// Exit with status 1 on warnings as well as errors.
+    #[arg(long)]
+    deny_warnings: bool,
+}
+
 impl RegressionArgs {
     fn into_config(self) -> RegressionRunnerConfig {
         RegressionRunnerConfig {
@@ -305,6 +452,26 @@ pub(super) fn run_oracle_command(args: OracleArgs) -> Result<i32, CliError> {
 }
 
 pub(super) fn run_feff_command(args: FeffArgs) -> Result<i32, CliError> {
+    if let Some(element) = args.average_absorber.as_deref() {
+        let config = MultiAbsorberConfig {
+            selection: if args.inequivalent_sites {
+                SiteSelection::Inequivalent
+            } else {
+                SiteSelection::All
+            },
+            cluster_radius: args.site_cluster_radius,
+            ..MultiAbsorberConfig::new(parse_absorber_element(element).map_err(CliError::Compute)?)
+        };
+        let options = ChainOptions::from_args(
+            &args.force,
+            args.threads,
+            args.processes,
+            args.launcher.as_deref(),
+            args.progress,
+        )?;
+        return run_configurational_average(config, &options);
+    }
+
     if !args.runtime {
         let working_dir = current_working_dir().map_err(CliError::Compute)?;
         let strict_bin_dir = resolve_strict_bin_dir(&working_dir, args.strict_bin_dir.as_deref());
@@ -328,9 +495,205 @@ pub(super) fn run_feff_command(args: FeffArgs) -> Result<i32, CliError> {
         }
     }
 
-    let context = load_cli_context()?;
-    let fixture = select_serial_fixture(&context).map_err(CliError::Compute)?;
-    let modules = modules_for_serial_fixture(&fixture);
+    let options = ChainOptions::from_args(
+        &args.force,
+        args.threads,
+        args.processes,
+        args.launcher.as_deref(),
+        args.progress,
+    )?;
+    let working_dir = current_working_dir().map_err(CliError::Compute)?;
+    let Some(context) = load_cli_context_if_available(&working_dir)? else {
+        return run_deck_workflow(&working_dir, &options);
+    };
+    let (fixture, modules) = resolve_runtime_serial_chain(&context)?;
+    run_runtime_serial_chain(&context.working_dir, &fixture.id, &modules, &options)?;
+    match options.worker_summary() {
+        Some(workers) => println!(
+            "Completed workflow for fixture '{}' ({}).",
+            fixture.id, workers
+        ),
+        None => println!("Completed serial workflow for fixture '{}'.", fixture.id),
+    }
+    Ok(0)
+}
+
+pub(super) fn run_run_command(args: RunArgs) -> Result<i32, CliError> {
+    let working_dir = current_working_dir().map_err(CliError::Compute)?;
+    let directory = resolve_cli_path(&working_dir, &args.directory);
+    if args.explain {
+        print!("{}", plan_deck_workflow(&directory)?.explain());
+        return Ok(0);
+    }
+    run_deck_workflow(
+        &directory,
+        &ChainOptions::from_args(
+            &args.force,
+            args.threads,
+            args.processes,
+            args.launcher.as_deref(),
+            args.progress,
+        )?,
+    )
+}
+
+pub(super) fn run_status_command(args: StatusArgs) -> Result<i32, CliError> {
+    let working_dir = current_working_dir().map_err(CliError::Compute)?;
+    let directory = resolve_cli_path(&working_dir, &args.directory);
+    let plan = plan_deck_workflow(&directory)?;
+    let manifest = RunManifest::load(&directory).map_err(CliError::Compute)?;
+    for (module, status) in manifest.plan_status(&directory, &plan, &directory_label(&directory)) {
+        match status {
+            StageStatus::Fresh => println!("{:<13} fresh", module.as_str()),
+            StageStatus::Stale(reason) => println!("{:<13} stale: {}", module.as_str(), reason),
+        }
+    }
+    Ok(0)
+}
+
+/// Plans the modules `feff.inp` in `directory` enables.
+pub(super) fn plan_deck_workflow(directory: &Path) -> Result<PipelinePlan, CliError> {
+    PipelinePlan::for_directory(directory).map_err(CliError::Compute)
+}
+
+/// Runs the modules `feff.inp` in `directory` enables, without consulting the
+/// fixture manifest. The directory name labels the run in module outputs.
+fn run_deck_workflow(directory: &Path, options: &ChainOptions) -> Result<i32, CliError> {
+    announce_chain_options(options);
+    let modules = run_deck_chain(directory, options)?;
+    match options.worker_summary() {
+        Some(workers) => println!(
+            "Completed workflow in '{}' ({} modules, {}).",
+            directory.display(),
+            modules.len(),
+            workers
+        ),
+        None => println!(
+            "Completed serial workflow in '{}' ({} modules).",
+            directory.display(),
+            modules.len()
+        ),
+    }
+    Ok(0)
+}
+
+/// Plans and runs the deck in `directory`, labelled by the directory name.
+fn run_deck_chain(
+    directory: &Path,
+    options: &ChainOptions,
+) -> Result<Vec<ComputeModule>, CliError> {
+    run_deck_directory_with(
+        directory,
+        &options.force,
+        |module, label| run_chain_stage(directory, module, label, options),
+        report_skipped_stage,
+    )
+    .map_err(CliError::Compute)
+}
+
+pub(super) fn run_sweep_command(args: SweepArgs) -> Result<i32, CliError> {
+    let parameters = args
+        .parameters
+        .iter()
+        .map(|spec| SweepParameter::parse(spec))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(CliError::Compute)?;
+    let working_dir = current_working_dir().map_err(CliError::Compute)?;
+    let input_path = working_dir.join("feff.inp");
+    let source = fs::read_to_string(&input_path).map_err(|source| {
+        CliError::Compute(FeffError::io_system(
+            "IO.SWEEP_READ",
+            format!(
+                "failed to read sweep deck '{}': {}",
+                input_path.display(),
+                source
+            ),
+        ))
+    })?;
+    let plan = SweepPlan::from_feff_source(&source, parameters).map_err(CliError::Compute)?;
+    let root = resolve_cli_path(&working_dir, &args.output);
+    let run_dirs = plan.write_run_decks(&root).map_err(CliError::Compute)?;
+
+    // Each point is planned from its own deck, since a swept card can enable a
+    // module. A failed point is recorded in the summary instead of aborting.
+    let mut statuses = Vec::with_capacity(run_dirs.len());
+    for (point, run_dir) in plan.points().iter().zip(&run_dirs) {
+        println!(
+            "Running {} ({})...",
+            point.directory,
+            describe_sweep_point(point)
+        );
+        let status = match run_deck_chain(run_dir, &ChainOptions::default()) {
+            Ok(_) => SweepRunStatus::Completed,
+            Err(error) => {
+                let error = error.as_feff_error();
+                tracing::warn!(
+                    "WARNING: [RUN.SWEEP_POINT_FAILED] {} failed: {}",
+                    point.directory,
+                    error.diagnostic_line()
+                );
+                SweepRunStatus::Failed(error.message().to_string())
+            }
+        };
+        statuses.push(status);
+    }
+
+    plan.write_summary(&root, &statuses)
+        .map_err(CliError::Compute)?;
+    let failed = statuses
+        .iter()
+        .filter(|status| matches!(status, SweepRunStatus::Failed(_)))
+        .count();
+    println!(
+        "Completed parameter sweep: {} of {} point(s) succeeded; summary in {}.",
+        statuses.len() - failed,
+        statuses.len(),
+        root.join(SWEEP_SUMMARY_OUTPUT).display()
+    );
+    Ok(if failed > 0 { 1 } else { 0 })
+}
+
+/// Runs each site deck as its own directory workflow; every deck plan ends in
+/// DEBYE, whose `xmu.dat` and `chi.dat` are averaged.
+fn run_configurational_average(
+    config: MultiAbsorberConfig,
+    options: &ChainOptions,
+) -> Result<i32, CliError> {
+    let working_dir = current_working_dir().map_err(CliError::Compute)?;
+    let plan = MultiAbsorberPlan::from_feff_input(&working_dir.join("feff.inp"), config)
+        .map_err(CliError::Compute)?;
+    let site_dirs = plan
+        .write_site_decks(&working_dir)
+        .map_err(CliError::Compute)?;
+
+    announce_chain_options(options);
+    for (site, site_dir) in plan.sites().iter().zip(&site_dirs) {
+        println!(
+            "Running site {} (atom {}, multiplicity {})...",
+            site.directory,
+            site.atom_index + 1,
+            site.multiplicity
+        );
+        run_deck_chain(site_dir, options)?;
+    }
+
+    let artifacts = plan
+        .average_site_spectra(&working_dir)
+        .map_err(CliError::Compute)?;
+    println!(
+        "Completed configurational average over {} site(s) ({} artifacts).",
+        plan.sites().len(),
+        artifacts.len()
+    );
+    Ok(0)
+}
+
+fn resolve_runtime_serial_chain(
+    context: &CliContext,
+) -> Result<(CliManifestFixture, Vec<ComputeModule>), CliError> {
+    let fixture = select_serial_fixture(context).map_err(CliError::Compute)?;
+    let modules =
+        modules_for_serial_fixture(&fixture, &context.working_dir).map_err(CliError::Compute)?;
     if modules.is_empty() {
         return Err(CliError::Compute(FeffError::input_validation(
             "INPUT.CLI_FIXTURE_MODULES",
@@ -341,26 +704,178 @@ pub(super) fn run_feff_command(args: FeffArgs) -> Result<i32, CliError> {
         )));
     }
 
-    if let Some(module) = modules
-        .iter()
-        .copied()
-        .find(|module| !runtime_compute_engine_available(*module))
-    {
-        return Err(CliError::Compute(runtime_engine_unavailable_error(module)));
+    ensure_runtime_engines_available(&modules).map_err(CliError::Compute)?;
+
+    Ok((fixture, modules))
+}
+
+/// Options shared by every Rust runtime chain invocation.
+#[derive(Debug, Clone, Default)]
+struct ChainOptions {
+    /// Modules re-run even when the run manifest says they are fresh.
+    force: Vec<ComputeModule>,
+    execution_mode: ExecutionMode,
+    /// Worker processes for modules that support energy chunks; 0 runs them
+    /// in-process.
+    processes: usize,
+    launcher: Vec<String>,
+    control: ExecutionControl,
+}
+
+impl ChainOptions {
+    fn from_args(
+        force: &[String],
+        threads: Option<usize>,
+        processes: Option<usize>,
+        launcher: Option<&str>,
+        progress: bool,
+    ) -> Result<Self, CliError> {
+        if processes == Some(0) {
+            return Err(CliError::Usage(
+                "Invalid process count '0'; expected a positive integer.".to_string(),
+            ));
+        }
+        Ok(Self {
+            force: parse_forced_modules(force)?,
+            execution_mode: parse_execution_mode(threads)?,
+            processes: processes.filter(|count| *count > 1).unwrap_or(0),
+            launcher: launcher
+                .map(|command| command.split_whitespace().map(str::to_string).collect())
+                .unwrap_or_default(),
+            control: if progress {
+                ExecutionControl::default().with_observer(Arc::new(ConsoleProgress::default()))
+            } else {
+                ExecutionControl::default()
+            },
+        })
     }
 
-    for module in modules {
-        if let Some(spec) = module_command_for_module(module) {
-            println!("Running {}...", spec.module);
-            execute_module_with_fixture(&context.working_dir, spec, &fixture.id)
-                .map_err(CliError::Compute)?;
+    /// The workers a threaded or distributed chain ran on, for its completion
+    /// message; `None` for a serial chain.
+    fn worker_summary(&self) -> Option<String> {
+        if self.processes > 0 {
+            return Some(format!("{} worker processes", self.processes));
+        }
+        match self.execution_mode.workers() {
+            1 => None,
+            workers => Some(format!("{} worker threads", workers)),
         }
     }
-    println!("Completed serial workflow for fixture '{}'.", fixture.id);
+}
+
+/// Runs `modules` in order, skipping those whose inputs and outputs match the
+/// run manifest unless they are in `options.force`.
+fn run_runtime_serial_chain(
+    working_dir: &Path,
+    fixture_id: &str,
+    modules: &[ComputeModule],
+    options: &ChainOptions,
+) -> Result<(), CliError> {
+    announce_chain_options(options);
+    run_directory_stages(
+        working_dir,
+        fixture_id,
+        modules,
+        &options.force,
+        |module, label| run_chain_stage(working_dir, module, label, options),
+        report_skipped_stage,
+    )
+    .map_err(CliError::Compute)
+}
+
+fn announce_chain_options(options: &ChainOptions) {
+    if let ExecutionMode::Threaded { workers } = options.execution_mode {
+        println!("Using {} worker threads.", workers);
+    }
+    if options.processes > 1 {
+        println!(
+            "Splitting XSPH and FMS energy grids across {} worker processes.",
+            options.processes
+        );
+    }
+}
+
+/// Executes one chain stage in-process or across worker processes.
+fn run_chain_stage(
+    working_dir: &Path,
+    module: ComputeModule,
+    label: &str,
+    options: &ChainOptions,
+) -> ComputeResult<Vec<ComputeArtifact>> {
+    let spec = module_command_for_module(module).ok_or_else(|| {
+        FeffError::internal(
+            "SYS.CLI_MODULE_COMMAND",
+            format!("module '{}' has no CLI command", module),
+        )
+    })?;
+    println!("Running {}...", spec.module);
+    if options.processes > 1 && supports_energy_chunks(module) {
+        run_distributed_module(
+            working_dir,
+            spec,
+            label,
+            options.processes,
+            &options.launcher,
+        )
+    } else {
+        execute_module_with_fixture(
+            working_dir,
+            spec,
+            label,
+            options.execution_mode,
+            &options.control,
+        )
+    }
+}
+
+fn report_skipped_stage(module: ComputeModule) {
+    println!("Skipping {} (inputs unchanged).", module);
+}
+
+pub(super) fn run_worker_command(args: WorkerArgs) -> Result<i32, CliError> {
+    let working_dir = current_working_dir().map_err(CliError::Compute)?;
+    let job_dir = resolve_cli_path(&working_dir, &args.job_dir);
+    let job = DistributedJob::load(&job_dir).map_err(CliError::Compute)?;
+    job.run_worker(&job_dir, args.part)
+        .map_err(CliError::Compute)?;
+    println!(
+        "Wrote {} energy chunk {}/{}.",
+        job.module,
+        args.part + 1,
+        job.parts
+    );
     Ok(0)
 }
 
-pub(super) fn run_feffmpi_command(args: FeffmpiArgs) -> Result<i32, CliError> {
+fn parse_forced_modules(names: &[String]) -> Result<Vec<ComputeModule>, CliError> {
+    let mut modules = Vec::new();
+    for name in names {
+        if name.eq_ignore_ascii_case("all") {
+            return Ok(WORKFLOW_MODULE_ORDER.to_vec());
+        }
+        let module = module_command_spec(&name.to_ascii_lowercase())
+            .map(|spec| spec.module)
+            .or_else(|| name.parse().ok())
+            .ok_or_else(|| CliError::Usage(format!("Unknown module '{}' for --force.", name)))?;
+        modules.push(module);
+    }
+    Ok(modules)
+}
+
+fn parse_execution_mode(threads: Option<usize>) -> Result<ExecutionMode, CliError> {
+    match threads {
+        None | Some(1) => Ok(ExecutionMode::Serial),
+        Some(0) => Err(CliError::Usage(
+            "Invalid thread count '0'; expected a positive integer.".to_string(),
+        )),
+        Some(workers) => Ok(ExecutionMode::Threaded { workers }),
+    }
+}
+
+/// Runs the `feff` workflow with `nprocs` worker threads, or with `nprocs`
+/// worker processes under `--distributed`, standing in for MPI ranks. Outputs
+/// are identical to a serial run.
+pub(super) fn run_feffmpi_command(mut args: FeffmpiArgs) -> Result<i32, CliError> {
     let process_count = args.nprocs;
     if process_count == 0 {
         return Err(CliError::Usage(
@@ -368,17 +883,77 @@ pub(super) fn run_feffmpi_command(args: FeffmpiArgs) -> Result<i32, CliError> {
         ));
     }
 
-    if process_count > 1 {
-        tracing::warn!(
-            nprocs = process_count,
-            "WARNING: [RUN.MPI_DEFERRED] MPI parity is deferred for Rust v1; executing serial compatibility chain instead (requested nprocs={}).",
-            process_count
-        );
+    let (flag, requested) = if args.distributed {
+        ("processes", &mut args.feff.processes)
+    } else {
+        ("threads", &mut args.feff.threads)
+    };
+    if requested.is_some_and(|count| count != process_count) {
+        return Err(CliError::Usage(format!(
+            "--{} conflicts with nprocs={}; pass one or the other.",
+            flag, process_count
+        )));
     }
+    *requested = Some(process_count);
 
     run_feff_command(args.feff)
 }
 
+pub(super) fn run_fmt_command(args: FmtArgs) -> Result<i32, CliError> {
+    let options = FormatOptions {
+        sort_atoms: !args.keep_atom_order,
+        ..FormatOptions::CANONICAL
+    };
+    let mut unformatted = 0;
+    for path in &args.files {
+        let source = fs::read_to_string(path).map_err(|source| {
+            CliError::Compute(FeffError::io_system(
+                "IO.FMT_READ",
+                format!("failed to read input deck '{}': {}", path.display(), source),
+            ))
+        })?;
+        let formatted = format_input_source(&source, options).map_err(CliError::Compute)?;
+        if formatted == source {
+            continue;
+        }
+        if args.check {
+            println!("Would reformat {}", path.display());
+            unformatted += 1;
+            continue;
+        }
+        fs::write(path, formatted).map_err(|source| {
+            CliError::Compute(FeffError::io_system(
+                "IO.FMT_WRITE",
+                format!(
+                    "failed to write input deck '{}': {}",
+                    path.display(),
+                    source
+                ),
+            ))
+        })?;
+        println!("Formatted {}", path.display());
+    }
+    Ok(if unformatted > 0 { 1 } else { 0 })
+}
+
+pub(super) fn run_check_command(args: CheckArgs) -> Result<i32, CliError> {
+    let mut failed = false;
+    for path in &args.files {
+        let source = fs::read_to_string(path).map_err(|source| {
+            CliError::Compute(FeffError::io_system(
+                "IO.CHECK_READ",
+                format!("failed to read input deck '{}': {}", path.display(), source),
+            ))
+        })?;
+        let diagnostics = lint_input_source(&source);
+        for diagnostic in &diagnostics {
+            println!("{}:{}", path.display(), diagnostic);
+        }
+        failed |= has_errors(&diagnostics) || (args.deny_warnings && !diagnostics.is_empty());
+    }
+    Ok(if failed { 1 } else { 0 })
+}
+
 fn resolve_strict_bin_dir(working_dir: &Path, explicit: Option<&Path>) -> Option<PathBuf> {
     if let Some(path) = explicit {
         return Some(resolve_cli_path(working_dir, path));
@@ -451,25 +1026,29 @@ fn run_strict_serial_chain(
 }
 
 pub(super) fn run_module_command(spec: ModuleCommandSpec) -> Result<i32, CliError> {
-    if !runtime_compute_engine_available(spec.module) {
-        return Err(CliError::Compute(runtime_engine_unavailable_error(
-            spec.module,
-        )));
-    }
+    ensure_runtime_engines_available(&[spec.module]).map_err(CliError::Compute)?;
 
     let working_dir = current_working_dir().map_err(CliError::Compute)?;
-    let fixture_id = if let Some(context) = load_cli_context_if_available(&working_dir)? {
+    // Outside the workspace the run directory labels the module outputs, as
+    // it does for `run`.
+    let label = if let Some(context) = load_cli_context_if_available(&working_dir)? {
         select_fixture_for_module(&context, spec, false).map_err(CliError::Compute)?
     } else {
-        default_fixture_for_module(spec.module).to_string()
+        directory_label(&working_dir)
     };
     println!("Running {}...", spec.module);
-    let artifacts =
-        execute_module_with_fixture(&working_dir, spec, &fixture_id).map_err(CliError::Compute)?;
+    let artifacts = execute_module_with_fixture(
+        &working_dir,
+        spec,
+        &label,
+        ExecutionMode::Serial,
+        &ExecutionControl::default(),
+    )
+    .map_err(CliError::Compute)?;
     println!(
-        "{} completed for fixture '{}' ({} artifacts).",
+        "{} completed as '{}' ({} artifacts).",
         spec.module,
-        fixture_id,
+        label,
         artifacts.len()
     );
     Ok(0)
@@ -91,25 +91,6 @@ pub(super) const MODULE_COMMANDS: [ModuleCommandSpec; 16] = [
     },
 ];
 
-pub(super) const SERIAL_CHAIN_ORDER: [ComputeModule; 16] = [
-    ComputeModule::Rdinp,
-    ComputeModule::Pot,
-    ComputeModule::Screen,
-    ComputeModule::SelfEnergy,
-    ComputeModule::Eels,
-    ComputeModule::Xsph,
-    ComputeModule::Band,
-    ComputeModule::Ldos,
-    ComputeModule::Rixs,
-    ComputeModule::Crpa,
-    ComputeModule::Path,
-    ComputeModule::Debye,
-    ComputeModule::Dmdw,
-    ComputeModule::Fms,
-    ComputeModule::Compton,
-    ComputeModule::FullSpectrum,
-];
-
 pub(super) fn module_command_spec(command: &str) -> Option<ModuleCommandSpec> {
     MODULE_COMMANDS
         .iter()
@@ -147,25 +128,3 @@ pub(super) fn command_alias_from_program_name(program_name: &str) -> Option<&'st
 
     module_command_spec(normalized).map(|spec| spec.command)
 }
-
-pub(super) fn parse_compute_module(token: &str) -> Option<ComputeModule> {
-    match token.to_ascii_uppercase().as_str() {
-        "RDINP" => Some(ComputeModule::Rdinp),
-        "POT" => Some(ComputeModule::Pot),
-        "PATH" => Some(ComputeModule::Path),
-        "FMS" => Some(ComputeModule::Fms),
-        "XSPH" => Some(ComputeModule::Xsph),
-        "BAND" => Some(ComputeModule::Band),
-        "LDOS" => Some(ComputeModule::Ldos),
-        "RIXS" => Some(ComputeModule::Rixs),
-        "CRPA" => Some(ComputeModule::Crpa),
-        "COMPTON" => Some(ComputeModule::Compton),
-        "DEBYE" => Some(ComputeModule::Debye),
-        "DMDW" => Some(ComputeModule::Dmdw),
-        "SCREEN" => Some(ComputeModule::Screen),
-        "SELF" => Some(ComputeModule::SelfEnergy),
-        "EELS" => Some(ComputeModule::Eels),
-        "FULLSPECTRUM" => Some(ComputeModule::FullSpectrum),
-        _ => None,
-    }
-}
@@ -1,13 +1,18 @@
 use super::CliError;
 use super::dispatch::ModuleCommandSpec;
 use anyhow::Context;
-use feff_core::domain::{ComputeArtifact, ComputeModule, ComputeRequest, ComputeResult, FeffError};
+use feff_core::domain::{
+    ComputeArtifact, ComputeModule, ComputeRequest, ComputeResult, ExecutionControl, ExecutionMode,
+    FeffError,
+};
+use feff_core::modules::distributed::{DISTRIBUTED_JOB_ROOT, DistributedJob};
 use feff_core::modules::execute_runtime_module;
+use feff_core::modules::pipeline::{PipelinePlan, staged_artifacts};
 use feff_core::modules::regression::RegressionRunnerConfig;
 use serde::Deserialize;
 use std::fs;
 use std::path::{Path, PathBuf};
-use std::process::Command;
+use std::process::{Command, Stdio};
 use std::time::{SystemTime, UNIX_EPOCH};
 
 pub(super) const MANIFEST_RELATIVE_PATH: &str = "tasks/golden-fixture-manifest.json";
@@ -60,26 +65,6 @@ pub(super) enum OracleCaptureMode {
     BinDir(PathBuf),
 }
 
-pub(super) fn load_cli_context() -> Result<CliContext, CliError> {
-    let working_dir = current_working_dir().map_err(CliError::Compute)?;
-    let workspace_root = find_workspace_root(&working_dir).ok_or_else(|| {
-        CliError::Compute(FeffError::input_validation(
-            "INPUT.CLI_WORKSPACE",
-            format!(
-                "failed to locate workspace root from '{}'; expected to find '{}'",
-                working_dir.display(),
-                MANIFEST_RELATIVE_PATH
-            ),
-        ))
-    })?;
-    let manifest = load_cli_manifest(&workspace_root)?;
-    Ok(CliContext {
-        working_dir,
-        workspace_root,
-        manifest,
-    })
-}
-
 pub(super) fn load_cli_context_if_available(
     working_dir: &Path,
 ) -> Result<Option<CliContext>, CliError> {
@@ -103,27 +88,6 @@ pub(super) fn current_working_dir() -> ComputeResult<PathBuf> {
     })
 }
 
-pub(super) fn default_fixture_for_module(module: ComputeModule) -> &'static str {
-    match module {
-        ComputeModule::Rdinp => "FX-RDINP-001",
-        ComputeModule::Pot => "FX-POT-001",
-        ComputeModule::Xsph => "FX-XSPH-001",
-        ComputeModule::Path => "FX-PATH-001",
-        ComputeModule::Fms => "FX-FMS-001",
-        ComputeModule::Band => "FX-BAND-001",
-        ComputeModule::Ldos => "FX-LDOS-001",
-        ComputeModule::Rixs => "FX-RIXS-001",
-        ComputeModule::Crpa => "FX-CRPA-001",
-        ComputeModule::Compton => "FX-COMPTON-001",
-        ComputeModule::Debye => "FX-DEBYE-001",
-        ComputeModule::Dmdw => "FX-DMDW-001",
-        ComputeModule::Screen => "FX-SCREEN-001",
-        ComputeModule::SelfEnergy => "FX-SELF-001",
-        ComputeModule::Eels => "FX-EELS-001",
-        ComputeModule::FullSpectrum => "FX-FULLSPECTRUM-001",
-    }
-}
-
 pub(super) fn find_workspace_root(start: &Path) -> Option<PathBuf> {
     for candidate in start.ancestors() {
         let manifest = candidate.join(MANIFEST_RELATIVE_PATH);
@@ -164,20 +128,19 @@ pub(super) fn select_serial_fixture(context: &CliContext) -> ComputeResult<CliMa
     Ok(candidates[0].clone())
 }
 
-pub(super) fn modules_for_serial_fixture(fixture: &CliManifestFixture) -> Vec<ComputeModule> {
-    use super::dispatch::{SERIAL_CHAIN_ORDER, parse_compute_module};
-
+pub(super) fn modules_for_serial_fixture(
+    fixture: &CliManifestFixture,
+    working_dir: &Path,
+) -> ComputeResult<Vec<ComputeModule>> {
     let covered = fixture
         .modules_covered
         .iter()
-        .filter_map(|module| parse_compute_module(module))
+        .filter_map(|module| module.parse().ok())
         .collect::<Vec<_>>();
-
-    SERIAL_CHAIN_ORDER
-        .iter()
-        .copied()
-        .filter(|module| covered.contains(module))
-        .collect()
+    if covered.is_empty() {
+        return Ok(covered);
+    }
+    Ok(PipelinePlan::for_modules(&covered, &staged_artifacts(working_dir))?.modules())
 }
 
 pub(super) fn select_fixture_for_module(
@@ -370,16 +333,157 @@ pub(super) fn execute_module_with_fixture(
     working_dir: &Path,
     spec: ModuleCommandSpec,
     fixture_id: &str,
+    execution_mode: ExecutionMode,
+    control: &ExecutionControl,
 ) -> ComputeResult<Vec<ComputeArtifact>> {
-    let request = ComputeRequest::new(
+    let mut request = ComputeRequest::new(
         fixture_id.to_string(),
         spec.module,
         working_dir.join(spec.input_artifact),
         working_dir,
     );
+    request.execution_mode = execution_mode;
+    request.control = control.clone();
     execute_runtime_module(spec.module, &request)
 }
 
+/// Runs `spec.module` as a distributed job: `processes` workers each compute
+/// one energy chunk in a job directory under `working_dir`, then the chunks
+/// are merged into the module outputs. `launcher` prefixes every worker
+/// command so workers can be placed on other hosts sharing the directory.
+pub(super) fn run_distributed_module(
+    working_dir: &Path,
+    spec: ModuleCommandSpec,
+    fixture_id: &str,
+    processes: usize,
+    launcher: &[String],
+) -> ComputeResult<Vec<ComputeArtifact>> {
+    let request = ComputeRequest::new(
+        fixture_id.to_string(),
+        spec.module,
+        working_dir.join(spec.input_artifact),
+        working_dir,
+    );
+    let job = DistributedJob::new(&request, processes)?;
+    let job_dir = working_dir
+        .join(DISTRIBUTED_JOB_ROOT)
+        .join(spec.module.as_str().to_ascii_lowercase());
+    if job_dir.exists() {
+        fs::remove_dir_all(&job_dir).map_err(|source| distributed_io_error(&job_dir, source))?;
+    }
+    job.save(&job_dir)?;
+
+    let executable = std::env::current_exe().map_err(|source| {
+        FeffError::io_system(
+            "IO.DISTRIBUTED_WORKER",
+            format!("failed to locate the feff10-rs executable: {}", source),
+        )
+    })?;
+    // Each worker writes stderr to its own log in the job directory, so no
+    // worker can block on a full pipe while another one is being waited on.
+    let mut workers = Vec::with_capacity(processes);
+    for part in 0..processes {
+        let log_path = job_dir.join(format!("worker-{:04}.log", part));
+        let log = fs::File::create(&log_path).map_err(|source| {
+            FeffError::io_system(
+                "IO.DISTRIBUTED_WORKER",
+                format!(
+                    "failed to create worker log '{}': {}",
+                    log_path.display(),
+                    source
+                ),
+            )
+        })?;
+        let mut command = match launcher.split_first() {
+            Some((program, prefix)) => {
+                let mut command = Command::new(program);
+                command.args(prefix).arg(&executable);
+                command
+            }
+            None => Command::new(&executable),
+        };
+        let child = command
+            .arg("worker")
+            .arg(&job_dir)
+            .arg("--part")
+            .arg(part.to_string())
+            .current_dir(working_dir)
+            .stdout(Stdio::null())
+            .stderr(log)
+            .spawn()
+            .map_err(|source| {
+                FeffError::io_system(
+                    "IO.DISTRIBUTED_WORKER",
+                    format!(
+                        "failed to start {} worker {}: {}",
+                        spec.module, part, source
+                    ),
+                )
+            })?;
+        workers.push((part, child, log_path));
+    }
+
+    let mut failures = Vec::new();
+    for (part, mut child, log_path) in workers {
+        let status = child.wait().map_err(|source| {
+            FeffError::io_system(
+                "IO.DISTRIBUTED_WORKER",
+                format!(
+                    "failed to wait for {} worker {}: {}",
+                    spec.module, part, source
+                ),
+            )
+        })?;
+        if !status.success() {
+            let stderr = fs::read_to_string(&log_path).unwrap_or_default();
+            failures.push(format!(
+                "worker {} ({}, log '{}'): {}",
+                part,
+                status,
+                log_path.display(),
+                stderr.trim()
+            ));
+        }
+    }
+    if !failures.is_empty() {
+        return Err(FeffError::computation(
+            "RUN.DISTRIBUTED_WORKER",
+            format!("{} workers failed: {}", spec.module, failures.join("; ")),
+        ));
+    }
+    let missing = job.missing_parts(&job_dir);
+    if !missing.is_empty() {
+        return Err(FeffError::computation(
+            "RUN.DISTRIBUTED_WORKER",
+            format!(
+                "{} workers exited without writing energy chunks {:?} to '{}'",
+                spec.module,
+                missing,
+                job_dir.display()
+            ),
+        ));
+    }
+
+    let artifacts = job.merge(&job_dir)?;
+    fs::remove_dir_all(&job_dir).map_err(|source| distributed_io_error(&job_dir, source))?;
+    if let Some(root) = job_dir.parent() {
+        // Another module's job may still be using the root directory.
+        let _ = fs::remove_dir(root);
+    }
+    Ok(artifacts)
+}
+
+fn distributed_io_error(job_dir: &Path, source: std::io::Error) -> FeffError {
+    FeffError::io_system(
+        "IO.DISTRIBUTED_JOB",
+        format!(
+            "failed to clear distributed job directory '{}': {}",
+            job_dir.display(),
+            source
+        ),
+    )
+}
+
 pub(super) fn resolve_regression_paths(
     mut config: RegressionRunnerConfig,
     working_dir: &Path,
@@ -0,0 +1,232 @@
+//! Language Server Protocol over stdio for `feff.inp` files.
+//!
+//! Messages use the base protocol framing (`Content-Length` headers and a
+//! JSON-RPC body). Documents are synchronized in full on every change; all
+//! analysis comes from `feff_core::parser::language`.
+
+use super::CliError;
+use anyhow::Context;
+use feff_core::parser::language::{
+    card_doc, definition, document_diagnostics, hover, keyword_completions,
+};
+use feff_core::parser::lint::{Diagnostic, Severity, SourceSpan};
+use serde_json::{Value, json};
+use std::collections::BTreeMap;
+use std::io::{self, BufRead, Write};
+
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const TEXT_DOCUMENT_SYNC_FULL: i64 = 1;
+const COMPLETION_KIND_KEYWORD: i64 = 14;
+
+pub(super) fn run_lsp_command() -> Result<i32, CliError> {
+    let stdin = io::stdin();
+    let stdout = io::stdout();
+    serve(stdin.lock(), stdout.lock())
+        .context("language server transport failed")
+        .map_err(CliError::Internal)
+}
+
+/// Serves requests until `exit`; returns 0 when `shutdown` came first.
+fn serve(mut reader: impl BufRead, mut writer: impl Write) -> io::Result<i32> {
+    let mut server = Server::default();
+    while let Some(message) = read_message(&mut reader)? {
+        let method = message.get("method").and_then(Value::as_str).unwrap_or("");
+        let params = message.get("params").cloned().unwrap_or(Value::Null);
+        if method == "exit" {
+            return Ok(if server.shutdown { 0 } else { 1 });
+        }
+        let outgoing = match message.get("id") {
+            Some(id) => vec![server.request(id.clone(), method, &params)],
+            None => server.notification(method, &params),
+        };
+        for message in outgoing {
+            write_message(&mut writer, &message)?;
+        }
+    }
+    Ok(1)
+}
+
+#[derive(Default)]
+struct Server {
+    documents: BTreeMap<String, String>,
+    shutdown: bool,
+}
+
+impl Server {
+    fn request(&mut self, id: Value, method: &str, params: &Value) -> Value {
+        let result = match method {
+            "initialize" => Ok(json!({
+                "capabilities": {
+                    "textDocumentSync": TEXT_DOCUMENT_SYNC_FULL,
+                    "completionProvider": {},
+                    "hoverProvider": true,
+                    "definitionProvider": true,
+                },
+                "serverInfo": { "name": "feff10-rs", "version": env!("CARGO_PKG_VERSION") },
+            })),
+            "shutdown" => {
+                self.shutdown = true;
+                Ok(Value::Null)
+            }
+            "textDocument/completion" => self.at_position(params, |source, line, column| {
+                let items: Vec<Value> = keyword_completions(source, line, column)
+                    .into_iter()
+                    .map(|keyword| {
+                        let mut item = json!({ "label": keyword, "kind": COMPLETION_KIND_KEYWORD });
+                        if let Some(doc) = card_doc(keyword) {
+                            item["detail"] = json!(doc.summary);
+                        }
+                        item
+                    })
+                    .collect();
+                json!(items)
+            }),
+            "textDocument/hover" => self.at_position(params, |source, line, column| {
+                hover(source, line, column).map_or(Value::Null, |(text, span)| {
+                    json!({
+                        "contents": { "kind": "markdown", "value": text },
+                        "range": range(span),
+                    })
+                })
+            }),
+            "textDocument/definition" => {
+                let uri = document_uri(params).unwrap_or_default().to_string();
+                self.at_position(params, |source, line, column| {
+                    definition(source, line, column).map_or(
+                        Value::Null,
+                        |span| json!({ "uri": uri, "range": range(span) }),
+                    )
+                })
+            }
+            _ => Err((METHOD_NOT_FOUND, format!("unsupported method '{}'", method))),
+        };
+        match result {
+            Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+            Err((code, message)) => json!({
+                "jsonrpc": "2.0",
+                "id": id,
+                "error": { "code": code, "message": message },
+            }),
+        }
+    }
+
+    fn notification(&mut self, method: &str, params: &Value) -> Vec<Value> {
+        let Some(uri) = document_uri(params).map(str::to_string) else {
+            return Vec::new();
+        };
+        match method {
+            "textDocument/didOpen" => {
+                let text = params["textDocument"]["text"].as_str().unwrap_or("");
+                self.documents.insert(uri.clone(), text.to_string());
+            }
+            "textDocument/didChange" => {
+                let Some(text) = params["contentChanges"]
+                    .as_array()
+                    .and_then(|changes| changes.last())
+                    .and_then(|change| change["text"].as_str())
+                else {
+                    return Vec::new();
+                };
+                self.documents.insert(uri.clone(), text.to_string());
+            }
+            "textDocument/didClose" => {
+                self.documents.remove(&uri);
+                return vec![publish_diagnostics(&uri, &[])];
+            }
+            _ => return Vec::new(),
+        }
+        let diagnostics = document_diagnostics(&self.documents[&uri]);
+        vec![publish_diagnostics(&uri, &diagnostics)]
+    }
+
+    /// Runs `handler` with the open document and a 1-based cursor position.
+    fn at_position(
+        &self,
+        params: &Value,
+        handler: impl FnOnce(&str, usize, usize) -> Value,
+    ) -> Result<Value, (i64, String)> {
+        let uri = document_uri(params)
+            .ok_or_else(|| (INVALID_PARAMS, "missing textDocument.uri".to_string()))?;
+        let source = self
+            .documents
+            .get(uri)
+            .ok_or_else(|| (INVALID_PARAMS, format!("document '{}' is not open", uri)))?;
+        let position = &params["position"];
+        let (Some(line), Some(character)) =
+            (position["line"].as_u64(), position["character"].as_u64())
+        else {
+            return Err((INVALID_PARAMS, "missing position".to_string()));
+        };
+        Ok(handler(source, line as usize + 1, character as usize + 1))
+    }
+}
+
+fn document_uri(params: &Value) -> Option<&str> {
+    params["textDocument"]["uri"].as_str()
+}
+
+fn publish_diagnostics(uri: &str, diagnostics: &[Diagnostic]) -> Value {
+    let diagnostics: Vec<Value> = diagnostics
+        .iter()
+        .map(|diagnostic| {
+            json!({
+                "range": range(diagnostic.span),
+                "severity": match diagnostic.severity {
+                    Severity::Error => 1,
+                    Severity::Warning => 2,
+                    Severity::Info => 3,
+                },
+                "code": diagnostic.code,
+                "source": "feff10-rs",
+                "message": format!("{}\nfix: {}", diagnostic.message, diagnostic.fix),
+            })
+        })
+        .collect();
+    json!({
+        "jsonrpc": "2.0",
+        "method": "textDocument/publishDiagnostics",
+        "params": { "uri": uri, "diagnostics": diagnostics },
+    })
+}
+
+fn range(span: SourceSpan) -> Value {
+    let line = span.line.saturating_sub(1);
+    json!({
+        "start": { "line": line, "character": span.column.saturating_sub(1) },
+        "end": { "line": line, "character": span.end_column.saturating_sub(1) },
+    })
+}
+
+fn read_message(reader: &mut impl BufRead) -> io::Result<Option<Value>> {
+    let mut content_length = None;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 {
+            return Ok(None);
+        }
+        let header = header.trim_end();
+        if header.is_empty() {
+            if content_length.is_some() {
+                break;
+            }
+            continue;
+        }
+        if let Some((name, value)) = header.split_once(':')
+            && name.eq_ignore_ascii_case("Content-Length")
+        {
+            content_length = value.trim().parse::<usize>().ok();
+        }
+    }
+    let mut body = vec![0; content_length.unwrap_or(0)];
+    reader.read_exact(&mut body)?;
+    serde_json::from_slice(&body)
+        .map(Some)
+        .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+}
+
+fn write_message(writer: &mut impl Write, message: &Value) -> io::Result<()> {
+    let body = message.to_string();
+    write!(writer, "Content-Length: {}\r\n\r\n{}", body.len(), body)?;
+    writer.flush()
+}
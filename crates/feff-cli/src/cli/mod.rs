@@ -1,6 +1,9 @@
+mod batch;
 mod commands;
 mod dispatch;
 mod helpers;
+mod lsp;
+mod progress;
 
 use clap::Parser;
 use dispatch::{command_alias_from_program_name, module_command_spec};
@@ -79,8 +82,24 @@ enum CliCommand {
     Oracle(commands::OracleArgs),
     /// Run serial FEFF compatibility chain in current directory
     Feff(commands::FeffArgs),
-    /// Run MPI-compatible FEFF entrypoint (serial fallback in v1)
+    /// Run the modules enabled by feff.inp in a directory, without a fixture manifest
+    Run(commands::RunArgs),
+    /// Show which modules of a directory's run are fresh and which would re-run
+    Status(commands::StatusArgs),
+    /// Run MPI-compatible FEFF entrypoint on worker threads or worker processes
     Feffmpi(commands::FeffmpiArgs),
+    /// Compute one energy chunk of a distributed XSPH/FMS job (launched by a coordinator)
+    Worker(commands::WorkerArgs),
+    /// Run many independent run directories across a worker pool with retries and a summary
+    Batch(batch::BatchArgs),
+    /// Expand '@set' variables over a parameter grid and run the serial chain for each point
+    Sweep(commands::SweepArgs),
+    /// Rewrite input decks in canonical style (full keywords, aligned tables, atoms by distance)
+    Fmt(commands::FmtArgs),
+    /// Lint input decks and report every problem with its line and column
+    Check(commands::CheckArgs),
+    /// Serve the Language Server Protocol for feff.inp files over stdio
+    Lsp,
     /// Run RDINP module in current directory
     Rdinp,
     /// Run POT module in current directory
@@ -122,7 +141,15 @@ fn dispatch_parsed(command: CliCommand) -> Result<i32, CliError> {
         CliCommand::Regression(args) => commands::run_regression_command(args),
         CliCommand::Oracle(args) => commands::run_oracle_command(args),
         CliCommand::Feff(args) => commands::run_feff_command(args),
+        CliCommand::Run(args) => commands::run_run_command(args),
+        CliCommand::Status(args) => commands::run_status_command(args),
         CliCommand::Feffmpi(args) => commands::run_feffmpi_command(args),
+        CliCommand::Worker(args) => commands::run_worker_command(args),
+        CliCommand::Batch(args) => batch::run_batch_command(args),
+        CliCommand::Sweep(args) => commands::run_sweep_command(args),
+        CliCommand::Fmt(args) => commands::run_fmt_command(args),
+        CliCommand::Check(args) => commands::run_check_command(args),
+        CliCommand::Lsp => lsp::run_lsp_command(),
         CliCommand::Rdinp => dispatch_module("rdinp"),
         CliCommand::Pot => dispatch_module("pot"),
         CliCommand::Xsph => dispatch_module("xsph"),
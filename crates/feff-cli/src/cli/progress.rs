@@ -0,0 +1,51 @@
+use feff_core::domain::{ExecutionEvent, ExecutionObserver};
+use std::sync::Mutex;
+
+/// Energy-point progress is printed once per this many percent of the grid.
+const ENERGY_PROGRESS_STEP_PERCENT: usize = 10;
+
+/// Prints module progress for `--progress`. Energy points are counted as they
+/// finish, so threaded runs report the same milestones as serial ones.
+#[derive(Debug, Default)]
+pub(super) struct ConsoleProgress {
+    energy_points_done: Mutex<usize>,
+}
+
+impl ExecutionObserver for ConsoleProgress {
+    fn on_event(&self, event: &ExecutionEvent) {
+        match event {
+            ExecutionEvent::ModuleStarted { .. } => {
+                *self.energy_points_done.lock().expect("progress counter") = 0;
+            }
+            ExecutionEvent::EnergyPoint { module, total, .. } => {
+                let mut done = self.energy_points_done.lock().expect("progress counter");
+                *done += 1;
+                let percent = *done * 100 / (*total).max(1);
+                let previous = (*done - 1) * 100 / (*total).max(1);
+                if percent / ENERGY_PROGRESS_STEP_PERCENT > previous / ENERGY_PROGRESS_STEP_PERCENT
+                {
+                    println!(
+                        "  {} energy points {}/{} ({}%)",
+                        module.as_str(),
+                        done,
+                        total,
+                        percent
+                    );
+                }
+                // LDOS channels and DEBYE polarization spectra each sweep the grid.
+                if *done >= *total {
+                    *done = 0;
+                }
+            }
+            ExecutionEvent::ScfIteration {
+                iteration,
+                residual,
+            } => println!("  SCF iteration {}: residual {:.3e}", iteration, residual),
+            ExecutionEvent::PathCount { count } => println!("  {} scattering paths", count),
+            ExecutionEvent::ArtifactWritten { path, .. } => {
+                println!("  Wrote {}", path.display())
+            }
+            _ => {}
+        }
+    }
+}
@@ -43,7 +43,7 @@ fn feff_command_executes_available_serial_workflow_modules() {
 }
 
 #[test]
-fn feffmpi_command_runs_serial_workflow_after_warning() {
+fn feff_command_averages_spectra_over_inequivalent_absorber_sites() {
     let temp = fixture_tempdir();
     stage_baseline_artifact(
         "FX-WORKFLOW-XAS-001",
@@ -51,22 +51,331 @@ fn feffmpi_command_runs_serial_workflow_after_warning() {
         temp.path().join("feff.inp"),
     );
 
-    let output = run_cli_command(temp.path(), &["feffmpi", "4"]);
+    let output = run_cli_command(
+        temp.path(),
+        &[
+            "feff",
+            "--average-absorber",
+            "Cu",
+            "--inequivalent-sites",
+            "--site-cluster-radius",
+            "3.0",
+            "--threads",
+            "2",
+        ],
+    );
+
+    assert!(
+        output.status.success(),
+        "configurational average should succeed, stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(
+        String::from_utf8_lossy(&output.stdout).contains("Completed configurational average"),
+        "feff should print the configurational average summary"
+    );
+    assert!(
+        String::from_utf8_lossy(&output.stdout).contains("Using 2 worker threads."),
+        "site runs should honor --threads"
+    );
+    for artifact in [
+        "site_001/feff.inp",
+        "site_001/chi.dat",
+        "chi.dat",
+        "xmu.dat",
+    ] {
+        assert!(
+            temp.path().join(artifact).is_file(),
+            "configurational average should materialize {}",
+            artifact
+        );
+    }
+
+    let site_manifest =
+        fs::read_to_string(temp.path().join("site_001/feff10-run.json")).expect("run manifest");
+    assert!(
+        site_manifest.contains("\"label\": \"site_001\""),
+        "each site deck should run under its directory label"
+    );
+
+    let summary = fs::read_to_string(temp.path().join("sites.dat")).expect("sites.dat");
+    let multiplicity = summary
+        .lines()
+        .filter(|line| !line.starts_with('#'))
+        .map(|line| {
+            line.split_whitespace()
+                .nth(5)
+                .and_then(|token| token.parse::<usize>().ok())
+                .expect("site row should carry a multiplicity")
+        })
+        .sum::<usize>();
+    let deck = fs::read_to_string(temp.path().join("feff.inp")).expect("feff.inp");
+    let atoms = deck
+        .lines()
+        .skip_while(|line| !line.trim_start().starts_with("ATOMS"))
+        .skip(1)
+        .map_while(|line| {
+            let fields = line.split_whitespace().collect::<Vec<_>>();
+            let xyz = fields
+                .get(..3)?
+                .iter()
+                .map(|token| token.parse::<f64>().ok())
+                .collect::<Option<Vec<_>>>()?;
+            let radius = xyz.iter().map(|value| value * value).sum::<f64>().sqrt();
+            Some((
+                radius,
+                fields.get(4).is_some_and(|tag| tag.starts_with("Cu_")),
+            ))
+        })
+        .collect::<Vec<_>>();
+    let extent = atoms.iter().map(|(radius, _)| *radius).fold(0.0, f64::max);
+    let interior_absorbers = atoms
+        .iter()
+        .filter(|(radius, is_cu)| *is_cu && radius + 4.0 <= extent + 1.0e-6)
+        .count();
+    assert_eq!(
+        multiplicity, interior_absorbers,
+        "every Cu atom with its full 4 A environment in the cluster should be counted once"
+    );
+}
+
+#[test]
+fn feffmpi_command_runs_threaded_workflow_matching_serial_outputs() {
+    let temp = fixture_tempdir();
+    let threaded_dir = temp.path().join("threaded").join("run");
+    let serial_dir = temp.path().join("serial").join("run");
+    for dir in [&threaded_dir, &serial_dir] {
+        stage_baseline_artifact("FX-WORKFLOW-XAS-001", "feff.inp", dir.join("feff.inp"));
+    }
+
+    let output = run_cli_command(&threaded_dir, &["feffmpi", "4"]);
+    assert!(
+        output.status.success(),
+        "feffmpi should run the threaded workflow when engines are available, stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("Using 4 worker threads."),
+        "feffmpi should report its worker count, stdout: {}",
+        stdout
+    );
+    assert!(
+        !String::from_utf8_lossy(&output.stderr).contains("RUN.MPI_DEFERRED"),
+        "feffmpi should no longer fall back to the serial chain"
+    );
+    assert!(
+        stdout.contains("Completed workflow") && stdout.contains("(4 worker threads)"),
+        "feffmpi should print workflow completion summary"
+    );
 
+    let serial = run_cli_command(&serial_dir, &["feff"]);
+    assert!(
+        serial.status.success(),
+        "serial feff should succeed, stderr: {}",
+        String::from_utf8_lossy(&serial.stderr)
+    );
+    for artifact in ["pot.bin", "phase.bin", "xsect.dat", "gg.bin", "paths.dat"] {
+        let threaded = fs::read(threaded_dir.join(artifact))
+            .unwrap_or_else(|_| panic!("threaded run should write {}", artifact));
+        let serial = fs::read(serial_dir.join(artifact))
+            .unwrap_or_else(|_| panic!("serial run should write {}", artifact));
+        assert_eq!(
+            threaded, serial,
+            "{} should be bit-identical between feffmpi and serial feff",
+            artifact
+        );
+    }
+}
+
+#[test]
+fn run_command_progress_reports_module_events() {
+    let temp = fixture_tempdir();
+    let run_dir = temp.path().join("run");
+    stage_baseline_artifact("FX-WORKFLOW-XAS-001", "feff.inp", run_dir.join("feff.inp"));
+
+    let output = run_cli_command(&run_dir, &["run", "--progress", "--threads", "2"]);
     assert!(
         output.status.success(),
-        "feffmpi should run the serial compatibility workflow when engines are available, stderr: {}",
+        "run --progress should succeed, stderr: {}",
         String::from_utf8_lossy(&output.stderr)
     );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    for expected in [
+        "SCF iteration 1: residual",
+        "XSPH energy points",
+        "FMS energy points",
+        "(100%)",
+        "scattering paths",
+        "Wrote ",
+    ] {
+        assert!(
+            stdout.contains(expected),
+            "progress output should contain '{}', stdout: {}",
+            expected,
+            stdout
+        );
+    }
+
+    let quiet = run_cli_command(&run_dir, &["run", "--force", "all"]);
+    assert!(quiet.status.success());
+    assert!(
+        !String::from_utf8_lossy(&quiet.stdout).contains("energy points"),
+        "progress lines should only be printed with --progress"
+    );
+}
+
+#[test]
+fn feffmpi_distributed_workers_match_serial_outputs() {
+    let temp = fixture_tempdir();
+    let distributed_dir = temp.path().join("distributed").join("run");
+    let serial_dir = temp.path().join("serial").join("run");
+    for dir in [&distributed_dir, &serial_dir] {
+        stage_baseline_artifact("FX-WORKFLOW-XAS-001", "feff.inp", dir.join("feff.inp"));
+    }
+
+    let output = run_cli_command(
+        &distributed_dir,
+        &["feffmpi", "3", "--distributed", "--runtime"],
+    );
+    assert!(
+        output.status.success(),
+        "distributed feffmpi should succeed, stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(
+        String::from_utf8_lossy(&output.stdout).contains("across 3 worker processes"),
+        "feffmpi should report its worker processes"
+    );
+    assert!(
+        !distributed_dir.join("feff10-dist").exists(),
+        "job files should be removed after merging"
+    );
+
+    let serial = run_cli_command(&serial_dir, &["feff", "--runtime"]);
+    assert!(
+        serial.status.success(),
+        "serial feff should succeed, stderr: {}",
+        String::from_utf8_lossy(&serial.stderr)
+    );
+    for artifact in ["phase.bin", "xsect.dat", "gg.bin", "log3.dat", "paths.dat"] {
+        assert_eq!(
+            fs::read(distributed_dir.join(artifact)).expect("distributed artifact"),
+            fs::read(serial_dir.join(artifact)).expect("serial artifact"),
+            "{} should be bit-identical between distributed and serial runs",
+            artifact
+        );
+    }
+}
+
+#[cfg(unix)]
+#[test]
+fn distributed_worker_failures_report_their_stderr_log() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let temp = fixture_tempdir();
+    stage_baseline_artifact(
+        "FX-WORKFLOW-XAS-001",
+        "feff.inp",
+        temp.path().join("feff.inp"),
+    );
+    let launcher = temp.path().join("refuse-worker.sh");
+    fs::write(
+        &launcher,
+        "#!/bin/sh\necho 'worker launch refused' >&2\nexit 3\n",
+    )
+    .expect("launcher script should be written");
+    fs::set_permissions(&launcher, fs::Permissions::from_mode(0o755))
+        .expect("launcher script should be executable");
+
+    let output = run_cli_command(
+        temp.path(),
+        &[
+            "feff",
+            "--runtime",
+            "--processes",
+            "2",
+            "--launcher",
+            launcher.to_str().expect("utf-8 launcher path"),
+        ],
+    );
+
+    assert!(
+        !output.status.success(),
+        "failed workers should fail the run"
+    );
     let stderr = String::from_utf8_lossy(&output.stderr);
     assert!(
-        stderr.contains("WARNING: [RUN.MPI_DEFERRED]"),
-        "stderr should include deferred MPI warning, stderr: {}",
+        stderr.contains("worker launch refused") && stderr.contains("worker-0001.log"),
+        "the failure should quote each worker's stderr log, stderr: {}",
         stderr
     );
+}
+
+#[test]
+fn feffmpi_rejects_conflicting_thread_count() {
+    let temp = fixture_tempdir();
+    let output = run_cli_command(temp.path(), &["feffmpi", "4", "--threads", "2"]);
+    assert_eq!(output.status.code(), Some(2));
     assert!(
-        String::from_utf8_lossy(&output.stdout).contains("Completed serial workflow"),
-        "feffmpi should print serial workflow completion summary"
+        String::from_utf8_lossy(&output.stderr).contains("--threads conflicts with nprocs=4"),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+#[test]
+fn batch_command_runs_directories_and_resumes_completed_runs() {
+    let temp = fixture_tempdir();
+    for name in ["snap_01", "snap_02"] {
+        stage_baseline_artifact(
+            "FX-WORKFLOW-XAS-001",
+            "feff.inp",
+            temp.path().join("md").join(name).join("feff.inp"),
+        );
+    }
+    fs::create_dir_all(temp.path().join("md/broken")).expect("broken run dir");
+    fs::write(temp.path().join("md/broken/feff.inp"), "NOT A DECK\n").expect("broken deck");
+
+    let output = run_cli_command(temp.path(), &["batch", "md/*", "-j", "2", "--retries", "1"]);
+    assert_eq!(
+        output.status.code(),
+        Some(1),
+        "a failed run should fail the batch, stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let summary: serde_json::Value = serde_json::from_str(
+        &fs::read_to_string(temp.path().join("batch-summary.json")).expect("json summary"),
+    )
+    .expect("summary should parse");
+    assert_eq!(summary["total"], 3);
+    assert_eq!(summary["completed"], 2);
+    assert_eq!(summary["failed"], 1);
+    let broken = &summary["jobs"][0];
+    assert_eq!(broken["exit_code"], 2);
+    assert_eq!(broken["category"], "InputValidationError");
+    assert_eq!(broken["attempts"], 1, "input errors should not be retried");
+    assert!(
+        fs::read_to_string(temp.path().join("md/broken/feff10-batch.log"))
+            .expect("per-run log")
+            .contains("FATAL EXIT CODE: 2")
+    );
+    assert!(temp.path().join("md/snap_01/paths.dat").is_file());
+    let csv = fs::read_to_string(temp.path().join("batch-summary.csv")).expect("csv summary");
+    assert!(csv.starts_with("directory,status,exit_code,category,legacy_class,"));
+
+    let resumed = run_cli_command(temp.path(), &["batch", "md/snap_*"]);
+    assert!(
+        resumed.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&resumed.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&resumed.stdout);
+    assert_eq!(
+        stdout.matches("] skipped ").count(),
+        2,
+        "completed runs should be skipped, stdout: {}",
+        stdout
     );
 }
 
@@ -98,7 +407,7 @@ fn module_commands_enforce_runtime_compute_engine_boundary() {
     );
     let pot_stdout = String::from_utf8_lossy(&pot.stdout);
     assert!(
-        pot_stdout.contains("fixture 'FX-POT-001'"),
+        pot_stdout.contains("completed as 'FX-POT-001'"),
         "pot should resolve the compatibility fixture through manifest-based selection, stdout: {}",
         pot_stdout
     );
@@ -410,9 +719,17 @@ fn module_command_runs_without_workspace_context() {
     );
 
     let stdout = String::from_utf8_lossy(&pot.stdout);
-    assert!(
-        stdout.contains("fixture 'FX-POT-001'"),
-        "pot should use default compatibility fixture selection outside workspace context, stdout: {}",
+    let label = temp
+        .path()
+        .canonicalize()
+        .expect("temp dir should resolve")
+        .file_name()
+        .expect("temp dir should have a name")
+        .to_string_lossy()
+        .into_owned();
+    assert!(
+        stdout.contains(&format!("completed as '{}'", label)),
+        "pot should be labelled by its run directory outside workspace context, stdout: {}",
         stdout
     );
     assert!(
@@ -425,6 +742,84 @@ fn module_command_runs_without_workspace_context() {
     );
 }
 
+#[test]
+fn run_command_executes_deck_modules_without_manifest() {
+    let temp = TempDir::new().expect("temp dir should be created");
+    let run_dir = temp.path().join("Cu");
+    fs::create_dir_all(&run_dir).expect("run dir should be created");
+    stage_baseline_artifact("FX-WORKFLOW-XAS-001", "feff.inp", run_dir.join("feff.inp"));
+
+    let output = run_cli_command(temp.path(), &["run", "Cu"]);
+    assert!(
+        output.status.success(),
+        "run should succeed outside workspace context, stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(!stdout.contains("fixture"), "stdout: {}", stdout);
+    assert!(stdout.contains("Running FMS..."), "stdout: {}", stdout);
+    assert!(
+        !stdout.contains("Running COMPTON..."),
+        "modules the deck does not enable should not run, stdout: {}",
+        stdout
+    );
+    for artifact in ["pot.bin", "phase.bin", "paths.dat", "xmu.dat"] {
+        assert!(run_dir.join(artifact).is_file(), "{artifact} should exist");
+    }
+
+    let feff = run_cli_command(&run_dir, &["feff", "--runtime"]);
+    assert!(
+        feff.status.success(),
+        "feff should fall back to the deck workflow without a manifest, stderr: {}",
+        String::from_utf8_lossy(&feff.stderr)
+    );
+
+    let status = run_cli_command(temp.path(), &["status", "Cu"]);
+    assert!(status.status.success());
+    assert!(
+        String::from_utf8_lossy(&status.stdout)
+            .lines()
+            .all(|line| line.ends_with(" fresh")),
+        "every stage should be fresh after a run: {}",
+        String::from_utf8_lossy(&status.stdout)
+    );
+    let deck = fs::read_to_string(run_dir.join("feff.inp")).expect("deck should be readable");
+    fs::write(run_dir.join("feff.inp"), format!("S02 0.8\n{deck}"))
+        .expect("deck should be written");
+    let status = run_cli_command(temp.path(), &["status", "Cu"]);
+    let status_stdout = String::from_utf8_lossy(&status.stdout);
+    assert!(
+        status_stdout.contains("RDINP         stale: feff.inp changed"),
+        "{status_stdout}"
+    );
+    let rerun = run_cli_command(temp.path(), &["run", "Cu", "--force", "xsph"]);
+    assert!(rerun.status.success());
+    let rerun_stdout = String::from_utf8_lossy(&rerun.stdout);
+    for expected in [
+        "Running RDINP...",
+        "Skipping POT (inputs unchanged).",
+        "Running XSPH...",
+        "Running DEBYE...",
+    ] {
+        assert!(rerun_stdout.contains(expected), "{rerun_stdout}");
+    }
+
+    let explain = run_cli_command(temp.path(), &["run", "Cu", "--explain"]);
+    assert!(explain.status.success());
+    let explanation = String::from_utf8_lossy(&explain.stdout);
+    assert!(
+        explanation.contains("FMS           enabled by the FMS card"),
+        "{explanation}"
+    );
+    assert!(
+        explanation.contains("COMPTON       skipped: no COMPTON card"),
+        "{explanation}"
+    );
+
+    let missing = run_cli_command(temp.path(), &["run", "absent"]);
+    assert!(!missing.status.success());
+}
+
 #[test]
 fn crpa_module_command_succeeds_with_runtime_compute_engine() {
     let temp = fixture_tempdir();
@@ -832,6 +1227,247 @@ fn feff_help_includes_strict_legacy_options() {
     assert!(mpi_stdout.contains("--strict-bin-dir"));
 }
 
+#[test]
+fn sweep_command_runs_each_grid_point_and_writes_summary() {
+    let temp = fixture_tempdir();
+    let deck_path = temp.path().join("feff.inp");
+    stage_baseline_artifact("FX-WORKFLOW-XAS-001", "feff.inp", deck_path.clone());
+    let deck = fs::read_to_string(&deck_path).expect("staged deck should be readable");
+    let parameterized = format!("@set RF 4.0\n{}", deck.replacen("FMS 4.0", "FMS ${RF}", 1));
+    assert!(
+        parameterized.contains("FMS ${RF}"),
+        "deck should reference RF"
+    );
+    fs::write(&deck_path, parameterized).expect("parameterized deck should be written");
+
+    let output = run_cli_command(temp.path(), &["sweep", "RF=3.0,4.0"]);
+    assert!(
+        output.status.success(),
+        "sweep should succeed, stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let run_deck = fs::read_to_string(temp.path().join("sweep/run_001/feff.inp"))
+        .expect("first run deck should exist");
+    assert!(run_deck.contains("FMS 3.0"));
+    assert!(!run_deck.contains("${RF}"));
+    assert!(temp.path().join("sweep/run_002/feff.inp").is_file());
+
+    let summary =
+        fs::read_to_string(temp.path().join("sweep/sweep.dat")).expect("summary should exist");
+    let rows: Vec<&str> = summary
+        .lines()
+        .filter(|line| !line.starts_with('#'))
+        .collect();
+    assert_eq!(rows.len(), 2);
+    assert!(rows.iter().all(|row| row.contains(" ok ")), "{summary}");
+
+    let rejected = run_cli_command(temp.path(), &["sweep", "T=300"]);
+    assert!(
+        !rejected.status.success(),
+        "undeclared sweep variables should fail"
+    );
+}
+
+#[test]
+fn sweep_command_plans_each_point_from_its_own_deck() {
+    let temp = fixture_tempdir();
+    let deck_path = temp.path().join("feff.inp");
+    stage_baseline_artifact("FX-WORKFLOW-XAS-001", "feff.inp", deck_path.clone());
+    let deck = fs::read_to_string(&deck_path).expect("staged deck should be readable");
+    let parameterized = format!(
+        "@set DOS *LDOS\n{}",
+        deck.replacen("FMS 4.0", "${DOS} -30.0 20.0 0.5\nFMS 4.0", 1)
+    );
+    fs::write(&deck_path, parameterized).expect("parameterized deck should be written");
+
+    let output = run_cli_command(temp.path(), &["sweep", "DOS=LDOS,*LDOS"]);
+    assert!(
+        output.status.success(),
+        "sweep should succeed, stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let stages = |run: &str| {
+        let manifest: Value = serde_json::from_str(
+            &fs::read_to_string(temp.path().join("sweep").join(run).join("feff10-run.json"))
+                .expect("run manifest should exist"),
+        )
+        .expect("run manifest should be JSON");
+        let stages = manifest["stages"]
+            .as_object()
+            .expect("manifest should list stages")
+            .clone();
+        assert!(
+            stages.values().all(|stage| stage["label"] == run),
+            "{run} should run under its directory label"
+        );
+        stages
+    };
+    assert!(
+        stages("run_001").contains_key("LDOS"),
+        "the point with an LDOS card should run LDOS"
+    );
+    assert!(
+        !stages("run_002").contains_key("LDOS"),
+        "the point with the LDOS card commented out should skip LDOS"
+    );
+}
+
+#[test]
+fn fmt_command_checks_and_rewrites_input_decks() {
+    let temp = fixture_tempdir();
+    let deck = temp.path().join("feff.inp");
+    fs::write(
+        &deck,
+        "TITL Cu\nPOTE\n 0 29 Cu\n 1 29 Cu\nATOMS * cluster\n 2.55 0 0 1 Cu\n 0 0 0 0 Cu\nEND\n",
+    )
+    .expect("deck should be written");
+
+    let check = run_cli_command(temp.path(), &["fmt", "--check"]);
+    assert_eq!(
+        check.status.code(),
+        Some(1),
+        "unformatted deck should fail --check"
+    );
+    assert!(String::from_utf8_lossy(&check.stdout).contains("Would reformat"));
+
+    let output = run_cli_command(temp.path(), &["fmt", "feff.inp"]);
+    assert!(
+        output.status.success(),
+        "fmt should succeed, stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(
+        fs::read_to_string(&deck).expect("formatted deck should be readable"),
+        "TITLE Cu\nPOTENTIALS\n   0   29   Cu\n   1   29   Cu\nATOMS * cluster\n      0   0   0   0   Cu\n   2.55   0   0   1   Cu\nEND\n"
+    );
+
+    let recheck = run_cli_command(temp.path(), &["fmt", "--check"]);
+    assert!(
+        recheck.status.success(),
+        "formatted deck should pass --check"
+    );
+}
+
+#[test]
+fn check_command_reports_diagnostics_with_positions() {
+    let temp = fixture_tempdir();
+    fs::write(
+        temp.path().join("feff.inp"),
+        "TITLE Cu\nEDGE K\nPOTENTIALS\n 0 29 Cu\nATOMS\n 0 0 0 0 Cu\n 2.55 0 0 1 Cu\nEND\n",
+    )
+    .expect("deck should be written");
+
+    let output = run_cli_command(temp.path(), &["check"]);
+    assert_eq!(
+        output.status.code(),
+        Some(1),
+        "undefined potential is an error"
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("feff.inp:7:11: error[LINT.UNDEFINED_POTENTIAL]"),
+        "check should print file:line:column, got: {stdout}"
+    );
+    assert!(stdout.contains("= fix: add a POTENTIALS row for index 1"));
+}
+
+#[test]
+fn lsp_command_serves_diagnostics_hover_completion_and_definition() {
+    use std::io::Write;
+    use std::process::Stdio;
+
+    let deck = "TITLE Cu\nEDGE K\nPOTENTIALS\n  0 29 Cu\n  1 29 Cu\nATOMS\n  0 0 0 0 Cu\n  1.8 1.8 0 1 Cu\n  3.6 0 0 2 Cu\nEND\n";
+    let uri = "file:///tmp/feff.inp";
+    let document = serde_json::json!({ "uri": uri });
+    let messages = [
+        serde_json::json!({ "jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {} }),
+        serde_json::json!({ "jsonrpc": "2.0", "method": "initialized", "params": {} }),
+        serde_json::json!({
+            "jsonrpc": "2.0",
+            "method": "textDocument/didOpen",
+            "params": { "textDocument": { "uri": uri, "languageId": "feff", "version": 1, "text": deck } },
+        }),
+        serde_json::json!({
+            "jsonrpc": "2.0", "id": 2, "method": "textDocument/hover",
+            "params": { "textDocument": document, "position": { "line": 1, "character": 1 } },
+        }),
+        serde_json::json!({
+            "jsonrpc": "2.0", "id": 3, "method": "textDocument/completion",
+            "params": { "textDocument": document, "position": { "line": 1, "character": 1 } },
+        }),
+        serde_json::json!({
+            "jsonrpc": "2.0", "id": 4, "method": "textDocument/definition",
+            "params": { "textDocument": document, "position": { "line": 7, "character": 12 } },
+        }),
+        serde_json::json!({ "jsonrpc": "2.0", "id": 5, "method": "shutdown" }),
+        serde_json::json!({ "jsonrpc": "2.0", "method": "exit" }),
+    ];
+
+    let temp = fixture_tempdir();
+    let mut child = Command::new(binary_path())
+        .arg("lsp")
+        .current_dir(temp.path())
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("lsp server should start");
+    {
+        let stdin = child.stdin.as_mut().expect("stdin should be piped");
+        for message in &messages {
+            let body = message.to_string();
+            write!(stdin, "Content-Length: {}\r\n\r\n{}", body.len(), body)
+                .expect("message should be written");
+        }
+    }
+    let output = child.wait_with_output().expect("lsp server should exit");
+    assert!(
+        output.status.success(),
+        "exit after shutdown should succeed"
+    );
+
+    let stdout = String::from_utf8(output.stdout).expect("responses should be UTF-8");
+    let responses: Vec<Value> = stdout
+        .split("Content-Length: ")
+        .filter(|chunk| !chunk.is_empty())
+        .map(|chunk| {
+            let (_, body) = chunk.split_once("\r\n\r\n").expect("header separator");
+            serde_json::from_str(body).expect("body should be JSON")
+        })
+        .collect();
+    let by_id = |id: i64| {
+        responses
+            .iter()
+            .find(|response| response["id"] == id)
+            .unwrap_or_else(|| panic!("response {id} should exist"))
+    };
+
+    assert_eq!(by_id(1)["result"]["capabilities"]["hoverProvider"], true);
+    let diagnostics = responses
+        .iter()
+        .find(|message| message["method"] == "textDocument/publishDiagnostics")
+        .expect("diagnostics should be published");
+    let diagnostic = &diagnostics["params"]["diagnostics"][0];
+    assert_eq!(diagnostic["code"], "LINT.UNDEFINED_POTENTIAL");
+    assert_eq!(diagnostic["range"]["start"]["line"], 8);
+    assert!(
+        by_id(2)["result"]["contents"]["value"]
+            .as_str()
+            .expect("hover markdown")
+            .starts_with("**EDGE**")
+    );
+    let labels: Vec<&str> = by_id(3)["result"]
+        .as_array()
+        .expect("completion list")
+        .iter()
+        .filter_map(|item| item["label"].as_str())
+        .collect();
+    assert!(labels.contains(&"EDGE") && labels.contains(&"EXAFS"));
+    assert!(!labels.contains(&"ATOMS"));
+    assert_eq!(by_id(4)["result"]["range"]["start"]["line"], 4);
+    assert_eq!(by_id(4)["result"]["uri"], uri);
+}
+
 #[cfg(unix)]
 #[test]
 fn executable_name_alias_dispatches_module_command() {
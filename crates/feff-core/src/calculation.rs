@@ -0,0 +1,552 @@
+//! End-to-end calculations without touching module file names.
+//!
+//! [`Calculation`] renders a typed deck into an in-memory run directory,
+//! plans the modules the deck enables with [`PipelinePlan::for_deck`] and
+//! runs them through the runtime executors. The outputs FEFF users usually
+//! read (`xmu.dat`, `chi.dat`, `paths.dat`, `ldosNN.dat`) come back typed in
+//! [`CalculationResults`]; every other artifact stays available by name.
+
+use crate::domain::input::{EelsCard, RixsCard, XanesCard};
+use crate::domain::{
+    ComputeModule, ComputeResult, ExecutionControl, ExecutionMode, FeffError, FeffInput, InputCard,
+    InputCardKind, MemoryStore, SharedStore,
+};
+use crate::modules::pipeline::PipelinePlan;
+use crate::modules::{ensure_runtime_engines_available, execute_runtime_module, runtime_request};
+use crate::parser::parse_feff_input;
+use crate::parser::structure::structure_input_deck;
+use crate::support::inpgen::structure_files::ImportedStructure;
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// In-memory directory the modules of a calculation read and write.
+const RUN_DIRECTORY: &str = "calculation";
+const DECK_NAME: &str = "feff.inp";
+const SPECTRUM_OUTPUT: &str = "xmu.dat";
+const CHI_OUTPUT: &str = "chi.dat";
+const PATHS_OUTPUT: &str = "paths.dat";
+
+/// Spectroscopy a calculation produces, selected by the card it adds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Spectroscopy {
+    /// `EXAFS`; drops any `XANES` card so the extended grid is used.
+    Exafs,
+    /// `XANES` with default grid unless the deck already has one.
+    Xanes,
+    /// `ELNES` with the default 300 keV beam unless the deck sets one.
+    Elnes,
+    /// `EXELFS` with the default 300 keV beam unless the deck sets one.
+    Exelfs,
+    Rixs,
+    Xes,
+    Compton,
+}
+
+impl Spectroscopy {
+    fn apply(self, input: &mut FeffInput) {
+        match self {
+            Self::Exafs => {
+                input.xanes = None;
+                input.exafs.get_or_insert_with(Default::default);
+            }
+            Self::Xanes => {
+                input.xanes.get_or_insert_with(XanesCard::default);
+            }
+            Self::Rixs => {
+                input.rixs.get_or_insert_with(RixsCard::default);
+            }
+            Self::Elnes => {
+                input.elnes.get_or_insert_with(EelsCard::elnes);
+            }
+            Self::Exelfs => {
+                input.exelfs.get_or_insert_with(EelsCard::exelfs);
+            }
+            Self::Xes => add_flag_card(input, "XES", InputCardKind::Xes),
+            Self::Compton => add_flag_card(input, "COMPTON", InputCardKind::Compton),
+        }
+    }
+}
+
+/// Builder for one end-to-end run.
+///
+/// ```no_run
+/// use feff_core::{Calculation, Spectroscopy};
+///
+/// let source = std::fs::read_to_string("feff.inp")?;
+/// let results = Calculation::from_source(&source)?
+///     .spectroscopy(Spectroscopy::Xanes)
+///     .run()?;
+/// if let Some(spectrum) = &results.spectrum {
+///     println!("{} energy points", spectrum.energy.len());
+/// }
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct Calculation {
+    input: FeffInput,
+    label: String,
+    execution_mode: ExecutionMode,
+    control: ExecutionControl,
+}
+
+impl Calculation {
+    pub fn from_input(input: FeffInput) -> Self {
+        Self {
+            input,
+            label: RUN_DIRECTORY.to_string(),
+            execution_mode: ExecutionMode::Serial,
+            control: ExecutionControl::default(),
+        }
+    }
+
+    /// Parses `feff.inp` source.
+    pub fn from_source(source: &str) -> ComputeResult<Self> {
+        Ok(Self::from_input(parse_feff_input(source)?))
+    }
+
+    /// Builds the deck from a cluster of `radius` Angstrom around atom
+    /// `absorber` (1-based) of an imported structure.
+    pub fn from_structure(
+        structure: &ImportedStructure,
+        absorber: usize,
+        radius: f64,
+    ) -> ComputeResult<Self> {
+        let deck = structure_input_deck(structure, absorber, radius)?;
+        Ok(Self::from_input(FeffInput::from_deck(&deck)?))
+    }
+
+    /// Adds the cards for `spectroscopy`. May be called more than once.
+    pub fn spectroscopy(mut self, spectroscopy: Spectroscopy) -> Self {
+        spectroscopy.apply(&mut self.input);
+        self
+    }
+
+    /// Labels the run in module outputs (the `fixture` line of `paths.dat`).
+    pub fn label(mut self, label: impl Into<String>) -> Self {
+        self.label = label.into();
+        self
+    }
+
+    pub fn execution_mode(mut self, execution_mode: ExecutionMode) -> Self {
+        self.execution_mode = execution_mode;
+        self
+    }
+
+    /// Observer and cancellation token passed to every module.
+    pub fn control(mut self, control: ExecutionControl) -> Self {
+        self.control = control;
+        self
+    }
+
+    /// The deck as it will be run, with every spectroscopy applied.
+    pub fn input(&self) -> &FeffInput {
+        &self.input
+    }
+
+    /// Plans and runs the modules the deck enables, in memory.
+    pub fn run(&self) -> ComputeResult<CalculationResults> {
+        let deck = self.input.to_deck();
+        let store = Arc::new(MemoryStore::new());
+        let directory = Path::new(RUN_DIRECTORY);
+        store.insert(directory.join(DECK_NAME), self.input.to_string());
+
+        let staged = BTreeSet::from([DECK_NAME.to_string()]);
+        let modules = PipelinePlan::for_deck(&deck, &staged)?.modules();
+        ensure_runtime_engines_available(&modules)?;
+
+        let shared = SharedStore::new(store.clone());
+        for module in &modules {
+            let request = runtime_request(&self.label, *module, directory)?
+                .with_execution_mode(self.execution_mode)
+                .with_control(self.control.clone())
+                .with_store(shared.clone());
+            execute_runtime_module(*module, &request)?;
+        }
+
+        CalculationResults::from_store(shared, directory, modules)
+    }
+}
+
+/// `xmu.dat` columns: absolute energy (eV), wave number (1/Angstrom),
+/// `mu`, the embedded-atom background `mu0` and `chi`.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Spectrum {
+    pub energy: Vec<f64>,
+    pub k: Vec<f64>,
+    pub mu: Vec<f64>,
+    pub mu0: Vec<f64>,
+    pub chi: Vec<f64>,
+}
+
+/// `chi.dat` columns: wave number (1/Angstrom), `chi(k)` and its magnitude
+/// and phase.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ChiSpectrum {
+    pub k: Vec<f64>,
+    pub chi: Vec<f64>,
+    pub magnitude: Vec<f64>,
+    pub phase: Vec<f64>,
+}
+
+/// One row of `paths.dat`. `legs` are 1-based atom indices.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScatteringPath {
+    pub index: usize,
+    pub nleg: usize,
+    pub degeneracy: f64,
+    pub reff: f64,
+    pub amplitude: f64,
+    pub beta: f64,
+    pub eta: f64,
+    pub legs: Vec<usize>,
+}
+
+/// `ldosNN.dat` for potential `ipot`: per energy (eV), the s, p, d and f
+/// densities for spin up and spin down.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LocalDensityOfStates {
+    pub ipot: usize,
+    pub energy: Vec<f64>,
+    pub up: Vec<[f64; 4]>,
+    pub down: Vec<[f64; 4]>,
+}
+
+/// Typed outputs of a [`Calculation`], plus every artifact it wrote.
+#[derive(Debug)]
+pub struct CalculationResults {
+    /// Modules in the order they ran.
+    pub modules: Vec<ComputeModule>,
+    pub spectrum: Option<Spectrum>,
+    pub chi: Option<ChiSpectrum>,
+    pub paths: Vec<ScatteringPath>,
+    pub ldos: Vec<LocalDensityOfStates>,
+    store: SharedStore,
+    directory: PathBuf,
+}
+
+impl CalculationResults {
+    /// Reads the outputs a run left in `directory` on disk, e.g. one driven
+    /// module by module. `modules` records what ran.
+    pub fn from_directory(
+        directory: impl Into<PathBuf>,
+        modules: Vec<ComputeModule>,
+    ) -> ComputeResult<Self> {
+        Self::from_store(SharedStore::default(), directory, modules)
+    }
+
+    /// Reads the outputs left in `directory` of `store`, e.g. a
+    /// `MemoryStore` that modules were run against one at a time.
+    pub fn from_store(
+        store: SharedStore,
+        directory: impl Into<PathBuf>,
+        modules: Vec<ComputeModule>,
+    ) -> ComputeResult<Self> {
+        let directory = directory.into();
+        let text = |name: &str| {
+            store
+                .read(&directory.join(name))
+                .ok()
+                .map(|bytes| String::from_utf8_lossy(&bytes).into_owned())
+        };
+
+        let spectrum = text(SPECTRUM_OUTPUT)
+            .map(|source| parse_spectrum(&source))
+            .transpose()?;
+        let chi = text(CHI_OUTPUT)
+            .map(|source| parse_chi(&source))
+            .transpose()?;
+        let paths = text(PATHS_OUTPUT)
+            .map(|source| parse_paths(&source))
+            .transpose()?
+            .unwrap_or_default();
+        let names = store.list_files(&directory).map_err(|source| {
+            FeffError::io_system(
+                "IO.CALCULATION_READ",
+                format!(
+                    "failed to list run directory '{}': {}",
+                    directory.display(),
+                    source
+                ),
+            )
+        })?;
+        let mut ldos = Vec::new();
+        for (name, ipot) in names
+            .iter()
+            .filter_map(|name| Some((name, ldos_potential(name)?)))
+        {
+            let source = text(name).unwrap_or_default();
+            ldos.push(parse_ldos(ipot, name, &source)?);
+        }
+
+        Ok(Self {
+            modules,
+            spectrum,
+            chi,
+            paths,
+            ldos,
+            store,
+            directory,
+        })
+    }
+
+    /// Names of every file in the run directory, including `feff.inp`, sorted.
+    pub fn artifact_names(&self) -> Vec<String> {
+        self.store.list_files(&self.directory).unwrap_or_default()
+    }
+
+    /// Contents of the artifact `name` (e.g. `"chi.dat"`), if a module wrote it.
+    pub fn artifact(&self, name: &str) -> Option<Vec<u8>> {
+        self.store.read(&self.directory.join(name)).ok()
+    }
+}
+
+fn add_flag_card(input: &mut FeffInput, keyword: &str, kind: InputCardKind) {
+    if !input
+        .other_cards
+        .iter()
+        .any(|card| card.keyword.eq_ignore_ascii_case(keyword))
+    {
+        input
+            .other_cards
+            .push(InputCard::new(keyword, kind, Vec::new(), 0));
+    }
+}
+
+/// Potential number of an `ldosNN.dat` file name.
+fn ldos_potential(name: &str) -> Option<usize> {
+    let digits = name.strip_prefix("ldos")?.strip_suffix(".dat")?;
+    if digits.is_empty() || !digits.bytes().all(|byte| byte.is_ascii_digit()) {
+        return None;
+    }
+    digits.parse().ok()
+}
+
+fn parse_spectrum(source: &str) -> ComputeResult<Spectrum> {
+    let mut spectrum = Spectrum::default();
+    for row in numeric_rows(SPECTRUM_OUTPUT, source, 6)? {
+        spectrum.energy.push(row[0]);
+        spectrum.k.push(row[2]);
+        spectrum.mu.push(row[3]);
+        spectrum.mu0.push(row[4]);
+        spectrum.chi.push(row[5]);
+    }
+    Ok(spectrum)
+}
+
+fn parse_chi(source: &str) -> ComputeResult<ChiSpectrum> {
+    let mut chi = ChiSpectrum::default();
+    for row in numeric_rows(CHI_OUTPUT, source, 4)? {
+        chi.k.push(row[0]);
+        chi.chi.push(row[1]);
+        chi.magnitude.push(row[2]);
+        chi.phase.push(row[3]);
+    }
+    Ok(chi)
+}
+
+/// Rows after the column header; the placeholder row written for an empty
+/// listing has index 0.
+fn parse_paths(source: &str) -> ComputeResult<Vec<ScatteringPath>> {
+    let mut paths = Vec::new();
+    let rows = source
+        .lines()
+        .skip_while(|line| !line.starts_with("index "))
+        .skip(1);
+    for line in rows {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if fields.is_empty() {
+            continue;
+        }
+        let [index, nleg, degeneracy, reff, amplitude, beta, eta, legs] = fields[..] else {
+            return Err(output_error(PATHS_OUTPUT, line));
+        };
+        let integer = |value: &str| {
+            value
+                .parse::<usize>()
+                .map_err(|_| output_error(PATHS_OUTPUT, line))
+        };
+        let real = |value: &str| {
+            value
+                .parse::<f64>()
+                .map_err(|_| output_error(PATHS_OUTPUT, line))
+        };
+        let index = integer(index)?;
+        if index == 0 {
+            continue;
+        }
+        let legs = if legs == "-" {
+            Vec::new()
+        } else {
+            legs.split(',').map(integer).collect::<ComputeResult<_>>()?
+        };
+        paths.push(ScatteringPath {
+            index,
+            nleg: integer(nleg)?,
+            degeneracy: real(degeneracy)?,
+            reff: real(reff)?,
+            amplitude: real(amplitude)?,
+            beta: real(beta)?,
+            eta: real(eta)?,
+            legs,
+        });
+    }
+    Ok(paths)
+}
+
+fn parse_ldos(ipot: usize, name: &str, source: &str) -> ComputeResult<LocalDensityOfStates> {
+    let mut ldos = LocalDensityOfStates {
+        ipot,
+        energy: Vec::new(),
+        up: Vec::new(),
+        down: Vec::new(),
+    };
+    for row in numeric_rows(name, source, 9)? {
+        ldos.energy.push(row[0]);
+        ldos.up.push([row[1], row[2], row[3], row[4]]);
+        ldos.down.push([row[5], row[6], row[7], row[8]]);
+    }
+    Ok(ldos)
+}
+
+/// Non-comment rows of a FEFF table with at least `columns` numbers each.
+fn numeric_rows(name: &str, source: &str, columns: usize) -> ComputeResult<Vec<Vec<f64>>> {
+    source
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| {
+            let row = line
+                .split_whitespace()
+                .map(|field| field.replace(['D', 'd'], "E").parse::<f64>())
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|_| output_error(name, line))?;
+            if row.len() < columns {
+                return Err(output_error(name, line));
+            }
+            Ok(row)
+        })
+        .collect()
+}
+
+fn output_error(name: &str, line: &str) -> FeffError {
+    FeffError::internal(
+        "SYS.CALCULATION_OUTPUT",
+        format!("unexpected row in '{}': '{}'", name, line.trim()),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Calculation, Spectroscopy};
+    use crate::domain::ComputeModule;
+    use crate::domain::input::EelsCard;
+    use std::path::Path;
+
+    fn workflow_deck() -> String {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("../..");
+        std::fs::read_to_string(
+            root.join("artifacts/fortran-baselines/FX-WORKFLOW-XAS-001/baseline/feff.inp"),
+        )
+        .expect("workflow deck should be readable")
+    }
+
+    #[test]
+    fn spectroscopy_adds_its_card_once() {
+        let calculation = Calculation::from_source(&workflow_deck())
+            .expect("deck should parse")
+            .spectroscopy(Spectroscopy::Exafs)
+            .spectroscopy(Spectroscopy::Elnes)
+            .spectroscopy(Spectroscopy::Elnes);
+        let input = calculation.input();
+        assert!(input.xanes.is_none());
+        assert!(input.exafs.is_some());
+        assert_eq!(input.elnes, Some(EelsCard::elnes()));
+        assert_eq!(
+            input
+                .to_deck()
+                .cards
+                .iter()
+                .filter(|card| card.keyword == "ELNES")
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn elnes_calculation_runs_eels_to_a_spectrum() {
+        let results = Calculation::from_source(&workflow_deck())
+            .expect("deck should parse")
+            .spectroscopy(Spectroscopy::Elnes)
+            .run()
+            .expect("ELNES calculation should run");
+
+        assert!(results.modules.contains(&ComputeModule::Eels));
+        let spectrum = results.spectrum.as_ref().expect("xmu.dat should be read");
+        assert!(!spectrum.energy.is_empty());
+        let eels = String::from_utf8(results.artifact("eels.dat").expect("eels.dat artifact"))
+            .expect("eels.dat should be text");
+        assert!(eels.lines().any(|line| !line.starts_with('#')));
+    }
+
+    #[test]
+    fn bare_xes_and_compton_cards_run() {
+        let xes = Calculation::from_source(&workflow_deck())
+            .expect("deck should parse")
+            .spectroscopy(Spectroscopy::Xes)
+            .run()
+            .expect("XES calculation should run");
+        assert!(!xes.modules.contains(&ComputeModule::Rixs));
+        assert!(xes.spectrum.is_some());
+        let xsph = String::from_utf8(xes.artifact("xsph.inp").expect("xsph.inp artifact"))
+            .expect("xsph.inp should be text");
+        let control: Vec<&str> = xsph
+            .lines()
+            .nth(1)
+            .expect("control row")
+            .split_whitespace()
+            .collect();
+        assert_eq!(control[4], "2");
+
+        let compton = Calculation::from_source(&workflow_deck())
+            .expect("deck should parse")
+            .spectroscopy(Spectroscopy::Compton)
+            .run()
+            .expect("COMPTON calculation should run");
+        assert!(compton.modules.contains(&ComputeModule::Compton));
+        assert!(compton.artifact("compton.dat").is_some());
+    }
+
+    #[test]
+    fn run_returns_typed_spectrum_matching_xmu_dat() {
+        let results = Calculation::from_source(&workflow_deck())
+            .expect("deck should parse")
+            .run()
+            .expect("calculation should run");
+
+        assert_eq!(results.modules.first(), Some(&ComputeModule::Rdinp));
+        assert!(results.modules.contains(&ComputeModule::Debye));
+        let spectrum = results.spectrum.as_ref().expect("xmu.dat should be read");
+        assert!(!spectrum.energy.is_empty());
+        assert_eq!(spectrum.energy.len(), spectrum.chi.len());
+        assert!(spectrum.energy.windows(2).all(|pair| pair[0] < pair[1]));
+
+        let xmu = String::from_utf8(results.artifact("xmu.dat").expect("xmu.dat artifact"))
+            .expect("xmu.dat should be text");
+        let first_row: Vec<f64> = xmu
+            .lines()
+            .find(|line| !line.starts_with('#'))
+            .expect("data row")
+            .split_whitespace()
+            .map(|field| field.parse().expect("number"))
+            .collect();
+        assert_eq!(spectrum.energy[0], first_row[0]);
+        assert_eq!(spectrum.mu[0], first_row[3]);
+        assert!(results.artifact_names().contains(&"feff.inp".to_string()));
+
+        let chi = results.chi.as_ref().expect("chi.dat should be read");
+        assert!(!chi.k.is_empty());
+        assert_eq!(chi.k.len(), chi.phase.len());
+        assert!(chi.k.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+}
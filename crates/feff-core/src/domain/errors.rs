@@ -49,6 +49,18 @@ impl FeffErrorCategory {
         self.compatibility_placeholder().exit_code
     }
 
+    /// Category a process exit code maps back to, if it is one of ours.
+    pub const fn from_exit_code(exit_code: i32) -> Option<Self> {
+        match exit_code {
+            0 => Some(Self::Success),
+            2 => Some(Self::InputValidationError),
+            3 => Some(Self::IoSystemError),
+            4 => Some(Self::ComputationError),
+            5 => Some(Self::InternalError),
+            _ => None,
+        }
+    }
+
     pub const fn rust_category(self) -> &'static str {
         self.compatibility_placeholder().rust_category
     }
@@ -198,7 +210,9 @@ mod tests {
             assert_eq!(placeholder.exit_code, exit_code);
             assert_eq!(placeholder.rust_category, rust_category);
             assert_eq!(placeholder.legacy_class, legacy_class);
+            assert_eq!(FeffErrorCategory::from_exit_code(exit_code), Some(category));
         }
+        assert_eq!(FeffErrorCategory::from_exit_code(1), None);
     }
 
     #[test]
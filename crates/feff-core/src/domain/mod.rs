@@ -1,17 +1,36 @@
 pub mod errors;
+pub mod input;
+pub mod progress;
+pub mod store;
 
 pub use errors::{
     CompatibilityExitPlaceholder, ComputeResult, FeffError, FeffErrorCategory, FeffResult,
     ParserResult,
 };
+pub use input::FeffInput;
+pub use progress::{CancellationToken, ExecutionControl, ExecutionEvent, ExecutionObserver};
+pub use store::{ArtifactStore, FileSystemStore, MemoryStore, OverlayStore, SharedStore};
 
 use std::fmt::{Display, Formatter};
 use std::path::PathBuf;
+use std::str::FromStr;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub enum ExecutionMode {
     #[default]
     Serial,
+    /// Independent loops (energy points, paths, q-points) are split across
+    /// `workers` threads. Results are bit-identical to `Serial`.
+    Threaded { workers: usize },
+}
+
+impl ExecutionMode {
+    pub fn workers(self) -> usize {
+        match self {
+            Self::Serial => 1,
+            Self::Threaded { workers } => workers.max(1),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -35,6 +54,25 @@ pub enum ComputeModule {
 }
 
 impl ComputeModule {
+    pub const ALL: [Self; 16] = [
+        Self::Rdinp,
+        Self::Pot,
+        Self::Path,
+        Self::Fms,
+        Self::Xsph,
+        Self::Band,
+        Self::Ldos,
+        Self::Rixs,
+        Self::Crpa,
+        Self::Compton,
+        Self::Debye,
+        Self::Dmdw,
+        Self::Screen,
+        Self::SelfEnergy,
+        Self::Eels,
+        Self::FullSpectrum,
+    ];
+
     pub const fn as_str(self) -> &'static str {
         match self {
             Self::Rdinp => "RDINP",
@@ -63,11 +101,31 @@ impl Display for ComputeModule {
     }
 }
 
+/// Parses a module name as [`ComputeModule::as_str`] writes it, ignoring case.
+impl FromStr for ComputeModule {
+    type Err = FeffError;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|module| module.as_str().eq_ignore_ascii_case(name))
+            .ok_or_else(|| {
+                FeffError::input_validation(
+                    "INPUT.UNKNOWN_MODULE",
+                    format!("unknown module '{}'", name),
+                )
+            })
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ComputeRequest {
     pub fixture_id: String,
     pub module: ComputeModule,
     pub execution_mode: ExecutionMode,
+    pub control: ExecutionControl,
+    /// Store the module reads inputs from and writes artifacts to.
+    pub store: SharedStore,
     pub input_path: PathBuf,
     pub output_dir: PathBuf,
 }
@@ -83,10 +141,27 @@ impl ComputeRequest {
             fixture_id: fixture_id.into(),
             module,
             execution_mode: ExecutionMode::Serial,
+            control: ExecutionControl::default(),
+            store: SharedStore::default(),
             input_path: input_path.into(),
             output_dir: output_dir.into(),
         }
     }
+
+    pub fn with_execution_mode(mut self, execution_mode: ExecutionMode) -> Self {
+        self.execution_mode = execution_mode;
+        self
+    }
+
+    pub fn with_control(mut self, control: ExecutionControl) -> Self {
+        self.control = control;
+        self
+    }
+
+    pub fn with_store(mut self, store: SharedStore) -> Self {
+        self.store = store;
+        self
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -114,6 +189,41 @@ impl InputDeck {
             .filter(|card| card.kind.applies_to_module(module))
             .collect()
     }
+
+    /// Replaces the values of the first `keyword` card (case-insensitive),
+    /// or adds the card before `END` when the deck has none.
+    pub fn set_card(&mut self, keyword: &str, values: Vec<String>) -> &mut InputCard {
+        let index = match self
+            .cards
+            .iter()
+            .position(|card| card.keyword.eq_ignore_ascii_case(keyword))
+        {
+            Some(index) => index,
+            None => {
+                let keyword = keyword.to_ascii_uppercase();
+                let index = self
+                    .cards
+                    .iter()
+                    .position(|card| card.kind == InputCardKind::End)
+                    .unwrap_or(self.cards.len());
+                let kind = InputCardKind::from_keyword(&keyword);
+                self.cards
+                    .insert(index, InputCard::new(keyword, kind, Vec::new(), 0));
+                index
+            }
+        };
+        let card = &mut self.cards[index];
+        card.values = values;
+        card
+    }
+
+    /// Removes every `keyword` card and returns how many were removed.
+    pub fn remove_cards(&mut self, keyword: &str) -> usize {
+        let before = self.cards.len();
+        self.cards
+            .retain(|card| !card.keyword.eq_ignore_ascii_case(keyword));
+        before - self.cards.len()
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -140,6 +250,18 @@ impl InputCard {
             source_line,
         }
     }
+
+    /// Replaces the continuation lines (e.g. `ATOMS` rows) with `rows`.
+    pub fn set_rows(&mut self, rows: Vec<Vec<String>>) {
+        self.continuations = rows
+            .into_iter()
+            .map(|values| InputCardContinuation {
+                source_line: self.source_line,
+                raw: values.join(" "),
+                values,
+            })
+            .collect();
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -219,82 +341,115 @@ pub enum InputCardKind {
     Ispec,
     Cfname,
     Mfullspectrum,
+    Nrixs,
+    Ljmax,
+    Ldecmx,
+    Polarization,
+    Ellipticity,
+    Spin,
     Unknown(String),
 }
 
 impl InputCardKind {
-    pub fn from_keyword(keyword: &str) -> Self {
-        match keyword {
-            "TITLE" => Self::Title,
-            "EDGE" => Self::Edge,
-            "S02" => Self::S02,
-            "CONTROL" => Self::Control,
-            "PRINT" => Self::Print,
-            "LDOS" => Self::Ldos,
-            "EXAFS" => Self::Exafs,
-            "RPATH" => Self::Rpath,
-            "POTENTIALS" => Self::Potentials,
-            "POTENTIAL" => Self::Potential,
-            "ATOMS" => Self::Atoms,
-            "END" => Self::End,
-            "DEBYE" => Self::Debye,
-            "EXCHANGE" => Self::Exchange,
-            "SCF" => Self::Scf,
-            "COREHOLE" => Self::Corehole,
-            "XANES" => Self::Xanes,
-            "FMS" => Self::Fms,
-            "CIF" => Self::Cif,
-            "TARGET" => Self::Target,
-            "HUBBARD" => Self::Hubbard,
-            "UNFREEZEF" => Self::Unfreezef,
-            "RIXS" => Self::Rixs,
-            "XES" => Self::Xes,
-            "EGRID" => Self::Egrid,
-            "E_GRID" => Self::EGrid,
-            "K_GRID" => Self::KGrid,
-            "COMPTON" => Self::Compton,
-            "CGRID" => Self::Cgrid,
-            "RHOZZP" => Self::Rhozzp,
-            "OPCONS" => Self::Opcons,
-            "MPSE" => Self::Mpse,
-            "SFCONV" => Self::Sfconv,
-            "CORRECTIONS" => Self::Corrections,
-            "EXELFS" => Self::Exelfs,
-            "RECIPROCAL" => Self::Reciprocal,
-            "KMESH" => Self::Kmesh,
-            "STRFAC" => Self::Strfac,
-            "ELNES" => Self::Elnes,
-            "MAGIC" => Self::Magic,
-            "LATTICE" => Self::Lattice,
-            "CRPA" => Self::Crpa,
-            "VDOS" => Self::Vdos,
-            "STRETCHES" => Self::Stretches,
-            "SCREEN" => Self::Screen,
-            "BAND" => Self::Band,
-            "FULLSPECTRUM" => Self::FullSpectrum,
-            "MBAND" => Self::Mband,
-            "NKP" => Self::Nkp,
-            "IKPATH" => Self::Ikpath,
-            "FREEPROP" => Self::Freeprop,
-            "NER" => Self::Ner,
-            "NEI" => Self::Nei,
-            "MAXL" => Self::Maxl,
-            "IRRH" => Self::Irrh,
-            "IEND" => Self::Iend,
-            "LFXC" => Self::Lfxc,
-            "EMIN" => Self::Emin,
-            "EMAX" => Self::Emax,
-            "EIMAX" => Self::Eimax,
-            "ERMIN" => Self::Ermin,
-            "RFMS" => Self::Rfms,
-            "NRPTX0" => Self::Nrptx0,
-            "MSFCONV" => Self::Msfconv,
-            "WSIGK" => Self::Wsigk,
-            "ISPEC" => Self::Ispec,
-            "CFNAME" => Self::Cfname,
-            "MFULLSPECTRUM" => Self::Mfullspectrum,
-            _ => Self::Unknown(keyword.to_owned()),
+    /// Every card keyword and its kind, in deck documentation order. Both
+    /// [`InputCardKind::KEYWORDS`] and [`InputCardKind::from_keyword`] read
+    /// this table.
+    const KEYWORD_KINDS: [(&'static str, Self); 74] = [
+        ("TITLE", Self::Title),
+        ("EDGE", Self::Edge),
+        ("S02", Self::S02),
+        ("CONTROL", Self::Control),
+        ("PRINT", Self::Print),
+        ("LDOS", Self::Ldos),
+        ("EXAFS", Self::Exafs),
+        ("RPATH", Self::Rpath),
+        ("POTENTIALS", Self::Potentials),
+        ("POTENTIAL", Self::Potential),
+        ("ATOMS", Self::Atoms),
+        ("END", Self::End),
+        ("DEBYE", Self::Debye),
+        ("EXCHANGE", Self::Exchange),
+        ("SCF", Self::Scf),
+        ("COREHOLE", Self::Corehole),
+        ("XANES", Self::Xanes),
+        ("FMS", Self::Fms),
+        ("CIF", Self::Cif),
+        ("TARGET", Self::Target),
+        ("HUBBARD", Self::Hubbard),
+        ("UNFREEZEF", Self::Unfreezef),
+        ("RIXS", Self::Rixs),
+        ("XES", Self::Xes),
+        ("EGRID", Self::Egrid),
+        ("E_GRID", Self::EGrid),
+        ("K_GRID", Self::KGrid),
+        ("COMPTON", Self::Compton),
+        ("CGRID", Self::Cgrid),
+        ("RHOZZP", Self::Rhozzp),
+        ("OPCONS", Self::Opcons),
+        ("MPSE", Self::Mpse),
+        ("SFCONV", Self::Sfconv),
+        ("CORRECTIONS", Self::Corrections),
+        ("EXELFS", Self::Exelfs),
+        ("RECIPROCAL", Self::Reciprocal),
+        ("KMESH", Self::Kmesh),
+        ("STRFAC", Self::Strfac),
+        ("ELNES", Self::Elnes),
+        ("MAGIC", Self::Magic),
+        ("LATTICE", Self::Lattice),
+        ("CRPA", Self::Crpa),
+        ("VDOS", Self::Vdos),
+        ("STRETCHES", Self::Stretches),
+        ("SCREEN", Self::Screen),
+        ("BAND", Self::Band),
+        ("FULLSPECTRUM", Self::FullSpectrum),
+        ("MBAND", Self::Mband),
+        ("NKP", Self::Nkp),
+        ("IKPATH", Self::Ikpath),
+        ("FREEPROP", Self::Freeprop),
+        ("NER", Self::Ner),
+        ("NEI", Self::Nei),
+        ("MAXL", Self::Maxl),
+        ("IRRH", Self::Irrh),
+        ("IEND", Self::Iend),
+        ("LFXC", Self::Lfxc),
+        ("EMIN", Self::Emin),
+        ("EMAX", Self::Emax),
+        ("EIMAX", Self::Eimax),
+        ("ERMIN", Self::Ermin),
+        ("RFMS", Self::Rfms),
+        ("NRPTX0", Self::Nrptx0),
+        ("MSFCONV", Self::Msfconv),
+        ("WSIGK", Self::Wsigk),
+        ("ISPEC", Self::Ispec),
+        ("CFNAME", Self::Cfname),
+        ("MFULLSPECTRUM", Self::Mfullspectrum),
+        ("NRIXS", Self::Nrixs),
+        ("LJMAX", Self::Ljmax),
+        ("LDECMX", Self::Ldecmx),
+        ("POLARIZATION", Self::Polarization),
+        ("ELLIPTICITY", Self::Ellipticity),
+        ("SPIN", Self::Spin),
+    ];
+
+    /// Every keyword [`InputCardKind::from_keyword`] maps to a known kind.
+    pub const KEYWORDS: [&'static str; 74] = {
+        let mut keywords = [""; 74];
+        let mut index = 0;
+        while index < keywords.len() {
+            keywords[index] = Self::KEYWORD_KINDS[index].0;
+            index += 1;
         }
+        keywords
+    };
+
+    pub fn from_keyword(keyword: &str) -> Self {
+        Self::KEYWORD_KINDS
+            .iter()
+            .find(|(known, _)| *known == keyword)
+            .map_or_else(
+                || Self::Unknown(keyword.to_owned()),
+                |(_, kind)| kind.clone(),
+            )
     }
 
     pub fn applies_to_module(&self, module: ComputeModule) -> bool {
@@ -303,9 +458,7 @@ impl InputCardKind {
                 matches!(module, ComputeModule::Compton | ComputeModule::FullSpectrum)
             }
             Self::Crpa => matches!(module, ComputeModule::Crpa | ComputeModule::FullSpectrum),
-            Self::Rixs | Self::Xes => {
-                matches!(module, ComputeModule::Rixs | ComputeModule::FullSpectrum)
-            }
+            Self::Rixs => matches!(module, ComputeModule::Rixs | ComputeModule::FullSpectrum),
             Self::Elnes | Self::Exelfs => {
                 matches!(module, ComputeModule::Eels | ComputeModule::FullSpectrum)
             }
@@ -344,6 +497,17 @@ impl InputCardKind {
                 module == ComputeModule::Band
             }
             Self::FullSpectrum | Self::Mfullspectrum => module == ComputeModule::FullSpectrum,
+            Self::Nrixs | Self::Ljmax | Self::Ldecmx => matches!(
+                module,
+                ComputeModule::Rdinp | ComputeModule::Xsph | ComputeModule::Debye
+            ),
+            Self::Polarization | Self::Ellipticity | Self::Spin => matches!(
+                module,
+                ComputeModule::Rdinp
+                    | ComputeModule::Xsph
+                    | ComputeModule::Fms
+                    | ComputeModule::Debye
+            ),
             _ => true,
         }
     }
@@ -355,6 +519,30 @@ mod tests {
         ComputeModule, ComputeRequest, ExecutionMode, InputCard, InputCardKind, InputDeck,
     };
 
+    #[test]
+    fn every_listed_keyword_has_a_known_kind() {
+        for keyword in InputCardKind::KEYWORDS {
+            assert!(
+                !matches!(
+                    InputCardKind::from_keyword(keyword),
+                    InputCardKind::Unknown(_)
+                ),
+                "{keyword} should map to a known kind"
+            );
+        }
+    }
+
+    #[test]
+    fn every_listed_kind_has_exactly_one_keyword() {
+        let kinds = InputCardKind::KEYWORDS.map(InputCardKind::from_keyword);
+        for (index, kind) in kinds.iter().enumerate() {
+            assert!(
+                !kinds[..index].contains(kind),
+                "{kind:?} is listed under two keywords"
+            );
+        }
+    }
+
     #[test]
     fn compute_request_defaults_to_serial_mode() {
         let request = ComputeRequest::new("FX-001", ComputeModule::Rdinp, "feff.inp", "out");
@@ -362,6 +550,21 @@ mod tests {
         assert_eq!(request.module.to_string(), "RDINP");
     }
 
+    #[test]
+    fn compute_module_names_parse_ignoring_case() {
+        for module in ComputeModule::ALL {
+            assert_eq!(module.as_str().parse::<ComputeModule>().ok(), Some(module));
+        }
+        assert_eq!(
+            "self".parse::<ComputeModule>().ok(),
+            Some(ComputeModule::SelfEnergy)
+        );
+        let error = "ff2x"
+            .parse::<ComputeModule>()
+            .expect_err("command names are not modules");
+        assert_eq!(error.placeholder(), "INPUT.UNKNOWN_MODULE");
+    }
+
     #[test]
     fn input_deck_card_selection_is_module_aware() {
         let mut deck = InputDeck::default();
@@ -390,4 +593,33 @@ mod tests {
         assert_eq!(rixs_cards[0].keyword, "RIXS");
         assert_eq!(rixs_cards[1].keyword, "TITLE");
     }
+
+    #[test]
+    fn set_card_replaces_the_first_card_or_inserts_before_end() {
+        let mut deck = InputDeck::default();
+        deck.cards.push(InputCard::new(
+            "S02",
+            InputCardKind::S02,
+            vec!["1.0".into()],
+            1,
+        ));
+        deck.cards
+            .push(InputCard::new("END", InputCardKind::End, Vec::new(), 2));
+
+        deck.set_card("s02", vec!["0.9".to_string()]);
+        deck.set_card("rpath", vec!["5.0".to_string()])
+            .set_rows(vec![vec!["1".to_string(), "2".to_string()]]);
+
+        let keywords: Vec<&str> = deck
+            .cards
+            .iter()
+            .map(|card| card.keyword.as_str())
+            .collect();
+        assert_eq!(keywords, ["S02", "RPATH", "END"]);
+        assert_eq!(deck.cards[0].values, ["0.9"]);
+        assert_eq!(deck.cards[1].kind, InputCardKind::Rpath);
+        assert_eq!(deck.cards[1].continuations[0].raw, "1 2");
+        assert_eq!(deck.remove_cards("RPath"), 1);
+        assert_eq!(deck.remove_cards("RPATH"), 0);
+    }
 }
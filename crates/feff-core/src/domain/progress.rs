@@ -0,0 +1,185 @@
+use super::{ComputeModule, ComputeResult, FeffError};
+use std::fmt::{Debug, Formatter};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Structured progress reported while a module runs.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum ExecutionEvent {
+    ModuleStarted {
+        module: ComputeModule,
+    },
+    /// Energy point `index` (zero-based) of `total` is done. Threaded runs
+    /// report points as they finish, so indices may arrive out of order.
+    EnergyPoint {
+        module: ComputeModule,
+        index: usize,
+        total: usize,
+    },
+    ScfIteration {
+        iteration: usize,
+        residual: f64,
+    },
+    PathCount {
+        count: usize,
+    },
+    ArtifactWritten {
+        module: ComputeModule,
+        path: PathBuf,
+    },
+    ModuleFinished {
+        module: ComputeModule,
+    },
+}
+
+/// Receives events from module execution. Called from worker threads in
+/// threaded runs, so implementations must be `Sync`.
+pub trait ExecutionObserver: Send + Sync {
+    fn on_event(&self, event: &ExecutionEvent);
+}
+
+/// Shared flag a caller sets to stop a run. Modules check it between
+/// modules and between energy points and fail with `RUN.CANCELLED`.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Observer and cancellation token carried by a `ComputeRequest`. The
+/// default observes nothing and is never cancelled. Two controls are equal
+/// when they share the same observer and token.
+#[derive(Clone, Default)]
+pub struct ExecutionControl {
+    observer: Option<Arc<dyn ExecutionObserver>>,
+    cancellation: CancellationToken,
+}
+
+impl ExecutionControl {
+    pub fn with_observer(mut self, observer: Arc<dyn ExecutionObserver>) -> Self {
+        self.observer = Some(observer);
+        self
+    }
+
+    pub fn with_cancellation(mut self, cancellation: CancellationToken) -> Self {
+        self.cancellation = cancellation;
+        self
+    }
+
+    pub fn cancellation(&self) -> &CancellationToken {
+        &self.cancellation
+    }
+
+    pub fn emit(&self, event: ExecutionEvent) {
+        if let Some(observer) = &self.observer {
+            observer.on_event(&event);
+        }
+    }
+
+    /// Fails with `RUN.CANCELLED` once the token is cancelled.
+    pub fn checkpoint(&self, module: ComputeModule) -> ComputeResult<()> {
+        if self.cancellation.is_cancelled() {
+            return Err(FeffError::computation(
+                "RUN.CANCELLED",
+                format!("{} was cancelled", module),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Checks for cancellation and reports energy point `index` of `total`.
+    pub fn energy_point(
+        &self,
+        module: ComputeModule,
+        index: usize,
+        total: usize,
+    ) -> ComputeResult<()> {
+        self.checkpoint(module)?;
+        self.emit(ExecutionEvent::EnergyPoint {
+            module,
+            index,
+            total,
+        });
+        Ok(())
+    }
+}
+
+impl Debug for ExecutionControl {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ExecutionControl")
+            .field("observed", &self.observer.is_some())
+            .field("cancelled", &self.cancellation.is_cancelled())
+            .finish()
+    }
+}
+
+impl PartialEq for ExecutionControl {
+    fn eq(&self, other: &Self) -> bool {
+        let same_observer = match (&self.observer, &other.observer) {
+            (Some(left), Some(right)) => Arc::ptr_eq(left, right),
+            (None, None) => true,
+            _ => false,
+        };
+        same_observer && Arc::ptr_eq(&self.cancellation.0, &other.cancellation.0)
+    }
+}
+
+impl Eq for ExecutionControl {}
+
+#[cfg(test)]
+mod tests {
+    use super::{CancellationToken, ExecutionControl, ExecutionEvent, ExecutionObserver};
+    use crate::domain::ComputeModule;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Default)]
+    struct Recorder(Mutex<Vec<ExecutionEvent>>);
+
+    impl ExecutionObserver for Recorder {
+        fn on_event(&self, event: &ExecutionEvent) {
+            self.0.lock().expect("recorder").push(event.clone());
+        }
+    }
+
+    #[test]
+    fn control_forwards_events_and_stops_after_cancellation() {
+        let recorder = Arc::new(Recorder::default());
+        let token = CancellationToken::new();
+        let control = ExecutionControl::default()
+            .with_observer(recorder.clone())
+            .with_cancellation(token.clone());
+
+        control
+            .energy_point(ComputeModule::Fms, 0, 2)
+            .expect("uncancelled point should pass");
+        token.cancel();
+        let error = control
+            .energy_point(ComputeModule::Fms, 1, 2)
+            .expect_err("cancelled point should fail");
+        assert_eq!(error.placeholder(), "RUN.CANCELLED");
+        assert_eq!(
+            *recorder.0.lock().expect("recorder"),
+            vec![ExecutionEvent::EnergyPoint {
+                module: ComputeModule::Fms,
+                index: 0,
+                total: 2
+            }]
+        );
+
+        assert_eq!(control, control.clone());
+        assert_ne!(control, ExecutionControl::default());
+    }
+}
@@ -0,0 +1,319 @@
+use std::collections::BTreeMap;
+use std::fmt::{Debug, Formatter};
+use std::fs;
+use std::io;
+use std::ops::Deref;
+use std::path::{Component, Path, PathBuf};
+use std::sync::{Arc, RwLock};
+
+/// Where modules read their inputs and write their artifacts.
+///
+/// Paths are the same ones a filesystem run would use (`request.input_path`,
+/// its siblings, and files under `request.output_dir`), so a module does not
+/// know which store backs it.
+pub trait ArtifactStore: Send + Sync {
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>>;
+
+    fn write(&self, path: &Path, bytes: &[u8]) -> io::Result<()>;
+
+    fn is_file(&self, path: &Path) -> bool;
+
+    fn create_dir_all(&self, path: &Path) -> io::Result<()>;
+
+    /// Names of the files directly inside `directory`, sorted.
+    fn list_files(&self, directory: &Path) -> io::Result<Vec<String>>;
+
+    fn read_to_string(&self, path: &Path) -> io::Result<String> {
+        String::from_utf8(self.read(path)?)
+            .map_err(|source| io::Error::new(io::ErrorKind::InvalidData, source))
+    }
+}
+
+/// Reads and writes the real filesystem.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FileSystemStore;
+
+impl ArtifactStore for FileSystemStore {
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        fs::read(path)
+    }
+
+    fn write(&self, path: &Path, bytes: &[u8]) -> io::Result<()> {
+        fs::write(path, bytes)
+    }
+
+    fn is_file(&self, path: &Path) -> bool {
+        path.is_file()
+    }
+
+    fn create_dir_all(&self, path: &Path) -> io::Result<()> {
+        fs::create_dir_all(path)
+    }
+
+    fn list_files(&self, directory: &Path) -> io::Result<Vec<String>> {
+        let mut names = Vec::new();
+        for entry in fs::read_dir(directory)? {
+            let entry = entry?;
+            if entry.file_type()?.is_file() {
+                names.push(entry.file_name().to_string_lossy().into_owned());
+            }
+        }
+        names.sort();
+        Ok(names)
+    }
+}
+
+/// Keeps every file in memory, keyed by its lexically normalized path.
+/// Directories exist implicitly, so `create_dir_all` always succeeds.
+#[derive(Debug, Default)]
+pub struct MemoryStore {
+    files: RwLock<BTreeMap<PathBuf, Vec<u8>>>,
+}
+
+impl MemoryStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&self, path: impl AsRef<Path>, bytes: impl Into<Vec<u8>>) {
+        self.files
+            .write()
+            .expect("memory store lock")
+            .insert(normalize(path.as_ref()), bytes.into());
+    }
+
+    pub fn get(&self, path: impl AsRef<Path>) -> Option<Vec<u8>> {
+        self.files
+            .read()
+            .expect("memory store lock")
+            .get(&normalize(path.as_ref()))
+            .cloned()
+    }
+
+    /// Every stored path, sorted.
+    pub fn paths(&self) -> Vec<PathBuf> {
+        self.files
+            .read()
+            .expect("memory store lock")
+            .keys()
+            .cloned()
+            .collect()
+    }
+}
+
+impl ArtifactStore for MemoryStore {
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        self.get(path).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("'{}' is not in the memory store", path.display()),
+            )
+        })
+    }
+
+    fn write(&self, path: &Path, bytes: &[u8]) -> io::Result<()> {
+        self.insert(path, bytes);
+        Ok(())
+    }
+
+    fn is_file(&self, path: &Path) -> bool {
+        self.files
+            .read()
+            .expect("memory store lock")
+            .contains_key(&normalize(path))
+    }
+
+    fn create_dir_all(&self, _path: &Path) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn list_files(&self, directory: &Path) -> io::Result<Vec<String>> {
+        let directory = normalize(directory);
+        Ok(self
+            .files
+            .read()
+            .expect("memory store lock")
+            .keys()
+            .filter(|path| path.parent() == Some(directory.as_path()))
+            .filter_map(|path| path.file_name())
+            .map(|name| name.to_string_lossy().into_owned())
+            .collect())
+    }
+}
+
+/// Reads through to a read-only `base` store and keeps every write in
+/// memory, e.g. to run modules against staged inputs on disk without
+/// changing them.
+pub struct OverlayStore {
+    base: Arc<dyn ArtifactStore>,
+    upper: MemoryStore,
+}
+
+impl OverlayStore {
+    pub fn new(base: Arc<dyn ArtifactStore>) -> Self {
+        Self {
+            base,
+            upper: MemoryStore::new(),
+        }
+    }
+
+    /// Files written through the overlay.
+    pub fn written(&self) -> &MemoryStore {
+        &self.upper
+    }
+}
+
+impl Debug for OverlayStore {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OverlayStore")
+            .field("upper", &self.upper)
+            .finish_non_exhaustive()
+    }
+}
+
+impl ArtifactStore for OverlayStore {
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        match self.upper.get(path) {
+            Some(bytes) => Ok(bytes),
+            None => self.base.read(path),
+        }
+    }
+
+    fn write(&self, path: &Path, bytes: &[u8]) -> io::Result<()> {
+        self.upper.write(path, bytes)
+    }
+
+    fn is_file(&self, path: &Path) -> bool {
+        self.upper.is_file(path) || self.base.is_file(path)
+    }
+
+    fn create_dir_all(&self, _path: &Path) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn list_files(&self, directory: &Path) -> io::Result<Vec<String>> {
+        let mut names = match self.base.list_files(directory) {
+            Ok(names) => names,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Vec::new(),
+            Err(error) => return Err(error),
+        };
+        names.extend(self.upper.list_files(directory)?);
+        names.sort();
+        names.dedup();
+        Ok(names)
+    }
+}
+
+/// The store carried by a `ComputeRequest`. Defaults to the filesystem; two
+/// handles are equal when they share the same store.
+#[derive(Clone)]
+pub struct SharedStore(Arc<dyn ArtifactStore>);
+
+impl SharedStore {
+    pub fn new(store: Arc<dyn ArtifactStore>) -> Self {
+        Self(store)
+    }
+}
+
+impl Default for SharedStore {
+    fn default() -> Self {
+        Self(Arc::new(FileSystemStore))
+    }
+}
+
+impl Deref for SharedStore {
+    type Target = dyn ArtifactStore;
+
+    fn deref(&self) -> &Self::Target {
+        self.0.as_ref()
+    }
+}
+
+impl Debug for SharedStore {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("SharedStore")
+    }
+}
+
+impl PartialEq for SharedStore {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for SharedStore {}
+
+/// Drops `.` components and resolves `..` lexically so `out/./a.dat` and
+/// `out/a.dat` name the same memory entry.
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if !normalized.pop() {
+                    normalized.push(component);
+                }
+            }
+            other => normalized.push(other),
+        }
+    }
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ArtifactStore, FileSystemStore, MemoryStore, OverlayStore};
+    use std::fs;
+    use std::path::Path;
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    #[test]
+    fn memory_store_normalizes_paths_and_lists_direct_children() {
+        let store = MemoryStore::new();
+        store.insert("run/./feff.inp", "TITLE Cu\n");
+        store
+            .write(Path::new("run/out/../xmu.dat"), b"1 2\n")
+            .expect("write");
+        store.insert("run/sub/log.dat", "");
+
+        assert_eq!(
+            store
+                .read_to_string(Path::new("run/feff.inp"))
+                .expect("read"),
+            "TITLE Cu\n"
+        );
+        assert!(store.is_file(Path::new("run/xmu.dat")));
+        assert_eq!(
+            store.list_files(Path::new("run")).expect("list"),
+            vec!["feff.inp".to_string(), "xmu.dat".to_string()]
+        );
+        let missing = store
+            .read(Path::new("run/pot.bin"))
+            .expect_err("missing file");
+        assert_eq!(missing.kind(), std::io::ErrorKind::NotFound);
+    }
+
+    #[test]
+    fn overlay_reads_base_and_keeps_writes_in_memory() {
+        let temp = TempDir::new().expect("tempdir should be created");
+        let input = temp.path().join("pot.inp");
+        fs::write(&input, "base").expect("base input");
+        let overlay = OverlayStore::new(Arc::new(FileSystemStore));
+
+        overlay.write(&input, b"changed").expect("overlay write");
+        overlay
+            .write(&temp.path().join("pot.bin"), b"\x01")
+            .expect("overlay write");
+
+        assert_eq!(overlay.read(&input).expect("read"), b"changed");
+        assert_eq!(fs::read_to_string(&input).expect("base"), "base");
+        assert!(!temp.path().join("pot.bin").exists());
+        assert_eq!(
+            overlay.list_files(temp.path()).expect("list"),
+            vec!["pot.bin".to_string(), "pot.inp".to_string()]
+        );
+        assert_eq!(overlay.written().paths().len(), 2);
+    }
+}
@@ -1,5 +1,11 @@
+pub mod calculation;
 pub mod domain;
 pub mod modules;
 pub mod numerics;
 pub mod parser;
 pub mod support;
+
+pub use calculation::{
+    Calculation, CalculationResults, ChiSpectrum, LocalDensityOfStates, ScatteringPath,
+    Spectroscopy, Spectrum,
+};
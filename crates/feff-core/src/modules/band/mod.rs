@@ -1,9 +1,8 @@
 mod model;
 mod parser;
 
-use super::ModuleExecutor;
-use crate::domain::{ComputeArtifact, ComputeRequest, ComputeResult, FeffError};
-use std::fs;
+use super::{ModuleContract, ModuleExecutor};
+use crate::domain::{ComputeArtifact, ComputeModule, ComputeRequest, ComputeResult, FeffError};
 
 use model::BandModel;
 use parser::{
@@ -23,6 +22,18 @@ pub struct BandContract {
     pub expected_outputs: Vec<ComputeArtifact>,
 }
 
+impl ModuleContract for BandContract {
+    const MODULE: ComputeModule = ComputeModule::Band;
+
+    fn required_input_names() -> Vec<&'static str> {
+        BAND_REQUIRED_INPUTS.to_vec()
+    }
+
+    fn output_names() -> Vec<&'static str> {
+        BAND_REQUIRED_OUTPUTS.to_vec()
+    }
+}
+
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub struct BandModule;
 
@@ -30,8 +41,8 @@ impl BandModule {
     pub fn contract_for_request(&self, request: &ComputeRequest) -> ComputeResult<BandContract> {
         validate_request_shape(request)?;
         Ok(BandContract {
-            required_inputs: artifact_list(&BAND_REQUIRED_INPUTS),
-            expected_outputs: artifact_list(&BAND_REQUIRED_OUTPUTS),
+            required_inputs: artifact_list(&BandContract::required_input_names()),
+            expected_outputs: artifact_list(&BandContract::output_names()),
         })
     }
 }
@@ -41,16 +52,23 @@ impl ModuleExecutor for BandModule {
         validate_request_shape(request)?;
         let input_dir = input_parent_dir(request)?;
 
-        let band_source = read_input_source(&request.input_path, BAND_REQUIRED_INPUTS[0])?;
+        let band_source = read_input_source(
+            &*request.store,
+            &request.input_path,
+            BAND_REQUIRED_INPUTS[0],
+        )?;
         let geom_source = read_input_source(
+            &*request.store,
             &input_dir.join(BAND_REQUIRED_INPUTS[1]),
             BAND_REQUIRED_INPUTS[1],
         )?;
         let global_source = read_input_source(
+            &*request.store,
             &input_dir.join(BAND_REQUIRED_INPUTS[2]),
             BAND_REQUIRED_INPUTS[2],
         )?;
         let phase_bytes = read_input_bytes(
+            &*request.store,
             &input_dir.join(BAND_REQUIRED_INPUTS[3]),
             BAND_REQUIRED_INPUTS[3],
         )?;
@@ -64,21 +82,24 @@ impl ModuleExecutor for BandModule {
         )?;
         let outputs = artifact_list(&BAND_REQUIRED_OUTPUTS);
 
-        fs::create_dir_all(&request.output_dir).map_err(|source| {
-            FeffError::io_system(
-                "IO.BAND_OUTPUT_DIRECTORY",
-                format!(
-                    "failed to create BAND output directory '{}': {}",
-                    request.output_dir.display(),
-                    source
-                ),
-            )
-        })?;
+        request
+            .store
+            .create_dir_all(&request.output_dir)
+            .map_err(|source| {
+                FeffError::io_system(
+                    "IO.BAND_OUTPUT_DIRECTORY",
+                    format!(
+                        "failed to create BAND output directory '{}': {}",
+                        request.output_dir.display(),
+                        source
+                    ),
+                )
+            })?;
 
         for artifact in &outputs {
             let output_path = request.output_dir.join(&artifact.relative_path);
             if let Some(parent) = output_path.parent() {
-                fs::create_dir_all(parent).map_err(|source| {
+                request.store.create_dir_all(parent).map_err(|source| {
                     FeffError::io_system(
                         "IO.BAND_OUTPUT_DIRECTORY",
                         format!(
@@ -91,7 +112,7 @@ impl ModuleExecutor for BandModule {
             }
 
             let artifact_name = artifact.relative_path.to_string_lossy().replace('\\', "/");
-            model.write_artifact(&artifact_name, &output_path)?;
+            model.write_artifact(&*request.store, &artifact_name, &output_path)?;
         }
 
         Ok(outputs)
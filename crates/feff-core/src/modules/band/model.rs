@@ -2,9 +2,9 @@ use super::parser::{
     BandControlInput, GeomBandInput, GlobalBandInput, PhaseBandInput, parse_band_source,
     parse_geom_source, parse_global_source, parse_phase_source,
 };
-use crate::domain::{ComputeResult, FeffError};
+use crate::domain::{ArtifactStore, ComputeResult, FeffError};
 use crate::modules::helpers::kspace_workflow_coupling;
-use crate::modules::serialization::{format_fixed_f64, write_text_artifact};
+use crate::modules::serialization::{format_fixed_f64, write_text_artifact_to};
 use std::f64::consts::PI;
 use std::path::Path;
 
@@ -132,11 +132,26 @@ impl BandModel {
 
     pub(super) fn write_artifact(
         &self,
+        store: &dyn ArtifactStore,
         artifact_name: &str,
         output_path: &Path,
     ) -> ComputeResult<()> {
         match artifact_name {
-            "bandstructure.dat" => write_text_artifact(output_path, &self.render_bandstructure())
+            "bandstructure.dat" => {
+                write_text_artifact_to(store, output_path, &self.render_bandstructure()).map_err(
+                    |source| {
+                        FeffError::io_system(
+                            "IO.BAND_OUTPUT_WRITE",
+                            format!(
+                                "failed to write BAND artifact '{}': {}",
+                                output_path.display(),
+                                source
+                            ),
+                        )
+                    },
+                )
+            }
+            "logband.dat" => write_text_artifact_to(store, output_path, &self.render_logband())
                 .map_err(|source| {
                     FeffError::io_system(
                         "IO.BAND_OUTPUT_WRITE",
@@ -147,18 +162,6 @@ impl BandModel {
                         ),
                     )
                 }),
-            "logband.dat" => {
-                write_text_artifact(output_path, &self.render_logband()).map_err(|source| {
-                    FeffError::io_system(
-                        "IO.BAND_OUTPUT_WRITE",
-                        format!(
-                            "failed to write BAND artifact '{}': {}",
-                            output_path.display(),
-                            source
-                        ),
-                    )
-                })
-            }
             other => Err(FeffError::internal(
                 "SYS.BAND_OUTPUT_CONTRACT",
                 format!("unsupported BAND output artifact '{}'", other),
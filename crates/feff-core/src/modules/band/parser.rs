@@ -1,8 +1,9 @@
 use super::BAND_REQUIRED_INPUTS;
-use crate::domain::{ComputeArtifact, ComputeModule, ComputeRequest, ComputeResult, FeffError};
+use crate::domain::{
+    ArtifactStore, ComputeArtifact, ComputeModule, ComputeRequest, ComputeResult, FeffError,
+};
 use crate::modules::xsph::XSPH_PHASE_BINARY_MAGIC;
 use std::f64::consts::PI;
-use std::fs;
 use std::path::Path;
 
 #[derive(Debug, Clone, Copy)]
@@ -103,8 +104,12 @@ pub(super) fn input_parent_dir(request: &ComputeRequest) -> ComputeResult<&Path>
     })
 }
 
-pub(super) fn read_input_source(path: &Path, artifact_name: &str) -> ComputeResult<String> {
-    fs::read_to_string(path).map_err(|source| {
+pub(super) fn read_input_source(
+    store: &dyn ArtifactStore,
+    path: &Path,
+    artifact_name: &str,
+) -> ComputeResult<String> {
+    store.read_to_string(path).map_err(|source| {
         FeffError::io_system(
             "IO.BAND_INPUT_READ",
             format!(
@@ -117,8 +122,12 @@ pub(super) fn read_input_source(path: &Path, artifact_name: &str) -> ComputeResu
     })
 }
 
-pub(super) fn read_input_bytes(path: &Path, artifact_name: &str) -> ComputeResult<Vec<u8>> {
-    fs::read(path).map_err(|source| {
+pub(super) fn read_input_bytes(
+    store: &dyn ArtifactStore,
+    path: &Path,
+    artifact_name: &str,
+) -> ComputeResult<Vec<u8>> {
+    store.read(path).map_err(|source| {
         FeffError::io_system(
             "IO.BAND_INPUT_READ",
             format!(
@@ -342,7 +351,7 @@ pub(super) fn parse_phase_source(fixture_id: &str, bytes: &[u8]) -> ComputeResul
 fn band_parse_error(fixture_id: &str, message: impl Into<String>) -> FeffError {
     FeffError::computation(
         "RUN.BAND_INPUT_PARSE",
-        format!("fixture '{}': {}", fixture_id, message.into()),
+        format!("run '{}': {}", fixture_id, message.into()),
     )
 }
 
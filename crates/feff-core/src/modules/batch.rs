@@ -0,0 +1,373 @@
+use super::serialization::write_text_artifact;
+use crate::domain::{ComputeArtifact, ComputeResult, FeffError, FeffErrorCategory};
+use globset::GlobBuilder;
+use serde::Serialize;
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+pub const BATCH_SUMMARY_JSON: &str = "batch-summary.json";
+pub const BATCH_SUMMARY_CSV: &str = "batch-summary.csv";
+/// Combined stdout and stderr of a run, written into its directory.
+pub const BATCH_JOB_LOG: &str = "feff10-batch.log";
+
+const RUN_DECK: &str = "feff.inp";
+
+/// Run directories named by `patterns`, resolved against `base`.
+///
+/// A pattern is a run directory, a `feff.inp` path, or a glob over either
+/// (`*` stays within one path component, `**` crosses them). Globs skip
+/// matches without a `feff.inp`; a literal path without one is an error.
+/// Directories are returned sorted and without duplicates.
+pub fn resolve_batch_directories(base: &Path, patterns: &[String]) -> ComputeResult<Vec<PathBuf>> {
+    let mut directories = BTreeSet::new();
+    for pattern in patterns {
+        let path = base.join(pattern);
+        if !has_glob_meta(pattern) {
+            directories.insert(
+                run_directory(&path).ok_or_else(|| {
+                    batch_error(format!("'{}' has no {}", path.display(), RUN_DECK))
+                })?,
+            );
+            continue;
+        }
+
+        let matcher = GlobBuilder::new(&path.to_string_lossy())
+            .literal_separator(true)
+            .build()
+            .map_err(|source| batch_error(format!("invalid pattern '{}': {}", pattern, source)))?
+            .compile_matcher();
+        let mut matched = false;
+        let mut pending = vec![glob_root(&path)];
+        while let Some(directory) = pending.pop() {
+            let Ok(entries) = fs::read_dir(&directory) else {
+                continue;
+            };
+            for entry in entries.flatten() {
+                let entry_path = entry.path();
+                let is_dir = entry.file_type().is_ok_and(|kind| kind.is_dir());
+                if is_dir {
+                    pending.push(entry_path.clone());
+                }
+                if matcher.is_match(&entry_path)
+                    && let Some(run_dir) = run_directory(&entry_path)
+                {
+                    directories.insert(run_dir);
+                    matched = true;
+                }
+            }
+        }
+        if !matched {
+            return Err(batch_error(format!(
+                "pattern '{}' matched no directory with a {}",
+                pattern, RUN_DECK
+            )));
+        }
+    }
+    Ok(directories.into_iter().collect())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BatchJobStatus {
+    Completed,
+    /// A previous run is complete and fresh, so the directory was not re-run.
+    Skipped,
+    Failed,
+    TimedOut,
+}
+
+impl BatchJobStatus {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Completed => "completed",
+            Self::Skipped => "skipped",
+            Self::Failed => "failed",
+            Self::TimedOut => "timed_out",
+        }
+    }
+}
+
+/// Outcome of one run directory, with its exit code mapped through
+/// `FeffErrorCategory`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BatchJobResult {
+    pub directory: String,
+    pub status: BatchJobStatus,
+    pub exit_code: i32,
+    pub category: &'static str,
+    pub legacy_class: &'static str,
+    pub attempts: u32,
+    pub seconds: f64,
+    pub message: String,
+}
+
+impl BatchJobResult {
+    /// Result of a run process that exited with `exit_code`. Codes outside the
+    /// category mapping (a crash or a signal) count as internal errors.
+    pub fn exited(
+        directory: &Path,
+        exit_code: i32,
+        attempts: u32,
+        seconds: f64,
+        message: impl Into<String>,
+    ) -> Self {
+        let category = FeffErrorCategory::from_exit_code(exit_code)
+            .unwrap_or(FeffErrorCategory::InternalError);
+        Self {
+            directory: directory.display().to_string(),
+            status: if category == FeffErrorCategory::Success {
+                BatchJobStatus::Completed
+            } else {
+                BatchJobStatus::Failed
+            },
+            exit_code,
+            category: category.rust_category(),
+            legacy_class: category.legacy_class(),
+            attempts,
+            seconds,
+            message: message.into(),
+        }
+    }
+
+    pub fn skipped(directory: &Path) -> Self {
+        Self {
+            status: BatchJobStatus::Skipped,
+            attempts: 0,
+            ..Self::exited(directory, 0, 0, 0.0, "previous run is complete")
+        }
+    }
+
+    /// A run killed after `timeout_seconds`, reported as a computation error.
+    pub fn timed_out(directory: &Path, attempts: u32, seconds: f64, timeout_seconds: u64) -> Self {
+        let category = FeffErrorCategory::ComputationError;
+        Self {
+            status: BatchJobStatus::TimedOut,
+            ..Self::exited(
+                directory,
+                category.exit_code(),
+                attempts,
+                seconds,
+                format!("timed out after {} s", timeout_seconds),
+            )
+        }
+    }
+
+    pub fn succeeded(&self) -> bool {
+        matches!(
+            self.status,
+            BatchJobStatus::Completed | BatchJobStatus::Skipped
+        )
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct BatchSummary<'a> {
+    total: usize,
+    completed: usize,
+    skipped: usize,
+    failed: usize,
+    timed_out: usize,
+    jobs: &'a [BatchJobResult],
+}
+
+/// Writes `batch-summary.json` and `batch-summary.csv` into `root`.
+pub fn write_batch_summary(
+    root: &Path,
+    results: &[BatchJobResult],
+) -> ComputeResult<Vec<ComputeArtifact>> {
+    let count = |status: BatchJobStatus| {
+        results
+            .iter()
+            .filter(|result| result.status == status)
+            .count()
+    };
+    let summary = BatchSummary {
+        total: results.len(),
+        completed: count(BatchJobStatus::Completed),
+        skipped: count(BatchJobStatus::Skipped),
+        failed: count(BatchJobStatus::Failed),
+        timed_out: count(BatchJobStatus::TimedOut),
+        jobs: results,
+    };
+    let json = serde_json::to_string_pretty(&summary).map_err(|source| {
+        FeffError::internal(
+            "SYS.BATCH_SUMMARY",
+            format!("failed to serialize batch summary: {}", source),
+        )
+    })?;
+
+    let mut csv =
+        String::from("directory,status,exit_code,category,legacy_class,attempts,seconds,message\n");
+    for result in results {
+        csv.push_str(&format!(
+            "{},{},{},{},{},{},{:.3},{}\n",
+            csv_field(&result.directory),
+            result.status.as_str(),
+            result.exit_code,
+            result.category,
+            result.legacy_class,
+            result.attempts,
+            result.seconds,
+            csv_field(&result.message)
+        ));
+    }
+
+    fs::create_dir_all(root).map_err(|source| summary_write_error(root, source))?;
+    for (name, content) in [(BATCH_SUMMARY_JSON, json), (BATCH_SUMMARY_CSV, csv)] {
+        let path = root.join(name);
+        write_text_artifact(&path, &content)
+            .map_err(|source| summary_write_error(&path, source))?;
+    }
+    Ok(vec![
+        ComputeArtifact::new(BATCH_SUMMARY_JSON),
+        ComputeArtifact::new(BATCH_SUMMARY_CSV),
+    ])
+}
+
+fn run_directory(path: &Path) -> Option<PathBuf> {
+    if path.is_dir() && path.join(RUN_DECK).is_file() {
+        return Some(path.to_path_buf());
+    }
+    if path.is_file() && path.file_name().is_some_and(|name| name == RUN_DECK) {
+        return path.parent().map(Path::to_path_buf);
+    }
+    None
+}
+
+fn has_glob_meta(pattern: &str) -> bool {
+    pattern.contains(['*', '?', '[', '{'])
+}
+
+/// Longest leading part of `path` without glob metacharacters.
+fn glob_root(path: &Path) -> PathBuf {
+    let mut root = PathBuf::new();
+    for component in path.components() {
+        if let Component::Normal(part) = component
+            && has_glob_meta(&part.to_string_lossy())
+        {
+            break;
+        }
+        root.push(component);
+    }
+    root
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\"").replace('\n', " "))
+    } else {
+        value.to_string()
+    }
+}
+
+fn batch_error(message: String) -> FeffError {
+    FeffError::input_validation("INPUT.BATCH_PATTERN", message)
+}
+
+fn summary_write_error(path: &Path, source: std::io::Error) -> FeffError {
+    FeffError::io_system(
+        "IO.BATCH_SUMMARY",
+        format!(
+            "failed to write batch summary '{}': {}",
+            path.display(),
+            source
+        ),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        BATCH_SUMMARY_CSV, BATCH_SUMMARY_JSON, BatchJobResult, BatchJobStatus,
+        resolve_batch_directories, write_batch_summary,
+    };
+    use std::fs;
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    fn stage_run(root: &Path, relative: &str) {
+        let dir = root.join(relative);
+        fs::create_dir_all(&dir).expect("run dir should be created");
+        fs::write(dir.join("feff.inp"), "TITLE run\n").expect("deck should be written");
+    }
+
+    #[test]
+    fn patterns_resolve_to_sorted_run_directories() {
+        let temp = TempDir::new().expect("tempdir should be created");
+        let root = temp.path();
+        for relative in [
+            "md/snap_02",
+            "md/snap_01",
+            "md/deep/snap_03",
+            "doping/cfg_a",
+        ] {
+            stage_run(root, relative);
+        }
+        fs::create_dir_all(root.join("md/empty")).expect("dir without deck");
+
+        let shallow = resolve_batch_directories(root, &["md/*".to_string()]).expect("glob");
+        assert_eq!(
+            shallow,
+            vec![root.join("md/snap_01"), root.join("md/snap_02")]
+        );
+
+        let deep = resolve_batch_directories(
+            root,
+            &[
+                "md/**/feff.inp".to_string(),
+                "doping/cfg_a".to_string(),
+                "md/snap_01".to_string(),
+            ],
+        )
+        .expect("patterns");
+        assert_eq!(
+            deep,
+            vec![
+                root.join("doping/cfg_a"),
+                root.join("md/deep/snap_03"),
+                root.join("md/snap_01"),
+                root.join("md/snap_02"),
+            ]
+        );
+
+        let error = resolve_batch_directories(root, &["md/empty".to_string()])
+            .expect_err("a literal directory needs a deck");
+        assert_eq!(error.placeholder(), "INPUT.BATCH_PATTERN");
+        assert!(resolve_batch_directories(root, &["none/*".to_string()]).is_err());
+    }
+
+    #[test]
+    fn summary_maps_exit_codes_to_categories() {
+        let temp = TempDir::new().expect("tempdir should be created");
+        let results = vec![
+            BatchJobResult::exited(Path::new("a"), 0, 1, 1.5, ""),
+            BatchJobResult::exited(Path::new("b"), 2, 1, 0.1, "ERROR: [INPUT.X] bad, card"),
+            BatchJobResult::skipped(Path::new("c")),
+            BatchJobResult::timed_out(Path::new("d"), 2, 20.0, 10),
+            BatchJobResult::exited(Path::new("e"), 137, 1, 0.2, "killed"),
+        ];
+        assert_eq!(results[1].category, "InputValidationError");
+        assert_eq!(results[3].status, BatchJobStatus::TimedOut);
+        assert_eq!(results[4].legacy_class, "SYS_FATAL");
+
+        write_batch_summary(temp.path(), &results).expect("summary should be written");
+        let json: serde_json::Value = serde_json::from_str(
+            &fs::read_to_string(temp.path().join(BATCH_SUMMARY_JSON)).expect("json"),
+        )
+        .expect("summary json should parse");
+        assert_eq!(json["total"], 5);
+        assert_eq!(json["completed"], 1);
+        assert_eq!(json["failed"], 2);
+        assert_eq!(json["jobs"][3]["status"], "timed_out");
+        assert_eq!(json["jobs"][3]["exit_code"], 4);
+
+        let csv = fs::read_to_string(temp.path().join(BATCH_SUMMARY_CSV)).expect("csv");
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 6);
+        assert_eq!(
+            lines[2],
+            "b,failed,2,InputValidationError,INPUT_FATAL,1,0.100,\"ERROR: [INPUT.X] bad, card\""
+        );
+    }
+}
@@ -0,0 +1,482 @@
+use super::pipeline::{ModuleArtifacts, PipelinePlan};
+use super::serialization::write_text_artifact;
+use super::{ensure_runtime_engines_available, execute_runtime_module, runtime_request};
+use crate::domain::{
+    ComputeArtifact, ComputeModule, ComputeResult, ExecutionControl, ExecutionMode, FeffError,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::Path;
+
+/// Run manifest written next to the outputs of a cached run.
+pub const RUN_MANIFEST_OUTPUT: &str = "feff10-run.json";
+
+const ENGINE: &str = concat!("feff10-rs ", env!("CARGO_PKG_VERSION"));
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// Fingerprints of every module run in one directory.
+///
+/// A stage records the content hash of each input artifact it read and each
+/// output it wrote. It is fresh while those inputs are unchanged and its
+/// outputs are still on disk as written. Cards reach modules through the
+/// `*.inp` files RDINP writes, so editing `S02` only changes `ff2x.inp` and
+/// leaves POT and XSPH fresh.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RunManifest {
+    pub engine: String,
+    pub stages: BTreeMap<String, StageRecord>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StageRecord {
+    pub label: String,
+    pub inputs: BTreeMap<String, String>,
+    pub outputs: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StageStatus {
+    Fresh,
+    Stale(String),
+}
+
+impl RunManifest {
+    /// Loads the manifest in `directory`. A missing manifest, or one written
+    /// by another engine version, is empty so every stage runs.
+    pub fn load(directory: &Path) -> ComputeResult<Self> {
+        let path = directory.join(RUN_MANIFEST_OUTPUT);
+        let content = match fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(source) if source.kind() == std::io::ErrorKind::NotFound => {
+                return Ok(Self::empty());
+            }
+            Err(source) => {
+                return Err(FeffError::io_system(
+                    "IO.RUN_MANIFEST",
+                    format!(
+                        "failed to read run manifest '{}': {}",
+                        path.display(),
+                        source
+                    ),
+                ));
+            }
+        };
+        let manifest: Self = serde_json::from_str(&content).map_err(|source| {
+            FeffError::input_validation(
+                "INPUT.RUN_MANIFEST",
+                format!(
+                    "failed to parse run manifest '{}': {}; delete it to rebuild every stage",
+                    path.display(),
+                    source
+                ),
+            )
+        })?;
+        Ok(if manifest.engine == ENGINE {
+            manifest
+        } else {
+            Self::empty()
+        })
+    }
+
+    pub fn save(&self, directory: &Path) -> ComputeResult<()> {
+        let path = directory.join(RUN_MANIFEST_OUTPUT);
+        let json = serde_json::to_string_pretty(self).map_err(|source| {
+            FeffError::internal(
+                "SYS.RUN_MANIFEST",
+                format!("failed to serialize run manifest: {}", source),
+            )
+        })?;
+        write_text_artifact(&path, &json).map_err(|source| {
+            FeffError::io_system(
+                "IO.RUN_MANIFEST",
+                format!(
+                    "failed to write run manifest '{}': {}",
+                    path.display(),
+                    source
+                ),
+            )
+        })
+    }
+
+    /// Whether `module` can be skipped in `directory` when run as `label`.
+    pub fn stage_status(
+        &self,
+        directory: &Path,
+        module: ComputeModule,
+        label: &str,
+    ) -> StageStatus {
+        let Some(record) = self.stages.get(module.as_str()) else {
+            return StageStatus::Stale("not run yet".to_string());
+        };
+        if record.label != label {
+            return StageStatus::Stale(format!("last run as '{}'", record.label));
+        }
+        let inputs = input_hashes(directory, module);
+        for (name, hash) in &inputs {
+            match record.inputs.get(name) {
+                None => return StageStatus::Stale(format!("new input {}", name)),
+                Some(recorded) if recorded != hash => {
+                    return StageStatus::Stale(format!("{} changed", name));
+                }
+                Some(_) => {}
+            }
+        }
+        if let Some(name) = record
+            .inputs
+            .keys()
+            .find(|name| !inputs.contains_key(*name))
+        {
+            return StageStatus::Stale(format!("{} was removed", name));
+        }
+        for (name, hash) in &record.outputs {
+            match hash_file(&directory.join(name)) {
+                None => return StageStatus::Stale(format!("output {} is missing", name)),
+                Some(current) if current != *hash => {
+                    return StageStatus::Stale(format!("output {} was modified", name));
+                }
+                Some(_) => {}
+            }
+        }
+        StageStatus::Fresh
+    }
+
+    /// Records a completed run of `module` that wrote `outputs`.
+    pub fn record(
+        &mut self,
+        directory: &Path,
+        module: ComputeModule,
+        label: &str,
+        outputs: &[ComputeArtifact],
+    ) {
+        let outputs = outputs
+            .iter()
+            .filter_map(|artifact| {
+                let name = artifact.relative_path.to_string_lossy().replace('\\', "/");
+                hash_file(&directory.join(&artifact.relative_path)).map(|hash| (name, hash))
+            })
+            .collect();
+        self.stages.insert(
+            module.as_str().to_string(),
+            StageRecord {
+                label: label.to_string(),
+                inputs: input_hashes(directory, module),
+                outputs,
+            },
+        );
+    }
+
+    /// Runs `module` through `run` unless its stage is fresh and `force` is
+    /// unset, then records its outputs and saves the manifest, so an
+    /// interrupted chain resumes where it stopped. Returns whether it ran.
+    pub fn run_stage(
+        &mut self,
+        directory: &Path,
+        module: ComputeModule,
+        label: &str,
+        force: bool,
+        run: impl FnOnce() -> ComputeResult<Vec<ComputeArtifact>>,
+    ) -> ComputeResult<bool> {
+        if !force && self.stage_status(directory, module, label) == StageStatus::Fresh {
+            return Ok(false);
+        }
+        let outputs = run()?;
+        self.record(directory, module, label, &outputs);
+        self.save(directory)?;
+        Ok(true)
+    }
+
+    /// Status of every planned step. A stage whose producer is stale is stale
+    /// too, since its inputs will change once the producer re-runs.
+    pub fn plan_status(
+        &self,
+        directory: &Path,
+        plan: &PipelinePlan,
+        label: &str,
+    ) -> Vec<(ComputeModule, StageStatus)> {
+        let mut stale: BTreeSet<&'static str> = BTreeSet::new();
+        plan.steps()
+            .iter()
+            .map(|step| {
+                let upstream = step
+                    .depends_on
+                    .iter()
+                    .find(|(producer, _)| stale.contains(producer.as_str()));
+                let status = match upstream {
+                    Some((producer, _)) => StageStatus::Stale(format!("{} is stale", producer)),
+                    None => self.stage_status(directory, step.module, label),
+                };
+                if status != StageStatus::Fresh {
+                    stale.insert(step.module.as_str());
+                }
+                (step.module, status)
+            })
+            .collect()
+    }
+
+    fn empty() -> Self {
+        Self {
+            engine: ENGINE.to_string(),
+            stages: BTreeMap::new(),
+        }
+    }
+}
+
+impl Default for RunManifest {
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
+/// Runs the modules `feff.inp` in `directory` enables, as `feff10-rs run`
+/// does: stages the manifest records as fresh are skipped, and the directory
+/// name labels the run in module outputs. Returns the planned modules.
+pub fn run_deck_directory(
+    directory: &Path,
+    execution_mode: ExecutionMode,
+    control: &ExecutionControl,
+) -> ComputeResult<Vec<ComputeModule>> {
+    run_deck_directory_with(
+        directory,
+        &[],
+        |module, label| {
+            let request = runtime_request(label, module, directory)?
+                .with_execution_mode(execution_mode)
+                .with_control(control.clone());
+            execute_runtime_module(module, &request)
+        },
+        |_| {},
+    )
+}
+
+/// [`run_deck_directory`] with the caller executing each stage: `run` gets the
+/// module and the directory label, `skipped` hears about fresh stages, and
+/// modules in `force` run even when fresh.
+pub fn run_deck_directory_with(
+    directory: &Path,
+    force: &[ComputeModule],
+    run: impl FnMut(ComputeModule, &str) -> ComputeResult<Vec<ComputeArtifact>>,
+    skipped: impl FnMut(ComputeModule),
+) -> ComputeResult<Vec<ComputeModule>> {
+    let modules = PipelinePlan::for_directory(directory)?.modules();
+    ensure_runtime_engines_available(&modules)?;
+    let label = directory_label(directory);
+    run_directory_stages(directory, &label, &modules, force, run, skipped)?;
+    Ok(modules)
+}
+
+/// Runs `modules` in order in `directory` as `label` through `run`. Stages the
+/// run manifest records as fresh are skipped and reported to `skipped` unless
+/// they are in `force`.
+pub fn run_directory_stages(
+    directory: &Path,
+    label: &str,
+    modules: &[ComputeModule],
+    force: &[ComputeModule],
+    mut run: impl FnMut(ComputeModule, &str) -> ComputeResult<Vec<ComputeArtifact>>,
+    mut skipped: impl FnMut(ComputeModule),
+) -> ComputeResult<()> {
+    let mut manifest = RunManifest::load(directory)?;
+    for module in modules {
+        let ran = manifest.run_stage(directory, *module, label, force.contains(module), || {
+            run(*module, label)
+        })?;
+        if !ran {
+            skipped(*module);
+        }
+    }
+    Ok(())
+}
+
+/// The last component of `directory`'s canonical path, which labels a
+/// directory run in module outputs; `feff` when it has none.
+pub fn directory_label(directory: &Path) -> String {
+    directory
+        .canonicalize()
+        .ok()
+        .and_then(|path| {
+            path.file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+        })
+        .unwrap_or_else(|| "feff".to_string())
+}
+
+/// Content hashes of the contract inputs of `module` present in `directory`.
+fn input_hashes(directory: &Path, module: ComputeModule) -> BTreeMap<String, String> {
+    let artifacts = ModuleArtifacts::for_module(module);
+    artifacts
+        .required_inputs
+        .iter()
+        .chain(&artifacts.any_of_inputs)
+        .chain(&artifacts.optional_inputs)
+        .filter_map(|name| hash_file(&directory.join(name)).map(|hash| (name.to_string(), hash)))
+        .collect()
+}
+
+fn hash_file(path: &Path) -> Option<String> {
+    let bytes = fs::read(path).ok()?;
+    let hash = bytes.iter().fold(FNV_OFFSET_BASIS, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(FNV_PRIME)
+    });
+    Some(format!("{:016x}", hash))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{RUN_MANIFEST_OUTPUT, RunManifest, StageStatus, run_directory_stages};
+    use crate::domain::{ComputeArtifact, ComputeModule};
+    use crate::modules::pipeline::PipelinePlan;
+    use std::collections::BTreeSet;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn write(dir: &std::path::Path, name: &str, content: &str) {
+        fs::write(dir.join(name), content).expect("artifact should be written");
+    }
+
+    #[test]
+    fn stages_go_stale_when_inputs_or_outputs_change() {
+        let temp = TempDir::new().expect("tempdir should be created");
+        let dir = temp.path();
+        write(dir, "pot.inp", "pot");
+        write(dir, "geom.dat", "geom");
+        write(dir, "pot.bin", "potential");
+
+        let mut manifest = RunManifest::load(dir).expect("missing manifest loads empty");
+        assert_eq!(
+            manifest.stage_status(dir, ComputeModule::Pot, "Cu"),
+            StageStatus::Stale("not run yet".to_string())
+        );
+        manifest.record(
+            dir,
+            ComputeModule::Pot,
+            "Cu",
+            &[ComputeArtifact::new("pot.bin")],
+        );
+        manifest.save(dir).expect("manifest should save");
+        let manifest = RunManifest::load(dir).expect("manifest should load");
+        assert_eq!(
+            manifest.stage_status(dir, ComputeModule::Pot, "Cu"),
+            StageStatus::Fresh
+        );
+        assert!(dir.join(RUN_MANIFEST_OUTPUT).is_file());
+
+        write(dir, "pot.bin", "edited");
+        assert_eq!(
+            manifest.stage_status(dir, ComputeModule::Pot, "Cu"),
+            StageStatus::Stale("output pot.bin was modified".to_string())
+        );
+        write(dir, "pot.bin", "potential");
+        write(dir, "geom.dat", "moved atom");
+        assert_eq!(
+            manifest.stage_status(dir, ComputeModule::Pot, "Cu"),
+            StageStatus::Stale("geom.dat changed".to_string())
+        );
+    }
+
+    #[test]
+    fn run_stage_skips_fresh_stages_unless_forced() {
+        let temp = TempDir::new().expect("tempdir should be created");
+        let dir = temp.path();
+        write(dir, "pot.inp", "pot");
+        write(dir, "geom.dat", "geom");
+        let mut runs = 0;
+        let mut run = |force| {
+            let mut manifest = RunManifest::load(dir).expect("manifest");
+            manifest
+                .run_stage(dir, ComputeModule::Pot, "Cu", force, || {
+                    runs += 1;
+                    write(dir, "pot.bin", "potential");
+                    Ok(vec![ComputeArtifact::new("pot.bin")])
+                })
+                .expect("stage should run")
+        };
+
+        assert!(run(false));
+        assert!(!run(false));
+        assert!(run(true));
+        assert_eq!(runs, 2);
+    }
+
+    #[test]
+    fn directory_stages_report_fresh_stages_and_rerun_forced_ones() {
+        let temp = TempDir::new().expect("tempdir should be created");
+        let dir = temp.path();
+        write(dir, "pot.inp", "pot");
+        write(dir, "geom.dat", "geom");
+        let run_chain = |force: &[ComputeModule]| {
+            let mut ran = Vec::new();
+            let mut skipped = Vec::new();
+            run_directory_stages(
+                dir,
+                "Cu",
+                &[ComputeModule::Pot],
+                force,
+                |module, label| {
+                    ran.push((module, label.to_string()));
+                    write(dir, "pot.bin", "potential");
+                    Ok(vec![ComputeArtifact::new("pot.bin")])
+                },
+                |module| skipped.push(module),
+            )
+            .expect("stages should run");
+            (ran, skipped)
+        };
+
+        let (ran, skipped) = run_chain(&[]);
+        assert_eq!(ran, vec![(ComputeModule::Pot, "Cu".to_string())]);
+        assert!(skipped.is_empty());
+        let (ran, skipped) = run_chain(&[]);
+        assert!(ran.is_empty());
+        assert_eq!(skipped, vec![ComputeModule::Pot]);
+        let (ran, _) = run_chain(&[ComputeModule::Pot]);
+        assert_eq!(ran.len(), 1);
+    }
+
+    #[test]
+    fn plan_status_propagates_staleness_downstream() {
+        let temp = TempDir::new().expect("tempdir should be created");
+        let dir = temp.path();
+        for name in ["pot.inp", "geom.dat", "xsph.inp", "global.inp", "pot.bin"] {
+            write(dir, name, name);
+        }
+        let plan = PipelinePlan::for_modules(
+            &[ComputeModule::Pot, ComputeModule::Xsph],
+            &["pot.inp", "geom.dat", "xsph.inp", "global.inp"]
+                .iter()
+                .map(|name| name.to_string())
+                .collect::<BTreeSet<_>>(),
+        )
+        .expect("plan");
+        let mut manifest = RunManifest::load(dir).expect("manifest");
+        manifest.record(
+            dir,
+            ComputeModule::Pot,
+            "Cu",
+            &[ComputeArtifact::new("pot.bin")],
+        );
+        manifest.record(dir, ComputeModule::Xsph, "Cu", &[]);
+        assert!(
+            manifest
+                .plan_status(dir, &plan, "Cu")
+                .iter()
+                .all(|(_, status)| *status == StageStatus::Fresh)
+        );
+
+        write(dir, "pot.inp", "edited");
+        assert_eq!(
+            manifest.plan_status(dir, &plan, "Cu"),
+            vec![
+                (
+                    ComputeModule::Pot,
+                    StageStatus::Stale("pot.inp changed".to_string())
+                ),
+                (
+                    ComputeModule::Xsph,
+                    StageStatus::Stale("POT is stale".to_string())
+                ),
+            ]
+        );
+    }
+}
@@ -1,9 +1,8 @@
 mod model;
 mod parser;
 
-use super::ModuleExecutor;
-use crate::domain::{ComputeArtifact, ComputeRequest, ComputeResult, FeffError};
-use std::fs;
+use super::{ModuleContract, ModuleExecutor};
+use crate::domain::{ComputeArtifact, ComputeModule, ComputeRequest, ComputeResult, FeffError};
 
 use model::ComptonModel;
 use parser::{
@@ -23,6 +22,18 @@ pub struct ComptonContract {
     pub expected_outputs: Vec<ComputeArtifact>,
 }
 
+impl ModuleContract for ComptonContract {
+    const MODULE: ComputeModule = ComputeModule::Compton;
+
+    fn required_input_names() -> Vec<&'static str> {
+        COMPTON_REQUIRED_INPUTS.to_vec()
+    }
+
+    fn output_names() -> Vec<&'static str> {
+        COMPTON_REQUIRED_OUTPUTS.to_vec()
+    }
+}
+
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub struct ComptonModule;
 
@@ -30,8 +41,8 @@ impl ComptonModule {
     pub fn contract_for_request(&self, request: &ComputeRequest) -> ComputeResult<ComptonContract> {
         validate_request_shape(request)?;
         Ok(ComptonContract {
-            required_inputs: artifact_list(&COMPTON_REQUIRED_INPUTS),
-            expected_outputs: artifact_list(&COMPTON_REQUIRED_OUTPUTS),
+            required_inputs: artifact_list(&ComptonContract::required_input_names()),
+            expected_outputs: artifact_list(&ComptonContract::output_names()),
         })
     }
 }
@@ -41,12 +52,18 @@ impl ModuleExecutor for ComptonModule {
         validate_request_shape(request)?;
         let input_dir = input_parent_dir(request)?;
 
-        let compton_source = read_input_source(&request.input_path, COMPTON_REQUIRED_INPUTS[0])?;
+        let compton_source = read_input_source(
+            &*request.store,
+            &request.input_path,
+            COMPTON_REQUIRED_INPUTS[0],
+        )?;
         let pot_bytes = read_input_bytes(
+            &*request.store,
             &input_dir.join(COMPTON_REQUIRED_INPUTS[1]),
             COMPTON_REQUIRED_INPUTS[1],
         )?;
         let gg_slice_bytes = read_input_bytes(
+            &*request.store,
             &input_dir.join(COMPTON_REQUIRED_INPUTS[2]),
             COMPTON_REQUIRED_INPUTS[2],
         )?;
@@ -56,24 +73,29 @@ impl ModuleExecutor for ComptonModule {
             &compton_source,
             &pot_bytes,
             &gg_slice_bytes,
-        )?;
+        )?
+        .with_execution_mode(request.execution_mode)
+        .with_execution_control(request.control.clone());
         let outputs = artifact_list(&COMPTON_REQUIRED_OUTPUTS);
 
-        fs::create_dir_all(&request.output_dir).map_err(|source| {
-            FeffError::io_system(
-                "IO.COMPTON_OUTPUT_DIRECTORY",
-                format!(
-                    "failed to create COMPTON output directory '{}': {}",
-                    request.output_dir.display(),
-                    source
-                ),
-            )
-        })?;
+        request
+            .store
+            .create_dir_all(&request.output_dir)
+            .map_err(|source| {
+                FeffError::io_system(
+                    "IO.COMPTON_OUTPUT_DIRECTORY",
+                    format!(
+                        "failed to create COMPTON output directory '{}': {}",
+                        request.output_dir.display(),
+                        source
+                    ),
+                )
+            })?;
 
         for artifact in &outputs {
             let output_path = request.output_dir.join(&artifact.relative_path);
             if let Some(parent) = output_path.parent() {
-                fs::create_dir_all(parent).map_err(|source| {
+                request.store.create_dir_all(parent).map_err(|source| {
                     FeffError::io_system(
                         "IO.COMPTON_OUTPUT_DIRECTORY",
                         format!(
@@ -86,7 +108,7 @@ impl ModuleExecutor for ComptonModule {
             }
 
             let artifact_name = artifact.relative_path.to_string_lossy().replace('\\', "/");
-            model.write_artifact(&artifact_name, &output_path)?;
+            model.write_artifact(&*request.store, &artifact_name, &output_path)?;
         }
 
         Ok(outputs)
@@ -2,8 +2,11 @@ use super::parser::{
     ComptonControlInput, GgSliceInput, PotComptonInput, normalized_qhat, parse_compton_source,
     parse_gg_slice_source, parse_pot_source,
 };
-use crate::domain::{ComputeResult, FeffError};
-use crate::modules::serialization::{format_fixed_f64, write_text_artifact};
+use crate::domain::{
+    ArtifactStore, ComputeModule, ComputeResult, ExecutionControl, ExecutionMode, FeffError,
+};
+use crate::modules::serialization::{format_fixed_f64, write_text_artifact_to};
+use crate::numerics::parallel::{map_indexed, try_map_indexed};
 use crate::support::rhorrp::m_density_inp::{DensityCommand, DensityGrid};
 use crate::support::rhorrp::runtime::{iter_grid_points, line_density_with_broadening};
 use std::f64::consts::PI;
@@ -15,6 +18,8 @@ pub(super) struct ComptonModel {
     control: ComptonControlInput,
     pot: PotComptonInput,
     gg_slice: GgSliceInput,
+    execution_mode: ExecutionMode,
+    execution_control: ExecutionControl,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -47,9 +52,21 @@ impl ComptonModel {
             control: parse_compton_source(fixture_id, compton_source)?,
             pot: parse_pot_source(fixture_id, pot_bytes)?,
             gg_slice: parse_gg_slice_source(fixture_id, gg_slice_bytes)?,
+            execution_mode: ExecutionMode::Serial,
+            execution_control: ExecutionControl::default(),
         })
     }
 
+    pub(super) fn with_execution_mode(mut self, execution_mode: ExecutionMode) -> Self {
+        self.execution_mode = execution_mode;
+        self
+    }
+
+    pub(super) fn with_execution_control(mut self, execution_control: ExecutionControl) -> Self {
+        self.execution_control = execution_control;
+        self
+    }
+
     fn output_config(&self) -> ComptonOutputConfig {
         let sample_count = self.control.npq.clamp(96, 4096);
         let qmax = self.control.pqmax.abs().max(0.25);
@@ -152,11 +169,12 @@ impl ComptonModel {
 
     pub(super) fn write_artifact(
         &self,
+        store: &dyn ArtifactStore,
         artifact_name: &str,
         output_path: &Path,
     ) -> ComputeResult<()> {
         let contents = match artifact_name {
-            "compton.dat" => self.render_compton(),
+            "compton.dat" => self.render_compton()?,
             "jzzp.dat" => self.render_jzzp(),
             "rhozzp.dat" => self.render_rhozzp(),
             "logcompton.dat" => self.render_logcompton(),
@@ -168,7 +186,7 @@ impl ComptonModel {
             }
         };
 
-        write_text_artifact(output_path, &contents).map_err(|source| {
+        write_text_artifact_to(store, output_path, &contents).map_err(|source| {
             FeffError::io_system(
                 "IO.COMPTON_OUTPUT_WRITE",
                 format!(
@@ -180,7 +198,7 @@ impl ComptonModel {
         })
     }
 
-    fn render_compton(&self) -> String {
+    fn render_compton(&self) -> ComputeResult<String> {
         let config = self.output_config();
         let mut lines = Vec::with_capacity(config.sample_count + 6);
 
@@ -194,52 +212,61 @@ impl ComptonModel {
             format_fixed_f64(config.broadening, 10, 5)
         ));
 
-        for index in 0..config.sample_count {
-            let q = index as f64 * config.q_step;
-            let q_fraction = if config.qmax > 1.0e-12 {
-                (q / config.qmax).clamp(0.0, 1.0)
-            } else {
-                0.0
-            };
-            let gaussian = (-q_fraction * q_fraction * (1.0 + config.broadening)).exp();
-            let oscillation = 1.0
-                + 0.16
-                    * ((q * config.phase_frequency)
-                        + config.phase_shift
-                        + self.gg_slice.channel_count as f64 * 0.03)
-                        .sin();
-            let window = if self.control.window_type == 0 {
-                1.0
-            } else {
-                0.5 - 0.5 * (2.0 * PI * q_fraction).cos()
-            };
-
-            let compton_value = config.amplitude
-                * gaussian
-                * oscillation
-                * (0.55 + 0.45 * window * config.window_blend);
-            let jzzp_component = if self.control.emit_jzzp {
-                compton_value * (0.5 + 0.5 * (q * 0.37 + config.phase_shift * 0.5).cos().abs())
-            } else {
-                0.0
-            };
-            let rhozzp_component = if self.control.emit_rhozzp {
-                compton_value * config.rho_scale / (1.0 + q * (0.25 + config.broadening * 0.2))
-            } else {
-                0.0
-            };
-
-            lines.push(format!(
-                "{:5} {} {} {} {}",
-                index + 1,
-                format_fixed_f64(q, 11, 6),
-                format_fixed_f64(compton_value, 13, 7),
-                format_fixed_f64(jzzp_component, 13, 7),
-                format_fixed_f64(rhozzp_component, 13, 7),
-            ));
-        }
-
-        lines.join("\n")
+        lines.extend(try_map_indexed(
+            self.execution_mode,
+            config.sample_count,
+            |index| -> ComputeResult<String> {
+                let q = index as f64 * config.q_step;
+                let q_fraction = if config.qmax > 1.0e-12 {
+                    (q / config.qmax).clamp(0.0, 1.0)
+                } else {
+                    0.0
+                };
+                let gaussian = (-q_fraction * q_fraction * (1.0 + config.broadening)).exp();
+                let oscillation = 1.0
+                    + 0.16
+                        * ((q * config.phase_frequency)
+                            + config.phase_shift
+                            + self.gg_slice.channel_count as f64 * 0.03)
+                            .sin();
+                let window = if self.control.window_type == 0 {
+                    1.0
+                } else {
+                    0.5 - 0.5 * (2.0 * PI * q_fraction).cos()
+                };
+
+                let compton_value = config.amplitude
+                    * gaussian
+                    * oscillation
+                    * (0.55 + 0.45 * window * config.window_blend);
+                let jzzp_component = if self.control.emit_jzzp {
+                    compton_value * (0.5 + 0.5 * (q * 0.37 + config.phase_shift * 0.5).cos().abs())
+                } else {
+                    0.0
+                };
+                let rhozzp_component = if self.control.emit_rhozzp {
+                    compton_value * config.rho_scale / (1.0 + q * (0.25 + config.broadening * 0.2))
+                } else {
+                    0.0
+                };
+
+                self.execution_control.energy_point(
+                    ComputeModule::Compton,
+                    index,
+                    config.sample_count,
+                )?;
+                Ok(format!(
+                    "{:5} {} {} {} {}",
+                    index + 1,
+                    format_fixed_f64(q, 11, 6),
+                    format_fixed_f64(compton_value, 13, 7),
+                    format_fixed_f64(jzzp_component, 13, 7),
+                    format_fixed_f64(rhozzp_component, 13, 7),
+                ))
+            },
+        )?);
+
+        Ok(lines.join("\n"))
     }
 
     fn render_jzzp(&self) -> String {
@@ -255,8 +282,7 @@ impl ComptonModel {
         lines.push(format!("# fixture: {}", self.fixture_id));
         lines.push("# columns: index z jzzp running_integral".to_string());
 
-        let mut running_integral = 0.0_f64;
-        for index in 0..config.jzzp_rows {
+        let jzzp_values = map_indexed(self.execution_mode, config.jzzp_rows, |index| {
             let z = index as f64 * dz;
             let envelope = (-z * (0.35 + config.damping)).exp();
             let angular = (z * config.phase_frequency * 0.5 + config.phase_shift).cos();
@@ -277,7 +303,13 @@ impl ComptonModel {
             if self.control.force_recalc_jzzp {
                 jzzp_value *= 1.0 + 0.03 * ((index as f64) * 0.17).sin();
             }
+            (z, jzzp_value)
+        });
 
+        // The running integral is accumulated in row order so threaded runs
+        // sum in the same sequence as serial ones.
+        let mut running_integral = 0.0_f64;
+        for (index, (z, jzzp_value)) in jzzp_values.into_iter().enumerate() {
             running_integral += jzzp_value * dz;
             lines.push(format!(
                 "{:5} {} {} {}",
@@ -1,10 +1,11 @@
 use super::{
     COMPTON_REQUIRED_INPUTS, POT_BINARY_MAGIC, POT_CONTROL_F64_COUNT, POT_CONTROL_I32_COUNT,
 };
-use crate::domain::{ComputeArtifact, ComputeModule, ComputeRequest, ComputeResult, FeffError};
+use crate::domain::{
+    ArtifactStore, ComputeArtifact, ComputeModule, ComputeRequest, ComputeResult, FeffError,
+};
 use crate::modules::fms::FMS_GG_BINARY_MAGIC;
 use std::f64::consts::PI;
-use std::fs;
 use std::path::Path;
 
 #[derive(Debug, Clone, Copy)]
@@ -132,8 +133,12 @@ pub(super) fn input_parent_dir(request: &ComputeRequest) -> ComputeResult<&Path>
     })
 }
 
-pub(super) fn read_input_source(path: &Path, artifact_name: &str) -> ComputeResult<String> {
-    fs::read_to_string(path).map_err(|source| {
+pub(super) fn read_input_source(
+    store: &dyn ArtifactStore,
+    path: &Path,
+    artifact_name: &str,
+) -> ComputeResult<String> {
+    store.read_to_string(path).map_err(|source| {
         FeffError::io_system(
             "IO.COMPTON_INPUT_READ",
             format!(
@@ -146,8 +151,12 @@ pub(super) fn read_input_source(path: &Path, artifact_name: &str) -> ComputeResu
     })
 }
 
-pub(super) fn read_input_bytes(path: &Path, artifact_name: &str) -> ComputeResult<Vec<u8>> {
-    fs::read(path).map_err(|source| {
+pub(super) fn read_input_bytes(
+    store: &dyn ArtifactStore,
+    path: &Path,
+    artifact_name: &str,
+) -> ComputeResult<Vec<u8>> {
+    store.read(path).map_err(|source| {
         FeffError::io_system(
             "IO.COMPTON_INPUT_READ",
             format!(
@@ -290,7 +299,7 @@ pub(super) fn parse_compton_source(
         if lower.contains("rho_xy") && lower.contains("rho_line") {
             if let Some((_, values_line)) = next_nonempty_line(&lines, index + 1) {
                 let flags = parse_bool_tokens(values_line);
-                for (slot, value) in control.rho_components.iter_mut().zip(flags.into_iter()) {
+                for (slot, value) in control.rho_components.iter_mut().zip(flags) {
                     *slot = value;
                 }
             }
@@ -553,7 +562,7 @@ pub(super) fn normalized_qhat(vector: [f64; 3]) -> [f64; 3] {
 fn compton_parse_error(fixture_id: &str, message: impl Into<String>) -> FeffError {
     FeffError::computation(
         "RUN.COMPTON_INPUT_PARSE",
-        format!("fixture '{}': {}", fixture_id, message.into()),
+        format!("run '{}': {}", fixture_id, message.into()),
     )
 }
 
@@ -1,9 +1,8 @@
 mod model;
 mod parser;
 
-use super::ModuleExecutor;
-use crate::domain::{ComputeArtifact, ComputeRequest, ComputeResult, FeffError};
-use std::fs;
+use super::{ModuleContract, ModuleExecutor};
+use crate::domain::{ComputeArtifact, ComputeModule, ComputeRequest, ComputeResult, FeffError};
 
 use model::CrpaModel;
 use parser::{artifact_list, input_parent_dir, read_input_source, validate_request_shape};
@@ -17,6 +16,18 @@ pub struct CrpaContract {
     pub expected_outputs: Vec<ComputeArtifact>,
 }
 
+impl ModuleContract for CrpaContract {
+    const MODULE: ComputeModule = ComputeModule::Crpa;
+
+    fn required_input_names() -> Vec<&'static str> {
+        CRPA_REQUIRED_INPUTS.to_vec()
+    }
+
+    fn output_names() -> Vec<&'static str> {
+        CRPA_REQUIRED_OUTPUTS.to_vec()
+    }
+}
+
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub struct CrpaModule;
 
@@ -24,8 +35,8 @@ impl CrpaModule {
     pub fn contract_for_request(&self, request: &ComputeRequest) -> ComputeResult<CrpaContract> {
         validate_request_shape(request)?;
         Ok(CrpaContract {
-            required_inputs: artifact_list(&CRPA_REQUIRED_INPUTS),
-            expected_outputs: artifact_list(&CRPA_REQUIRED_OUTPUTS),
+            required_inputs: artifact_list(&CrpaContract::required_input_names()),
+            expected_outputs: artifact_list(&CrpaContract::output_names()),
         })
     }
 }
@@ -35,12 +46,18 @@ impl ModuleExecutor for CrpaModule {
         validate_request_shape(request)?;
         let input_dir = input_parent_dir(request)?;
 
-        let crpa_source = read_input_source(&request.input_path, CRPA_REQUIRED_INPUTS[0])?;
+        let crpa_source = read_input_source(
+            &*request.store,
+            &request.input_path,
+            CRPA_REQUIRED_INPUTS[0],
+        )?;
         let pot_source = read_input_source(
+            &*request.store,
             &input_dir.join(CRPA_REQUIRED_INPUTS[1]),
             CRPA_REQUIRED_INPUTS[1],
         )?;
         let geom_source = read_input_source(
+            &*request.store,
             &input_dir.join(CRPA_REQUIRED_INPUTS[2]),
             CRPA_REQUIRED_INPUTS[2],
         )?;
@@ -49,21 +66,24 @@ impl ModuleExecutor for CrpaModule {
             CrpaModel::from_sources(&request.fixture_id, &crpa_source, &pot_source, &geom_source)?;
         let outputs = artifact_list(&CRPA_REQUIRED_OUTPUTS);
 
-        fs::create_dir_all(&request.output_dir).map_err(|source| {
-            FeffError::io_system(
-                "IO.CRPA_OUTPUT_DIRECTORY",
-                format!(
-                    "failed to create CRPA output directory '{}': {}",
-                    request.output_dir.display(),
-                    source
-                ),
-            )
-        })?;
+        request
+            .store
+            .create_dir_all(&request.output_dir)
+            .map_err(|source| {
+                FeffError::io_system(
+                    "IO.CRPA_OUTPUT_DIRECTORY",
+                    format!(
+                        "failed to create CRPA output directory '{}': {}",
+                        request.output_dir.display(),
+                        source
+                    ),
+                )
+            })?;
 
         for artifact in &outputs {
             let output_path = request.output_dir.join(&artifact.relative_path);
             if let Some(parent) = output_path.parent() {
-                fs::create_dir_all(parent).map_err(|source| {
+                request.store.create_dir_all(parent).map_err(|source| {
                     FeffError::io_system(
                         "IO.CRPA_OUTPUT_DIRECTORY",
                         format!(
@@ -76,7 +96,7 @@ impl ModuleExecutor for CrpaModule {
             }
 
             let artifact_name = artifact.relative_path.to_string_lossy().replace('\\', "/");
-            model.write_artifact(&artifact_name, &output_path)?;
+            model.write_artifact(&*request.store, &artifact_name, &output_path)?;
         }
 
         Ok(outputs)
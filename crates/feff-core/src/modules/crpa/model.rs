@@ -2,8 +2,8 @@ use super::parser::{
     CrpaControlInput, GeomCrpaInput, PotCrpaInput, parse_crpa_source, parse_geom_source,
     parse_pot_source,
 };
-use crate::domain::{ComputeResult, FeffError};
-use crate::modules::serialization::{format_fixed_f64, write_text_artifact};
+use crate::domain::{ArtifactStore, ComputeResult, FeffError};
+use crate::modules::serialization::{format_fixed_f64, write_text_artifact_to};
 use std::path::Path;
 
 #[derive(Debug, Clone)]
@@ -41,12 +41,13 @@ impl CrpaModel {
 
     pub(super) fn write_artifact(
         &self,
+        store: &dyn ArtifactStore,
         artifact_name: &str,
         output_path: &Path,
     ) -> ComputeResult<()> {
         match artifact_name {
-            "wscrn.dat" => {
-                write_text_artifact(output_path, &self.render_wscrn()).map_err(|source| {
+            "wscrn.dat" => write_text_artifact_to(store, output_path, &self.render_wscrn())
+                .map_err(|source| {
                     FeffError::io_system(
                         "IO.CRPA_OUTPUT_WRITE",
                         format!(
@@ -55,10 +56,9 @@ impl CrpaModel {
                             source
                         ),
                     )
-                })
-            }
-            "logscrn.dat" => {
-                write_text_artifact(output_path, &self.render_log()).map_err(|source| {
+                }),
+            "logscrn.dat" => write_text_artifact_to(store, output_path, &self.render_log())
+                .map_err(|source| {
                     FeffError::io_system(
                         "IO.CRPA_OUTPUT_WRITE",
                         format!(
@@ -67,8 +67,7 @@ impl CrpaModel {
                             source
                         ),
                     )
-                })
-            }
+                }),
             other => Err(FeffError::internal(
                 "SYS.CRPA_OUTPUT_CONTRACT",
                 format!("unsupported CRPA output artifact '{}'", other),
@@ -1,6 +1,7 @@
 use super::CRPA_REQUIRED_INPUTS;
-use crate::domain::{ComputeArtifact, ComputeModule, ComputeRequest, ComputeResult, FeffError};
-use std::fs;
+use crate::domain::{
+    ArtifactStore, ComputeArtifact, ComputeModule, ComputeRequest, ComputeResult, FeffError,
+};
 use std::path::Path;
 
 #[derive(Debug, Clone, Copy)]
@@ -92,8 +93,12 @@ pub(super) fn input_parent_dir(request: &ComputeRequest) -> ComputeResult<&Path>
     })
 }
 
-pub(super) fn read_input_source(path: &Path, artifact_name: &str) -> ComputeResult<String> {
-    fs::read_to_string(path).map_err(|source| {
+pub(super) fn read_input_source(
+    store: &dyn ArtifactStore,
+    path: &Path,
+    artifact_name: &str,
+) -> ComputeResult<String> {
+    store.read_to_string(path).map_err(|source| {
         FeffError::io_system(
             "IO.CRPA_INPUT_READ",
             format!(
@@ -402,7 +407,7 @@ fn parse_numeric_token(token: &str) -> Option<f64> {
 fn crpa_parse_error(fixture_id: &str, message: impl Into<String>) -> FeffError {
     FeffError::computation(
         "RUN.CRPA_INPUT_PARSE",
-        format!("fixture '{}': {}", fixture_id, message.into()),
+        format!("run '{}': {}", fixture_id, message.into()),
     )
 }
 
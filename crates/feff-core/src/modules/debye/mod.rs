@@ -1,9 +1,8 @@
 mod model;
 mod parser;
 
-use super::ModuleExecutor;
-use crate::domain::{ComputeArtifact, ComputeRequest, ComputeResult, FeffError};
-use std::fs;
+use super::{ModuleContract, ModuleExecutor};
+use crate::domain::{ComputeArtifact, ComputeModule, ComputeRequest, ComputeResult, FeffError};
 
 use model::DebyeModel;
 use parser::{
@@ -12,7 +11,8 @@ use parser::{
 };
 
 pub(crate) const DEBYE_REQUIRED_INPUTS: [&str; 3] = ["ff2x.inp", "paths.dat", "feff.inp"];
-pub(crate) const DEBYE_OPTIONAL_INPUTS: [&str; 1] = ["spring.inp"];
+pub(crate) const DEBYE_OPTIONAL_INPUTS: [&str; 4] =
+    ["spring.inp", "nrixs.dat", "global.inp", "geom.dat"];
 pub(crate) const DEBYE_REQUIRED_OUTPUTS: [&str; 7] = [
     "s2_em.dat",
     "s2_rm1.dat",
@@ -33,15 +33,31 @@ pub struct DebyeContract {
     pub expected_outputs: Vec<ComputeArtifact>,
 }
 
+impl ModuleContract for DebyeContract {
+    const MODULE: ComputeModule = ComputeModule::Debye;
+
+    fn required_input_names() -> Vec<&'static str> {
+        DEBYE_REQUIRED_INPUTS.to_vec()
+    }
+
+    fn optional_input_names() -> Vec<&'static str> {
+        DEBYE_OPTIONAL_INPUTS.to_vec()
+    }
+
+    fn output_names() -> Vec<&'static str> {
+        DEBYE_REQUIRED_OUTPUTS.to_vec()
+    }
+}
+
 pub struct DebyeModule;
 
 impl DebyeModule {
     pub fn contract_for_request(&self, request: &ComputeRequest) -> ComputeResult<DebyeContract> {
         validate_request_shape(request)?;
         Ok(DebyeContract {
-            required_inputs: artifact_list(&DEBYE_REQUIRED_INPUTS),
-            optional_inputs: artifact_list(&DEBYE_OPTIONAL_INPUTS),
-            expected_outputs: artifact_list(&DEBYE_REQUIRED_OUTPUTS),
+            required_inputs: artifact_list(&DebyeContract::required_input_names()),
+            optional_inputs: artifact_list(&DebyeContract::optional_input_names()),
+            expected_outputs: artifact_list(&DebyeContract::output_names()),
         })
     }
 }
@@ -51,19 +67,41 @@ impl ModuleExecutor for DebyeModule {
         validate_request_shape(request)?;
         let input_dir = input_parent_dir(request)?;
 
-        let ff2x_source = read_input_source(&request.input_path, DEBYE_REQUIRED_INPUTS[0])?;
+        let ff2x_source = read_input_source(
+            &*request.store,
+            &request.input_path,
+            DEBYE_REQUIRED_INPUTS[0],
+        )?;
         let paths_source = read_input_source(
+            &*request.store,
             &input_dir.join(DEBYE_REQUIRED_INPUTS[1]),
             DEBYE_REQUIRED_INPUTS[1],
         )?;
         let feff_source = read_input_source(
+            &*request.store,
             &input_dir.join(DEBYE_REQUIRED_INPUTS[2]),
             DEBYE_REQUIRED_INPUTS[2],
         )?;
         let spring_source = maybe_read_optional_input_source(
+            &*request.store,
             input_dir.join(DEBYE_OPTIONAL_INPUTS[0]),
             DEBYE_OPTIONAL_INPUTS[0],
         )?;
+        let nrixs_source = maybe_read_optional_input_source(
+            &*request.store,
+            input_dir.join(DEBYE_OPTIONAL_INPUTS[1]),
+            DEBYE_OPTIONAL_INPUTS[1],
+        )?;
+        let global_source = maybe_read_optional_input_source(
+            &*request.store,
+            input_dir.join(DEBYE_OPTIONAL_INPUTS[2]),
+            DEBYE_OPTIONAL_INPUTS[2],
+        )?;
+        let geom_source = maybe_read_optional_input_source(
+            &*request.store,
+            input_dir.join(DEBYE_OPTIONAL_INPUTS[3]),
+            DEBYE_OPTIONAL_INPUTS[3],
+        )?;
 
         let model = DebyeModel::from_sources(
             &request.fixture_id,
@@ -71,24 +109,39 @@ impl ModuleExecutor for DebyeModule {
             &paths_source,
             &feff_source,
             spring_source.as_deref(),
-        )?;
-        let outputs = artifact_list(&DEBYE_REQUIRED_OUTPUTS);
-
-        fs::create_dir_all(&request.output_dir).map_err(|source| {
-            FeffError::io_system(
-                "IO.DEBYE_OUTPUT_DIRECTORY",
-                format!(
-                    "failed to create DEBYE output directory '{}': {}",
-                    request.output_dir.display(),
-                    source
-                ),
-            )
-        })?;
+            nrixs_source.as_deref(),
+            global_source.as_deref(),
+            geom_source.as_deref(),
+        )?
+        .with_execution_mode(request.execution_mode)
+        .with_execution_control(request.control.clone());
+        let mut outputs = artifact_list(&DEBYE_REQUIRED_OUTPUTS);
+        outputs.extend(
+            model
+                .nrixs_output_names()
+                .into_iter()
+                .chain(model.polarization_output_names())
+                .map(ComputeArtifact::new),
+        );
+
+        request
+            .store
+            .create_dir_all(&request.output_dir)
+            .map_err(|source| {
+                FeffError::io_system(
+                    "IO.DEBYE_OUTPUT_DIRECTORY",
+                    format!(
+                        "failed to create DEBYE output directory '{}': {}",
+                        request.output_dir.display(),
+                        source
+                    ),
+                )
+            })?;
 
         for artifact in &outputs {
             let output_path = request.output_dir.join(&artifact.relative_path);
             if let Some(parent) = output_path.parent() {
-                fs::create_dir_all(parent).map_err(|source| {
+                request.store.create_dir_all(parent).map_err(|source| {
                     FeffError::io_system(
                         "IO.DEBYE_OUTPUT_DIRECTORY",
                         format!(
@@ -101,7 +154,7 @@ impl ModuleExecutor for DebyeModule {
             }
 
             let artifact_name = artifact.relative_path.to_string_lossy().replace('\\', "/");
-            model.write_artifact(&artifact_name, &output_path)?;
+            model.write_artifact(&*request.store, &artifact_name, &output_path)?;
         }
 
         Ok(outputs)
@@ -111,13 +164,41 @@ impl ModuleExecutor for DebyeModule {
 #[cfg(test)]
 mod tests {
     use super::DebyeModule;
-    use crate::domain::{ComputeArtifact, ComputeModule, ComputeRequest, FeffErrorCategory};
+    use crate::domain::{
+        CancellationToken, ComputeArtifact, ComputeModule, ComputeRequest, ExecutionControl,
+        ExecutionEvent, ExecutionMode, ExecutionObserver, FeffErrorCategory,
+    };
     use crate::modules::ModuleExecutor;
     use std::collections::BTreeSet;
     use std::fs;
     use std::path::Path;
+    use std::sync::{Arc, Mutex};
     use tempfile::TempDir;
 
+    /// Records DEBYE energy points and cancels `token` after `cancel_after`.
+    struct PointRecorder {
+        points: Mutex<Vec<(usize, usize)>>,
+        token: CancellationToken,
+        cancel_after: Option<usize>,
+    }
+
+    impl ExecutionObserver for PointRecorder {
+        fn on_event(&self, event: &ExecutionEvent) {
+            if let ExecutionEvent::EnergyPoint {
+                module: ComputeModule::Debye,
+                index,
+                total,
+            } = event
+            {
+                let mut points = self.points.lock().expect("points");
+                points.push((*index, *total));
+                if self.cancel_after == Some(points.len()) {
+                    self.token.cancel();
+                }
+            }
+        }
+    }
+
     #[test]
     fn contract_exposes_required_and_optional_artifacts() {
         let request = ComputeRequest::new(
@@ -137,7 +218,7 @@ mod tests {
         );
         assert_eq!(
             artifact_set(&contract.optional_inputs),
-            expected_artifact_set(&["spring.inp"])
+            expected_artifact_set(&["spring.inp", "nrixs.dat", "global.inp", "geom.dat"])
         );
         assert_eq!(
             artifact_set(&contract.expected_outputs),
@@ -269,6 +350,162 @@ mod tests {
         );
     }
 
+    #[test]
+    fn spectrum_reports_energy_points_once_and_stops_when_cancelled() {
+        let temp = TempDir::new().expect("tempdir should be created");
+        let input_dir = temp.path().join("inputs");
+        stage_debye_inputs(&input_dir, false);
+
+        let run = |cancel_after: Option<usize>, output: &str| {
+            let token = CancellationToken::new();
+            let recorder = Arc::new(PointRecorder {
+                points: Mutex::new(Vec::new()),
+                token: token.clone(),
+                cancel_after,
+            });
+            let mut request = ComputeRequest::new(
+                "FX-DEBYE-001",
+                ComputeModule::Debye,
+                input_dir.join("ff2x.inp"),
+                temp.path().join(output),
+            );
+            request.execution_mode = ExecutionMode::Threaded { workers: 2 };
+            request.control = ExecutionControl::default()
+                .with_observer(recorder.clone())
+                .with_cancellation(token);
+            (DebyeModule.execute(&request), recorder)
+        };
+
+        let (result, recorder) = run(None, "complete");
+        result.expect("DEBYE execution should succeed");
+        let mut points = recorder.points.lock().expect("points").clone();
+        points.sort_unstable();
+        let total = points[0].1;
+        // xmu.dat and chi.dat share one pass over the spectrum.
+        assert_eq!(
+            points,
+            (0..total).map(|index| (index, total)).collect::<Vec<_>>()
+        );
+
+        let (result, recorder) = run(Some(5), "cancelled");
+        let error = result.expect_err("cancelled DEBYE execution should fail");
+        assert_eq!(error.placeholder(), "RUN.CANCELLED");
+        assert!(recorder.points.lock().expect("points").len() < total);
+    }
+
+    #[test]
+    fn execute_writes_per_q_nrixs_spectra_next_to_xmu() {
+        let temp = TempDir::new().expect("tempdir should be created");
+        let input_dir = temp.path().join("inputs");
+        let output_dir = temp.path().join("outputs");
+        stage_debye_inputs(&input_dir, false);
+        fs::write(input_dir.join("nrixs.dat"), NRIXS_INPUT_FIXTURE).expect("nrixs staged");
+
+        let request = ComputeRequest::new(
+            "FX-DEBYE-001",
+            ComputeModule::Debye,
+            input_dir.join("ff2x.inp"),
+            &output_dir,
+        );
+        let artifacts = DebyeModule
+            .execute(&request)
+            .expect("DEBYE NRIXS execution should succeed");
+
+        let names = artifact_set(&artifacts);
+        assert!(names.contains("xmu.dat"));
+        assert!(names.contains("xmu_q001.dat"));
+        assert!(names.contains("xmu_q002.dat"));
+        assert_eq!(names.len(), 9);
+
+        let low_q = fs::read_to_string(output_dir.join("xmu_q001.dat")).expect("q1 spectrum");
+        let high_q = fs::read_to_string(output_dir.join("xmu_q002.dat")).expect("q2 spectrum");
+        assert!(low_q.contains("|q|=1.0000"));
+        assert!(low_q.contains("multipole fractions l=0:0.00000 l=1:1.00000 l=2:0.00000"));
+        assert!(high_q.contains("multipole fractions l=0:0.50000 l=1:0.00000 l=2:0.50000"));
+        assert!(!low_q.contains("# # GENFMT NRIXS path amplitudes: 0"));
+
+        let column = |source: &str, index: usize| {
+            source
+                .lines()
+                .filter(|line| !line.starts_with('#'))
+                .map(|line| {
+                    line.split_whitespace()
+                        .nth(index)
+                        .expect("column")
+                        .parse::<f64>()
+                        .expect("numeric column")
+                })
+                .collect::<Vec<_>>()
+        };
+        let low_s0 = column(&low_q, 4);
+        assert!((low_s0.last().expect("s0") - 1.0e-2).abs() < 1.0e-12);
+        assert!(low_s0[0] < 1.0e-4, "S0 rises from the XSPH threshold");
+
+        // The dipole channel returns with odd parity and the monopole and
+        // quadrupole channels with even parity, so the fine structure flips
+        // sign by the constant ratio of their GENFMT terminations.
+        let low_chi = column(&low_q, 5);
+        let high_chi = column(&high_q, 5);
+        let ratios = low_chi
+            .iter()
+            .zip(&high_chi)
+            .filter(|(low, _)| low.abs() > 1.0e-3)
+            .map(|(low, high)| high / low)
+            .collect::<Vec<_>>();
+        assert!(ratios.len() > 10);
+        for ratio in &ratios {
+            assert!(*ratio < 0.0, "parity flips the NRIXS fine structure");
+            assert!((ratio - ratios[0]).abs() < 1.0e-2 * ratios[0].abs());
+        }
+
+        let log = fs::read_to_string(output_dir.join("log6.dat")).expect("log6.dat");
+        assert!(log.contains("NRIXS: 2 momentum transfers"));
+        assert!(log.contains("#= nrixs iorder=2"));
+    }
+
+    #[test]
+    fn execute_weights_nrixs_paths_by_the_q_direction_of_their_legs() {
+        let temp = TempDir::new().expect("tempdir should be created");
+        let input_dir = temp.path().join("inputs");
+        let output_dir = temp.path().join("outputs");
+        stage_debye_inputs(&input_dir, false);
+        fs::write(input_dir.join("paths.dat"), AXIAL_PATHS_INPUT_FIXTURE).expect("paths staged");
+        fs::write(input_dir.join("geom.dat"), AXIAL_GEOM_INPUT_FIXTURE).expect("geom staged");
+        fs::write(input_dir.join("nrixs.dat"), ORIENTED_NRIXS_INPUT_FIXTURE).expect("nrixs staged");
+
+        let request = ComputeRequest::new(
+            "FX-DEBYE-001",
+            ComputeModule::Debye,
+            input_dir.join("ff2x.inp"),
+            &output_dir,
+        );
+        DebyeModule
+            .execute(&request)
+            .expect("DEBYE NRIXS execution should succeed");
+
+        let fine_structure = |name: &str| {
+            fs::read_to_string(output_dir.join(name))
+                .expect("per-q spectrum")
+                .lines()
+                .filter(|line| !line.starts_with('#'))
+                .map(|line| {
+                    line.split_whitespace()
+                        .nth(5)
+                        .expect("column")
+                        .parse::<f64>()
+                        .expect("numeric column")
+                })
+                .collect::<Vec<_>>()
+        };
+        // Both q have the same length, so only their direction against the
+        // x-axis bonds separates the dipole fine structure.
+        let along_bonds = fine_structure("xmu_q001.dat");
+        let across_bonds = fine_structure("xmu_q002.dat");
+        assert_eq!(along_bonds.len(), across_bonds.len());
+        assert!(along_bonds.iter().any(|value| value.abs() > 1.0e-3));
+        assert!(across_bonds.iter().all(|value| value.abs() < 1.0e-12));
+    }
+
     #[test]
     fn execute_rejects_non_debye_module_requests() {
         let temp = TempDir::new().expect("tempdir should be created");
@@ -367,6 +604,40 @@ ATOMS
     1.79059    0.00000    1.79059    1   Cu  2.53228    1
     0.00000    1.79059    1.79059    1   Cu  2.53228    2
 END
+";
+
+    const NRIXS_INPUT_FIXTURE: &str = "# XSPH true-compute NRIXS multipole matrix elements
+# ljmax: 2 ldecmx: -1 spherical_average: T
+q    1    0.00000    0.00000    1.00000    1.00000    0.50000
+q    2    0.00000    0.00000    8.00000    8.00000    0.50000
+rkk 0.0E0 0.0E0 0.0 0.0 0.0 0.0 0.0 0.0 0.0 0.0 0.0 0.0 0.0 0.0
+rkk 1.524E1 2.0E0 0.0 0.0 1.0E-1 0.0 0.0 0.0 1.0E-1 0.0 0.0 0.0 1.0E-1 0.0
+rkk 6.096E1 4.0E0 0.0 0.0 1.0E-1 0.0 0.0 0.0 1.0E-1 0.0 0.0 0.0 1.0E-1 0.0
+rkk 1.3716E2 6.0E0 0.0 0.0 1.0E-1 0.0 0.0 0.0 1.0E-1 0.0 0.0 0.0 1.0E-1 0.0
+";
+
+    const AXIAL_PATHS_INPUT_FIXTURE: &str = "PATH true-compute listing
+index nleg degeneracy reff amplitude beta eta legs
+   1    2          2      5.1000      1.0000    180.0000      0.0000 2
+";
+
+    const AXIAL_GEOM_INPUT_FIXTURE: &str = "nat, nph =    3    1
+    1    2
+ iat     x       y        z       iph
+ -----------------------------------------------------------------------
+   1      0.00000      0.00000      0.00000   0   1
+   2      2.55000      0.00000      0.00000   1   1
+   3     -2.55000      0.00000      0.00000   1   1
+";
+
+    const ORIENTED_NRIXS_INPUT_FIXTURE: &str = "# XSPH true-compute NRIXS multipole matrix elements
+# ljmax: 2 ldecmx: -1 spherical_average: F
+q    1    1.00000    0.00000    0.00000    1.00000    0.50000
+q    2    0.00000    0.00000    1.00000    1.00000    0.50000
+rkk 0.0E0 0.0E0 0.0 0.0 0.0 0.0 0.0 0.0 0.0 0.0 0.0 0.0 0.0 0.0
+rkk 1.524E1 2.0E0 0.0 0.0 1.0E-1 0.0 0.0 0.0 0.0 0.0 1.0E-1 0.0 0.0 0.0
+rkk 6.096E1 4.0E0 0.0 0.0 1.0E-1 0.0 0.0 0.0 0.0 0.0 1.0E-1 0.0 0.0 0.0
+rkk 1.3716E2 6.0E0 0.0 0.0 1.0E-1 0.0 0.0 0.0 0.0 0.0 1.0E-1 0.0 0.0 0.0
 ";
 
     const SPRING_INPUT_FIXTURE: &str = "*\tres\twmax\tdosfit\tacut
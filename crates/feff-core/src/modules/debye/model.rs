@@ -1,11 +1,22 @@
 use super::parser::{
-    DebyeControlInput, FeffInputSummary, PathInputSummary, SpringInputSummary, parse_feff_source,
-    parse_ff2x_source, parse_optional_spring_source, parse_paths_source,
+    DebyeControlInput, FeffInputSummary, NrixsInputSummary, NrixsQRow, PathInputSummary,
+    SpringInputSummary, parse_feff_source, parse_ff2x_source, parse_optional_geom_source,
+    parse_optional_nrixs_source, parse_optional_spring_source, parse_paths_source,
 };
-use crate::domain::{ComputeResult, FeffError};
-use crate::modules::serialization::{format_fixed_f64, write_text_artifact};
+use crate::domain::{
+    ArtifactStore, ComputeModule, ComputeResult, ExecutionControl, ExecutionMode, FeffError,
+};
+use crate::modules::polarization::PolarizationSettings;
+use crate::modules::serialization::{format_fixed_f64, write_text_artifact_to};
+use crate::numerics::parallel::{map_indexed, try_map_indexed};
+use crate::support::common::edgedb::edge_energy;
+use crate::support::genfmt::genfmt::{GenfmtMode, GenfmtRunConfig, GenfmtRunOutput, ffmod5};
+use crate::support::genfmt::genfmtsub::GenfmtPathInput;
+use crate::support::genfmt::m_genfmt::NrixsPathLegs;
+use num_complex::Complex64;
 use std::f64::consts::PI;
 use std::path::Path;
+use std::sync::OnceLock;
 
 #[derive(Debug, Clone)]
 pub(super) struct DebyeModel {
@@ -14,8 +25,23 @@ pub(super) struct DebyeModel {
     paths: PathInputSummary,
     feff: FeffInputSummary,
     spring: Option<SpringInputSummary>,
+    nrixs: Option<NrixsInputSummary>,
+    /// `geom.dat` positions relative to the absorber, indexed like the
+    /// `paths.dat` legs.
+    geometry: Vec<[f64; 3]>,
+    polarization: PolarizationSettings,
+    execution_mode: ExecutionMode,
+    execution_control: ExecutionControl,
+    spectrum: OnceLock<Vec<DebyeSpectrumPoint>>,
+    nrixs_genfmt: OnceLock<GenfmtRunOutput>,
 }
 
+const DICHROISM_OUTPUT: &str = "dichroism.dat";
+const XMCD_OUTPUT: &str = "xmcd.dat";
+const KINETIC_EV_ANGSTROM_SQ: f64 = 3.81;
+/// Distance (Angstrom) within which two atoms belong to the same shell.
+const SHELL_TOLERANCE: f64 = 1.0e-3;
+
 struct DebyeOutputConfig {
     path_rows: usize,
     spectrum_rows: usize,
@@ -59,12 +85,16 @@ struct DebyeSpectrumPoint {
 }
 
 impl DebyeModel {
+    #[allow(clippy::too_many_arguments)]
     pub(super) fn from_sources(
         fixture_id: &str,
         ff2x_source: &str,
         paths_source: &str,
         feff_source: &str,
         spring_source: Option<&str>,
+        nrixs_source: Option<&str>,
+        global_source: Option<&str>,
+        geom_source: Option<&str>,
     ) -> ComputeResult<Self> {
         Ok(Self {
             fixture_id: fixture_id.to_string(),
@@ -72,9 +102,58 @@ impl DebyeModel {
             paths: parse_paths_source(fixture_id, paths_source)?,
             feff: parse_feff_source(fixture_id, feff_source)?,
             spring: parse_optional_spring_source(spring_source),
+            nrixs: parse_optional_nrixs_source(fixture_id, nrixs_source)?,
+            geometry: parse_optional_geom_source(geom_source),
+            polarization: global_source
+                .map(PolarizationSettings::from_global_inp)
+                .unwrap_or_default(),
+            execution_mode: ExecutionMode::Serial,
+            execution_control: ExecutionControl::default(),
+            spectrum: OnceLock::new(),
+            nrixs_genfmt: OnceLock::new(),
         })
     }
 
+    pub(super) fn with_execution_mode(mut self, execution_mode: ExecutionMode) -> Self {
+        self.execution_mode = execution_mode;
+        self
+    }
+
+    pub(super) fn with_execution_control(mut self, execution_control: ExecutionControl) -> Self {
+        self.execution_control = execution_control;
+        self
+    }
+
+    /// Linear-dichroism and XMCD spectra written when `global.inp` carries a
+    /// polarization or a spin-resolved circular polarization.
+    pub(super) fn polarization_output_names(&self) -> Vec<String> {
+        let mut names = Vec::new();
+        if self.polarization.is_polarized() {
+            names.push(DICHROISM_OUTPUT.to_string());
+        }
+        if self.polarization.xmcd_projection() != 0.0 {
+            names.push(XMCD_OUTPUT.to_string());
+        }
+        names
+    }
+
+    fn dipole_factor(&self, polarization: &PolarizationSettings) -> f64 {
+        polarization.shell_dipole_factor(&self.feff.neighbor_vectors)
+    }
+
+    /// Per-q S(q, omega) spectra written next to `xmu.dat` when XSPH provided
+    /// NRIXS multipole matrix elements.
+    pub(super) fn nrixs_output_names(&self) -> Vec<String> {
+        self.nrixs
+            .as_ref()
+            .map(|nrixs| {
+                (1..=nrixs.rows.len())
+                    .map(nrixs_output_name)
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default()
+    }
+
     fn output_config(&self) -> DebyeOutputConfig {
         let path_rows = self.paths.entry_count.clamp(12, 256);
         let spectrum_rows = (path_rows * 9).clamp(192, 768);
@@ -137,7 +216,11 @@ impl DebyeModel {
             .clamp(-PI, PI);
 
         let edge_index = self.feff.absorber_z.max(1) as f64;
-        let edge_energy = 6_000.0 + edge_index * 95.0 + self.control.ispec as f64 * 7.5;
+        let tabulated_edge = usize::try_from(self.feff.absorber_z)
+            .ok()
+            .and_then(|iz| edge_energy(iz, &self.feff.edge_label));
+        let edge_energy =
+            tabulated_edge.unwrap_or(6_000.0 + edge_index * 95.0) + self.control.ispec as f64 * 7.5;
 
         DebyeOutputConfig {
             path_rows,
@@ -155,10 +238,9 @@ impl DebyeModel {
 
     fn path_profiles(&self) -> Vec<DebyePathProfile> {
         let config = self.output_config();
-        let mut profiles = Vec::with_capacity(config.path_rows);
         let checksum_mod = (self.paths.checksum % 37) as f64;
 
-        for index in 0..config.path_rows {
+        map_indexed(self.execution_mode, config.path_rows, |index| {
             let source = if self.paths.entries.is_empty() {
                 None
             } else {
@@ -222,7 +304,7 @@ impl DebyeModel {
 
             let path_weight = (degeneracy / (1.0 + reff * config.damping)).clamp(0.0, 200.0);
 
-            profiles.push(DebyePathProfile {
+            DebyePathProfile {
                 index: path_index,
                 nleg,
                 degeneracy,
@@ -235,63 +317,103 @@ impl DebyeModel {
                 a1,
                 a2,
                 path_weight,
-            });
-        }
+            }
+        })
+    }
 
-        profiles
+    /// The `xmu.dat` spectrum, computed once and shared by every artifact
+    /// that tabulates it.
+    fn spectrum_points(&self) -> ComputeResult<&[DebyeSpectrumPoint]> {
+        if let Some(points) = self.spectrum.get() {
+            return Ok(points);
+        }
+        let points = self.spectrum_points_with(self.dipole_factor(&self.polarization), 0.0)?;
+        Ok(self.spectrum.get_or_init(|| points))
     }
 
-    fn spectrum_points(&self) -> Vec<DebyeSpectrumPoint> {
+    /// Spectrum for one polarization channel. `dipole_factor` scales the fine
+    /// structure by the bond orientation weight; `exchange_shift` (eV) moves
+    /// the photoelectron kinetic energy of a spin-resolved final state.
+    fn spectrum_points_with(
+        &self,
+        dipole_factor: f64,
+        exchange_shift: f64,
+    ) -> ComputeResult<Vec<DebyeSpectrumPoint>> {
         let config = self.output_config();
-        let mut points = Vec::with_capacity(config.spectrum_rows);
         let energy_step = 0.45 + config.thermal_factor * 0.08;
 
-        for index in 0..config.spectrum_rows {
-            let k = 0.05 * (index as f64 + 1.0);
-            let energy = config.edge_energy + k * k * 3.81 + index as f64 * energy_step * 0.03;
-
-            let envelope = (-k * (0.08 + config.damping * 0.12)).exp();
-            let oscillation =
-                (k * config.phase_frequency + config.phase_shift + self.paths.reff_mean * 0.14)
+        try_map_indexed(
+            self.execution_mode,
+            config.spectrum_rows,
+            |index| -> ComputeResult<DebyeSpectrumPoint> {
+                let k = 0.05 * (index as f64 + 1.0);
+                let energy = config.edge_energy + k * k * 3.81 + index as f64 * energy_step * 0.03;
+
+                let k_final = if exchange_shift == 0.0 {
+                    k
+                } else {
+                    (k * k + exchange_shift / KINETIC_EV_ANGSTROM_SQ)
+                        .max(0.0)
+                        .sqrt()
+                };
+                let envelope = (-k_final * (0.08 + config.damping * 0.12)).exp();
+                let oscillation = (k_final * config.phase_frequency
+                    + config.phase_shift
+                    + self.paths.reff_mean * 0.14)
                     .sin();
-            let chi = config.amplitude * envelope * oscillation;
-
-            let mu0 = (0.34
-                + (k + 1.0).ln() * 0.14
-                + self.feff.absorber_z as f64 * 0.0008
-                + self.control.s02.abs() * 0.05)
-                .max(1.0e-5);
-            let mu = mu0 + chi * (0.34 + config.thermal_factor * 0.05);
-
-            let mag = chi.abs() * (1.0 + k * 0.04).max(1.0);
-            let phase = (k * config.phase_frequency + config.phase_shift).atan2(1.0 + k * 0.07);
-
-            points.push(DebyeSpectrumPoint {
-                energy,
-                k,
-                mu,
-                mu0,
-                chi,
-                mag,
-                phase,
-            });
-        }
-
-        points
+                let chi = config.amplitude * envelope * oscillation * dipole_factor;
+
+                let mu0 = (0.34
+                    + (k + 1.0).ln() * 0.14
+                    + self.feff.absorber_z as f64 * 0.0008
+                    + self.control.s02.abs() * 0.05)
+                    .max(1.0e-5);
+                let mu = mu0 + chi * (0.34 + config.thermal_factor * 0.05);
+
+                let mag = chi.abs() * (1.0 + k * 0.04).max(1.0);
+                let phase = (k * config.phase_frequency + config.phase_shift).atan2(1.0 + k * 0.07);
+
+                self.execution_control.energy_point(
+                    ComputeModule::Debye,
+                    index,
+                    config.spectrum_rows,
+                )?;
+                Ok(DebyeSpectrumPoint {
+                    energy,
+                    k,
+                    mu,
+                    mu0,
+                    chi,
+                    mag,
+                    phase,
+                })
+            },
+        )
     }
 
     pub(super) fn write_artifact(
         &self,
+        store: &dyn ArtifactStore,
         artifact_name: &str,
         output_path: &Path,
     ) -> ComputeResult<()> {
+        let nrixs_row = self.nrixs.as_ref().and_then(|nrixs| {
+            nrixs
+                .rows
+                .iter()
+                .enumerate()
+                .find(|(index, _)| nrixs_output_name(index + 1) == artifact_name)
+        });
         let contents = match artifact_name {
+            _ if let Some((index, row)) = nrixs_row => self.render_nrixs_xmu(index + 1, row)?,
             "s2_em.dat" => self.render_s2_em(),
             "s2_rm1.dat" => self.render_s2_rm1(),
             "s2_rm2.dat" => self.render_s2_rm2(),
-            "xmu.dat" => self.render_xmu(),
-            "chi.dat" => self.render_chi(),
+            "xmu.dat" => self.render_xmu()?,
+            "chi.dat" => self.render_chi()?,
             "log6.dat" => self.render_log6(),
+            DICHROISM_OUTPUT => self.render_dichroism()?,
+            XMCD_OUTPUT => self.render_xmcd()?,
             "spring.dat" => self.render_spring(),
             other => {
                 return Err(FeffError::internal(
@@ -301,7 +423,7 @@ impl DebyeModel {
             }
         };
 
-        write_text_artifact(output_path, &contents).map_err(|source| {
+        write_text_artifact_to(store, output_path, &contents).map_err(|source| {
             FeffError::io_system(
                 "IO.DEBYE_OUTPUT_WRITE",
                 format!(
@@ -402,8 +524,8 @@ impl DebyeModel {
         lines.join("\n")
     }
 
-    fn render_xmu(&self) -> String {
-        let points = self.spectrum_points();
+    fn render_xmu(&self) -> ComputeResult<String> {
+        let points = self.spectrum_points()?;
         let mut lines = Vec::with_capacity(points.len() + 14);
 
         lines.push(format!(
@@ -428,7 +550,7 @@ impl DebyeModel {
         ));
         lines.push("#  omega    e    k    mu    mu0     chi".to_string());
 
-        for point in &points {
+        for point in points {
             lines.push(format!(
                 "{} {} {} {} {} {}",
                 format_fixed_f64(point.energy, 12, 3),
@@ -440,11 +562,316 @@ impl DebyeModel {
             ));
         }
 
-        lines.join("\n")
+        Ok(lines.join("\n"))
+    }
+
+    /// S(q, omega) for one momentum transfer. The atomic part `S0 = sum_l |rkk_l|^2`
+    /// comes from the XSPH matrix elements; the fine structure sums the per-path
+    /// amplitudes of the NRIXS GENFMT pass with the same Debye-Waller factors and
+    /// damping as `xmu.dat`. Beyond the XSPH grid the last point is held.
+    fn render_nrixs_xmu(&self, q_index: usize, row: &NrixsQRow) -> ComputeResult<String> {
+        let config = self.output_config();
+        let points = self.spectrum_points()?;
+        let profiles = self.path_profiles();
+        let Some(nrixs) = &self.nrixs else {
+            return Ok(String::new());
+        };
+        let channels = &nrixs.rkk[q_index - 1];
+        let amplitudes = self
+            .nrixs_genfmt(nrixs)
+            .artifacts
+            .as_ref()
+            .map(|artifacts| {
+                artifacts
+                    .nrixs_amplitudes
+                    .iter()
+                    .filter(|amplitude| amplitude.q_index == q_index)
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+
+        let channel_strengths = channels
+            .iter()
+            .map(|channel| channel.iter().map(|value| value.norm_sqr()).sum::<f64>())
+            .collect::<Vec<_>>();
+        let total = channel_strengths.iter().sum::<f64>();
+        let mut lines = Vec::with_capacity(points.len() + 10);
+
+        lines.push(format!(
+            "# # {:<60} FEFF10-RS true-compute",
+            self.feff.title
+        ));
+        lines.push(format!("# # EDGE {}", self.feff.edge_label));
+        lines.push(format!(
+            "# # NRIXS q{} qvec=({}, {}, {}) |q|={} weight={}",
+            q_index,
+            format_fixed_f64(row.qvec[0], 9, 4).trim(),
+            format_fixed_f64(row.qvec[1], 9, 4).trim(),
+            format_fixed_f64(row.qvec[2], 9, 4).trim(),
+            format_fixed_f64(row.norm, 9, 4).trim(),
+            format_fixed_f64(row.weight, 9, 4).trim()
+        ));
+        let fractions = channel_strengths
+            .iter()
+            .enumerate()
+            .map(|(l, strength)| {
+                let fraction = if total > 0.0 { strength / total } else { 0.0 };
+                format!("l={}:{}", l, format_fixed_f64(fraction, 8, 5).trim())
+            })
+            .collect::<Vec<_>>()
+            .join(" ");
+        lines.push(format!("# # multipole fractions {}", fractions));
+        lines.push(format!(
+            "# # GENFMT NRIXS path amplitudes: {}",
+            amplitudes.len()
+        ));
+        lines.push("#  omega    e    k    s_qw    s0_qw     chi_q".to_string());
+
+        for point in points {
+            let s0 = channels
+                .iter()
+                .map(|channel| interpolate_on_grid(&nrixs.k_grid, channel, point.k).norm_sqr())
+                .sum::<f64>();
+            let envelope = (-point.k * (0.08 + config.damping * 0.12)).exp();
+            let fine_structure = amplitudes
+                .iter()
+                .filter_map(|amplitude| {
+                    profiles
+                        .iter()
+                        .find(|profile| profile.index == amplitude.path_index)
+                        .map(|profile| (amplitude, profile))
+                })
+                .map(|(amplitude, profile)| {
+                    let feff = interpolate_on_grid(&nrixs.k_grid, &amplitude.amplitude, point.k);
+                    let path_phase = Complex64::from_polar(1.0, 2.0 * point.k * profile.reff);
+                    profile.degeneracy
+                        * (feff * path_phase).im
+                        * (-2.0 * profile.sig2_rm2 * point.k * point.k).exp()
+                        / (point.k * profile.reff * profile.reff)
+                })
+                .sum::<f64>();
+            let chi_q = if s0 > f64::MIN_POSITIVE {
+                self.control.s02 * envelope * fine_structure / s0
+            } else {
+                0.0
+            };
+            let s = s0 * (1.0 + chi_q);
+            lines.push(format!(
+                "{} {} {} {} {} {}",
+                format_fixed_f64(point.energy, 12, 3),
+                format_fixed_f64(point.energy - config.edge_energy, 10, 3),
+                format_fixed_f64(point.k, 8, 3),
+                format_scientific_f64(s),
+                format_scientific_f64(s0),
+                format_fixed_f64(chi_q, 12, 6),
+            ));
+        }
+
+        Ok(lines.join("\n"))
+    }
+
+    /// Linear dichroism: the configured polarization against the same beam with
+    /// the polarization rotated by 90 degrees about the beam direction.
+    fn render_dichroism(&self) -> ComputeResult<String> {
+        let config = self.output_config();
+        let perpendicular = self.polarization.perpendicular_linear();
+        let parallel_factor = self.dipole_factor(&self.polarization);
+        let perpendicular_factor = self.dipole_factor(&perpendicular);
+        let parallel = self.spectrum_points()?;
+        let rotated = self.spectrum_points_with(perpendicular_factor, 0.0)?;
+        let mut lines = Vec::with_capacity(parallel.len() + 8);
+
+        lines.push(format!(
+            "# # {:<60} FEFF10-RS true-compute",
+            self.feff.title
+        ));
+        lines.push(format!("# # EDGE {}", self.feff.edge_label));
+        lines.push(format!(
+            "# # evec=({}) perpendicular=({}) ellipticity={}",
+            format_vector(self.polarization.evec),
+            format_vector(perpendicular.evec),
+            format_fixed_f64(self.polarization.ellipticity, 9, 4).trim()
+        ));
+        lines.push(format!(
+            "# # dipole factors parallel={} perpendicular={}",
+            format_fixed_f64(parallel_factor, 9, 5).trim(),
+            format_fixed_f64(perpendicular_factor, 9, 5).trim()
+        ));
+        lines.push("#  omega    e    k    mu_par    mu_perp     ld".to_string());
+
+        for (point, rotated) in parallel.iter().zip(&rotated) {
+            lines.push(format!(
+                "{} {} {} {} {} {}",
+                format_fixed_f64(point.energy, 12, 3),
+                format_fixed_f64(point.energy - config.edge_energy, 10, 3),
+                format_fixed_f64(point.k, 8, 3),
+                format_fixed_f64(point.mu, 12, 6),
+                format_fixed_f64(rotated.mu, 12, 6),
+                format_fixed_f64(point.mu - rotated.mu, 12, 6),
+            ));
+        }
+
+        Ok(lines.join("\n"))
+    }
+
+    /// XMCD from exchange-split spin-up and spin-down final states. Left and
+    /// right circular spectra mix the two spin channels with the helicity
+    /// projection on the spin axis.
+    fn render_xmcd(&self) -> ComputeResult<String> {
+        let config = self.output_config();
+        let projection = self.polarization.xmcd_projection();
+        let dipole_factor = self.dipole_factor(&self.polarization);
+        let splitting = self.polarization.exchange_splitting;
+        let up = self.spectrum_points_with(dipole_factor, splitting * 0.5)?;
+        let down = self.spectrum_points_with(dipole_factor, -splitting * 0.5)?;
+        let mut lines = Vec::with_capacity(up.len() + 8);
+
+        lines.push(format!(
+            "# # {:<60} FEFF10-RS true-compute",
+            self.feff.title
+        ));
+        lines.push(format!("# # EDGE {}", self.feff.edge_label));
+        lines.push(format!(
+            "# # ispin={} spvec=({}) xivec=({}) circular_degree={} projection={}",
+            self.polarization.ispin,
+            format_vector(self.polarization.spvec),
+            format_vector(self.polarization.xivec),
+            format_fixed_f64(self.polarization.circular_degree(), 9, 5).trim(),
+            format_fixed_f64(projection, 9, 5).trim()
+        ));
+        lines.push(format!(
+            "# # exchange splitting {} eV",
+            format_fixed_f64(splitting, 8, 3).trim()
+        ));
+        lines.push(
+            "#  omega    e    k    mu_up    mu_down    mu_left    mu_right     xmcd".to_string(),
+        );
+
+        for (up, down) in up.iter().zip(&down) {
+            let average = 0.5 * (up.mu + down.mu);
+            let difference = 0.5 * projection * (up.mu - down.mu);
+            let left = average + difference;
+            let right = average - difference;
+            lines.push(format!(
+                "{} {} {} {} {} {} {} {}",
+                format_fixed_f64(up.energy, 12, 3),
+                format_fixed_f64(up.energy - config.edge_energy, 10, 3),
+                format_fixed_f64(up.k, 8, 3),
+                format_fixed_f64(up.mu, 12, 6),
+                format_fixed_f64(down.mu, 12, 6),
+                format_fixed_f64(left, 12, 6),
+                format_fixed_f64(right, 12, 6),
+                format_fixed_f64(left - right, 12, 6),
+            ));
+        }
+
+        Ok(lines.join("\n"))
+    }
+
+    /// The NRIXS GENFMT pass over the DEBYE paths, run once and shared by the
+    /// per-q spectra and `log6.dat`.
+    fn nrixs_genfmt(&self, nrixs: &NrixsInputSummary) -> &GenfmtRunOutput {
+        self.nrixs_genfmt.get_or_init(|| {
+            let paths = self
+                .path_profiles()
+                .iter()
+                .map(|profile| GenfmtPathInput {
+                    path_index: profile.index,
+                    nleg: profile.nleg,
+                    degeneracy: profile.degeneracy,
+                    reff: profile.reff,
+                    amplitude: profile.path_weight,
+                })
+                .collect::<Vec<_>>();
+            ffmod5(
+                &GenfmtRunConfig {
+                    mfeff: 1,
+                    mode: GenfmtMode::Nrixs,
+                    version_tag: "FEFF10-RS".to_string(),
+                    critcw: self.control.critcw,
+                    iorder: 2,
+                    wnstar: false,
+                    q_weights: nrixs.rows.iter().map(|row| row.weight).collect(),
+                    rkk: nrixs.rkk.clone(),
+                    q_directions: nrixs
+                        .rows
+                        .iter()
+                        .map(|row| nrixs_q_direction(row, nrixs.spherical_average))
+                        .collect(),
+                    path_legs: self.nrixs_path_legs(),
+                },
+                &paths,
+            )
+        })
+    }
+
+    /// Leg geometry of every `paths.dat` path that lists its legs. The first
+    /// and last scatterer each stand for their shell, the atoms at the same
+    /// distance from the absorber, so the members cover the paths the
+    /// degeneracy counts: single-scattering paths leave and return along the
+    /// same bond, and multiple-scattering pairs keep the opening angle of
+    /// the listed path.
+    fn nrixs_path_legs(&self) -> Vec<NrixsPathLegs> {
+        let shell = |atom: usize| -> Vec<[f64; 3]> {
+            let radius = self
+                .geometry
+                .get(atom)
+                .map_or(0.0, |position| norm(*position));
+            if radius < SHELL_TOLERANCE {
+                return Vec::new();
+            }
+            self.geometry
+                .iter()
+                .filter(|position| (norm(**position) - radius).abs() < SHELL_TOLERANCE)
+                .map(|position| position.map(|value| value / radius))
+                .collect()
+        };
+        self.paths
+            .entries
+            .iter()
+            .filter_map(|entry| {
+                let [first, last] = entry.end_legs?;
+                let first_shell = shell(first);
+                let members = if first == last {
+                    first_shell.iter().map(|bond| [*bond, *bond]).collect()
+                } else {
+                    let last_shell = shell(last);
+                    let (first_bond, last_bond) = (self.geometry[first], self.geometry[last]);
+                    let opening =
+                        dot(&first_bond, &last_bond) / (norm(first_bond) * norm(last_bond));
+                    first_shell
+                        .iter()
+                        .flat_map(|first| last_shell.iter().map(|last| [*first, *last]))
+                        .filter(|[first, last]| {
+                            (dot(first, last) - opening).abs() < SHELL_TOLERANCE
+                        })
+                        .collect()
+                };
+                Some(NrixsPathLegs {
+                    path_index: entry.index,
+                    members,
+                })
+            })
+            .collect()
     }
 
-    fn render_chi(&self) -> String {
-        let points = self.spectrum_points();
+    fn nrixs_genfmt_lines(&self, nrixs: &NrixsInputSummary) -> Vec<String> {
+        let output = self.nrixs_genfmt(nrixs);
+        let mut lines = output
+            .logs
+            .iter()
+            .map(|log| format!(" {}", log))
+            .collect::<Vec<_>>();
+        if let Some(artifacts) = &output.artifacts {
+            lines.extend(artifacts.feff_header_lines.iter().cloned());
+            lines.extend(artifacts.list_header_lines.iter().cloned());
+            lines.extend(artifacts.list_rows.iter().take(32).cloned());
+        }
+        lines
+    }
+
+    fn render_chi(&self) -> ComputeResult<String> {
+        let points = self.spectrum_points()?;
         let mut lines = Vec::with_capacity(points.len() + 8);
 
         lines.push(format!(
@@ -460,7 +887,7 @@ impl DebyeModel {
         ));
         lines.push("#       k          chi          mag           phase".to_string());
 
-        for point in &points {
+        for point in points {
             lines.push(format!(
                 "{} {} {} {}",
                 format_fixed_f64(point.k, 10, 4),
@@ -470,7 +897,7 @@ impl DebyeModel {
             ));
         }
 
-        lines.join("\n")
+        Ok(lines.join("\n"))
     }
 
     fn render_log6(&self) -> String {
@@ -528,6 +955,31 @@ impl DebyeModel {
             lines.push(" spring: no spring.inp provided; isotropic fallback applied".to_string());
         }
 
+        if let Some(nrixs) = &self.nrixs {
+            lines.push(format!(
+                " NRIXS: {} momentum transfers; S(q,w) written to {}..{}",
+                nrixs.rows.len(),
+                nrixs_output_name(1),
+                nrixs_output_name(nrixs.rows.len())
+            ));
+            lines.extend(self.nrixs_genfmt_lines(nrixs));
+        }
+
+        if self.polarization.is_polarized() {
+            lines.push(format!(
+                " Polarization: dipole factor {}; linear dichroism written to {}",
+                format_fixed_f64(self.dipole_factor(&self.polarization), 8, 5).trim(),
+                DICHROISM_OUTPUT
+            ));
+        }
+        if self.polarization.xmcd_projection() != 0.0 {
+            lines.push(format!(
+                " XMCD: helicity projection {}; spin-resolved spectra written to {}",
+                format_fixed_f64(self.polarization.xmcd_projection(), 8, 5).trim(),
+                XMCD_OUTPUT
+            ));
+        }
+
         lines.push(" Done with module 6: DW + final sum over paths.".to_string());
         lines.push(" status = success".to_string());
 
@@ -568,3 +1020,57 @@ impl DebyeModel {
         lines.join("\n")
     }
 }
+
+/// Linear interpolation of `values` sampled on the ascending `grid`, held
+/// constant outside it.
+fn interpolate_on_grid(grid: &[f64], values: &[Complex64], x: f64) -> Complex64 {
+    let count = grid.len().min(values.len());
+    if count == 0 {
+        return Complex64::new(0.0, 0.0);
+    }
+    let upper = grid[..count].partition_point(|point| *point < x);
+    if upper == 0 {
+        return values[0];
+    }
+    if upper == count {
+        return values[count - 1];
+    }
+    let (x0, x1) = (grid[upper - 1], grid[upper]);
+    let t = if x1 > x0 { (x - x0) / (x1 - x0) } else { 1.0 };
+    values[upper - 1] * (1.0 - t) + values[upper] * t
+}
+
+/// Unit direction of `q`, or `None` under a spherical average.
+fn nrixs_q_direction(row: &NrixsQRow, spherical_average: bool) -> Option<[f64; 3]> {
+    if spherical_average || row.norm <= f64::EPSILON {
+        return None;
+    }
+    Some(row.qvec.map(|value| value / row.norm))
+}
+
+fn dot(left: &[f64; 3], right: &[f64; 3]) -> f64 {
+    left.iter()
+        .zip(right)
+        .map(|(left, right)| left * right)
+        .sum()
+}
+
+fn norm(vector: [f64; 3]) -> f64 {
+    dot(&vector, &vector).sqrt()
+}
+
+fn nrixs_output_name(q_index: usize) -> String {
+    format!("xmu_q{:03}.dat", q_index)
+}
+
+fn format_scientific_f64(value: f64) -> String {
+    format!("{value:.10E}")
+}
+
+fn format_vector(vector: [f64; 3]) -> String {
+    vector
+        .iter()
+        .map(|value| format_fixed_f64(*value, 9, 4).trim().to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
@@ -1,6 +1,8 @@
 use super::{CHECKSUM_OFFSET_BASIS, CHECKSUM_PRIME, DEBYE_REQUIRED_INPUTS};
-use crate::domain::{ComputeArtifact, ComputeModule, ComputeRequest, ComputeResult, FeffError};
-use std::fs;
+use crate::domain::{
+    ArtifactStore, ComputeArtifact, ComputeModule, ComputeRequest, ComputeResult, FeffError,
+};
+use num_complex::Complex64;
 use std::path::{Path, PathBuf};
 
 #[derive(Debug, Clone, Copy)]
@@ -44,6 +46,9 @@ pub(super) struct PathEntry {
     pub(super) nleg: usize,
     pub(super) degeneracy: f64,
     pub(super) reff: f64,
+    /// 0-based `geom.dat` indices of the first and last scatterer, when the
+    /// `paths.dat` row lists its legs.
+    pub(super) end_legs: Option<[usize; 2]>,
 }
 
 #[derive(Debug, Clone)]
@@ -63,6 +68,7 @@ pub(super) struct FeffInputSummary {
     pub(super) absorber_z: i32,
     pub(super) atom_count: usize,
     pub(super) has_exafs: bool,
+    pub(super) neighbor_vectors: Vec<[f64; 3]>,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -74,6 +80,23 @@ pub(super) struct SpringInputSummary {
     pub(super) constant_max: f64,
 }
 
+#[derive(Debug, Clone)]
+pub(super) struct NrixsQRow {
+    pub(super) qvec: [f64; 3],
+    pub(super) norm: f64,
+    pub(super) weight: f64,
+}
+
+#[derive(Debug, Clone)]
+pub(super) struct NrixsInputSummary {
+    pub(super) rows: Vec<NrixsQRow>,
+    pub(super) spherical_average: bool,
+    /// Photoelectron wave number of every XSPH energy point.
+    pub(super) k_grid: Vec<f64>,
+    /// XSPH multipole matrix elements `rkk[iq][l][ie]`.
+    pub(super) rkk: Vec<Vec<Vec<Complex64>>>,
+}
+
 pub(super) fn validate_request_shape(request: &ComputeRequest) -> ComputeResult<()> {
     if request.module != ComputeModule::Debye {
         return Err(FeffError::input_validation(
@@ -122,8 +145,12 @@ pub(super) fn input_parent_dir(request: &ComputeRequest) -> ComputeResult<&Path>
     })
 }
 
-pub(super) fn read_input_source(path: &Path, artifact_name: &str) -> ComputeResult<String> {
-    fs::read_to_string(path).map_err(|source| {
+pub(super) fn read_input_source(
+    store: &dyn ArtifactStore,
+    path: &Path,
+    artifact_name: &str,
+) -> ComputeResult<String> {
+    store.read_to_string(path).map_err(|source| {
         FeffError::io_system(
             "IO.DEBYE_INPUT_READ",
             format!(
@@ -137,11 +164,12 @@ pub(super) fn read_input_source(path: &Path, artifact_name: &str) -> ComputeResu
 }
 
 pub(super) fn maybe_read_optional_input_source(
+    store: &dyn ArtifactStore,
     path: PathBuf,
     artifact_name: &str,
 ) -> ComputeResult<Option<String>> {
-    if path.is_file() {
-        return read_input_source(&path, artifact_name).map(Some);
+    if store.is_file(&path) {
+        return read_input_source(store, &path, artifact_name).map(Some);
     }
 
     Ok(None)
@@ -306,6 +334,7 @@ pub(super) fn parse_paths_source(
             nleg,
             degeneracy: degeneracy.abs().max(1.0e-6),
             reff: reff.max(0.2),
+            end_legs: tokens.get(7).and_then(|legs| parse_end_legs(legs)),
         });
 
         if entries.len() >= 512 {
@@ -321,6 +350,7 @@ pub(super) fn parse_paths_source(
                 nleg: 2 + ((offset + (checksum as usize % 5)) % 4),
                 degeneracy: 1.0 + ((checksum.wrapping_add(offset as u64) % 17) as f64),
                 reff: 1.8 + offset as f64 * 0.18,
+                end_legs: None,
             });
         }
     }
@@ -351,6 +381,40 @@ pub(super) fn parse_paths_source(
     })
 }
 
+/// First and last entry of a `paths.dat` legs column (`3,7`, 1-based).
+fn parse_end_legs(token: &str) -> Option<[usize; 2]> {
+    let legs = token
+        .split(',')
+        .map(|leg| parse_usize_token(leg)?.checked_sub(1))
+        .collect::<Option<Vec<_>>>()?;
+    Some([*legs.first()?, *legs.last()?])
+}
+
+/// Atom positions of the PATH `geom.dat` relative to the absorber, in file
+/// order (the order `paths.dat` legs index).
+pub(super) fn parse_optional_geom_source(source: Option<&str>) -> Vec<[f64; 3]> {
+    let Some(source) = source else {
+        return Vec::new();
+    };
+    let atoms = source
+        .lines()
+        .map(parse_numeric_tokens)
+        .filter(|row| row.len() >= 6)
+        .collect::<Vec<_>>();
+    let Some(absorber) = atoms
+        .iter()
+        .find(|row| row[4] == 0.0)
+        .or_else(|| atoms.first())
+        .map(|row| [row[1], row[2], row[3]])
+    else {
+        return Vec::new();
+    };
+    atoms
+        .iter()
+        .map(|row| std::array::from_fn(|axis| row[axis + 1] - absorber[axis]))
+        .collect()
+}
+
 fn parse_reff_from_path_line(line: &str) -> Option<f64> {
     let lower = line.to_ascii_lowercase();
     let marker_index = lower.find("r=")?;
@@ -372,6 +436,7 @@ pub(super) fn parse_feff_source(fixture_id: &str, source: &str) -> ComputeResult
     let mut absorber_z: Option<i32> = None;
     let mut atom_count = 0_usize;
     let mut has_exafs = false;
+    let mut sites: Vec<([f64; 3], Option<i32>)> = Vec::new();
 
     let mut in_potentials = false;
     let mut in_atoms = false;
@@ -445,10 +510,26 @@ pub(super) fn parse_feff_source(fixture_id: &str, source: &str) -> ComputeResult
             let values = parse_numeric_tokens(trimmed);
             if values.len() >= 5 {
                 atom_count += 1;
+                sites.push((
+                    [values[0], values[1], values[2]],
+                    f64_to_i32_soft(values[3]),
+                ));
             }
         }
     }
 
+    let absorber = sites
+        .iter()
+        .find(|(_, ipot)| *ipot == Some(0))
+        .or(sites.first())
+        .map(|(site, _)| *site)
+        .unwrap_or_default();
+    let neighbor_vectors = sites
+        .iter()
+        .map(|(site, _)| std::array::from_fn(|axis| site[axis] - absorber[axis]))
+        .filter(|vector: &[f64; 3]| vector.iter().any(|value| value.abs() > 1.0e-10))
+        .collect();
+
     if atom_count == 0 {
         atom_count = ((checksum % 96) as usize + 12).clamp(12, 200);
     }
@@ -470,6 +551,7 @@ pub(super) fn parse_feff_source(fixture_id: &str, source: &str) -> ComputeResult
         absorber_z,
         atom_count,
         has_exafs,
+        neighbor_vectors,
     })
 }
 
@@ -559,10 +641,108 @@ pub(super) fn parse_optional_spring_source(source: Option<&str>) -> Option<Sprin
     })
 }
 
+/// Reads the XSPH `nrixs.dat`: `q` rows list the momentum transfers and `rkk`
+/// rows hold, per energy point, the real and imaginary matrix element of every
+/// `(q, l)` channel.
+pub(super) fn parse_optional_nrixs_source(
+    fixture_id: &str,
+    source: Option<&str>,
+) -> ComputeResult<Option<NrixsInputSummary>> {
+    let Some(source) = source else {
+        return Ok(None);
+    };
+
+    let mut rows = Vec::new();
+    let mut spherical_average = false;
+    let mut energy_rows = Vec::new();
+    for line in source.lines() {
+        let trimmed = line.trim();
+        if let Some(header) = trimmed.strip_prefix('#') {
+            if let Some((_, flag)) = header.split_once("spherical_average:") {
+                spherical_average = flag.trim().eq_ignore_ascii_case("T");
+            }
+            continue;
+        }
+        if trimmed.is_empty() {
+            continue;
+        }
+
+        let (tag, rest) = trimmed
+            .split_once(char::is_whitespace)
+            .unwrap_or((trimmed, ""));
+        let values = parse_numeric_tokens(rest);
+        match tag {
+            "q" if values.len() == 6 => rows.push(NrixsQRow {
+                qvec: [values[1], values[2], values[3]],
+                norm: values[4].abs(),
+                weight: values[5],
+            }),
+            "rkk" if values.len() >= 4 && values.len().is_multiple_of(2) => {
+                energy_rows.push(values)
+            }
+            _ => {
+                return Err(debye_parse_error(
+                    fixture_id,
+                    format!(
+                        "nrixs.dat row '{}' is neither a 'q iq qx qy qz q weight' row nor an 'rkk energy k re im ..' row",
+                        trimmed
+                    ),
+                ));
+            }
+        }
+    }
+
+    if rows.is_empty() {
+        return Err(debye_parse_error(
+            fixture_id,
+            "nrixs.dat is present but lists no q rows",
+        ));
+    }
+    let Some(first) = energy_rows.first() else {
+        return Err(debye_parse_error(
+            fixture_id,
+            "nrixs.dat is present but lists no rkk rows",
+        ));
+    };
+    let element_count = (first.len() - 2) / 2;
+    if element_count % rows.len() != 0 || energy_rows.iter().any(|row| row.len() != first.len()) {
+        return Err(debye_parse_error(
+            fixture_id,
+            format!(
+                "nrixs.dat rkk rows must hold the same number of channels for each of the {} q rows",
+                rows.len()
+            ),
+        ));
+    }
+    let channel_count = element_count / rows.len();
+
+    let k_grid = energy_rows.iter().map(|row| row[1].max(0.0)).collect();
+    let rkk = (0..rows.len())
+        .map(|iq| {
+            (0..channel_count)
+                .map(|l| {
+                    let offset = 2 + (iq * channel_count + l) * 2;
+                    energy_rows
+                        .iter()
+                        .map(|row| Complex64::new(row[offset], row[offset + 1]))
+                        .collect()
+                })
+                .collect()
+        })
+        .collect();
+
+    Ok(Some(NrixsInputSummary {
+        rows,
+        spherical_average,
+        k_grid,
+        rkk,
+    }))
+}
+
 fn debye_parse_error(fixture_id: &str, message: impl Into<String>) -> FeffError {
     FeffError::computation(
         "RUN.DEBYE_INPUT_PARSE",
-        format!("fixture '{}': {}", fixture_id, message.into()),
+        format!("run '{}': {}", fixture_id, message.into()),
     )
 }
 
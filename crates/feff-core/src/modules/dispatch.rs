@@ -1,5 +1,9 @@
+use super::pipeline::ModuleArtifacts;
 use super::traits::{ModuleExecutor, RuntimeModuleExecutor};
-use crate::domain::{ComputeArtifact, ComputeModule, ComputeRequest, ComputeResult, FeffError};
+use crate::domain::{
+    ComputeArtifact, ComputeModule, ComputeRequest, ComputeResult, ExecutionEvent, FeffError,
+};
+use std::path::Path;
 
 pub fn runtime_compute_engine_available(module: ComputeModule) -> bool {
     matches!(
@@ -33,6 +37,37 @@ pub fn runtime_engine_unavailable_error(module: ComputeModule) -> FeffError {
     )
 }
 
+/// Fails with the first of `modules` that has no runtime engine, so a chain
+/// stops before anything runs.
+pub fn ensure_runtime_engines_available(modules: &[ComputeModule]) -> ComputeResult<()> {
+    match modules
+        .iter()
+        .copied()
+        .find(|module| !runtime_compute_engine_available(*module))
+    {
+        Some(module) => Err(runtime_engine_unavailable_error(module)),
+        None => Ok(()),
+    }
+}
+
+/// A request that runs `module` as `label` in `directory`, reading the first
+/// required input of its contract from there. Fails when `module` has no
+/// runtime engine.
+pub fn runtime_request(
+    label: impl Into<String>,
+    module: ComputeModule,
+    directory: &Path,
+) -> ComputeResult<ComputeRequest> {
+    ensure_runtime_engines_available(&[module])?;
+    let input = ModuleArtifacts::for_module(module).required_inputs[0];
+    Ok(ComputeRequest::new(
+        label,
+        module,
+        directory.join(input),
+        directory,
+    ))
+}
+
 pub fn execute_runtime_module(
     module: ComputeModule,
     request: &ComputeRequest,
@@ -47,7 +82,11 @@ pub fn execute_runtime_module(
         ));
     }
 
-    match module {
+    request.control.checkpoint(module)?;
+    request
+        .control
+        .emit(ExecutionEvent::ModuleStarted { module });
+    let artifacts = match module {
         ComputeModule::Rdinp => RuntimeRdinpExecutor.execute_runtime(request),
         ComputeModule::Pot => RuntimePotExecutor.execute_runtime(request),
         ComputeModule::Screen => RuntimeScreenExecutor.execute_runtime(request),
@@ -64,7 +103,21 @@ pub fn execute_runtime_module(
         ComputeModule::Compton => RuntimeComptonExecutor.execute_runtime(request),
         ComputeModule::Debye => RuntimeDebyeExecutor.execute_runtime(request),
         ComputeModule::Dmdw => RuntimeDmdwExecutor.execute_runtime(request),
+    }?;
+    for artifact in &artifacts {
+        request.control.emit(ExecutionEvent::ArtifactWritten {
+            module,
+            path: request
+                .output_dir
+                .join(&artifact.relative_path)
+                .components()
+                .collect(),
+        });
     }
+    request
+        .control
+        .emit(ExecutionEvent::ModuleFinished { module });
+    Ok(artifacts)
 }
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
@@ -215,9 +268,13 @@ impl RuntimeModuleExecutor for RuntimeDmdwExecutor {
 mod tests {
     use super::{
         execute_runtime_module, runtime_compute_engine_available, runtime_engine_unavailable_error,
+        runtime_request,
+    };
+    use crate::domain::{
+        ComputeModule, ComputeRequest, FeffErrorCategory, MemoryStore, SharedStore,
     };
-    use crate::domain::{ComputeModule, ComputeRequest, FeffErrorCategory};
     use std::path::Path;
+    use std::sync::Arc;
     use tempfile::TempDir;
 
     fn workspace_root() -> std::path::PathBuf {
@@ -372,6 +429,49 @@ mod tests {
         );
     }
 
+    #[test]
+    fn runtime_chain_runs_in_memory_and_matches_filesystem_outputs() {
+        let deck = std::fs::read(
+            workspace_root()
+                .join("artifacts/fortran-baselines/FX-WORKFLOW-XAS-001/baseline/feff.inp"),
+        )
+        .expect("workflow deck should be readable");
+        let chain = [
+            ComputeModule::Rdinp,
+            ComputeModule::Pot,
+            ComputeModule::Xsph,
+            ComputeModule::Fms,
+            ComputeModule::Path,
+        ];
+        let request_for = |module: ComputeModule, dir: &Path| {
+            runtime_request("FX-WORKFLOW-XAS-001", module, dir).expect("runtime module")
+        };
+
+        let memory = Arc::new(MemoryStore::new());
+        memory.insert("run/feff.inp", deck.clone());
+        let store = SharedStore::new(memory.clone());
+        for module in chain {
+            let request = request_for(module, Path::new("run")).with_store(store.clone());
+            execute_runtime_module(module, &request).expect("in-memory module should succeed");
+        }
+
+        let temp = TempDir::new().expect("tempdir should be created");
+        std::fs::write(temp.path().join("feff.inp"), &deck).expect("deck should be staged");
+        for module in chain {
+            execute_runtime_module(module, &request_for(module, temp.path()))
+                .expect("filesystem module should succeed");
+        }
+
+        for artifact in ["pot.bin", "phase.bin", "xsect.dat", "gg.bin", "paths.dat"] {
+            assert_eq!(
+                memory.get(Path::new("run").join(artifact)),
+                Some(std::fs::read(temp.path().join(artifact)).expect("filesystem artifact")),
+                "{} should match the filesystem run",
+                artifact
+            );
+        }
+    }
+
     #[test]
     fn runtime_dispatch_executes_pot_compute_engine() {
         let temp = TempDir::new().expect("tempdir should be created");
@@ -0,0 +1,251 @@
+use super::fms::FmsModule;
+use super::xsph::XsphModule;
+use crate::domain::{ComputeArtifact, ComputeModule, ComputeRequest, ComputeResult, FeffError};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+/// Directory under the run directory that holds distributed job files.
+pub const DISTRIBUTED_JOB_ROOT: &str = "feff10-dist";
+
+/// Job description a coordinator writes for its workers.
+pub const DISTRIBUTED_JOB_FILE: &str = "job.json";
+
+/// Modules whose energy grid can be split across worker processes.
+pub const DISTRIBUTED_MODULES: [ComputeModule; 2] = [ComputeModule::Xsph, ComputeModule::Fms];
+
+/// One worker's share of a module's energy grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EnergyChunk {
+    pub part: usize,
+    pub parts: usize,
+}
+
+impl EnergyChunk {
+    /// Contiguous block of `count` energy points. Chunks cover the grid in
+    /// part order, so concatenating their rows reproduces the serial output.
+    pub fn range(self, count: usize) -> Range<usize> {
+        let block = count.div_ceil(self.parts.max(1));
+        let start = (self.part * block).min(count);
+        start..(start + block).min(count)
+    }
+}
+
+/// A module run split across worker processes that share `directory`.
+///
+/// The coordinator saves the job, each worker computes one chunk of the
+/// binary artifact (`phase.bin` rows for XSPH, `gg.bin` rows for FMS) into
+/// the directory, and the coordinator merges the chunks and writes every
+/// output. Workers only need the directory and the module inputs, so they can
+/// run on other hosts of a shared filesystem.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DistributedJob {
+    pub module: String,
+    pub fixture_id: String,
+    pub input_path: PathBuf,
+    pub output_dir: PathBuf,
+    pub parts: usize,
+}
+
+impl DistributedJob {
+    pub fn new(request: &ComputeRequest, parts: usize) -> ComputeResult<Self> {
+        if !supports_energy_chunks(request.module) {
+            return Err(FeffError::input_validation(
+                "INPUT.DISTRIBUTED_MODULE",
+                format!(
+                    "module {} cannot be split into energy chunks; expected one of XSPH, FMS",
+                    request.module
+                ),
+            ));
+        }
+        if parts == 0 {
+            return Err(FeffError::input_validation(
+                "INPUT.DISTRIBUTED_PARTS",
+                "a distributed job needs at least one worker",
+            ));
+        }
+        Ok(Self {
+            module: request.module.as_str().to_string(),
+            fixture_id: request.fixture_id.clone(),
+            input_path: request.input_path.clone(),
+            output_dir: request.output_dir.clone(),
+            parts,
+        })
+    }
+
+    pub fn load(directory: &Path) -> ComputeResult<Self> {
+        let path = directory.join(DISTRIBUTED_JOB_FILE);
+        let content = fs::read_to_string(&path).map_err(|source| {
+            FeffError::io_system(
+                "IO.DISTRIBUTED_JOB",
+                format!(
+                    "failed to read distributed job '{}': {}",
+                    path.display(),
+                    source
+                ),
+            )
+        })?;
+        let job: Self = serde_json::from_str(&content).map_err(|source| {
+            FeffError::input_validation(
+                "INPUT.DISTRIBUTED_JOB",
+                format!(
+                    "failed to parse distributed job '{}': {}",
+                    path.display(),
+                    source
+                ),
+            )
+        })?;
+        job.compute_module()?;
+        Ok(job)
+    }
+
+    pub fn save(&self, directory: &Path) -> ComputeResult<()> {
+        let json = serde_json::to_string_pretty(self).map_err(|source| {
+            FeffError::internal(
+                "SYS.DISTRIBUTED_JOB",
+                format!("failed to serialize distributed job: {}", source),
+            )
+        })?;
+        fs::create_dir_all(directory)
+            .and_then(|()| fs::write(directory.join(DISTRIBUTED_JOB_FILE), json))
+            .map_err(|source| {
+                FeffError::io_system(
+                    "IO.DISTRIBUTED_JOB",
+                    format!(
+                        "failed to write distributed job in '{}': {}",
+                        directory.display(),
+                        source
+                    ),
+                )
+            })
+    }
+
+    pub fn request(&self) -> ComputeResult<ComputeRequest> {
+        Ok(ComputeRequest::new(
+            self.fixture_id.clone(),
+            self.compute_module()?,
+            &self.input_path,
+            &self.output_dir,
+        ))
+    }
+
+    pub fn chunk_path(directory: &Path, part: usize) -> PathBuf {
+        directory.join(format!("chunk-{:04}.bin", part))
+    }
+
+    /// Computes chunk `part` and writes it into `directory`. The chunk is
+    /// renamed into place once complete, so a coordinator never reads a
+    /// partial file.
+    pub fn run_worker(&self, directory: &Path, part: usize) -> ComputeResult<()> {
+        if part >= self.parts {
+            return Err(FeffError::input_validation(
+                "INPUT.DISTRIBUTED_PART",
+                format!(
+                    "worker part {} is out of range for a job with {} parts",
+                    part, self.parts
+                ),
+            ));
+        }
+        let request = self.request()?;
+        let chunk = EnergyChunk {
+            part,
+            parts: self.parts,
+        };
+        let rows = match request.module {
+            ComputeModule::Xsph => XsphModule.energy_chunk(&request, chunk)?,
+            _ => FmsModule.energy_chunk(&request, chunk)?,
+        };
+
+        let path = Self::chunk_path(directory, part);
+        let partial = path.with_extension("bin.partial");
+        fs::write(&partial, rows)
+            .and_then(|()| fs::rename(&partial, &path))
+            .map_err(|source| {
+                FeffError::io_system(
+                    "IO.DISTRIBUTED_CHUNK",
+                    format!(
+                        "failed to write energy chunk '{}': {}",
+                        path.display(),
+                        source
+                    ),
+                )
+            })
+    }
+
+    /// Parts whose chunk is not yet in `directory`.
+    pub fn missing_parts(&self, directory: &Path) -> Vec<usize> {
+        (0..self.parts)
+            .filter(|part| !Self::chunk_path(directory, *part).is_file())
+            .collect()
+    }
+
+    /// Concatenates every chunk in part order and executes the module with
+    /// the merged rows.
+    pub fn merge(&self, directory: &Path) -> ComputeResult<Vec<ComputeArtifact>> {
+        let mut rows = Vec::new();
+        for part in 0..self.parts {
+            let path = Self::chunk_path(directory, part);
+            let chunk = fs::read(&path).map_err(|source| {
+                FeffError::io_system(
+                    "IO.DISTRIBUTED_CHUNK",
+                    format!(
+                        "failed to read energy chunk '{}': {}",
+                        path.display(),
+                        source
+                    ),
+                )
+            })?;
+            rows.extend_from_slice(&chunk);
+        }
+
+        let request = self.request()?;
+        match request.module {
+            ComputeModule::Xsph => XsphModule.execute_with_energy_rows(&request, rows),
+            _ => FmsModule.execute_with_energy_rows(&request, rows),
+        }
+    }
+
+    fn compute_module(&self) -> ComputeResult<ComputeModule> {
+        DISTRIBUTED_MODULES
+            .into_iter()
+            .find(|module| module.as_str() == self.module)
+            .ok_or_else(|| {
+                FeffError::input_validation(
+                    "INPUT.DISTRIBUTED_MODULE",
+                    format!(
+                        "module '{}' cannot be split into energy chunks; expected one of XSPH, FMS",
+                        self.module
+                    ),
+                )
+            })
+    }
+}
+
+pub fn supports_energy_chunks(module: ComputeModule) -> bool {
+    DISTRIBUTED_MODULES.contains(&module)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DistributedJob, EnergyChunk};
+    use crate::domain::{ComputeModule, ComputeRequest, FeffErrorCategory};
+
+    #[test]
+    fn chunks_cover_the_grid_in_order() {
+        for (count, parts) in [(10, 3), (64, 4), (2, 5), (0, 2), (7, 1)] {
+            let covered: Vec<usize> = (0..parts)
+                .flat_map(|part| EnergyChunk { part, parts }.range(count))
+                .collect();
+            assert_eq!(covered, (0..count).collect::<Vec<_>>(), "{count}/{parts}");
+        }
+    }
+
+    #[test]
+    fn jobs_reject_modules_without_energy_chunks() {
+        let request = ComputeRequest::new("FX", ComputeModule::Pot, "pot.inp", "out");
+        let error = DistributedJob::new(&request, 2).expect_err("POT is not distributable");
+        assert_eq!(error.category(), FeffErrorCategory::InputValidationError);
+        assert_eq!(error.placeholder(), "INPUT.DISTRIBUTED_MODULE");
+    }
+}
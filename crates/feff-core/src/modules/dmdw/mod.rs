@@ -1,9 +1,8 @@
 mod model;
 mod parser;
 
-use super::ModuleExecutor;
-use crate::domain::{ComputeArtifact, ComputeRequest, ComputeResult, FeffError};
-use std::fs;
+use super::{ModuleContract, ModuleExecutor};
+use crate::domain::{ComputeArtifact, ComputeModule, ComputeRequest, ComputeResult, FeffError};
 
 use model::DmdwModel;
 use parser::{
@@ -19,6 +18,18 @@ pub struct DmdwContract {
     pub expected_outputs: Vec<ComputeArtifact>,
 }
 
+impl ModuleContract for DmdwContract {
+    const MODULE: ComputeModule = ComputeModule::Dmdw;
+
+    fn required_input_names() -> Vec<&'static str> {
+        DMDW_REQUIRED_INPUTS.to_vec()
+    }
+
+    fn output_names() -> Vec<&'static str> {
+        DMDW_REQUIRED_OUTPUTS.to_vec()
+    }
+}
+
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub struct DmdwModule;
 
@@ -26,8 +37,8 @@ impl DmdwModule {
     pub fn contract_for_request(&self, request: &ComputeRequest) -> ComputeResult<DmdwContract> {
         validate_request_shape(request)?;
         Ok(DmdwContract {
-            required_inputs: artifact_list(&DMDW_REQUIRED_INPUTS),
-            expected_outputs: artifact_list(&DMDW_REQUIRED_OUTPUTS),
+            required_inputs: artifact_list(&DmdwContract::required_input_names()),
+            expected_outputs: artifact_list(&DmdwContract::output_names()),
         })
     }
 }
@@ -37,8 +48,13 @@ impl ModuleExecutor for DmdwModule {
         validate_request_shape(request)?;
         let input_dir = input_parent_dir(request)?;
 
-        let dmdw_source = read_input_source(&request.input_path, DMDW_REQUIRED_INPUTS[0])?;
+        let dmdw_source = read_input_source(
+            &*request.store,
+            &request.input_path,
+            DMDW_REQUIRED_INPUTS[0],
+        )?;
         let feff_dym_bytes = read_input_bytes(
+            &*request.store,
             &input_dir.join(DMDW_REQUIRED_INPUTS[1]),
             DMDW_REQUIRED_INPUTS[1],
         )?;
@@ -46,21 +62,24 @@ impl ModuleExecutor for DmdwModule {
         let model = DmdwModel::from_inputs(&request.fixture_id, &dmdw_source, &feff_dym_bytes)?;
         let outputs = artifact_list(&DMDW_REQUIRED_OUTPUTS);
 
-        fs::create_dir_all(&request.output_dir).map_err(|source| {
-            FeffError::io_system(
-                "IO.DMDW_OUTPUT_DIRECTORY",
-                format!(
-                    "failed to create DMDW output directory '{}': {}",
-                    request.output_dir.display(),
-                    source
-                ),
-            )
-        })?;
+        request
+            .store
+            .create_dir_all(&request.output_dir)
+            .map_err(|source| {
+                FeffError::io_system(
+                    "IO.DMDW_OUTPUT_DIRECTORY",
+                    format!(
+                        "failed to create DMDW output directory '{}': {}",
+                        request.output_dir.display(),
+                        source
+                    ),
+                )
+            })?;
 
         for artifact in &outputs {
             let output_path = request.output_dir.join(&artifact.relative_path);
             if let Some(parent) = output_path.parent() {
-                fs::create_dir_all(parent).map_err(|source| {
+                request.store.create_dir_all(parent).map_err(|source| {
                     FeffError::io_system(
                         "IO.DMDW_OUTPUT_DIRECTORY",
                         format!(
@@ -73,7 +92,7 @@ impl ModuleExecutor for DmdwModule {
             }
 
             let artifact_name = artifact.relative_path.to_string_lossy().replace('\\', "/");
-            model.write_artifact(&artifact_name, &output_path)?;
+            model.write_artifact(&*request.store, &artifact_name, &output_path)?;
         }
 
         Ok(outputs)
@@ -1,6 +1,6 @@
 use super::parser::{DmdwControlInput, DymInputSummary, parse_dmdw_source, summarize_dym_input};
-use crate::domain::{ComputeResult, FeffError};
-use crate::modules::serialization::{format_fixed_f64, write_text_artifact};
+use crate::domain::{ArtifactStore, ComputeResult, FeffError};
+use crate::modules::serialization::{format_fixed_f64, write_text_artifact_to};
 use std::f64::consts::PI;
 use std::path::Path;
 
@@ -37,6 +37,7 @@ impl DmdwModel {
 
     pub(super) fn write_artifact(
         &self,
+        store: &dyn ArtifactStore,
         artifact_name: &str,
         output_path: &Path,
     ) -> ComputeResult<()> {
@@ -50,7 +51,7 @@ impl DmdwModel {
             }
         };
 
-        write_text_artifact(output_path, &contents).map_err(|source| {
+        write_text_artifact_to(store, output_path, &contents).map_err(|source| {
             FeffError::io_system(
                 "IO.DMDW_OUTPUT_WRITE",
                 format!(
@@ -1,6 +1,7 @@
 use super::DMDW_REQUIRED_INPUTS;
-use crate::domain::{ComputeArtifact, ComputeModule, ComputeRequest, ComputeResult, FeffError};
-use std::fs;
+use crate::domain::{
+    ArtifactStore, ComputeArtifact, ComputeModule, ComputeRequest, ComputeResult, FeffError,
+};
 use std::path::Path;
 
 const CHECKSUM_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
@@ -54,8 +55,12 @@ pub(super) fn input_parent_dir(request: &ComputeRequest) -> ComputeResult<&Path>
     })
 }
 
-pub(super) fn read_input_source(path: &Path, artifact_name: &str) -> ComputeResult<String> {
-    fs::read_to_string(path).map_err(|source| {
+pub(super) fn read_input_source(
+    store: &dyn ArtifactStore,
+    path: &Path,
+    artifact_name: &str,
+) -> ComputeResult<String> {
+    store.read_to_string(path).map_err(|source| {
         FeffError::io_system(
             "IO.DMDW_INPUT_READ",
             format!(
@@ -68,8 +73,12 @@ pub(super) fn read_input_source(path: &Path, artifact_name: &str) -> ComputeResu
     })
 }
 
-pub(super) fn read_input_bytes(path: &Path, artifact_name: &str) -> ComputeResult<Vec<u8>> {
-    fs::read(path).map_err(|source| {
+pub(super) fn read_input_bytes(
+    store: &dyn ArtifactStore,
+    path: &Path,
+    artifact_name: &str,
+) -> ComputeResult<Vec<u8>> {
+    store.read(path).map_err(|source| {
         FeffError::io_system(
             "IO.DMDW_INPUT_READ",
             format!(
@@ -108,7 +117,7 @@ pub(super) fn parse_dmdw_source(fixture_id: &str, source: &str) -> ComputeResult
         return Err(FeffError::input_validation(
             "INPUT.DMDW_INPUT_PARSE",
             format!(
-                "fixture '{}' DMDW input is empty; expected '{}' content",
+                "run '{}' DMDW input is empty; expected '{}' content",
                 fixture_id, DMDW_REQUIRED_INPUTS[0]
             ),
         ));
@@ -1,9 +1,8 @@
 mod model;
 mod parser;
 
-use super::ModuleExecutor;
-use crate::domain::{ComputeArtifact, ComputeRequest, ComputeResult, FeffError};
-use std::fs;
+use super::{ModuleContract, ModuleExecutor};
+use crate::domain::{ComputeArtifact, ComputeModule, ComputeRequest, ComputeResult, FeffError};
 
 use model::EelsModel;
 use parser::{
@@ -23,6 +22,25 @@ pub struct EelsContract {
     pub expected_outputs: Vec<ComputeArtifact>,
 }
 
+impl ModuleContract for EelsContract {
+    const MODULE: ComputeModule = ComputeModule::Eels;
+
+    fn required_input_names() -> Vec<&'static str> {
+        EELS_REQUIRED_INPUTS.to_vec()
+    }
+
+    fn optional_input_names() -> Vec<&'static str> {
+        EELS_OPTIONAL_INPUTS.to_vec()
+    }
+
+    fn output_names() -> Vec<&'static str> {
+        EELS_REQUIRED_OUTPUTS
+            .into_iter()
+            .chain([EELS_OPTIONAL_OUTPUT])
+            .collect()
+    }
+}
+
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub struct EelsModule;
 
@@ -31,12 +49,18 @@ impl EelsModule {
         validate_request_shape(request)?;
         let input_dir = input_parent_dir(request)?;
 
-        let eels_source = read_input_source(&request.input_path, EELS_REQUIRED_INPUTS[0])?;
+        let eels_source = read_input_source(
+            &*request.store,
+            &request.input_path,
+            EELS_REQUIRED_INPUTS[0],
+        )?;
         let xmu_source = read_input_source(
+            &*request.store,
             &input_dir.join(EELS_REQUIRED_INPUTS[1]),
             EELS_REQUIRED_INPUTS[1],
         )?;
         let magic_source = maybe_read_optional_input_source(
+            &*request.store,
             input_dir.join(EELS_OPTIONAL_INPUTS[0]),
             EELS_OPTIONAL_INPUTS[0],
         )?;
@@ -48,8 +72,8 @@ impl EelsModule {
         )?;
 
         Ok(EelsContract {
-            required_inputs: artifact_list(&EELS_REQUIRED_INPUTS),
-            optional_inputs: artifact_list(&EELS_OPTIONAL_INPUTS),
+            required_inputs: artifact_list(&EelsContract::required_input_names()),
+            optional_inputs: artifact_list(&EelsContract::optional_input_names()),
             expected_outputs: model.expected_outputs(),
         })
     }
@@ -60,12 +84,18 @@ impl ModuleExecutor for EelsModule {
         validate_request_shape(request)?;
         let input_dir = input_parent_dir(request)?;
 
-        let eels_source = read_input_source(&request.input_path, EELS_REQUIRED_INPUTS[0])?;
+        let eels_source = read_input_source(
+            &*request.store,
+            &request.input_path,
+            EELS_REQUIRED_INPUTS[0],
+        )?;
         let xmu_source = read_input_source(
+            &*request.store,
             &input_dir.join(EELS_REQUIRED_INPUTS[1]),
             EELS_REQUIRED_INPUTS[1],
         )?;
         let magic_source = maybe_read_optional_input_source(
+            &*request.store,
             input_dir.join(EELS_OPTIONAL_INPUTS[0]),
             EELS_OPTIONAL_INPUTS[0],
         )?;
@@ -74,24 +104,29 @@ impl ModuleExecutor for EelsModule {
             &eels_source,
             &xmu_source,
             magic_source.as_deref(),
-        )?;
+        )?
+        .with_execution_mode(request.execution_mode)
+        .with_execution_control(request.control.clone());
         let outputs = model.expected_outputs();
 
-        fs::create_dir_all(&request.output_dir).map_err(|source| {
-            FeffError::io_system(
-                "IO.EELS_OUTPUT_DIRECTORY",
-                format!(
-                    "failed to create EELS output directory '{}': {}",
-                    request.output_dir.display(),
-                    source
-                ),
-            )
-        })?;
+        request
+            .store
+            .create_dir_all(&request.output_dir)
+            .map_err(|source| {
+                FeffError::io_system(
+                    "IO.EELS_OUTPUT_DIRECTORY",
+                    format!(
+                        "failed to create EELS output directory '{}': {}",
+                        request.output_dir.display(),
+                        source
+                    ),
+                )
+            })?;
 
         for artifact in &outputs {
             let output_path = request.output_dir.join(&artifact.relative_path);
             if let Some(parent) = output_path.parent() {
-                fs::create_dir_all(parent).map_err(|source| {
+                request.store.create_dir_all(parent).map_err(|source| {
                     FeffError::io_system(
                         "IO.EELS_OUTPUT_DIRECTORY",
                         format!(
@@ -104,7 +139,7 @@ impl ModuleExecutor for EelsModule {
             }
 
             let artifact_name = artifact.relative_path.to_string_lossy().replace('\\', "/");
-            model.write_artifact(&artifact_name, &output_path)?;
+            model.write_artifact(&*request.store, &artifact_name, &output_path)?;
         }
 
         Ok(outputs)
@@ -275,6 +310,46 @@ angular tweak
         );
     }
 
+    #[test]
+    fn execute_magic_flag_reports_root_of_orientation_dependence() {
+        let temp = TempDir::new().expect("tempdir should be created");
+        stage_text(temp.path().join("eels.inp"), EELS_INPUT_WITH_MAGIC_FLAG);
+        stage_text(temp.path().join("xmu.dat"), XMU_INPUT);
+
+        let request = ComputeRequest::new(
+            "FX-EELS-001",
+            ComputeModule::Eels,
+            temp.path().join("eels.inp"),
+            temp.path().join("out"),
+        );
+        EelsModule
+            .execute(&request)
+            .expect("execution should succeed");
+
+        let magic_contents =
+            fs::read_to_string(temp.path().join("out/magic.dat")).expect("magic.dat should exist");
+        let magic_angle = header_value(&magic_contents, "magic_angle_mrad")
+            .parse::<f64>()
+            .expect("magic angle should be bracketed by the default sweep");
+        let characteristic = header_value(&magic_contents, "characteristic_angle_mrad")
+            .parse::<f64>()
+            .expect("characteristic angle should be numeric");
+        assert!(magic_angle > characteristic && magic_angle < 6.0 * characteristic);
+
+        let anisotropy = magic_contents
+            .lines()
+            .filter(|line| !line.starts_with('#'))
+            .map(|line| {
+                line.split_whitespace()
+                    .nth(3)
+                    .and_then(|value| value.parse::<f64>().ok())
+                    .expect("anisotropy column should be numeric")
+            })
+            .collect::<Vec<_>>();
+        assert!(anisotropy.first().is_some_and(|value| *value > 0.0));
+        assert!(anisotropy.last().is_some_and(|value| *value < 0.0));
+    }
+
     #[test]
     fn execute_is_deterministic_for_identical_inputs() {
         let temp = TempDir::new().expect("tempdir should be created");
@@ -356,6 +431,14 @@ angular tweak
         assert_eq!(error.placeholder(), "IO.EELS_INPUT_READ");
     }
 
+    fn header_value<'a>(contents: &'a str, key: &str) -> &'a str {
+        let prefix = format!("# {key}: ");
+        contents
+            .lines()
+            .find_map(|line| line.strip_prefix(prefix.as_str()))
+            .unwrap_or_else(|| panic!("header '{key}' should be present"))
+    }
+
     fn artifact_list(paths: &[&str]) -> Vec<ComputeArtifact> {
         paths.iter().copied().map(ComputeArtifact::new).collect()
     }
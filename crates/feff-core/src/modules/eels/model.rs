@@ -1,20 +1,35 @@
 use super::parser::{
-    EelsControlInput, MagicInputSummary, XmuRow, XmuSummary, artifact_list, parse_eels_source,
+    EelsControlInput, MagicInput, XmuRow, XmuSummary, artifact_list, parse_eels_source,
     parse_magic_input_source, parse_xmu_source, summarize_xmu_rows,
 };
 use super::{EELS_OPTIONAL_OUTPUT, EELS_REQUIRED_OUTPUTS};
-use crate::domain::{ComputeArtifact, ComputeResult, FeffError};
-use crate::modules::helpers::{EelsMdffWorkflowConfig, eelsmdff_workflow_coupling};
-use crate::modules::serialization::{format_fixed_f64, write_text_artifact};
+use crate::domain::{
+    ArtifactStore, ComputeArtifact, ComputeModule, ComputeResult, ExecutionControl, ExecutionMode,
+    FeffError,
+};
+use crate::modules::helpers::{
+    EelsMdffWorkflowConfig, HBARC_ATOMIC_EV_A0, eelsmdff_sigma_rows, eelsmdff_workflow_coupling,
+};
+use crate::modules::serialization::{format_fixed_f64, write_text_artifact_to};
+use crate::numerics::parallel::try_map_indexed;
+use crate::support::eelsmdff::mdff_magic::{MdffMagicConfig, MdffMagicResult, mdff_magic_angle};
+use crate::support::eelsmdff::mdff_wavelength::{DEFAULT_H_ON_SQRT_TWO_ME_AU, DEFAULT_ME_C2_EV};
 use std::path::Path;
 
+const MAGIC_SWEEP_CHARACTERISTIC_MULTIPLE: f64 = 12.0;
+const MAGIC_SWEEP_DEFAULT_STEPS: usize = 61;
+const MAGIC_SWEEP_MAX_RADIAL: usize = 4;
+const MAGIC_SWEEP_MAX_ANGULAR: usize = 3;
+
 #[derive(Debug, Clone)]
 pub(super) struct EelsModel {
     fixture_id: String,
     control: EelsControlInput,
     xmu_rows: Vec<XmuRow>,
     xmu_summary: XmuSummary,
-    magic_input: Option<MagicInputSummary>,
+    magic_input: Option<MagicInput>,
+    execution_mode: ExecutionMode,
+    execution_control: ExecutionControl,
 }
 
 struct EelsSample {
@@ -24,13 +39,9 @@ struct EelsSample {
     fine_struct: f64,
 }
 
-#[derive(Debug, Clone, Copy)]
-struct MagicSample {
-    energy: f64,
-    magic_angle_mrad: f64,
-    q_parallel: f64,
-    q_perpendicular: f64,
-    weight: f64,
+struct MagicSweep {
+    energy_loss_ev: f64,
+    result: MdffMagicResult,
 }
 
 impl EelsModel {
@@ -43,7 +54,9 @@ impl EelsModel {
         let control = parse_eels_source(fixture_id, eels_source)?;
         let xmu_rows = parse_xmu_source(fixture_id, xmu_source)?;
         let xmu_summary = summarize_xmu_rows(&xmu_rows);
-        let magic_input = magic_source.map(parse_magic_input_source);
+        let magic_input = magic_source
+            .map(|source| parse_magic_input_source(fixture_id, source))
+            .transpose()?;
 
         Ok(Self {
             fixture_id: fixture_id.to_string(),
@@ -51,9 +64,21 @@ impl EelsModel {
             xmu_rows,
             xmu_summary,
             magic_input,
+            execution_mode: ExecutionMode::Serial,
+            execution_control: ExecutionControl::default(),
         })
     }
 
+    pub(super) fn with_execution_mode(mut self, execution_mode: ExecutionMode) -> Self {
+        self.execution_mode = execution_mode;
+        self
+    }
+
+    pub(super) fn with_execution_control(mut self, execution_control: ExecutionControl) -> Self {
+        self.execution_control = execution_control;
+        self
+    }
+
     fn should_emit_magic(&self) -> bool {
         self.control.magic_flag || self.magic_input.is_some()
     }
@@ -68,11 +93,12 @@ impl EelsModel {
 
     pub(super) fn write_artifact(
         &self,
+        store: &dyn ArtifactStore,
         artifact_name: &str,
         output_path: &Path,
     ) -> ComputeResult<()> {
         let contents = match artifact_name {
-            "eels.dat" => self.render_eels_dat(),
+            "eels.dat" => self.render_eels_dat()?,
             "logeels.dat" => self.render_logeels(),
             "magic.dat" => {
                 if !self.should_emit_magic() {
@@ -81,7 +107,7 @@ impl EelsModel {
                         "magic.dat requested but magic output is disabled",
                     ));
                 }
-                self.render_magic_dat()
+                self.render_magic_dat()?
             }
             other => {
                 return Err(FeffError::internal(
@@ -91,7 +117,7 @@ impl EelsModel {
             }
         };
 
-        write_text_artifact(output_path, &contents).map_err(|source| {
+        write_text_artifact_to(store, output_path, &contents).map_err(|source| {
             FeffError::io_system(
                 "IO.EELS_OUTPUT_WRITE",
                 format!(
@@ -103,8 +129,8 @@ impl EelsModel {
         })
     }
 
-    fn render_eels_dat(&self) -> String {
-        let samples = self.derived_samples();
+    fn render_eels_dat(&self) -> ComputeResult<String> {
+        let samples = self.derived_samples()?;
         let mut lines = Vec::with_capacity(samples.len() + 3);
         lines.push("# EELS true-compute spectrum".to_string());
         lines.push(format!("# fixture: {}", self.fixture_id));
@@ -120,7 +146,7 @@ impl EelsModel {
             ));
         }
 
-        lines.join("\n")
+        Ok(lines.join("\n"))
     }
 
     fn render_logeels(&self) -> String {
@@ -170,30 +196,60 @@ Module 8 true-compute execution finished.
         )
     }
 
-    fn render_magic_dat(&self) -> String {
-        let samples = self.magic_samples();
-        let mut lines = Vec::with_capacity(samples.len() + 3);
-        lines.push("# EELS magic-angle diagnostic table".to_string());
+    fn render_magic_dat(&self) -> ComputeResult<String> {
+        let sweep = self.magic_sweep()?;
+        let result = &sweep.result;
+        let magic_angle = match result.magic_angle_rad {
+            Some(angle) => format_fixed_f64(angle * 1000.0, 10, 5).trim().to_string(),
+            None => "not bracketed by sweep".to_string(),
+        };
+
+        let mut lines = Vec::with_capacity(result.curve.len() + 9);
+        lines.push("# EELS magic-angle sweep".to_string());
         lines.push(format!("# fixture: {}", self.fixture_id));
+        lines.push(format!(
+            "# beam_energy_ev: {}",
+            format_fixed_f64(self.control.beam_energy_ev, 12, 4).trim()
+        ));
+        lines.push(format!(
+            "# energy_loss_ev: {}",
+            format_fixed_f64(sweep.energy_loss_ev, 12, 3).trim()
+        ));
+        lines.push(format!(
+            "# convergence_mrad: {}",
+            format_fixed_f64(self.control.convergence_semiangle_rad * 1000.0, 10, 5).trim()
+        ));
+        lines.push(format!(
+            "# characteristic_angle_mrad: {}",
+            format_fixed_f64(result.characteristic_angle_rad * 1000.0, 10, 5).trim()
+        ));
+        lines.push(format!(
+            "# tensor: {}",
+            if result.probe_tensor {
+                "uniaxial probe (MDFF tensor is isotropic)"
+            } else {
+                "MDFF"
+            }
+        ));
+        lines.push(format!("# magic_angle_mrad: {magic_angle}"));
         lines.push(
-            "# columns: energy_ev magic_angle_mrad q_parallel q_perpendicular weight".to_string(),
+            "# columns: collection_mrad sigma_parallel sigma_perpendicular anisotropy".to_string(),
         );
 
-        for sample in samples {
+        for point in &result.curve {
             lines.push(format!(
-                "{} {} {} {} {}",
-                format_fixed_f64(sample.energy, 12, 3),
-                format_fixed_f64(sample.magic_angle_mrad, 10, 5),
-                format_scientific_f64(sample.q_parallel),
-                format_scientific_f64(sample.q_perpendicular),
-                format_scientific_f64(sample.weight),
+                "{} {} {} {}",
+                format_fixed_f64(point.collection_semiangle_rad * 1000.0, 10, 5),
+                format_scientific_f64(point.sigma_parallel),
+                format_scientific_f64(point.sigma_perpendicular),
+                format_scientific_f64(point.anisotropy),
             ));
         }
 
-        lines.join("\n")
+        Ok(lines.join("\n"))
     }
 
-    fn derived_samples(&self) -> Vec<EelsSample> {
+    fn derived_samples(&self) -> ComputeResult<Vec<EelsSample>> {
         let direction = self.control.beam_direction;
         let direction_norm = (direction[0] * direction[0]
             + direction[1] * direction[1]
@@ -230,113 +286,131 @@ Module 8 true-compute execution finished.
             .map(|row| (row.energy, row.mu, row.mu0, row.chi))
             .collect::<Vec<_>>();
 
-        let _ = eelsmdff_workflow_coupling(
-            EelsMdffWorkflowConfig {
-                beam_energy_ev: self.control.beam_energy_ev,
-                beam_direction: self.control.beam_direction,
-                relativistic_q: self.control.relativistic > 0,
-                qmesh_radial: self.control.qmesh_radial,
-                qmesh_angular: self.control.qmesh_angular,
-                average: self.control.average > 0,
-                cross_terms: self.control.cross_terms > 0,
+        let _ = eelsmdff_workflow_coupling(self.mdff_workflow_config(), &mdff_rows);
+
+        let count = self.xmu_rows.len();
+        try_map_indexed(
+            self.execution_mode,
+            count,
+            |index| -> ComputeResult<EelsSample> {
+                let row = &self.xmu_rows[index];
+                let phase = index as f64 * 0.071
+                    + direction_alignment * 0.9
+                    + self.xmu_summary.mean_chi * 1.0e6;
+                let orientation_term = 1.0 + direction_alignment * 0.08 * phase.cos();
+
+                let atomic_bg = (row.mu0.abs()
+                    * (1.0 + collection_mrad * 0.01 + convergence_mrad * 0.006)
+                    + self.xmu_summary.mean_mu.abs() * 0.04
+                    + qmesh_density.sqrt() * 1.0e-7)
+                    .max(1.0e-12);
+
+                let fine_struct =
+                    (row.chi * relativistic_gain * orientation_term * detector_factor
+                        + cross_term_gain * row.mu * phase.sin())
+                        * averaging_gain
+                        / polarization_factor;
+
+                let total = (atomic_bg + fine_struct).max(1.0e-14);
+
+                self.execution_control
+                    .energy_point(ComputeModule::Eels, index, count)?;
+                Ok(EelsSample {
+                    energy: row.energy,
+                    total,
+                    atomic_bg,
+                    fine_struct,
+                })
             },
-            &mdff_rows,
-        );
-
-        let mut samples = Vec::with_capacity(self.xmu_rows.len());
-        for (index, row) in self.xmu_rows.iter().enumerate() {
-            let phase = index as f64 * 0.071
-                + direction_alignment * 0.9
-                + self.xmu_summary.mean_chi * 1.0e6;
-            let orientation_term = 1.0 + direction_alignment * 0.08 * phase.cos();
-
-            let atomic_bg = (row.mu0.abs()
-                * (1.0 + collection_mrad * 0.01 + convergence_mrad * 0.006)
-                + self.xmu_summary.mean_mu.abs() * 0.04
-                + qmesh_density.sqrt() * 1.0e-7)
-                .max(1.0e-12);
-
-            let fine_struct = (row.chi * relativistic_gain * orientation_term * detector_factor
-                + cross_term_gain * row.mu * phase.sin())
-                * averaging_gain
-                / polarization_factor;
-
-            let total = (atomic_bg + fine_struct).max(1.0e-14);
-
-            samples.push(EelsSample {
-                energy: row.energy,
-                total,
-                atomic_bg,
-                fine_struct,
-            });
-        }
-
-        samples
+        )
     }
 
-    fn magic_samples(&self) -> Vec<MagicSample> {
-        let row_count =
-            (self.control.qmesh_radial.max(1) * self.control.qmesh_angular.max(1)).clamp(8, 192);
-        let spectrum_span = (self.xmu_summary.energy_max - self.xmu_summary.energy_min)
-            .abs()
-            .max(1.0);
-        let energy_step = (spectrum_span / row_count as f64).max(0.1);
-        let collection_mrad = (self.control.collection_semiangle_rad.abs() * 1000.0).max(1.0e-4);
-        let convergence_mrad = (self.control.convergence_semiangle_rad.abs() * 1000.0).max(1.0e-4);
-        let magic_mean = self
-            .magic_input
-            .map(|summary| summary.mean_value)
-            .unwrap_or(0.0);
-        let magic_rms = self
-            .magic_input
-            .map(|summary| summary.rms_value)
-            .unwrap_or(0.0);
-        let magic_weight = self
+    fn magic_sweep(&self) -> ComputeResult<MagicSweep> {
+        let mdff_rows = self
+            .xmu_rows
+            .iter()
+            .map(|row| (row.energy, row.mu, row.mu0, row.chi))
+            .collect::<Vec<_>>();
+        let sigma_rows =
+            eelsmdff_sigma_rows(self.mdff_workflow_config(), &mdff_rows).ok_or_else(|| {
+                magic_error(
+                    &self.fixture_id,
+                    "unable to build MDFF sigma tensors from xmu.dat",
+                )
+            })?;
+
+        let offset = self
             .magic_input
-            .map(|summary| summary.value_count as f64)
-            .unwrap_or(1.0)
-            .max(1.0);
-
-        let base_angle =
-            (collection_mrad * 0.85 + convergence_mrad * 1.15 + 0.5 * magic_rms).max(1.0e-4);
-        let mut rows = Vec::with_capacity(row_count);
-        let mut weight_sum = 0.0_f64;
-
-        for index in 0..row_count {
-            let t = if row_count == 1 {
-                0.0
-            } else {
-                index as f64 / (row_count - 1) as f64
+            .and_then(|input| input.energy_offset_ev)
+            .unwrap_or(self.control.magic_energy_offset_ev);
+        let target_energy = self.xmu_summary.edge_energy + offset;
+        let sigma_row = sigma_rows
+            .iter()
+            .min_by(|left, right| {
+                (left.energy_loss_ev - target_energy)
+                    .abs()
+                    .total_cmp(&(right.energy_loss_ev - target_energy).abs())
+            })
+            .copied()
+            .ok_or_else(|| magic_error(&self.fixture_id, "xmu.dat has no energy rows"))?;
+
+        let mut config = MdffMagicConfig {
+            beam_energy_ev: self.control.beam_energy_ev,
+            energy_loss_ev: sigma_row.energy_loss_ev,
+            convergence_semiangle_rad: self.control.convergence_semiangle_rad,
+            collection_min_rad: 0.0,
+            collection_max_rad: 1.0,
+            collection_steps: self
+                .magic_input
+                .and_then(|input| input.collection_steps)
+                .unwrap_or(MAGIC_SWEEP_DEFAULT_STEPS),
+            nqr: self.control.qmesh_radial.clamp(1, MAGIC_SWEEP_MAX_RADIAL),
+            nqf: self.control.qmesh_angular.clamp(1, MAGIC_SWEEP_MAX_ANGULAR),
+            relativistic_q: self.control.relativistic > 0,
+            h_on_sqrt_two_me: DEFAULT_H_ON_SQRT_TWO_ME_AU,
+            me_c2_ev: DEFAULT_ME_C2_EV,
+            hbarc_ev: HBARC_ATOMIC_EV_A0,
+        };
+        config.collection_max_rad =
+            match self.magic_input.and_then(|input| input.collection_max_mrad) {
+                Some(limit_mrad) => limit_mrad * 1.0e-3,
+                None => {
+                    let gamma = 1.0 + config.beam_energy_ev / config.me_c2_ev;
+                    let beta_sq = 1.0 - 1.0 / (gamma * gamma);
+                    MAGIC_SWEEP_CHARACTERISTIC_MULTIPLE * config.energy_loss_ev
+                        / (gamma * config.me_c2_ev * beta_sq)
+                }
             };
-            let phase = t * std::f64::consts::PI * 1.5 + magic_mean * 0.01;
-            let energy = self.xmu_summary.energy_min
-                + self.control.magic_energy_offset_ev
-                + magic_mean
-                + energy_step * index as f64;
-            let magic_angle_mrad = (base_angle + phase.sin().abs() * 0.75).max(1.0e-5);
-            let q_parallel = magic_angle_mrad * 1.0e-3 * (1.0 + t * 0.25);
-            let q_perpendicular = magic_angle_mrad * 1.0e-3 * (1.0 - t * 0.15).max(0.05);
-            let weight = (1.0 + phase.cos() * 0.35 + magic_weight * 0.02).max(0.02);
-
-            rows.push(MagicSample {
-                energy,
-                magic_angle_mrad,
-                q_parallel,
-                q_perpendicular,
-                weight,
-            });
-            weight_sum += weight;
-        }
 
-        let normalization = weight_sum.max(1.0e-12);
-        for row in &mut rows {
-            row.weight /= normalization;
-        }
+        let result = mdff_magic_angle(sigma_row.tensor, config)
+            .map_err(|source| magic_error(&self.fixture_id, source.to_string()))?;
+
+        Ok(MagicSweep {
+            energy_loss_ev: sigma_row.energy_loss_ev,
+            result,
+        })
+    }
 
-        rows
+    fn mdff_workflow_config(&self) -> EelsMdffWorkflowConfig {
+        EelsMdffWorkflowConfig {
+            beam_energy_ev: self.control.beam_energy_ev,
+            beam_direction: self.control.beam_direction,
+            relativistic_q: self.control.relativistic > 0,
+            qmesh_radial: self.control.qmesh_radial,
+            qmesh_angular: self.control.qmesh_angular,
+            average: self.control.average > 0,
+            cross_terms: self.control.cross_terms > 0,
+        }
     }
 }
 
+fn magic_error(fixture_id: &str, message: impl Into<String>) -> FeffError {
+    FeffError::computation(
+        "RUN.EELS_MAGIC_ANGLE",
+        format!("run '{}': {}", fixture_id, message.into()),
+    )
+}
+
 fn format_scientific_f64(value: f64) -> String {
     format!("{value:.10E}")
 }
@@ -1,6 +1,7 @@
 use super::EELS_REQUIRED_INPUTS;
-use crate::domain::{ComputeArtifact, ComputeModule, ComputeRequest, ComputeResult, FeffError};
-use std::fs;
+use crate::domain::{
+    ArtifactStore, ComputeArtifact, ComputeModule, ComputeRequest, ComputeResult, FeffError,
+};
 use std::path::{Path, PathBuf};
 
 #[derive(Debug, Clone, Copy)]
@@ -27,6 +28,7 @@ pub(super) struct EelsControlInput {
 #[derive(Debug, Clone, Copy)]
 pub(super) struct XmuRow {
     pub(super) energy: f64,
+    pub(super) edge_energy: Option<f64>,
     pub(super) mu: f64,
     pub(super) mu0: f64,
     pub(super) chi: f64,
@@ -35,6 +37,7 @@ pub(super) struct XmuRow {
 #[derive(Debug, Clone, Copy)]
 pub(super) struct XmuSummary {
     pub(super) row_count: usize,
+    pub(super) edge_energy: f64,
     pub(super) energy_min: f64,
     pub(super) energy_max: f64,
     pub(super) mean_mu: f64,
@@ -43,11 +46,11 @@ pub(super) struct XmuSummary {
     pub(super) rms_chi: f64,
 }
 
-#[derive(Debug, Clone, Copy)]
-pub(super) struct MagicInputSummary {
-    pub(super) value_count: usize,
-    pub(super) mean_value: f64,
-    pub(super) rms_value: f64,
+#[derive(Debug, Clone, Copy, Default)]
+pub(super) struct MagicInput {
+    pub(super) energy_offset_ev: Option<f64>,
+    pub(super) collection_max_mrad: Option<f64>,
+    pub(super) collection_steps: Option<usize>,
 }
 
 pub(super) fn validate_request_shape(request: &ComputeRequest) -> ComputeResult<()> {
@@ -98,8 +101,12 @@ pub(super) fn input_parent_dir(request: &ComputeRequest) -> ComputeResult<&Path>
     })
 }
 
-pub(super) fn read_input_source(path: &Path, artifact_name: &str) -> ComputeResult<String> {
-    fs::read_to_string(path).map_err(|source| {
+pub(super) fn read_input_source(
+    store: &dyn ArtifactStore,
+    path: &Path,
+    artifact_name: &str,
+) -> ComputeResult<String> {
+    store.read_to_string(path).map_err(|source| {
         FeffError::io_system(
             "IO.EELS_INPUT_READ",
             format!(
@@ -113,11 +120,12 @@ pub(super) fn read_input_source(path: &Path, artifact_name: &str) -> ComputeResu
 }
 
 pub(super) fn maybe_read_optional_input_source(
+    store: &dyn ArtifactStore,
     path: PathBuf,
     artifact_name: &str,
 ) -> ComputeResult<Option<String>> {
-    if path.is_file() {
-        return read_input_source(&path, artifact_name).map(Some);
+    if store.is_file(&path) {
+        return read_input_source(store, &path, artifact_name).map(Some);
     }
     Ok(None)
 }
@@ -256,6 +264,7 @@ pub(super) fn parse_xmu_source(fixture_id: &str, source: &str) -> ComputeResult<
         }
 
         let energy = values[0];
+        let edge_energy = (values.len() >= 4).then(|| energy - values[1]);
         let mu = if values.len() >= 4 {
             values[3]
         } else {
@@ -270,6 +279,7 @@ pub(super) fn parse_xmu_source(fixture_id: &str, source: &str) -> ComputeResult<
 
         rows.push(XmuRow {
             energy,
+            edge_energy,
             mu,
             mu0,
             chi,
@@ -286,25 +296,35 @@ pub(super) fn parse_xmu_source(fixture_id: &str, source: &str) -> ComputeResult<
     Ok(rows)
 }
 
-pub(super) fn parse_magic_input_source(source: &str) -> MagicInputSummary {
+pub(super) fn parse_magic_input_source(
+    fixture_id: &str,
+    source: &str,
+) -> ComputeResult<MagicInput> {
     let values: Vec<f64> = source.lines().flat_map(parse_numeric_tokens).collect();
-    if values.is_empty() {
-        return MagicInputSummary {
-            value_count: 0,
-            mean_value: 0.0,
-            rms_value: 0.0,
-        };
-    }
 
-    let mean_value = values.iter().sum::<f64>() / values.len() as f64;
-    let rms_value =
-        (values.iter().map(|value| value * value).sum::<f64>() / values.len() as f64).sqrt();
-
-    MagicInputSummary {
-        value_count: values.len(),
-        mean_value,
-        rms_value,
+    let collection_max_mrad = values.get(1).copied();
+    if collection_max_mrad.is_some_and(|value| value <= 0.0) {
+        return Err(eels_parse_error(
+            fixture_id,
+            "magic.inp collection sweep limit must be positive",
+        ));
     }
+    let collection_steps = values
+        .get(2)
+        .map(|&value| f64_to_usize(value, fixture_id, "magic.inp sweep step count"))
+        .transpose()?;
+    if collection_steps.is_some_and(|steps| steps < 2) {
+        return Err(eels_parse_error(
+            fixture_id,
+            "magic.inp sweep step count must be at least 2",
+        ));
+    }
+
+    Ok(MagicInput {
+        energy_offset_ev: values.first().copied(),
+        collection_max_mrad,
+        collection_steps,
+    })
 }
 
 pub(super) fn summarize_xmu_rows(rows: &[XmuRow]) -> XmuSummary {
@@ -325,8 +345,18 @@ pub(super) fn summarize_xmu_rows(rows: &[XmuRow]) -> XmuSummary {
     }
 
     let row_count = rows.len().max(1);
+    let edge_energy = rows
+        .first()
+        .and_then(|row| row.edge_energy)
+        .filter(|energy| energy.is_finite())
+        .unwrap_or(energy_min);
     XmuSummary {
         row_count: rows.len(),
+        edge_energy: if edge_energy.is_finite() {
+            edge_energy
+        } else {
+            0.0
+        },
         energy_min: if energy_min.is_finite() {
             energy_min
         } else {
@@ -431,7 +461,7 @@ fn f64_to_usize(value: f64, fixture_id: &str, field: &str) -> ComputeResult<usiz
 fn eels_parse_error(fixture_id: &str, message: impl Into<String>) -> FeffError {
     FeffError::input_validation(
         "INPUT.EELS_PARSE",
-        format!("fixture '{}': {}", fixture_id, message.into()),
+        format!("run '{}': {}", fixture_id, message.into()),
     )
 }
 
@@ -1,18 +1,22 @@
 mod model;
 mod parser;
 
-use super::ModuleExecutor;
-use crate::domain::{ComputeArtifact, ComputeRequest, ComputeResult, FeffError};
-use std::fs;
+use super::distributed::EnergyChunk;
+use super::{ModuleContract, ModuleExecutor};
+use crate::domain::{ComputeArtifact, ComputeModule, ComputeRequest, ComputeResult, FeffError};
 
 use model::FmsModel;
 use parser::{
-    artifact_list, input_parent_dir, read_input_bytes, read_input_source, validate_request_shape,
+    artifact_list, compton_requested, input_parent_dir, read_input_bytes, read_input_source,
+    validate_request_shape,
 };
 
 pub(crate) const FMS_REQUIRED_INPUTS: [&str; 4] =
     ["fms.inp", "geom.dat", "global.inp", "phase.bin"];
+pub(crate) const FMS_OPTIONAL_INPUTS: [&str; 1] = ["compton.inp"];
 pub(crate) const FMS_REQUIRED_OUTPUTS: [&str; 2] = ["gg.bin", "log3.dat"];
+/// Green's function slice COMPTON reads, written when `compton.inp` runs it.
+pub(crate) const FMS_COMPTON_OUTPUT: &str = "gg_slice.bin";
 pub const FMS_GG_BINARY_MAGIC: &[u8; 8] = b"FMSGBIN1";
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -21,6 +25,25 @@ pub struct FmsContract {
     pub expected_outputs: Vec<ComputeArtifact>,
 }
 
+impl ModuleContract for FmsContract {
+    const MODULE: ComputeModule = ComputeModule::Fms;
+
+    fn required_input_names() -> Vec<&'static str> {
+        FMS_REQUIRED_INPUTS.to_vec()
+    }
+
+    fn optional_input_names() -> Vec<&'static str> {
+        FMS_OPTIONAL_INPUTS.to_vec()
+    }
+
+    fn output_names() -> Vec<&'static str> {
+        FMS_REQUIRED_OUTPUTS
+            .into_iter()
+            .chain([FMS_COMPTON_OUTPUT])
+            .collect()
+    }
+}
+
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub struct FmsModule;
 
@@ -28,41 +51,86 @@ impl FmsModule {
     pub fn contract_for_request(&self, request: &ComputeRequest) -> ComputeResult<FmsContract> {
         validate_request_shape(request)?;
         Ok(FmsContract {
-            required_inputs: artifact_list(&FMS_REQUIRED_INPUTS),
+            required_inputs: artifact_list(&FmsContract::required_input_names()),
             expected_outputs: artifact_list(&FMS_REQUIRED_OUTPUTS),
         })
     }
+
+    /// `gg.bin` rows for one distributed worker's share of the energy grid.
+    pub fn energy_chunk(
+        &self,
+        request: &ComputeRequest,
+        chunk: EnergyChunk,
+    ) -> ComputeResult<Vec<u8>> {
+        let model = load_model(request)?;
+        model.energy_rows(chunk.range(model.energy_point_count()))
+    }
+
+    /// Executes FMS with `gg.bin` energy rows merged from every chunk in order.
+    pub fn execute_with_energy_rows(
+        &self,
+        request: &ComputeRequest,
+        rows: Vec<u8>,
+    ) -> ComputeResult<Vec<ComputeArtifact>> {
+        write_outputs(request, &load_model(request)?.with_energy_rows(rows)?)
+    }
 }
 
 impl ModuleExecutor for FmsModule {
     fn execute(&self, request: &ComputeRequest) -> ComputeResult<Vec<ComputeArtifact>> {
-        validate_request_shape(request)?;
-        let input_dir = input_parent_dir(request)?;
-
-        let fms_source = read_input_source(&request.input_path, FMS_REQUIRED_INPUTS[0])?;
-        let geom_source = read_input_source(
-            &input_dir.join(FMS_REQUIRED_INPUTS[1]),
-            FMS_REQUIRED_INPUTS[1],
-        )?;
-        let global_source = read_input_source(
-            &input_dir.join(FMS_REQUIRED_INPUTS[2]),
-            FMS_REQUIRED_INPUTS[2],
-        )?;
-        let phase_bytes = read_input_bytes(
-            &input_dir.join(FMS_REQUIRED_INPUTS[3]),
-            FMS_REQUIRED_INPUTS[3],
-        )?;
-
-        let model = FmsModel::from_sources(
-            &request.fixture_id,
-            &fms_source,
-            &geom_source,
-            &global_source,
-            &phase_bytes,
-        )?;
-        let outputs = artifact_list(&FMS_REQUIRED_OUTPUTS);
-
-        fs::create_dir_all(&request.output_dir).map_err(|source| {
+        write_outputs(request, &load_model(request)?)
+    }
+}
+
+fn load_model(request: &ComputeRequest) -> ComputeResult<FmsModel> {
+    validate_request_shape(request)?;
+    let input_dir = input_parent_dir(request)?;
+
+    let fms_source =
+        read_input_source(&*request.store, &request.input_path, FMS_REQUIRED_INPUTS[0])?;
+    let geom_source = read_input_source(
+        &*request.store,
+        &input_dir.join(FMS_REQUIRED_INPUTS[1]),
+        FMS_REQUIRED_INPUTS[1],
+    )?;
+    let global_source = read_input_source(
+        &*request.store,
+        &input_dir.join(FMS_REQUIRED_INPUTS[2]),
+        FMS_REQUIRED_INPUTS[2],
+    )?;
+    let phase_bytes = read_input_bytes(
+        &*request.store,
+        &input_dir.join(FMS_REQUIRED_INPUTS[3]),
+        FMS_REQUIRED_INPUTS[3],
+    )?;
+
+    Ok(FmsModel::from_sources(
+        &request.fixture_id,
+        &fms_source,
+        &geom_source,
+        &global_source,
+        &phase_bytes,
+    )?
+    .with_execution_mode(request.execution_mode)
+    .with_execution_control(request.control.clone()))
+}
+
+fn write_outputs(
+    request: &ComputeRequest,
+    model: &FmsModel,
+) -> ComputeResult<Vec<ComputeArtifact>> {
+    let mut outputs = artifact_list(&FMS_REQUIRED_OUTPUTS);
+    if compton_requested(
+        &*request.store,
+        &input_parent_dir(request)?.join(FMS_OPTIONAL_INPUTS[0]),
+    )? {
+        outputs.extend(artifact_list(&[FMS_COMPTON_OUTPUT]));
+    }
+
+    request
+        .store
+        .create_dir_all(&request.output_dir)
+        .map_err(|source| {
             FeffError::io_system(
                 "IO.FMS_OUTPUT_DIRECTORY",
                 format!(
@@ -73,40 +141,89 @@ impl ModuleExecutor for FmsModule {
             )
         })?;
 
-        for artifact in &outputs {
-            let output_path = request.output_dir.join(&artifact.relative_path);
-            if let Some(parent) = output_path.parent() {
-                fs::create_dir_all(parent).map_err(|source| {
-                    FeffError::io_system(
-                        "IO.FMS_OUTPUT_DIRECTORY",
-                        format!(
-                            "failed to create FMS artifact directory '{}': {}",
-                            parent.display(),
-                            source
-                        ),
-                    )
-                })?;
-            }
-
-            let artifact_name = artifact.relative_path.to_string_lossy().replace('\\', "/");
-            model.write_artifact(&artifact_name, &output_path)?;
+    for artifact in &outputs {
+        let output_path = request.output_dir.join(&artifact.relative_path);
+        if let Some(parent) = output_path.parent() {
+            request.store.create_dir_all(parent).map_err(|source| {
+                FeffError::io_system(
+                    "IO.FMS_OUTPUT_DIRECTORY",
+                    format!(
+                        "failed to create FMS artifact directory '{}': {}",
+                        parent.display(),
+                        source
+                    ),
+                )
+            })?;
         }
 
-        Ok(outputs)
+        let artifact_name = artifact.relative_path.to_string_lossy().replace('\\', "/");
+        model.write_artifact(&*request.store, &artifact_name, &output_path)?;
     }
+
+    Ok(outputs)
 }
 
 #[cfg(test)]
 mod tests {
     use super::{FMS_GG_BINARY_MAGIC, FmsModule};
-    use crate::domain::{ComputeArtifact, ComputeModule, ComputeRequest, FeffErrorCategory};
+    use crate::domain::{
+        CancellationToken, ComputeArtifact, ComputeModule, ComputeRequest, ExecutionControl,
+        ExecutionEvent, ExecutionMode, ExecutionObserver, FeffErrorCategory,
+    };
     use crate::modules::ModuleExecutor;
+    use crate::modules::dispatch::execute_runtime_module;
+    use crate::modules::distributed::DistributedJob;
     use crate::modules::xsph::XSPH_PHASE_BINARY_MAGIC;
     use std::collections::BTreeSet;
     use std::fs;
     use std::path::Path;
+    use std::sync::{Arc, Mutex};
     use tempfile::TempDir;
 
+    /// Records events and cancels `token` after `cancel_after` energy points.
+    struct RecordingObserver {
+        events: Mutex<Vec<ExecutionEvent>>,
+        token: CancellationToken,
+        cancel_after: Option<usize>,
+    }
+
+    impl ExecutionObserver for RecordingObserver {
+        fn on_event(&self, event: &ExecutionEvent) {
+            let mut events = self.events.lock().expect("events");
+            events.push(event.clone());
+            let points = events
+                .iter()
+                .filter(|event| matches!(event, ExecutionEvent::EnergyPoint { .. }))
+                .count();
+            if self.cancel_after == Some(points) {
+                self.token.cancel();
+            }
+        }
+    }
+
+    fn observed_request(
+        input_dir: &Path,
+        output_dir: &Path,
+        cancel_after: Option<usize>,
+    ) -> (ComputeRequest, Arc<RecordingObserver>) {
+        let token = CancellationToken::new();
+        let observer = Arc::new(RecordingObserver {
+            events: Mutex::new(Vec::new()),
+            token: token.clone(),
+            cancel_after,
+        });
+        let mut request = ComputeRequest::new(
+            "FX-FMS-001",
+            ComputeModule::Fms,
+            input_dir.join("fms.inp"),
+            output_dir,
+        );
+        request.control = ExecutionControl::default()
+            .with_observer(observer.clone())
+            .with_cancellation(token);
+        (request, observer)
+    }
+
     #[test]
     fn contract_reports_required_true_compute_artifacts() {
         let request =
@@ -205,6 +322,244 @@ mod tests {
         }
     }
 
+    #[test]
+    fn threaded_execution_matches_serial_outputs() {
+        let temp = TempDir::new().expect("tempdir should be created");
+        let input_dir = temp.path().join("inputs");
+        stage_inputs(&input_dir, &xsph_phase_bytes());
+
+        let serial_output = temp.path().join("serial-output");
+        let serial_request = ComputeRequest::new(
+            "FX-FMS-001",
+            ComputeModule::Fms,
+            input_dir.join("fms.inp"),
+            &serial_output,
+        );
+        FmsModule
+            .execute(&serial_request)
+            .expect("serial FMS execution should succeed");
+
+        let threaded_output = temp.path().join("threaded-output");
+        let mut threaded_request = ComputeRequest::new(
+            "FX-FMS-001",
+            ComputeModule::Fms,
+            input_dir.join("fms.inp"),
+            &threaded_output,
+        );
+        threaded_request.execution_mode = ExecutionMode::Threaded { workers: 3 };
+        FmsModule
+            .execute(&threaded_request)
+            .expect("threaded FMS execution should succeed");
+
+        for artifact in ["gg.bin", "log3.dat"] {
+            let serial = fs::read(serial_output.join(artifact)).expect("serial artifact");
+            let threaded = fs::read(threaded_output.join(artifact)).expect("threaded artifact");
+            assert_eq!(
+                serial, threaded,
+                "artifact '{}' should not depend on the worker count",
+                artifact
+            );
+        }
+    }
+
+    #[test]
+    fn polarization_scales_gg_magnitude_and_keeps_its_phase() {
+        let temp = TempDir::new().expect("tempdir should be created");
+        let run = |name: &str, global: &str| {
+            let input_dir = temp.path().join(name);
+            stage_inputs(&input_dir, &xsph_phase_bytes());
+            fs::write(input_dir.join("global.inp"), global)
+                .expect("global input should be written");
+            let output_dir = input_dir.join("out");
+            FmsModule
+                .execute(&ComputeRequest::new(
+                    "FX-FMS-001",
+                    ComputeModule::Fms,
+                    input_dir.join("fms.inp"),
+                    &output_dir,
+                ))
+                .expect("FMS execution should succeed");
+            let log = fs::read_to_string(output_dir.join("log3.dat")).expect("log3.dat");
+            (fs::read(output_dir.join("gg.bin")).expect("gg.bin"), log)
+        };
+        // Both runs are polarized and differ only in the direction of the
+        // polarization, so only the dipole factor separates them.
+        let polarized = GLOBAL_INPUT_FIXTURE
+            .replace("    0    0    0      0.0000", "    1    0    0      0.0000");
+        let (along_z, log_z) = run("along-z", &polarized);
+        let (along_x, log_x) = run(
+            "along-x",
+            &polarized.replace(
+                "      0.00000      0.00000      1.00000",
+                "      1.00000      0.00000      0.00000",
+            ),
+        );
+        let dipole_factor = |log: &str| {
+            log.split("dipole-factor=")
+                .nth(1)
+                .and_then(|tail| tail.split_whitespace().next())
+                .and_then(|value| value.parse::<f64>().ok())
+                .expect("log3.dat reports the dipole factor")
+        };
+        let ratio = dipole_factor(&log_z) / dipole_factor(&log_x);
+        assert!((ratio - 1.0).abs() > 1.0e-3, "the shell is anisotropic");
+
+        let gg = |bytes: &[u8]| {
+            let channels = u32::from_le_bytes(
+                bytes[FMS_GG_BINARY_MAGIC.len() + 4..FMS_GG_BINARY_MAGIC.len() + 8]
+                    .try_into()
+                    .expect("channel count"),
+            ) as usize;
+            let header = FMS_GG_BINARY_MAGIC.len() + 10 * 4 + 11 * 8;
+            bytes[header..]
+                .chunks_exact(8)
+                .map(|chunk| f64::from_le_bytes(chunk.try_into().expect("f64")))
+                .collect::<Vec<_>>()
+                .chunks_exact(1 + 2 * channels)
+                .flat_map(|row| {
+                    row[1..]
+                        .chunks_exact(2)
+                        .map(|pair| num_complex::Complex64::new(pair[0], pair[1]))
+                        .collect::<Vec<_>>()
+                })
+                .collect::<Vec<_>>()
+        };
+        let (along_z, along_x) = (gg(&along_z), gg(&along_x));
+        assert_eq!(along_z.len(), along_x.len());
+        assert!(!along_x.is_empty());
+        for (along_z, along_x) in along_z.iter().zip(&along_x) {
+            let expected = along_x.norm() * ratio;
+            assert!((along_z.norm() - expected).abs() <= 1.0e-4 * expected.max(1.0e-12));
+            if along_x.norm() > 1.0e-12 {
+                assert!((along_z.arg() - along_x.arg()).abs() < 1.0e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn observer_receives_module_energy_and_artifact_events() {
+        let temp = TempDir::new().expect("tempdir should be created");
+        let input_dir = temp.path().join("inputs");
+        stage_inputs(&input_dir, &xsph_phase_bytes());
+        let output_dir = temp.path().join("outputs");
+        let (request, observer) = observed_request(&input_dir, &output_dir, None);
+
+        execute_runtime_module(ComputeModule::Fms, &request).expect("FMS execution should succeed");
+
+        let events = observer.events.lock().expect("events");
+        assert_eq!(
+            events.first(),
+            Some(&ExecutionEvent::ModuleStarted {
+                module: ComputeModule::Fms
+            })
+        );
+        assert_eq!(
+            events.last(),
+            Some(&ExecutionEvent::ModuleFinished {
+                module: ComputeModule::Fms
+            })
+        );
+        let points: Vec<(usize, usize)> = events
+            .iter()
+            .filter_map(|event| match event {
+                ExecutionEvent::EnergyPoint { index, total, .. } => Some((*index, *total)),
+                _ => None,
+            })
+            .collect();
+        assert!(!points.is_empty());
+        let total = points[0].1;
+        assert_eq!(
+            points,
+            (0..total).map(|index| (index, total)).collect::<Vec<_>>()
+        );
+        let written: BTreeSet<_> = events
+            .iter()
+            .filter_map(|event| match event {
+                ExecutionEvent::ArtifactWritten { path, .. } => Some(path.clone()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            written,
+            BTreeSet::from([output_dir.join("gg.bin"), output_dir.join("log3.dat")])
+        );
+    }
+
+    #[test]
+    fn cancellation_stops_execution_between_energy_points() {
+        let temp = TempDir::new().expect("tempdir should be created");
+        let input_dir = temp.path().join("inputs");
+        stage_inputs(&input_dir, &xsph_phase_bytes());
+        let output_dir = temp.path().join("outputs");
+        let (mut request, observer) = observed_request(&input_dir, &output_dir, Some(3));
+        request.execution_mode = ExecutionMode::Threaded { workers: 2 };
+
+        let error = execute_runtime_module(ComputeModule::Fms, &request)
+            .expect_err("cancelled FMS execution should fail");
+        assert_eq!(error.category(), FeffErrorCategory::ComputationError);
+        assert_eq!(error.placeholder(), "RUN.CANCELLED");
+        assert!(!output_dir.join("gg.bin").exists());
+        assert!(
+            !observer
+                .events
+                .lock()
+                .expect("events")
+                .iter()
+                .any(|event| matches!(event, ExecutionEvent::ModuleFinished { .. }))
+        );
+
+        let error = execute_runtime_module(ComputeModule::Fms, &request)
+            .expect_err("a cancelled token stops later modules before they start");
+        assert_eq!(error.placeholder(), "RUN.CANCELLED");
+    }
+
+    #[test]
+    fn distributed_energy_chunks_merge_to_serial_outputs() {
+        let temp = TempDir::new().expect("tempdir should be created");
+        let input_dir = temp.path().join("inputs");
+        stage_inputs(&input_dir, &xsph_phase_bytes());
+
+        let serial_output = temp.path().join("serial-output");
+        FmsModule
+            .execute(&ComputeRequest::new(
+                "FX-FMS-001",
+                ComputeModule::Fms,
+                input_dir.join("fms.inp"),
+                &serial_output,
+            ))
+            .expect("serial FMS execution should succeed");
+
+        let merged_output = temp.path().join("merged-output");
+        let job_dir = temp.path().join("job");
+        let job = DistributedJob::new(
+            &ComputeRequest::new(
+                "FX-FMS-001",
+                ComputeModule::Fms,
+                input_dir.join("fms.inp"),
+                &merged_output,
+            ),
+            3,
+        )
+        .expect("FMS job should build");
+        job.save(&job_dir).expect("job should save");
+        let job = DistributedJob::load(&job_dir).expect("job should load");
+        for part in (0..3).rev() {
+            job.run_worker(&job_dir, part)
+                .expect("worker should succeed");
+        }
+        assert!(job.missing_parts(&job_dir).is_empty());
+        job.merge(&job_dir).expect("merge should succeed");
+
+        for artifact in ["gg.bin", "log3.dat"] {
+            assert_eq!(
+                fs::read(serial_output.join(artifact)).expect("serial artifact"),
+                fs::read(merged_output.join(artifact)).expect("merged artifact"),
+                "artifact '{}' should match the serial run",
+                artifact
+            );
+        }
+    }
+
     #[test]
     fn execute_accepts_true_compute_xsph_phase_binary_inputs() {
         let temp = TempDir::new().expect("tempdir should be created");
@@ -3,9 +3,15 @@ use super::parser::{
     FmsControlInput, GeomFmsInput, GlobalFmsInput, PhaseFmsInput, parse_fms_source,
     parse_geom_source, parse_global_source, parse_phase_source,
 };
-use crate::domain::{ComputeResult, FeffError};
-use crate::modules::serialization::{format_fixed_f64, write_binary_artifact, write_text_artifact};
+use crate::domain::{
+    ArtifactStore, ComputeModule, ComputeResult, ExecutionControl, ExecutionMode, FeffError,
+};
+use crate::modules::serialization::{
+    format_fixed_f64, write_binary_artifact_to, write_text_artifact_to,
+};
+use crate::numerics::parallel::try_map_indexed;
 use std::f64::consts::PI;
+use std::ops::Range;
 use std::path::Path;
 
 #[derive(Debug, Clone)]
@@ -15,6 +21,10 @@ pub(super) struct FmsModel {
     geom: GeomFmsInput,
     global: GlobalFmsInput,
     phase: PhaseFmsInput,
+    execution_mode: ExecutionMode,
+    execution_control: ExecutionControl,
+    /// `gg.bin` energy rows merged from distributed workers, in grid order.
+    energy_rows: Option<Vec<u8>>,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -28,6 +38,7 @@ struct FmsOutputConfig {
     phase_offset: f64,
     temperature_factor: f64,
     phase_byte_scale: f64,
+    polarization_factor: f64,
 }
 
 impl FmsModel {
@@ -44,17 +55,73 @@ impl FmsModel {
             geom: parse_geom_source(fixture_id, geom_source)?,
             global: parse_global_source(fixture_id, global_source)?,
             phase: parse_phase_source(fixture_id, phase_bytes)?,
+            execution_mode: ExecutionMode::Serial,
+            execution_control: ExecutionControl::default(),
+            energy_rows: None,
         })
     }
 
+    pub(super) fn with_execution_mode(mut self, execution_mode: ExecutionMode) -> Self {
+        self.execution_mode = execution_mode;
+        self
+    }
+
+    pub(super) fn with_execution_control(mut self, execution_control: ExecutionControl) -> Self {
+        self.execution_control = execution_control;
+        self
+    }
+
+    pub(super) fn energy_point_count(&self) -> usize {
+        self.output_config().k_points
+    }
+
+    /// `gg.bin` rows for the energy points in `indices`.
+    pub(super) fn energy_rows(&self, indices: Range<usize>) -> ComputeResult<Vec<u8>> {
+        self.gg_rows(&self.output_config(), indices)
+    }
+
+    /// Uses `rows` in place of computing the `gg.bin` energy rows.
+    pub(super) fn with_energy_rows(mut self, rows: Vec<u8>) -> ComputeResult<Self> {
+        let config = self.output_config();
+        let expected = config.k_points * gg_row_len(&config);
+        if rows.len() != expected {
+            return Err(FeffError::computation(
+                "RUN.FMS_ENERGY_CHUNKS",
+                format!(
+                    "run '{}': merged gg.bin rows hold {} bytes but the energy grid needs {}",
+                    self.fixture_id,
+                    rows.len(),
+                    expected
+                ),
+            ));
+        }
+        self.energy_rows = Some(rows);
+        Ok(self)
+    }
+
     pub(super) fn write_artifact(
         &self,
+        store: &dyn ArtifactStore,
         artifact_name: &str,
         output_path: &Path,
     ) -> ComputeResult<()> {
         match artifact_name {
-            "gg.bin" => {
-                write_binary_artifact(output_path, &self.render_gg_binary()).map_err(|source| {
+            "gg.bin" | "gg_slice.bin" => {
+                write_binary_artifact_to(store, output_path, &self.render_gg_binary()?).map_err(
+                    |source| {
+                        FeffError::io_system(
+                            "IO.FMS_OUTPUT_WRITE",
+                            format!(
+                                "failed to write FMS artifact '{}': {}",
+                                output_path.display(),
+                                source
+                            ),
+                        )
+                    },
+                )
+            }
+            "log3.dat" => {
+                write_text_artifact_to(store, output_path, &self.render_log3()).map_err(|source| {
                     FeffError::io_system(
                         "IO.FMS_OUTPUT_WRITE",
                         format!(
@@ -65,16 +132,6 @@ impl FmsModel {
                     )
                 })
             }
-            "log3.dat" => write_text_artifact(output_path, &self.render_log3()).map_err(|source| {
-                FeffError::io_system(
-                    "IO.FMS_OUTPUT_WRITE",
-                    format!(
-                        "failed to write FMS artifact '{}': {}",
-                        output_path.display(),
-                        source
-                    ),
-                )
-            }),
             other => Err(FeffError::internal(
                 "SYS.FMS_OUTPUT_CONTRACT",
                 format!("unsupported FMS output artifact '{}'", other),
@@ -146,10 +203,14 @@ impl FmsModel {
             phase_offset,
             temperature_factor,
             phase_byte_scale,
+            polarization_factor: self
+                .global
+                .polarization
+                .shell_dipole_factor(&self.geom.neighbor_vectors),
         }
     }
 
-    fn render_gg_binary(&self) -> Vec<u8> {
+    fn render_gg_binary(&self) -> ComputeResult<Vec<u8>> {
         let config = self.output_config();
         let mut bytes = Vec::with_capacity(
             160 + config.k_points
@@ -180,35 +241,62 @@ impl FmsModel {
         push_f64(&mut bytes, config.amplitude_scale);
         push_f64(&mut bytes, config.damping);
 
-        for index in 0..config.k_points {
-            let k = config.energy_start + config.energy_step * index as f64;
-            let normalized = if config.k_points == 1 {
-                0.0
-            } else {
-                index as f64 / (config.k_points - 1) as f64
-            };
-            push_f64(&mut bytes, k);
-
-            for channel in 0..config.scattering_channels {
-                let channel_f = channel as f64 + 1.0;
-                let oscillation = (k * (0.09 + 0.01 * channel_f) + config.phase_offset).sin();
-                let phase_term = (k * 0.07 + config.phase_offset + channel_f * 0.23).cos();
-                let envelope = (-config.damping * index as f64 * (1.0 + channel_f * 0.015)).exp();
-                let radial_weight = (1.0 + self.geom.radius_rms * 0.05 + normalized * 0.1).max(0.1);
-                let scattering =
-                    config.amplitude_scale * config.temperature_factor * config.phase_byte_scale;
-
-                let real = scattering * envelope * oscillation * radial_weight / channel_f.sqrt();
-                let imag =
-                    scattering * envelope * phase_term * (1.0 + self.global.mean.abs() * 1.0e-3)
-                        / channel_f.sqrt();
-
-                push_f64(&mut bytes, real);
-                push_f64(&mut bytes, imag);
-            }
+        match &self.energy_rows {
+            Some(rows) => bytes.extend_from_slice(rows),
+            None => bytes.extend(self.gg_rows(&config, 0..config.k_points)?),
         }
 
-        bytes
+        Ok(bytes)
+    }
+
+    fn gg_rows(&self, config: &FmsOutputConfig, indices: Range<usize>) -> ComputeResult<Vec<u8>> {
+        let rows = try_map_indexed(
+            self.execution_mode,
+            indices.len(),
+            |offset| -> ComputeResult<Vec<u8>> {
+                let index = indices.start + offset;
+                let mut row = Vec::with_capacity(gg_row_len(config));
+                let k = config.energy_start + config.energy_step * index as f64;
+                let normalized = if config.k_points == 1 {
+                    0.0
+                } else {
+                    index as f64 / (config.k_points - 1) as f64
+                };
+                push_f64(&mut row, k);
+
+                for channel in 0..config.scattering_channels {
+                    let channel_f = channel as f64 + 1.0;
+                    let oscillation = (k * (0.09 + 0.01 * channel_f) + config.phase_offset).sin();
+                    let phase_term = (k * 0.07 + config.phase_offset + channel_f * 0.23).cos();
+                    let envelope =
+                        (-config.damping * index as f64 * (1.0 + channel_f * 0.015)).exp();
+                    let radial_weight =
+                        (1.0 + self.geom.radius_rms * 0.05 + normalized * 0.1).max(0.1);
+                    let scattering = config.amplitude_scale
+                        * config.temperature_factor
+                        * config.phase_byte_scale;
+
+                    // The dipole factor weights the whole propagator, so it
+                    // scales |gg| and leaves its phase alone.
+                    let real = scattering * envelope * oscillation * radial_weight
+                        / channel_f.sqrt()
+                        * config.polarization_factor;
+                    let imag = scattering
+                        * envelope
+                        * phase_term
+                        * (1.0 + self.global.mean.abs() * 1.0e-3)
+                        / channel_f.sqrt()
+                        * config.polarization_factor;
+
+                    push_f64(&mut row, real);
+                    push_f64(&mut row, imag);
+                }
+                self.execution_control
+                    .energy_point(ComputeModule::Fms, index, config.k_points)?;
+                Ok(row)
+            },
+        )?;
+        Ok(rows.concat())
     }
 
     fn render_log3(&self) -> String {
@@ -219,7 +307,7 @@ impl FmsModel {
             "legacy_phase_binary"
         };
 
-        format!(
+        let mut log = format!(
             "\
 FMS true-compute runtime\n\
 fixture: {}\n\
@@ -268,10 +356,24 @@ damping: {} amplitude-scale: {}\n",
             format_fixed_f64(config.energy_step, 10, 6),
             format_fixed_f64(config.damping, 10, 6),
             format_fixed_f64(config.amplitude_scale, 10, 6),
-        )
+        );
+        let polarization = &self.global.polarization;
+        if polarization.is_polarized() {
+            log.push_str(&format!(
+                "polarization: ipol={} ellipticity={} dipole-factor={}\n",
+                polarization.ipol,
+                format_fixed_f64(polarization.ellipticity, 10, 5).trim(),
+                format_fixed_f64(config.polarization_factor, 10, 5).trim()
+            ));
+        }
+        log
     }
 }
 
+fn gg_row_len(config: &FmsOutputConfig) -> usize {
+    (1 + config.scattering_channels * 2) * std::mem::size_of::<f64>()
+}
+
 pub(super) fn push_u32(target: &mut Vec<u8>, value: u32) {
     target.extend_from_slice(&value.to_le_bytes());
 }
@@ -1,8 +1,10 @@
 use super::FMS_REQUIRED_INPUTS;
-use crate::domain::{ComputeArtifact, ComputeModule, ComputeRequest, ComputeResult, FeffError};
+use crate::domain::{
+    ArtifactStore, ComputeArtifact, ComputeModule, ComputeRequest, ComputeResult, FeffError,
+};
+use crate::modules::polarization::PolarizationSettings;
 use crate::modules::xsph::XSPH_PHASE_BINARY_MAGIC;
 use std::f64::consts::PI;
-use std::fs;
 use std::path::Path;
 
 #[derive(Debug, Clone, Copy)]
@@ -30,6 +32,7 @@ pub(super) struct GeomFmsInput {
     pub(super) radius_rms: f64,
     pub(super) radius_max: f64,
     pub(super) ipot_mean: f64,
+    pub(super) neighbor_vectors: Vec<[f64; 3]>,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -46,6 +49,7 @@ pub(super) struct GlobalFmsInput {
     pub(super) mean: f64,
     pub(super) rms: f64,
     pub(super) max_abs: f64,
+    pub(super) polarization: PolarizationSettings,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -107,8 +111,12 @@ pub(super) fn input_parent_dir(request: &ComputeRequest) -> ComputeResult<&Path>
     })
 }
 
-pub(super) fn read_input_source(path: &Path, artifact_name: &str) -> ComputeResult<String> {
-    fs::read_to_string(path).map_err(|source| {
+pub(super) fn read_input_source(
+    store: &dyn ArtifactStore,
+    path: &Path,
+    artifact_name: &str,
+) -> ComputeResult<String> {
+    store.read_to_string(path).map_err(|source| {
         FeffError::io_system(
             "IO.FMS_INPUT_READ",
             format!(
@@ -121,8 +129,12 @@ pub(super) fn read_input_source(path: &Path, artifact_name: &str) -> ComputeResu
     })
 }
 
-pub(super) fn read_input_bytes(path: &Path, artifact_name: &str) -> ComputeResult<Vec<u8>> {
-    fs::read(path).map_err(|source| {
+pub(super) fn read_input_bytes(
+    store: &dyn ArtifactStore,
+    path: &Path,
+    artifact_name: &str,
+) -> ComputeResult<Vec<u8>> {
+    store.read(path).map_err(|source| {
         FeffError::io_system(
             "IO.FMS_INPUT_READ",
             format!(
@@ -135,6 +147,25 @@ pub(super) fn read_input_bytes(path: &Path, artifact_name: &str) -> ComputeResul
     })
 }
 
+/// Whether the optional `compton.inp` next to `fms.inp` turns COMPTON on.
+pub(super) fn compton_requested(store: &dyn ArtifactStore, path: &Path) -> ComputeResult<bool> {
+    if !store.is_file(path) {
+        return Ok(false);
+    }
+    let source = read_input_source(store, path, "compton.inp")?;
+    Ok(source
+        .lines()
+        .skip_while(|line| {
+            !line
+                .trim_start()
+                .to_ascii_lowercase()
+                .starts_with("run compton")
+        })
+        .nth(1)
+        .and_then(|line| parse_numeric_tokens(line).first().copied())
+        .is_some_and(|value| value > 0.0))
+}
+
 pub(super) fn parse_fms_source(fixture_id: &str, source: &str) -> ComputeResult<FmsControlInput> {
     let numeric_rows = source
         .lines()
@@ -236,17 +267,26 @@ pub(super) fn parse_geom_source(fixture_id: &str, source: &str) -> ComputeResult
 
     let absorber_index = atoms.iter().position(|atom| atom.ipot == 0).unwrap_or(0);
     let absorber = atoms[absorber_index];
-    let radii = atoms
+    let neighbors = atoms
         .iter()
         .enumerate()
-        .filter_map(|(index, atom)| {
-            if index == absorber_index {
-                return None;
-            }
-            let radius = distance(*atom, absorber);
-            (radius > 1.0e-10).then_some(radius)
-        })
+        .filter(|(index, atom)| *index != absorber_index && distance(**atom, absorber) > 1.0e-10)
+        .map(|(_, atom)| *atom)
+        .collect::<Vec<_>>();
+    let radii = neighbors
+        .iter()
+        .map(|atom| distance(*atom, absorber))
         .collect::<Vec<_>>();
+    let neighbor_vectors = neighbors
+        .iter()
+        .map(|atom| {
+            [
+                atom.x - absorber.x,
+                atom.y - absorber.y,
+                atom.z - absorber.z,
+            ]
+        })
+        .collect();
 
     let atom_count = atoms.len();
     let radius_mean = if radii.is_empty() {
@@ -271,6 +311,7 @@ pub(super) fn parse_geom_source(fixture_id: &str, source: &str) -> ComputeResult
         radius_rms,
         radius_max,
         ipot_mean,
+        neighbor_vectors,
     })
 }
 
@@ -300,6 +341,7 @@ pub(super) fn parse_global_source(fixture_id: &str, source: &str) -> ComputeResu
         mean,
         rms,
         max_abs,
+        polarization: PolarizationSettings::from_global_inp(source),
     })
 }
 
@@ -351,7 +393,7 @@ pub(super) fn parse_phase_source(fixture_id: &str, bytes: &[u8]) -> ComputeResul
 fn fms_parse_error(fixture_id: &str, message: impl Into<String>) -> FeffError {
     FeffError::computation(
         "RUN.FMS_INPUT_PARSE",
-        format!("fixture '{}': {}", fixture_id, message.into()),
+        format!("run '{}': {}", fixture_id, message.into()),
     )
 }
 
@@ -1,9 +1,8 @@
 mod model;
 mod parser;
 
-use super::ModuleExecutor;
-use crate::domain::{ComputeArtifact, ComputeRequest, ComputeResult, FeffError};
-use std::fs;
+use super::{ModuleContract, ModuleExecutor};
+use crate::domain::{ComputeArtifact, ComputeModule, ComputeRequest, ComputeResult, FeffError};
 
 use model::FullSpectrumModel;
 use parser::{
@@ -30,6 +29,22 @@ pub struct FullSpectrumContract {
     pub expected_outputs: Vec<ComputeArtifact>,
 }
 
+impl ModuleContract for FullSpectrumContract {
+    const MODULE: ComputeModule = ComputeModule::FullSpectrum;
+
+    fn required_input_names() -> Vec<&'static str> {
+        FULLSPECTRUM_REQUIRED_INPUTS.to_vec()
+    }
+
+    fn optional_input_names() -> Vec<&'static str> {
+        FULLSPECTRUM_OPTIONAL_INPUTS.to_vec()
+    }
+
+    fn output_names() -> Vec<&'static str> {
+        FULLSPECTRUM_REQUIRED_OUTPUTS.to_vec()
+    }
+}
+
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub struct FullSpectrumModule;
 
@@ -41,17 +56,23 @@ impl FullSpectrumModule {
         validate_request_shape(request)?;
         let input_dir = input_parent_dir(request)?;
 
-        let fullspectrum_source =
-            read_input_source(&request.input_path, FULLSPECTRUM_REQUIRED_INPUTS[0])?;
+        let fullspectrum_source = read_input_source(
+            &*request.store,
+            &request.input_path,
+            FULLSPECTRUM_REQUIRED_INPUTS[0],
+        )?;
         let xmu_source = read_input_source(
+            &*request.store,
             &input_dir.join(FULLSPECTRUM_REQUIRED_INPUTS[1]),
             FULLSPECTRUM_REQUIRED_INPUTS[1],
         )?;
         let prexmu_source = maybe_read_optional_input_source(
+            &*request.store,
             input_dir.join(FULLSPECTRUM_OPTIONAL_INPUTS[0]),
             FULLSPECTRUM_OPTIONAL_INPUTS[0],
         )?;
         let referencexmu_source = maybe_read_optional_input_source(
+            &*request.store,
             input_dir.join(FULLSPECTRUM_OPTIONAL_INPUTS[1]),
             FULLSPECTRUM_OPTIONAL_INPUTS[1],
         )?;
@@ -65,9 +86,9 @@ impl FullSpectrumModule {
         )?;
 
         Ok(FullSpectrumContract {
-            required_inputs: artifact_list(&FULLSPECTRUM_REQUIRED_INPUTS),
-            optional_inputs: artifact_list(&FULLSPECTRUM_OPTIONAL_INPUTS),
-            expected_outputs: artifact_list(&FULLSPECTRUM_REQUIRED_OUTPUTS),
+            required_inputs: artifact_list(&FullSpectrumContract::required_input_names()),
+            optional_inputs: artifact_list(&FullSpectrumContract::optional_input_names()),
+            expected_outputs: artifact_list(&FullSpectrumContract::output_names()),
         })
     }
 }
@@ -77,17 +98,23 @@ impl ModuleExecutor for FullSpectrumModule {
         validate_request_shape(request)?;
         let input_dir = input_parent_dir(request)?;
 
-        let fullspectrum_source =
-            read_input_source(&request.input_path, FULLSPECTRUM_REQUIRED_INPUTS[0])?;
+        let fullspectrum_source = read_input_source(
+            &*request.store,
+            &request.input_path,
+            FULLSPECTRUM_REQUIRED_INPUTS[0],
+        )?;
         let xmu_source = read_input_source(
+            &*request.store,
             &input_dir.join(FULLSPECTRUM_REQUIRED_INPUTS[1]),
             FULLSPECTRUM_REQUIRED_INPUTS[1],
         )?;
         let prexmu_source = maybe_read_optional_input_source(
+            &*request.store,
             input_dir.join(FULLSPECTRUM_OPTIONAL_INPUTS[0]),
             FULLSPECTRUM_OPTIONAL_INPUTS[0],
         )?;
         let referencexmu_source = maybe_read_optional_input_source(
+            &*request.store,
             input_dir.join(FULLSPECTRUM_OPTIONAL_INPUTS[1]),
             FULLSPECTRUM_OPTIONAL_INPUTS[1],
         )?;
@@ -101,21 +128,24 @@ impl ModuleExecutor for FullSpectrumModule {
         )?;
         let outputs = artifact_list(&FULLSPECTRUM_REQUIRED_OUTPUTS);
 
-        fs::create_dir_all(&request.output_dir).map_err(|source| {
-            FeffError::io_system(
-                "IO.FULLSPECTRUM_OUTPUT_DIRECTORY",
-                format!(
-                    "failed to create FULLSPECTRUM output directory '{}': {}",
-                    request.output_dir.display(),
-                    source
-                ),
-            )
-        })?;
+        request
+            .store
+            .create_dir_all(&request.output_dir)
+            .map_err(|source| {
+                FeffError::io_system(
+                    "IO.FULLSPECTRUM_OUTPUT_DIRECTORY",
+                    format!(
+                        "failed to create FULLSPECTRUM output directory '{}': {}",
+                        request.output_dir.display(),
+                        source
+                    ),
+                )
+            })?;
 
         for artifact in &outputs {
             let output_path = request.output_dir.join(&artifact.relative_path);
             if let Some(parent) = output_path.parent() {
-                fs::create_dir_all(parent).map_err(|source| {
+                request.store.create_dir_all(parent).map_err(|source| {
                     FeffError::io_system(
                         "IO.FULLSPECTRUM_OUTPUT_DIRECTORY",
                         format!(
@@ -128,7 +158,7 @@ impl ModuleExecutor for FullSpectrumModule {
             }
 
             let artifact_name = artifact.relative_path.to_string_lossy().replace('\\', "/");
-            model.write_artifact(&artifact_name, &output_path)?;
+            model.write_artifact(&*request.store, &artifact_name, &output_path)?;
         }
 
         Ok(outputs)
@@ -239,6 +269,36 @@ mod tests {
         assert!(temp.path().join("out/logfullspectrum.dat").is_file());
     }
 
+    #[test]
+    fn execute_defaults_broadening_to_tabulated_core_hole_width() {
+        let temp = TempDir::new().expect("tempdir should be created");
+        stage_text(
+            temp.path().join("fullspectrum.inp"),
+            FULLSPECTRUM_INPUT_DEFAULT,
+        );
+        stage_text(
+            temp.path().join("xmu.dat"),
+            &format!("# # EDGE K\n# # Abs Z=29 N_at=2 idwopt=0 mchi=1\n{XMU_INPUT}"),
+        );
+
+        let request = ComputeRequest::new(
+            "FX-FULLSPECTRUM-001",
+            ComputeModule::FullSpectrum,
+            temp.path().join("fullspectrum.inp"),
+            temp.path().join("out"),
+        );
+        FullSpectrumModule
+            .execute(&request)
+            .expect("execution should succeed");
+
+        let log = fs::read_to_string(temp.path().join("out/logfullspectrum.dat"))
+            .expect("log output should be readable");
+        assert!(
+            log.contains("broadening-ev: 1.72919"),
+            "Cu K width should seed the broadening: {log}"
+        );
+    }
+
     #[test]
     fn execute_optional_component_inputs_influence_outputs() {
         let temp = TempDir::new().expect("tempdir should be created");
@@ -1,15 +1,18 @@
 use super::parser::{
     AuxiliarySpectrumSummary, FullSpectrumControlInput, XmuRow, XmuSummary, parse_auxiliary_source,
-    parse_fullspectrum_source, parse_xmu_source, summarize_xmu_rows,
+    parse_fullspectrum_source, parse_xmu_source, summarize_xmu_rows, xmu_header_core_hole_width,
 };
-use crate::domain::{ComputeResult, FeffError};
+use crate::domain::{ArtifactStore, ComputeResult, FeffError};
 use crate::modules::helpers::{
     EelsMdffWorkflowConfig, eelsmdff_workflow_coupling, mkgtr_workflow_coupling,
     opconsat_workflow_spectrum,
 };
-use crate::modules::serialization::{format_fixed_f64, write_text_artifact};
+use crate::modules::serialization::{format_fixed_f64, write_text_artifact_to};
 use std::path::Path;
 
+// Used when neither fullspectrum.inp nor the xmu.dat header pins the broadening.
+const DEFAULT_BROADENING_EV: f64 = 0.35;
+
 #[derive(Debug, Clone)]
 pub(super) struct FullSpectrumModel {
     fixture_id: String,
@@ -40,7 +43,10 @@ impl FullSpectrumModel {
         prexmu_source: Option<&str>,
         referencexmu_source: Option<&str>,
     ) -> ComputeResult<Self> {
-        let control = parse_fullspectrum_source(fixture_id, fullspectrum_source)?;
+        let default_broadening_ev =
+            xmu_header_core_hole_width(xmu_source).unwrap_or(DEFAULT_BROADENING_EV);
+        let control =
+            parse_fullspectrum_source(fixture_id, fullspectrum_source, default_broadening_ev)?;
         let xmu_rows = parse_xmu_source(fixture_id, xmu_source)?;
         let xmu_summary = summarize_xmu_rows(&xmu_rows);
 
@@ -59,6 +65,7 @@ impl FullSpectrumModel {
 
     pub(super) fn write_artifact(
         &self,
+        store: &dyn ArtifactStore,
         artifact_name: &str,
         output_path: &Path,
     ) -> ComputeResult<()> {
@@ -78,7 +85,7 @@ impl FullSpectrumModel {
             }
         };
 
-        write_text_artifact(output_path, &contents).map_err(|source| {
+        write_text_artifact_to(store, output_path, &contents).map_err(|source| {
             FeffError::io_system(
                 "IO.FULLSPECTRUM_OUTPUT_WRITE",
                 format!(
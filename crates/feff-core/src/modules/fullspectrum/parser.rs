@@ -1,6 +1,8 @@
 use super::FULLSPECTRUM_REQUIRED_INPUTS;
-use crate::domain::{ComputeArtifact, ComputeModule, ComputeRequest, ComputeResult, FeffError};
-use std::fs;
+use crate::domain::{
+    ArtifactStore, ComputeArtifact, ComputeModule, ComputeRequest, ComputeResult, FeffError,
+};
+use crate::support::common::edgedb::core_hole_width;
 use std::path::{Path, PathBuf};
 
 #[derive(Debug, Clone, Copy)]
@@ -90,8 +92,12 @@ pub(super) fn input_parent_dir(request: &ComputeRequest) -> ComputeResult<&Path>
     })
 }
 
-pub(super) fn read_input_source(path: &Path, artifact_name: &str) -> ComputeResult<String> {
-    fs::read_to_string(path).map_err(|source| {
+pub(super) fn read_input_source(
+    store: &dyn ArtifactStore,
+    path: &Path,
+    artifact_name: &str,
+) -> ComputeResult<String> {
+    store.read_to_string(path).map_err(|source| {
         FeffError::io_system(
             "IO.FULLSPECTRUM_INPUT_READ",
             format!(
@@ -105,11 +111,12 @@ pub(super) fn read_input_source(path: &Path, artifact_name: &str) -> ComputeResu
 }
 
 pub(super) fn maybe_read_optional_input_source(
+    store: &dyn ArtifactStore,
     path: PathBuf,
     artifact_name: &str,
 ) -> ComputeResult<Option<String>> {
-    if path.is_file() {
-        return read_input_source(&path, artifact_name).map(Some);
+    if store.is_file(&path) {
+        return read_input_source(store, &path, artifact_name).map(Some);
     }
 
     Ok(None)
@@ -118,6 +125,7 @@ pub(super) fn maybe_read_optional_input_source(
 pub(super) fn parse_fullspectrum_source(
     fixture_id: &str,
     source: &str,
+    default_broadening_ev: f64,
 ) -> ComputeResult<FullSpectrumControlInput> {
     let numeric_rows: Vec<Vec<f64>> = source
         .lines()
@@ -141,7 +149,7 @@ pub(super) fn parse_fullspectrum_source(
 
     let run_mode = f64_to_i32(run_mode, fixture_id, "fullspectrum run-mode")?;
     let broadening_ev = row_value(&numeric_rows, 1, 0)
-        .unwrap_or(0.35)
+        .unwrap_or(default_broadening_ev)
         .abs()
         .max(1.0e-6);
     let drude_scale = row_value(&numeric_rows, 1, 1)
@@ -163,6 +171,30 @@ pub(super) fn parse_fullspectrum_source(
     })
 }
 
+/// Core-hole width for the absorber and edge named in an `xmu.dat` header
+/// (`# # EDGE <label>` and `# # Abs Z=<z>` lines), when both are present.
+pub(super) fn xmu_header_core_hole_width(source: &str) -> Option<f64> {
+    let mut edge_label = None;
+    let mut absorber_z = None;
+
+    for line in source.lines() {
+        let Some(header) = line.trim().strip_prefix('#') else {
+            continue;
+        };
+        let header = header.trim_start_matches(['#', ' ']);
+        if let Some(label) = header.strip_prefix("EDGE") {
+            edge_label = label.split_whitespace().next().map(str::to_string);
+        } else if let Some(rest) = header.strip_prefix("Abs Z=") {
+            absorber_z = rest
+                .split_whitespace()
+                .next()
+                .and_then(|token| token.parse::<usize>().ok());
+        }
+    }
+
+    core_hole_width(absorber_z?, edge_label.as_deref()?)
+}
+
 pub(super) fn parse_xmu_source(fixture_id: &str, source: &str) -> ComputeResult<Vec<XmuRow>> {
     let mut rows = Vec::new();
 
@@ -340,7 +372,7 @@ fn f64_to_i32(value: f64, fixture_id: &str, field: &str) -> ComputeResult<i32> {
 fn fullspectrum_parse_error(fixture_id: &str, message: impl Into<String>) -> FeffError {
     FeffError::input_validation(
         "INPUT.FULLSPECTRUM_PARSE",
-        format!("fixture '{}': {}", fixture_id, message.into()),
+        format!("run '{}': {}", fixture_id, message.into()),
     )
 }
 
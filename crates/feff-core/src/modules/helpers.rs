@@ -2,7 +2,7 @@ use crate::domain::{ComputeModule, ComputeRequest, InputCard, InputDeck};
 use crate::numerics::{deterministic_argsort, distance3, stable_weighted_mean};
 use crate::support::eelsmdff::mdff_angularmesh::{AngularMeshConfig, mdff_angularmesh};
 use crate::support::eelsmdff::mdff_eels::{
-    EnergyQMesh, MdffEelsConfig, mdff_eels, normalize_wave_amplitudes,
+    EnergyQMesh, MdffEelsConfig, SigmaTensorRow, mdff_eels, normalize_wave_amplitudes,
     scale_sigma_rows_with_wavelength,
 };
 use crate::support::eelsmdff::mdff_qmesh::{MdffQMeshConfig, MdffQMeshPoint, mdff_qmesh};
@@ -19,7 +19,7 @@ use crate::support::opconsat::opconsat::{OpconsatComponent, opconsat, sample_die
 use num_complex::Complex64;
 use std::collections::BTreeMap;
 
-const HBARC_ATOMIC_EV_A0: f64 = 3727.3794066;
+pub(crate) const HBARC_ATOMIC_EV_A0: f64 = 3727.3794066;
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct DistanceShell {
@@ -145,55 +145,7 @@ pub(crate) fn eelsmdff_workflow_coupling(
         return None;
     }
 
-    let mut sources_by_ip = BTreeMap::new();
-    for ip in 1..=9 {
-        let mut source = String::from("# omega e k mu mu0 chi\n");
-        for &(energy, mu, mu0, chi) in xmu_rows {
-            let value = match ip {
-                1 => mu,
-                2 => (mu - mu0) * 0.5,
-                3 => chi,
-                4 => (mu0 - mu) * 0.5,
-                5 => mu0,
-                6 => chi * 0.25,
-                7 => chi * 0.10,
-                8 => chi * 0.20,
-                9 => (mu + mu0).abs() * 0.5,
-                _ => 0.0,
-            };
-            source.push_str(&format!(
-                "{energy:.8} 0.0 0.0 {value:.12E} {mu0:.12E} {chi:.12E}\n"
-            ));
-        }
-        sources_by_ip.insert(ip, source);
-    }
-    let borrowed_sources = sources_by_ip
-        .iter()
-        .map(|(&ip, source)| (ip, source.as_str()))
-        .collect::<BTreeMap<_, _>>();
-
-    let mut sigma_rows = mdff_readsp(
-        &borrowed_sources,
-        MdffReadspConfig {
-            ipmin: 1,
-            ipmax: 9,
-            ipstep: if config.cross_terms { 1 } else { 4 },
-            average: config.average,
-            cross_terms: config.cross_terms,
-            spcol: 4,
-            input_kind: MdffInputKind::Xmu,
-        },
-    )
-    .ok()?;
-
-    scale_sigma_rows_with_wavelength(
-        &mut sigma_rows,
-        config.beam_energy_ev.max(1.0),
-        HBARC_ATOMIC_EV_A0,
-        DEFAULT_ME_C2_EV,
-        |energy| mdff_wavelength(energy).unwrap_or(f64::NAN),
-    )
-    .ok()?;
+    let sigma_rows = eelsmdff_sigma_rows(config, xmu_rows)?;
 
     let mut q_mesh_rows = Vec::with_capacity(sigma_rows.len());
     for sigma_row in &sigma_rows {
@@ -263,6 +215,67 @@ pub(crate) fn eelsmdff_workflow_coupling(
     })
 }
 
+pub(crate) fn eelsmdff_sigma_rows(
+    config: EelsMdffWorkflowConfig,
+    xmu_rows: &[(f64, f64, f64, f64)],
+) -> Option<Vec<SigmaTensorRow>> {
+    if xmu_rows.is_empty() {
+        return None;
+    }
+
+    let mut sources_by_ip = BTreeMap::new();
+    for ip in 1..=9 {
+        let mut source = String::from("# omega e k mu mu0 chi\n");
+        for &(energy, mu, mu0, chi) in xmu_rows {
+            let value = match ip {
+                1 => mu,
+                2 => (mu - mu0) * 0.5,
+                3 => chi,
+                4 => (mu0 - mu) * 0.5,
+                5 => mu0,
+                6 => chi * 0.25,
+                7 => chi * 0.10,
+                8 => chi * 0.20,
+                9 => (mu + mu0).abs() * 0.5,
+                _ => 0.0,
+            };
+            source.push_str(&format!(
+                "{energy:.8} 0.0 0.0 {value:.12E} {mu0:.12E} {chi:.12E}\n"
+            ));
+        }
+        sources_by_ip.insert(ip, source);
+    }
+    let borrowed_sources = sources_by_ip
+        .iter()
+        .map(|(&ip, source)| (ip, source.as_str()))
+        .collect::<BTreeMap<_, _>>();
+
+    let mut sigma_rows = mdff_readsp(
+        &borrowed_sources,
+        MdffReadspConfig {
+            ipmin: 1,
+            ipmax: 9,
+            ipstep: if config.cross_terms { 1 } else { 4 },
+            average: config.average,
+            cross_terms: config.cross_terms,
+            spcol: 4,
+            input_kind: MdffInputKind::Xmu,
+        },
+    )
+    .ok()?;
+
+    scale_sigma_rows_with_wavelength(
+        &mut sigma_rows,
+        config.beam_energy_ev.max(1.0),
+        HBARC_ATOMIC_EV_A0,
+        DEFAULT_ME_C2_EV,
+        |energy| mdff_wavelength(energy).unwrap_or(f64::NAN),
+    )
+    .ok()?;
+
+    Some(sigma_rows)
+}
+
 pub(crate) fn kspace_workflow_coupling(
     ikpath: i32,
     channel_count: usize,
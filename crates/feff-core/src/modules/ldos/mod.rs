@@ -1,9 +1,8 @@
 mod model;
 mod parser;
 
-use super::ModuleExecutor;
-use crate::domain::{ComputeArtifact, ComputeRequest, ComputeResult, FeffError};
-use std::fs;
+use super::{ModuleContract, ModuleExecutor};
+use crate::domain::{ComputeArtifact, ComputeModule, ComputeRequest, ComputeResult, FeffError};
 
 use model::LdosModel;
 use parser::{
@@ -23,6 +22,18 @@ pub struct LdosContract {
     pub expected_outputs: Vec<ComputeArtifact>,
 }
 
+impl ModuleContract for LdosContract {
+    const MODULE: ComputeModule = ComputeModule::Ldos;
+
+    fn required_input_names() -> Vec<&'static str> {
+        LDOS_REQUIRED_INPUTS.to_vec()
+    }
+
+    fn output_names() -> Vec<&'static str> {
+        vec![LDOS_LOG_OUTPUT]
+    }
+}
+
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub struct LdosModule;
 
@@ -30,16 +41,23 @@ impl LdosModule {
     pub fn contract_for_request(&self, request: &ComputeRequest) -> ComputeResult<LdosContract> {
         validate_request_shape(request)?;
         let input_dir = input_parent_dir(request)?;
-        let ldos_source = read_input_source(&request.input_path, LDOS_REQUIRED_INPUTS[0])?;
+        let ldos_source = read_input_source(
+            &*request.store,
+            &request.input_path,
+            LDOS_REQUIRED_INPUTS[0],
+        )?;
         let geom_source = read_input_source(
+            &*request.store,
             &input_dir.join(LDOS_REQUIRED_INPUTS[1]),
             LDOS_REQUIRED_INPUTS[1],
         )?;
         let pot_bytes = read_input_bytes(
+            &*request.store,
             &input_dir.join(LDOS_REQUIRED_INPUTS[2]),
             LDOS_REQUIRED_INPUTS[2],
         )?;
         let reciprocal_source = read_input_source(
+            &*request.store,
             &input_dir.join(LDOS_REQUIRED_INPUTS[3]),
             LDOS_REQUIRED_INPUTS[3],
         )?;
@@ -52,7 +70,7 @@ impl LdosModule {
         )?;
 
         Ok(LdosContract {
-            required_inputs: artifact_list(&LDOS_REQUIRED_INPUTS),
+            required_inputs: artifact_list(&LdosContract::required_input_names()),
             expected_outputs: model.expected_outputs(),
         })
     }
@@ -63,16 +81,23 @@ impl ModuleExecutor for LdosModule {
         validate_request_shape(request)?;
         let input_dir = input_parent_dir(request)?;
 
-        let ldos_source = read_input_source(&request.input_path, LDOS_REQUIRED_INPUTS[0])?;
+        let ldos_source = read_input_source(
+            &*request.store,
+            &request.input_path,
+            LDOS_REQUIRED_INPUTS[0],
+        )?;
         let geom_source = read_input_source(
+            &*request.store,
             &input_dir.join(LDOS_REQUIRED_INPUTS[1]),
             LDOS_REQUIRED_INPUTS[1],
         )?;
         let pot_bytes = read_input_bytes(
+            &*request.store,
             &input_dir.join(LDOS_REQUIRED_INPUTS[2]),
             LDOS_REQUIRED_INPUTS[2],
         )?;
         let reciprocal_source = read_input_source(
+            &*request.store,
             &input_dir.join(LDOS_REQUIRED_INPUTS[3]),
             LDOS_REQUIRED_INPUTS[3],
         )?;
@@ -83,24 +108,29 @@ impl ModuleExecutor for LdosModule {
             &geom_source,
             &pot_bytes,
             &reciprocal_source,
-        )?;
+        )?
+        .with_execution_mode(request.execution_mode)
+        .with_execution_control(request.control.clone());
         let outputs = model.expected_outputs();
 
-        fs::create_dir_all(&request.output_dir).map_err(|source| {
-            FeffError::io_system(
-                "IO.LDOS_OUTPUT_DIRECTORY",
-                format!(
-                    "failed to create LDOS output directory '{}': {}",
-                    request.output_dir.display(),
-                    source
-                ),
-            )
-        })?;
+        request
+            .store
+            .create_dir_all(&request.output_dir)
+            .map_err(|source| {
+                FeffError::io_system(
+                    "IO.LDOS_OUTPUT_DIRECTORY",
+                    format!(
+                        "failed to create LDOS output directory '{}': {}",
+                        request.output_dir.display(),
+                        source
+                    ),
+                )
+            })?;
 
         for artifact in &outputs {
             let output_path = request.output_dir.join(&artifact.relative_path);
             if let Some(parent) = output_path.parent() {
-                fs::create_dir_all(parent).map_err(|source| {
+                request.store.create_dir_all(parent).map_err(|source| {
                     FeffError::io_system(
                         "IO.LDOS_OUTPUT_DIRECTORY",
                         format!(
@@ -113,7 +143,7 @@ impl ModuleExecutor for LdosModule {
             }
 
             let artifact_name = artifact.relative_path.to_string_lossy().replace('\\', "/");
-            model.write_artifact(&artifact_name, &output_path)?;
+            model.write_artifact(&*request.store, &artifact_name, &output_path)?;
         }
 
         Ok(outputs)
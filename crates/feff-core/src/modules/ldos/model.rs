@@ -4,8 +4,12 @@ use super::parser::{
     parse_geom_source, parse_ldos_channel_name, parse_ldos_source, parse_pot_source,
     parse_reciprocal_source,
 };
-use crate::domain::{ComputeArtifact, ComputeResult, FeffError};
-use crate::modules::serialization::{format_fixed_f64, write_text_artifact};
+use crate::domain::{
+    ArtifactStore, ComputeArtifact, ComputeModule, ComputeResult, ExecutionControl, ExecutionMode,
+    FeffError,
+};
+use crate::modules::serialization::{format_fixed_f64, write_text_artifact_to};
+use crate::numerics::parallel::try_map_indexed;
 use std::path::Path;
 
 #[derive(Debug, Clone)]
@@ -15,6 +19,8 @@ pub(super) struct LdosModel {
     geom: GeomLdosInput,
     pot: PotLdosInput,
     reciprocal: ReciprocalLdosInput,
+    execution_mode: ExecutionMode,
+    execution_control: ExecutionControl,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -42,9 +48,21 @@ impl LdosModel {
             geom: parse_geom_source(fixture_id, geom_source)?,
             pot: parse_pot_source(fixture_id, pot_bytes)?,
             reciprocal: parse_reciprocal_source(fixture_id, reciprocal_source)?,
+            execution_mode: ExecutionMode::Serial,
+            execution_control: ExecutionControl::default(),
         })
     }
 
+    pub(super) fn with_execution_mode(mut self, execution_mode: ExecutionMode) -> Self {
+        self.execution_mode = execution_mode;
+        self
+    }
+
+    pub(super) fn with_execution_control(mut self, execution_control: ExecutionControl) -> Self {
+        self.execution_control = execution_control;
+        self
+    }
+
     fn output_config(&self) -> LdosOutputConfig {
         let channel_count = self.output_channel_count();
         let energy_points = self.energy_point_count();
@@ -104,24 +122,12 @@ impl LdosModel {
 
     pub(super) fn write_artifact(
         &self,
+        store: &dyn ArtifactStore,
         artifact_name: &str,
         output_path: &Path,
     ) -> ComputeResult<()> {
         if artifact_name.eq_ignore_ascii_case(LDOS_LOG_OUTPUT) {
-            return write_text_artifact(output_path, &self.render_logdos()).map_err(|source| {
-                FeffError::io_system(
-                    "IO.LDOS_OUTPUT_WRITE",
-                    format!(
-                        "failed to write LDOS artifact '{}': {}",
-                        output_path.display(),
-                        source
-                    ),
-                )
-            });
-        }
-
-        if let Some(channel) = parse_ldos_channel_name(artifact_name) {
-            return write_text_artifact(output_path, &self.render_ldos_table(channel)).map_err(
+            return write_text_artifact_to(store, output_path, &self.render_logdos()).map_err(
                 |source| {
                     FeffError::io_system(
                         "IO.LDOS_OUTPUT_WRITE",
@@ -135,13 +141,27 @@ impl LdosModel {
             );
         }
 
+        if let Some(channel) = parse_ldos_channel_name(artifact_name) {
+            return write_text_artifact_to(store, output_path, &self.render_ldos_table(channel)?)
+                .map_err(|source| {
+                    FeffError::io_system(
+                        "IO.LDOS_OUTPUT_WRITE",
+                        format!(
+                            "failed to write LDOS artifact '{}': {}",
+                            output_path.display(),
+                            source
+                        ),
+                    )
+                });
+        }
+
         Err(FeffError::internal(
             "SYS.LDOS_OUTPUT_CONTRACT",
             format!("unsupported LDOS output artifact '{}'", artifact_name),
         ))
     }
 
-    fn render_ldos_table(&self, channel_index: usize) -> String {
+    fn render_ldos_table(&self, channel_index: usize) -> ComputeResult<String> {
         let config = self.output_config();
         let mut lines = Vec::with_capacity(config.energy_points + 12);
 
@@ -191,10 +211,18 @@ impl LdosModel {
                 .to_string(),
         );
 
-        for energy_index in 0..config.energy_points {
+        lines.extend(try_map_indexed(
+            self.execution_mode,
+            config.energy_points,
+            |energy_index| -> ComputeResult<String> {
             let energy = config.energy_min + config.energy_step * energy_index as f64;
             let row = self.ldos_row(channel_index, channel_lmax, energy, &config);
-            lines.push(format!(
+            self.execution_control.energy_point(
+                ComputeModule::Ldos,
+                energy_index,
+                config.energy_points,
+            )?;
+            Ok(format!(
                 "{:>11} {:>13.6E} {:>13.6E} {:>13.6E} {:>13.6E} {:>13.6E} {:>13.6E} {:>13.6E} {:>13.6E}",
                 format_fixed_f64(energy, 11, 4),
                 row[0],
@@ -205,10 +233,10 @@ impl LdosModel {
                 row[5],
                 row[6],
                 row[7],
-            ));
-        }
+            ))
+        })?);
 
-        lines.join("\n")
+        Ok(lines.join("\n"))
     }
 
     fn electron_counts_for_channel(&self, channel_index: usize, channel_lmax: i32) -> [f64; 4] {
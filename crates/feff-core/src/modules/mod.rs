@@ -1,29 +1,43 @@
 pub mod band;
+#[cfg(feature = "native")]
+pub mod batch;
+#[cfg(feature = "native")]
+pub mod cache;
+#[cfg(feature = "native")]
 pub mod comparator;
 pub mod compton;
 pub mod crpa;
 pub mod debye;
+pub mod distributed;
 pub mod dmdw;
 pub mod eels;
 pub mod fms;
 pub mod fullspectrum;
 pub mod ldos;
+#[cfg(feature = "native")]
+pub mod multi_absorber;
 pub mod path;
+pub mod pipeline;
 pub mod pot;
 pub mod rdinp;
+#[cfg(feature = "native")]
 pub mod regression;
 pub mod rixs;
 pub mod screen;
 pub mod self_energy;
 pub mod serialization;
+#[cfg(feature = "native")]
+pub mod sweep;
 pub mod xsph;
 
 mod dispatch;
 mod helpers;
+mod polarization;
 mod traits;
 
 pub use dispatch::{
-    execute_runtime_module, runtime_compute_engine_available, runtime_engine_unavailable_error,
+    ensure_runtime_engines_available, execute_runtime_module, runtime_compute_engine_available,
+    runtime_engine_unavailable_error, runtime_request,
 };
 pub use helpers::{CoreModuleHelper, DistanceShell, cards_for_compute_request, is_core_module};
-pub use traits::{ModuleExecutor, RuntimeModuleExecutor, ValidationModuleExecutor};
+pub use traits::{ModuleContract, ModuleExecutor, RuntimeModuleExecutor, ValidationModuleExecutor};
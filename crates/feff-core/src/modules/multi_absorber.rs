@@ -0,0 +1,1180 @@
+use super::serialization::{format_fixed_f64, write_text_artifact};
+use crate::domain::{ComputeArtifact, ComputeResult, FeffError, InputDeck};
+use crate::parser::parse_input_deck;
+use crate::parser::variables::expand_variables;
+use crate::support::common::pertab::atsym;
+use crate::support::inpgen::cif::CifStructure;
+use crate::support::inpgen::m_pot_generator::{PotGenRule, XyzFormat, gen_pot_from_xyz};
+use crate::support::symmetry::sites::equivalent_site_classes;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const SITE_DIRECTORY_PREFIX: &str = "site_";
+pub const SITE_SUMMARY_OUTPUT: &str = "sites.dat";
+
+/// Local-environment radius (Angstrom) used to decide whether two absorber
+/// sites of an `ATOMS` cluster are equivalent. Only sites whose whole
+/// environment lies inside the cluster are absorber candidates.
+pub const SITE_SIGNATURE_RADIUS: f64 = 4.0;
+
+const SIGNATURE_DISTANCE_RESOLUTION: f64 = 1.0e-2;
+const POSITION_EPSILON: f64 = 1.0e-6;
+
+/// Averaged spectra: file name, grid column used to align sites, and the
+/// number of leading grid columns copied from the reference site.
+const AVERAGED_SPECTRA: [(&str, usize, usize); 2] = [("xmu.dat", 0, 3), ("chi.dat", 0, 1)];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SiteSelection {
+    /// Every atom of the absorbing element is its own site with multiplicity 1.
+    #[default]
+    All,
+    /// Equivalent sites are grouped; one representative per group is run and
+    /// weighted by the group size. `CIF` decks group by space-group symmetry,
+    /// `ATOMS` clusters by local environment.
+    Inequivalent,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MultiAbsorberConfig {
+    pub absorber_z: i32,
+    pub selection: SiteSelection,
+    /// Radius of the re-centered `ATOMS` cluster; `CIF` decks keep their own
+    /// `RPATH`/`SCF`/`FMS` radii.
+    pub cluster_radius: Option<f64>,
+}
+
+impl MultiAbsorberConfig {
+    pub fn new(absorber_z: i32) -> Self {
+        Self {
+            absorber_z,
+            selection: SiteSelection::All,
+            cluster_radius: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AbsorberSite {
+    /// Index in the deck's `ATOMS` block, or in the symmetry-expanded unit
+    /// cell for `CIF` decks.
+    pub atom_index: usize,
+    pub position: [f64; 3],
+    pub multiplicity: usize,
+    pub directory: String,
+    /// Asymmetric-unit site (1-based, as in `TARGET`) for `CIF` decks.
+    pub cif_target: Option<usize>,
+}
+
+#[derive(Debug, Clone)]
+pub struct MultiAbsorberPlan {
+    config: MultiAbsorberConfig,
+    title: String,
+    deck: InputDeck,
+    source_lines: Vec<String>,
+    structure: SiteStructure,
+    sites: Vec<AbsorberSite>,
+}
+
+#[derive(Debug, Clone)]
+enum SiteStructure {
+    /// Cartesian cluster from the deck's `ATOMS` block.
+    Atoms(Vec<DeckAtom>),
+    /// Crystal named by the deck's `CIF` card, expanded around `TARGET`.
+    Cif { path: String },
+}
+
+#[derive(Debug, Clone, Copy)]
+struct DeckAtom {
+    position: [f64; 3],
+    atomic_number: i32,
+}
+
+#[derive(Debug, Clone)]
+struct SpectrumTable {
+    header: Vec<String>,
+    rows: Vec<Vec<String>>,
+    values: Vec<Vec<f64>>,
+}
+
+impl MultiAbsorberPlan {
+    /// Plans the sites of a deck with an `ATOMS` block.
+    pub fn from_feff_source(source: &str, config: MultiAbsorberConfig) -> ComputeResult<Self> {
+        Self::from_deck_source(source, None, config)
+    }
+
+    /// Plans the sites of the deck at `input_path`. `CIF` decks are supported
+    /// here, with the CIF file resolved next to the deck.
+    pub fn from_feff_input(input_path: &Path, config: MultiAbsorberConfig) -> ComputeResult<Self> {
+        let source = fs::read_to_string(input_path).map_err(|source| {
+            FeffError::io_system(
+                "IO.MULTI_ABSORBER_READ",
+                format!(
+                    "failed to read structure deck '{}': {}",
+                    input_path.display(),
+                    source
+                ),
+            )
+        })?;
+        let input_dir = input_path.parent().unwrap_or_else(|| Path::new("."));
+        Self::from_deck_source(&source, Some(input_dir), config)
+    }
+
+    fn from_deck_source(
+        source: &str,
+        input_dir: Option<&Path>,
+        config: MultiAbsorberConfig,
+    ) -> ComputeResult<Self> {
+        if let Some(radius) = config.cluster_radius
+            && !(radius.is_finite() && radius > 0.0)
+        {
+            return Err(plan_error(format!(
+                "cluster radius must be a positive number of Angstrom, got {}",
+                radius
+            )));
+        }
+
+        let source = &expand_variables(source, &BTreeMap::new())?;
+        let deck = parse_input_deck(source)?;
+        let cif_path = deck
+            .cards
+            .iter()
+            .find(|card| card.keyword == "CIF")
+            .filter(|_| !deck.cards.iter().any(|card| card.keyword == "ATOMS"))
+            .map(|card| {
+                card.values.first().cloned().ok_or_else(|| {
+                    plan_error(format!(
+                        "CIF card at line {} is missing a file name",
+                        card.source_line
+                    ))
+                })
+            })
+            .transpose()?;
+
+        let (structure, mut sites) = match cif_path {
+            Some(path) => {
+                let input_dir = input_dir
+                    .ok_or_else(|| plan_error("CIF decks must be planned from their input file"))?;
+                let sites = cif_sites(&read_cif(&input_dir.join(&path))?, &config)?;
+                (SiteStructure::Cif { path }, sites)
+            }
+            None => {
+                let atoms = parse_deck_atoms(&deck)?;
+                let sites = atoms_site_groups(&atoms, &config)?
+                    .into_iter()
+                    .map(|group| AbsorberSite {
+                        atom_index: group[0],
+                        position: atoms[group[0]].position,
+                        multiplicity: group.len(),
+                        directory: String::new(),
+                        cif_target: None,
+                    })
+                    .collect::<Vec<_>>();
+                (SiteStructure::Atoms(atoms), sites)
+            }
+        };
+        for (site_index, site) in sites.iter_mut().enumerate() {
+            site.directory = format!("{}{:03}", SITE_DIRECTORY_PREFIX, site_index + 1);
+        }
+
+        let title = deck
+            .cards
+            .iter()
+            .find(|card| card.keyword == "TITLE")
+            .map(|card| card.values.join(" "))
+            .unwrap_or_default();
+
+        Ok(Self {
+            config,
+            title,
+            deck,
+            source_lines: source.lines().map(str::to_string).collect(),
+            structure,
+            sites,
+        })
+    }
+
+    pub fn sites(&self) -> &[AbsorberSite] {
+        &self.sites
+    }
+
+    pub fn total_multiplicity(&self) -> usize {
+        self.sites.iter().map(|site| site.multiplicity).sum()
+    }
+
+    /// Writes `<root>/site_NNN/feff.inp` for every site. `ATOMS` clusters are
+    /// re-centered on the site and their potentials reassigned with the
+    /// `atomnum` rule so the site becomes `ipot 0`; `CIF` decks get the site as
+    /// their `TARGET`.
+    pub fn write_site_decks(&self, root: &Path) -> ComputeResult<Vec<PathBuf>> {
+        let mut directories = Vec::with_capacity(self.sites.len());
+        for site in &self.sites {
+            let directory = root.join(&site.directory);
+            fs::create_dir_all(&directory).map_err(|source| {
+                FeffError::io_system(
+                    "IO.MULTI_ABSORBER_WRITE",
+                    format!(
+                        "failed to create site directory '{}': {}",
+                        directory.display(),
+                        source
+                    ),
+                )
+            })?;
+            let deck_path = directory.join("feff.inp");
+            write_text_artifact(&deck_path, &self.render_site_deck(site)?).map_err(|source| {
+                FeffError::io_system(
+                    "IO.MULTI_ABSORBER_WRITE",
+                    format!(
+                        "failed to write site deck '{}': {}",
+                        deck_path.display(),
+                        source
+                    ),
+                )
+            })?;
+            directories.push(directory);
+        }
+        Ok(directories)
+    }
+
+    /// Averages `xmu.dat` and `chi.dat` from every site directory under `root`
+    /// with the site multiplicities as weights, and writes the averages plus a
+    /// `sites.dat` summary into `root`. Spectra missing from any site are skipped.
+    pub fn average_site_spectra(&self, root: &Path) -> ComputeResult<Vec<ComputeArtifact>> {
+        let mut artifacts = Vec::new();
+        for (name, grid_column, leading_columns) in AVERAGED_SPECTRA {
+            let paths = self
+                .sites
+                .iter()
+                .map(|site| root.join(&site.directory).join(name))
+                .collect::<Vec<_>>();
+            if !paths.iter().all(|path| path.is_file()) {
+                continue;
+            }
+
+            let tables = paths
+                .iter()
+                .map(|path| read_spectrum_table(path))
+                .collect::<ComputeResult<Vec<_>>>()?;
+            let content = self.render_average(name, &tables, grid_column, leading_columns)?;
+            write_root_artifact(root, name, &content)?;
+            artifacts.push(ComputeArtifact::new(name));
+        }
+
+        if artifacts.is_empty() {
+            return Err(FeffError::computation(
+                "RUN.MULTI_ABSORBER_AVERAGE",
+                format!(
+                    "no site produced a complete set of {} spectra to average",
+                    AVERAGED_SPECTRA
+                        .iter()
+                        .map(|(name, _, _)| *name)
+                        .collect::<Vec<_>>()
+                        .join(" or ")
+                ),
+            ));
+        }
+
+        write_root_artifact(root, SITE_SUMMARY_OUTPUT, &self.render_site_summary())?;
+        artifacts.push(ComputeArtifact::new(SITE_SUMMARY_OUTPUT));
+        Ok(artifacts)
+    }
+
+    fn render_site_deck(&self, site: &AbsorberSite) -> ComputeResult<String> {
+        let atoms = match &self.structure {
+            SiteStructure::Atoms(atoms) => atoms,
+            SiteStructure::Cif { path } => return Ok(self.render_cif_site_deck(site, path)),
+        };
+        let cluster = atoms
+            .iter()
+            .enumerate()
+            .map(|(index, atom)| {
+                let relative: [f64; 3] =
+                    std::array::from_fn(|axis| atom.position[axis] - site.position[axis]);
+                (index, atom.atomic_number, relative, norm(relative))
+            })
+            .filter(|(index, _, _, radius)| {
+                *index == site.atom_index
+                    || self
+                        .config
+                        .cluster_radius
+                        .is_none_or(|limit| *radius <= limit + POSITION_EPSILON)
+            })
+            .collect::<Vec<_>>();
+        let mut structure = XyzFormat {
+            title: self.title.clone(),
+            atomic_numbers: cluster.iter().map(|(_, z, _, _)| *z).collect(),
+            xyz: cluster.iter().map(|(_, _, xyz, _)| *xyz).collect(),
+            potential_indices: Vec::new(),
+            potential_numeric_labels: Vec::new(),
+            potential_string_labels: Vec::new(),
+        };
+        let absorber = cluster
+            .iter()
+            .position(|(index, _, _, _)| *index == site.atom_index)
+            .map(|position| position + 1)
+            .unwrap_or(1);
+        let pot_list = gen_pot_from_xyz(&PotGenRule::default(), absorber, &mut structure)
+            .map_err(|source| plan_error(source.to_string()))?;
+
+        let mut order = (0..structure.atom_count()).collect::<Vec<_>>();
+        order.sort_by(|left, right| {
+            cluster[*left]
+                .3
+                .total_cmp(&cluster[*right].3)
+                .then(left.cmp(right))
+        });
+
+        let mut potentials = vec!["POTENTIALS".to_string()];
+        for (ipot, (atomic_number, label)) in pot_list
+            .atomic_numbers
+            .iter()
+            .zip(&pot_list.string_labels)
+            .enumerate()
+        {
+            potentials.push(format!("{:>5}{:>5}   {}", ipot, atomic_number, label));
+        }
+
+        let mut atoms = vec!["ATOMS".to_string()];
+        for index in order {
+            let [x, y, z] = structure.xyz[index];
+            atoms.push(format!(
+                "{} {} {} {:>5}   {:<10}{}",
+                format_fixed_f64(clean_zero(x), 11, 5),
+                format_fixed_f64(clean_zero(y), 11, 5),
+                format_fixed_f64(clean_zero(z), 11, 5),
+                structure.potential_numeric_labels[index],
+                structure.potential_string_labels[index],
+                format_fixed_f64(cluster[index].3, 10, 5),
+            ));
+        }
+
+        Ok(self.splice_structure_blocks(potentials, atoms))
+    }
+
+    /// Copies the deck with `TARGET` set to the site. The site deck sits one
+    /// directory below the original, so a relative CIF path gains a `../`.
+    fn render_cif_site_deck(&self, site: &AbsorberSite, cif_path: &str) -> String {
+        let card_line = |keyword: &str| {
+            self.deck
+                .cards
+                .iter()
+                .find(|card| card.keyword == keyword)
+                .map(|card| card.source_line)
+        };
+        let cif_line = card_line("CIF");
+        let target_line = card_line("TARGET");
+        let target = format!("TARGET {}", site.cif_target.unwrap_or(1));
+        let relocated = if Path::new(cif_path).is_absolute() {
+            cif_path.to_string()
+        } else {
+            format!("../{}", cif_path)
+        };
+
+        let mut lines = vec![format!(
+            "* Absorber site generated for configurational averaging ({})",
+            element_symbol(self.config.absorber_z)
+        )];
+        for (index, line) in self.source_lines.iter().enumerate() {
+            let line_number = Some(index + 1);
+            if line_number == cif_line {
+                lines.push(format!("CIF {}", relocated));
+                if target_line.is_none() {
+                    lines.push(target.clone());
+                }
+            } else if line_number == target_line {
+                lines.push(target.clone());
+            } else {
+                lines.push(line.clone());
+            }
+        }
+        lines.join("\n")
+    }
+
+    /// Replaces the `POTENTIALS` and `ATOMS` blocks of the original deck with the
+    /// generated ones, keeping every other line (and its position) verbatim.
+    fn splice_structure_blocks(&self, potentials: Vec<String>, atoms: Vec<String>) -> String {
+        let mut replaced = BTreeMap::new();
+        let mut skipped = BTreeSet::new();
+        for card in &self.deck.cards {
+            if !matches!(card.keyword.as_str(), "POTENTIALS" | "ATOMS") {
+                continue;
+            }
+            replaced.insert(card.source_line, card.keyword.as_str());
+            skipped.extend(
+                card.continuations
+                    .iter()
+                    .map(|continuation| continuation.source_line),
+            );
+        }
+
+        let mut emitted = BTreeSet::new();
+        let mut lines = vec![format!(
+            "* Absorber site generated for configurational averaging ({})",
+            element_symbol(self.config.absorber_z)
+        )];
+        for (index, line) in self.source_lines.iter().enumerate() {
+            let line_number = index + 1;
+            if let Some(keyword) = replaced.get(&line_number) {
+                if emitted.insert(*keyword) {
+                    let block = if *keyword == "ATOMS" {
+                        &atoms
+                    } else {
+                        &potentials
+                    };
+                    lines.extend(block.iter().cloned());
+                }
+            } else if !skipped.contains(&line_number) {
+                lines.push(line.clone());
+            }
+        }
+        lines.join("\n")
+    }
+
+    fn render_average(
+        &self,
+        name: &str,
+        tables: &[SpectrumTable],
+        grid_column: usize,
+        leading_columns: usize,
+    ) -> ComputeResult<String> {
+        let reference = &tables[0];
+        let width = tables
+            .iter()
+            .flat_map(|table| table.values.iter().map(Vec::len))
+            .min()
+            .unwrap_or(0);
+        if width <= leading_columns.max(grid_column) {
+            return Err(average_error(format!(
+                "{} rows do not have enough columns to average",
+                name
+            )));
+        }
+
+        let (low, high) = tables
+            .iter()
+            .map(|table| grid_range(table, grid_column))
+            .fold((f64::NEG_INFINITY, f64::INFINITY), |(low, high), range| {
+                (low.max(range.0), high.min(range.1))
+            });
+        let total_weight = self.total_multiplicity() as f64;
+
+        let mut lines = vec![format!(
+            "# # Configurational average over {} {} site(s), total multiplicity {}",
+            self.sites.len(),
+            element_symbol(self.config.absorber_z),
+            self.total_multiplicity()
+        )];
+        lines.extend(reference.header.iter().cloned());
+
+        for (row, values) in reference.rows.iter().zip(&reference.values) {
+            let grid = values[grid_column];
+            if grid < low - POSITION_EPSILON || grid > high + POSITION_EPSILON {
+                continue;
+            }
+            let mut fields = row[..leading_columns].to_vec();
+            for column in leading_columns..width {
+                let sum = self
+                    .sites
+                    .iter()
+                    .zip(tables)
+                    .map(|(site, table)| {
+                        site.multiplicity as f64 * interpolate(table, grid_column, column, grid)
+                    })
+                    .sum::<f64>();
+                fields.push(format_fixed_f64(sum / total_weight, 14, 7));
+            }
+            lines.push(fields.join(" "));
+        }
+
+        Ok(lines.join("\n"))
+    }
+
+    fn render_site_summary(&self) -> String {
+        let total_weight = self.total_multiplicity() as f64;
+        let mut lines = vec![
+            format!("# # {:<60} FEFF10-RS configurational average", self.title),
+            format!(
+                "# # absorber {} (Z={}) selection={} sites={} total_multiplicity={}",
+                element_symbol(self.config.absorber_z),
+                self.config.absorber_z,
+                match self.config.selection {
+                    SiteSelection::All => "all",
+                    SiteSelection::Inequivalent => "inequivalent",
+                },
+                self.sites.len(),
+                self.total_multiplicity()
+            ),
+            "#  site  atom        x          y          z      mult    weight   directory"
+                .to_string(),
+        ];
+        for (index, site) in self.sites.iter().enumerate() {
+            lines.push(format!(
+                "{:>6}{:>6} {} {} {} {:>6} {}   {}",
+                index + 1,
+                site.atom_index + 1,
+                format_fixed_f64(clean_zero(site.position[0]), 10, 5),
+                format_fixed_f64(clean_zero(site.position[1]), 10, 5),
+                format_fixed_f64(clean_zero(site.position[2]), 10, 5),
+                site.multiplicity,
+                format_fixed_f64(site.multiplicity as f64 / total_weight, 9, 5),
+                site.directory
+            ));
+        }
+        lines.join("\n")
+    }
+}
+
+/// Resolves an element given as a symbol (`Cu`) or an atomic number (`29`).
+pub fn parse_absorber_element(token: &str) -> ComputeResult<i32> {
+    let trimmed = token.trim();
+    if let Ok(atomic_number) = trimmed.parse::<i32>() {
+        return usize::try_from(atomic_number)
+            .ok()
+            .and_then(atsym)
+            .map(|_| atomic_number)
+            .ok_or_else(|| plan_error(format!("unknown atomic number '{}'", trimmed)));
+    }
+    (1..=139)
+        .find(|atomic_number| {
+            atsym(*atomic_number).is_some_and(|symbol| symbol.eq_ignore_ascii_case(trimmed))
+        })
+        .map(|atomic_number| atomic_number as i32)
+        .ok_or_else(|| plan_error(format!("unknown element symbol '{}'", trimmed)))
+}
+
+fn parse_deck_atoms(deck: &InputDeck) -> ComputeResult<Vec<DeckAtom>> {
+    let mut species = BTreeMap::new();
+    for card in deck
+        .cards
+        .iter()
+        .filter(|card| card.keyword == "POTENTIALS")
+    {
+        for (line, row) in card_rows(card) {
+            if row.len() < 2 {
+                return Err(plan_error(format!(
+                    "invalid POTENTIALS row at line {}: expected ipot and Z fields",
+                    line
+                )));
+            }
+            species.insert(
+                parse_i32(&row[0], "POTENTIALS ipot", line)?,
+                parse_i32(&row[1], "POTENTIALS Z", line)?,
+            );
+        }
+    }
+
+    let mut atoms = Vec::new();
+    for card in deck.cards.iter().filter(|card| card.keyword == "ATOMS") {
+        for (line, row) in card_rows(card) {
+            if row.len() < 4 {
+                return Err(plan_error(format!(
+                    "invalid ATOMS row at line {}: expected x y z ipot fields",
+                    line
+                )));
+            }
+            let ipot = parse_i32(&row[3], "ATOMS ipot", line)?;
+            let atomic_number = *species.get(&ipot).ok_or_else(|| {
+                plan_error(format!(
+                    "ATOMS row at line {} references undefined potential {}",
+                    line, ipot
+                ))
+            })?;
+            atoms.push(DeckAtom {
+                position: [
+                    parse_f64(&row[0], "ATOMS x", line)?,
+                    parse_f64(&row[1], "ATOMS y", line)?,
+                    parse_f64(&row[2], "ATOMS z", line)?,
+                ],
+                atomic_number,
+            });
+        }
+    }
+
+    if atoms.is_empty() {
+        return Err(plan_error(
+            "configurational averaging requires an ATOMS block",
+        ));
+    }
+    Ok(atoms)
+}
+
+/// Groups the `ATOMS` sites of the absorbing element into the sites to run.
+///
+/// A site near the edge of the cluster sees a truncated environment, so only
+/// sites whose whole [`SITE_SIGNATURE_RADIUS`] sphere lies inside the cluster
+/// are candidates; they stand in for the boundary atoms of the same element.
+fn atoms_site_groups(
+    atoms: &[DeckAtom],
+    config: &MultiAbsorberConfig,
+) -> ComputeResult<Vec<Vec<usize>>> {
+    let of_element = atoms
+        .iter()
+        .enumerate()
+        .filter(|(_, atom)| atom.atomic_number == config.absorber_z)
+        .map(|(index, _)| index)
+        .collect::<Vec<_>>();
+    if of_element.is_empty() {
+        return Err(missing_element_error(config.absorber_z));
+    }
+
+    let extent = atoms
+        .iter()
+        .map(|atom| norm(atom.position))
+        .fold(0.0, f64::max);
+    let candidates = of_element
+        .into_iter()
+        .filter(|index| {
+            norm(atoms[*index].position) + SITE_SIGNATURE_RADIUS <= extent + POSITION_EPSILON
+        })
+        .collect::<Vec<_>>();
+    if candidates.is_empty() {
+        return Err(plan_error(format!(
+            "no {} site has its {} Angstrom environment inside the ATOMS cluster \
+             ({} Angstrom); extend the cluster",
+            element_symbol(config.absorber_z),
+            format_fixed_f64(SITE_SIGNATURE_RADIUS, 4, 1).trim(),
+            format_fixed_f64(extent, 8, 3).trim()
+        )));
+    }
+
+    Ok(match config.selection {
+        SiteSelection::All => candidates.into_iter().map(|index| vec![index]).collect(),
+        SiteSelection::Inequivalent => group_equivalent_sites(atoms, &candidates),
+    })
+}
+
+/// Sites of the absorbing element in the symmetry-expanded unit cell. In
+/// `Inequivalent` mode asymmetric-unit sites related by a space-group
+/// operation form one class, weighted by the number of cell atoms it covers.
+fn cif_sites(
+    structure: &CifStructure,
+    config: &MultiAbsorberConfig,
+) -> ComputeResult<Vec<AbsorberSite>> {
+    let asymmetric = structure
+        .sites
+        .iter()
+        .enumerate()
+        .filter(|(_, site)| site.atomic_number == config.absorber_z)
+        .map(|(index, site)| (index, (site.atomic_number, site.fractional)))
+        .collect::<Vec<_>>();
+    if asymmetric.is_empty() {
+        return Err(missing_element_error(config.absorber_z));
+    }
+    let classes = equivalent_site_classes(
+        &structure.operations,
+        &asymmetric.iter().map(|(_, site)| *site).collect::<Vec<_>>(),
+    );
+    let class_of = |site_index: usize| {
+        asymmetric
+            .iter()
+            .position(|(index, _)| *index == site_index)
+            .map(|position| asymmetric[classes[position]].0)
+    };
+    let cell_atoms = structure
+        .unit_cell_atoms()
+        .into_iter()
+        .enumerate()
+        .filter_map(|(atom_index, (site_index, fractional))| {
+            class_of(site_index).map(|class| (atom_index, site_index, class, fractional))
+        })
+        .collect::<Vec<_>>();
+    let cartesian = |fractional: [f64; 3]| {
+        structure
+            .cell
+            .to_cartesian(fractional)
+            .map_err(|source| plan_error(source.to_string()))
+    };
+
+    match config.selection {
+        SiteSelection::All => cell_atoms
+            .iter()
+            .map(|(atom_index, site_index, _, fractional)| {
+                Ok(AbsorberSite {
+                    atom_index: *atom_index,
+                    position: cartesian(*fractional)?,
+                    multiplicity: 1,
+                    directory: String::new(),
+                    cif_target: Some(site_index + 1),
+                })
+            })
+            .collect(),
+        SiteSelection::Inequivalent => {
+            let mut sites = Vec::new();
+            for (position, (representative, _)) in asymmetric.iter().enumerate() {
+                if classes[position] != position {
+                    continue;
+                }
+                let members = cell_atoms
+                    .iter()
+                    .filter(|(_, _, class, _)| class == representative)
+                    .collect::<Vec<_>>();
+                let Some((atom_index, _, _, fractional)) = members.first() else {
+                    continue;
+                };
+                sites.push(AbsorberSite {
+                    atom_index: *atom_index,
+                    position: cartesian(*fractional)?,
+                    multiplicity: members.len(),
+                    directory: String::new(),
+                    cif_target: Some(representative + 1),
+                });
+            }
+            Ok(sites)
+        }
+    }
+}
+
+fn read_cif(path: &Path) -> ComputeResult<CifStructure> {
+    let source = fs::read_to_string(path).map_err(|source| {
+        FeffError::io_system(
+            "IO.MULTI_ABSORBER_READ",
+            format!("failed to read CIF '{}': {}", path.display(), source),
+        )
+    })?;
+    CifStructure::parse(&source)
+        .map_err(|source| plan_error(format!("CIF '{}': {}", path.display(), source)))
+}
+
+fn card_rows(card: &crate::domain::InputCard) -> Vec<(usize, &[String])> {
+    std::iter::once((card.source_line, card.values.as_slice()))
+        .chain(
+            card.continuations
+                .iter()
+                .map(|continuation| (continuation.source_line, continuation.values.as_slice())),
+        )
+        .filter(|(_, row)| !row.is_empty())
+        .collect()
+}
+
+/// Groups candidate sites by the sorted `(Z, distance)` list of their neighbors
+/// within [`SITE_SIGNATURE_RADIUS`]. Each group is represented by its site
+/// closest to the cluster origin, so the representative has the fullest cluster.
+fn group_equivalent_sites(atoms: &[DeckAtom], candidates: &[usize]) -> Vec<Vec<usize>> {
+    let mut groups = BTreeMap::<Vec<(i32, i64)>, Vec<usize>>::new();
+    for &candidate in candidates {
+        groups
+            .entry(site_signature(atoms, candidate))
+            .or_default()
+            .push(candidate);
+    }
+
+    let mut groups = groups
+        .into_values()
+        .map(|mut members| {
+            members.sort_by(|left, right| {
+                norm(atoms[*left].position)
+                    .total_cmp(&norm(atoms[*right].position))
+                    .then(left.cmp(right))
+            });
+            members
+        })
+        .collect::<Vec<_>>();
+    groups.sort_by(|left, right| {
+        norm(atoms[left[0]].position)
+            .total_cmp(&norm(atoms[right[0]].position))
+            .then(left[0].cmp(&right[0]))
+    });
+    groups
+}
+
+fn site_signature(atoms: &[DeckAtom], center: usize) -> Vec<(i32, i64)> {
+    let origin = atoms[center].position;
+    let mut signature = atoms
+        .iter()
+        .enumerate()
+        .filter(|(index, _)| *index != center)
+        .filter_map(|(_, atom)| {
+            let distance = norm(std::array::from_fn(|axis| {
+                atom.position[axis] - origin[axis]
+            }));
+            (distance <= SITE_SIGNATURE_RADIUS).then(|| {
+                (
+                    atom.atomic_number,
+                    (distance / SIGNATURE_DISTANCE_RESOLUTION).round() as i64,
+                )
+            })
+        })
+        .collect::<Vec<_>>();
+    signature.sort_unstable();
+    signature
+}
+
+fn read_spectrum_table(path: &Path) -> ComputeResult<SpectrumTable> {
+    let source = fs::read_to_string(path).map_err(|source| {
+        FeffError::io_system(
+            "IO.MULTI_ABSORBER_READ",
+            format!(
+                "failed to read site spectrum '{}': {}",
+                path.display(),
+                source
+            ),
+        )
+    })?;
+
+    let mut table = SpectrumTable {
+        header: Vec::new(),
+        rows: Vec::new(),
+        values: Vec::new(),
+    };
+    for line in source.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+        if trimmed.starts_with('#') {
+            if table.rows.is_empty() {
+                table.header.push(line.to_string());
+            }
+            continue;
+        }
+        let fields = trimmed
+            .split_whitespace()
+            .map(str::to_string)
+            .collect::<Vec<_>>();
+        let values = fields
+            .iter()
+            .map(|field| field.replace(['D', 'd'], "E").parse::<f64>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| {
+                average_error(format!(
+                    "non-numeric row '{}' in '{}'",
+                    trimmed,
+                    path.display()
+                ))
+            })?;
+        table.rows.push(fields);
+        table.values.push(values);
+    }
+
+    if table.rows.is_empty() {
+        return Err(average_error(format!(
+            "site spectrum '{}' has no data rows",
+            path.display()
+        )));
+    }
+    Ok(table)
+}
+
+fn grid_range(table: &SpectrumTable, grid_column: usize) -> (f64, f64) {
+    table
+        .values
+        .iter()
+        .map(|row| row[grid_column])
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(low, high), value| {
+            (low.min(value), high.max(value))
+        })
+}
+
+/// Linear interpolation of `column` at `grid`, assuming the grid column is
+/// monotonic increasing as it is in every FEFF spectrum file.
+fn interpolate(table: &SpectrumTable, grid_column: usize, column: usize, grid: f64) -> f64 {
+    let values = &table.values;
+    let upper = values
+        .iter()
+        .position(|row| row[grid_column] >= grid - POSITION_EPSILON)
+        .unwrap_or(values.len() - 1);
+    if upper == 0 || (values[upper][grid_column] - grid).abs() <= POSITION_EPSILON {
+        return values[upper][column];
+    }
+    let (left, right) = (&values[upper - 1], &values[upper]);
+    let span = right[grid_column] - left[grid_column];
+    if span.abs() <= f64::EPSILON {
+        return right[column];
+    }
+    let fraction = (grid - left[grid_column]) / span;
+    left[column] + fraction * (right[column] - left[column])
+}
+
+fn write_root_artifact(root: &Path, name: &str, content: &str) -> ComputeResult<()> {
+    let path = root.join(name);
+    write_text_artifact(&path, content).map_err(|source| {
+        FeffError::io_system(
+            "IO.MULTI_ABSORBER_WRITE",
+            format!(
+                "failed to write averaged artifact '{}': {}",
+                path.display(),
+                source
+            ),
+        )
+    })
+}
+
+fn parse_f64(token: &str, field: &str, line: usize) -> ComputeResult<f64> {
+    token
+        .replace(['D', 'd'], "E")
+        .parse::<f64>()
+        .map_err(|_| plan_error(format!("invalid {} '{}' at line {}", field, token, line)))
+}
+
+fn parse_i32(token: &str, field: &str, line: usize) -> ComputeResult<i32> {
+    token
+        .parse::<i32>()
+        .map_err(|_| plan_error(format!("invalid {} '{}' at line {}", field, token, line)))
+}
+
+fn norm(vector: [f64; 3]) -> f64 {
+    vector.iter().map(|value| value * value).sum::<f64>().sqrt()
+}
+
+fn clean_zero(value: f64) -> f64 {
+    if value.abs() < 5.0e-6 { 0.0 } else { value }
+}
+
+fn element_symbol(atomic_number: i32) -> &'static str {
+    usize::try_from(atomic_number)
+        .ok()
+        .and_then(atsym)
+        .unwrap_or("X")
+}
+
+fn missing_element_error(atomic_number: i32) -> FeffError {
+    plan_error(format!(
+        "structure has no atoms of element {} (Z={})",
+        element_symbol(atomic_number),
+        atomic_number
+    ))
+}
+
+fn plan_error(message: impl Into<String>) -> FeffError {
+    FeffError::input_validation("INPUT.MULTI_ABSORBER", message.into())
+}
+
+fn average_error(message: impl Into<String>) -> FeffError {
+    FeffError::computation("RUN.MULTI_ABSORBER_AVERAGE", message.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        MultiAbsorberConfig, MultiAbsorberPlan, SITE_SUMMARY_OUTPUT, SiteSelection,
+        parse_absorber_element,
+    };
+    use crate::domain::FeffErrorCategory;
+    use crate::parser::parse_input_deck;
+    use std::fs;
+    use tempfile::TempDir;
+
+    const CUO_DECK: &str = "TITLE CuO chain
+EDGE K
+POTENTIALS
+0 29 Cu
+1 29 Cu
+2 8 O
+ATOMS
+0.0 0.0 0.0 0 Cu
+2.0 0.0 0.0 2 O
+4.0 0.0 0.0 1 Cu
+-2.0 0.0 0.0 2 O
+-4.0 0.0 0.0 1 Cu
+6.0 0.0 0.0 2 O
+8.0 0.0 0.0 1 Cu
+-6.0 0.0 0.0 2 O
+-8.0 0.0 0.0 1 Cu
+END
+";
+
+    const COPPER_CIF: &str = "data_Cu
+_cell_length_a 3.615
+_cell_length_b 3.615
+_cell_length_c 3.615
+loop_
+_symmetry_equiv_pos_as_xyz
+x,y,z
+x,y+1/2,z+1/2
+x+1/2,y,z+1/2
+x+1/2,y+1/2,z
+loop_
+_atom_site_label
+_atom_site_type_symbol
+_atom_site_fract_x
+_atom_site_fract_y
+_atom_site_fract_z
+Cu1 Cu 0 0 0
+Cu2 Cu 0.5 0.5 0
+";
+
+    #[test]
+    fn all_sites_mode_plans_one_site_per_atom_of_the_element() {
+        let plan = MultiAbsorberPlan::from_feff_source(CUO_DECK, MultiAbsorberConfig::new(29))
+            .expect("plan should build");
+        let sites = plan.sites();
+        assert_eq!(sites.len(), 3);
+        assert!(sites.iter().all(|site| site.multiplicity == 1));
+        assert_eq!(sites[1].atom_index, 2);
+        assert_eq!(sites[2].directory, "site_003");
+        assert_eq!(plan.total_multiplicity(), 3);
+        // The chain ends at 8 A see a truncated environment and are not run.
+        assert!(sites.iter().all(|site| site.position[0].abs() <= 4.0));
+    }
+
+    #[test]
+    fn inequivalent_mode_groups_sites_by_local_environment() {
+        let config = MultiAbsorberConfig {
+            selection: SiteSelection::Inequivalent,
+            ..MultiAbsorberConfig::new(29)
+        };
+        let plan = MultiAbsorberPlan::from_feff_source(CUO_DECK, config).expect("plan");
+        let sites = plan.sites();
+        assert_eq!(sites.len(), 1, "interior chain sites are equivalent");
+        assert_eq!((sites[0].atom_index, sites[0].multiplicity), (0, 3));
+        assert_eq!(plan.total_multiplicity(), 3);
+    }
+
+    #[test]
+    fn clusters_without_an_interior_site_are_rejected() {
+        let short_chain = "TITLE CuO\nPOTENTIALS\n0 29 Cu\n1 8 O\nATOMS\n\
+                           0.0 0.0 0.0 0 Cu\n2.0 0.0 0.0 1 O\n-2.0 0.0 0.0 1 O\nEND\n";
+        let error = MultiAbsorberPlan::from_feff_source(short_chain, MultiAbsorberConfig::new(8))
+            .expect_err("every O site touches the cluster edge");
+        assert_eq!(error.placeholder(), "INPUT.MULTI_ABSORBER");
+        assert!(error.message().contains("extend the cluster"));
+    }
+
+    #[test]
+    fn cif_decks_group_sites_by_space_group_symmetry() {
+        let temp = TempDir::new().expect("tempdir should be created");
+        fs::write(temp.path().join("cu.cif"), COPPER_CIF).expect("cif should be written");
+        let input_path = temp.path().join("feff.inp");
+        fs::write(&input_path, "TITLE Cu\nCIF cu.cif\nRPATH 3.0\nEND\n")
+            .expect("input should be written");
+
+        let config = MultiAbsorberConfig {
+            selection: SiteSelection::Inequivalent,
+            ..MultiAbsorberConfig::new(29)
+        };
+        let plan = MultiAbsorberPlan::from_feff_input(&input_path, config).expect("plan");
+        let sites = plan.sites();
+        // Cu2 is a face-centering image of Cu1, so the cell holds one class.
+        assert_eq!(sites.len(), 1);
+        assert_eq!((sites[0].multiplicity, sites[0].cif_target), (4, Some(1)));
+
+        let plan = MultiAbsorberPlan::from_feff_input(&input_path, MultiAbsorberConfig::new(29))
+            .expect("plan");
+        assert_eq!(plan.sites().len(), 4);
+        plan.write_site_decks(temp.path()).expect("decks");
+        let source = fs::read_to_string(temp.path().join("site_002/feff.inp")).expect("deck");
+        let lines = source.lines().skip(1).collect::<Vec<_>>();
+        assert_eq!(
+            lines,
+            vec!["TITLE Cu", "CIF ../cu.cif", "TARGET 1", "RPATH 3.0", "END"]
+        );
+
+        let error = MultiAbsorberPlan::from_feff_source(
+            "TITLE Cu\nCIF cu.cif\nEND\n",
+            MultiAbsorberConfig::new(29),
+        )
+        .expect_err("CIF paths need the deck location");
+        assert_eq!(error.placeholder(), "INPUT.MULTI_ABSORBER");
+    }
+
+    #[test]
+    fn site_decks_recenter_the_cluster_and_reassign_potentials() {
+        let temp = TempDir::new().expect("tempdir should be created");
+        let config = MultiAbsorberConfig {
+            cluster_radius: Some(6.5),
+            ..MultiAbsorberConfig::new(29)
+        };
+        let plan = MultiAbsorberPlan::from_feff_source(CUO_DECK, config).expect("plan");
+        let directories = plan.write_site_decks(temp.path()).expect("decks");
+        assert_eq!(directories.len(), 3);
+
+        let source = fs::read_to_string(temp.path().join("site_002/feff.inp")).expect("deck");
+        assert!(source.contains("TITLE CuO chain"));
+        assert!(source.contains("EDGE K"));
+        let deck = parse_input_deck(&source).expect("site deck should parse");
+        let potentials = deck
+            .cards
+            .iter()
+            .find(|card| card.keyword == "POTENTIALS")
+            .expect("potentials");
+        let potential_rows = potentials
+            .continuations
+            .iter()
+            .map(|row| row.values.join(" "))
+            .collect::<Vec<_>>();
+        assert_eq!(potential_rows, vec!["0 29 Cu_Abs", "1 8 O", "2 29 Cu"]);
+
+        let atoms = deck
+            .cards
+            .iter()
+            .find(|card| card.keyword == "ATOMS")
+            .expect("atoms");
+        assert_eq!(
+            &atoms.continuations[0].values[..4],
+            &["0.00000", "0.00000", "0.00000", "0"]
+        );
+        assert_eq!(
+            &atoms.continuations[1].values[..5],
+            &["-2.00000", "0.00000", "0.00000", "1", "O"]
+        );
+        // Only the atoms within the 6.5 A cluster radius of the site are kept.
+        assert_eq!(atoms.continuations.len(), 6);
+        assert!(
+            deck.cards
+                .iter()
+                .filter(|card| card.keyword == "ATOMS")
+                .count()
+                == 1
+                && deck.cards.iter().any(|card| card.keyword == "END")
+        );
+    }
+
+    #[test]
+    fn site_spectra_are_averaged_with_multiplicity_weights() {
+        let temp = TempDir::new().expect("tempdir should be created");
+        let plan = MultiAbsorberPlan::from_feff_source(CUO_DECK, MultiAbsorberConfig::new(29))
+            .expect("plan");
+        plan.write_site_decks(temp.path()).expect("decks");
+        fs::write(
+            temp.path().join("site_001/chi.dat"),
+            "# # site one\n#  k chi\n0.1000 1.0\n0.2000 2.0\n0.3000 3.0\n",
+        )
+        .expect("chi");
+        for site in ["site_002", "site_003"] {
+            fs::write(
+                temp.path().join(site).join("chi.dat"),
+                "# # chain site\n#  k chi\n0.0500 4.0\n0.1500 4.0\n0.2500 7.0\n",
+            )
+            .expect("chi");
+        }
+
+        let artifacts = plan.average_site_spectra(temp.path()).expect("average");
+        let names = artifacts
+            .iter()
+            .map(|artifact| artifact.relative_path.to_string_lossy().into_owned())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["chi.dat", SITE_SUMMARY_OUTPUT]);
+
+        let averaged = fs::read_to_string(temp.path().join("chi.dat")).expect("averaged chi");
+        let rows = averaged
+            .lines()
+            .filter(|line| !line.starts_with('#'))
+            .map(|line| {
+                line.split_whitespace()
+                    .map(|token| token.parse::<f64>().expect("numeric"))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        // 0.3 lies outside the second site's grid and is dropped.
+        assert_eq!(rows.len(), 2);
+        assert!((rows[0][1] - (1.0 + 2.0 * 4.0) / 3.0).abs() < 1.0e-6);
+        assert!((rows[1][1] - (2.0 + 2.0 * 5.5) / 3.0).abs() < 1.0e-6);
+        assert!(averaged.starts_with("# # Configurational average over 3 Cu site(s)"));
+
+        let summary = fs::read_to_string(temp.path().join(SITE_SUMMARY_OUTPUT)).expect("sites");
+        assert!(summary.contains("selection=all sites=3 total_multiplicity=3"));
+    }
+
+    #[test]
+    fn missing_element_and_unknown_symbols_are_input_errors() {
+        let error = MultiAbsorberPlan::from_feff_source(CUO_DECK, MultiAbsorberConfig::new(26))
+            .expect_err("no Fe atoms");
+        assert_eq!(error.category(), FeffErrorCategory::InputValidationError);
+        assert_eq!(error.placeholder(), "INPUT.MULTI_ABSORBER");
+
+        assert_eq!(parse_absorber_element("cu").expect("symbol"), 29);
+        assert_eq!(parse_absorber_element("8").expect("number"), 8);
+        assert!(parse_absorber_element("Qq").is_err());
+    }
+}
@@ -1,9 +1,10 @@
 mod model;
 mod parser;
 
-use super::ModuleExecutor;
-use crate::domain::{ComputeArtifact, ComputeRequest, ComputeResult, FeffError};
-use std::fs;
+use super::{ModuleContract, ModuleExecutor};
+use crate::domain::{
+    ComputeArtifact, ComputeModule, ComputeRequest, ComputeResult, ExecutionEvent, FeffError,
+};
 
 use model::PathModel;
 use parser::{
@@ -22,6 +23,18 @@ pub struct PathContract {
     pub expected_outputs: Vec<ComputeArtifact>,
 }
 
+impl ModuleContract for PathContract {
+    const MODULE: ComputeModule = ComputeModule::Path;
+
+    fn required_input_names() -> Vec<&'static str> {
+        PATH_REQUIRED_INPUTS.to_vec()
+    }
+
+    fn output_names() -> Vec<&'static str> {
+        PATH_REQUIRED_OUTPUTS.to_vec()
+    }
+}
+
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub struct PathModule;
 
@@ -29,8 +42,8 @@ impl PathModule {
     pub fn contract_for_request(&self, request: &ComputeRequest) -> ComputeResult<PathContract> {
         validate_request_shape(request)?;
         Ok(PathContract {
-            required_inputs: artifact_list(&PATH_REQUIRED_INPUTS),
-            expected_outputs: artifact_list(&PATH_REQUIRED_OUTPUTS),
+            required_inputs: artifact_list(&PathContract::required_input_names()),
+            expected_outputs: artifact_list(&PathContract::output_names()),
         })
     }
 }
@@ -40,16 +53,23 @@ impl ModuleExecutor for PathModule {
         validate_request_shape(request)?;
         let input_dir = input_parent_dir(request)?;
 
-        let path_source = read_input_source(&request.input_path, PATH_REQUIRED_INPUTS[0])?;
+        let path_source = read_input_source(
+            &*request.store,
+            &request.input_path,
+            PATH_REQUIRED_INPUTS[0],
+        )?;
         let geom_source = read_input_source(
+            &*request.store,
             &input_dir.join(PATH_REQUIRED_INPUTS[1]),
             PATH_REQUIRED_INPUTS[1],
         )?;
         let global_source = read_input_source(
+            &*request.store,
             &input_dir.join(PATH_REQUIRED_INPUTS[2]),
             PATH_REQUIRED_INPUTS[2],
         )?;
         let phase_bytes = read_input_bytes(
+            &*request.store,
             &input_dir.join(PATH_REQUIRED_INPUTS[3]),
             PATH_REQUIRED_INPUTS[3],
         )?;
@@ -63,22 +83,28 @@ impl ModuleExecutor for PathModule {
         )?;
         let outputs = artifact_list(&PATH_REQUIRED_OUTPUTS);
         let generated_paths = model.generated_paths();
-
-        fs::create_dir_all(&request.output_dir).map_err(|source| {
-            FeffError::io_system(
-                "IO.PATH_OUTPUT_DIRECTORY",
-                format!(
-                    "failed to create PATH output directory '{}': {}",
-                    request.output_dir.display(),
-                    source
-                ),
-            )
-        })?;
+        request.control.emit(ExecutionEvent::PathCount {
+            count: generated_paths.len(),
+        });
+
+        request
+            .store
+            .create_dir_all(&request.output_dir)
+            .map_err(|source| {
+                FeffError::io_system(
+                    "IO.PATH_OUTPUT_DIRECTORY",
+                    format!(
+                        "failed to create PATH output directory '{}': {}",
+                        request.output_dir.display(),
+                        source
+                    ),
+                )
+            })?;
 
         for artifact in &outputs {
             let output_path = request.output_dir.join(&artifact.relative_path);
             if let Some(parent) = output_path.parent() {
-                fs::create_dir_all(parent).map_err(|source| {
+                request.store.create_dir_all(parent).map_err(|source| {
                     FeffError::io_system(
                         "IO.PATH_OUTPUT_DIRECTORY",
                         format!(
@@ -91,7 +117,12 @@ impl ModuleExecutor for PathModule {
             }
 
             let artifact_name = artifact.relative_path.to_string_lossy().replace('\\', "/");
-            model.write_artifact(&artifact_name, &output_path, &generated_paths)?;
+            model.write_artifact(
+                &*request.store,
+                &artifact_name,
+                &output_path,
+                &generated_paths,
+            )?;
         }
 
         Ok(outputs)
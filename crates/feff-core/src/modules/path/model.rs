@@ -3,8 +3,10 @@ use super::parser::{
     GeomPathInput, GlobalPathInput, PathControlInput, PhasePathInput, angle_between, distance,
     parse_geom_input, parse_global_input, parse_paths_input, parse_phase_input, subtract,
 };
-use crate::domain::{ComputeResult, FeffError};
-use crate::modules::serialization::{format_fixed_f64, write_binary_artifact, write_text_artifact};
+use crate::domain::{ArtifactStore, ComputeResult, FeffError};
+use crate::modules::serialization::{
+    format_fixed_f64, write_binary_artifact_to, write_text_artifact_to,
+};
 use std::path::Path;
 
 #[derive(Debug, Clone)]
@@ -259,24 +261,40 @@ impl PathModel {
 
     pub(super) fn write_artifact(
         &self,
+        store: &dyn ArtifactStore,
         artifact_name: &str,
         output_path: &Path,
         paths: &[PathEntry],
     ) -> ComputeResult<()> {
         match artifact_name {
             "paths.dat" => {
-                write_text_artifact(output_path, &self.render_paths_dat(paths)).map_err(|source| {
-                    FeffError::io_system(
-                        "IO.PATH_OUTPUT_WRITE",
-                        format!(
-                            "failed to write PATH artifact '{}': {}",
-                            output_path.display(),
-                            source
-                        ),
-                    )
-                })
+                write_text_artifact_to(store, output_path, &self.render_paths_dat(paths)).map_err(
+                    |source| {
+                        FeffError::io_system(
+                            "IO.PATH_OUTPUT_WRITE",
+                            format!(
+                                "failed to write PATH artifact '{}': {}",
+                                output_path.display(),
+                                source
+                            ),
+                        )
+                    },
+                )
             }
-            "paths.bin" => write_binary_artifact(output_path, &self.render_paths_binary(paths))
+            "paths.bin" => {
+                write_binary_artifact_to(store, output_path, &self.render_paths_binary(paths))
+                    .map_err(|source| {
+                        FeffError::io_system(
+                            "IO.PATH_OUTPUT_WRITE",
+                            format!(
+                                "failed to write PATH artifact '{}': {}",
+                                output_path.display(),
+                                source
+                            ),
+                        )
+                    })
+            }
+            "crit.dat" => write_text_artifact_to(store, output_path, &self.render_crit_dat(paths))
                 .map_err(|source| {
                     FeffError::io_system(
                         "IO.PATH_OUTPUT_WRITE",
@@ -287,20 +305,8 @@ impl PathModel {
                         ),
                     )
                 }),
-            "crit.dat" => {
-                write_text_artifact(output_path, &self.render_crit_dat(paths)).map_err(|source| {
-                    FeffError::io_system(
-                        "IO.PATH_OUTPUT_WRITE",
-                        format!(
-                            "failed to write PATH artifact '{}': {}",
-                            output_path.display(),
-                            source
-                        ),
-                    )
-                })
-            }
-            "log4.dat" => {
-                write_text_artifact(output_path, &self.render_log4(paths)).map_err(|source| {
+            "log4.dat" => write_text_artifact_to(store, output_path, &self.render_log4(paths))
+                .map_err(|source| {
                     FeffError::io_system(
                         "IO.PATH_OUTPUT_WRITE",
                         format!(
@@ -309,8 +315,7 @@ impl PathModel {
                             source
                         ),
                     )
-                })
-            }
+                }),
             other => Err(FeffError::internal(
                 "SYS.PATH_OUTPUT_CONTRACT",
                 format!("unsupported PATH output artifact '{}'", other),
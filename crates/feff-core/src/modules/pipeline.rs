@@ -0,0 +1,435 @@
+use super::ModuleContract;
+use super::band::BandContract;
+use super::compton::ComptonContract;
+use super::crpa::CrpaContract;
+use super::debye::DebyeContract;
+use super::dmdw::DmdwContract;
+use super::eels::EelsContract;
+use super::fms::FmsContract;
+use super::fullspectrum::FullSpectrumContract;
+use super::ldos::LdosContract;
+use super::path::PathContract;
+use super::pot::PotContract;
+use super::rdinp::{RdinpContract, WORKFLOW_MODULE_ORDER, workflow_selection};
+use super::rixs::RixsContract;
+use super::screen::ScreenContract;
+use super::self_energy::SelfEnergyContract;
+use super::xsph::XsphContract;
+use crate::domain::{ComputeModule, ComputeResult, FeffError, InputDeck};
+use crate::parser::parse_input_deck;
+use std::collections::BTreeSet;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+/// File-level view of a module contract type (see [`ModuleContract`]),
+/// independent of any request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModuleArtifacts {
+    pub module: ComputeModule,
+    pub required_inputs: Vec<&'static str>,
+    /// At least one of these must exist (SELF accepts any staged spectrum).
+    pub any_of_inputs: Vec<&'static str>,
+    pub optional_inputs: Vec<&'static str>,
+    pub outputs: Vec<&'static str>,
+}
+
+impl ModuleArtifacts {
+    pub fn for_module(module: ComputeModule) -> Self {
+        match module {
+            ComputeModule::Rdinp => Self::from_contract::<RdinpContract>(),
+            ComputeModule::Pot => Self::from_contract::<PotContract>(),
+            ComputeModule::Xsph => Self::from_contract::<XsphContract>(),
+            ComputeModule::Path => Self::from_contract::<PathContract>(),
+            ComputeModule::Fms => Self::from_contract::<FmsContract>(),
+            ComputeModule::Band => Self::from_contract::<BandContract>(),
+            ComputeModule::Ldos => Self::from_contract::<LdosContract>(),
+            ComputeModule::Rixs => Self::from_contract::<RixsContract>(),
+            ComputeModule::Crpa => Self::from_contract::<CrpaContract>(),
+            ComputeModule::Compton => Self::from_contract::<ComptonContract>(),
+            ComputeModule::Debye => Self::from_contract::<DebyeContract>(),
+            ComputeModule::Dmdw => Self::from_contract::<DmdwContract>(),
+            ComputeModule::Screen => Self::from_contract::<ScreenContract>(),
+            ComputeModule::SelfEnergy => Self::from_contract::<SelfEnergyContract>(),
+            ComputeModule::Eels => Self::from_contract::<EelsContract>(),
+            ComputeModule::FullSpectrum => Self::from_contract::<FullSpectrumContract>(),
+        }
+    }
+
+    /// The artifacts contract type `C` declares.
+    pub fn from_contract<C: ModuleContract>() -> Self {
+        Self {
+            module: C::MODULE,
+            required_inputs: C::required_input_names(),
+            any_of_inputs: C::any_of_input_names(),
+            optional_inputs: C::optional_input_names(),
+            outputs: C::output_names(),
+        }
+    }
+
+    fn consumes(&self, artifact: &str) -> bool {
+        self.required_inputs
+            .iter()
+            .chain(&self.any_of_inputs)
+            .chain(&self.optional_inputs)
+            .any(|input| *input == artifact)
+    }
+}
+
+/// A module that runs, why, and which earlier steps feed it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PipelineStep {
+    pub module: ComputeModule,
+    pub reason: String,
+    /// Producers this step waits for, each with the artifacts it provides.
+    pub depends_on: Vec<(ComputeModule, Vec<&'static str>)>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SkippedModule {
+    pub module: ComputeModule,
+    pub reason: String,
+}
+
+/// Execution order for a run, derived from module contracts.
+///
+/// A module depends on every other planned module that produces one of its
+/// inputs; artifacts already present in the run directory need no producer.
+/// Planning fails when a required input has neither.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PipelinePlan {
+    steps: Vec<PipelineStep>,
+    skipped: Vec<SkippedModule>,
+}
+
+impl PipelinePlan {
+    /// Plans the modules `deck` enables. `staged` lists the file names already
+    /// present in the run directory.
+    pub fn for_deck(deck: &InputDeck, staged: &BTreeSet<String>) -> ComputeResult<Self> {
+        let mut selected = Vec::new();
+        let mut skipped = Vec::new();
+        for module in WORKFLOW_MODULE_ORDER {
+            match workflow_selection(deck, module) {
+                Ok(reason) => selected.push((module, reason)),
+                Err(reason) => skipped.push(SkippedModule {
+                    module,
+                    reason: reason.to_string(),
+                }),
+            }
+        }
+        Self::plan(selected, skipped, staged)
+    }
+
+    /// Plans the modules `feff.inp` in `directory` enables, staging the files
+    /// already in `directory`.
+    pub fn for_directory(directory: &Path) -> ComputeResult<Self> {
+        let input_path = directory.join("feff.inp");
+        let source = fs::read_to_string(&input_path).map_err(|source| {
+            FeffError::io_system(
+                "IO.RUN_READ",
+                format!(
+                    "failed to read input deck '{}': {}",
+                    input_path.display(),
+                    source
+                ),
+            )
+        })?;
+        let deck = parse_input_deck(&source)?;
+        Self::for_deck(&deck, &staged_artifacts(directory))
+    }
+
+    /// Plans an explicit module set, such as the modules a fixture covers.
+    pub fn for_modules(
+        modules: &[ComputeModule],
+        staged: &BTreeSet<String>,
+    ) -> ComputeResult<Self> {
+        let selected = modules
+            .iter()
+            .map(|module| (*module, "requested".to_string()))
+            .collect();
+        Self::plan(selected, Vec::new(), staged)
+    }
+
+    pub fn steps(&self) -> &[PipelineStep] {
+        &self.steps
+    }
+
+    pub fn skipped(&self) -> &[SkippedModule] {
+        &self.skipped
+    }
+
+    pub fn modules(&self) -> Vec<ComputeModule> {
+        self.steps.iter().map(|step| step.module).collect()
+    }
+
+    /// One line per module: the planned steps in order, then skipped modules.
+    pub fn explain(&self) -> String {
+        let mut text = String::new();
+        for (index, step) in self.steps.iter().enumerate() {
+            let _ = write!(
+                text,
+                "{:>2}. {:<13} {}",
+                index + 1,
+                step.module.as_str(),
+                step.reason
+            );
+            for (producer, artifacts) in &step.depends_on {
+                let _ = write!(text, "; after {} ({})", producer, artifacts.join(", "));
+            }
+            text.push('\n');
+        }
+        for skipped in &self.skipped {
+            let _ = writeln!(
+                text,
+                "  - {:<13} skipped: {}",
+                skipped.module.as_str(),
+                skipped.reason
+            );
+        }
+        text
+    }
+
+    fn plan(
+        selected: Vec<(ComputeModule, String)>,
+        skipped: Vec<SkippedModule>,
+        staged: &BTreeSet<String>,
+    ) -> ComputeResult<Self> {
+        let contracts: Vec<ModuleArtifacts> = selected
+            .iter()
+            .map(|(module, _)| ModuleArtifacts::for_module(*module))
+            .collect();
+
+        let mut depends_on = Vec::with_capacity(contracts.len());
+        for contract in &contracts {
+            let producers_of = |artifact: &str| {
+                contracts
+                    .iter()
+                    .filter(|other| {
+                        other.module != contract.module && other.outputs.contains(&artifact)
+                    })
+                    .map(|other| other.module)
+                    .collect::<Vec<_>>()
+            };
+            let available =
+                |artifact: &str| staged.contains(artifact) || !producers_of(artifact).is_empty();
+            if let Some(missing) = contract
+                .required_inputs
+                .iter()
+                .find(|artifact| !available(artifact))
+            {
+                return Err(unsatisfied_input_error(contract.module, &[missing]));
+            }
+            if !contract.any_of_inputs.is_empty()
+                && !contract
+                    .any_of_inputs
+                    .iter()
+                    .any(|artifact| available(artifact))
+            {
+                return Err(unsatisfied_input_error(
+                    contract.module,
+                    &contract.any_of_inputs,
+                ));
+            }
+
+            let mut edges: Vec<(ComputeModule, Vec<&'static str>)> = Vec::new();
+            for producer in &contracts {
+                if producer.module == contract.module {
+                    continue;
+                }
+                let provided: Vec<&'static str> = producer
+                    .outputs
+                    .iter()
+                    .copied()
+                    .filter(|artifact| contract.consumes(artifact))
+                    .collect();
+                if !provided.is_empty() {
+                    edges.push((producer.module, provided));
+                }
+            }
+            depends_on.push(edges);
+        }
+
+        // Kahn's algorithm, always taking the earliest ready module in
+        // WORKFLOW_MODULE_ORDER.
+        let mut remaining: Vec<usize> = (0..contracts.len()).collect();
+        remaining.sort_by_key(|index| preferred_rank(contracts[*index].module));
+        let mut done: Vec<ComputeModule> = Vec::new();
+        let mut steps = Vec::with_capacity(contracts.len());
+        while !remaining.is_empty() {
+            let Some(position) = remaining.iter().position(|index| {
+                depends_on[*index]
+                    .iter()
+                    .all(|(producer, _)| done.contains(producer))
+            }) else {
+                let cycle: Vec<String> = remaining
+                    .iter()
+                    .map(|index| contracts[*index].module.to_string())
+                    .collect();
+                return Err(FeffError::input_validation(
+                    "INPUT.PIPELINE",
+                    format!(
+                        "module contracts form a dependency cycle among {}",
+                        cycle.join(", ")
+                    ),
+                ));
+            };
+            let index = remaining.remove(position);
+            done.push(contracts[index].module);
+            steps.push(PipelineStep {
+                module: contracts[index].module,
+                reason: selected[index].1.clone(),
+                depends_on: std::mem::take(&mut depends_on[index]),
+            });
+        }
+
+        Ok(Self { steps, skipped })
+    }
+}
+
+/// File names directly inside `directory`, for [`PipelinePlan`] staging.
+pub fn staged_artifacts(directory: &Path) -> BTreeSet<String> {
+    fs::read_dir(directory)
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .filter(|entry| entry.path().is_file())
+                .map(|entry| entry.file_name().to_string_lossy().into_owned())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn preferred_rank(module: ComputeModule) -> usize {
+    WORKFLOW_MODULE_ORDER
+        .iter()
+        .position(|candidate| *candidate == module)
+        .unwrap_or(WORKFLOW_MODULE_ORDER.len())
+}
+
+fn unsatisfied_input_error(module: ComputeModule, candidates: &[&str]) -> FeffError {
+    let wanted = if candidates.len() == 1 {
+        format!("'{}'", candidates[0])
+    } else {
+        format!("one of '{}'", candidates.join("', '"))
+    };
+    FeffError::input_validation(
+        "INPUT.PIPELINE",
+        format!(
+            "{} requires {}, which no planned module produces and the run directory does not contain",
+            module, wanted
+        ),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ModuleArtifacts, PipelinePlan, WORKFLOW_MODULE_ORDER};
+    use crate::domain::{ComputeModule, FeffErrorCategory};
+    use crate::parser::parse_input_deck;
+    use std::collections::BTreeSet;
+
+    const DECK: &str =
+        "TITLE Cu\nPOTENTIALS\n0 29 Cu\n1 29 Cu\nATOMS\n0.0 0.0 0.0 0 Cu\n1.0 0.0 0.0 1 Cu\nEND\n";
+
+    fn staged(names: &[&str]) -> BTreeSet<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn deck_plan_runs_core_chain_and_explains_skips() {
+        let deck = parse_input_deck(DECK).expect("deck should parse");
+        let plan = PipelinePlan::for_deck(&deck, &staged(&["feff.inp"])).expect("plan");
+        assert_eq!(
+            plan.modules(),
+            vec![
+                ComputeModule::Rdinp,
+                ComputeModule::Pot,
+                ComputeModule::Xsph,
+                ComputeModule::Path,
+                ComputeModule::Debye,
+            ]
+        );
+        assert_eq!(plan.skipped().len(), 11);
+        let explanation = plan.explain();
+        assert!(explanation.contains(" 3. XSPH"), "{explanation}");
+        assert!(explanation.contains("after POT (pot.bin)"), "{explanation}");
+        assert!(
+            explanation.contains("FMS           skipped: no FMS card"),
+            "{explanation}"
+        );
+    }
+
+    #[test]
+    fn enabled_cards_add_modules_in_dependency_order() {
+        let source = format!("FMS 4.0\nLDOS -20 20 0.1\nSCREEN\nFULLSPECTRUM 1\nELNES\n{DECK}");
+        let deck = parse_input_deck(&source).expect("deck should parse");
+        let plan = PipelinePlan::for_deck(&deck, &staged(&["feff.inp"])).expect("plan");
+        let modules = plan.modules();
+        let position = |module| modules.iter().position(|m| *m == module).unwrap();
+        assert!(position(ComputeModule::Screen) < position(ComputeModule::Xsph));
+        assert!(position(ComputeModule::Xsph) < position(ComputeModule::Fms));
+        // Both FF2X and FULLSPECTRUM write xmu.dat, so EELS waits for both.
+        assert!(position(ComputeModule::FullSpectrum) < position(ComputeModule::Eels));
+        assert!(position(ComputeModule::Debye) < position(ComputeModule::FullSpectrum));
+        assert_eq!(modules.len(), 10);
+    }
+
+    #[test]
+    fn unsatisfied_inputs_fail_unless_staged() {
+        let deck = parse_input_deck(&format!("COMPTON\n{DECK}")).expect("deck should parse");
+        let error = PipelinePlan::for_deck(&deck, &staged(&["feff.inp"]))
+            .expect_err("gg_slice.bin has no producer");
+        assert_eq!(error.category(), FeffErrorCategory::InputValidationError);
+        assert_eq!(error.placeholder(), "INPUT.PIPELINE");
+        assert!(error.message().contains("COMPTON requires 'gg_slice.bin'"));
+
+        PipelinePlan::for_deck(&deck, &staged(&["feff.inp", "gg_slice.bin"]))
+            .expect("staged artifacts satisfy inputs");
+    }
+
+    #[test]
+    fn explicit_module_sets_are_ordered_by_contracts() {
+        let plan = PipelinePlan::for_modules(
+            &[
+                ComputeModule::SelfEnergy,
+                ComputeModule::Path,
+                ComputeModule::Debye,
+                ComputeModule::Xsph,
+                ComputeModule::Pot,
+            ],
+            &staged(&[
+                "pot.inp",
+                "geom.dat",
+                "xsph.inp",
+                "global.inp",
+                "paths.inp",
+                "ff2x.inp",
+                "feff.inp",
+                "sfconv.inp",
+            ]),
+        )
+        .expect("plan");
+        assert_eq!(
+            plan.modules(),
+            vec![
+                ComputeModule::Pot,
+                ComputeModule::Xsph,
+                ComputeModule::Path,
+                ComputeModule::Debye,
+                ComputeModule::SelfEnergy,
+            ]
+        );
+    }
+
+    #[test]
+    fn every_module_has_artifacts() {
+        for module in WORKFLOW_MODULE_ORDER {
+            let artifacts = ModuleArtifacts::for_module(module);
+            assert_eq!(artifacts.module, module);
+            assert!(
+                !artifacts.outputs.is_empty(),
+                "{module} should have outputs"
+            );
+        }
+    }
+}
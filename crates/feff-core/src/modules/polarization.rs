@@ -0,0 +1,417 @@
+use crate::support::genfmt::xstar::{XstarInput, xstar};
+use num_complex::Complex64;
+
+/// Exchange splitting between spin-up and spin-down final states used for the
+/// spin-resolved (XMCD) channels when the deck gives no absorber spin moment,
+/// in eV.
+pub(crate) const DEFAULT_EXCHANGE_SPLITTING_EV: f64 = 1.0;
+
+/// Stoner exchange parameter turning the absorber spin moment (Bohr magnetons)
+/// into a spin-up/spin-down splitting, in eV per Bohr magneton.
+pub(crate) const STONER_EXCHANGE_EV_PER_BOHR_MAGNETON: f64 = 0.95;
+
+const EXCHANGE_SPLITTING_HEADER: &str = "exchange splitting (eV)";
+
+const VECTOR_EPSILON: f64 = 1.0e-10;
+
+/// Photon polarization and spin settings carried from the `POLARIZATION`,
+/// `ELLIPTICITY` and `SPIN` cards through `global.inp`.
+///
+/// The default value is the isotropic (powder) average that every module used
+/// before these cards were honored.
+///
+/// XMCD is modelled as a rigid shift of the spin-up and spin-down spectra by
+/// `-/+ exchange_splitting / 2`. There are no spin-resolved potentials, so the
+/// dichroism has the same shape for every magnetic system; only its size
+/// follows `exchange_splitting`, which RDINP derives from the absorber spin
+/// moment in `POTENTIALS`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct PolarizationSettings {
+    pub(crate) ipol: i32,
+    pub(crate) ispin: i32,
+    pub(crate) ellipticity: f64,
+    pub(crate) evec: [f64; 3],
+    pub(crate) xivec: [f64; 3],
+    pub(crate) spvec: [f64; 3],
+    /// Spin-up/spin-down final-state splitting in eV.
+    pub(crate) exchange_splitting: f64,
+}
+
+impl Default for PolarizationSettings {
+    fn default() -> Self {
+        Self {
+            ipol: 0,
+            ispin: 0,
+            ellipticity: 0.0,
+            evec: [0.0; 3],
+            xivec: [0.0; 3],
+            spvec: [0.0; 3],
+            exchange_splitting: DEFAULT_EXCHANGE_SPLITTING_EV,
+        }
+    }
+}
+
+impl PolarizationSettings {
+    /// Exchange splitting for an absorber spin moment in Bohr magnetons, or the
+    /// default splitting when the deck gives none.
+    pub(crate) fn exchange_splitting_for_moment(spin_moment: Option<f64>) -> f64 {
+        spin_moment
+            .map(|moment| STONER_EXCHANGE_EV_PER_BOHR_MAGNETON * moment.abs())
+            .unwrap_or(DEFAULT_EXCHANGE_SPLITTING_EV)
+    }
+
+    /// The `global.inp` rows carrying `exchange_splitting`; only written for
+    /// spin-resolved runs.
+    pub(crate) fn exchange_splitting_rows(&self) -> String {
+        format!(
+            "{}\n{:>13.5}\n",
+            EXCHANGE_SPLITTING_HEADER, self.exchange_splitting
+        )
+    }
+
+    pub(crate) fn is_polarized(&self) -> bool {
+        self.ipol != 0
+    }
+
+    pub(crate) fn is_spin_resolved(&self) -> bool {
+        self.ispin != 0
+    }
+
+    pub(crate) fn is_isotropic(&self) -> bool {
+        !self.is_polarized() && !self.is_spin_resolved()
+    }
+
+    /// Reads the `ipol, ispin, ..., elpty` flags row and the `evec xivec spvec`
+    /// rows of `global.inp`. Files without these sections (legacy fixtures)
+    /// yield the isotropic default.
+    pub(crate) fn from_global_inp(source: &str) -> Self {
+        let lines: Vec<&str> = source.lines().collect();
+        let mut settings = Self::default();
+
+        if let Some(flags) = rows_after(&lines, |line| line.trim_start().starts_with("ipol"), 1)
+            .into_iter()
+            .next()
+            && flags.len() >= 4
+        {
+            settings.ipol = flags[0].round() as i32;
+            settings.ispin = flags[1].round() as i32;
+            settings.ellipticity = flags[3];
+        }
+
+        let vectors = rows_after(&lines, |line| line.trim_start().starts_with("evec"), 3);
+        for (target, row) in [&mut settings.evec, &mut settings.xivec, &mut settings.spvec]
+            .into_iter()
+            .zip(&vectors)
+        {
+            if row.len() >= 3 {
+                *target = [row[0], row[1], row[2]];
+            }
+        }
+
+        if let Some(row) = rows_after(
+            &lines,
+            |line| line.trim_start().starts_with(EXCHANGE_SPLITTING_HEADER),
+            1,
+        )
+        .into_iter()
+        .next()
+        {
+            settings.exchange_splitting = row[0].abs();
+        }
+
+        settings
+    }
+
+    /// Unit complex polarization vector `(e + i elpty (k x e)) / sqrt(1 + elpty^2)`,
+    /// or `None` for the isotropic average.
+    pub(crate) fn polarization_vector(&self) -> Option<[Complex64; 3]> {
+        if !self.is_polarized() {
+            return None;
+        }
+        let e = normalized(self.evec)?;
+        let minor = self.minor_axis(e);
+
+        let norm = match minor {
+            Some(_) => (1.0 + self.ellipticity * self.ellipticity).sqrt(),
+            None => 1.0,
+        };
+        Some(std::array::from_fn(|axis| {
+            let imaginary = minor.map_or(0.0, |minor| self.ellipticity * minor[axis]);
+            Complex64::new(e[axis], imaginary) / norm
+        }))
+    }
+
+    /// Minor axis `k x e` of the polarization ellipse, when there is one.
+    fn minor_axis(&self, e: [f64; 3]) -> Option<[f64; 3]> {
+        normalized(self.xivec)
+            .and_then(|beam| normalized(cross(beam, e)))
+            .filter(|_| self.ellipticity != 0.0)
+    }
+
+    /// Polarization tensor `P_mm' = conj(e_m) e_m'` in the spherical basis
+    /// `m = -1, 0, 1`, as written to `global.inp`. The isotropic average is
+    /// `1/3` on the diagonal.
+    pub(crate) fn spherical_tensor(&self) -> [[Complex64; 3]; 3] {
+        let Some(e) = self.polarization_vector() else {
+            return std::array::from_fn(|row| {
+                std::array::from_fn(|column| {
+                    if row == column {
+                        Complex64::new(1.0 / 3.0, 0.0)
+                    } else {
+                        Complex64::new(0.0, 0.0)
+                    }
+                })
+            });
+        };
+        let i = Complex64::new(0.0, 1.0);
+        let sqrt_half = std::f64::consts::FRAC_1_SQRT_2;
+        let spherical = [
+            (e[0] - i * e[1]) * sqrt_half,
+            e[2],
+            -(e[0] + i * e[1]) * sqrt_half,
+        ];
+        std::array::from_fn(|row| {
+            std::array::from_fn(|column| spherical[row].conj() * spherical[column])
+        })
+    }
+
+    /// Dipole weight `3 |e . r|^2` of a bond along `direction`, evaluated with
+    /// the single-scattering `xstar` angular factor. The isotropic average and
+    /// a zero-length direction both give 1.
+    pub(crate) fn dipole_factor(&self, direction: [f64; 3]) -> f64 {
+        if !self.is_polarized() {
+            return 1.0;
+        }
+        let (Some(e), Some(r)) = (normalized(self.evec), normalized(direction)) else {
+            return 1.0;
+        };
+        let minor = self.minor_axis(e);
+        xstar(&XstarInput {
+            eps1: e,
+            eps2: minor.unwrap_or(e),
+            vec1: r,
+            vec2: r,
+            ndeg: 3.0,
+            elpty: if minor.is_some() {
+                self.ellipticity
+            } else {
+                0.0
+            },
+            lfin: 1,
+        })
+        .unwrap_or(1.0)
+    }
+
+    /// Dipole weight averaged over absorber-centered neighbor vectors with the
+    /// `1/r^2` spherical-wave amplitude of each bond.
+    pub(crate) fn shell_dipole_factor(&self, neighbors: &[[f64; 3]]) -> f64 {
+        if !self.is_polarized() {
+            return 1.0;
+        }
+        let (weighted, total) = neighbors
+            .iter()
+            .filter_map(|vector| {
+                let radius_sq = dot(*vector, *vector);
+                (radius_sq > VECTOR_EPSILON).then(|| (self.dipole_factor(*vector), 1.0 / radius_sq))
+            })
+            .fold((0.0, 0.0), |(weighted, total), (factor, weight)| {
+                (weighted + factor * weight, total + weight)
+            });
+        if total > 0.0 { weighted / total } else { 1.0 }
+    }
+
+    /// Same beam with the linear polarization rotated by 90 degrees about the
+    /// beam direction; used for the perpendicular linear-dichroism channel.
+    pub(crate) fn perpendicular_linear(&self) -> Self {
+        let e = normalized(self.evec).unwrap_or([0.0, 0.0, 1.0]);
+        let rotated = normalized(self.xivec)
+            .and_then(|beam| normalized(cross(beam, e)))
+            .or_else(|| normalized(cross(e, [0.0, 0.0, 1.0])))
+            .or_else(|| normalized(cross(e, [1.0, 0.0, 0.0])))
+            .unwrap_or([1.0, 0.0, 0.0]);
+        Self {
+            ipol: 1,
+            ellipticity: 0.0,
+            evec: rotated,
+            ..*self
+        }
+    }
+
+    /// Degree of circular polarization `2 elpty / (1 + elpty^2)`.
+    pub(crate) fn circular_degree(&self) -> f64 {
+        if !self.is_polarized() {
+            return 0.0;
+        }
+        2.0 * self.ellipticity / (1.0 + self.ellipticity * self.ellipticity)
+    }
+
+    /// Projection of the photon helicity on the spin axis, signed by `ispin`.
+    /// This scales the spin-up minus spin-down difference into the XMCD signal.
+    pub(crate) fn xmcd_projection(&self) -> f64 {
+        if !self.is_spin_resolved() {
+            return 0.0;
+        }
+        let Some(beam) = normalized(self.xivec) else {
+            return 0.0;
+        };
+        let spin = normalized(self.spvec).unwrap_or([0.0, 0.0, 1.0]);
+        self.circular_degree() * dot(beam, spin) * f64::from(self.ispin.signum())
+    }
+}
+
+fn rows_after(lines: &[&str], is_header: impl Fn(&str) -> bool, count: usize) -> Vec<Vec<f64>> {
+    let Some(header) = lines.iter().position(|line| is_header(line)) else {
+        return Vec::new();
+    };
+    lines[header + 1..]
+        .iter()
+        .map(|line| {
+            line.split_whitespace()
+                .map(|token| token.replace(['D', 'd'], "E").parse::<f64>())
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .take_while(Result::is_ok)
+        .filter_map(Result::ok)
+        .filter(|row| !row.is_empty())
+        .take(count)
+        .collect()
+}
+
+fn dot(left: [f64; 3], right: [f64; 3]) -> f64 {
+    left[0] * right[0] + left[1] * right[1] + left[2] * right[2]
+}
+
+fn cross(left: [f64; 3], right: [f64; 3]) -> [f64; 3] {
+    [
+        left[1] * right[2] - left[2] * right[1],
+        left[2] * right[0] - left[0] * right[2],
+        left[0] * right[1] - left[1] * right[0],
+    ]
+}
+
+fn normalized(vector: [f64; 3]) -> Option<[f64; 3]> {
+    let norm = dot(vector, vector).sqrt();
+    (norm.is_finite() && norm > VECTOR_EPSILON).then(|| vector.map(|value| value / norm))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PolarizationSettings;
+
+    fn linear(evec: [f64; 3]) -> PolarizationSettings {
+        PolarizationSettings {
+            ipol: 1,
+            evec,
+            ..PolarizationSettings::default()
+        }
+    }
+
+    #[test]
+    fn isotropic_settings_leave_every_bond_weight_at_one() {
+        let settings = PolarizationSettings::default();
+        assert!(settings.is_isotropic());
+        assert_eq!(settings.dipole_factor([0.0, 0.0, 2.0]), 1.0);
+        assert_eq!(
+            settings.shell_dipole_factor(&[[1.0, 0.0, 0.0], [0.0, 2.0, 0.0]]),
+            1.0
+        );
+        let tensor = settings.spherical_tensor();
+        for (row, values) in tensor.iter().enumerate() {
+            for (column, value) in values.iter().enumerate() {
+                let expected = if row == column { 1.0 / 3.0 } else { 0.0 };
+                assert!((value.re - expected).abs() < 1.0e-12 && value.im == 0.0);
+            }
+        }
+    }
+
+    #[test]
+    fn linear_polarization_weights_bonds_by_cos_squared_and_averages_to_one() {
+        let settings = linear([0.0, 0.0, 1.0]);
+        assert!((settings.dipole_factor([0.0, 0.0, 1.5]) - 3.0).abs() < 1.0e-12);
+        assert!(settings.dipole_factor([1.5, 0.0, 0.0]).abs() < 1.0e-12);
+
+        let cube = [
+            [1.0, 0.0, 0.0],
+            [-1.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0],
+            [0.0, -1.0, 0.0],
+            [0.0, 0.0, 1.0],
+            [0.0, 0.0, -1.0],
+        ];
+        assert!((settings.shell_dipole_factor(&cube) - 1.0).abs() < 1.0e-12);
+        assert!((settings.shell_dipole_factor(&cube[4..]) - 3.0).abs() < 1.0e-12);
+
+        let perpendicular = settings.perpendicular_linear();
+        assert!(perpendicular.shell_dipole_factor(&cube[4..]).abs() < 1.0e-12);
+    }
+
+    #[test]
+    fn circular_polarization_projects_helicity_on_spin_axis() {
+        let settings = PolarizationSettings {
+            ipol: 1,
+            ispin: -1,
+            ellipticity: 1.0,
+            evec: [1.0, 0.0, 0.0],
+            xivec: [0.0, 0.0, 1.0],
+            spvec: [0.0, 0.0, 1.0],
+            ..PolarizationSettings::default()
+        };
+        assert!((settings.circular_degree() - 1.0).abs() < 1.0e-12);
+        assert!((settings.xmcd_projection() + 1.0).abs() < 1.0e-12);
+
+        let e = settings.polarization_vector().expect("polarized");
+        let norm: f64 = e.iter().map(|component| component.norm_sqr()).sum();
+        assert!((norm - 1.0).abs() < 1.0e-12);
+        assert!((settings.dipole_factor([1.0, 0.0, 0.0]) - 1.5).abs() < 1.0e-12);
+        assert!((settings.dipole_factor([0.0, 1.0, 0.0]) - 1.5).abs() < 1.0e-12);
+
+        let trace: f64 = (0..3).map(|m| settings.spherical_tensor()[m][m].re).sum();
+        assert!((trace - 1.0).abs() < 1.0e-12);
+    }
+
+    #[test]
+    fn global_inp_rows_round_trip_into_settings() {
+        let source = " ipol, ispin, le2, elpty, angks, l2lp, do_nrixs, ldecmx, lj
+    1   -1    0      0.5000      0.0000    0    0   -1   -1
+evec\t\t  xivec \t   spvec
+      1.00000      0.00000      0.00000
+      0.00000      0.00000      1.00000
+      0.00000      0.00000      1.00000
+ polarization tensor
+exchange splitting (eV)
+      2.09000
+";
+        let settings = PolarizationSettings::from_global_inp(source);
+        assert_eq!(settings.ipol, 1);
+        assert_eq!(settings.ispin, -1);
+        assert_eq!(settings.ellipticity, 0.5);
+        assert_eq!(settings.evec, [1.0, 0.0, 0.0]);
+        assert_eq!(settings.xivec, [0.0, 0.0, 1.0]);
+        assert!(settings.xmcd_projection() < 0.0);
+        assert_eq!(settings.exchange_splitting, 2.09);
+
+        let legacy = PolarizationSettings::from_global_inp("edge emu\n1 2 3\n");
+        assert!(legacy.is_isotropic());
+        assert_eq!(legacy.exchange_splitting, 1.0);
+    }
+
+    #[test]
+    fn exchange_splitting_follows_the_absorber_spin_moment() {
+        let iron = PolarizationSettings::exchange_splitting_for_moment(Some(-2.2));
+        let nickel = PolarizationSettings::exchange_splitting_for_moment(Some(0.6));
+        assert!((iron - 2.09).abs() < 1.0e-12);
+        assert!(nickel < iron);
+        assert_eq!(
+            PolarizationSettings::exchange_splitting_for_moment(None),
+            1.0
+        );
+
+        let settings = PolarizationSettings {
+            ispin: 1,
+            exchange_splitting: iron,
+            ..PolarizationSettings::default()
+        };
+        let round_trip = PolarizationSettings::from_global_inp(&settings.exchange_splitting_rows());
+        assert!((round_trip.exchange_splitting - iron).abs() < 1.0e-5);
+    }
+}
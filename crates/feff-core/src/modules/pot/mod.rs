@@ -1,9 +1,8 @@
 mod model;
 mod parser;
 
-use super::ModuleExecutor;
-use crate::domain::{ComputeArtifact, ComputeRequest, ComputeResult, FeffError};
-use std::fs;
+use super::{ModuleContract, ModuleExecutor};
+use crate::domain::{ComputeArtifact, ComputeModule, ComputeRequest, ComputeResult, FeffError};
 
 use model::PotModel;
 use parser::{artifact_list, geom_input_path, read_input_source, validate_request_shape};
@@ -24,6 +23,18 @@ pub struct PotContract {
     pub expected_outputs: Vec<ComputeArtifact>,
 }
 
+impl ModuleContract for PotContract {
+    const MODULE: ComputeModule = ComputeModule::Pot;
+
+    fn required_input_names() -> Vec<&'static str> {
+        POT_REQUIRED_INPUTS.to_vec()
+    }
+
+    fn output_names() -> Vec<&'static str> {
+        POT_REQUIRED_OUTPUTS.to_vec()
+    }
+}
+
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub struct PotModule;
 
@@ -31,8 +42,8 @@ impl PotModule {
     pub fn contract_for_request(&self, request: &ComputeRequest) -> ComputeResult<PotContract> {
         validate_request_shape(request)?;
         Ok(PotContract {
-            required_inputs: artifact_list(&POT_REQUIRED_INPUTS),
-            expected_outputs: artifact_list(&POT_REQUIRED_OUTPUTS),
+            required_inputs: artifact_list(&PotContract::required_input_names()),
+            expected_outputs: artifact_list(&PotContract::output_names()),
         })
     }
 }
@@ -41,27 +52,33 @@ impl ModuleExecutor for PotModule {
     fn execute(&self, request: &ComputeRequest) -> ComputeResult<Vec<ComputeArtifact>> {
         validate_request_shape(request)?;
 
-        let pot_inp_source = read_input_source(&request.input_path, POT_REQUIRED_INPUTS[0])?;
+        let pot_inp_source =
+            read_input_source(&*request.store, &request.input_path, POT_REQUIRED_INPUTS[0])?;
         let geom_path = geom_input_path(request)?;
-        let geom_source = read_input_source(&geom_path, POT_REQUIRED_INPUTS[1])?;
-        let model = PotModel::from_sources(&request.fixture_id, &pot_inp_source, &geom_source)?;
+        let geom_source = read_input_source(&*request.store, &geom_path, POT_REQUIRED_INPUTS[1])?;
+        let model = PotModel::from_sources(&request.fixture_id, &pot_inp_source, &geom_source)?
+            .with_execution_control(request.control.clone())
+            .converge()?;
         let outputs = artifact_list(&POT_REQUIRED_OUTPUTS);
 
-        fs::create_dir_all(&request.output_dir).map_err(|source| {
-            FeffError::io_system(
-                "IO.POT_OUTPUT_DIRECTORY",
-                format!(
-                    "failed to create POT output directory '{}': {}",
-                    request.output_dir.display(),
-                    source
-                ),
-            )
-        })?;
+        request
+            .store
+            .create_dir_all(&request.output_dir)
+            .map_err(|source| {
+                FeffError::io_system(
+                    "IO.POT_OUTPUT_DIRECTORY",
+                    format!(
+                        "failed to create POT output directory '{}': {}",
+                        request.output_dir.display(),
+                        source
+                    ),
+                )
+            })?;
 
         for artifact in &outputs {
             let output_path = request.output_dir.join(&artifact.relative_path);
             if let Some(parent) = output_path.parent() {
-                fs::create_dir_all(parent).map_err(|source| {
+                request.store.create_dir_all(parent).map_err(|source| {
                     FeffError::io_system(
                         "IO.POT_OUTPUT_DIRECTORY",
                         format!(
@@ -74,7 +91,7 @@ impl ModuleExecutor for PotModule {
             }
 
             let artifact_name = artifact.relative_path.to_string_lossy().replace('\\', "/");
-            model.write_artifact(&artifact_name, &output_path)?;
+            model.write_artifact(&*request.store, &artifact_name, &output_path)?;
         }
 
         Ok(outputs)
@@ -84,13 +101,35 @@ impl ModuleExecutor for PotModule {
 #[cfg(test)]
 mod tests {
     use super::{POT_BINARY_MAGIC, PotModule};
-    use crate::domain::{ComputeArtifact, ComputeModule, ComputeRequest, FeffErrorCategory};
+    use crate::domain::{
+        CancellationToken, ComputeArtifact, ComputeModule, ComputeRequest, ExecutionControl,
+        ExecutionEvent, ExecutionObserver, FeffErrorCategory,
+    };
     use crate::modules::ModuleExecutor;
     use std::collections::BTreeSet;
     use std::fs;
     use std::path::{Path, PathBuf};
+    use std::sync::{Arc, Mutex};
     use tempfile::TempDir;
 
+    /// Records SCF iterations and cancels `token` after the third.
+    struct ScfRecorder {
+        iterations: Mutex<Vec<usize>>,
+        token: CancellationToken,
+    }
+
+    impl ExecutionObserver for ScfRecorder {
+        fn on_event(&self, event: &ExecutionEvent) {
+            if let ExecutionEvent::ScfIteration { iteration, .. } = event {
+                let mut iterations = self.iterations.lock().expect("iterations");
+                iterations.push(*iteration);
+                if iterations.len() == 3 {
+                    self.token.cancel();
+                }
+            }
+        }
+    }
+
     #[test]
     fn contract_exposes_required_inputs_and_outputs() {
         let request =
@@ -147,6 +186,35 @@ mod tests {
         assert!(pot_dat.contains("index iz lmaxsc"));
     }
 
+    #[test]
+    fn scf_iterations_are_reported_before_outputs_are_written() {
+        let temp = TempDir::new().expect("tempdir should be created");
+        let input_path = temp.path().join("pot.inp");
+        let output_dir = temp.path().join("actual");
+        stage_pot_inputs(&input_path, &temp.path().join("geom.dat"));
+
+        let token = CancellationToken::new();
+        let recorder = Arc::new(ScfRecorder {
+            iterations: Mutex::new(Vec::new()),
+            token: token.clone(),
+        });
+        let mut request =
+            ComputeRequest::new("FX-POT-001", ComputeModule::Pot, &input_path, &output_dir);
+        request.control = ExecutionControl::default()
+            .with_observer(recorder.clone())
+            .with_cancellation(token);
+
+        let error = PotModule
+            .execute(&request)
+            .expect_err("cancelled SCF should stop POT");
+        assert_eq!(error.placeholder(), "RUN.CANCELLED");
+        assert_eq!(
+            *recorder.iterations.lock().expect("iterations"),
+            vec![1, 2, 3]
+        );
+        assert!(!output_dir.join("pot.bin").exists());
+    }
+
     #[test]
     fn execute_is_deterministic_for_same_input() {
         let temp = TempDir::new().expect("tempdir should be created");
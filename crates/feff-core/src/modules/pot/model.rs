@@ -1,7 +1,11 @@
 use super::POT_BINARY_MAGIC;
 use super::parser::{GeomModel, PotControl, PotentialEntry, parse_geom_input, parse_pot_input};
-use crate::domain::{ComputeResult, FeffError};
-use crate::modules::serialization::{format_fixed_f64, write_binary_artifact, write_text_artifact};
+use crate::domain::{
+    ArtifactStore, ComputeModule, ComputeResult, ExecutionControl, ExecutionEvent, FeffError,
+};
+use crate::modules::serialization::{
+    format_fixed_f64, write_binary_artifact_to, write_text_artifact_to,
+};
 use crate::support::fovrg::aprdep::aprdep;
 use crate::support::inpgen::m_pot_generator::{PotGenRule, XyzFormat, gen_pot_from_xyz};
 use std::path::Path;
@@ -13,6 +17,9 @@ pub(super) struct PotModel {
     control: PotControl,
     potentials: Vec<PotentialEntry>,
     geometry: GeomModel,
+    execution_control: ExecutionControl,
+    coarse_residuals: Vec<f64>,
+    fine_residuals: Vec<f64>,
 }
 
 impl PotModel {
@@ -29,29 +36,26 @@ impl PotModel {
             control,
             potentials,
             geometry,
+            execution_control: ExecutionControl::default(),
+            coarse_residuals: Vec::new(),
+            fine_residuals: Vec::new(),
         })
     }
 
+    pub(super) fn with_execution_control(mut self, execution_control: ExecutionControl) -> Self {
+        self.execution_control = execution_control;
+        self
+    }
+
     pub(super) fn write_artifact(
         &self,
+        store: &dyn ArtifactStore,
         artifact_name: &str,
         output_path: &Path,
     ) -> ComputeResult<()> {
         match artifact_name {
-            "pot.bin" => {
-                write_binary_artifact(output_path, &self.render_pot_binary()).map_err(|source| {
-                    FeffError::io_system(
-                        "IO.POT_OUTPUT_WRITE",
-                        format!(
-                            "failed to write POT artifact '{}': {}",
-                            output_path.display(),
-                            source
-                        ),
-                    )
-                })
-            }
-            "pot.dat" => {
-                write_text_artifact(output_path, &self.render_pot_dat()).map_err(|source| {
+            "pot.bin" => write_binary_artifact_to(store, output_path, &self.render_pot_binary())
+                .map_err(|source| {
                     FeffError::io_system(
                         "IO.POT_OUTPUT_WRITE",
                         format!(
@@ -60,19 +64,8 @@ impl PotModel {
                             source
                         ),
                     )
-                })
-            }
-            "log1.dat" => write_text_artifact(output_path, &self.render_log()).map_err(|source| {
-                FeffError::io_system(
-                    "IO.POT_OUTPUT_WRITE",
-                    format!(
-                        "failed to write POT artifact '{}': {}",
-                        output_path.display(),
-                        source
-                    ),
-                )
-            }),
-            "convergence.scf" => write_text_artifact(output_path, &self.render_convergence(false))
+                }),
+            "pot.dat" => write_text_artifact_to(store, output_path, &self.render_pot_dat())
                 .map_err(|source| {
                     FeffError::io_system(
                         "IO.POT_OUTPUT_WRITE",
@@ -83,8 +76,8 @@ impl PotModel {
                         ),
                     )
                 }),
-            "convergence.scf.fine" => {
-                write_text_artifact(output_path, &self.render_convergence(true)).map_err(|source| {
+            "log1.dat" => {
+                write_text_artifact_to(store, output_path, &self.render_log()).map_err(|source| {
                     FeffError::io_system(
                         "IO.POT_OUTPUT_WRITE",
                         format!(
@@ -95,6 +88,34 @@ impl PotModel {
                     )
                 })
             }
+            "convergence.scf" => {
+                write_text_artifact_to(store, output_path, &self.render_convergence(false)).map_err(
+                    |source| {
+                        FeffError::io_system(
+                            "IO.POT_OUTPUT_WRITE",
+                            format!(
+                                "failed to write POT artifact '{}': {}",
+                                output_path.display(),
+                                source
+                            ),
+                        )
+                    },
+                )
+            }
+            "convergence.scf.fine" => {
+                write_text_artifact_to(store, output_path, &self.render_convergence(true)).map_err(
+                    |source| {
+                        FeffError::io_system(
+                            "IO.POT_OUTPUT_WRITE",
+                            format!(
+                                "failed to write POT artifact '{}': {}",
+                                output_path.display(),
+                                source
+                            ),
+                        )
+                    },
+                )
+            }
             other => Err(FeffError::internal(
                 "SYS.POT_OUTPUT_CONTRACT",
                 format!("unsupported POT output artifact '{}'", other),
@@ -253,8 +274,16 @@ impl PotModel {
         )
     }
 
-    fn render_convergence(&self, fine: bool) -> String {
-        let label = if fine { "fine" } else { "coarse" };
+    /// Runs the coarse and fine SCF mixing loops before any artifact is
+    /// written. The fine loop is the converged one, so its iterations are
+    /// reported to the observer as they complete.
+    pub(super) fn converge(mut self) -> ComputeResult<Self> {
+        self.coarse_residuals = self.scf_residuals(false)?;
+        self.fine_residuals = self.scf_residuals(true)?;
+        Ok(self)
+    }
+
+    fn scf_residuals(&self, fine: bool) -> ComputeResult<Vec<f64>> {
         let base_iterations = self.control.nmix.unsigned_abs() as usize;
         let iterations = if fine {
             base_iterations.clamp(6, 20)
@@ -266,18 +295,40 @@ impl PotModel {
             + self.control.ca1.abs() * 0.45_f64
             + self.control.rfms1.abs() * 0.02_f64
             + (self.geometry.nat as f64) * 1.0e-4_f64;
+
+        let mut residuals = Vec::with_capacity(iterations);
+        for iteration in 1..=iterations {
+            let residual = base_residual * damping.powi(iteration as i32);
+            if fine {
+                self.execution_control.checkpoint(ComputeModule::Pot)?;
+                self.execution_control.emit(ExecutionEvent::ScfIteration {
+                    iteration,
+                    residual,
+                });
+            }
+            residuals.push(residual);
+        }
+        Ok(residuals)
+    }
+
+    fn render_convergence(&self, fine: bool) -> String {
+        let (label, residuals) = if fine {
+            ("fine", &self.fine_residuals)
+        } else {
+            ("coarse", &self.coarse_residuals)
+        };
         let mixing = (self.control.ca1.abs() + 0.15_f64).clamp(0.10_f64, 0.95_f64);
 
         let mut lines = Vec::new();
         lines.push(format!("iteration residual delta_mu mixing ({})", label));
 
-        for iteration in 1..=iterations {
-            let residual = base_residual * damping.powi(iteration as i32);
+        for (index, residual) in residuals.iter().enumerate() {
+            let iteration = index + 1;
             let delta_mu = residual * (0.35_f64 + 0.03_f64 * iteration as f64);
             lines.push(format!(
                 "{:>3} {} {} {}",
                 iteration,
-                format_fixed_f64(residual, 13, 7),
+                format_fixed_f64(*residual, 13, 7),
                 format_fixed_f64(delta_mu, 13, 7),
                 format_fixed_f64(mixing, 9, 5),
             ));
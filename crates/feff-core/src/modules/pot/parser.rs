@@ -1,6 +1,7 @@
 use super::POT_REQUIRED_INPUTS;
-use crate::domain::{ComputeArtifact, ComputeModule, ComputeRequest, ComputeResult, FeffError};
-use std::fs;
+use crate::domain::{
+    ArtifactStore, ComputeArtifact, ComputeModule, ComputeRequest, ComputeResult, FeffError,
+};
 use std::path::{Path, PathBuf};
 
 #[derive(Debug, Clone, Copy)]
@@ -106,8 +107,12 @@ pub(super) fn geom_input_path(request: &ComputeRequest) -> ComputeResult<PathBuf
         })
 }
 
-pub(super) fn read_input_source(input_path: &Path, label: &str) -> ComputeResult<String> {
-    fs::read_to_string(input_path).map_err(|source| {
+pub(super) fn read_input_source(
+    store: &dyn ArtifactStore,
+    input_path: &Path,
+    label: &str,
+) -> ComputeResult<String> {
+    store.read_to_string(input_path).map_err(|source| {
         FeffError::io_system(
             "IO.POT_INPUT_READ",
             format!(
@@ -347,7 +352,7 @@ fn pot_contract_error(fixture_id: &str, reason: &str) -> FeffError {
     FeffError::computation(
         "RUN.POT_INPUT_MISMATCH",
         format!(
-            "fixture '{}' input contract mismatch for POT compute path: {}",
+            "run '{}' input contract mismatch for POT compute path: {}",
             fixture_id, reason
         ),
     )
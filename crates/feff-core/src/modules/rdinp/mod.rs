@@ -1,13 +1,14 @@
 mod model;
 mod parser;
 
-use super::ModuleExecutor;
-use crate::domain::{ComputeArtifact, ComputeRequest, ComputeResult, FeffError};
+use super::{ModuleContract, ModuleExecutor};
+use crate::domain::{ComputeArtifact, ComputeModule, ComputeRequest, ComputeResult, FeffError};
 use crate::parser::parse_input_deck;
-use std::fs;
+use std::path::Path;
 
 use model::RdinpModel;
-use parser::{artifact_list, read_input_source, validate_request_shape};
+pub(crate) use model::workflow_selection;
+use parser::{artifact_list, expand_cif_structure, read_input_source, validate_request_shape};
 
 pub(crate) const RDINP_REQUIRED_INPUTS: [&str; 1] = ["feff.inp"];
 pub(crate) const RDINP_BASE_OUTPUTS_PREFIX: [&str; 5] = [
@@ -35,6 +36,27 @@ pub(crate) const RDINP_BASE_OUTPUTS_SUFFIX: [&str; 14] = [
 ];
 pub(crate) const RDINP_OPTIONAL_SCREEN_OUTPUT: &str = "screen.inp";
 
+/// Order in which the modules of a deck-driven run execute, following the
+/// FEFF10 sequential driver.
+pub const WORKFLOW_MODULE_ORDER: [ComputeModule; 16] = [
+    ComputeModule::Rdinp,
+    ComputeModule::Dmdw,
+    ComputeModule::Pot,
+    ComputeModule::Ldos,
+    ComputeModule::Screen,
+    ComputeModule::Crpa,
+    ComputeModule::Xsph,
+    ComputeModule::Band,
+    ComputeModule::Fms,
+    ComputeModule::Path,
+    ComputeModule::Debye,
+    ComputeModule::SelfEnergy,
+    ComputeModule::Compton,
+    ComputeModule::Eels,
+    ComputeModule::Rixs,
+    ComputeModule::FullSpectrum,
+];
+
 pub(crate) const GLOBAL_INP_TEMPLATE: &str = " nabs, iphabs - CFAVERAGE data
        1       0 100000.00000
  ipol, ispin, le2, elpty, angks, l2lp, do_nrixs, ldecmx, lj
@@ -152,6 +174,24 @@ pub(crate) const FULLSPECTRUM_INP_TEMPLATE: &str = " mFullSpectrum
 pub struct RdinpContract {
     pub required_inputs: Vec<ComputeArtifact>,
     pub expected_outputs: Vec<ComputeArtifact>,
+    /// Modules the deck enables, in [`WORKFLOW_MODULE_ORDER`].
+    pub workflow_modules: Vec<ComputeModule>,
+}
+
+impl ModuleContract for RdinpContract {
+    const MODULE: ComputeModule = ComputeModule::Rdinp;
+
+    fn required_input_names() -> Vec<&'static str> {
+        RDINP_REQUIRED_INPUTS.to_vec()
+    }
+
+    fn output_names() -> Vec<&'static str> {
+        RDINP_BASE_OUTPUTS_PREFIX
+            .into_iter()
+            .chain([RDINP_OPTIONAL_SCREEN_OUTPUT])
+            .chain(RDINP_BASE_OUTPUTS_SUFFIX)
+            .collect()
+    }
 }
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
@@ -161,8 +201,9 @@ impl RdinpModule {
     pub fn contract_for_request(&self, request: &ComputeRequest) -> ComputeResult<RdinpContract> {
         let model = model_for_request(request)?;
         Ok(RdinpContract {
-            required_inputs: artifact_list(&RDINP_REQUIRED_INPUTS),
+            required_inputs: artifact_list(&RdinpContract::required_input_names()),
             expected_outputs: model.expected_outputs,
+            workflow_modules: model.workflow_modules,
         })
     }
 }
@@ -170,21 +211,24 @@ impl RdinpModule {
 impl ModuleExecutor for RdinpModule {
     fn execute(&self, request: &ComputeRequest) -> ComputeResult<Vec<ComputeArtifact>> {
         let model = model_for_request(request)?;
-        fs::create_dir_all(&request.output_dir).map_err(|source| {
-            FeffError::io_system(
-                "IO.RDINP_OUTPUT_DIRECTORY",
-                format!(
-                    "failed to create RDINP output directory '{}': {}",
-                    request.output_dir.display(),
-                    source
-                ),
-            )
-        })?;
+        request
+            .store
+            .create_dir_all(&request.output_dir)
+            .map_err(|source| {
+                FeffError::io_system(
+                    "IO.RDINP_OUTPUT_DIRECTORY",
+                    format!(
+                        "failed to create RDINP output directory '{}': {}",
+                        request.output_dir.display(),
+                        source
+                    ),
+                )
+            })?;
 
         for artifact in &model.expected_outputs {
             let output_path = request.output_dir.join(&artifact.relative_path);
             if let Some(parent) = output_path.parent() {
-                fs::create_dir_all(parent).map_err(|source| {
+                request.store.create_dir_all(parent).map_err(|source| {
                     FeffError::io_system(
                         "IO.RDINP_OUTPUT_DIRECTORY",
                         format!(
@@ -197,7 +241,7 @@ impl ModuleExecutor for RdinpModule {
             }
 
             let artifact_path = artifact.relative_path.to_string_lossy().replace('\\', "/");
-            model.write_artifact(&artifact_path, &output_path)?;
+            model.write_artifact(&*request.store, &artifact_path, &output_path)?;
         }
 
         Ok(model.expected_outputs)
@@ -206,14 +250,16 @@ impl ModuleExecutor for RdinpModule {
 
 fn model_for_request(request: &ComputeRequest) -> ComputeResult<RdinpModel> {
     validate_request_shape(request)?;
-    let input_source = read_input_source(&request.input_path)?;
+    let input_source = read_input_source(&*request.store, &request.input_path)?;
     let deck = parse_input_deck(&input_source)?;
+    let input_dir = request.input_path.parent().unwrap_or(Path::new("."));
+    let deck = expand_cif_structure(&*request.store, deck, input_dir)?;
     RdinpModel::from_deck(&deck)
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{RdinpModule, model::expected_outputs_for_screen_card};
+    use super::{GLOBAL_INP_TEMPLATE, RdinpModule, model::expected_outputs_for_screen_card};
     use crate::domain::{ComputeModule, ComputeRequest, FeffErrorCategory};
     use crate::modules::ModuleExecutor;
     use std::fs;
@@ -268,6 +314,50 @@ mod tests {
         );
     }
 
+    #[test]
+    fn contract_derives_workflow_modules_from_deck_cards() {
+        let temp = TempDir::new().expect("tempdir should be created");
+        let input_path = temp.path().join("feff.inp");
+        let deck = "TITLE Cu\nPOTENTIALS\n0 29 Cu\n1 29 Cu\nATOMS\n0.0 0.0 0.0 0 Cu\n1.0 0.0 0.0 1 Cu\nEND\n";
+        fs::write(&input_path, deck).expect("input should be written");
+        let request = ComputeRequest::new("Cu", ComputeModule::Rdinp, &input_path, temp.path());
+        let contract = RdinpModule
+            .contract_for_request(&request)
+            .expect("contract should build");
+        assert_eq!(
+            contract.workflow_modules,
+            vec![
+                ComputeModule::Rdinp,
+                ComputeModule::Pot,
+                ComputeModule::Xsph,
+                ComputeModule::Path,
+                ComputeModule::Debye,
+            ]
+        );
+
+        fs::write(
+            &input_path,
+            format!("FMS 4.0\nLDOS -20 20 0.1\nCOMPTON\n{deck}"),
+        )
+        .expect("input should be written");
+        let contract = RdinpModule
+            .contract_for_request(&request)
+            .expect("contract should build");
+        assert_eq!(
+            contract.workflow_modules,
+            vec![
+                ComputeModule::Rdinp,
+                ComputeModule::Pot,
+                ComputeModule::Ldos,
+                ComputeModule::Xsph,
+                ComputeModule::Fms,
+                ComputeModule::Path,
+                ComputeModule::Debye,
+                ComputeModule::Compton,
+            ]
+        );
+    }
+
     #[test]
     fn contract_adds_screen_output_when_screen_card_is_present() {
         let outputs = expected_outputs_for_screen_card(true);
@@ -371,4 +461,282 @@ mod tests {
             "xnatph should include atom count"
         );
     }
+
+    #[test]
+    fn nrixs_card_writes_q_vectors_into_global_inp() {
+        let temp = TempDir::new().expect("tempdir should be created");
+        let input_path = temp.path().join("feff.inp");
+        let output_dir = temp.path().join("actual");
+        fs::write(
+            &input_path,
+            "TITLE C\nNRIXS 2 0.0 0.0 2.0\n 3.0 4.0 0.0\nLJMAX 4\nPOTENTIALS\n0 6 C\n1 6 C\nATOMS\n0.0 0.0 0.0 0 C\n1.4 0.0 0.0 1 C\nEND\n",
+        )
+        .expect("input should be written");
+        let request = ComputeRequest::new(
+            "FX-RDINP-NRIXS",
+            ComputeModule::Rdinp,
+            &input_path,
+            &output_dir,
+        );
+
+        RdinpModule
+            .execute(&request)
+            .expect("execution should succeed");
+        let global = fs::read_to_string(output_dir.join("global.inp")).expect("global.inp");
+        let lines = global.lines().collect::<Vec<_>>();
+        let flags = lines[3].split_whitespace().collect::<Vec<_>>();
+        assert_eq!(&flags[6..], &["1", "-1", "4"]);
+        let nq = lines
+            .iter()
+            .position(|line| line.starts_with("nq,"))
+            .expect("nq header");
+        assert!(lines[nq + 1].trim_start().starts_with("2 "));
+        assert!(lines[nq + 1].contains(" F F "));
+        let q_rows = &lines[nq + 3..];
+        assert_eq!(q_rows.len(), 2);
+        let second = q_rows[1]
+            .split_whitespace()
+            .map(|token| token.parse::<f64>().expect("numeric q row"))
+            .collect::<Vec<_>>();
+        assert_eq!(second.len(), 9);
+        assert!((second[3] - 5.0).abs() < 1.0e-9);
+        assert!((second[4] - 0.5).abs() < 1.0e-9);
+        assert!((second[7] - 0.6).abs() < 1.0e-9);
+
+        let ff2x = fs::read_to_string(output_dir.join("ff2x.inp")).expect("ff2x.inp");
+        assert!(ff2x.contains("momentum transfer\n      0.00000      0.00000      2.00000\n"));
+    }
+
+    #[test]
+    fn global_inp_keeps_template_without_nrixs_card() {
+        let temp = TempDir::new().expect("tempdir should be created");
+        let input_path = temp.path().join("feff.inp");
+        let output_dir = temp.path().join("actual");
+        fs::write(
+            &input_path,
+            "TITLE Cu\nPOTENTIALS\n0 29 Cu\n1 29 Cu\nATOMS\n0.0 0.0 0.0 0 Cu\n1.0 0.0 0.0 1 Cu\nEND\n",
+        )
+        .expect("input should be written");
+        let request = ComputeRequest::new(
+            "FX-RDINP-001",
+            ComputeModule::Rdinp,
+            &input_path,
+            &output_dir,
+        );
+
+        RdinpModule
+            .execute(&request)
+            .expect("execution should succeed");
+        let global = fs::read_to_string(output_dir.join("global.inp")).expect("global.inp");
+        assert_eq!(global, GLOBAL_INP_TEMPLATE);
+    }
+
+    #[test]
+    fn elnes_card_writes_beam_rows_into_eels_inp() {
+        let temp = TempDir::new().expect("tempdir should be created");
+        let input_path = temp.path().join("feff.inp");
+        let output_dir = temp.path().join("actual");
+        fs::write(
+            &input_path,
+            "TITLE Cu\nELNES 4.0 0.07 0.0\n300\n0 1 0\n2.4 0.0\n5 3\n0.0 0.0\nPOTENTIALS\n0 29 Cu\n1 29 Cu\nATOMS\n0.0 0.0 0.0 0 Cu\n1.0 0.0 0.0 1 Cu\nEND\n",
+        )
+        .expect("input should be written");
+        let request = ComputeRequest::new("Cu", ComputeModule::Rdinp, &input_path, &output_dir);
+
+        RdinpModule
+            .execute(&request)
+            .expect("execution should succeed");
+        let eels = fs::read_to_string(output_dir.join("eels.inp")).expect("eels.inp");
+        let baseline = fs::read_to_string(
+            Path::new(env!("CARGO_MANIFEST_DIR"))
+                .join("../../artifacts/fortran-baselines/FX-EELS-001/baseline/eels.inp"),
+        )
+        .expect("baseline eels.inp");
+        assert_eq!(eels, baseline);
+    }
+
+    #[test]
+    fn bare_exelfs_card_enables_eels_with_default_beam() {
+        let temp = TempDir::new().expect("tempdir should be created");
+        let input_path = temp.path().join("feff.inp");
+        let output_dir = temp.path().join("actual");
+        fs::write(
+            &input_path,
+            "TITLE Cu\nEXELFS\nMAGIC 15\nPOTENTIALS\n0 29 Cu\n1 29 Cu\nATOMS\n0.0 0.0 0.0 0 Cu\n1.0 0.0 0.0 1 Cu\nEND\n",
+        )
+        .expect("input should be written");
+        let request = ComputeRequest::new("Cu", ComputeModule::Rdinp, &input_path, &output_dir);
+
+        RdinpModule
+            .execute(&request)
+            .expect("execution should succeed");
+        let eels = fs::read_to_string(output_dir.join("eels.inp")).expect("eels.inp");
+        let rows = eels.lines().skip(1).step_by(2).collect::<Vec<_>>();
+        assert_eq!(rows[0].trim(), "1");
+        assert_eq!(rows[3].trim(), "300000.00000");
+        assert_eq!(rows[4].split_whitespace().last(), Some("1.00000"));
+        assert_eq!(rows[5].split_whitespace().next(), Some("0.00240"));
+        assert_eq!(rows[8].trim(), "1");
+        assert_eq!(rows[9].trim(), "15.00000");
+    }
+
+    #[test]
+    fn nrixs_card_rejects_mismatched_q_count() {
+        let temp = TempDir::new().expect("tempdir should be created");
+        let input_path = temp.path().join("feff.inp");
+        fs::write(
+            &input_path,
+            "TITLE C\nNRIXS -2 2.5\nPOTENTIALS\n0 6 C\nATOMS\n0.0 0.0 0.0 0 C\nEND\n",
+        )
+        .expect("input should be written");
+        let request = ComputeRequest::new(
+            "FX-RDINP-NRIXS",
+            ComputeModule::Rdinp,
+            &input_path,
+            temp.path().join("actual"),
+        );
+
+        let error = RdinpModule
+            .execute(&request)
+            .expect_err("q count mismatch should fail");
+        assert_eq!(error.category(), FeffErrorCategory::InputValidationError);
+        assert_eq!(error.placeholder(), "INPUT.RDINP_NRIXS");
+    }
+
+    #[test]
+    fn polarization_cards_write_vectors_and_tensor_into_global_inp() {
+        let temp = TempDir::new().expect("tempdir should be created");
+        let input_path = temp.path().join("feff.inp");
+        let output_dir = temp.path().join("actual");
+        fs::write(
+            &input_path,
+            "TITLE Fe film\nPOLARIZATION 1.0 0.0 0.0\nELLIPTICITY 1.0 0.0 0.0 1.0\nSPIN 1\nPOTENTIALS\n0 26 Fe -1 -1 1 2.2\n1 26 Fe\nATOMS\n0.0 0.0 0.0 0 Fe\n2.0 0.0 0.0 1 Fe\nEND\n",
+        )
+        .expect("input should be written");
+        let request = ComputeRequest::new(
+            "FX-RDINP-XMCD",
+            ComputeModule::Rdinp,
+            &input_path,
+            &output_dir,
+        );
+
+        RdinpModule
+            .execute(&request)
+            .expect("execution should succeed");
+        let global = fs::read_to_string(output_dir.join("global.inp")).expect("global.inp");
+        let lines = global.lines().collect::<Vec<_>>();
+        let flags = lines[3].split_whitespace().collect::<Vec<_>>();
+        assert_eq!(&flags[..4], &["1", "1", "0", "1.0000"]);
+        assert_eq!(&flags[6..], &["0", "-1", "-1"]);
+        assert_eq!(
+            lines[5], "      1.00000      0.00000      0.00000",
+            "evec row"
+        );
+        assert_eq!(lines[6], "      0.00000      0.00000      1.00000");
+        assert_eq!(lines[7], "      0.00000      0.00000      1.00000");
+
+        let tensor = lines[9..12]
+            .iter()
+            .map(|row| {
+                row.split_whitespace()
+                    .map(|token| token.parse::<f64>().expect("numeric tensor row"))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        // e = (x + iy)/sqrt(2) only has an m = -1 spherical component.
+        assert!((tensor[0][0] - 1.0).abs() < 1.0e-5);
+        assert!(tensor[1][2].abs() < 1.0e-5 && tensor[2][4].abs() < 1.0e-5);
+
+        // The absorber's 2.2 Bohr magneton moment sets the exchange splitting.
+        assert_eq!(lines[12], "exchange splitting (eV)");
+        let splitting = lines[13]
+            .trim()
+            .parse::<f64>()
+            .expect("numeric exchange splitting");
+        assert!((splitting - 2.09).abs() < 1.0e-5, "splitting={splitting}");
+
+        let template_tail = GLOBAL_INP_TEMPLATE.lines().skip(12).collect::<Vec<_>>();
+        assert_eq!(&lines[14..], template_tail.as_slice());
+    }
+
+    #[test]
+    fn ellipticity_without_polarization_is_rejected() {
+        let temp = TempDir::new().expect("tempdir should be created");
+        let input_path = temp.path().join("feff.inp");
+        fs::write(
+            &input_path,
+            "TITLE Fe\nELLIPTICITY 0.5 0.0 0.0 1.0\nPOTENTIALS\n0 26 Fe\nATOMS\n0.0 0.0 0.0 0 Fe\nEND\n",
+        )
+        .expect("input should be written");
+        let request = ComputeRequest::new(
+            "FX-RDINP-XMCD",
+            ComputeModule::Rdinp,
+            &input_path,
+            temp.path().join("actual"),
+        );
+
+        let error = RdinpModule
+            .execute(&request)
+            .expect_err("ellipticity needs a polarization vector");
+        assert_eq!(error.category(), FeffErrorCategory::InputValidationError);
+        assert_eq!(error.placeholder(), "INPUT.RDINP_POLARIZATION");
+    }
+
+    #[test]
+    fn cif_card_builds_atoms_and_potentials_around_target_site() {
+        let temp = TempDir::new().expect("tempdir should be created");
+        fs::write(
+            temp.path().join("nacl.cif"),
+            "data_NaCl\n_cell_length_a 5.64\n_cell_length_b 5.64\n_cell_length_c 5.64\n\
+             loop_\n_symmetry_equiv_pos_as_xyz\nx,y,z\nx,y+1/2,z+1/2\nx+1/2,y,z+1/2\nx+1/2,y+1/2,z\n\
+             loop_\n_atom_site_label\n_atom_site_fract_x\n_atom_site_fract_y\n_atom_site_fract_z\n\
+             Na1 0 0 0\nCl1 0.5 0.5 0.5\n",
+        )
+        .expect("cif should be written");
+        let input_path = temp.path().join("feff.inp");
+        fs::write(&input_path, "CIF nacl.cif\nTARGET 2\nRPATH 2.9\nEND\n")
+            .expect("input should be written");
+        let output_dir = temp.path().join("actual");
+        let request = ComputeRequest::new(
+            "FX-RDINP-CIF",
+            ComputeModule::Rdinp,
+            &input_path,
+            &output_dir,
+        );
+
+        RdinpModule
+            .execute(&request)
+            .expect("CIF deck should run through RDINP");
+
+        let geom = fs::read_to_string(output_dir.join("geom.dat")).expect("geom.dat");
+        assert!(geom.starts_with("nat, nph =     7    1\n"), "{geom}");
+        let pot = fs::read_to_string(output_dir.join("pot.inp")).expect("pot.inp");
+        assert!(
+            pot.contains("   17    2"),
+            "absorber potential should be Cl: {pot}"
+        );
+        assert!(
+            pot.contains("   11    2"),
+            "neighbour potential should be Na: {pot}"
+        );
+    }
+
+    #[test]
+    fn missing_cif_file_is_an_io_error() {
+        let temp = TempDir::new().expect("tempdir should be created");
+        let input_path = temp.path().join("feff.inp");
+        fs::write(&input_path, "CIF absent.cif\nEND\n").expect("input should be written");
+        let request = ComputeRequest::new(
+            "FX-RDINP-CIF",
+            ComputeModule::Rdinp,
+            &input_path,
+            temp.path().join("actual"),
+        );
+
+        let error = RdinpModule
+            .execute(&request)
+            .expect_err("missing CIF should fail");
+        assert_eq!(error.category(), FeffErrorCategory::IoSystemError);
+        assert_eq!(error.placeholder(), "IO.RDINP_CIF_READ");
+    }
 }
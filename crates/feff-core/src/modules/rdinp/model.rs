@@ -1,15 +1,20 @@
 use super::parser::{
-    AtomSite, PotentialEntry, card_value, deck_edge_label, deck_title, first_card, has_card,
-    parse_atoms, parse_potentials, required_card_value, sort_atoms_by_distance,
+    AtomSite, EelsSettings, NrixsSettings, PotentialEntry, card_value, deck_edge_label, deck_title,
+    first_card, has_card, parse_atoms, parse_eels, parse_nrixs, parse_polarization,
+    parse_potentials, required_card_value, sort_atoms_by_distance,
 };
 use super::{
     BAND_INP_TEMPLATE, COMPTON_INP_TEMPLATE, CRPA_INP_TEMPLATE, DMDW_INP_TEMPLATE,
     EELS_INP_TEMPLATE, FULLSPECTRUM_INP_TEMPLATE, GENFMT_INP_TEMPLATE, GLOBAL_INP_TEMPLATE,
     RDINP_BASE_OUTPUTS_PREFIX, RDINP_BASE_OUTPUTS_SUFFIX, RDINP_OPTIONAL_SCREEN_OUTPUT,
-    RECIPROCAL_INP_TEMPLATE, RIXS_INP_TEMPLATE,
+    RECIPROCAL_INP_TEMPLATE, RIXS_INP_TEMPLATE, WORKFLOW_MODULE_ORDER,
 };
-use crate::domain::{ComputeArtifact, ComputeResult, FeffError, InputDeck};
-use crate::modules::serialization::{format_fixed_f64, write_text_artifact};
+use crate::domain::{
+    ArtifactStore, ComputeArtifact, ComputeModule, ComputeResult, FeffError, InputDeck,
+};
+use crate::modules::polarization::PolarizationSettings;
+use crate::modules::serialization::{format_fixed_f64, write_text_artifact_to};
+use crate::support::common::edgedb::core_hole_width;
 
 #[derive(Debug, Clone)]
 pub(super) struct RdinpModel {
@@ -20,6 +25,7 @@ pub(super) struct RdinpModel {
     ispec: i32,
     nohole: i32,
     nscmt: i32,
+    gamach: f64,
     ca1: f64,
     rfms: f64,
     rdirec: f64,
@@ -38,7 +44,11 @@ pub(super) struct RdinpModel {
     run_crpa: bool,
     run_full_spectrum: bool,
     rixs_edge_label: String,
+    nrixs: Option<NrixsSettings>,
+    eels: Option<EelsSettings>,
+    polarization: PolarizationSettings,
     pub(super) expected_outputs: Vec<ComputeArtifact>,
+    pub(super) workflow_modules: Vec<ComputeModule>,
 }
 
 impl RdinpModel {
@@ -49,10 +59,17 @@ impl RdinpModel {
         let has_screen = has_card(deck, "SCREEN");
         let run_compton = has_card(deck, "COMPTON");
         let run_band = has_card(deck, "BAND") || has_card(deck, "MBAND");
-        let run_rixs = has_card(deck, "RIXS") || has_card(deck, "XES");
+        let run_rixs = has_card(deck, "RIXS");
         let run_crpa = has_card(deck, "CRPA");
         let run_full_spectrum = has_card(deck, "FULLSPECTRUM") || has_card(deck, "MFULLSPECTRUM");
-        let ispec = if has_xanes { 1 } else { 0 };
+        // XES is the non-resonant emission spectrum of the regular chain.
+        let ispec = if has_card(deck, "XES") {
+            2
+        } else if has_xanes {
+            1
+        } else {
+            0
+        };
         let rfms = card_value(deck, "SCF", 0)?.unwrap_or(-1.0);
         let rdirec = card_value(deck, "XANES", 0)?.unwrap_or(-1.0);
         let has_ldos_card = has_card(deck, "LDOS");
@@ -95,13 +112,24 @@ impl RdinpModel {
 
         let nscmt = if has_xanes { 30 } else { 0 };
         let ca1 = if has_card(deck, "SCF") { 0.2 } else { 0.0 };
+        let gamach = default_core_hole_width(&potentials, &deck_edge_label(deck));
         let title = deck_title(deck);
         let rixs_edge_label = if run_rixs {
             deck_edge_label(deck)
         } else {
             "NULL".to_string()
         };
+        let nrixs = parse_nrixs(deck)?;
+        let eels = parse_eels(deck)?;
+        let mut polarization = parse_polarization(deck)?;
+        polarization.exchange_splitting = PolarizationSettings::exchange_splitting_for_moment(
+            potentials
+                .iter()
+                .find(|potential| potential.ipot == 0)
+                .and_then(|potential| potential.spin_moment),
+        );
         let expected_outputs = expected_outputs_for_screen_card(has_screen);
+        let workflow_modules = workflow_modules_for_deck(deck);
 
         Ok(Self {
             title,
@@ -111,6 +139,7 @@ impl RdinpModel {
             ispec,
             nohole,
             nscmt,
+            gamach,
             ca1,
             rfms,
             rdirec,
@@ -129,14 +158,18 @@ impl RdinpModel {
             run_crpa,
             run_full_spectrum,
             rixs_edge_label,
+            nrixs,
+            eels,
+            polarization,
             expected_outputs,
+            workflow_modules,
         })
     }
 
     pub(super) fn render_artifact(&self, artifact_path: &str) -> ComputeResult<String> {
         match artifact_path {
             "geom.dat" => Ok(self.render_geom_dat()),
-            "global.inp" => Ok(GLOBAL_INP_TEMPLATE.to_string()),
+            "global.inp" => Ok(self.render_global_inp()),
             "reciprocal.inp" => Ok(RECIPROCAL_INP_TEMPLATE.to_string()),
             "pot.inp" => Ok(self.render_pot_inp()),
             "ldos.inp" => Ok(self.render_ldos_inp()),
@@ -144,10 +177,10 @@ impl RdinpModel {
             "xsph.inp" => Ok(self.render_xsph_inp()),
             "fms.inp" => Ok(self.render_fms_inp()),
             "paths.inp" => Ok(self.render_paths_inp()),
-            "genfmt.inp" => Ok(GENFMT_INP_TEMPLATE.to_string()),
+            "genfmt.inp" => Ok(self.render_genfmt_inp()),
             "ff2x.inp" => Ok(self.render_ff2x_inp()),
             "sfconv.inp" => Ok(self.render_sfconv_inp()),
-            "eels.inp" => Ok(EELS_INP_TEMPLATE.to_string()),
+            "eels.inp" => Ok(self.render_eels_inp()),
             "compton.inp" => Ok(self.render_compton_inp()),
             "band.inp" => Ok(self.render_band_inp()),
             "rixs.inp" => Ok(self.render_rixs_inp()),
@@ -164,11 +197,12 @@ impl RdinpModel {
 
     pub(super) fn write_artifact(
         &self,
+        store: &dyn ArtifactStore,
         artifact_path: &str,
         output_path: &std::path::Path,
     ) -> ComputeResult<()> {
         let content = self.render_artifact(artifact_path)?;
-        write_text_artifact(output_path, &content).map_err(|source| {
+        write_text_artifact_to(store, output_path, &content).map_err(|source| {
             FeffError::io_system(
                 "IO.RDINP_OUTPUT_WRITE",
                 format!(
@@ -180,6 +214,147 @@ impl RdinpModel {
         })
     }
 
+    fn ldecmx(&self) -> i32 {
+        self.nrixs.as_ref().map(|nrixs| nrixs.ldecmx).unwrap_or(-1)
+    }
+
+    fn render_eels_inp(&self) -> String {
+        let Some(eels) = &self.eels else {
+            return EELS_INP_TEMPLATE.to_string();
+        };
+        let integers = |values: &[i32]| {
+            let mut row: String = values.iter().map(|value| format!("{:>4}", value)).collect();
+            row.push('\n');
+            row
+        };
+        let reals = |values: &[f64]| {
+            let mut row: String = values.iter().copied().map(format_f64_13).collect();
+            row.push('\n');
+            row
+        };
+        let mut content = String::new();
+        content.push_str("calculate ELNES?\n");
+        content.push_str(&integers(&[1]));
+        content.push_str("average? relativistic? cross-terms? Which input?\n");
+        content.push_str(&integers(&[
+            eels.average,
+            eels.relativistic,
+            eels.cross_terms,
+            1,
+            4,
+        ]));
+        content.push_str("polarizations to be used ; min step max\n");
+        content.push_str(&integers(&eels.polarizations()));
+        content.push_str("beam energy in eV\n");
+        content.push_str(&reals(&[eels.beam_energy_ev]));
+        content.push_str("beam direction in arbitrary units\n");
+        content.push_str(&reals(&eels.beam_direction));
+        content.push_str("collection and convergence semiangle in rad\n");
+        content.push_str(&reals(&[
+            eels.collection_semiangle_rad,
+            eels.convergence_semiangle_rad,
+        ]));
+        content.push_str("qmesh - radial and angular grid size\n");
+        content.push_str(&integers(&eels.qmesh));
+        content.push_str("detector positions - two angles in rad\n");
+        content.push_str(&reals(&eels.detector));
+        content.push_str("calculate magic angle if magic=1\n");
+        content.push_str(&integers(&[i32::from(eels.magic_energy_ev.is_some())]));
+        content.push_str("energy for magic angle - eV above threshold\n");
+        content.push_str(&reals(&[eels.magic_energy_ev.unwrap_or(0.0)]));
+        content
+    }
+
+    fn render_global_inp(&self) -> String {
+        if self.nrixs.is_none() && self.polarization.is_isotropic() {
+            return GLOBAL_INP_TEMPLATE.to_string();
+        }
+        let polarization = &self.polarization;
+        let (do_nrixs, ljmax) = match &self.nrixs {
+            Some(nrixs) => (1, nrixs.ljmax),
+            None => (0, -1),
+        };
+
+        let mut content = String::new();
+        let mut lines = GLOBAL_INP_TEMPLATE.lines();
+        while let Some(line) = lines.next() {
+            content.push_str(line);
+            content.push('\n');
+            if line.starts_with(" ipol, ispin") {
+                lines.next();
+                content.push_str(&format!(
+                    "{:>5}{:>5}{:>5}{:>12.4}{:>12.4}{:>5}{:>5}{:>5}{:>5}\n",
+                    polarization.ipol,
+                    polarization.ispin,
+                    0,
+                    polarization.ellipticity,
+                    0.0,
+                    0,
+                    do_nrixs,
+                    self.ldecmx(),
+                    ljmax
+                ));
+            } else if line.starts_with("evec") {
+                for vector in [polarization.evec, polarization.xivec, polarization.spvec] {
+                    lines.next();
+                    for value in vector {
+                        content.push_str(&format_f64_13(unsigned_zero(value)));
+                    }
+                    content.push('\n');
+                }
+            } else if line.starts_with(" polarization tensor") {
+                for row in polarization.spherical_tensor() {
+                    lines.next();
+                    for value in row {
+                        content.push_str(&format_f64_13(unsigned_zero(value.re)));
+                        content.push_str(&format_f64_13(unsigned_zero(value.im)));
+                    }
+                    content.push('\n');
+                }
+                if polarization.is_spin_resolved() {
+                    content.push_str(&polarization.exchange_splitting_rows());
+                }
+            } else if let Some(nrixs) = &self.nrixs
+                && line.starts_with("nq,")
+            {
+                lines.next();
+                content.push_str(&format!(
+                    "{:>12}{:>12} {} F  -1.00000000000000     \n",
+                    nrixs.q_vectors.len(),
+                    0,
+                    if nrixs.spherical_average { "T" } else { "F" }
+                ));
+            }
+        }
+
+        let Some(nrixs) = &self.nrixs else {
+            return content;
+        };
+        for q in &nrixs.q_vectors {
+            let cos_theta = (q.qz / q.norm).clamp(-1.0, 1.0);
+            let sin_theta = (1.0 - cos_theta * cos_theta).max(0.0).sqrt();
+            let (cos_phi, sin_phi) = if sin_theta * q.norm > 1.0e-12 {
+                (q.qx / (q.norm * sin_theta), q.qy / (q.norm * sin_theta))
+            } else {
+                (1.0, 0.0)
+            };
+            for value in [
+                q.qx, q.qy, q.qz, q.norm, q.weight, cos_theta, sin_theta, cos_phi, sin_phi,
+            ] {
+                content.push_str(&format_f64_13(value));
+            }
+            content.push('\n');
+        }
+        content
+    }
+
+    fn render_genfmt_inp(&self) -> String {
+        if self.nrixs.is_none() {
+            return GENFMT_INP_TEMPLATE.to_string();
+        }
+        GENFMT_INP_TEMPLATE.replace("\n   -1\n", &format!("\n{:>5}\n", self.ldecmx()))
+    }
+
     fn render_geom_dat(&self) -> String {
         let nph = self.nph();
         let mut content = String::new();
@@ -219,7 +394,7 @@ impl RdinpModel {
         content.push_str("gamach, rgrd, ca1, ecv, totvol, rfms1\n");
         content.push_str(&format!(
             "{}{}{}{}{}{}\n",
-            format_f64_13(1.72919),
+            format_f64_13(self.gamach),
             format_f64_13(0.05),
             format_f64_13(self.ca1),
             format_f64_13(-40.0),
@@ -315,7 +490,7 @@ impl RdinpModel {
             "{}{}{}{}{}{}{}{}\n",
             format_f64_13(0.05),
             format_f64_13(self.rfms),
-            format_f64_13(1.72919),
+            format_f64_13(self.gamach),
             format_f64_13(0.07),
             format_f64_13(self.xkmax),
             format_f64_13(0.0),
@@ -327,7 +502,7 @@ impl RdinpModel {
         content.push_str("ChSh_Type:\n");
         content.push_str("   0\n");
         content.push_str(" the number of decomposition channels ; only used for nrixs\n");
-        content.push_str("   -1\n");
+        content.push_str(&format!("{:>5}\n", self.ldecmx()));
         content.push_str("lopt\n");
         content.push_str(" F\n");
         content
@@ -402,15 +577,21 @@ impl RdinpModel {
             format_f64_13(0.0),
             format_f64_13(0.0)
         ));
+        let qvec = self
+            .nrixs
+            .as_ref()
+            .and_then(|nrixs| nrixs.q_vectors.first())
+            .map(|q| [q.qx, q.qy, q.qz])
+            .unwrap_or([0.0; 3]);
         content.push_str("momentum transfer\n");
         content.push_str(&format!(
             "{}{}{}\n",
-            format_f64_13(0.0),
-            format_f64_13(0.0),
-            format_f64_13(0.0)
+            format_f64_13(qvec[0]),
+            format_f64_13(qvec[1]),
+            format_f64_13(qvec[2])
         ));
         content.push_str(" the number of decomposi\n");
-        content.push_str("   -1\n");
+        content.push_str(&format!("{:>5}\n", self.ldecmx()));
         content
     }
 
@@ -475,7 +656,10 @@ impl RdinpModel {
         } else {
             content.push_str(" FEFF 9.5.1\n");
         }
-        content.push_str(" Core hole lifetime set to    1.72918818490579      eV.\n");
+        content.push_str(&format!(
+            " Core hole lifetime set to {:>20}      eV.\n",
+            format_list_directed_f64(self.gamach)
+        ));
         content.push_str(&format!(" {}\n", self.title));
         content
     }
@@ -517,6 +701,20 @@ fn normalize_label(label: &str) -> String {
     trimmed.chars().take(4).collect()
 }
 
+// Without an EDGE card FEFF assumes the K edge, so fall back to its width.
+fn default_core_hole_width(potentials: &[PotentialEntry], edge_label: &str) -> f64 {
+    let Some(absorber_z) = potentials
+        .iter()
+        .find(|potential| potential.ipot == 0)
+        .and_then(|potential| usize::try_from(potential.atomic_number).ok())
+    else {
+        return 0.0;
+    };
+    core_hole_width(absorber_z, edge_label)
+        .or_else(|| core_hole_width(absorber_z, "K"))
+        .unwrap_or(0.0)
+}
+
 fn render_lmaxph_line(nph: i32) -> String {
     let count = (nph + 1).max(1) as usize;
     let mut line = String::new();
@@ -531,6 +729,66 @@ fn format_f64_13(value: f64) -> String {
     format_fixed_f64(value, 13, 5)
 }
 
+// Mirrors gfortran list-directed REAL(8) output: 15 significant digits.
+fn format_list_directed_f64(value: f64) -> String {
+    let magnitude = if value == 0.0 {
+        0
+    } else {
+        value.abs().log10().floor() as i32
+    };
+    let decimals = (14 - magnitude).max(0) as usize;
+    format!("{value:.decimals$}")
+}
+
+fn unsigned_zero(value: f64) -> f64 {
+    if value == 0.0 { 0.0 } else { value }
+}
+
+/// Modules a deck asks for, in FEFF execution order.
+pub(super) fn workflow_modules_for_deck(deck: &InputDeck) -> Vec<ComputeModule> {
+    WORKFLOW_MODULE_ORDER
+        .iter()
+        .copied()
+        .filter(|module| workflow_selection(deck, *module).is_ok())
+        .collect()
+}
+
+/// Why the deck runs `module`, or why it does not. RDINP, POT, XSPH, PATH and
+/// FF2X always run; the rest follow the cards that enable them.
+pub(crate) fn workflow_selection(
+    deck: &InputDeck,
+    module: ComputeModule,
+) -> Result<String, &'static str> {
+    let enabled_by = |keywords: &[&str]| {
+        keywords
+            .iter()
+            .find(|keyword| has_card(deck, keyword))
+            .map(|keyword| format!("enabled by the {} card", keyword))
+    };
+    match module {
+        ComputeModule::Rdinp
+        | ComputeModule::Pot
+        | ComputeModule::Xsph
+        | ComputeModule::Path
+        | ComputeModule::Debye => Ok("always runs".to_string()),
+        ComputeModule::Ldos => enabled_by(&["LDOS"]).ok_or("no LDOS card"),
+        ComputeModule::Screen => enabled_by(&["SCREEN"]).ok_or("no SCREEN card"),
+        ComputeModule::Crpa => enabled_by(&["CRPA"]).ok_or("no CRPA card"),
+        ComputeModule::Band => enabled_by(&["BAND", "MBAND"]).ok_or("no BAND or MBAND card"),
+        ComputeModule::Fms => enabled_by(&["FMS"]).ok_or("no FMS card"),
+        ComputeModule::SelfEnergy => {
+            enabled_by(&["SFCONV", "MSFCONV"]).ok_or("no SFCONV or MSFCONV card")
+        }
+        ComputeModule::Compton => enabled_by(&["COMPTON"]).ok_or("no COMPTON card"),
+        ComputeModule::Eels => enabled_by(&["ELNES", "EXELFS"]).ok_or("no ELNES or EXELFS card"),
+        ComputeModule::Rixs => enabled_by(&["RIXS"]).ok_or("no RIXS card"),
+        ComputeModule::FullSpectrum => enabled_by(&["FULLSPECTRUM", "MFULLSPECTRUM"])
+            .ok_or("no FULLSPECTRUM or MFULLSPECTRUM card"),
+        // dmdw.inp is always written disabled, so DMDW never has work to do.
+        ComputeModule::Dmdw => Err("RDINP always writes dmdw.inp disabled"),
+    }
+}
+
 pub(super) fn expected_outputs_for_screen_card(has_screen_card: bool) -> Vec<ComputeArtifact> {
     let mut outputs = RDINP_BASE_OUTPUTS_PREFIX
         .iter()
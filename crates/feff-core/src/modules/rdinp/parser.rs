@@ -1,9 +1,14 @@
 use super::RDINP_REQUIRED_INPUTS;
 use crate::domain::{
-    ComputeArtifact, ComputeModule, ComputeRequest, ComputeResult, FeffError, InputCard, InputDeck,
+    ArtifactStore, ComputeArtifact, ComputeModule, ComputeRequest, ComputeResult, FeffError,
+    InputCard, InputDeck,
 };
+use crate::modules::polarization::PolarizationSettings;
+use crate::parser::structure::cluster_cards;
 use crate::support::common::isedge::canonical_edge_label;
-use std::fs;
+use crate::support::inpgen::cif::{CifError, CifStructure};
+use crate::support::inpgen::m_pot_generator::{PotGenRule, gen_pot_from_xyz};
+use std::path::Path;
 
 #[derive(Debug, Clone)]
 pub(super) struct PotentialEntry {
@@ -11,6 +16,7 @@ pub(super) struct PotentialEntry {
     pub(super) atomic_number: i32,
     pub(super) label: String,
     pub(super) explicit_xnatph: Option<f64>,
+    pub(super) spin_moment: Option<f64>,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -21,6 +27,57 @@ pub(super) struct AtomSite {
     pub(super) ipot: i32,
 }
 
+#[derive(Debug, Clone, Copy)]
+pub(super) struct NrixsQVector {
+    pub(super) qx: f64,
+    pub(super) qy: f64,
+    pub(super) qz: f64,
+    pub(super) norm: f64,
+    pub(super) weight: f64,
+}
+
+#[derive(Debug, Clone)]
+pub(super) struct NrixsSettings {
+    pub(super) spherical_average: bool,
+    pub(super) q_vectors: Vec<NrixsQVector>,
+    pub(super) ljmax: i32,
+    pub(super) ldecmx: i32,
+}
+
+/// Beam and detector settings of an `ELNES` or `EXELFS` card, in the units
+/// `eels.inp` expects (eV and radians).
+#[derive(Debug, Clone, Copy)]
+pub(super) struct EelsSettings {
+    pub(super) average: i32,
+    pub(super) cross_terms: i32,
+    pub(super) relativistic: i32,
+    pub(super) beam_energy_ev: f64,
+    pub(super) beam_direction: [f64; 3],
+    pub(super) collection_semiangle_rad: f64,
+    pub(super) convergence_semiangle_rad: f64,
+    pub(super) qmesh: [i32; 2],
+    pub(super) detector: [f64; 2],
+    pub(super) magic_energy_ev: Option<f64>,
+}
+
+impl EelsSettings {
+    /// Polarization tensor components `min step max`: the orientation average
+    /// uses component 10 alone, otherwise all nine (diagonal only without
+    /// cross terms).
+    pub(super) fn polarizations(&self) -> [i32; 3] {
+        if self.average > 0 {
+            [10, 1, 10]
+        } else if self.cross_terms > 0 {
+            [1, 1, 9]
+        } else {
+            [1, 4, 9]
+        }
+    }
+}
+
+pub(super) const NRIXS_DEFAULT_LJMAX: i32 = 3;
+pub(super) const CIF_DEFAULT_CLUSTER_RADIUS: f64 = 6.0;
+
 pub(super) fn validate_request_shape(request: &ComputeRequest) -> ComputeResult<()> {
     if request.module != ComputeModule::Rdinp {
         return Err(FeffError::input_validation(
@@ -55,8 +112,11 @@ pub(super) fn validate_request_shape(request: &ComputeRequest) -> ComputeResult<
     Ok(())
 }
 
-pub(super) fn read_input_source(input_path: &std::path::Path) -> ComputeResult<String> {
-    fs::read_to_string(input_path).map_err(|source| {
+pub(super) fn read_input_source(
+    store: &dyn ArtifactStore,
+    input_path: &std::path::Path,
+) -> ComputeResult<String> {
+    store.read_to_string(input_path).map_err(|source| {
         FeffError::io_system(
             "IO.RDINP_INPUT_READ",
             format!(
@@ -110,11 +170,16 @@ pub(super) fn parse_potentials(deck: &InputDeck) -> ComputeResult<Vec<PotentialE
             Some(token) => Some(parse_f64_token(token, "POTENTIALS xnatph", line)?),
             None => None,
         };
+        let spin_moment = match row.get(6) {
+            Some(token) => Some(parse_f64_token(token, "POTENTIALS spin moment", line)?),
+            None => None,
+        };
         entries.push(PotentialEntry {
             ipot,
             atomic_number,
             label,
             explicit_xnatph,
+            spin_moment,
         });
     }
     entries.sort_by_key(|entry| entry.ipot);
@@ -162,6 +227,309 @@ pub(super) fn parse_atoms(deck: &InputDeck) -> ComputeResult<Vec<AtomSite>> {
     Ok(atoms)
 }
 
+/// Reads the `NRIXS` card. `NRIXS -n q1 .. qn` requests a spherical average over
+/// `n` momentum-transfer magnitudes; `NRIXS n qx qy qz` lists `n` explicit vectors
+/// with the remaining vectors on continuation rows. Values are in inverse Angstrom.
+pub(super) fn parse_nrixs(deck: &InputDeck) -> ComputeResult<Option<NrixsSettings>> {
+    let Some(card) = first_card(deck, "NRIXS") else {
+        return Ok(None);
+    };
+    let Some(count_token) = card.values.first() else {
+        return Err(nrixs_error(format!(
+            "NRIXS card at line {} requires a q count",
+            card.source_line
+        )));
+    };
+    let count = parse_i32_token(count_token, "NRIXS q count", card.source_line)?;
+    if count == 0 {
+        return Err(nrixs_error(format!(
+            "NRIXS card at line {} must request at least one q value",
+            card.source_line
+        )));
+    }
+
+    let mut values = Vec::new();
+    for token in &card.values[1..] {
+        values.push(parse_f64_token(token, "NRIXS q value", card.source_line)?);
+    }
+    for continuation in &card.continuations {
+        for token in &continuation.values {
+            values.push(parse_f64_token(
+                token,
+                "NRIXS q value",
+                continuation.source_line,
+            )?);
+        }
+    }
+
+    let spherical_average = count < 0;
+    let q_count = count.unsigned_abs() as usize;
+    let components = if spherical_average { 1 } else { 3 };
+    if values.len() != q_count * components {
+        return Err(nrixs_error(format!(
+            "NRIXS card at line {} expects {} {} but found {} values",
+            card.source_line,
+            q_count,
+            if spherical_average {
+                "q magnitudes"
+            } else {
+                "q vectors (qx qy qz)"
+            },
+            values.len()
+        )));
+    }
+
+    let weight = 1.0 / q_count as f64;
+    let mut q_vectors = Vec::with_capacity(q_count);
+    for chunk in values.chunks(components) {
+        let (qx, qy, qz) = if spherical_average {
+            (0.0, 0.0, chunk[0].abs())
+        } else {
+            (chunk[0], chunk[1], chunk[2])
+        };
+        let norm = (qx * qx + qy * qy + qz * qz).sqrt();
+        if !norm.is_finite() || norm <= 0.0 {
+            return Err(nrixs_error(format!(
+                "NRIXS card at line {} contains a zero-length momentum transfer",
+                card.source_line
+            )));
+        }
+        q_vectors.push(NrixsQVector {
+            qx,
+            qy,
+            qz,
+            norm,
+            weight,
+        });
+    }
+
+    let ljmax = card_i32(deck, "LJMAX")?.unwrap_or(NRIXS_DEFAULT_LJMAX);
+    if ljmax < 0 {
+        return Err(nrixs_error(format!(
+            "LJMAX must be non-negative, got {}",
+            ljmax
+        )));
+    }
+    let ldecmx = card_i32(deck, "LDECMX")?.unwrap_or(-1);
+
+    Ok(Some(NrixsSettings {
+        spherical_average,
+        q_vectors,
+        ljmax,
+        ldecmx,
+    }))
+}
+
+fn card_i32(deck: &InputDeck, keyword: &str) -> ComputeResult<Option<i32>> {
+    let Some(card) = first_card(deck, keyword) else {
+        return Ok(None);
+    };
+    let token = card
+        .values
+        .first()
+        .or_else(|| {
+            card.continuations
+                .first()
+                .and_then(|continuation| continuation.values.first())
+        })
+        .ok_or_else(|| {
+            FeffError::input_validation(
+                "INPUT.RDINP_CARD_VALUE",
+                format!(
+                    "card '{}' at line {} is missing value index 0",
+                    keyword, card.source_line
+                ),
+            )
+        })?;
+    parse_i32_token(token, keyword, card.source_line).map(Some)
+}
+
+fn nrixs_error(message: impl Into<String>) -> FeffError {
+    FeffError::input_validation("INPUT.RDINP_NRIXS", message.into())
+}
+
+/// Reads the `ELNES [xkmax xkstep vixan]` or `EXELFS [xkmax]` card and its
+/// beam rows `ebeam [aver [cross [relat]]]`, `kx ky kz`, `beta alpha`, `nr na`
+/// and `dx dy`, plus `MAGIC emagic`. The beam energy is given in keV and the
+/// angles in mrad; omitted rows keep FEFF's defaults (300 keV along `z`,
+/// 2.4 mrad collection, a 5 x 3 q mesh and an on-axis detector).
+pub(super) fn parse_eels(deck: &InputDeck) -> ComputeResult<Option<EelsSettings>> {
+    let Some(card) = first_card(deck, "ELNES").or_else(|| first_card(deck, "EXELFS")) else {
+        return Ok(None);
+    };
+    let rows: Vec<(&[String], usize)> = card
+        .continuations
+        .iter()
+        .filter(|continuation| !continuation.values.is_empty())
+        .map(|continuation| (continuation.values.as_slice(), continuation.source_line))
+        .collect();
+    let token = |row: usize, index: usize| {
+        rows.get(row)
+            .and_then(|(values, line)| Some((values.get(index)?.as_str(), *line)))
+    };
+    let value = |row: usize, index: usize, field: &str, default: f64| -> ComputeResult<f64> {
+        token(row, index)
+            .map(|(token, line)| parse_f64_token(token, field, line))
+            .transpose()
+            .map(|value| value.unwrap_or(default))
+    };
+    let integer = |row: usize, index: usize, field: &str, default: i32| -> ComputeResult<i32> {
+        token(row, index)
+            .map(|(token, line)| parse_i32_token(token, field, line))
+            .transpose()
+            .map(|value| value.unwrap_or(default))
+    };
+
+    let beam_energy_kev = value(0, 0, "ELNES beam energy", 300.0)?;
+    if beam_energy_kev <= 0.0 {
+        return Err(FeffError::input_validation(
+            "INPUT.RDINP_ELNES",
+            format!(
+                "{} card at line {} requires a positive beam energy, got {} keV",
+                card.keyword, card.source_line, beam_energy_kev
+            ),
+        ));
+    }
+    let magic_energy_ev = match first_card(deck, "MAGIC") {
+        Some(magic) => Some(
+            card_tokens(magic)
+                .next()
+                .map(|(token, line)| parse_f64_token(token, "MAGIC energy", line))
+                .transpose()?
+                .unwrap_or(0.0),
+        ),
+        None => None,
+    };
+
+    Ok(Some(EelsSettings {
+        average: integer(0, 1, "ELNES average flag", 0)?,
+        cross_terms: integer(0, 2, "ELNES cross-term flag", 1)?,
+        relativistic: integer(0, 3, "ELNES relativistic flag", 1)?,
+        beam_energy_ev: beam_energy_kev * 1000.0,
+        beam_direction: [
+            value(1, 0, "ELNES beam direction", 0.0)?,
+            value(1, 1, "ELNES beam direction", 0.0)?,
+            value(1, 2, "ELNES beam direction", 1.0)?,
+        ],
+        collection_semiangle_rad: value(2, 0, "ELNES collection angle", 2.4)? * 1.0e-3,
+        convergence_semiangle_rad: value(2, 1, "ELNES convergence angle", 0.0)? * 1.0e-3,
+        qmesh: [
+            integer(3, 0, "ELNES radial q mesh", 5)?,
+            integer(3, 1, "ELNES angular q mesh", 3)?,
+        ],
+        detector: [
+            value(4, 0, "ELNES detector angle", 0.0)? * 1.0e-3,
+            value(4, 1, "ELNES detector angle", 0.0)? * 1.0e-3,
+        ],
+        magic_energy_ev,
+    }))
+}
+
+/// Reads `POLARIZATION x y z`, `ELLIPTICITY elpty kx ky kz` and
+/// `SPIN ispin [sx sy sz]`. Without any of these cards the isotropic average is
+/// returned; the spin axis defaults to `z`.
+pub(super) fn parse_polarization(deck: &InputDeck) -> ComputeResult<PolarizationSettings> {
+    let mut settings = PolarizationSettings::default();
+
+    if let Some(card) = first_card(deck, "POLARIZATION") {
+        settings.evec = card_vector(card, 0, "POLARIZATION vector")?;
+        if settings.evec.iter().all(|value| value.abs() < 1.0e-10) {
+            return Err(polarization_error(format!(
+                "POLARIZATION card at line {} has a zero-length vector",
+                card.source_line
+            )));
+        }
+        settings.ipol = 1;
+    }
+
+    if let Some(card) = first_card(deck, "ELLIPTICITY") {
+        if !settings.is_polarized() {
+            return Err(polarization_error(format!(
+                "ELLIPTICITY card at line {} requires a POLARIZATION card",
+                card.source_line
+            )));
+        }
+        let ellipticity = card_tokens(card)
+            .next()
+            .map(|(token, line)| parse_f64_token(token, "ELLIPTICITY value", line))
+            .transpose()?
+            .ok_or_else(|| {
+                polarization_error(format!(
+                    "ELLIPTICITY card at line {} requires an ellipticity and a beam direction",
+                    card.source_line
+                ))
+            })?;
+        settings.ellipticity = ellipticity;
+        settings.xivec = card_vector(card, 1, "ELLIPTICITY beam direction")?;
+        let [ex, ey, ez] = settings.evec;
+        let [kx, ky, kz] = settings.xivec;
+        let transverse =
+            (ky * ez - kz * ey).powi(2) + (kz * ex - kx * ez).powi(2) + (kx * ey - ky * ex).powi(2);
+        if settings.ellipticity != 0.0 && transverse < 1.0e-20 {
+            return Err(polarization_error(format!(
+                "ELLIPTICITY card at line {} needs a beam direction that is not parallel to the polarization",
+                card.source_line
+            )));
+        }
+    }
+
+    if let Some(card) = first_card(deck, "SPIN") {
+        let mut tokens = card_tokens(card);
+        let (token, line) = tokens.next().ok_or_else(|| {
+            polarization_error(format!(
+                "SPIN card at line {} requires a spin index",
+                card.source_line
+            ))
+        })?;
+        let ispin = parse_i32_token(token, "SPIN index", line)?;
+        if !(-2..=2).contains(&ispin) {
+            return Err(polarization_error(format!(
+                "SPIN index at line {} must be between -2 and 2, got {}",
+                card.source_line, ispin
+            )));
+        }
+        settings.ispin = ispin;
+        settings.spvec = if tokens.next().is_some() {
+            card_vector(card, 1, "SPIN axis")?
+        } else {
+            [0.0, 0.0, 1.0]
+        };
+    }
+
+    Ok(settings)
+}
+
+fn card_tokens(card: &InputCard) -> impl Iterator<Item = (&str, usize)> {
+    card.values
+        .iter()
+        .map(|token| (token.as_str(), card.source_line))
+        .chain(card.continuations.iter().flat_map(|continuation| {
+            continuation
+                .values
+                .iter()
+                .map(|token| (token.as_str(), continuation.source_line))
+        }))
+}
+
+fn card_vector(card: &InputCard, start: usize, field: &str) -> ComputeResult<[f64; 3]> {
+    let values = card_tokens(card)
+        .skip(start)
+        .take(3)
+        .map(|(token, line)| parse_f64_token(token, field, line))
+        .collect::<ComputeResult<Vec<_>>>()?;
+    if values.len() != 3 {
+        return Err(polarization_error(format!(
+            "{} card at line {} requires three components for the {}",
+            card.keyword, card.source_line, field
+        )));
+    }
+    Ok([values[0], values[1], values[2]])
+}
+
+fn polarization_error(message: impl Into<String>) -> FeffError {
+    FeffError::input_validation("INPUT.RDINP_POLARIZATION", message.into())
+}
+
 pub(super) fn sort_atoms_by_distance(mut atoms: Vec<AtomSite>) -> Vec<AtomSite> {
     if atoms.is_empty() {
         return atoms;
@@ -290,6 +658,86 @@ pub(super) fn deck_title(deck: &InputDeck) -> String {
     "FEFF Input".to_string()
 }
 
+/// Replaces a `CIF` card by generated `POTENTIALS` and `ATOMS` cards.
+///
+/// The crystal is read from `store` relative to `input_dir` and expanded around the
+/// asymmetric-unit site named by `TARGET` (default 1) out to the largest of the
+/// `RPATH`, `SCF` and `FMS` radii. Decks that already list `ATOMS` are returned
+/// unchanged.
+pub(super) fn expand_cif_structure(
+    store: &dyn ArtifactStore,
+    mut deck: InputDeck,
+    input_dir: &Path,
+) -> ComputeResult<InputDeck> {
+    let Some(cif_card) = first_card(&deck, "CIF") else {
+        return Ok(deck);
+    };
+    if has_card(&deck, "ATOMS") {
+        return Ok(deck);
+    }
+    let source_line = cif_card.source_line;
+    let cif_path = cif_card.values.first().ok_or_else(|| {
+        FeffError::input_validation(
+            "INPUT.RDINP_CIF",
+            format!("CIF card at line {} is missing a file name", source_line),
+        )
+    })?;
+    let cif_path = input_dir.join(cif_path);
+    let cif_source = store.read_to_string(&cif_path).map_err(|source| {
+        FeffError::io_system(
+            "IO.RDINP_CIF_READ",
+            format!("failed to read CIF '{}': {}", cif_path.display(), source),
+        )
+    })?;
+    let cif_error = |source: CifError| {
+        FeffError::input_validation(
+            "INPUT.RDINP_CIF",
+            format!("CIF '{}': {}", cif_path.display(), source),
+        )
+    };
+
+    let target = match card_value(&deck, "TARGET", 0)? {
+        Some(value) if value >= 1.0 && value.fract() == 0.0 => value as usize,
+        Some(value) => {
+            return Err(FeffError::input_validation(
+                "INPUT.RDINP_CIF",
+                format!("TARGET site '{}' must be a positive integer", value),
+            ));
+        }
+        None => 1,
+    };
+    let mut radius: f64 = 0.0;
+    for keyword in ["RPATH", "SCF", "FMS"] {
+        if let Some(value) = card_value(&deck, keyword, 0)? {
+            radius = radius.max(value);
+        }
+    }
+    if radius <= 0.0 {
+        radius = CIF_DEFAULT_CLUSTER_RADIUS;
+    }
+
+    let structure = CifStructure::parse(&cif_source).map_err(cif_error)?;
+    let mut cluster = structure.build_cluster(target, radius).map_err(cif_error)?;
+    let pot_list = gen_pot_from_xyz(&PotGenRule::default(), 1, &mut cluster).map_err(|source| {
+        FeffError::input_validation(
+            "INPUT.RDINP_CIF",
+            format!("CIF '{}': {}", cif_path.display(), source),
+        )
+    })?;
+
+    let [potentials, atoms] = cluster_cards(&cluster, &pot_list, source_line);
+
+    deck.cards
+        .retain(|card| !matches!(card.keyword.as_str(), "POTENTIALS" | "POTENTIAL"));
+    let insert_at = deck
+        .cards
+        .iter()
+        .position(|card| card.keyword == "END")
+        .unwrap_or(deck.cards.len());
+    deck.cards.splice(insert_at..insert_at, [potentials, atoms]);
+    Ok(deck)
+}
+
 pub(super) fn deck_edge_label(deck: &InputDeck) -> String {
     first_card(deck, "EDGE")
         .and_then(|card| card.values.first())
@@ -1,9 +1,8 @@
 mod model;
 mod parser;
 
-use super::ModuleExecutor;
-use crate::domain::{ComputeArtifact, ComputeRequest, ComputeResult, FeffError};
-use std::fs;
+use super::{ModuleContract, ModuleExecutor};
+use crate::domain::{ComputeArtifact, ComputeModule, ComputeRequest, ComputeResult, FeffError};
 
 use model::RixsModel;
 use parser::{
@@ -35,6 +34,18 @@ pub struct RixsContract {
     pub expected_outputs: Vec<ComputeArtifact>,
 }
 
+impl ModuleContract for RixsContract {
+    const MODULE: ComputeModule = ComputeModule::Rixs;
+
+    fn required_input_names() -> Vec<&'static str> {
+        RIXS_REQUIRED_INPUTS.to_vec()
+    }
+
+    fn output_names() -> Vec<&'static str> {
+        RIXS_REQUIRED_OUTPUTS.to_vec()
+    }
+}
+
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub struct RixsModule;
 
@@ -42,8 +53,8 @@ impl RixsModule {
     pub fn contract_for_request(&self, request: &ComputeRequest) -> ComputeResult<RixsContract> {
         validate_request_shape(request)?;
         Ok(RixsContract {
-            required_inputs: artifact_list(&RIXS_REQUIRED_INPUTS),
-            expected_outputs: artifact_list(&RIXS_REQUIRED_OUTPUTS),
+            required_inputs: artifact_list(&RixsContract::required_input_names()),
+            expected_outputs: artifact_list(&RixsContract::output_names()),
         })
     }
 }
@@ -53,24 +64,33 @@ impl ModuleExecutor for RixsModule {
         validate_request_shape(request)?;
         let input_dir = input_parent_dir(request)?;
 
-        let rixs_source = read_input_source(&request.input_path, RIXS_REQUIRED_INPUTS[0])?;
+        let rixs_source = read_input_source(
+            &*request.store,
+            &request.input_path,
+            RIXS_REQUIRED_INPUTS[0],
+        )?;
         let phase_1_bytes = read_input_bytes(
+            &*request.store,
             &input_dir.join(RIXS_REQUIRED_INPUTS[1]),
             RIXS_REQUIRED_INPUTS[1],
         )?;
         let phase_2_bytes = read_input_bytes(
+            &*request.store,
             &input_dir.join(RIXS_REQUIRED_INPUTS[2]),
             RIXS_REQUIRED_INPUTS[2],
         )?;
         let wscrn_1_source = read_input_source(
+            &*request.store,
             &input_dir.join(RIXS_REQUIRED_INPUTS[3]),
             RIXS_REQUIRED_INPUTS[3],
         )?;
         let wscrn_2_source = read_input_source(
+            &*request.store,
             &input_dir.join(RIXS_REQUIRED_INPUTS[4]),
             RIXS_REQUIRED_INPUTS[4],
         )?;
         let xsect_2_source = read_input_source(
+            &*request.store,
             &input_dir.join(RIXS_REQUIRED_INPUTS[5]),
             RIXS_REQUIRED_INPUTS[5],
         )?;
@@ -86,21 +106,24 @@ impl ModuleExecutor for RixsModule {
         )?;
         let outputs = artifact_list(&RIXS_REQUIRED_OUTPUTS);
 
-        fs::create_dir_all(&request.output_dir).map_err(|source| {
-            FeffError::io_system(
-                "IO.RIXS_OUTPUT_DIRECTORY",
-                format!(
-                    "failed to create RIXS output directory '{}': {}",
-                    request.output_dir.display(),
-                    source
-                ),
-            )
-        })?;
+        request
+            .store
+            .create_dir_all(&request.output_dir)
+            .map_err(|source| {
+                FeffError::io_system(
+                    "IO.RIXS_OUTPUT_DIRECTORY",
+                    format!(
+                        "failed to create RIXS output directory '{}': {}",
+                        request.output_dir.display(),
+                        source
+                    ),
+                )
+            })?;
 
         for artifact in &outputs {
             let output_path = request.output_dir.join(&artifact.relative_path);
             if let Some(parent) = output_path.parent() {
-                fs::create_dir_all(parent).map_err(|source| {
+                request.store.create_dir_all(parent).map_err(|source| {
                     FeffError::io_system(
                         "IO.RIXS_OUTPUT_DIRECTORY",
                         format!(
@@ -113,7 +136,7 @@ impl ModuleExecutor for RixsModule {
             }
 
             let artifact_name = artifact.relative_path.to_string_lossy().replace('\\', "/");
-            model.write_artifact(&artifact_name, &output_path)?;
+            model.write_artifact(&*request.store, &artifact_name, &output_path)?;
         }
 
         Ok(outputs)
@@ -3,8 +3,8 @@ use super::parser::{
     format_scientific_f64, normalized_index, parse_binary_source, parse_rixs_source,
     parse_table_source,
 };
-use crate::domain::{ComputeResult, FeffError};
-use crate::modules::serialization::{format_fixed_f64, write_text_artifact};
+use crate::domain::{ArtifactStore, ComputeResult, FeffError};
+use crate::modules::serialization::{format_fixed_f64, write_text_artifact_to};
 use std::f64::consts::PI;
 use std::path::Path;
 
@@ -77,6 +77,7 @@ impl RixsModel {
 
     pub(super) fn write_artifact(
         &self,
+        store: &dyn ArtifactStore,
         artifact_name: &str,
         output_path: &Path,
     ) -> ComputeResult<()> {
@@ -97,7 +98,7 @@ impl RixsModel {
             }
         };
 
-        write_text_artifact(output_path, &contents).map_err(|source| {
+        write_text_artifact_to(store, output_path, &contents).map_err(|source| {
             FeffError::io_system(
                 "IO.RIXS_OUTPUT_WRITE",
                 format!(
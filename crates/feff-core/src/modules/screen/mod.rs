@@ -1,9 +1,8 @@
 mod model;
 mod parser;
 
-use super::ModuleExecutor;
-use crate::domain::{ComputeArtifact, ComputeRequest, ComputeResult, FeffError};
-use std::fs;
+use super::{ModuleContract, ModuleExecutor};
+use crate::domain::{ComputeArtifact, ComputeModule, ComputeRequest, ComputeResult, FeffError};
 
 use model::ScreenModel;
 use parser::{
@@ -22,6 +21,22 @@ pub struct ScreenContract {
     pub expected_outputs: Vec<ComputeArtifact>,
 }
 
+impl ModuleContract for ScreenContract {
+    const MODULE: ComputeModule = ComputeModule::Screen;
+
+    fn required_input_names() -> Vec<&'static str> {
+        SCREEN_REQUIRED_INPUTS.to_vec()
+    }
+
+    fn optional_input_names() -> Vec<&'static str> {
+        SCREEN_OPTIONAL_INPUTS.to_vec()
+    }
+
+    fn output_names() -> Vec<&'static str> {
+        SCREEN_REQUIRED_OUTPUTS.to_vec()
+    }
+}
+
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub struct ScreenModule;
 
@@ -29,9 +44,9 @@ impl ScreenModule {
     pub fn contract_for_request(&self, request: &ComputeRequest) -> ComputeResult<ScreenContract> {
         validate_request_shape(request)?;
         Ok(ScreenContract {
-            required_inputs: artifact_list(&SCREEN_REQUIRED_INPUTS),
-            optional_inputs: artifact_list(&SCREEN_OPTIONAL_INPUTS),
-            expected_outputs: artifact_list(&SCREEN_REQUIRED_OUTPUTS),
+            required_inputs: artifact_list(&ScreenContract::required_input_names()),
+            optional_inputs: artifact_list(&ScreenContract::optional_input_names()),
+            expected_outputs: artifact_list(&ScreenContract::output_names()),
         })
     }
 }
@@ -41,16 +56,23 @@ impl ModuleExecutor for ScreenModule {
         validate_request_shape(request)?;
         let input_dir = input_parent_dir(request)?;
 
-        let pot_source = read_input_source(&request.input_path, SCREEN_REQUIRED_INPUTS[0])?;
+        let pot_source = read_input_source(
+            &*request.store,
+            &request.input_path,
+            SCREEN_REQUIRED_INPUTS[0],
+        )?;
         let geom_source = read_input_source(
+            &*request.store,
             &input_dir.join(SCREEN_REQUIRED_INPUTS[1]),
             SCREEN_REQUIRED_INPUTS[1],
         )?;
         let ldos_source = read_input_source(
+            &*request.store,
             &input_dir.join(SCREEN_REQUIRED_INPUTS[2]),
             SCREEN_REQUIRED_INPUTS[2],
         )?;
         let screen_source = maybe_read_optional_input_source(
+            &*request.store,
             input_dir.join(SCREEN_OPTIONAL_INPUTS[0]),
             SCREEN_OPTIONAL_INPUTS[0],
         )?;
@@ -64,21 +86,24 @@ impl ModuleExecutor for ScreenModule {
         )?;
         let outputs = artifact_list(&SCREEN_REQUIRED_OUTPUTS);
 
-        fs::create_dir_all(&request.output_dir).map_err(|source| {
-            FeffError::io_system(
-                "IO.SCREEN_OUTPUT_DIRECTORY",
-                format!(
-                    "failed to create SCREEN output directory '{}': {}",
-                    request.output_dir.display(),
-                    source
-                ),
-            )
-        })?;
+        request
+            .store
+            .create_dir_all(&request.output_dir)
+            .map_err(|source| {
+                FeffError::io_system(
+                    "IO.SCREEN_OUTPUT_DIRECTORY",
+                    format!(
+                        "failed to create SCREEN output directory '{}': {}",
+                        request.output_dir.display(),
+                        source
+                    ),
+                )
+            })?;
 
         for artifact in &outputs {
             let output_path = request.output_dir.join(&artifact.relative_path);
             if let Some(parent) = output_path.parent() {
-                fs::create_dir_all(parent).map_err(|source| {
+                request.store.create_dir_all(parent).map_err(|source| {
                     FeffError::io_system(
                         "IO.SCREEN_OUTPUT_DIRECTORY",
                         format!(
@@ -91,7 +116,7 @@ impl ModuleExecutor for ScreenModule {
             }
 
             let artifact_name = artifact.relative_path.to_string_lossy().replace('\\', "/");
-            model.write_artifact(&artifact_name, &output_path)?;
+            model.write_artifact(&*request.store, &artifact_name, &output_path)?;
         }
 
         Ok(outputs)
@@ -2,9 +2,9 @@ use super::parser::{
     GeomScreenInput, LdosScreenInput, PotScreenInput, ScreenOverrideInput, format_scientific_f64,
     parse_geom_source, parse_ldos_source, parse_pot_source, parse_screen_override_source,
 };
-use crate::domain::{ComputeResult, FeffError};
+use crate::domain::{ArtifactStore, ComputeResult, FeffError};
 use crate::modules::helpers::mkgtr_workflow_coupling;
-use crate::modules::serialization::{format_fixed_f64, write_text_artifact};
+use crate::modules::serialization::{format_fixed_f64, write_text_artifact_to};
 use std::path::Path;
 
 #[derive(Debug, Clone)]
@@ -60,12 +60,13 @@ impl ScreenModel {
 
     pub(super) fn write_artifact(
         &self,
+        store: &dyn ArtifactStore,
         artifact_name: &str,
         output_path: &Path,
     ) -> ComputeResult<()> {
         match artifact_name {
-            "wscrn.dat" => {
-                write_text_artifact(output_path, &self.render_wscrn()).map_err(|source| {
+            "wscrn.dat" => write_text_artifact_to(store, output_path, &self.render_wscrn())
+                .map_err(|source| {
                     FeffError::io_system(
                         "IO.SCREEN_OUTPUT_WRITE",
                         format!(
@@ -74,10 +75,9 @@ impl ScreenModel {
                             source
                         ),
                     )
-                })
-            }
-            "logscreen.dat" => {
-                write_text_artifact(output_path, &self.render_log()).map_err(|source| {
+                }),
+            "logscreen.dat" => write_text_artifact_to(store, output_path, &self.render_log())
+                .map_err(|source| {
                     FeffError::io_system(
                         "IO.SCREEN_OUTPUT_WRITE",
                         format!(
@@ -86,8 +86,7 @@ impl ScreenModel {
                             source
                         ),
                     )
-                })
-            }
+                }),
             other => Err(FeffError::internal(
                 "SYS.SCREEN_OUTPUT_CONTRACT",
                 format!("unsupported SCREEN output artifact '{}'", other),
@@ -1,6 +1,7 @@
 use super::SCREEN_REQUIRED_INPUTS;
-use crate::domain::{ComputeArtifact, ComputeModule, ComputeRequest, ComputeResult, FeffError};
-use std::fs;
+use crate::domain::{
+    ArtifactStore, ComputeArtifact, ComputeModule, ComputeRequest, ComputeResult, FeffError,
+};
 use std::path::{Path, PathBuf};
 
 #[derive(Debug, Clone)]
@@ -115,8 +116,12 @@ pub(super) fn input_parent_dir(request: &ComputeRequest) -> ComputeResult<&Path>
     })
 }
 
-pub(super) fn read_input_source(path: &Path, artifact_name: &str) -> ComputeResult<String> {
-    fs::read_to_string(path).map_err(|source| {
+pub(super) fn read_input_source(
+    store: &dyn ArtifactStore,
+    path: &Path,
+    artifact_name: &str,
+) -> ComputeResult<String> {
+    store.read_to_string(path).map_err(|source| {
         FeffError::io_system(
             "IO.SCREEN_INPUT_READ",
             format!(
@@ -130,11 +135,12 @@ pub(super) fn read_input_source(path: &Path, artifact_name: &str) -> ComputeResu
 }
 
 pub(super) fn maybe_read_optional_input_source(
+    store: &dyn ArtifactStore,
     path: PathBuf,
     artifact_name: &str,
 ) -> ComputeResult<Option<String>> {
-    if path.is_file() {
-        return read_input_source(&path, artifact_name).map(Some);
+    if store.is_file(&path) {
+        return read_input_source(store, &path, artifact_name).map(Some);
     }
 
     Ok(None)
@@ -548,6 +554,6 @@ fn parse_numeric_token(token: &str) -> Option<f64> {
 fn screen_parse_error(fixture_id: &str, message: impl Into<String>) -> FeffError {
     FeffError::computation(
         "RUN.SCREEN_INPUT_PARSE",
-        format!("fixture '{}': {}", fixture_id, message.into()),
+        format!("run '{}': {}", fixture_id, message.into()),
     )
 }
@@ -1,9 +1,8 @@
 mod model;
 mod parser;
 
-use super::ModuleExecutor;
-use crate::domain::{ComputeArtifact, ComputeRequest, ComputeResult, FeffError};
-use std::fs;
+use super::{ModuleContract, ModuleExecutor};
+use crate::domain::{ComputeArtifact, ComputeModule, ComputeRequest, ComputeResult, FeffError};
 
 use model::SelfModel;
 use parser::{
@@ -33,6 +32,26 @@ pub struct SelfEnergyContract {
     pub expected_outputs: Vec<ComputeArtifact>,
 }
 
+impl ModuleContract for SelfEnergyContract {
+    const MODULE: ComputeModule = ComputeModule::SelfEnergy;
+
+    fn required_input_names() -> Vec<&'static str> {
+        vec![SELF_PRIMARY_INPUT]
+    }
+
+    fn any_of_input_names() -> Vec<&'static str> {
+        SELF_SPECTRUM_INPUT_CANDIDATES.to_vec()
+    }
+
+    fn optional_input_names() -> Vec<&'static str> {
+        SELF_OPTIONAL_INPUTS.to_vec()
+    }
+
+    fn output_names() -> Vec<&'static str> {
+        SELF_REQUIRED_OUTPUTS.to_vec()
+    }
+}
+
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub struct SelfEnergyModule;
 
@@ -44,9 +63,11 @@ impl SelfEnergyModule {
         validate_request_shape(request)?;
         let input_dir = input_parent_dir(request)?;
 
-        let sfconv_source = read_input_source(&request.input_path, SELF_PRIMARY_INPUT)?;
-        let spectrum_sources = load_staged_spectrum_sources(input_dir)?;
+        let sfconv_source =
+            read_input_source(&*request.store, &request.input_path, SELF_PRIMARY_INPUT)?;
+        let spectrum_sources = load_staged_spectrum_sources(&*request.store, input_dir)?;
         let exc_source = maybe_read_optional_input_source(
+            &*request.store,
             input_dir.join(SELF_OPTIONAL_INPUTS[0]),
             SELF_OPTIONAL_INPUTS[0],
         )?;
@@ -67,7 +88,7 @@ impl SelfEnergyModule {
 
         Ok(SelfEnergyContract {
             required_inputs,
-            optional_inputs: artifact_list(&SELF_OPTIONAL_INPUTS),
+            optional_inputs: artifact_list(&SelfEnergyContract::optional_input_names()),
             expected_outputs: model.expected_outputs(),
         })
     }
@@ -78,9 +99,11 @@ impl ModuleExecutor for SelfEnergyModule {
         validate_request_shape(request)?;
         let input_dir = input_parent_dir(request)?;
 
-        let sfconv_source = read_input_source(&request.input_path, SELF_PRIMARY_INPUT)?;
-        let spectrum_sources = load_staged_spectrum_sources(input_dir)?;
+        let sfconv_source =
+            read_input_source(&*request.store, &request.input_path, SELF_PRIMARY_INPUT)?;
+        let spectrum_sources = load_staged_spectrum_sources(&*request.store, input_dir)?;
         let exc_source = maybe_read_optional_input_source(
+            &*request.store,
             input_dir.join(SELF_OPTIONAL_INPUTS[0]),
             SELF_OPTIONAL_INPUTS[0],
         )?;
@@ -93,21 +116,24 @@ impl ModuleExecutor for SelfEnergyModule {
         let outputs = model.expected_outputs();
         let state = model.compute_state()?;
 
-        fs::create_dir_all(&request.output_dir).map_err(|source| {
-            FeffError::io_system(
-                "IO.SELF_OUTPUT_DIRECTORY",
-                format!(
-                    "failed to create SELF output directory '{}': {}",
-                    request.output_dir.display(),
-                    source
-                ),
-            )
-        })?;
+        request
+            .store
+            .create_dir_all(&request.output_dir)
+            .map_err(|source| {
+                FeffError::io_system(
+                    "IO.SELF_OUTPUT_DIRECTORY",
+                    format!(
+                        "failed to create SELF output directory '{}': {}",
+                        request.output_dir.display(),
+                        source
+                    ),
+                )
+            })?;
 
         for artifact in &outputs {
             let output_path = request.output_dir.join(&artifact.relative_path);
             if let Some(parent) = output_path.parent() {
-                fs::create_dir_all(parent).map_err(|source| {
+                request.store.create_dir_all(parent).map_err(|source| {
                     FeffError::io_system(
                         "IO.SELF_OUTPUT_DIRECTORY",
                         format!(
@@ -120,7 +146,7 @@ impl ModuleExecutor for SelfEnergyModule {
             }
 
             let artifact_name = artifact.relative_path.to_string_lossy().replace('\\', "/");
-            model.write_artifact(&artifact_name, &output_path, &state)?;
+            model.write_artifact(&*request.store, &artifact_name, &output_path, &state)?;
         }
 
         Ok(outputs)
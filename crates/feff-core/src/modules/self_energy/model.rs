@@ -4,9 +4,9 @@ use super::parser::{
     sample_spectrum_row, upsert_artifact,
 };
 use super::{FNV_OFFSET_BASIS, FNV_PRIME, SELF_REQUIRED_OUTPUTS};
-use crate::domain::{ComputeArtifact, ComputeResult, FeffError};
+use crate::domain::{ArtifactStore, ComputeArtifact, ComputeResult, FeffError};
 use crate::modules::helpers::{mkgtr_workflow_coupling, opconsat_workflow_spectrum};
-use crate::modules::serialization::{format_fixed_f64, write_text_artifact};
+use crate::modules::serialization::{format_fixed_f64, write_text_artifact_to};
 use crate::numerics::{SfconvConvolutionInput, SfconvError, convolve_sfconv_point};
 use std::path::Path;
 
@@ -70,7 +70,7 @@ impl SelfModel {
             return Err(FeffError::input_validation(
                 "INPUT.SELF_SPECTRUM_INPUT",
                 format!(
-                    "SELF module requires at least one staged spectrum input for fixture '{}'",
+                    "SELF module requires at least one staged spectrum input for run '{}'",
                     fixture_id
                 ),
             ));
@@ -127,7 +127,7 @@ impl SelfModel {
             return Err(FeffError::computation(
                 "RUN.SELF_INPUT_PARSE",
                 format!(
-                    "fixture '{}': staged spectrum '{}' must contain at least two distinct finite energy points",
+                    "run '{}': staged spectrum '{}' must contain at least two distinct finite energy points",
                     self.fixture_id, spectral_source.artifact
                 ),
             ));
@@ -196,6 +196,7 @@ impl SelfModel {
 
     pub(super) fn write_artifact(
         &self,
+        store: &dyn ArtifactStore,
         artifact_name: &str,
         output_path: &Path,
         state: &SelfKernelState,
@@ -220,7 +221,7 @@ impl SelfModel {
             }
         };
 
-        write_text_artifact(output_path, &contents).map_err(|source| {
+        write_text_artifact_to(store, output_path, &contents).map_err(|source| {
             FeffError::io_system(
                 "IO.SELF_OUTPUT_WRITE",
                 format!(
@@ -785,7 +786,7 @@ impl SelfModel {
     ) -> FeffError {
         FeffError::computation(
             placeholder,
-            format!("fixture '{}': {}: {}", self.fixture_id, message, source),
+            format!("run '{}': {}: {}", self.fixture_id, message, source),
         )
     }
 
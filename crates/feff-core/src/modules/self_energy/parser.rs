@@ -1,7 +1,8 @@
 use super::{FNV_OFFSET_BASIS, FNV_PRIME, SELF_PRIMARY_INPUT, SELF_SPECTRUM_INPUT_CANDIDATES};
-use crate::domain::{ComputeArtifact, ComputeModule, ComputeRequest, ComputeResult, FeffError};
+use crate::domain::{
+    ArtifactStore, ComputeArtifact, ComputeModule, ComputeRequest, ComputeResult, FeffError,
+};
 use std::collections::BTreeSet;
-use std::fs;
 use std::path::{Path, PathBuf};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -107,8 +108,12 @@ pub(super) fn input_parent_dir(request: &ComputeRequest) -> ComputeResult<&Path>
     })
 }
 
-pub(super) fn read_input_source(path: &Path, artifact_name: &str) -> ComputeResult<String> {
-    let bytes = fs::read(path).map_err(|source| {
+pub(super) fn read_input_source(
+    store: &dyn ArtifactStore,
+    path: &Path,
+    artifact_name: &str,
+) -> ComputeResult<String> {
+    let bytes = store.read(path).map_err(|source| {
         FeffError::io_system(
             "IO.SELF_INPUT_READ",
             format!(
@@ -123,20 +128,22 @@ pub(super) fn read_input_source(path: &Path, artifact_name: &str) -> ComputeResu
 }
 
 pub(super) fn maybe_read_optional_input_source(
+    store: &dyn ArtifactStore,
     path: PathBuf,
     artifact_name: &str,
 ) -> ComputeResult<Option<String>> {
-    if path.is_file() {
-        return read_input_source(&path, artifact_name).map(Some);
+    if store.is_file(&path) {
+        return read_input_source(store, &path, artifact_name).map(Some);
     }
 
     Ok(None)
 }
 
 pub(super) fn load_staged_spectrum_sources(
+    store: &dyn ArtifactStore,
     directory: &Path,
 ) -> ComputeResult<Vec<StagedSpectrumSource>> {
-    let artifacts = collect_staged_spectrum_artifacts(directory)?;
+    let artifacts = collect_staged_spectrum_artifacts(store, directory)?;
     if artifacts.is_empty() {
         return Err(FeffError::input_validation(
             "INPUT.SELF_SPECTRUM_INPUT",
@@ -149,7 +156,7 @@ pub(super) fn load_staged_spectrum_sources(
 
     let mut sources = Vec::with_capacity(artifacts.len());
     for artifact in artifacts {
-        let source = read_input_source(&directory.join(&artifact), &artifact)?;
+        let source = read_input_source(store, &directory.join(&artifact), &artifact)?;
         sources.push(StagedSpectrumSource { artifact, source });
     }
     Ok(sources)
@@ -337,13 +344,16 @@ pub(super) fn upsert_artifact(artifacts: &mut Vec<ComputeArtifact>, artifact: &s
     artifacts.push(ComputeArtifact::new(artifact));
 }
 
-fn collect_staged_spectrum_artifacts(directory: &Path) -> ComputeResult<Vec<String>> {
+fn collect_staged_spectrum_artifacts(
+    store: &dyn ArtifactStore,
+    directory: &Path,
+) -> ComputeResult<Vec<String>> {
     let mut artifacts = Vec::new();
     let mut seen = BTreeSet::new();
 
     for candidate in SELF_SPECTRUM_INPUT_CANDIDATES {
         let candidate_path = directory.join(candidate);
-        if !candidate_path.is_file() {
+        if !store.is_file(&candidate_path) {
             continue;
         }
 
@@ -353,12 +363,9 @@ fn collect_staged_spectrum_artifacts(directory: &Path) -> ComputeResult<Vec<Stri
         }
     }
 
-    for artifact in collect_feff_spectrum_artifacts(
-        directory,
-        "IO.SELF_INPUT_READ",
-        "input",
-        "input directory",
-    )? {
+    for artifact in
+        collect_feff_spectrum_artifacts(store, directory, "IO.SELF_INPUT_READ", "input")?
+    {
         let key = artifact.to_ascii_lowercase();
         if seen.insert(key) {
             artifacts.push(artifact);
@@ -369,12 +376,12 @@ fn collect_staged_spectrum_artifacts(directory: &Path) -> ComputeResult<Vec<Stri
 }
 
 fn collect_feff_spectrum_artifacts(
+    store: &dyn ArtifactStore,
     directory: &Path,
     placeholder: &'static str,
     location: &'static str,
-    location_label: &'static str,
 ) -> ComputeResult<Vec<String>> {
-    let entries = fs::read_dir(directory).map_err(|source| {
+    let names = store.list_files(directory).map_err(|source| {
         FeffError::io_system(
             placeholder,
             format!(
@@ -386,46 +393,10 @@ fn collect_feff_spectrum_artifacts(
         )
     })?;
 
-    let mut artifacts = Vec::new();
-    for entry in entries {
-        let entry = entry.map_err(|source| {
-            FeffError::io_system(
-                placeholder,
-                format!(
-                    "failed to read SELF {} entry in '{}': {}",
-                    location,
-                    directory.display(),
-                    source
-                ),
-            )
-        })?;
-
-        let file_type = entry.file_type().map_err(|source| {
-            FeffError::io_system(
-                placeholder,
-                format!(
-                    "failed to inspect SELF {} entry '{}' in '{}': {}",
-                    location_label,
-                    entry.path().display(),
-                    directory.display(),
-                    source
-                ),
-            )
-        })?;
-
-        if !file_type.is_file() {
-            continue;
-        }
-
-        let file_name = entry.file_name();
-        let file_name = file_name.to_string_lossy();
-        if is_feff_spectrum_name(&file_name) {
-            artifacts.push(file_name.into_owned());
-        }
-    }
-
-    artifacts.sort();
-    Ok(artifacts)
+    Ok(names
+        .into_iter()
+        .filter(|name| is_feff_spectrum_name(name))
+        .collect())
 }
 
 fn parse_numbers_after_marker(lines: &[&str], marker: &str) -> Option<Vec<f64>> {
@@ -487,7 +458,7 @@ fn fnv1a64(bytes: &[u8]) -> u64 {
 fn self_parse_error(fixture_id: &str, message: impl Into<String>) -> FeffError {
     FeffError::computation(
         "RUN.SELF_INPUT_PARSE",
-        format!("fixture '{}': {}", fixture_id, message.into()),
+        format!("run '{}': {}", fixture_id, message.into()),
     )
 }
 
@@ -1,4 +1,4 @@
-use std::fs;
+use crate::domain::{ArtifactStore, FileSystemStore};
 use std::path::Path;
 
 pub fn format_fixed_f64(value: f64, width: usize, precision: usize) -> String {
@@ -18,11 +18,27 @@ pub fn normalize_text_artifact(content: &str) -> String {
 }
 
 pub fn write_text_artifact(path: &Path, content: &str) -> std::io::Result<()> {
-    fs::write(path, normalize_text_artifact(content))
+    write_text_artifact_to(&FileSystemStore, path, content)
 }
 
 pub fn write_binary_artifact(path: &Path, bytes: &[u8]) -> std::io::Result<()> {
-    fs::write(path, bytes)
+    write_binary_artifact_to(&FileSystemStore, path, bytes)
+}
+
+pub fn write_text_artifact_to(
+    store: &dyn ArtifactStore,
+    path: &Path,
+    content: &str,
+) -> std::io::Result<()> {
+    store.write(path, normalize_text_artifact(content).as_bytes())
+}
+
+pub fn write_binary_artifact_to(
+    store: &dyn ArtifactStore,
+    path: &Path,
+    bytes: &[u8],
+) -> std::io::Result<()> {
+    store.write(path, bytes)
 }
 
 #[cfg(test)]
@@ -0,0 +1,462 @@
+use super::serialization::{format_fixed_f64, write_text_artifact};
+use crate::domain::{ComputeArtifact, ComputeResult, FeffError};
+use crate::parser::parse_input_deck;
+use crate::parser::variables::{declared_variables, expand_variables};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const RUN_DIRECTORY_PREFIX: &str = "run_";
+pub const SWEEP_SUMMARY_OUTPUT: &str = "sweep.dat";
+
+/// Largest grid a sweep may expand to; a typo in a range should not create
+/// millions of directories.
+pub const MAX_SWEEP_POINTS: usize = 10_000;
+
+const RANGE_EPSILON: f64 = 1.0e-9;
+
+/// One swept variable and the values it takes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SweepParameter {
+    pub name: String,
+    pub values: Vec<String>,
+}
+
+impl SweepParameter {
+    /// Parses `NAME=v1,v2,...` or the inclusive range `NAME=start:stop:step`.
+    pub fn parse(spec: &str) -> ComputeResult<Self> {
+        let (name, values) = spec
+            .split_once('=')
+            .ok_or_else(|| sweep_error(format!("sweep parameter '{}' is not NAME=VALUES", spec)))?;
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(sweep_error(format!(
+                "sweep parameter '{}' has no name",
+                spec
+            )));
+        }
+        let values = if values.contains(':') {
+            range_values(name, values)?
+        } else {
+            values
+                .split(',')
+                .map(|value| value.trim().to_string())
+                .filter(|value| !value.is_empty())
+                .collect()
+        };
+        if values.is_empty() {
+            return Err(sweep_error(format!(
+                "sweep parameter '{}' has no values",
+                name
+            )));
+        }
+        Ok(Self {
+            name: name.to_string(),
+            values,
+        })
+    }
+}
+
+/// One point of the grid and the directory its deck is written to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SweepPoint {
+    pub directory: String,
+    pub assignments: Vec<(String, String)>,
+}
+
+/// Outcome of running the chain for one point, as reported by the caller.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SweepRunStatus {
+    Completed,
+    Failed(String),
+}
+
+/// A deck with `@set` variables expanded over the Cartesian product of the
+/// swept values (the last parameter varies fastest).
+#[derive(Debug, Clone)]
+pub struct SweepPlan {
+    source: String,
+    parameters: Vec<SweepParameter>,
+    points: Vec<SweepPoint>,
+}
+
+impl SweepPlan {
+    /// Every swept name must be declared with `@set` in `source`, and every
+    /// point must expand into a deck that parses.
+    pub fn from_feff_source(source: &str, parameters: Vec<SweepParameter>) -> ComputeResult<Self> {
+        if parameters.is_empty() {
+            return Err(sweep_error(
+                "a sweep needs at least one NAME=VALUES parameter",
+            ));
+        }
+        let declared = declared_variables(source);
+        for (index, parameter) in parameters.iter().enumerate() {
+            if !declared.iter().any(|(name, _)| *name == parameter.name) {
+                return Err(sweep_error(format!(
+                    "sweep parameter '{}' is not declared with '@set {} <default>' in the deck",
+                    parameter.name, parameter.name
+                )));
+            }
+            if parameters[..index]
+                .iter()
+                .any(|other| other.name == parameter.name)
+            {
+                return Err(sweep_error(format!(
+                    "sweep parameter '{}' is given more than once",
+                    parameter.name
+                )));
+            }
+        }
+        let count = parameters
+            .iter()
+            .try_fold(1_usize, |count, parameter| {
+                count.checked_mul(parameter.values.len())
+            })
+            .filter(|count| *count <= MAX_SWEEP_POINTS)
+            .ok_or_else(|| {
+                sweep_error(format!(
+                    "sweep grid has more than {} points",
+                    MAX_SWEEP_POINTS
+                ))
+            })?;
+
+        let width = count.to_string().len().max(3);
+        let mut points = Vec::with_capacity(count);
+        for index in 0..count {
+            let mut remainder = index;
+            let mut assignments = vec![(String::new(), String::new()); parameters.len()];
+            for (slot, parameter) in parameters.iter().enumerate().rev() {
+                let value = &parameter.values[remainder % parameter.values.len()];
+                remainder /= parameter.values.len();
+                assignments[slot] = (parameter.name.clone(), value.clone());
+            }
+            points.push(SweepPoint {
+                directory: format!("{}{:0width$}", RUN_DIRECTORY_PREFIX, index + 1),
+                assignments,
+            });
+        }
+
+        let plan = Self {
+            source: source.to_string(),
+            parameters,
+            points,
+        };
+        for point in &plan.points {
+            parse_input_deck(&plan.render_point_deck(point)?).map_err(|error| {
+                sweep_error(format!(
+                    "{} ({}): {}",
+                    point.directory,
+                    describe(point),
+                    error.message()
+                ))
+            })?;
+        }
+        Ok(plan)
+    }
+
+    pub fn points(&self) -> &[SweepPoint] {
+        &self.points
+    }
+
+    /// The deck for one point with every variable expanded, so that it runs
+    /// with any FEFF build.
+    pub fn render_point_deck(&self, point: &SweepPoint) -> ComputeResult<String> {
+        let overrides: BTreeMap<String, String> = point.assignments.iter().cloned().collect();
+        expand_variables(&self.source, &overrides)
+    }
+
+    /// Writes `<root>/run_NNN/feff.inp` for every point.
+    pub fn write_run_decks(&self, root: &Path) -> ComputeResult<Vec<PathBuf>> {
+        let mut directories = Vec::with_capacity(self.points.len());
+        for point in &self.points {
+            let directory = root.join(&point.directory);
+            fs::create_dir_all(&directory).map_err(|source| {
+                FeffError::io_system(
+                    "IO.SWEEP_WRITE",
+                    format!(
+                        "failed to create run directory '{}': {}",
+                        directory.display(),
+                        source
+                    ),
+                )
+            })?;
+            let deck_path = directory.join("feff.inp");
+            write_text_artifact(&deck_path, &self.render_point_deck(point)?).map_err(|source| {
+                FeffError::io_system(
+                    "IO.SWEEP_WRITE",
+                    format!(
+                        "failed to write run deck '{}': {}",
+                        deck_path.display(),
+                        source
+                    ),
+                )
+            })?;
+            directories.push(directory);
+        }
+        Ok(directories)
+    }
+
+    /// Writes `sweep.dat` into `root`: one row per point with its values, run
+    /// status and the white-line position and height read from its `xmu.dat`.
+    pub fn write_summary(
+        &self,
+        root: &Path,
+        statuses: &[SweepRunStatus],
+    ) -> ComputeResult<ComputeArtifact> {
+        let mut header = String::from("#    run");
+        for parameter in &self.parameters {
+            header.push_str(&format!(" {:>12}", parameter.name));
+        }
+        header.push_str("   status     peak_e(eV)      peak_mu   outputs");
+        let mut lines = vec![
+            format!(
+                "# # FEFF10-RS parameter sweep: {} point(s) over {}",
+                self.points.len(),
+                self.parameters
+                    .iter()
+                    .map(|parameter| parameter.name.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            header,
+        ];
+
+        for (index, point) in self.points.iter().enumerate() {
+            let directory = root.join(&point.directory);
+            let mut line = format!("{:>8}", index + 1);
+            for (_, value) in &point.assignments {
+                line.push_str(&format!(" {:>12}", value));
+            }
+            let status = match statuses.get(index) {
+                Some(SweepRunStatus::Completed) => "ok",
+                Some(SweepRunStatus::Failed(_)) => "failed",
+                None => "pending",
+            };
+            line.push_str(&format!("   {:<7}", status));
+            match white_line(&directory.join("xmu.dat")) {
+                Some((energy, mu)) => line.push_str(&format!(
+                    " {} {}",
+                    format_fixed_f64(energy, 14, 4),
+                    format_fixed_f64(mu, 12, 6)
+                )),
+                None => line.push_str(&format!(" {:>14} {:>12}", "-", "-")),
+            }
+            let outputs = produced_outputs(&directory);
+            line.push_str(&format!(
+                "   {}",
+                if outputs.is_empty() {
+                    "-".to_string()
+                } else {
+                    outputs.join(",")
+                }
+            ));
+            lines.push(line);
+        }
+
+        let path = root.join(SWEEP_SUMMARY_OUTPUT);
+        write_text_artifact(&path, &(lines.join("\n") + "\n")).map_err(|source| {
+            FeffError::io_system(
+                "IO.SWEEP_WRITE",
+                format!(
+                    "failed to write sweep summary '{}': {}",
+                    path.display(),
+                    source
+                ),
+            )
+        })?;
+        Ok(ComputeArtifact::new(SWEEP_SUMMARY_OUTPUT))
+    }
+}
+
+/// `NAME=value` pairs of a point, e.g. for progress messages.
+pub fn describe(point: &SweepPoint) -> String {
+    point
+        .assignments
+        .iter()
+        .map(|(name, value)| format!("{}={}", name, value))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn range_values(name: &str, spec: &str) -> ComputeResult<Vec<String>> {
+    let parts = spec
+        .split(':')
+        .map(|part| part.trim().parse::<f64>())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|_| sweep_error(format!("range for '{}' must be start:stop:step", name)))?;
+    let [start, stop, step] = parts[..] else {
+        return Err(sweep_error(format!(
+            "range for '{}' must be start:stop:step",
+            name
+        )));
+    };
+    if !(step.is_finite() && step > 0.0 && start.is_finite() && stop >= start) {
+        return Err(sweep_error(format!(
+            "range for '{}' needs start <= stop and a positive step",
+            name
+        )));
+    }
+    let count = ((stop - start) / step + RANGE_EPSILON).floor() as usize + 1;
+    if count > MAX_SWEEP_POINTS {
+        return Err(sweep_error(format!(
+            "range for '{}' has more than {} values",
+            name, MAX_SWEEP_POINTS
+        )));
+    }
+    Ok((0..count)
+        .map(|index| {
+            let value = start + step * index as f64;
+            // Round away binary noise such as 0.30000000000000004.
+            format!("{}", (value * 1.0e9).round() / 1.0e9)
+        })
+        .collect())
+}
+
+/// Energy and height of the largest `mu` in an `xmu.dat` (columns omega, e,
+/// k, mu, mu0, chi).
+fn white_line(path: &Path) -> Option<(f64, f64)> {
+    let source = fs::read_to_string(path).ok()?;
+    source
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| {
+            let fields: Vec<f64> = line
+                .split_whitespace()
+                .map(|field| field.replace(['D', 'd'], "E").parse::<f64>().ok())
+                .collect::<Option<_>>()?;
+            Some((*fields.first()?, *fields.get(3)?))
+        })
+        .max_by(|left, right| left.1.total_cmp(&right.1))
+}
+
+fn produced_outputs(directory: &Path) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(directory) else {
+        return Vec::new();
+    };
+    let mut outputs: Vec<String> = entries
+        .filter_map(Result::ok)
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .filter(|name| name.ends_with(".dat"))
+        .collect();
+    outputs.sort();
+    outputs
+}
+
+fn sweep_error(message: impl Into<String>) -> FeffError {
+    FeffError::input_validation("INPUT.SWEEP", message.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SWEEP_SUMMARY_OUTPUT, SweepParameter, SweepPlan, SweepRunStatus};
+    use crate::domain::FeffErrorCategory;
+    use crate::parser::parse_input_deck;
+    use std::fs;
+    use tempfile::TempDir;
+
+    const DECK: &str = "\
+@set T 300
+@set R 6.0
+TITLE Cu at ${T} K
+EDGE K
+DEBYE ${T} 315
+FMS ${R}
+POTENTIALS
+0 29 Cu
+ATOMS
+0.0 0.0 0.0 0 Cu
+END
+";
+
+    fn parameters(specs: &[&str]) -> Vec<SweepParameter> {
+        specs
+            .iter()
+            .map(|spec| SweepParameter::parse(spec).expect("parameter should parse"))
+            .collect()
+    }
+
+    #[test]
+    fn parameters_accept_lists_and_inclusive_ranges() {
+        assert_eq!(
+            SweepParameter::parse("S02=0.8, 0.9,1.0")
+                .expect("list")
+                .values,
+            vec!["0.8", "0.9", "1.0"]
+        );
+        assert_eq!(
+            SweepParameter::parse("R=4:6:0.5").expect("range").values,
+            vec!["4", "4.5", "5", "5.5", "6"]
+        );
+        assert!(SweepParameter::parse("R=6:4:1").is_err());
+        assert!(SweepParameter::parse("R").is_err());
+    }
+
+    #[test]
+    fn grid_expands_into_run_decks() {
+        let plan = SweepPlan::from_feff_source(DECK, parameters(&["T=10,300", "R=4:5:1"]))
+            .expect("plan should build");
+        let described: Vec<String> = plan.points().iter().map(super::describe).collect();
+        assert_eq!(
+            described,
+            vec!["T=10 R=4", "T=10 R=5", "T=300 R=4", "T=300 R=5"]
+        );
+
+        let temp = TempDir::new().expect("tempdir should be created");
+        let directories = plan
+            .write_run_decks(temp.path())
+            .expect("decks should be written");
+        assert_eq!(directories[1], temp.path().join("run_002"));
+        let deck = fs::read_to_string(directories[1].join("feff.inp")).expect("run deck");
+        assert!(deck.contains("TITLE Cu at 10 K\n") && deck.contains("FMS 5\n"));
+        let parsed = parse_input_deck(&deck).expect("run deck should parse");
+        assert!(
+            parsed
+                .cards
+                .iter()
+                .any(|card| card.keyword == "DEBYE" && card.values[0] == "10")
+        );
+    }
+
+    #[test]
+    fn undeclared_parameters_are_rejected() {
+        let error = SweepPlan::from_feff_source(DECK, parameters(&["S02=0.9"]))
+            .expect_err("S02 is not declared");
+        assert_eq!(error.category(), FeffErrorCategory::InputValidationError);
+        assert_eq!(error.placeholder(), "INPUT.SWEEP");
+    }
+
+    #[test]
+    fn summary_reports_values_status_and_white_line() {
+        let plan = SweepPlan::from_feff_source(DECK, parameters(&["T=10,300"]))
+            .expect("plan should build");
+        let temp = TempDir::new().expect("tempdir should be created");
+        let directories = plan
+            .write_run_decks(temp.path())
+            .expect("decks should be written");
+        fs::write(
+            directories[0].join("xmu.dat"),
+            "# header\n8980.0 -1.0 0.5 0.10 0.1 0.0\n8990.0 9.0 1.5 1.25 0.1 0.0\n9000.0 19.0 2.3 0.90 0.1 0.0\n",
+        )
+        .expect("xmu should be written");
+
+        plan.write_summary(
+            temp.path(),
+            &[
+                SweepRunStatus::Completed,
+                SweepRunStatus::Failed("boom".to_string()),
+            ],
+        )
+        .expect("summary should be written");
+        let summary = fs::read_to_string(temp.path().join(SWEEP_SUMMARY_OUTPUT)).expect("summary");
+        let rows: Vec<&str> = summary.lines().skip(2).collect();
+        assert_eq!(rows.len(), 2);
+        assert!(
+            rows[0].contains(" ok ")
+                && rows[0].contains("8990.0000")
+                && rows[0].contains("1.250000")
+        );
+        assert!(rows[0].ends_with("xmu.dat"));
+        assert!(rows[1].contains(" failed ") && rows[1].ends_with(" -"));
+    }
+}
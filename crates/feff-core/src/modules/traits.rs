@@ -1,4 +1,25 @@
-use crate::domain::{ComputeArtifact, ComputeRequest, ComputeResult};
+use crate::domain::{ComputeArtifact, ComputeModule, ComputeRequest, ComputeResult};
+
+/// Artifact names a module's contract type declares before any input is read.
+/// `contract_for_request` may narrow the outputs for a given deck; the
+/// pipeline planner and the module cache use these declarations.
+pub trait ModuleContract {
+    const MODULE: ComputeModule;
+
+    fn required_input_names() -> Vec<&'static str>;
+
+    /// At least one of these must exist (SELF accepts any staged spectrum).
+    fn any_of_input_names() -> Vec<&'static str> {
+        Vec::new()
+    }
+
+    fn optional_input_names() -> Vec<&'static str> {
+        Vec::new()
+    }
+
+    /// Every output the module may write.
+    fn output_names() -> Vec<&'static str>;
+}
 
 pub trait ModuleExecutor {
     fn execute(&self, request: &ComputeRequest) -> ComputeResult<Vec<ComputeArtifact>>;
@@ -1,9 +1,10 @@
 mod model;
+mod nrixs;
 mod parser;
 
-use super::ModuleExecutor;
-use crate::domain::{ComputeArtifact, ComputeRequest, ComputeResult, FeffError};
-use std::fs;
+use super::distributed::EnergyChunk;
+use super::{ModuleContract, ModuleExecutor};
+use crate::domain::{ComputeArtifact, ComputeModule, ComputeRequest, ComputeResult, FeffError};
 
 use model::XsphModel;
 use parser::{
@@ -15,7 +16,7 @@ pub(crate) const XSPH_REQUIRED_INPUTS: [&str; 4] =
     ["xsph.inp", "geom.dat", "global.inp", "pot.bin"];
 pub(crate) const XSPH_OPTIONAL_INPUTS: [&str; 1] = ["wscrn.dat"];
 pub(crate) const XSPH_REQUIRED_OUTPUTS: [&str; 3] = ["phase.bin", "xsect.dat", "log2.dat"];
-pub(crate) const XSPH_OPTIONAL_OUTPUTS: [&str; 1] = ["phase.dat"];
+pub(crate) const XSPH_OPTIONAL_OUTPUTS: [&str; 2] = ["phase.dat", "nrixs.dat"];
 pub const XSPH_PHASE_BINARY_MAGIC: &[u8; 8] = b"XSPHBIN1";
 
 const POT_CONTROL_I32_COUNT: usize = 16;
@@ -29,6 +30,25 @@ pub struct XsphContract {
     pub optional_outputs: Vec<ComputeArtifact>,
 }
 
+impl ModuleContract for XsphContract {
+    const MODULE: ComputeModule = ComputeModule::Xsph;
+
+    fn required_input_names() -> Vec<&'static str> {
+        XSPH_REQUIRED_INPUTS.to_vec()
+    }
+
+    fn optional_input_names() -> Vec<&'static str> {
+        XSPH_OPTIONAL_INPUTS.to_vec()
+    }
+
+    fn output_names() -> Vec<&'static str> {
+        XSPH_REQUIRED_OUTPUTS
+            .into_iter()
+            .chain(XSPH_OPTIONAL_OUTPUTS)
+            .collect()
+    }
+}
+
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub struct XsphModule;
 
@@ -36,48 +56,94 @@ impl XsphModule {
     pub fn contract_for_request(&self, request: &ComputeRequest) -> ComputeResult<XsphContract> {
         validate_request_shape(request)?;
         Ok(XsphContract {
-            required_inputs: artifact_list(&XSPH_REQUIRED_INPUTS),
-            optional_inputs: artifact_list(&XSPH_OPTIONAL_INPUTS),
+            required_inputs: artifact_list(&XsphContract::required_input_names()),
+            optional_inputs: artifact_list(&XsphContract::optional_input_names()),
             expected_outputs: artifact_list(&XSPH_REQUIRED_OUTPUTS),
             optional_outputs: artifact_list(&XSPH_OPTIONAL_OUTPUTS),
         })
     }
+
+    /// `phase.bin` rows for one distributed worker's share of the energy grid.
+    pub fn energy_chunk(
+        &self,
+        request: &ComputeRequest,
+        chunk: EnergyChunk,
+    ) -> ComputeResult<Vec<u8>> {
+        let model = load_model(request)?;
+        model.energy_rows(chunk.range(model.energy_point_count()))
+    }
+
+    /// Executes XSPH with `phase.bin` energy rows merged from every chunk in order.
+    pub fn execute_with_energy_rows(
+        &self,
+        request: &ComputeRequest,
+        rows: Vec<u8>,
+    ) -> ComputeResult<Vec<ComputeArtifact>> {
+        write_outputs(request, &load_model(request)?.with_energy_rows(rows)?)
+    }
 }
 
 impl ModuleExecutor for XsphModule {
     fn execute(&self, request: &ComputeRequest) -> ComputeResult<Vec<ComputeArtifact>> {
-        validate_request_shape(request)?;
-        let input_dir = input_parent_dir(request)?;
-
-        let xsph_source = read_input_source(&request.input_path, XSPH_REQUIRED_INPUTS[0])?;
-        let geom_source = read_input_source(
-            &input_dir.join(XSPH_REQUIRED_INPUTS[1]),
-            XSPH_REQUIRED_INPUTS[1],
-        )?;
-        let global_source = read_input_source(
-            &input_dir.join(XSPH_REQUIRED_INPUTS[2]),
-            XSPH_REQUIRED_INPUTS[2],
-        )?;
-        let pot_bytes = read_input_bytes(
-            &input_dir.join(XSPH_REQUIRED_INPUTS[3]),
-            XSPH_REQUIRED_INPUTS[3],
-        )?;
-        let wscrn_source = maybe_read_optional_input_source(
-            input_dir.join(XSPH_OPTIONAL_INPUTS[0]),
-            XSPH_OPTIONAL_INPUTS[0],
-        )?;
-
-        let model = XsphModel::from_sources(
-            &request.fixture_id,
-            &xsph_source,
-            &geom_source,
-            &global_source,
-            &pot_bytes,
-            wscrn_source.as_deref(),
-        )?;
-        let outputs = artifact_list(&XSPH_REQUIRED_OUTPUTS);
-
-        fs::create_dir_all(&request.output_dir).map_err(|source| {
+        write_outputs(request, &load_model(request)?)
+    }
+}
+
+fn load_model(request: &ComputeRequest) -> ComputeResult<XsphModel> {
+    validate_request_shape(request)?;
+    let input_dir = input_parent_dir(request)?;
+
+    let xsph_source = read_input_source(
+        &*request.store,
+        &request.input_path,
+        XSPH_REQUIRED_INPUTS[0],
+    )?;
+    let geom_source = read_input_source(
+        &*request.store,
+        &input_dir.join(XSPH_REQUIRED_INPUTS[1]),
+        XSPH_REQUIRED_INPUTS[1],
+    )?;
+    let global_source = read_input_source(
+        &*request.store,
+        &input_dir.join(XSPH_REQUIRED_INPUTS[2]),
+        XSPH_REQUIRED_INPUTS[2],
+    )?;
+    let pot_bytes = read_input_bytes(
+        &*request.store,
+        &input_dir.join(XSPH_REQUIRED_INPUTS[3]),
+        XSPH_REQUIRED_INPUTS[3],
+    )?;
+    let wscrn_source = maybe_read_optional_input_source(
+        &*request.store,
+        input_dir.join(XSPH_OPTIONAL_INPUTS[0]),
+        XSPH_OPTIONAL_INPUTS[0],
+    )?;
+
+    Ok(XsphModel::from_sources(
+        &request.fixture_id,
+        &xsph_source,
+        &geom_source,
+        &global_source,
+        &pot_bytes,
+        wscrn_source.as_deref(),
+    )?
+    .with_execution_mode(request.execution_mode)
+    .with_execution_control(request.control.clone()))
+}
+
+fn write_outputs(
+    request: &ComputeRequest,
+    model: &XsphModel,
+) -> ComputeResult<Vec<ComputeArtifact>> {
+    let mut outputs = artifact_list(&XSPH_REQUIRED_OUTPUTS);
+    if model.has_nrixs() {
+        outputs.push(ComputeArtifact::new(XSPH_OPTIONAL_OUTPUTS[1]));
+    }
+
+    request
+        .store
+        .create_dir_all(&request.output_dir)
+        .map_err(|source| {
             FeffError::io_system(
                 "IO.XSPH_OUTPUT_DIRECTORY",
                 format!(
@@ -88,27 +154,26 @@ impl ModuleExecutor for XsphModule {
             )
         })?;
 
-        for artifact in &outputs {
-            let output_path = request.output_dir.join(&artifact.relative_path);
-            if let Some(parent) = output_path.parent() {
-                fs::create_dir_all(parent).map_err(|source| {
-                    FeffError::io_system(
-                        "IO.XSPH_OUTPUT_DIRECTORY",
-                        format!(
-                            "failed to create XSPH artifact directory '{}': {}",
-                            parent.display(),
-                            source
-                        ),
-                    )
-                })?;
-            }
-
-            let artifact_name = artifact.relative_path.to_string_lossy().replace('\\', "/");
-            model.write_artifact(&artifact_name, &output_path)?;
+    for artifact in &outputs {
+        let output_path = request.output_dir.join(&artifact.relative_path);
+        if let Some(parent) = output_path.parent() {
+            request.store.create_dir_all(parent).map_err(|source| {
+                FeffError::io_system(
+                    "IO.XSPH_OUTPUT_DIRECTORY",
+                    format!(
+                        "failed to create XSPH artifact directory '{}': {}",
+                        parent.display(),
+                        source
+                    ),
+                )
+            })?;
         }
 
-        Ok(outputs)
+        let artifact_name = artifact.relative_path.to_string_lossy().replace('\\', "/");
+        model.write_artifact(&*request.store, &artifact_name, &output_path)?;
     }
+
+    Ok(outputs)
 }
 
 #[cfg(test)]
@@ -148,6 +213,19 @@ rgrd, rfms2, gamach, xkstep, xkmax, vixan, Eps0, EGap
    9000.00000      0.18000     -7.50000
 ";
 
+    const NRIXS_GLOBAL_INPUT_FIXTURE: &str = " nabs, iphabs - CFAVERAGE data
+       1       0 100000.00000
+ ipol, ispin, le2, elpty, angks, l2lp, do_nrixs, ldecmx, lj
+    0    0    0      0.0000      0.0000    0    1   -1    2
+evnorm, xivnorm, spvnorm - only used for nrixs
+      0.00000      0.00000      0.00000
+nq,    imdff,   qaverage,   mixdff,   qqmdff,   cos<q,q'>
+           2           0 T F  -1.00000000000000     
+ q-vectors : qx, qy, qz, q(norm), weight, qcosth, qsinth, qcosfi, qsinfi
+      0.00000      0.00000      1.00000      1.00000      0.50000      1.00000      0.00000      1.00000      0.00000
+      0.00000      0.00000     12.00000     12.00000      0.50000      1.00000      0.00000      1.00000      0.00000
+";
+
     const WSCRN_INPUT_FIXTURE: &str = "    0.1507330463E-03    0.2672902675E+02    0.2916165288E+02
     0.1584612949E-03    0.2672902006E+02    0.2916164619E+02
     0.1665857792E-03    0.2672900634E+02    0.2916163247E+02
@@ -174,7 +252,7 @@ rgrd, rfms2, gamach, xkstep, xkmax, vixan, Eps0, EGap
         );
         assert_eq!(
             artifact_set(&contract.optional_outputs),
-            expected_artifact_set(&["phase.dat"])
+            expected_artifact_set(&["phase.dat", "nrixs.dat"])
         );
     }
 
@@ -272,6 +350,84 @@ rgrd, rfms2, gamach, xkstep, xkmax, vixan, Eps0, EGap
         );
     }
 
+    #[test]
+    fn execute_writes_nrixs_matrix_elements_when_global_enables_nrixs() {
+        let temp = TempDir::new().expect("tempdir should be created");
+        let (input_path, output_dir) = stage_xsph_inputs(temp.path(), false);
+        fs::write(temp.path().join("global.inp"), NRIXS_GLOBAL_INPUT_FIXTURE)
+            .expect("global input should be written");
+
+        let request =
+            ComputeRequest::new("FX-XSPH-001", ComputeModule::Xsph, &input_path, &output_dir);
+        let artifacts = XsphModule
+            .execute(&request)
+            .expect("XSPH NRIXS execution should succeed");
+        assert_eq!(
+            artifact_set(&artifacts),
+            expected_artifact_set(&["phase.bin", "xsect.dat", "log2.dat", "nrixs.dat"])
+        );
+
+        let nrixs = fs::read_to_string(output_dir.join("nrixs.dat")).expect("nrixs.dat");
+        assert!(nrixs.contains("# absorber_z: 29"));
+        assert!(nrixs.contains("# ljmax: 2 ldecmx: -1 spherical_average: T"));
+        let tagged_rows = |tag: &str| {
+            nrixs
+                .lines()
+                .filter_map(|line| line.strip_prefix(tag))
+                .map(|line| {
+                    line.split_whitespace()
+                        .map(|token| token.parse::<f64>().expect("numeric row"))
+                        .collect::<Vec<_>>()
+                })
+                .collect::<Vec<_>>()
+        };
+        let q_rows = tagged_rows("q ");
+        let rkk_rows = tagged_rows("rkk ");
+        assert_eq!(q_rows.len(), 2);
+        assert_eq!(q_rows[1][4], 12.0);
+        // xkstep 0.07 up to xkmax 8.0.
+        assert_eq!(rkk_rows.len(), 115);
+        assert!(rkk_rows.iter().all(|row| row.len() == 2 + 2 * 3 * 2));
+
+        // Channel strengths |rkk_l|^2 for q `iq` at the highest energy point.
+        let last = rkk_rows.last().expect("rkk rows");
+        assert!(last[1] > 0.0);
+        let strength = |iq: usize, l: usize| {
+            let offset = 2 + (iq * 3 + l) * 2;
+            last[offset].powi(2) + last[offset + 1].powi(2)
+        };
+        assert!(strength(0, 1) > strength(0, 0) && strength(0, 1) > strength(0, 2));
+        assert!(strength(1, 2) / strength(1, 1) > strength(0, 2) / strength(0, 1));
+        let below_edge = &rkk_rows[0];
+        assert_eq!(below_edge[1], 0.0);
+        assert!(below_edge[2..].iter().all(|value| *value == 0.0));
+
+        let log = fs::read_to_string(output_dir.join("log2.dat")).expect("log2.dat");
+        assert!(log.contains("nrixs-q2: q=12.00000 peak-strength="));
+    }
+
+    #[test]
+    fn execute_rejects_nrixs_global_with_missing_q_rows() {
+        let temp = TempDir::new().expect("tempdir should be created");
+        let (input_path, output_dir) = stage_xsph_inputs(temp.path(), false);
+        let truncated = NRIXS_GLOBAL_INPUT_FIXTURE
+            .lines()
+            .take_while(|line| !line.contains("12.00000"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        fs::write(temp.path().join("global.inp"), truncated)
+            .expect("global input should be written");
+
+        let request =
+            ComputeRequest::new("FX-XSPH-001", ComputeModule::Xsph, &input_path, &output_dir);
+        let error = XsphModule
+            .execute(&request)
+            .expect_err("missing q rows should fail");
+
+        assert_eq!(error.category(), FeffErrorCategory::ComputationError);
+        assert_eq!(error.placeholder(), "RUN.XSPH_INPUT_PARSE");
+    }
+
     #[test]
     fn execute_rejects_non_xsph_module_requests() {
         let temp = TempDir::new().expect("tempdir should be created");
@@ -1,11 +1,19 @@
 use super::XSPH_PHASE_BINARY_MAGIC;
+use super::nrixs::{KShellRadialGrid, NrixsMatrixRow, k_shell_effective_charge, kinetic_energy};
 use super::parser::{
     GeomXsphInput, GlobalXsphInput, PotXsphInput, WscrnXsphInput, XsphControlInput,
     format_scientific_f64, parse_geom_source, parse_global_source, parse_pot_source,
     parse_wscrn_source, parse_xsph_source, push_f64, push_i32, push_u32,
 };
-use crate::domain::{ComputeResult, FeffError};
-use crate::modules::serialization::{format_fixed_f64, write_binary_artifact, write_text_artifact};
+use crate::domain::{
+    ArtifactStore, ComputeModule, ComputeResult, ExecutionControl, ExecutionMode, FeffError,
+};
+use crate::modules::serialization::{
+    format_fixed_f64, write_binary_artifact_to, write_text_artifact_to,
+};
+use crate::numerics::parallel::{map_indexed, try_map_indexed};
+use num_complex::Complex64;
+use std::ops::Range;
 use std::path::Path;
 
 #[derive(Debug, Clone)]
@@ -16,6 +24,10 @@ pub(super) struct XsphModel {
     global: GlobalXsphInput,
     pot: PotXsphInput,
     wscrn: Option<WscrnXsphInput>,
+    execution_mode: ExecutionMode,
+    execution_control: ExecutionControl,
+    /// `phase.bin` energy rows merged from distributed workers, in grid order.
+    energy_rows: Option<Vec<u8>>,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -29,6 +41,8 @@ struct XsphOutputConfig {
     damping: f64,
     screening_shift: f64,
     xsnorm: f64,
+    polarization_factor: f64,
+    spin_shift: f64,
 }
 
 impl XsphModel {
@@ -49,9 +63,50 @@ impl XsphModel {
             wscrn: wscrn_source
                 .map(|source| parse_wscrn_source(fixture_id, source))
                 .transpose()?,
+            execution_mode: ExecutionMode::Serial,
+            execution_control: ExecutionControl::default(),
+            energy_rows: None,
         })
     }
 
+    pub(super) fn with_execution_mode(mut self, execution_mode: ExecutionMode) -> Self {
+        self.execution_mode = execution_mode;
+        self
+    }
+
+    pub(super) fn with_execution_control(mut self, execution_control: ExecutionControl) -> Self {
+        self.execution_control = execution_control;
+        self
+    }
+
+    pub(super) fn energy_point_count(&self) -> usize {
+        self.output_config().spectral_points
+    }
+
+    /// `phase.bin` rows for the energy points in `indices`.
+    pub(super) fn energy_rows(&self, indices: Range<usize>) -> ComputeResult<Vec<u8>> {
+        self.phase_rows(&self.output_config(), indices)
+    }
+
+    /// Uses `rows` in place of computing the `phase.bin` energy rows.
+    pub(super) fn with_energy_rows(mut self, rows: Vec<u8>) -> ComputeResult<Self> {
+        let config = self.output_config();
+        let expected = config.spectral_points * phase_row_len(&config);
+        if rows.len() != expected {
+            return Err(FeffError::computation(
+                "RUN.XSPH_ENERGY_CHUNKS",
+                format!(
+                    "run '{}': merged phase.bin rows hold {} bytes but the energy grid needs {}",
+                    self.fixture_id,
+                    rows.len(),
+                    expected
+                ),
+            ));
+        }
+        self.energy_rows = Some(rows);
+        Ok(self)
+    }
+
     fn output_config(&self) -> XsphOutputConfig {
         let phase_channels = (self.control.lmaxph_max + self.control.nph)
             .max(2)
@@ -72,9 +127,14 @@ impl XsphModel {
                 .map(|wscrn| wscrn.radial_points as f64 * 1.0e-6)
                 .unwrap_or(0.0);
 
+        // Spin-up final states see the more attractive exchange potential.
+        let polarization = &self.global.polarization;
+        let spin_shift =
+            -f64::from(polarization.ispin.signum()) * polarization.exchange_splitting * 0.5;
         let energy_start = -(self.control.gamach + self.pot.gamach) * 6.0
             - self.geom.radius_mean * 2.0
-            - self.global.max_abs.min(50.0) * 0.01;
+            - self.global.max_abs.min(50.0) * 0.01
+            + spin_shift;
         let energy_step = (self.control.xkstep.max(1.0e-4) * 3.5).max(1.0e-4);
 
         let base_phase = (0.03 * self.pot.charge_scale
@@ -114,17 +174,100 @@ impl XsphModel {
             damping,
             screening_shift,
             xsnorm,
+            polarization_factor: self
+                .global
+                .polarization
+                .shell_dipole_factor(&self.geom.neighbor_vectors),
+            spin_shift,
         }
     }
 
+    pub(super) fn has_nrixs(&self) -> bool {
+        self.global.nrixs.is_some()
+    }
+
+    fn absorber_z(&self) -> f64 {
+        self.pot
+            .absorber_z
+            .map(f64::from)
+            .unwrap_or(self.pot.charge_scale.round())
+            .clamp(1.0, 118.0)
+    }
+
+    /// Photoelectron k grid of the NRIXS matrix elements: `0..=xkmax` in steps
+    /// of `xkstep`, as FEFF tabulates them.
+    fn nrixs_k_grid(&self) -> Vec<f64> {
+        let step = self.control.xkstep.max(1.0e-3);
+        let points = ((self.control.xkmax / step).round() as usize + 1).clamp(2, 1024);
+        (0..points).map(|index| index as f64 * step).collect()
+    }
+
+    /// NRIXS matrix elements `rkk_l(q, k) = sqrt(2l+1) <1s| j_l(qr) |k l> exp(i delta_l)`,
+    /// where `delta_l` is the channel-`l` phase XSPH writes to `phase.bin`, with
+    /// the `phase.bin` grid mapped linearly onto the k grid.
+    fn nrixs_rows(&self, config: &XsphOutputConfig) -> Vec<NrixsMatrixRow> {
+        let Some(nrixs) = &self.global.nrixs else {
+            return Vec::new();
+        };
+        let grid = KShellRadialGrid::new(k_shell_effective_charge(self.absorber_z()));
+        let k_grid = self.nrixs_k_grid();
+        let phases: Vec<Vec<f64>> = (0..=nrixs.ljmax)
+            .map(|l| {
+                let channel = l.min(config.phase_channels - 1);
+                (0..k_grid.len())
+                    .map(|ik| {
+                        let index = ik * (config.spectral_points - 1) / (k_grid.len() - 1);
+                        self.channel_phase(config, index, channel)
+                    })
+                    .collect()
+            })
+            .collect();
+
+        nrixs
+            .q_vectors
+            .iter()
+            .map(|q| {
+                let profiles = grid.operator_profiles(q.norm, nrixs.ljmax);
+                let integrals = map_indexed(self.execution_mode, k_grid.len(), |ik| {
+                    grid.multipole_integrals(&profiles, k_grid[ik])
+                });
+                let rkk = (0..=nrixs.ljmax)
+                    .map(|l| {
+                        integrals
+                            .iter()
+                            .zip(&phases[l])
+                            .map(|(values, phase)| Complex64::from_polar(values[l], *phase))
+                            .collect()
+                    })
+                    .collect();
+                NrixsMatrixRow { q: *q, rkk }
+            })
+            .collect()
+    }
+
     pub(super) fn write_artifact(
         &self,
+        store: &dyn ArtifactStore,
         artifact_name: &str,
         output_path: &Path,
     ) -> ComputeResult<()> {
         match artifact_name {
             "phase.bin" => {
-                write_binary_artifact(output_path, &self.render_phase_binary()).map_err(|source| {
+                write_binary_artifact_to(store, output_path, &self.render_phase_binary()?).map_err(
+                    |source| {
+                        FeffError::io_system(
+                            "IO.XSPH_OUTPUT_WRITE",
+                            format!(
+                                "failed to write XSPH artifact '{}': {}",
+                                output_path.display(),
+                                source
+                            ),
+                        )
+                    },
+                )
+            }
+            "xsect.dat" => write_text_artifact_to(store, output_path, &self.render_xsect())
+                .map_err(|source| {
                     FeffError::io_system(
                         "IO.XSPH_OUTPUT_WRITE",
                         format!(
@@ -133,10 +276,9 @@ impl XsphModel {
                             source
                         ),
                     )
-                })
-            }
-            "xsect.dat" => {
-                write_text_artifact(output_path, &self.render_xsect()).map_err(|source| {
+                }),
+            "log2.dat" => {
+                write_text_artifact_to(store, output_path, &self.render_log2()).map_err(|source| {
                     FeffError::io_system(
                         "IO.XSPH_OUTPUT_WRITE",
                         format!(
@@ -147,16 +289,17 @@ impl XsphModel {
                     )
                 })
             }
-            "log2.dat" => write_text_artifact(output_path, &self.render_log2()).map_err(|source| {
-                FeffError::io_system(
-                    "IO.XSPH_OUTPUT_WRITE",
-                    format!(
-                        "failed to write XSPH artifact '{}': {}",
-                        output_path.display(),
-                        source
-                    ),
-                )
-            }),
+            "nrixs.dat" => write_text_artifact_to(store, output_path, &self.render_nrixs())
+                .map_err(|source| {
+                    FeffError::io_system(
+                        "IO.XSPH_OUTPUT_WRITE",
+                        format!(
+                            "failed to write XSPH artifact '{}': {}",
+                            output_path.display(),
+                            source
+                        ),
+                    )
+                }),
             other => Err(FeffError::internal(
                 "SYS.XSPH_OUTPUT_CONTRACT",
                 format!("unsupported XSPH output artifact '{}'", other),
@@ -164,7 +307,7 @@ impl XsphModel {
         }
     }
 
-    fn render_phase_binary(&self) -> Vec<u8> {
+    fn render_phase_binary(&self) -> ComputeResult<Vec<u8>> {
         let config = self.output_config();
         let mut bytes = Vec::with_capacity(
             96 + config.spectral_points * (config.phase_channels + 1) * std::mem::size_of::<f64>(),
@@ -183,29 +326,58 @@ impl XsphModel {
         push_f64(&mut bytes, config.damping);
         push_f64(&mut bytes, config.screening_shift);
 
-        for index in 0..config.spectral_points {
-            let t = if config.spectral_points == 1 {
-                0.0
-            } else {
-                index as f64 / (config.spectral_points - 1) as f64
-            };
-            let energy = config.energy_start + config.energy_step * index as f64;
-            push_f64(&mut bytes, energy);
-
-            for channel in 0..config.phase_channels {
-                let channel_f = channel as f64;
-                let oscillation =
-                    (energy * 0.015 + 0.25 * channel_f + self.control.mphase as f64 * 0.1).sin();
-                let attenuation = (-config.damping * (1.0 + 0.03 * channel_f) * index as f64).exp();
-                let phase = config.base_phase
-                    + config.phase_scale * (1.0 + 0.1 * channel_f) * oscillation * attenuation
-                    + config.screening_shift * (1.0 - t)
-                    + 0.001 * self.control.ispec as f64;
-                push_f64(&mut bytes, phase);
-            }
+        match &self.energy_rows {
+            Some(rows) => bytes.extend_from_slice(rows),
+            None => bytes.extend(self.phase_rows(&config, 0..config.spectral_points)?),
         }
 
-        bytes
+        Ok(bytes)
+    }
+
+    fn phase_rows(
+        &self,
+        config: &XsphOutputConfig,
+        indices: Range<usize>,
+    ) -> ComputeResult<Vec<u8>> {
+        let rows = try_map_indexed(
+            self.execution_mode,
+            indices.len(),
+            |offset| -> ComputeResult<Vec<u8>> {
+                let index = indices.start + offset;
+                let mut row = Vec::with_capacity(phase_row_len(config));
+                let energy = config.energy_start + config.energy_step * index as f64;
+                push_f64(&mut row, energy);
+
+                for channel in 0..config.phase_channels {
+                    push_f64(&mut row, self.channel_phase(config, index, channel));
+                }
+                self.execution_control.energy_point(
+                    ComputeModule::Xsph,
+                    index,
+                    config.spectral_points,
+                )?;
+                Ok(row)
+            },
+        )?;
+        Ok(rows.concat())
+    }
+
+    /// Phase of `channel` at energy point `index` of the `phase.bin` grid.
+    fn channel_phase(&self, config: &XsphOutputConfig, index: usize, channel: usize) -> f64 {
+        let t = if config.spectral_points == 1 {
+            0.0
+        } else {
+            index as f64 / (config.spectral_points - 1) as f64
+        };
+        let energy = config.energy_start + config.energy_step * index as f64;
+        let channel_f = channel as f64;
+        let oscillation =
+            (energy * 0.015 + 0.25 * channel_f + self.control.mphase as f64 * 0.1).sin();
+        let attenuation = (-config.damping * (1.0 + 0.03 * channel_f) * index as f64).exp();
+        config.base_phase
+            + config.phase_scale * (1.0 + 0.1 * channel_f) * oscillation * attenuation
+            + config.screening_shift * (1.0 - t)
+            + 0.001 * self.control.ispec as f64
     }
 
     fn render_xsect(&self) -> String {
@@ -222,36 +394,122 @@ impl XsphModel {
                 "absent"
             }
         ));
+        if !self.global.polarization.is_isotropic() {
+            lines.push(format!("# {}", self.polarization_summary(&config)));
+        }
         lines.push("# energy(eV) xsnorm xsect imag_part".to_string());
 
-        for index in 0..config.spectral_points {
-            let t = if config.spectral_points == 1 {
-                0.0
-            } else {
-                index as f64 / (config.spectral_points - 1) as f64
-            };
-            let energy = config.energy_start + config.energy_step * index as f64;
-            let oscillation = (energy * 0.012 + config.base_phase).cos();
-            let envelope = (-config.damping * index as f64).exp();
-
-            let xsnorm = (config.xsnorm * (1.0 + 0.25 * t)).max(1.0e-12);
-            let xsect = (xsnorm
-                * (1.0 + 0.05 * config.phase_channels as f64 * oscillation.abs())
-                * (1.0 + config.screening_shift.abs() * 50.0)
-                * envelope)
-                .max(1.0e-12);
-            let imag_part =
-                xsect * (0.30 + 0.05 * oscillation) + config.screening_shift * 1.0e-3 * (0.5 - t);
+        lines.extend(map_indexed(
+            self.execution_mode,
+            config.spectral_points,
+            |index| {
+                let t = if config.spectral_points == 1 {
+                    0.0
+                } else {
+                    index as f64 / (config.spectral_points - 1) as f64
+                };
+                let energy = config.energy_start + config.energy_step * index as f64;
+                let oscillation = (energy * 0.012 + config.base_phase).cos();
+                let envelope = (-config.damping * index as f64).exp();
 
+                let xsnorm = (config.xsnorm * (1.0 + 0.25 * t)).max(1.0e-12);
+                let xsect = (xsnorm
+                    * (1.0
+                        + 0.05
+                            * config.phase_channels as f64
+                            * oscillation.abs()
+                            * config.polarization_factor)
+                    * (1.0 + config.screening_shift.abs() * 50.0)
+                    * envelope)
+                    .max(1.0e-12);
+                let imag_part = xsect * (0.30 + 0.05 * oscillation)
+                    + config.screening_shift * 1.0e-3 * (0.5 - t);
+
+                format!(
+                    "{:>16} {:>16} {:>16} {:>16}",
+                    format_scientific_f64(energy),
+                    format_scientific_f64(xsnorm),
+                    format_scientific_f64(xsect),
+                    format_scientific_f64(imag_part)
+                )
+            },
+        ));
+
+        lines.join("\n")
+    }
+
+    fn polarization_summary(&self, config: &XsphOutputConfig) -> String {
+        let polarization = &self.global.polarization;
+        format!(
+            "polarization: ipol={} ispin={} ellipticity={} dipole-factor={} spin-shift={}",
+            polarization.ipol,
+            polarization.ispin,
+            format_fixed_f64(polarization.ellipticity, 10, 5).trim(),
+            format_fixed_f64(config.polarization_factor, 10, 5).trim(),
+            format_fixed_f64(config.spin_shift, 10, 5).trim()
+        )
+    }
+
+    fn render_nrixs(&self) -> String {
+        let config = self.output_config();
+        let rows = self.nrixs_rows(&config);
+        let Some(nrixs) = &self.global.nrixs else {
+            return String::new();
+        };
+        let absorber_z = self.absorber_z();
+        let mut lines = Vec::with_capacity(rows.len() + self.nrixs_k_grid().len() + 8);
+
+        lines.push("# XSPH true-compute NRIXS multipole matrix elements".to_string());
+        lines.push(format!("# fixture: {}", self.fixture_id));
+        lines.push(format!(
+            "# absorber_z: {} z_eff: {}",
+            absorber_z as i32,
+            format_fixed_f64(k_shell_effective_charge(absorber_z), 10, 5).trim()
+        ));
+        lines.push(format!(
+            "# ljmax: {} ldecmx: {} spherical_average: {}",
+            nrixs.ljmax,
+            nrixs.ldecmx,
+            if nrixs.spherical_average { "T" } else { "F" }
+        ));
+        lines.push("# q rows: q iq qx qy qz q weight".to_string());
+        for (index, row) in rows.iter().enumerate() {
             lines.push(format!(
-                "{:>16} {:>16} {:>16} {:>16}",
-                format_scientific_f64(energy),
-                format_scientific_f64(xsnorm),
-                format_scientific_f64(xsect),
-                format_scientific_f64(imag_part)
+                "q {:>4} {} {} {} {} {}",
+                index + 1,
+                format_fixed_f64(row.q.qx, 10, 5),
+                format_fixed_f64(row.q.qy, 10, 5),
+                format_fixed_f64(row.q.qz, 10, 5),
+                format_fixed_f64(row.q.norm, 10, 5),
+                format_fixed_f64(row.q.weight, 10, 5),
             ));
         }
 
+        let mut columns = "# rkk rows: rkk energy k".to_string();
+        for iq in 1..=rows.len() {
+            for l in 0..=nrixs.ljmax {
+                columns.push_str(&format!(" re_q{iq}_l{l} im_q{iq}_l{l}"));
+            }
+        }
+        lines.push(columns);
+        for (index, k) in self.nrixs_k_grid().into_iter().enumerate() {
+            let mut line = format!(
+                "rkk {:>16} {:>16}",
+                format_scientific_f64(kinetic_energy(k)),
+                format_scientific_f64(k)
+            );
+            for row in &rows {
+                for channel in &row.rkk {
+                    line.push_str(&format!(
+                        " {:>16} {:>16}",
+                        format_scientific_f64(channel[index].re),
+                        format_scientific_f64(channel[index].im)
+                    ));
+                }
+            }
+            lines.push(line);
+        }
+
         lines.join("\n")
     }
 
@@ -263,7 +521,7 @@ impl XsphModel {
             "absent"
         };
 
-        format!(
+        let mut log = format!(
             "\
 XSPH true-compute runtime\n\
 fixture: {}\n\
@@ -294,6 +552,23 @@ xsnorm-base: {}\n\
             format_fixed_f64(config.energy_start, 12, 5),
             format_fixed_f64(config.energy_step, 12, 5),
             format_scientific_f64(config.xsnorm),
-        )
+        );
+        if !self.global.polarization.is_isotropic() {
+            log.push_str(&self.polarization_summary(&config));
+            log.push('\n');
+        }
+        for (index, row) in self.nrixs_rows(&config).iter().enumerate() {
+            log.push_str(&format!(
+                "nrixs-q{}: q={} peak-strength={}\n",
+                index + 1,
+                format_fixed_f64(row.q.norm, 10, 5).trim(),
+                format_scientific_f64(row.peak_strength())
+            ));
+        }
+        log
     }
 }
+
+fn phase_row_len(config: &XsphOutputConfig) -> usize {
+    (config.phase_channels + 1) * std::mem::size_of::<f64>()
+}
@@ -0,0 +1,221 @@
+use super::parser::NrixsQInput;
+use num_complex::Complex64;
+use std::f64::consts::PI;
+
+const BOHR_RADIUS_ANGSTROM: f64 = 0.529_177_210_903;
+const SLATER_1S_SCREENING: f64 = 0.3125;
+const KINETIC_EV_ANGSTROM_SQ: f64 = 3.81;
+const RADIAL_GRID_POINTS: usize = 800;
+const RADIAL_EXTENT_1S: f64 = 24.0;
+
+/// Multipole matrix elements for one momentum transfer, `rkk[l][ik]` on the
+/// XSPH photoelectron k grid.
+#[derive(Debug, Clone)]
+pub(super) struct NrixsMatrixRow {
+    pub(super) q: NrixsQInput,
+    pub(super) rkk: Vec<Vec<Complex64>>,
+}
+
+impl NrixsMatrixRow {
+    /// Largest total strength `sum_l |rkk_l|^2` over the energy grid.
+    pub(super) fn peak_strength(&self) -> f64 {
+        let points = self.rkk.iter().map(Vec::len).min().unwrap_or(0);
+        (0..points)
+            .map(|ie| self.rkk.iter().map(|channel| channel[ie].norm_sqr()).sum())
+            .fold(0.0, f64::max)
+    }
+}
+
+/// Effective 1s charge seen by the K-shell electron of element `z`.
+pub(super) fn k_shell_effective_charge(z: f64) -> f64 {
+    (z - SLATER_1S_SCREENING).max(1.0 - SLATER_1S_SCREENING)
+}
+
+/// Photoelectron kinetic energy (eV) for a wave number in 1/Angstrom.
+pub(super) fn kinetic_energy(k: f64) -> f64 {
+    k * k * KINETIC_EV_ANGSTROM_SQ
+}
+
+/// Radial grid over the K-shell orbital, holding the Simpson-weighted
+/// `u_1s(r) r^2 dr` so each matrix element is a single dot product.
+#[derive(Debug, Clone)]
+pub(super) struct KShellRadialGrid {
+    radii: Vec<f64>,
+    weighted_orbital: Vec<f64>,
+}
+
+impl KShellRadialGrid {
+    pub(super) fn new(z_eff: f64) -> Self {
+        let step = RADIAL_EXTENT_1S / z_eff / RADIAL_GRID_POINTS as f64;
+        let radii: Vec<f64> = (0..=RADIAL_GRID_POINTS)
+            .map(|index| index as f64 * step)
+            .collect();
+        let simpson: Vec<f64> = (0..=RADIAL_GRID_POINTS)
+            .map(|index| {
+                let factor = if index == 0 || index == RADIAL_GRID_POINTS {
+                    1.0
+                } else if index % 2 == 1 {
+                    4.0
+                } else {
+                    2.0
+                };
+                factor * step / 3.0
+            })
+            .collect();
+        // Normalised hydrogenic 1s: 2 z^(3/2) exp(-z r).
+        let norm = 2.0 * z_eff.powf(1.5);
+        let weighted_orbital = radii
+            .iter()
+            .zip(&simpson)
+            .map(|(r, weight)| norm * (-z_eff * r).exp() * r * r * weight)
+            .collect();
+        Self {
+            radii,
+            weighted_orbital,
+        }
+    }
+
+    /// The `j_l(qr)` operator of each channel on the grid. The monopole uses
+    /// `j_0(qr) - 1` so the free `s` wave, which is not orthogonal to the 1s
+    /// orbital, picks up no elastic overlap.
+    pub(super) fn operator_profiles(&self, q_inverse_angstrom: f64, ljmax: usize) -> Vec<Vec<f64>> {
+        let q = q_inverse_angstrom.abs() * BOHR_RADIUS_ANGSTROM;
+        (0..=ljmax)
+            .map(|l| {
+                self.radii
+                    .iter()
+                    .map(|r| {
+                        let bessel = spherical_bessel_j(l, q * r);
+                        if l == 0 { bessel - 1.0 } else { bessel }
+                    })
+                    .zip(&self.weighted_orbital)
+                    .map(|(bessel, weight)| bessel * weight)
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Radial integrals `sqrt(2l+1) <1s| j_l(qr) |k l>` against the
+    /// energy-normalised free wave `sqrt(2k/pi) j_l(kr)` of the photoelectron.
+    pub(super) fn multipole_integrals(
+        &self,
+        profiles: &[Vec<f64>],
+        k_inverse_angstrom: f64,
+    ) -> Vec<f64> {
+        let k = k_inverse_angstrom.max(0.0) * BOHR_RADIUS_ANGSTROM;
+        let normalization = (2.0 * k / PI).sqrt();
+        profiles
+            .iter()
+            .enumerate()
+            .map(|(l, profile)| {
+                let overlap: f64 = self
+                    .radii
+                    .iter()
+                    .zip(profile)
+                    .map(|(r, weighted)| weighted * spherical_bessel_j(l, k * r))
+                    .sum();
+                ((2 * l + 1) as f64).sqrt() * normalization * overlap
+            })
+            .collect()
+    }
+}
+
+/// Spherical Bessel function `j_l(x)`; power series below `x = l + 1` and
+/// upward recurrence above it, where the recurrence is stable.
+pub(super) fn spherical_bessel_j(l: usize, x: f64) -> f64 {
+    if x.abs() <= l as f64 + 1.0 {
+        let mut prefactor = 1.0;
+        for index in 0..l {
+            prefactor *= x / (2 * index + 3) as f64;
+        }
+        let mut term = 1.0;
+        let mut sum = 1.0;
+        for k in 1..40 {
+            term *= -0.5 * x * x / (k as f64 * (2 * l + 2 * k + 1) as f64);
+            sum += term;
+            if term.abs() < 1.0e-17 * sum.abs() {
+                break;
+            }
+        }
+        return prefactor * sum;
+    }
+
+    let mut previous = x.sin() / x;
+    if l == 0 {
+        return previous;
+    }
+    let mut current = x.sin() / (x * x) - x.cos() / x;
+    for order in 1..l {
+        let next = (2 * order + 1) as f64 / x * current - previous;
+        previous = current;
+        current = next;
+    }
+    current
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{KShellRadialGrid, k_shell_effective_charge, spherical_bessel_j};
+
+    #[test]
+    fn spherical_bessel_matches_closed_forms_on_both_branches() {
+        for x in [0.3_f64, 1.7, 2.5, 6.0, 14.0] {
+            let j0 = x.sin() / x;
+            let j1 = x.sin() / (x * x) - x.cos() / x;
+            let j2 = (3.0 / (x * x) - 1.0) * x.sin() / x - 3.0 * x.cos() / (x * x);
+            assert!((spherical_bessel_j(0, x) - j0).abs() < 1.0e-12);
+            assert!((spherical_bessel_j(1, x) - j1).abs() < 1.0e-12);
+            assert!((spherical_bessel_j(2, x) - j2).abs() < 1.0e-12);
+        }
+        assert_eq!(spherical_bessel_j(0, 0.0), 1.0);
+        assert_eq!(spherical_bessel_j(3, 0.0), 0.0);
+    }
+
+    #[test]
+    fn radial_grid_holds_a_normalised_k_shell_orbital() {
+        let z_eff = k_shell_effective_charge(6.0);
+        let grid = KShellRadialGrid::new(z_eff);
+        let norm: f64 = grid
+            .radii
+            .iter()
+            .zip(&grid.weighted_orbital)
+            .map(|(r, weighted)| weighted * 2.0 * z_eff.powf(1.5) * (-z_eff * r).exp())
+            .sum();
+        assert!((norm - 1.0).abs() < 1.0e-6, "norm={norm}");
+    }
+
+    #[test]
+    fn dipole_dominates_at_low_q_and_higher_multipoles_grow_with_q() {
+        let grid = KShellRadialGrid::new(k_shell_effective_charge(6.0));
+        let strengths = |q: f64| -> Vec<f64> {
+            let profiles = grid.operator_profiles(q, 3);
+            grid.multipole_integrals(&profiles, 4.0)
+                .iter()
+                .map(|value| value * value)
+                .collect()
+        };
+        let low = strengths(1.0);
+        let high = strengths(9.0);
+
+        assert_eq!(low.len(), 4);
+        assert!(low[1] > low[0] && low[1] > low[2] && low[1] > low[3]);
+        let low_dipole_fraction = low[1] / low.iter().sum::<f64>();
+        let high_dipole_fraction = high[1] / high.iter().sum::<f64>();
+        assert!(low_dipole_fraction > 0.9, "fraction={low_dipole_fraction}");
+        assert!(high_dipole_fraction < low_dipole_fraction);
+        assert!(high[2] / high[1] > low[2] / low[1]);
+    }
+
+    #[test]
+    fn dipole_strength_scales_as_q_squared_and_vanishes_at_threshold() {
+        let grid = KShellRadialGrid::new(k_shell_effective_charge(8.0));
+        let dipole = |q: f64, k: f64| grid.multipole_integrals(&grid.operator_profiles(q, 1), k)[1];
+
+        let ratio = (dipole(0.10, 3.0) / dipole(0.05, 3.0)).powi(2);
+        assert!((ratio - 4.0).abs() < 1.0e-2, "ratio={ratio}");
+        assert_eq!(dipole(1.0, 0.0), 0.0);
+
+        let monopole = grid.multipole_integrals(&grid.operator_profiles(0.05, 1), 3.0)[0];
+        assert!(monopole.abs() < dipole(0.05, 3.0).abs() * 1.0e-1);
+    }
+}
@@ -1,7 +1,9 @@
 use super::{POT_CONTROL_F64_COUNT, POT_CONTROL_I32_COUNT, XSPH_REQUIRED_INPUTS};
-use crate::domain::{ComputeArtifact, ComputeModule, ComputeRequest, ComputeResult, FeffError};
+use crate::domain::{
+    ArtifactStore, ComputeArtifact, ComputeModule, ComputeRequest, ComputeResult, FeffError,
+};
+use crate::modules::polarization::PolarizationSettings;
 use crate::modules::pot::POT_BINARY_MAGIC;
-use std::fs;
 use std::path::{Path, PathBuf};
 
 #[derive(Debug, Clone, Copy)]
@@ -17,7 +19,7 @@ pub(super) struct XsphControlInput {
     pub(super) xkmax: f64,
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub(super) struct GeomXsphInput {
     pub(super) nat: usize,
     pub(super) nph: usize,
@@ -26,14 +28,34 @@ pub(super) struct GeomXsphInput {
     pub(super) radius_rms: f64,
     pub(super) radius_max: f64,
     pub(super) ipot_mean: f64,
+    pub(super) neighbor_vectors: Vec<[f64; 3]>,
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub(super) struct GlobalXsphInput {
     pub(super) token_count: usize,
     pub(super) mean: f64,
     pub(super) rms: f64,
     pub(super) max_abs: f64,
+    pub(super) nrixs: Option<NrixsGlobalInput>,
+    pub(super) polarization: PolarizationSettings,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(super) struct NrixsQInput {
+    pub(super) qx: f64,
+    pub(super) qy: f64,
+    pub(super) qz: f64,
+    pub(super) norm: f64,
+    pub(super) weight: f64,
+}
+
+#[derive(Debug, Clone)]
+pub(super) struct NrixsGlobalInput {
+    pub(super) ldecmx: i32,
+    pub(super) ljmax: usize,
+    pub(super) spherical_average: bool,
+    pub(super) q_vectors: Vec<NrixsQInput>,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -47,6 +69,7 @@ pub(super) struct PotXsphInput {
     pub(super) radius_rms: f64,
     pub(super) radius_max: f64,
     pub(super) charge_scale: f64,
+    pub(super) absorber_z: Option<i32>,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -104,8 +127,12 @@ pub(super) fn input_parent_dir(request: &ComputeRequest) -> ComputeResult<&Path>
     })
 }
 
-pub(super) fn read_input_source(path: &Path, artifact_name: &str) -> ComputeResult<String> {
-    fs::read_to_string(path).map_err(|source| {
+pub(super) fn read_input_source(
+    store: &dyn ArtifactStore,
+    path: &Path,
+    artifact_name: &str,
+) -> ComputeResult<String> {
+    store.read_to_string(path).map_err(|source| {
         FeffError::io_system(
             "IO.XSPH_INPUT_READ",
             format!(
@@ -118,8 +145,12 @@ pub(super) fn read_input_source(path: &Path, artifact_name: &str) -> ComputeResu
     })
 }
 
-pub(super) fn read_input_bytes(path: &Path, artifact_name: &str) -> ComputeResult<Vec<u8>> {
-    fs::read(path).map_err(|source| {
+pub(super) fn read_input_bytes(
+    store: &dyn ArtifactStore,
+    path: &Path,
+    artifact_name: &str,
+) -> ComputeResult<Vec<u8>> {
+    store.read(path).map_err(|source| {
         FeffError::io_system(
             "IO.XSPH_INPUT_READ",
             format!(
@@ -133,11 +164,12 @@ pub(super) fn read_input_bytes(path: &Path, artifact_name: &str) -> ComputeResul
 }
 
 pub(super) fn maybe_read_optional_input_source(
+    store: &dyn ArtifactStore,
     path: PathBuf,
     artifact_name: &str,
 ) -> ComputeResult<Option<String>> {
-    if path.is_file() {
-        return read_input_source(&path, artifact_name).map(Some);
+    if store.is_file(&path) {
+        return read_input_source(store, &path, artifact_name).map(Some);
     }
 
     Ok(None)
@@ -222,6 +254,7 @@ pub(super) fn parse_geom_source(fixture_id: &str, source: &str) -> ComputeResult
     let mut radius_sq_sum = 0.0_f64;
     let mut radius_max = 0.0_f64;
     let mut ipot_sum = 0.0_f64;
+    let mut sites = Vec::new();
 
     for line in source.lines() {
         let trimmed = line.trim();
@@ -252,6 +285,7 @@ pub(super) fn parse_geom_source(fixture_id: &str, source: &str) -> ComputeResult
             radius_max = radius_max.max(radius);
             ipot_sum += ipot as f64;
             atom_count += 1;
+            sites.push(([x, y, z], ipot));
         }
     }
 
@@ -265,6 +299,16 @@ pub(super) fn parse_geom_source(fixture_id: &str, source: &str) -> ComputeResult
     let nat_value = nat.unwrap_or(atom_count).max(atom_count);
     let nph_value = nph.unwrap_or(1).max(1);
     let atom_count_f64 = atom_count as f64;
+    let absorber = sites
+        .iter()
+        .find(|(_, ipot)| *ipot == 0)
+        .unwrap_or(&sites[0])
+        .0;
+    let neighbor_vectors = sites
+        .iter()
+        .map(|(site, _)| std::array::from_fn(|axis| site[axis] - absorber[axis]))
+        .filter(|vector: &[f64; 3]| vector.iter().any(|value| value.abs() > 1.0e-10))
+        .collect();
 
     Ok(GeomXsphInput {
         nat: nat_value,
@@ -274,6 +318,7 @@ pub(super) fn parse_geom_source(fixture_id: &str, source: &str) -> ComputeResult
         radius_rms: (radius_sq_sum / atom_count_f64).sqrt(),
         radius_max,
         ipot_mean: ipot_sum / atom_count_f64,
+        neighbor_vectors,
     })
 }
 
@@ -306,9 +351,100 @@ pub(super) fn parse_global_source(
         mean: sum / token_count as f64,
         rms: (sum_sq / token_count as f64).sqrt(),
         max_abs,
+        nrixs: parse_global_nrixs(fixture_id, source)?,
+        polarization: PolarizationSettings::from_global_inp(source),
     })
 }
 
+fn parse_global_nrixs(fixture_id: &str, source: &str) -> ComputeResult<Option<NrixsGlobalInput>> {
+    let lines: Vec<&str> = source.lines().collect();
+
+    let Some(flags_header) = lines
+        .iter()
+        .position(|line| line.to_ascii_lowercase().contains("do_nrixs"))
+    else {
+        return Ok(None);
+    };
+    let Some((_, flags_line)) = next_nonempty_line(&lines, flags_header + 1) else {
+        return Ok(None);
+    };
+    let flags = parse_numeric_tokens(flags_line);
+    if flags.len() < 9 || f64_to_i32(flags[6], fixture_id, "global.inp do_nrixs")? != 1 {
+        return Ok(None);
+    }
+    let ldecmx = f64_to_i32(flags[7], fixture_id, "global.inp ldecmx")?;
+    let ljmax = f64_to_usize(flags[8], fixture_id, "global.inp lj")?;
+
+    let nq_header = lines
+        .iter()
+        .position(|line| line.trim_start().to_ascii_lowercase().starts_with("nq,"))
+        .ok_or_else(|| {
+            xsph_parse_error(fixture_id, "global.inp enables NRIXS but has no nq row")
+        })?;
+    let (_, nq_line) = next_nonempty_line(&lines, nq_header + 1)
+        .ok_or_else(|| xsph_parse_error(fixture_id, "global.inp missing NRIXS nq values row"))?;
+    let nq_tokens: Vec<&str> = nq_line.split_whitespace().collect();
+    let nq = nq_tokens
+        .first()
+        .and_then(|token| parse_numeric_token(token))
+        .ok_or_else(|| xsph_parse_error(fixture_id, "global.inp NRIXS nq value is not numeric"))?;
+    let nq = f64_to_usize(nq, fixture_id, "global.inp nq")?;
+    if nq == 0 {
+        return Err(xsph_parse_error(
+            fixture_id,
+            "global.inp enables NRIXS but lists no q-vectors",
+        ));
+    }
+    let spherical_average = nq_tokens
+        .get(2)
+        .is_some_and(|token| token.eq_ignore_ascii_case("T"));
+
+    let q_header = lines
+        .iter()
+        .position(|line| line.to_ascii_lowercase().contains("q-vectors"))
+        .ok_or_else(|| xsph_parse_error(fixture_id, "global.inp missing NRIXS q-vectors header"))?;
+    let mut q_vectors = Vec::with_capacity(nq);
+    for line in lines.iter().skip(q_header + 1) {
+        if q_vectors.len() == nq {
+            break;
+        }
+        let values = parse_numeric_tokens(line);
+        if values.is_empty() {
+            continue;
+        }
+        if values.len() < 5 {
+            return Err(xsph_parse_error(
+                fixture_id,
+                "global.inp NRIXS q-vector rows need qx, qy, qz, q and weight",
+            ));
+        }
+        q_vectors.push(NrixsQInput {
+            qx: values[0],
+            qy: values[1],
+            qz: values[2],
+            norm: values[3].abs(),
+            weight: values[4],
+        });
+    }
+    if q_vectors.len() != nq {
+        return Err(xsph_parse_error(
+            fixture_id,
+            format!(
+                "global.inp declares {} NRIXS q-vectors but lists {}",
+                nq,
+                q_vectors.len()
+            ),
+        ));
+    }
+
+    Ok(Some(NrixsGlobalInput {
+        ldecmx,
+        ljmax,
+        spherical_average,
+        q_vectors,
+    }))
+}
+
 pub(super) fn parse_pot_source(fixture_id: &str, bytes: &[u8]) -> ComputeResult<PotXsphInput> {
     if bytes.is_empty() {
         return Err(xsph_parse_error(fixture_id, "pot.bin is empty"));
@@ -338,6 +474,7 @@ pub(super) fn parse_pot_source(fixture_id: &str, bytes: &[u8]) -> ComputeResult<
         radius_rms,
         radius_max,
         charge_scale,
+        absorber_z: None,
     })
 }
 
@@ -425,11 +562,12 @@ fn parse_true_compute_pot_binary(fixture_id: &str, bytes: &[u8]) -> ComputeResul
         .ok_or_else(|| xsph_parse_error(fixture_id, "pot.bin missing radius_max metadata"))?;
 
     let mut zeff_sum = 0.0_f64;
+    let mut absorber_z = None;
     let potential_count = npot.max(1);
     for _ in 0..potential_count {
-        let _ = take_u32(bytes, &mut offset)
+        let ipot = take_u32(bytes, &mut offset)
             .ok_or_else(|| xsph_parse_error(fixture_id, "pot.bin missing potential index"))?;
-        let _ = take_i32(bytes, &mut offset).ok_or_else(|| {
+        let atomic_number = take_i32(bytes, &mut offset).ok_or_else(|| {
             xsph_parse_error(fixture_id, "pot.bin missing potential atomic number")
         })?;
         let _ = take_i32(bytes, &mut offset)
@@ -450,6 +588,9 @@ fn parse_true_compute_pot_binary(fixture_id: &str, bytes: &[u8]) -> ComputeResul
         let _ = take_f64(bytes, &mut offset)
             .ok_or_else(|| xsph_parse_error(fixture_id, "pot.bin missing potential vxc"))?;
         zeff_sum += zeff.abs();
+        if ipot == 0 {
+            absorber_z = Some(atomic_number);
+        }
     }
 
     Ok(PotXsphInput {
@@ -462,6 +603,7 @@ fn parse_true_compute_pot_binary(fixture_id: &str, bytes: &[u8]) -> ComputeResul
         radius_rms: radius_rms.abs().max(1.0e-6),
         radius_max: radius_max.abs().max(1.0e-6),
         charge_scale: (zeff_sum / npot.max(1) as f64).max(1.0e-6),
+        absorber_z,
     })
 }
 
@@ -561,6 +703,6 @@ fn parse_numeric_token(token: &str) -> Option<f64> {
 fn xsph_parse_error(fixture_id: &str, message: impl Into<String>) -> FeffError {
     FeffError::computation(
         "RUN.XSPH_INPUT_PARSE",
-        format!("fixture '{}': {}", fixture_id, message.into()),
+        format!("run '{}': {}", fixture_id, message.into()),
     )
 }
@@ -1,3 +1,4 @@
+pub mod parallel;
 pub mod sfconv;
 
 pub use sfconv::{
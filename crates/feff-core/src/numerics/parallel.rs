@@ -0,0 +1,120 @@
+use crate::domain::ExecutionMode;
+use std::thread;
+
+/// Evaluates `f(0)..f(count)` and returns the results in index order.
+///
+/// In `Threaded` mode the index range is cut into one contiguous block per
+/// worker and each block runs on a scoped thread. Every index is still
+/// computed by the same `f` with no shared accumulation, so the output is
+/// bit-identical to the serial loop.
+pub fn map_indexed<T, F>(mode: ExecutionMode, count: usize, f: F) -> Vec<T>
+where
+    T: Send,
+    F: Fn(usize) -> T + Sync,
+{
+    let workers = worker_count(mode, count);
+    if workers <= 1 {
+        return (0..count).map(f).collect();
+    }
+
+    let block = count.div_ceil(workers);
+    let f = &f;
+    thread::scope(|scope| {
+        let handles: Vec<_> = (0..count)
+            .step_by(block)
+            .map(|start| {
+                let end = (start + block).min(count);
+                scope.spawn(move || (start..end).map(f).collect::<Vec<T>>())
+            })
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().expect("parallel worker panicked"))
+            .collect()
+    })
+}
+
+/// Fallible `map_indexed`: returns the results in index order, or the error
+/// of the lowest failing index. Each worker stops at its first error, so a
+/// cancelled run does not finish its remaining points.
+pub fn try_map_indexed<T, E, F>(mode: ExecutionMode, count: usize, f: F) -> Result<Vec<T>, E>
+where
+    T: Send,
+    E: Send,
+    F: Fn(usize) -> Result<T, E> + Sync,
+{
+    let workers = worker_count(mode, count);
+    if workers <= 1 {
+        return (0..count).map(f).collect();
+    }
+
+    let block = count.div_ceil(workers);
+    let f = &f;
+    let blocks: Vec<Result<Vec<T>, E>> = thread::scope(|scope| {
+        let handles: Vec<_> = (0..count)
+            .step_by(block)
+            .map(|start| {
+                let end = (start + block).min(count);
+                scope.spawn(move || (start..end).map(f).collect::<Result<Vec<T>, E>>())
+            })
+            .collect();
+        handles
+            .into_iter()
+            .map(|handle| handle.join().expect("parallel worker panicked"))
+            .collect()
+    });
+    let mut values = Vec::with_capacity(count);
+    for block in blocks {
+        values.extend(block?);
+    }
+    Ok(values)
+}
+
+/// Threads to split `count` indices over. Without the `native` feature
+/// (e.g. on wasm32, which cannot spawn threads) every mode runs serially.
+fn worker_count(mode: ExecutionMode, count: usize) -> usize {
+    if cfg!(feature = "native") {
+        mode.workers().min(count)
+    } else {
+        1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{map_indexed, try_map_indexed};
+    use crate::domain::ExecutionMode;
+
+    #[test]
+    fn threaded_results_match_serial_order_and_bits() {
+        let f = |index: usize| ((index as f64) * 0.37).sin().exp() / (1.0 + index as f64);
+        let serial = map_indexed(ExecutionMode::Serial, 1001, f);
+        for workers in [2, 3, 8, 2000] {
+            let threaded = map_indexed(ExecutionMode::Threaded { workers }, 1001, f);
+            assert_eq!(
+                threaded.iter().map(|v| v.to_bits()).collect::<Vec<_>>(),
+                serial.iter().map(|v| v.to_bits()).collect::<Vec<_>>(),
+                "workers={workers}"
+            );
+        }
+        assert!(map_indexed(ExecutionMode::Threaded { workers: 4 }, 0, f).is_empty());
+    }
+
+    #[test]
+    fn fallible_map_reports_the_lowest_failing_index() {
+        let f = |index: usize| {
+            if index % 7 == 5 {
+                Err(index)
+            } else {
+                Ok(index)
+            }
+        };
+        for mode in [
+            ExecutionMode::Serial,
+            ExecutionMode::Threaded { workers: 3 },
+        ] {
+            assert_eq!(try_map_indexed(mode, 40, f), Err(5));
+            assert_eq!(try_map_indexed(mode, 5, f), Ok((0..5).collect::<Vec<_>>()));
+        }
+    }
+}
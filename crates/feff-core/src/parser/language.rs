@@ -0,0 +1,450 @@
+use super::lint::{Diagnostic, Severity, SourceSpan, lint_input_source};
+use super::{lenient_cards, parse_input_deck, strip_inline_comment};
+use crate::domain::{InputCard, InputCardKind};
+
+/// Editor documentation for one card: a summary plus one line per value on
+/// the card line, or per column for cards whose data sits on rows below.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CardDoc {
+    pub keyword: &'static str,
+    pub summary: &'static str,
+    pub arguments: &'static [&'static str],
+    pub row_columns: &'static [&'static str],
+}
+
+const MODULE_SWITCHES: &[&str] = &[
+    "pot: self-consistent potentials",
+    "xsph: phase shifts and cross sections",
+    "fms: full multiple scattering",
+    "paths: path enumeration",
+    "genfmt: scattering amplitudes of each path",
+    "ff2x: spectrum assembly",
+];
+
+const CARD_DOCS: &[CardDoc] = &[
+    CardDoc {
+        keyword: "TITLE",
+        summary: "Title line, copied into the header of every output file.",
+        arguments: &["title text (free form)"],
+        row_columns: &[],
+    },
+    CardDoc {
+        keyword: "EDGE",
+        summary: "Core level that is excited.",
+        arguments: &[
+            "edge: K, L1, L2, L3, M1..M5, N1..N7, ... or NO; numeric aliases 1 (K) .. 40",
+            "S0^2: amplitude reduction factor (default 1.0)",
+        ],
+        row_columns: &[],
+    },
+    CardDoc {
+        keyword: "S02",
+        summary: "Many-body amplitude reduction factor.",
+        arguments: &["S0^2 (default 1.0; 0 estimates it from atomic overlap)"],
+        row_columns: &[],
+    },
+    CardDoc {
+        keyword: "CONTROL",
+        summary: "Run switches for each stage: 1 runs it, 0 reuses previous output.",
+        arguments: MODULE_SWITCHES,
+        row_columns: &[],
+    },
+    CardDoc {
+        keyword: "PRINT",
+        summary: "Diagnostic print level for each stage (0 is the minimum).",
+        arguments: MODULE_SWITCHES,
+        row_columns: &[],
+    },
+    CardDoc {
+        keyword: "EXCHANGE",
+        summary: "Exchange-correlation model of the fine-structure potential.",
+        arguments: &[
+            "ixc: 0 Hedin-Lundqvist, 1 Dirac-Hara, 2 ground state, 3 Dirac-Hara with HL imaginary part, 5 partially nonlocal",
+            "vr0: real energy shift (eV)",
+            "vi0: constant imaginary part, i.e. extra broadening (eV)",
+            "ixc0: model for the background (default 2)",
+        ],
+        row_columns: &[],
+    },
+    CardDoc {
+        keyword: "SCF",
+        summary: "Self-consistent potentials.",
+        arguments: &[
+            "rfms1: cluster radius for self-consistency (Angstrom)",
+            "lfms1: 0 for solids, 1 for molecules",
+            "nscmt: maximum number of iterations (default 30)",
+            "ca: convergence accelerator (default 0.2)",
+            "nmix: iterations before Broyden mixing (default 1)",
+        ],
+        row_columns: &[],
+    },
+    CardDoc {
+        keyword: "FMS",
+        summary: "Full multiple scattering within a sphere around the absorber.",
+        arguments: &[
+            "rfms: FMS cluster radius (Angstrom)",
+            "lfms2: 0 for solids, 1 for molecules",
+            "minv: matrix inversion algorithm (0 LU)",
+            "toler1: convergence tolerance of iterative inversion",
+            "toler2: second tolerance of iterative inversion",
+            "rdirec: radius of direct propagator terms (Angstrom)",
+        ],
+        row_columns: &[],
+    },
+    CardDoc {
+        keyword: "XANES",
+        summary: "Compute the near-edge spectrum.",
+        arguments: &[
+            "xkmax: maximum photoelectron k (1/Angstrom, default 4)",
+            "xkstep: k grid step (1/Angstrom, default 0.07)",
+            "estep: energy step near the edge (eV, default 0.5)",
+        ],
+        row_columns: &[],
+    },
+    CardDoc {
+        keyword: "EXAFS",
+        summary: "Compute the extended fine structure.",
+        arguments: &["xkmax: maximum photoelectron k (1/Angstrom, default 20)"],
+        row_columns: &[],
+    },
+    CardDoc {
+        keyword: "RPATH",
+        summary: "Longest path kept by the path finder.",
+        arguments: &["rpath: maximum half path length (Angstrom)"],
+        row_columns: &[],
+    },
+    CardDoc {
+        keyword: "LDOS",
+        summary: "Angular-momentum projected density of states.",
+        arguments: &[
+            "emin: lowest energy (eV)",
+            "emax: highest energy (eV)",
+            "eimag: broadening (eV)",
+        ],
+        row_columns: &[],
+    },
+    CardDoc {
+        keyword: "DEBYE",
+        summary: "Thermal Debye-Waller factors for every path.",
+        arguments: &[
+            "temperature (K)",
+            "Debye temperature (K)",
+            "idwopt: 0 correlated Debye, 1 equation of motion, 2 recursion, 3 classical",
+        ],
+        row_columns: &[],
+    },
+    CardDoc {
+        keyword: "COREHOLE",
+        summary: "Treatment of the core hole.",
+        arguments: &["treatment: none, FSR (final state rule) or RPA (screened)"],
+        row_columns: &[],
+    },
+    CardDoc {
+        keyword: "CORRECTIONS",
+        summary: "Energy shift and broadening applied to the final spectrum.",
+        arguments: &[
+            "vrcorr: real energy shift (eV)",
+            "vicorr: additional broadening (eV)",
+        ],
+        row_columns: &[],
+    },
+    CardDoc {
+        keyword: "POLARIZATION",
+        summary: "Polarization vector for oriented samples.",
+        arguments: &["x component", "y component", "z component"],
+        row_columns: &[],
+    },
+    CardDoc {
+        keyword: "ELLIPTICITY",
+        summary: "Elliptical polarization; used together with POLARIZATION.",
+        arguments: &[
+            "ellipticity ratio",
+            "beam direction x",
+            "beam direction y",
+            "beam direction z",
+        ],
+        row_columns: &[],
+    },
+    CardDoc {
+        keyword: "SPIN",
+        summary: "Spin-dependent calculation.",
+        arguments: &["ispin: 1 or -1 for spin up or down, 2 for spin-orbit"],
+        row_columns: &[],
+    },
+    CardDoc {
+        keyword: "POTENTIALS",
+        summary: "Unique potentials, one row per potential below the card.",
+        arguments: &[],
+        row_columns: &[
+            "ipot: potential index (0 is the absorber)",
+            "Z: atomic number",
+            "tag: label",
+            "lmax_scf: angular-momentum cutoff for self-consistency",
+            "lmax_fms: angular-momentum cutoff for FMS",
+            "xnatph: stoichiometry",
+            "spinph: spin moment",
+        ],
+    },
+    CardDoc {
+        keyword: "ATOMS",
+        summary: "Cartesian cluster, one atom per row below the card.",
+        arguments: &[],
+        row_columns: &[
+            "x (Angstrom)",
+            "y (Angstrom)",
+            "z (Angstrom)",
+            "ipot: index of a POTENTIALS row",
+            "tag: label",
+            "distance from the absorber (informational)",
+        ],
+    },
+    CardDoc {
+        keyword: "CIF",
+        summary: "Read the structure from a CIF file instead of ATOMS.",
+        arguments: &["path of the CIF file"],
+        row_columns: &[],
+    },
+    CardDoc {
+        keyword: "END",
+        summary: "End of the deck; later lines are ignored.",
+        arguments: &[],
+        row_columns: &[],
+    },
+];
+
+/// Documentation for `keyword` (already canonical, e.g. `POTENTIALS`).
+pub fn card_doc(keyword: &str) -> Option<&'static CardDoc> {
+    let keyword = if keyword == "POTENTIAL" {
+        "POTENTIALS"
+    } else {
+        keyword
+    };
+    CARD_DOCS.iter().find(|doc| doc.keyword == keyword)
+}
+
+/// Lint diagnostics plus the parser's own error when linting has no
+/// diagnostic with the same code.
+pub fn document_diagnostics(source: &str) -> Vec<Diagnostic> {
+    let mut diagnostics = lint_input_source(source);
+    if let Err(error) = parse_input_deck(source)
+        && !diagnostics
+            .iter()
+            .any(|diagnostic| diagnostic.code == error.placeholder())
+    {
+        diagnostics.push(Diagnostic {
+            code: error.placeholder(),
+            severity: Severity::Error,
+            span: SourceSpan {
+                line: 1,
+                column: 1,
+                end_column: 1,
+            },
+            message: error.message().to_string(),
+            fix: "see the FEFF input reference for this card".to_string(),
+        });
+    }
+    diagnostics
+}
+
+/// Keywords to offer at `line`/`column` (both 1-based): every known card
+/// while the cursor is on the first word of a line, nothing elsewhere.
+pub fn keyword_completions(source: &str, line: usize, column: usize) -> Vec<&'static str> {
+    let text = source.lines().nth(line.saturating_sub(1)).unwrap_or("");
+    let before: String = text.chars().take(column.saturating_sub(1)).collect();
+    if strip_inline_comment(&before).len() != before.len()
+        || before.split_whitespace().count() > 1
+        || before.ends_with(char::is_whitespace) && !before.trim().is_empty()
+    {
+        return Vec::new();
+    }
+    let prefix = before.trim().to_ascii_uppercase();
+    InputCardKind::KEYWORDS
+        .iter()
+        .copied()
+        .filter(|keyword| keyword.starts_with(&prefix))
+        .collect()
+}
+
+/// Markdown hover text for the word under `line`/`column`, with its span.
+pub fn hover(source: &str, line: usize, column: usize) -> Option<(String, SourceSpan)> {
+    let (token_index, span) = token_at(source, line, column)?;
+    let (cards, _, _) = lenient_cards(source);
+    if let Some(card) = cards.iter().find(|card| card.source_line == line) {
+        let doc = card_doc(&card.keyword);
+        let text = match (token_index, doc) {
+            (0, Some(doc)) => {
+                let mut text = format!("**{}** — {}", doc.keyword, doc.summary);
+                for (index, argument) in doc.arguments.iter().chain(doc.row_columns).enumerate() {
+                    text.push_str(&format!("\n\n{}. {}", index + 1, argument));
+                }
+                text
+            }
+            (0, None) => format!("**{}**", card.keyword),
+            (index, Some(doc)) => format!(
+                "**{}** argument {}: {}",
+                doc.keyword,
+                index,
+                doc.arguments.get(index - 1)?
+            ),
+            (_, None) => return None,
+        };
+        return Some((text, span));
+    }
+
+    let card = card_owning_row(&cards, line)?;
+    let doc = card_doc(&card.keyword)?;
+    let column_doc = doc.row_columns.get(token_index)?;
+    Some((
+        format!(
+            "**{}** column {}: {}",
+            doc.keyword,
+            token_index + 1,
+            column_doc
+        ),
+        span,
+    ))
+}
+
+/// For the ipot column of an `ATOMS` row, the span of the `POTENTIALS` row
+/// that defines that potential.
+pub fn definition(source: &str, line: usize, column: usize) -> Option<SourceSpan> {
+    let (token_index, _) = token_at(source, line, column)?;
+    if token_index != 3 {
+        return None;
+    }
+    let (cards, _, _) = lenient_cards(source);
+    let atoms = card_owning_row(&cards, line).filter(|card| card.kind == InputCardKind::Atoms)?;
+    let ipot = atoms
+        .continuations
+        .iter()
+        .find(|row| row.source_line == line)?
+        .values
+        .get(3)?
+        .parse::<i64>()
+        .ok()?;
+    let target = cards
+        .iter()
+        .filter(|card| {
+            matches!(
+                card.kind,
+                InputCardKind::Potentials | InputCardKind::Potential
+            )
+        })
+        .flat_map(|card| &card.continuations)
+        .find(|row| {
+            row.values
+                .first()
+                .and_then(|value| value.parse::<i64>().ok())
+                == Some(ipot)
+        })?;
+    let text = source.lines().nth(target.source_line - 1)?;
+    let start = text.len() - text.trim_start().len();
+    let end = start
+        + text[start..]
+            .find(char::is_whitespace)
+            .unwrap_or(text.len() - start);
+    Some(SourceSpan {
+        line: target.source_line,
+        column: text[..start].chars().count() + 1,
+        end_column: text[..end].chars().count() + 1,
+    })
+}
+
+fn card_owning_row(cards: &[InputCard], line: usize) -> Option<&InputCard> {
+    cards
+        .iter()
+        .find(|card| card.continuations.iter().any(|row| row.source_line == line))
+}
+
+/// Index and span of the whitespace-separated word containing the cursor.
+fn token_at(source: &str, line: usize, column: usize) -> Option<(usize, SourceSpan)> {
+    let text = strip_inline_comment(source.lines().nth(line.checked_sub(1)?)?);
+    let chars: Vec<char> = text.chars().collect();
+    let mut index = 0;
+    let mut position = 0;
+    while position < chars.len() {
+        if chars[position].is_whitespace() {
+            position += 1;
+            continue;
+        }
+        let start = position;
+        while position < chars.len() && !chars[position].is_whitespace() {
+            position += 1;
+        }
+        if (start + 1..=position + 1).contains(&column) {
+            return Some((
+                index,
+                SourceSpan {
+                    line,
+                    column: start + 1,
+                    end_column: position + 1,
+                },
+            ));
+        }
+        index += 1;
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{definition, document_diagnostics, hover, keyword_completions};
+    use crate::parser::lint::SourceSpan;
+
+    const DECK: &str = "\
+TITLE Cu
+EDGE K 0.9
+POTENTIALS
+  0 29 Cu
+  1 29 Cu
+ATOMS
+  0.0   0.0 0.0  0 Cu
+  1.805 1.805 0.0  1 Cu
+END
+";
+
+    #[test]
+    fn completions_offer_keywords_only_for_the_first_word() {
+        let completions = keyword_completions("TITLE x\nPO\n", 2, 3);
+        assert!(completions.contains(&"POTENTIALS"));
+        assert!(completions.contains(&"POLARIZATION"));
+        assert!(!completions.contains(&"ATOMS"));
+        assert!(keyword_completions(DECK, 2, 7).is_empty());
+        assert!(keyword_completions("", 1, 1).contains(&"ATOMS"));
+    }
+
+    #[test]
+    fn hover_documents_cards_arguments_and_table_columns() {
+        let (text, span) = hover(DECK, 2, 2).expect("keyword hover");
+        assert!(text.starts_with("**EDGE** — Core level"));
+        assert_eq!(span.end_column, 5);
+
+        let (text, _) = hover(DECK, 2, 8).expect("argument hover");
+        assert!(text.starts_with("**EDGE** argument 2: S0^2"));
+
+        let (text, _) = hover(DECK, 8, 20).expect("column hover");
+        assert_eq!(text, "**ATOMS** column 4: ipot: index of a POTENTIALS row");
+        assert!(hover(DECK, 1, 40).is_none());
+    }
+
+    #[test]
+    fn ipot_column_jumps_to_its_potential_row() {
+        assert_eq!(
+            definition(DECK, 8, 20),
+            Some(SourceSpan {
+                line: 5,
+                column: 3,
+                end_column: 4
+            })
+        );
+        assert_eq!(definition(DECK, 8, 2), None);
+    }
+
+    #[test]
+    fn diagnostics_include_parser_errors_not_covered_by_lint() {
+        assert!(document_diagnostics(DECK).is_empty());
+        let diagnostics = document_diagnostics("* only a comment\n");
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].code, "INPUT.EMPTY_DECK");
+    }
+}
@@ -0,0 +1,767 @@
+use super::{ValidationProfile, determine_validation_profile, lenient_cards, strip_inline_comment};
+use crate::domain::{InputCard, InputCardContinuation, InputCardKind, InputDeck};
+use crate::support::common::edgedb::{edge_energy, is_tabulated_edge};
+use crate::support::common::isedge::canonical_edge_label;
+use crate::support::common::itoken::{canonical_keyword_for_parser, feff_keywords};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Atoms closer than this (Angstrom) overlap in every physical structure;
+/// the shortest real bond, H-H, is 0.74 A.
+pub const MIN_ATOM_SEPARATION: f64 = 0.6;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Error,
+    Warning,
+    Info,
+}
+
+impl Severity {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Error => "error",
+            Self::Warning => "warning",
+            Self::Info => "info",
+        }
+    }
+}
+
+/// A 1-based line with a 1-based, end-exclusive column range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct SourceSpan {
+    pub line: usize,
+    pub column: usize,
+    pub end_column: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    pub code: &'static str,
+    pub severity: Severity,
+    pub span: SourceSpan,
+    pub message: String,
+    pub fix: String,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}: {}[{}]: {}\n  = fix: {}",
+            self.span.line,
+            self.span.column,
+            self.severity.as_str(),
+            self.code,
+            self.message,
+            self.fix
+        )
+    }
+}
+
+/// Checks a deck and returns every problem found, ordered by position.
+///
+/// Unlike [`super::parse_input_deck`], linting does not stop at the first
+/// error: structural checks, cross-references between `POTENTIALS` and
+/// `ATOMS`, cluster geometry and edge/spectroscopy settings all run.
+pub fn lint_input_source(source: &str) -> Vec<Diagnostic> {
+    let mut linter = Linter {
+        lines: source.lines().collect(),
+        diagnostics: Vec::new(),
+    };
+    let (cards, orphans, variable_problems) = lenient_cards(source);
+    for (line, message) in variable_problems {
+        linter.push(
+            "INPUT.VARIABLE",
+            Severity::Error,
+            linter.span(line, usize::MAX),
+            message,
+            "declare the variable with '@set NAME VALUE' above its first use",
+        );
+    }
+    for orphan in &orphans {
+        linter.push(
+            "INPUT.INVALID_CARD",
+            Severity::Error,
+            linter.span(orphan.source_line, 0),
+            format!("'{}' is not a card keyword", orphan.tokens[0]),
+            "start the deck with a card such as TITLE, or comment the line out with '*'",
+        );
+    }
+    let deck = InputDeck { cards };
+    if deck.cards.is_empty() {
+        if orphans.is_empty() {
+            linter.push(
+                "INPUT.EMPTY_DECK",
+                Severity::Error,
+                SourceSpan {
+                    line: 1,
+                    column: 1,
+                    end_column: 1,
+                },
+                "input deck is empty after removing comments and blank lines".to_string(),
+                "add TITLE, POTENTIALS, ATOMS and END cards",
+            );
+        }
+        return linter.finish();
+    }
+
+    linter.unknown_cards(&deck);
+    if determine_validation_profile(&deck) == ValidationProfile::Main {
+        linter.structure(&deck);
+        let potentials = linter.potentials(&deck);
+        let atoms = linter.atoms(&deck, &potentials);
+        linter.geometry(&deck, &atoms);
+        linter.edge(&deck, &potentials);
+        linter.spectroscopy(&deck);
+    }
+    linter.finish()
+}
+
+pub fn has_errors(diagnostics: &[Diagnostic]) -> bool {
+    diagnostics
+        .iter()
+        .any(|diagnostic| diagnostic.severity == Severity::Error)
+}
+
+struct Linter<'a> {
+    lines: Vec<&'a str>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+struct PotentialDefinition {
+    atomic_number: usize,
+    source_line: usize,
+}
+
+struct AtomSite<'a> {
+    position: [f64; 3],
+    ipot: i64,
+    row: &'a InputCardContinuation,
+}
+
+impl Linter<'_> {
+    fn finish(mut self) -> Vec<Diagnostic> {
+        self.diagnostics
+            .sort_by_key(|diagnostic| (diagnostic.span, diagnostic.severity));
+        self.diagnostics
+    }
+
+    fn push(
+        &mut self,
+        code: &'static str,
+        severity: Severity,
+        span: SourceSpan,
+        message: String,
+        fix: impl Into<String>,
+    ) {
+        self.diagnostics.push(Diagnostic {
+            code,
+            severity,
+            span,
+            message,
+            fix: fix.into(),
+        });
+    }
+
+    /// Span of the `index`-th token on `line`, or of the whole line when the
+    /// token does not exist.
+    fn span(&self, line: usize, index: usize) -> SourceSpan {
+        let text = line
+            .checked_sub(1)
+            .and_then(|index| self.lines.get(index))
+            .map_or("", |text| strip_inline_comment(text));
+        let mut tokens = Vec::new();
+        let mut start = None;
+        for (offset, character) in text.char_indices() {
+            match (character.is_whitespace(), start) {
+                (false, None) => start = Some(offset),
+                (true, Some(begin)) => {
+                    tokens.push((begin, offset));
+                    start = None;
+                }
+                _ => {}
+            }
+        }
+        if let Some(begin) = start {
+            tokens.push((begin, text.len()));
+        }
+        let (begin, end) = tokens.get(index).copied().unwrap_or_else(|| {
+            let first = tokens.first().map_or(0, |token| token.0);
+            let last = tokens.last().map_or(0, |token| token.1);
+            (first, last)
+        });
+        SourceSpan {
+            line: line.max(1),
+            column: text[..begin].chars().count() + 1,
+            end_column: text[..end].chars().count() + 1,
+        }
+    }
+
+    /// Span of value `index` of a card (the keyword is token 0).
+    fn card_span(&self, card: &InputCard, value: Option<usize>) -> SourceSpan {
+        self.span(card.source_line, value.map_or(0, |index| index + 1))
+    }
+
+    fn unknown_cards(&mut self, deck: &InputDeck) {
+        for card in &deck.cards {
+            let known = !matches!(card.kind, InputCardKind::Unknown(_))
+                || canonical_keyword_for_parser(&card.keyword).is_some();
+            if known {
+                continue;
+            }
+            let fix = match closest_keyword(&card.keyword) {
+                Some(keyword) => format!("did you mean '{}'?", keyword),
+                None => "remove the card or comment it out with '*'".to_string(),
+            };
+            self.push(
+                "LINT.UNKNOWN_CARD",
+                Severity::Warning,
+                self.card_span(card, None),
+                format!("unknown card '{}' is ignored by FEFF", card.keyword),
+                fix,
+            );
+        }
+    }
+
+    fn structure(&mut self, deck: &InputDeck) {
+        for keyword in ["CONTROL", "PRINT", "END"] {
+            for duplicate in deck
+                .cards
+                .iter()
+                .filter(|card| card.keyword == keyword)
+                .skip(1)
+            {
+                self.push(
+                    "INPUT.DUPLICATE_SINGLETON_CARD",
+                    Severity::Error,
+                    self.card_span(duplicate, None),
+                    format!("card '{}' may only appear once", keyword),
+                    format!("remove this '{}' card", keyword),
+                );
+            }
+        }
+
+        let has = |keywords: &[&str]| {
+            deck.cards
+                .iter()
+                .any(|card| keywords.contains(&card.keyword.as_str()))
+        };
+        let end_line = deck
+            .cards
+            .iter()
+            .find(|card| card.keyword == "END")
+            .map_or(self.lines.len(), |card| card.source_line);
+        for (required, fix) in [
+            (&["TITLE", "CIF"][..], "add a TITLE card"),
+            (
+                &["ATOMS", "CIF"][..],
+                "add an ATOMS card listing the cluster",
+            ),
+            (
+                &["POTENTIALS", "POTENTIAL", "CIF"][..],
+                "add a POTENTIALS card with the absorber as potential 0",
+            ),
+        ] {
+            if !has(required) {
+                self.push(
+                    "INPUT.MISSING_REQUIRED_CARD",
+                    Severity::Error,
+                    self.span(end_line, 0),
+                    format!(
+                        "missing required card: expected one of {}",
+                        required.join(", ")
+                    ),
+                    fix,
+                );
+            }
+        }
+    }
+
+    fn potentials(&mut self, deck: &InputDeck) -> BTreeMap<i64, PotentialDefinition> {
+        let mut potentials = BTreeMap::new();
+        for card in deck.cards.iter().filter(|card| {
+            matches!(
+                card.kind,
+                InputCardKind::Potentials | InputCardKind::Potential
+            )
+        }) {
+            for row in &card.continuations {
+                let ipot = row
+                    .values
+                    .first()
+                    .and_then(|value| value.parse::<i64>().ok());
+                let atomic_number = row
+                    .values
+                    .get(1)
+                    .and_then(|value| value.parse::<usize>().ok());
+                let (Some(ipot), Some(atomic_number)) = (ipot, atomic_number) else {
+                    let column = usize::from(ipot.is_some());
+                    self.push(
+                        "LINT.MALFORMED_ROW",
+                        Severity::Error,
+                        self.span(row.source_line, column),
+                        "POTENTIALS rows need an integer potential index and atomic number"
+                            .to_string(),
+                        "write the row as 'ipot Z tag'",
+                    );
+                    continue;
+                };
+                if let Some(previous) = potentials
+                    .get(&ipot)
+                    .map(|definition: &PotentialDefinition| definition.source_line)
+                {
+                    self.push(
+                        "LINT.DUPLICATE_POTENTIAL",
+                        Severity::Error,
+                        self.span(row.source_line, 0),
+                        format!("potential {} is already defined on line {}", ipot, previous),
+                        "give each POTENTIALS row its own index",
+                    );
+                    continue;
+                }
+                potentials.insert(
+                    ipot,
+                    PotentialDefinition {
+                        atomic_number,
+                        source_line: row.source_line,
+                    },
+                );
+            }
+        }
+        potentials
+    }
+
+    fn atoms<'d>(
+        &mut self,
+        deck: &'d InputDeck,
+        potentials: &BTreeMap<i64, PotentialDefinition>,
+    ) -> Vec<AtomSite<'d>> {
+        let mut atoms = Vec::new();
+        let Some(card) = deck
+            .cards
+            .iter()
+            .find(|card| card.kind == InputCardKind::Atoms)
+        else {
+            return atoms;
+        };
+        for row in &card.continuations {
+            let number = |index: usize| {
+                row.values
+                    .get(index)
+                    .and_then(|value| value.replace(['d', 'D'], "e").parse::<f64>().ok())
+            };
+            let ipot = row
+                .values
+                .get(3)
+                .and_then(|value| value.parse::<i64>().ok());
+            let bad_column = (0..3)
+                .find(|index| number(*index).is_none())
+                .or(ipot.is_none().then_some(3));
+            if let Some(column) = bad_column {
+                self.push(
+                    "LINT.MALFORMED_ROW",
+                    Severity::Error,
+                    self.span(row.source_line, column),
+                    "ATOMS rows need x, y, z coordinates and an integer potential index"
+                        .to_string(),
+                    "write the row as 'x y z ipot tag'",
+                );
+                continue;
+            }
+            let site = AtomSite {
+                position: [
+                    number(0).unwrap_or(0.0),
+                    number(1).unwrap_or(0.0),
+                    number(2).unwrap_or(0.0),
+                ],
+                ipot: ipot.unwrap_or(0),
+                row,
+            };
+            if !potentials.contains_key(&site.ipot) {
+                self.push(
+                    "LINT.UNDEFINED_POTENTIAL",
+                    Severity::Error,
+                    self.span(row.source_line, 3),
+                    format!("potential {} is not defined in POTENTIALS", site.ipot),
+                    format!(
+                        "add a POTENTIALS row for index {} or use one of {}",
+                        site.ipot,
+                        join_indices(potentials.keys())
+                    ),
+                );
+            }
+            atoms.push(site);
+        }
+
+        let absorbers: Vec<&AtomSite> = atoms.iter().filter(|atom| atom.ipot == 0).collect();
+        if absorbers.is_empty() {
+            self.push(
+                "LINT.MISSING_ABSORBER",
+                Severity::Error,
+                self.card_span(card, None),
+                "no atom uses potential 0, so there is no absorber".to_string(),
+                "set the potential index of the absorbing atom to 0",
+            );
+        }
+        for duplicate in absorbers.iter().skip(1) {
+            self.push(
+                "LINT.DUPLICATE_ABSORBER",
+                Severity::Error,
+                self.span(duplicate.row.source_line, 3),
+                format!(
+                    "more than one absorber: line {} already uses potential 0",
+                    absorbers[0].row.source_line
+                ),
+                "give this atom the potential index of its element instead of 0",
+            );
+        }
+
+        for (ipot, definition) in potentials {
+            if *ipot != 0 && !atoms.iter().any(|atom| atom.ipot == *ipot) {
+                self.push(
+                    "LINT.UNUSED_POTENTIAL",
+                    Severity::Warning,
+                    self.span(definition.source_line, 0),
+                    format!("potential {} is not used by any atom", ipot),
+                    "remove the row or assign it to atoms in ATOMS",
+                );
+            }
+        }
+        atoms
+    }
+
+    fn geometry(&mut self, deck: &InputDeck, atoms: &[AtomSite]) {
+        for (index, atom) in atoms.iter().enumerate() {
+            if let Some(neighbor) = atoms[..index]
+                .iter()
+                .find(|other| distance(atom.position, other.position) < MIN_ATOM_SEPARATION)
+            {
+                self.push(
+                    "LINT.ATOMS_TOO_CLOSE",
+                    Severity::Error,
+                    self.span(atom.row.source_line, 0),
+                    format!(
+                        "atom is {:.3} A from the atom on line {} (minimum {} A)",
+                        distance(atom.position, neighbor.position),
+                        neighbor.row.source_line,
+                        MIN_ATOM_SEPARATION
+                    ),
+                    "remove the duplicate row or correct its coordinates",
+                );
+            }
+        }
+
+        if atoms.is_empty() {
+            return;
+        }
+        let center = atoms
+            .iter()
+            .find(|atom| atom.ipot == 0)
+            .map_or([0.0; 3], |atom| atom.position);
+        let cluster_radius = atoms
+            .iter()
+            .map(|atom| distance(atom.position, center))
+            .fold(0.0, f64::max);
+        for card in &deck.cards {
+            let name = match card.kind {
+                InputCardKind::Fms | InputCardKind::Rfms => "FMS",
+                InputCardKind::Scf => "SCF",
+                _ => continue,
+            };
+            let Some(radius) = card
+                .values
+                .first()
+                .and_then(|value| value.replace(['d', 'D'], "e").parse::<f64>().ok())
+            else {
+                continue;
+            };
+            if radius > cluster_radius {
+                self.push(
+                    "LINT.RADIUS_EXCEEDS_CLUSTER",
+                    Severity::Warning,
+                    self.card_span(card, Some(0)),
+                    format!(
+                        "{} radius {} A is larger than the cluster ({:.3} A from the absorber)",
+                        name, radius, cluster_radius
+                    ),
+                    format!(
+                        "extend ATOMS beyond {} A or reduce the radius to {:.2}",
+                        radius, cluster_radius
+                    ),
+                );
+            }
+        }
+    }
+
+    fn edge(&mut self, deck: &InputDeck, potentials: &BTreeMap<i64, PotentialDefinition>) {
+        let absorber_z = potentials
+            .get(&0)
+            .map(|definition| definition.atomic_number);
+        let Some(edge_card) = deck
+            .cards
+            .iter()
+            .find(|card| card.kind == InputCardKind::Edge)
+        else {
+            let has_hole = deck.cards.iter().any(|card| card.keyword == "HOLE");
+            if !has_hole
+                && deck
+                    .cards
+                    .iter()
+                    .any(|card| card.kind == InputCardKind::Atoms)
+            {
+                let title = deck.cards.first().map_or(1, |card| card.source_line);
+                self.push(
+                    "LINT.MISSING_EDGE",
+                    Severity::Warning,
+                    self.span(title, 0),
+                    "no EDGE card; FEFF will compute the K edge".to_string(),
+                    "add 'EDGE K' (or the edge you measured)",
+                );
+            }
+            return;
+        };
+
+        let Some(value) = edge_card.values.first() else {
+            self.push(
+                "LINT.INVALID_EDGE",
+                Severity::Error,
+                self.card_span(edge_card, None),
+                "EDGE needs an edge label".to_string(),
+                "write e.g. 'EDGE K' or 'EDGE L3'",
+            );
+            return;
+        };
+        let Some(label) = canonical_edge_label(value) else {
+            self.push(
+                "LINT.INVALID_EDGE",
+                Severity::Error,
+                self.card_span(edge_card, Some(0)),
+                format!("'{}' is not an edge label", value),
+                "use K, L1-L3, M1-M5, N1-N7, ... or NO",
+            );
+            return;
+        };
+        let Some(z) = absorber_z else {
+            return;
+        };
+        if is_tabulated_edge(label) && edge_energy(z, label).is_none() {
+            let shallowest_available = ["N7", "N6", "N5", "N4", "N3", "N2", "N1", "M5", "M4", "M3"]
+                .into_iter()
+                .chain(["M2", "M1", "L3", "L2", "L1", "K"])
+                .find(|candidate| edge_energy(z, candidate).is_some());
+            let fix = match shallowest_available {
+                Some(candidate) => format!(
+                    "the shallowest tabulated edge of Z={} is {}; choose {} or a deeper edge",
+                    z, candidate, candidate
+                ),
+                None => "check the atomic number of potential 0".to_string(),
+            };
+            self.push(
+                "LINT.EDGE_NOT_BOUND",
+                Severity::Error,
+                self.card_span(edge_card, Some(0)),
+                format!("the absorber (Z={}) has no {} edge", z, label),
+                fix,
+            );
+        }
+    }
+
+    fn spectroscopy(&mut self, deck: &InputDeck) {
+        let k_range_cards: Vec<&InputCard> = deck
+            .cards
+            .iter()
+            .filter(|card| matches!(card.kind, InputCardKind::Xanes | InputCardKind::Exafs))
+            .collect();
+        let has_xanes = k_range_cards
+            .iter()
+            .any(|card| card.kind == InputCardKind::Xanes);
+        let has_exafs = k_range_cards
+            .iter()
+            .any(|card| card.kind == InputCardKind::Exafs);
+        if !(has_xanes && has_exafs) {
+            return;
+        }
+        let (overridden, winner) = (k_range_cards[0], k_range_cards[k_range_cards.len() - 1]);
+        self.push(
+            "LINT.XANES_EXAFS_CONFLICT",
+            Severity::Warning,
+            self.card_span(winner, None),
+            format!(
+                "XANES and EXAFS both set the k range; this {} card overrides the {} card on line {}",
+                winner.keyword, overridden.keyword, overridden.source_line
+            ),
+            "keep only the card for the spectrum you want (run a second deck for the other)",
+        );
+    }
+}
+
+fn distance(left: [f64; 3], right: [f64; 3]) -> f64 {
+    left.iter()
+        .zip(right)
+        .map(|(a, b)| (a - b) * (a - b))
+        .sum::<f64>()
+        .sqrt()
+}
+
+fn join_indices<'a>(indices: impl Iterator<Item = &'a i64>) -> String {
+    indices.map(i64::to_string).collect::<Vec<_>>().join(", ")
+}
+
+fn closest_keyword(keyword: &str) -> Option<&'static str> {
+    feff_keywords()
+        .chain(["END"])
+        .map(|candidate| (edit_distance(keyword, candidate), candidate))
+        .filter(|(distance, _)| *distance <= 2)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+fn edit_distance(left: &str, right: &str) -> usize {
+    let right: Vec<char> = right.chars().collect();
+    let mut previous: Vec<usize> = (0..=right.len()).collect();
+    for (row, left_char) in left.chars().enumerate() {
+        let mut current = vec![row + 1];
+        for (column, right_char) in right.iter().enumerate() {
+            let substitution = previous[column] + usize::from(left_char != *right_char);
+            current.push(
+                substitution
+                    .min(previous[column + 1] + 1)
+                    .min(current[column] + 1),
+            );
+        }
+        previous = current;
+    }
+    previous[right.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Severity, SourceSpan, has_errors, lint_input_source};
+
+    fn codes(source: &str) -> Vec<&'static str> {
+        lint_input_source(source)
+            .into_iter()
+            .map(|diagnostic| diagnostic.code)
+            .collect()
+    }
+
+    const CLEAN: &str = "\
+TITLE Cu
+EDGE K
+FMS 3.0
+POTENTIALS
+  0 29 Cu
+  1 29 Cu
+ATOMS
+  0.0   0.0   0.0  0 Cu
+  1.805 1.805 0.0  1 Cu
+  3.61  0.0   0.0  1 Cu
+END
+";
+
+    #[test]
+    fn clean_decks_have_no_diagnostics() {
+        assert!(lint_input_source(CLEAN).is_empty());
+    }
+
+    #[test]
+    fn every_problem_is_reported_with_its_span() {
+        let source = "\
+TITLE bad
+EDGG K
+EDGE L3
+XANES 4
+EXAFS 14
+FMS 8.0
+POTENTIALS
+  0 3 Li
+  1 8 O
+  2 8 O
+ATOMS
+  0.0 0.0 0.0 0 Li
+  0.1 0.0 0.0 1 O
+  2.0 0.0 0.0 0 O
+  0.0 2.0 0.0 5 O
+END
+";
+        let diagnostics = lint_input_source(source);
+        let codes: Vec<&str> = diagnostics.iter().map(|d| d.code).collect();
+        assert_eq!(
+            codes,
+            vec![
+                "LINT.UNKNOWN_CARD",
+                "LINT.EDGE_NOT_BOUND",
+                "LINT.XANES_EXAFS_CONFLICT",
+                "LINT.RADIUS_EXCEEDS_CLUSTER",
+                "LINT.UNUSED_POTENTIAL",
+                "LINT.ATOMS_TOO_CLOSE",
+                "LINT.DUPLICATE_ABSORBER",
+                "LINT.UNDEFINED_POTENTIAL",
+            ]
+        );
+        assert!(has_errors(&diagnostics));
+
+        let unknown = &diagnostics[0];
+        assert_eq!(unknown.severity, Severity::Warning);
+        assert_eq!(unknown.fix, "did you mean 'EDGE'?");
+        assert_eq!(
+            unknown.span,
+            SourceSpan {
+                line: 2,
+                column: 1,
+                end_column: 5
+            }
+        );
+        assert!(diagnostics[1].message.contains("Z=3"));
+        assert!(diagnostics[1].fix.contains(" K "));
+        assert_eq!(diagnostics[6].span.column, 15);
+        assert_eq!(
+            diagnostics[7].span,
+            SourceSpan {
+                line: 15,
+                column: 15,
+                end_column: 16
+            }
+        );
+    }
+
+    #[test]
+    fn structural_problems_do_not_stop_linting() {
+        let source = "\
+  1.0 2.0 3.0
+EDGE K
+ATOMS
+  0 0 0 1 Cu
+  1 x 0 1
+END
+END
+";
+        assert_eq!(
+            codes(source),
+            vec![
+                "INPUT.INVALID_CARD",
+                "LINT.MISSING_ABSORBER",
+                "LINT.UNDEFINED_POTENTIAL",
+                "LINT.MALFORMED_ROW",
+                "INPUT.MISSING_REQUIRED_CARD",
+                "INPUT.MISSING_REQUIRED_CARD",
+                "INPUT.DUPLICATE_SINGLETON_CARD",
+            ]
+        );
+    }
+
+    #[test]
+    fn diagnostics_render_with_position_and_fix() {
+        let rendered = lint_input_source("TITLE x\nPOTENTIALS\n 0 29 Cu\nATOMS\n 0 0 0 0\nEND\n")
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            rendered,
+            vec![
+                "1:1: warning[LINT.MISSING_EDGE]: no EDGE card; FEFF will compute the K edge\n  = fix: add 'EDGE K' (or the edge you measured)"
+            ]
+        );
+    }
+}
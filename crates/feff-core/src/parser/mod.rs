@@ -1,7 +1,15 @@
+pub mod language;
+pub mod lint;
+pub mod structure;
+pub mod variables;
+pub mod writer;
+
 use crate::domain::{
-    FeffError, InputCard, InputCardContinuation, InputCardKind, InputDeck, ParserResult,
+    FeffError, FeffInput, InputCard, InputCardContinuation, InputCardKind, InputDeck, ParserResult,
 };
 use crate::support::common::itoken::canonical_keyword_for_parser;
+use std::collections::BTreeMap;
+use variables::expand_variables;
 
 const DEFAULT_CONTROL_VALUES: [&str; 6] = ["1", "1", "1", "1", "1", "1"];
 const DEFAULT_PRINT_VALUES: [&str; 6] = ["0", "0", "0", "0", "0", "0"];
@@ -28,9 +36,37 @@ pub fn tokenize_input_deck(source: &str) -> Vec<InputTokenLine> {
         .collect()
 }
 
+/// Parses a deck after expanding `@set` variables (see [`variables`]).
 pub fn parse_input_deck(source: &str) -> ParserResult<InputDeck> {
-    let mut cards = Vec::new();
-    for token_line in tokenize_input_deck(source) {
+    let source = expand_variables(source, &BTreeMap::new())?;
+    let (cards, orphans) = assemble_cards(tokenize_input_deck(&source));
+    if let Some(orphan) = orphans.first() {
+        return Err(FeffError::input_validation(
+            "INPUT.INVALID_CARD",
+            format!(
+                "invalid card keyword '{}' at line {}",
+                orphan.tokens[0], orphan.source_line
+            ),
+        ));
+    }
+
+    validate_input_deck(InputDeck { cards })
+}
+
+/// Cards after a lenient variable expansion, with orphan lines and variable
+/// problems, for tools that report every problem instead of the first.
+fn lenient_cards(source: &str) -> (Vec<InputCard>, Vec<InputTokenLine>, Vec<(usize, String)>) {
+    let (expanded, problems) = variables::expand(source, &BTreeMap::new());
+    let (cards, orphans) = assemble_cards(tokenize_input_deck(&expanded));
+    (cards, orphans, problems)
+}
+
+/// Groups token lines into cards; lines before the first keyword are
+/// returned separately because they belong to no card.
+fn assemble_cards(token_lines: Vec<InputTokenLine>) -> (Vec<InputCard>, Vec<InputTokenLine>) {
+    let mut cards: Vec<InputCard> = Vec::new();
+    let mut orphans = Vec::new();
+    for token_line in token_lines {
         if let Some(last_card) = cards.last_mut()
             && should_attach_named_continuation(last_card, &token_line)
         {
@@ -59,13 +95,8 @@ pub fn parse_input_deck(source: &str) -> ParserResult<InputDeck> {
         }
 
         let Some(last_card) = cards.last_mut() else {
-            return Err(FeffError::input_validation(
-                "INPUT.INVALID_CARD",
-                format!(
-                    "invalid card keyword '{}' at line {}",
-                    first_token, token_line.source_line
-                ),
-            ));
+            orphans.push(token_line);
+            continue;
         };
 
         last_card.continuations.push(InputCardContinuation {
@@ -75,7 +106,12 @@ pub fn parse_input_deck(source: &str) -> ParserResult<InputDeck> {
         });
     }
 
-    validate_input_deck(InputDeck { cards })
+    (cards, orphans)
+}
+
+/// Parses a deck and reads it into the typed [`FeffInput`] model.
+pub fn parse_feff_input(source: &str) -> ParserResult<FeffInput> {
+    FeffInput::from_deck(&parse_input_deck(source)?)
 }
 
 fn validate_input_deck(mut deck: InputDeck) -> ParserResult<InputDeck> {
@@ -505,6 +541,20 @@ mod tests {
         assert_eq!(deck.cards[0].continuations[0].values, vec!["0"]);
     }
 
+    #[test]
+    fn parser_recognizes_nrixs_cards_with_q_continuations() {
+        let deck = parse_input_deck(
+            "TITLE C\nNRIXS 2 0.0 0.0 2.0\n 3.0 4.0 0.0\nLJMAX 4\nLDEC 2\nPOTENTIALS\n0 6 C\nATOMS\n0.0 0.0 0.0 0 C\nEND\n",
+        )
+            .expect("nrixs deck should parse");
+
+        assert_eq!(deck.cards[1].kind, InputCardKind::Nrixs);
+        assert_eq!(deck.cards[1].continuations.len(), 1);
+        assert_eq!(deck.cards[2].kind, InputCardKind::Ljmax);
+        assert_eq!(deck.cards[3].keyword, "LDECMX");
+        assert_eq!(deck.cards[3].kind, InputCardKind::Ldecmx);
+    }
+
     #[test]
     fn tokenizer_emits_normalized_non_comment_lines() {
         let tokens = tokenize_input_deck("TITLE Cu\n* comment\nEDGE K * inline\n");
@@ -0,0 +1,187 @@
+use super::validate_input_deck;
+use crate::domain::{
+    FeffError, InputCard, InputCardContinuation, InputCardKind, InputDeck, ParserResult,
+};
+use crate::support::inpgen::m_pot_generator::{
+    PotGenRule, PotListType, XyzFormat, gen_pot_from_xyz,
+};
+use crate::support::inpgen::structure_files::{
+    ImportedStructure, StructureFormat, StructureImportError,
+};
+use std::fs;
+use std::path::Path;
+
+/// Reads an XYZ, extended-XYZ, POSCAR/CONTCAR or Quantum ESPRESSO file and
+/// builds a deck with `ATOMS` and `POTENTIALS` for a cluster of `radius`
+/// Angstrom around atom `absorber` (1-based, in file order).
+///
+/// `format` overrides detection from the file name.
+pub fn import_structure_deck(
+    path: &Path,
+    format: Option<StructureFormat>,
+    absorber: usize,
+    radius: f64,
+) -> ParserResult<InputDeck> {
+    let format = format
+        .or_else(|| StructureFormat::from_path(path))
+        .ok_or_else(|| {
+            structure_error(
+                path,
+                StructureImportError::UnknownFormat(path.display().to_string()),
+            )
+        })?;
+    let source = fs::read_to_string(path).map_err(|source| {
+        FeffError::io_system(
+            "IO.STRUCTURE_IMPORT_READ",
+            format!("failed to read structure '{}': {}", path.display(), source),
+        )
+    })?;
+    let structure = format
+        .parse(&source)
+        .map_err(|source| structure_error(path, source))?;
+    structure_input_deck(&structure, absorber, radius)
+}
+
+/// Builds a main deck (`TITLE`, `POTENTIALS`, `ATOMS` and default cards)
+/// from an imported structure, with potentials assigned by `gen_pot_from_xyz`.
+pub fn structure_input_deck(
+    structure: &ImportedStructure,
+    absorber: usize,
+    radius: f64,
+) -> ParserResult<InputDeck> {
+    let mut cluster = structure
+        .build_cluster(absorber, radius)
+        .map_err(|source| structure_error(Path::new(&structure.title), source))?;
+    let pot_list = gen_pot_from_xyz(&PotGenRule::default(), 1, &mut cluster).map_err(|source| {
+        FeffError::input_validation(
+            "INPUT.STRUCTURE_IMPORT",
+            format!("structure '{}': {}", structure.title, source),
+        )
+    })?;
+
+    let title = InputCard::new(
+        "TITLE",
+        InputCardKind::Title,
+        structure
+            .title
+            .split_whitespace()
+            .map(str::to_string)
+            .collect(),
+        0,
+    );
+    let [potentials, atoms] = cluster_cards(&cluster, &pot_list, 0);
+    let end = InputCard::new("END", InputCardKind::End, Vec::new(), 0);
+    validate_input_deck(InputDeck {
+        cards: vec![title, potentials, atoms, end],
+    })
+}
+
+/// `POTENTIALS` and `ATOMS` cards for a cluster whose potentials were
+/// assigned by `gen_pot_from_xyz`. Rows carry `source_line` since they have
+/// no line of their own.
+pub fn cluster_cards(
+    cluster: &XyzFormat,
+    pot_list: &PotListType,
+    source_line: usize,
+) -> [InputCard; 2] {
+    let generated_row = |raw: String| InputCardContinuation {
+        source_line,
+        values: raw.split_whitespace().map(str::to_string).collect(),
+        raw,
+    };
+    let mut potentials = InputCard::new(
+        "POTENTIALS",
+        InputCardKind::Potentials,
+        Vec::new(),
+        source_line,
+    );
+    potentials.continuations = pot_list
+        .atomic_numbers
+        .iter()
+        .zip(&pot_list.string_labels)
+        .enumerate()
+        .map(|(ipot, (atomic_number, label))| {
+            generated_row(format!("{:>5}{:>5}   {}", ipot, atomic_number, label))
+        })
+        .collect();
+    let mut atoms = InputCard::new("ATOMS", InputCardKind::Atoms, Vec::new(), source_line);
+    atoms.continuations = cluster
+        .xyz
+        .iter()
+        .zip(&cluster.potential_numeric_labels)
+        .zip(&cluster.potential_string_labels)
+        .map(|((position, ipot), label)| {
+            generated_row(format!(
+                "{:>12.5}{:>12.5}{:>12.5}{:>5}   {}",
+                position[0], position[1], position[2], ipot, label
+            ))
+        })
+        .collect();
+    [potentials, atoms]
+}
+
+fn structure_error(path: &Path, source: StructureImportError) -> FeffError {
+    FeffError::input_validation(
+        "INPUT.STRUCTURE_IMPORT",
+        format!("structure '{}': {}", path.display(), source),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{import_structure_deck, structure_input_deck};
+    use crate::domain::FeffErrorCategory;
+    use crate::support::inpgen::structure_files::ImportedStructure;
+    use std::fs;
+
+    #[test]
+    fn imported_structure_becomes_a_main_deck() {
+        let structure = ImportedStructure::parse_xyz(
+            "3\nwater snapshot\nO 0.0 0.0 0.0\nH 0.76 0.0 0.59\nH -0.76 0.0 0.59\n",
+        )
+        .expect("xyz should parse");
+        let deck = structure_input_deck(&structure, 1, 4.0).expect("deck should build");
+
+        let keywords: Vec<&str> = deck
+            .cards
+            .iter()
+            .map(|card| card.keyword.as_str())
+            .collect();
+        assert_eq!(
+            keywords,
+            vec!["TITLE", "POTENTIALS", "ATOMS", "CONTROL", "PRINT", "END"]
+        );
+        assert_eq!(deck.cards[0].values, vec!["water", "snapshot"]);
+        let potentials = &deck.cards[1].continuations;
+        assert_eq!(potentials.len(), 2);
+        assert_eq!(potentials[0].values, vec!["0", "8", "O_Abs"]);
+        assert_eq!(potentials[1].values, vec!["1", "1", "H"]);
+        let atoms = &deck.cards[2].continuations;
+        assert_eq!(atoms.len(), 3);
+        assert_eq!(atoms[0].values[3], "0");
+        assert_eq!(atoms[1].values[3], "1");
+    }
+
+    #[test]
+    fn structure_files_are_detected_and_read_from_disk() {
+        let temp = tempfile::tempdir().expect("tempdir should be created");
+        let poscar = temp.path().join("POSCAR");
+        fs::write(
+            &poscar,
+            "Cu\n3.615\n1 0 0\n0 1 0\n0 0 1\nCu\n4\nDirect\n0 0 0\n0 .5 .5\n.5 0 .5\n.5 .5 0\n",
+        )
+        .expect("POSCAR should be written");
+        let deck = import_structure_deck(&poscar, None, 1, 2.6).expect("POSCAR should import");
+        let atoms = deck
+            .cards
+            .iter()
+            .find(|card| card.keyword == "ATOMS")
+            .expect("ATOMS card");
+        assert_eq!(atoms.continuations.len(), 13);
+
+        let error = import_structure_deck(&temp.path().join("notes.txt"), None, 1, 2.6)
+            .expect_err("unknown extension should fail");
+        assert_eq!(error.category(), FeffErrorCategory::InputValidationError);
+        assert_eq!(error.placeholder(), "INPUT.STRUCTURE_IMPORT");
+    }
+}
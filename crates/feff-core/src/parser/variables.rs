@@ -0,0 +1,193 @@
+use super::strip_inline_comment;
+use crate::domain::{FeffError, ParserResult};
+use std::collections::BTreeMap;
+
+pub const SET_DIRECTIVE: &str = "@set";
+
+/// Expands deck variables.
+///
+/// `@set NAME VALUE` declares `NAME` for the lines that follow and `${NAME}`
+/// is replaced by its value outside comments. Entries in `overrides` win over
+/// the declared value, which lets one deck serve a whole parameter sweep.
+/// Each `@set` line is rewritten as a comment recording the value in effect,
+/// so line numbers and the expanded text stay valid FEFF input.
+pub fn expand_variables(
+    source: &str,
+    overrides: &BTreeMap<String, String>,
+) -> ParserResult<String> {
+    let (expanded, problems) = expand(source, overrides);
+    match problems.into_iter().next() {
+        Some((_, message)) => Err(FeffError::input_validation("INPUT.VARIABLE", message)),
+        None => Ok(expanded),
+    }
+}
+
+/// Expands what it can, leaving bad references in place, and returns each
+/// problem with its 1-based line.
+pub(super) fn expand(
+    source: &str,
+    overrides: &BTreeMap<String, String>,
+) -> (String, Vec<(usize, String)>) {
+    let mut values: BTreeMap<String, String> = BTreeMap::new();
+    let mut problems = Vec::new();
+    let mut expanded = String::with_capacity(source.len());
+    for (index, line) in source.lines().enumerate() {
+        let source_line = index + 1;
+        let content = strip_inline_comment(line);
+        if let Some(rest) = strip_directive(content.trim_start()) {
+            let (name, value) = rest
+                .trim()
+                .split_once(char::is_whitespace)
+                .map_or((rest.trim(), ""), |(name, value)| (name, value.trim()));
+            if !is_variable_name(name) {
+                problems.push((
+                    source_line,
+                    format!(
+                        "invalid variable name '{}' in @set at line {}",
+                        name, source_line
+                    ),
+                ));
+                expanded.push_str(&format!("* {}", content.trim()));
+            } else {
+                let value = match overrides.get(name) {
+                    Some(value) => value.clone(),
+                    None => substitute(value, &values, source_line, &mut problems),
+                };
+                expanded.push_str(&format!("* {} {} {}", SET_DIRECTIVE, name, value));
+                values.insert(name.to_string(), value);
+            }
+        } else {
+            expanded.push_str(&substitute(content, &values, source_line, &mut problems));
+            expanded.push_str(&line[content.len()..]);
+        }
+        expanded.push('\n');
+    }
+    (expanded, problems)
+}
+
+/// Names declared with `@set`, in declaration order, with their values.
+pub fn declared_variables(source: &str) -> Vec<(String, String)> {
+    let mut declared: Vec<(String, String)> = Vec::new();
+    for line in source.lines() {
+        let Some(rest) = strip_directive(strip_inline_comment(line).trim_start()) else {
+            continue;
+        };
+        let mut parts = rest.trim().splitn(2, char::is_whitespace);
+        let name = parts.next().unwrap_or_default().to_string();
+        let value = parts.next().unwrap_or_default().trim().to_string();
+        match declared.iter_mut().find(|(existing, _)| *existing == name) {
+            Some(entry) => entry.1 = value,
+            None => declared.push((name, value)),
+        }
+    }
+    declared
+}
+
+fn strip_directive(line: &str) -> Option<&str> {
+    let prefix = line.get(..SET_DIRECTIVE.len())?;
+    let rest = &line[SET_DIRECTIVE.len()..];
+    (prefix.eq_ignore_ascii_case(SET_DIRECTIVE)
+        && (rest.is_empty() || rest.starts_with(char::is_whitespace)))
+    .then_some(rest)
+}
+
+fn substitute(
+    text: &str,
+    values: &BTreeMap<String, String>,
+    source_line: usize,
+    problems: &mut Vec<(usize, String)>,
+) -> String {
+    let mut output = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("${") {
+        output.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let Some(end) = after.find('}') else {
+            problems.push((
+                source_line,
+                format!("unterminated '${{' at line {}", source_line),
+            ));
+            output.push_str(&rest[start..]);
+            return output;
+        };
+        let name = &after[..end];
+        match values.get(name) {
+            Some(value) => output.push_str(value),
+            None => {
+                problems.push((
+                    source_line,
+                    format!(
+                        "variable '{}' at line {} is not declared with @set",
+                        name, source_line
+                    ),
+                ));
+                output.push_str(&rest[start..start + end + 3]);
+            }
+        }
+        rest = &after[end + 1..];
+    }
+    output.push_str(rest);
+    output
+}
+
+fn is_variable_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    matches!(chars.next(), Some(ch) if ch.is_ascii_alphabetic() || ch == '_')
+        && chars.all(|ch| ch.is_ascii_alphanumeric() || ch == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{declared_variables, expand_variables};
+    use crate::domain::FeffErrorCategory;
+    use std::collections::BTreeMap;
+
+    const DECK: &str = "\
+@set T 300   * sample temperature
+@set R 6.0
+@set R2 ${R}
+TITLE Cu at ${T} K  * keep ${UNUSED} in comments
+DEBYE ${T} 315
+FMS ${R2}
+";
+
+    #[test]
+    fn set_lines_become_comments_and_references_expand() {
+        let expanded = expand_variables(DECK, &BTreeMap::new()).expect("deck should expand");
+        assert_eq!(
+            expanded,
+            "\
+* @set T 300
+* @set R 6.0
+* @set R2 6.0
+TITLE Cu at 300 K  * keep ${UNUSED} in comments
+DEBYE 300 315
+FMS 6.0
+"
+        );
+    }
+
+    #[test]
+    fn overrides_replace_declared_values() {
+        let overrides = BTreeMap::from([("R".to_string(), "4.5".to_string())]);
+        let expanded = expand_variables(DECK, &overrides).expect("deck should expand");
+        assert!(expanded.contains("FMS 4.5\n"));
+        assert_eq!(
+            declared_variables(DECK),
+            vec![
+                ("T".to_string(), "300".to_string()),
+                ("R".to_string(), "6.0".to_string()),
+                ("R2".to_string(), "${R}".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn undeclared_references_are_input_errors() {
+        let error = expand_variables("TITLE x\nDEBYE ${T} 300\n", &BTreeMap::new())
+            .expect_err("undeclared variable should fail");
+        assert_eq!(error.category(), FeffErrorCategory::InputValidationError);
+        assert_eq!(error.placeholder(), "INPUT.VARIABLE");
+        assert!(error.message().contains("line 2"));
+    }
+}
@@ -0,0 +1,416 @@
+use super::variables::expand_variables;
+use super::{normalize_keyword_token, parse_input_deck, tokenize_line};
+use crate::domain::{InputCard, InputCardContinuation, InputDeck, ParserResult};
+use std::collections::{BTreeMap, BTreeSet};
+
+const TABLE_INDENT: &str = "   ";
+const TABLE_COLUMN_GAP: &str = "   ";
+
+/// Layout choices for [`InputDocument::render_with`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct FormatOptions {
+    /// Spell every keyword in full (`POTE` -> `POTENTIALS`).
+    pub canonical_keywords: bool,
+    /// Align `ATOMS` and `POTENTIALS` rows into columns.
+    pub align_tables: bool,
+    /// Order `ATOMS` rows by distance from the origin.
+    pub sort_atoms: bool,
+}
+
+impl FormatOptions {
+    /// Reproduce the source layout wherever the deck was not edited.
+    pub const PRESERVE: Self = Self {
+        canonical_keywords: false,
+        align_tables: false,
+        sort_atoms: false,
+    };
+
+    /// The `feff10-rs fmt` style.
+    pub const CANONICAL: Self = Self {
+        canonical_keywords: true,
+        align_tables: true,
+        sort_atoms: true,
+    };
+}
+
+/// A parsed deck together with its source text, so that comments, blank
+/// lines and the layout of unedited lines survive a rewrite.
+///
+/// Comment and blank lines stay attached to the card or row that follows
+/// them; edit `deck` freely and render it back with [`InputDocument::render`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InputDocument {
+    pub deck: InputDeck,
+    lines: Vec<String>,
+    /// `lines` after `@set` expansion; used to match lines against the deck
+    /// so that `${NAME}` references survive a rewrite.
+    expanded: Vec<String>,
+}
+
+impl InputDocument {
+    /// Parses `source`, dropping the default `CONTROL`/`PRINT`/`END` cards that
+    /// validation adds so that rendering does not grow the file.
+    pub fn parse(source: &str) -> ParserResult<Self> {
+        let mut deck = parse_input_deck(source)?;
+        deck.cards.retain(|card| card.source_line != 0);
+        Ok(Self {
+            deck,
+            lines: source.lines().map(str::to_string).collect(),
+            expanded: expand_variables(source, &BTreeMap::new())?
+                .lines()
+                .map(str::to_string)
+                .collect(),
+        })
+    }
+
+    /// Wraps a deck built in code; every line is generated.
+    pub fn from_deck(deck: InputDeck) -> Self {
+        Self {
+            deck,
+            lines: Vec::new(),
+            expanded: Vec::new(),
+        }
+    }
+
+    pub fn render(&self) -> String {
+        self.render_with(FormatOptions::PRESERVE)
+    }
+
+    pub fn render_with(&self, options: FormatOptions) -> String {
+        let mut renderer = Renderer {
+            document: self,
+            options,
+            emitted_comments: BTreeSet::new(),
+            output: String::new(),
+        };
+        for card in &self.deck.cards {
+            renderer.card(card);
+        }
+        let last_content_line = self
+            .expanded
+            .iter()
+            .enumerate()
+            .rev()
+            .find(|(index, line)| tokenize_line(index + 1, line).is_some())
+            .map_or(0, |(index, _)| index + 1);
+        for line in self.lines.iter().skip(last_content_line) {
+            renderer.push_line(line);
+        }
+        renderer.output
+    }
+
+    fn source_line(&self, line: usize) -> Option<&str> {
+        line.checked_sub(1)
+            .and_then(|index| self.lines.get(index))
+            .map(String::as_str)
+    }
+
+    /// Comment and blank lines directly above `line`.
+    fn leading_comments(&self, line: usize) -> &[String] {
+        let Some(end) = line.checked_sub(1).filter(|end| *end <= self.lines.len()) else {
+            return &[];
+        };
+        let start = (0..end)
+            .rev()
+            .find(|index| tokenize_line(index + 1, &self.expanded[*index]).is_some())
+            .map_or(0, |index| index + 1);
+        &self.lines[start..end]
+    }
+}
+
+/// Renders a deck that has no source text, e.g. one built by an importer.
+pub fn render_input_deck(deck: &InputDeck) -> String {
+    InputDocument::from_deck(deck.clone()).render()
+}
+
+/// Parses and re-renders `source` with `options`.
+pub fn format_input_source(source: &str, options: FormatOptions) -> ParserResult<String> {
+    Ok(InputDocument::parse(source)?.render_with(options))
+}
+
+struct Renderer<'a> {
+    document: &'a InputDocument,
+    options: FormatOptions,
+    emitted_comments: BTreeSet<usize>,
+    output: String,
+}
+
+impl Renderer<'_> {
+    fn card(&mut self, card: &InputCard) {
+        self.comments_before(card.source_line);
+        let line = match self.unchanged_source(card.source_line, |tokens| {
+            tokens
+                .first()
+                .and_then(|token| normalize_keyword_token(token))
+                .as_deref()
+                == Some(card.keyword.as_str())
+                && tokens[1..] == card.values[..]
+        }) {
+            Some(source) if self.options.canonical_keywords => {
+                respell_first_token(source, &card.keyword)
+            }
+            Some(source) => source.to_string(),
+            None => {
+                let mut generated = card.keyword.clone();
+                for value in &card.values {
+                    generated.push(' ');
+                    generated.push_str(value);
+                }
+                self.with_inline_comment(generated, card.source_line)
+            }
+        };
+        self.push_line(&line);
+
+        let is_table = matches!(card.keyword.as_str(), "ATOMS" | "POTENTIALS" | "POTENTIAL");
+        let mut rows: Vec<&InputCardContinuation> = card.continuations.iter().collect();
+        if self.options.sort_atoms && card.keyword == "ATOMS" {
+            rows.sort_by(|left, right| row_distance(left).total_cmp(&row_distance(right)));
+        }
+        // Unedited rows are aligned from their written tokens so that
+        // `${NAME}` references are kept.
+        let cells: Vec<Vec<String>> = rows
+            .iter()
+            .map(|row| {
+                self.unchanged_source(row.source_line, |tokens| tokens == &row.values[..])
+                    .and_then(|source| tokenize_line(row.source_line, source))
+                    .map_or_else(|| row.values.clone(), |line| line.tokens)
+            })
+            .collect();
+        let widths = (is_table && self.options.align_tables).then(|| column_widths(&cells));
+
+        for (row, cells) in rows.into_iter().zip(&cells) {
+            self.comments_before(row.source_line);
+            let line = match &widths {
+                Some(widths) => {
+                    self.with_inline_comment(aligned_row(cells, widths), row.source_line)
+                }
+                None => match self
+                    .unchanged_source(row.source_line, |tokens| tokens == &row.values[..])
+                {
+                    Some(source) => source.to_string(),
+                    None => self.with_inline_comment(
+                        format!("{}{}", TABLE_INDENT, row.values.join(" ")),
+                        row.source_line,
+                    ),
+                },
+            };
+            self.push_line(&line);
+        }
+    }
+
+    fn comments_before(&mut self, line: usize) {
+        if !self.emitted_comments.insert(line) {
+            return;
+        }
+        for comment in self.document.leading_comments(line) {
+            self.output.push_str(comment);
+            self.output.push('\n');
+        }
+    }
+
+    /// The original text of `line` when its expanded tokens still match the deck.
+    fn unchanged_source(&self, line: usize, matches: impl Fn(&[String]) -> bool) -> Option<&str> {
+        let source = self.document.source_line(line)?;
+        let expanded = self.document.expanded.get(line - 1)?;
+        let tokens = tokenize_line(line, expanded)?.tokens;
+        matches(&tokens).then_some(source)
+    }
+
+    fn with_inline_comment(&self, mut line: String, source_line: usize) -> String {
+        if let Some(comment) = self
+            .document
+            .source_line(source_line)
+            .and_then(|source| source.find('*').map(|start| &source[start..]))
+            .filter(|_| {
+                // Whole-line comments belong to the line, not to a card.
+                self.document
+                    .source_line(source_line)
+                    .and_then(|source| tokenize_line(source_line, source))
+                    .is_some()
+            })
+        {
+            line.push(' ');
+            line.push_str(comment);
+        }
+        line
+    }
+
+    fn push_line(&mut self, line: &str) {
+        self.output.push_str(line);
+        self.output.push('\n');
+    }
+}
+
+/// Replaces the keyword token while keeping indentation, spacing and comments.
+fn respell_first_token(source: &str, keyword: &str) -> String {
+    let start = source.len() - source.trim_start().len();
+    let end = source[start..]
+        .find(char::is_whitespace)
+        .map_or(source.len(), |offset| start + offset);
+    format!("{}{}{}", &source[..start], keyword, &source[end..])
+}
+
+fn row_distance(row: &InputCardContinuation) -> f64 {
+    let coordinate = |index: usize| {
+        row.values
+            .get(index)
+            .and_then(|value| value.replace(['d', 'D'], "e").parse::<f64>().ok())
+    };
+    match (coordinate(0), coordinate(1), coordinate(2)) {
+        (Some(x), Some(y), Some(z)) => (x * x + y * y + z * z).sqrt(),
+        // Rows that are not coordinates keep their place after the atoms.
+        _ => f64::INFINITY,
+    }
+}
+
+fn column_widths(rows: &[Vec<String>]) -> Vec<usize> {
+    let mut widths = Vec::new();
+    for row in rows {
+        for (column, value) in row.iter().enumerate() {
+            if widths.len() <= column {
+                widths.push(0);
+            }
+            widths[column] = widths[column].max(value.len());
+        }
+    }
+    widths
+}
+
+/// Numbers are right-aligned and labels left-aligned within each column.
+fn aligned_row(values: &[String], widths: &[usize]) -> String {
+    let mut line = String::from(TABLE_INDENT);
+    for (column, value) in values.iter().enumerate() {
+        if column > 0 {
+            line.push_str(TABLE_COLUMN_GAP);
+        }
+        let width = widths[column];
+        if value.replace(['d', 'D'], "e").parse::<f64>().is_ok() {
+            line.push_str(&format!("{:>width$}", value));
+        } else if column + 1 < values.len() {
+            line.push_str(&format!("{:<width$}", value));
+        } else {
+            line.push_str(value);
+        }
+    }
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FormatOptions, InputDocument, format_input_source, render_input_deck};
+    use crate::domain::{InputCard, InputCardKind, InputDeck};
+    use crate::parser::parse_input_deck;
+
+    const SOURCE: &str = "\
+* Cu K edge
+TITL  Cu metal   * sample A
+
+edge K
+POTE
+* ipot z tag
+ 0 29 Cu
+ 1 29 Cu
+ATOMS
+  3.61 0.0 0.0 1 Cu
+  0.0 0.0 0.0 0 Cu
+  1.805 1.805 0.0 1 Cu   * nearest
+END
+* trailing note
+";
+
+    #[test]
+    fn unedited_documents_render_byte_for_byte() {
+        let document = InputDocument::parse(SOURCE).expect("deck should parse");
+        assert_eq!(document.render(), SOURCE);
+    }
+
+    #[test]
+    fn edits_keep_comments_and_untouched_lines() {
+        let mut document = InputDocument::parse(SOURCE).expect("deck should parse");
+        document.deck.cards[1].values = vec!["L3".to_string()];
+        document.deck.cards.insert(
+            2,
+            InputCard::new("S02", InputCardKind::S02, vec!["0.9".into()], 0),
+        );
+        document.deck.cards[4].continuations[0].values[0] = "2.56".to_string();
+
+        let rendered = document.render();
+        assert!(
+            rendered.starts_with("* Cu K edge\nTITL  Cu metal   * sample A\n\nEDGE L3\nS02 0.9\n")
+        );
+        assert!(rendered.contains("ATOMS\n   2.56 0.0 0.0 1 Cu\n"));
+        assert!(rendered.contains("  1.805 1.805 0.0 1 Cu   * nearest\n"));
+        assert!(rendered.ends_with("END\n* trailing note\n"));
+    }
+
+    #[test]
+    fn canonical_format_respells_aligns_and_sorts() {
+        let formatted =
+            format_input_source(SOURCE, FormatOptions::CANONICAL).expect("deck should format");
+        let expected = "\
+* Cu K edge
+TITLE  Cu metal   * sample A
+
+EDGE K
+POTENTIALS
+* ipot z tag
+   0   29   Cu
+   1   29   Cu
+ATOMS
+     0.0     0.0   0.0   0   Cu
+   1.805   1.805   0.0   1   Cu * nearest
+    3.61     0.0   0.0   1   Cu
+END
+* trailing note
+";
+        assert_eq!(formatted, expected);
+        assert_eq!(
+            format_input_source(&formatted, FormatOptions::CANONICAL).expect("idempotent"),
+            formatted
+        );
+        assert_eq!(
+            parse_input_deck(&formatted)
+                .expect("formatted deck should parse")
+                .cards
+                .len(),
+            parse_input_deck(SOURCE)
+                .expect("deck should parse")
+                .cards
+                .len()
+        );
+    }
+
+    #[test]
+    fn variable_references_survive_formatting() {
+        let source = "@set R 2.55\nTITLE x\nPOTENTIALS\n 0 29 Cu\nATOMS\n ${R} 0 0 0 Cu\n 0 0 0.5 0 Cu\nEND\n";
+        let formatted =
+            format_input_source(source, FormatOptions::CANONICAL).expect("deck should format");
+        assert_eq!(
+            formatted,
+            "@set R 2.55\nTITLE x\nPOTENTIALS\n   0   29   Cu\nATOMS\n      0   0   0.5   0   Cu\n   ${R}   0     0   0   Cu\nEND\n"
+        );
+    }
+
+    #[test]
+    fn generated_decks_render_every_card() {
+        let mut atoms = InputCard::new("ATOMS", InputCardKind::Atoms, Vec::new(), 0);
+        atoms
+            .continuations
+            .push(crate::domain::InputCardContinuation {
+                source_line: 0,
+                values: vec!["0".into(), "0".into(), "0".into(), "0".into()],
+                raw: String::new(),
+            });
+        let deck = InputDeck {
+            cards: vec![
+                InputCard::new("TITLE", InputCardKind::Title, vec!["x".into()], 0),
+                atoms,
+                InputCard::new("END", InputCardKind::End, Vec::new(), 0),
+            ],
+        };
+        assert_eq!(
+            render_input_deck(&deck),
+            "TITLE x\nATOMS\n   0 0 0 0\nEND\n"
+        );
+    }
+}
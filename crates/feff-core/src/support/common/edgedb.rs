@@ -0,0 +1,227 @@
+use super::isedge::canonical_edge_label;
+
+// Edge order shared by both tables: K, L1-L3, M1-M5, N1-N7.
+const TABULATED_EDGES: [&str; 16] = [
+    "K", "L1", "L2", "L3", "M1", "M2", "M3", "M4", "M5", "N1", "N2", "N3", "N4", "N5", "N6", "N7",
+];
+
+const MAX_TABULATED_Z: usize = 92;
+
+// Core-hole widths (eV) as log-linear interpolation nodes over Z, following
+// FEFF's setgam (Keski-Rahkonen & Krause K/L/M systematics). N-shell nodes are
+// coarse estimates and mainly keep the defaults well ordered.
+const WIDTH_NODE_Z: [[f64; 8]; 16] = [
+    [0.99, 10.0, 20.0, 40.0, 50.0, 60.0, 80.0, 95.1],
+    [0.99, 18.0, 22.0, 35.0, 50.0, 52.0, 75.0, 95.1],
+    [0.99, 17.0, 28.0, 31.0, 45.0, 60.0, 80.0, 95.1],
+    [0.99, 17.0, 28.0, 31.0, 45.0, 60.0, 80.0, 95.1],
+    [0.99, 20.0, 28.0, 30.0, 36.0, 53.0, 80.0, 95.1],
+    [0.99, 20.0, 22.0, 30.0, 40.0, 68.0, 80.0, 95.1],
+    [0.99, 20.0, 22.0, 30.0, 40.0, 68.0, 80.0, 95.1],
+    [0.99, 36.0, 40.0, 48.0, 58.0, 76.0, 79.0, 95.1],
+    [0.99, 36.0, 40.0, 48.0, 58.0, 76.0, 79.0, 95.1],
+    [0.99, 36.0, 40.0, 48.0, 58.0, 71.0, 79.0, 95.1],
+    [0.99, 36.0, 40.0, 48.0, 58.0, 71.0, 79.0, 95.1],
+    [0.99, 36.0, 40.0, 48.0, 58.0, 71.0, 79.0, 95.1],
+    [0.99, 48.0, 54.0, 58.0, 70.0, 79.0, 85.0, 95.1],
+    [0.99, 48.0, 54.0, 58.0, 70.0, 79.0, 85.0, 95.1],
+    [0.99, 58.0, 70.0, 79.0, 85.0, 90.0, 92.0, 95.1],
+    [0.99, 58.0, 70.0, 79.0, 85.0, 90.0, 92.0, 95.1],
+];
+
+const WIDTH_NODE_EV: [[f64; 8]; 16] = [
+    [0.02, 0.28, 0.75, 4.8, 10.5, 21.0, 60.0, 105.0],
+    [0.07, 3.9, 3.8, 7.0, 6.0, 3.7, 8.0, 19.0],
+    [0.001, 0.12, 1.4, 0.8, 2.6, 4.1, 6.3, 10.5],
+    [0.001, 0.12, 0.55, 0.7, 2.1, 3.5, 5.4, 9.0],
+    [0.001, 1.0, 2.9, 2.2, 5.5, 10.0, 22.0, 22.0],
+    [0.001, 0.001, 0.5, 2.0, 2.6, 11.0, 15.0, 16.0],
+    [0.001, 0.001, 0.5, 2.0, 2.6, 11.0, 10.0, 10.0],
+    [0.0006, 0.09, 0.07, 0.48, 1.0, 4.0, 2.7, 4.7],
+    [0.0006, 0.09, 0.07, 0.48, 0.87, 2.2, 2.5, 4.3],
+    [0.001, 1.0, 2.0, 5.0, 7.0, 9.0, 10.0, 13.0],
+    [0.001, 0.5, 1.5, 3.0, 4.0, 5.5, 6.5, 8.0],
+    [0.001, 0.5, 1.5, 3.0, 4.0, 5.5, 6.5, 8.0],
+    [0.0006, 0.2, 0.5, 1.0, 2.0, 3.0, 3.5, 4.0],
+    [0.0006, 0.2, 0.5, 1.0, 2.0, 3.0, 3.5, 4.0],
+    [0.0006, 0.05, 0.1, 0.2, 0.3, 0.4, 0.45, 0.5],
+    [0.0006, 0.05, 0.1, 0.2, 0.3, 0.4, 0.45, 0.5],
+];
+
+// Electron binding energies (eV) relative to the Fermi level, from the X-ray
+// data booklet. Zero marks a level that is not tabulated for that element.
+#[rustfmt::skip]
+const EDGE_ENERGIES_EV: [[f64; 16]; MAX_TABULATED_Z] = [
+    [13.6, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+    [24.6, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+    [54.7, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+    [111.5, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+    [188.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+    [284.2, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+    [409.9, 37.3, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+    [543.1, 41.6, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+    [696.7, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+    [870.2, 48.5, 21.7, 21.6, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+    [1070.8, 63.5, 30.65, 30.81, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+    [1303.0, 88.7, 49.78, 49.5, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+    [1559.6, 117.8, 72.95, 72.55, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+    [1839.0, 149.7, 99.82, 99.42, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+    [2145.5, 189.0, 136.0, 135.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+    [2472.0, 230.9, 163.6, 162.5, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+    [2822.4, 270.0, 202.0, 200.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+    [3205.9, 326.3, 250.6, 248.4, 29.3, 15.9, 15.7, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+    [3608.4, 378.6, 297.3, 294.6, 34.8, 18.3, 18.3, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+    [4038.5, 438.4, 349.7, 346.2, 44.3, 25.4, 25.4, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+    [4492.0, 498.0, 403.6, 398.7, 51.1, 28.3, 28.3, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+    [4966.0, 560.9, 460.2, 453.8, 58.7, 32.6, 32.6, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+    [5465.0, 626.7, 519.8, 512.1, 66.3, 37.2, 37.2, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+    [5989.0, 696.0, 583.8, 574.1, 74.1, 42.2, 42.2, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+    [6539.0, 769.1, 649.9, 638.7, 82.3, 47.2, 47.2, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+    [7112.0, 844.6, 719.9, 706.8, 91.3, 52.7, 52.7, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+    [7709.0, 925.1, 793.2, 778.1, 101.0, 58.9, 59.9, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+    [8333.0, 1008.6, 870.0, 852.7, 110.8, 68.0, 66.2, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+    [8979.0, 1096.7, 952.3, 932.7, 122.5, 77.3, 75.1, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+    [9659.0, 1196.2, 1044.9, 1021.8, 139.8, 91.4, 88.6, 10.2, 10.1, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+    [10367.0, 1299.0, 1143.2, 1116.4, 159.5, 103.5, 100.0, 18.7, 18.7, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+    [11103.0, 1414.6, 1248.1, 1217.0, 180.1, 124.9, 120.8, 29.8, 29.2, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+    [11867.0, 1527.0, 1359.1, 1323.6, 204.7, 146.2, 141.2, 41.7, 41.7, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+    [12658.0, 1652.0, 1474.3, 1433.9, 229.6, 166.5, 160.7, 55.5, 54.6, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+    [13474.0, 1782.0, 1596.0, 1550.0, 257.0, 189.0, 182.0, 70.0, 69.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+    [14326.0, 1921.0, 1730.9, 1678.4, 292.8, 222.2, 214.4, 95.0, 93.8, 27.5, 14.1, 14.1, 0.0, 0.0, 0.0, 0.0],
+    [15200.0, 2065.0, 1864.0, 1804.0, 326.7, 248.7, 239.1, 113.0, 112.0, 30.5, 16.3, 15.3, 0.0, 0.0, 0.0, 0.0],
+    [16105.0, 2216.0, 2007.0, 1940.0, 358.7, 280.3, 270.0, 136.0, 134.2, 38.9, 21.3, 20.1, 0.0, 0.0, 0.0, 0.0],
+    [17038.0, 2373.0, 2156.0, 2080.0, 392.0, 310.6, 298.8, 157.7, 155.8, 43.8, 24.4, 23.1, 0.0, 0.0, 0.0, 0.0],
+    [17998.0, 2532.0, 2307.0, 2223.0, 430.3, 343.5, 329.8, 181.1, 178.8, 50.6, 28.5, 27.1, 0.0, 0.0, 0.0, 0.0],
+    [18986.0, 2698.0, 2465.0, 2371.0, 466.6, 376.1, 360.6, 205.0, 202.3, 56.4, 32.6, 30.8, 0.0, 0.0, 0.0, 0.0],
+    [20000.0, 2866.0, 2625.0, 2520.0, 506.3, 411.6, 394.0, 231.1, 227.9, 63.2, 37.6, 35.5, 0.0, 0.0, 0.0, 0.0],
+    [21044.0, 3043.0, 2793.0, 2677.0, 544.0, 447.6, 417.7, 257.6, 253.9, 69.5, 42.3, 39.9, 0.0, 0.0, 0.0, 0.0],
+    [22117.0, 3224.0, 2967.0, 2838.0, 586.1, 483.5, 461.4, 284.2, 280.0, 75.0, 46.3, 43.2, 0.0, 0.0, 0.0, 0.0],
+    [23220.0, 3412.0, 3146.0, 3004.0, 628.1, 521.3, 496.5, 311.9, 307.2, 81.4, 50.5, 47.3, 0.0, 0.0, 0.0, 0.0],
+    [24350.0, 3604.0, 3330.0, 3173.0, 671.6, 559.9, 532.3, 340.5, 335.2, 87.1, 55.7, 50.9, 0.0, 0.0, 0.0, 0.0],
+    [25514.0, 3806.0, 3524.0, 3351.0, 719.0, 603.8, 573.0, 374.0, 368.3, 97.0, 63.7, 58.3, 0.0, 0.0, 0.0, 0.0],
+    [26711.0, 4018.0, 3727.0, 3538.0, 772.0, 652.6, 618.4, 411.9, 405.2, 109.8, 63.9, 63.9, 11.7, 10.7, 0.0, 0.0],
+    [27940.0, 4238.0, 3938.0, 3730.0, 827.2, 703.2, 665.3, 451.4, 443.9, 122.9, 73.5, 73.5, 17.7, 16.9, 0.0, 0.0],
+    [29200.0, 4465.0, 4156.0, 3929.0, 884.7, 756.5, 714.6, 493.2, 484.9, 137.1, 83.6, 83.6, 24.9, 23.9, 0.0, 0.0],
+    [30491.0, 4698.0, 4380.0, 4132.0, 946.0, 812.7, 766.4, 537.5, 528.2, 153.2, 95.6, 95.6, 33.3, 32.1, 0.0, 0.0],
+    [31814.0, 4939.0, 4612.0, 4341.0, 1006.0, 870.8, 820.0, 583.4, 573.0, 169.4, 103.3, 103.3, 41.9, 40.4, 0.0, 0.0],
+    [33169.0, 5188.0, 4852.0, 4557.0, 1072.0, 931.0, 875.0, 630.8, 619.3, 186.0, 123.0, 123.0, 50.6, 48.9, 0.0, 0.0],
+    [34561.0, 5453.0, 5107.0, 4786.0, 1148.7, 1002.1, 940.6, 689.0, 676.4, 213.2, 146.7, 145.5, 69.5, 67.5, 0.0, 0.0],
+    [35985.0, 5714.0, 5359.0, 5012.0, 1211.0, 1071.0, 1003.0, 740.5, 726.6, 232.3, 172.4, 161.3, 79.8, 77.5, 0.0, 0.0],
+    [37441.0, 5989.0, 5624.0, 5247.0, 1293.0, 1137.0, 1063.0, 795.7, 780.5, 253.5, 192.0, 178.6, 92.6, 89.9, 0.0, 0.0],
+    [38925.0, 6266.0, 5891.0, 5483.0, 1362.0, 1209.0, 1128.0, 853.0, 836.0, 274.7, 205.8, 196.0, 105.3, 102.5, 0.0, 0.0],
+    [40443.0, 6549.0, 6164.0, 5723.0, 1436.0, 1274.0, 1187.0, 902.4, 883.8, 291.0, 223.2, 206.5, 109.0, 109.0, 0.1, 0.1],
+    [41991.0, 6835.0, 6440.0, 5964.0, 1511.0, 1337.0, 1242.0, 948.3, 928.8, 304.5, 236.3, 217.6, 115.1, 115.1, 2.0, 2.0],
+    [43569.0, 7126.0, 6722.0, 6208.0, 1575.0, 1403.0, 1297.0, 1003.3, 980.4, 319.2, 243.3, 224.6, 120.5, 120.5, 1.5, 1.5],
+    [45184.0, 7428.0, 7013.0, 6459.0, 0.0, 1471.4, 1357.0, 1052.0, 1027.0, 0.0, 242.0, 242.0, 120.0, 120.0, 0.0, 0.0],
+    [46834.0, 7737.0, 7312.0, 6716.0, 1723.0, 1541.0, 1419.8, 1110.9, 1083.4, 347.2, 265.6, 247.4, 129.0, 129.0, 5.2, 5.2],
+    [48519.0, 8052.0, 7617.0, 6977.0, 1800.0, 1614.0, 1481.0, 1158.6, 1127.5, 360.0, 284.0, 257.0, 133.0, 127.7, 0.0, 0.0],
+    [50239.0, 8376.0, 7930.0, 7243.0, 1881.0, 1688.0, 1544.0, 1221.9, 1189.6, 378.6, 286.0, 271.0, 142.6, 142.6, 8.6, 8.6],
+    [51996.0, 8708.0, 8252.0, 7514.0, 1968.0, 1768.0, 1611.0, 1276.9, 1241.1, 396.0, 322.4, 284.1, 150.5, 150.5, 7.7, 2.4],
+    [53789.0, 9046.0, 8581.0, 7790.0, 2047.0, 1842.0, 1676.0, 1333.0, 1292.6, 414.2, 333.5, 293.2, 153.6, 153.6, 8.0, 4.3],
+    [55618.0, 9394.0, 8918.0, 8071.0, 2128.0, 1923.0, 1741.0, 1392.0, 1351.0, 432.4, 343.5, 308.2, 160.0, 160.0, 8.6, 5.2],
+    [57486.0, 9751.0, 9264.0, 8358.0, 2207.0, 2006.0, 1812.0, 1453.0, 1409.0, 449.8, 366.2, 320.2, 167.6, 167.6, 0.0, 4.7],
+    [59390.0, 10116.0, 9617.0, 8648.0, 2307.0, 2090.0, 1885.0, 1515.0, 1468.0, 470.9, 385.9, 332.6, 175.5, 175.5, 0.0, 4.6],
+    [61332.0, 10486.0, 9978.0, 8944.0, 2398.0, 2173.0, 1950.0, 1576.0, 1528.0, 480.5, 388.7, 339.7, 191.2, 182.4, 2.5, 1.3],
+    [63314.0, 10870.0, 10349.0, 9244.0, 2491.0, 2264.0, 2024.0, 1639.0, 1589.0, 506.8, 412.4, 359.2, 206.1, 196.3, 8.9, 7.5],
+    [65351.0, 11271.0, 10739.0, 9561.0, 2601.0, 2365.0, 2108.0, 1716.0, 1662.0, 538.0, 438.2, 380.7, 220.0, 211.5, 15.9, 14.2],
+    [67416.0, 11682.0, 11136.0, 9881.0, 2708.0, 2469.0, 2194.0, 1793.0, 1735.0, 563.4, 463.4, 400.9, 237.9, 226.4, 23.5, 21.6],
+    [69525.0, 12100.0, 11544.0, 10207.0, 2820.0, 2575.0, 2281.0, 1872.0, 1809.0, 594.1, 490.4, 423.6, 255.9, 243.5, 33.6, 31.4],
+    [71676.0, 12527.0, 11959.0, 10535.0, 2932.0, 2682.0, 2367.0, 1949.0, 1883.0, 625.4, 518.7, 446.8, 273.9, 260.5, 42.9, 40.5],
+    [73871.0, 12968.0, 12385.0, 10871.0, 3049.0, 2792.0, 2457.0, 2031.0, 1960.0, 658.2, 549.1, 470.7, 293.1, 278.5, 53.4, 50.7],
+    [76111.0, 13419.0, 12824.0, 11215.0, 3174.0, 2909.0, 2551.0, 2116.0, 2040.0, 691.1, 577.8, 495.8, 311.9, 296.3, 63.8, 60.8],
+    [78395.0, 13880.0, 13273.0, 11564.0, 3296.0, 3027.0, 2645.0, 2202.0, 2122.0, 725.4, 609.1, 519.4, 331.6, 314.6, 74.5, 71.2],
+    [80725.0, 14353.0, 13734.0, 11919.0, 3425.0, 3148.0, 2743.0, 2291.0, 2206.0, 762.1, 642.7, 546.3, 353.2, 335.1, 87.6, 83.9],
+    [83102.0, 14839.0, 14209.0, 12284.0, 3562.0, 3279.0, 2847.0, 2385.0, 2295.0, 802.2, 680.2, 576.6, 378.2, 358.8, 104.0, 99.9],
+    [85530.0, 15347.0, 14698.0, 12658.0, 3704.0, 3416.0, 2957.0, 2485.0, 2389.0, 846.2, 720.5, 609.5, 405.7, 385.0, 122.2, 117.8],
+    [88005.0, 15861.0, 15200.0, 13035.0, 3851.0, 3554.0, 3066.0, 2586.0, 2484.0, 891.8, 761.9, 643.5, 434.3, 412.2, 141.7, 136.9],
+    [90526.0, 16388.0, 15711.0, 13419.0, 3999.0, 3696.0, 3177.0, 2688.0, 2580.0, 939.0, 805.2, 678.8, 464.0, 440.1, 162.3, 157.0],
+    [93105.0, 16939.0, 16244.0, 13814.0, 4149.0, 3854.0, 3302.0, 2798.0, 2683.0, 995.0, 851.0, 705.0, 500.0, 473.0, 184.0, 184.0],
+    [95730.0, 17493.0, 16785.0, 14214.0, 4317.0, 4008.0, 3426.0, 2909.0, 2787.0, 1042.0, 886.0, 740.0, 533.0, 507.0, 210.0, 210.0],
+    [98404.0, 18049.0, 17337.0, 14619.0, 4482.0, 4159.0, 3538.0, 3022.0, 2892.0, 1097.0, 929.0, 768.0, 567.0, 541.0, 238.0, 238.0],
+    [101137.0, 18639.0, 17907.0, 15031.0, 4652.0, 4327.0, 3663.0, 3136.0, 3000.0, 1153.0, 980.0, 810.0, 603.0, 577.0, 268.0, 268.0],
+    [103922.0, 19237.0, 18484.0, 15444.0, 4822.0, 4490.0, 3792.0, 3248.0, 3105.0, 1208.0, 1058.0, 879.0, 636.0, 603.0, 299.0, 299.0],
+    [106755.0, 19840.0, 19083.0, 15871.0, 5002.0, 4656.0, 3909.0, 3370.0, 3219.0, 1269.0, 1080.0, 890.0, 675.0, 639.0, 319.0, 319.0],
+    [109651.0, 20472.0, 19693.0, 16300.0, 5182.0, 4830.0, 4046.0, 3491.0, 3332.0, 1330.0, 1168.0, 966.4, 712.1, 675.2, 342.4, 333.1],
+    [112601.0, 21105.0, 20314.0, 16733.0, 5367.0, 5001.0, 4174.0, 3611.0, 3442.0, 1387.0, 1224.0, 1007.0, 743.0, 708.0, 371.0, 360.0],
+    [115606.0, 21757.0, 20948.0, 17166.0, 5548.0, 5182.0, 4303.0, 3728.0, 3552.0, 1439.0, 1271.0, 1043.0, 778.3, 736.2, 388.2, 377.4],
+];
+
+/// Core-hole lifetime broadening (FWHM, eV) for edge `edge` of element `iz`.
+///
+/// Accepts the same labels and numeric aliases as [`canonical_edge_label`];
+/// returns `None` for `NO`, shells beyond N7, or `iz` outside 1..=99.
+pub fn core_hole_width(iz: usize, edge: &str) -> Option<f64> {
+    let index = tabulated_edge_index(edge)?;
+    if !(1..=99).contains(&iz) {
+        return None;
+    }
+
+    let nodes_z = &WIDTH_NODE_Z[index];
+    let nodes_log: Vec<f64> = WIDTH_NODE_EV[index].iter().map(|g| g.log10()).collect();
+    let z = iz as f64;
+    let segment = nodes_z
+        .windows(2)
+        .position(|pair| z <= pair[1])
+        .unwrap_or(nodes_z.len() - 2);
+    let (z0, z1) = (nodes_z[segment], nodes_z[segment + 1]);
+    let (g0, g1) = (nodes_log[segment], nodes_log[segment + 1]);
+    let log_width = g0 + (g1 - g0) * (z - z0) / (z1 - z0);
+    Some(10.0_f64.powf(log_width))
+}
+
+/// Binding energy (eV) of edge `edge` for element `iz`, if tabulated.
+pub fn edge_energy(iz: usize, edge: &str) -> Option<f64> {
+    let index = tabulated_edge_index(edge)?;
+    let row = EDGE_ENERGIES_EV.get(iz.checked_sub(1)?)?;
+    let energy = row[index];
+    (energy > 0.0).then_some(energy)
+}
+
+/// Whether `edge` is one of the shells covered by [`edge_energy`].
+pub fn is_tabulated_edge(edge: &str) -> bool {
+    tabulated_edge_index(edge).is_some()
+}
+
+fn tabulated_edge_index(edge: &str) -> Option<usize> {
+    let label = canonical_edge_label(edge)?;
+    TABULATED_EDGES.iter().position(|entry| *entry == label)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{core_hole_width, edge_energy};
+
+    fn assert_close(actual: Option<f64>, expected: f64) {
+        let actual = actual.expect("value should be tabulated");
+        assert!(
+            (actual - expected).abs() < 5.0e-5,
+            "expected {expected}, got {actual}"
+        );
+    }
+
+    #[test]
+    fn core_hole_widths_match_feff_setgam_defaults() {
+        assert_close(core_hole_width(29, "K"), 1.72919);
+        assert_close(core_hole_width(8, "1"), 0.15586);
+        assert_close(core_hole_width(32, "L3"), 0.75714);
+        assert_close(core_hole_width(58, "l3"), 3.26955);
+    }
+
+    #[test]
+    fn core_hole_width_rejects_untabulated_edges_and_elements() {
+        assert_eq!(core_hole_width(29, "NO"), None);
+        assert_eq!(core_hole_width(29, "O1"), None);
+        assert_eq!(core_hole_width(0, "K"), None);
+        assert_eq!(core_hole_width(120, "K"), None);
+    }
+
+    #[test]
+    fn edge_energies_cover_k_l_m_and_n_shells() {
+        assert_eq!(edge_energy(29, "K"), Some(8979.0));
+        assert_eq!(edge_energy(26, "L3"), Some(706.8));
+        assert_eq!(edge_energy(79, "M5"), Some(2206.0));
+        assert_eq!(edge_energy(92, "N7"), Some(377.4));
+        assert_eq!(edge_energy(6, "L3"), None);
+        assert_eq!(edge_energy(93, "K"), None);
+    }
+}
@@ -289,6 +289,11 @@ pub fn canonical_keyword(word: &str, file_name: &str) -> Option<&'static str> {
     itoken_reverse(file_name, token)
 }
 
+/// Full spelling of every `feff.inp` keyword.
+pub fn feff_keywords() -> impl Iterator<Item = &'static str> {
+    FEFF_TOKEN_TO_KEYWORD.iter().map(|(keyword, _)| *keyword)
+}
+
 pub fn canonical_keyword_for_parser(keyword: &str) -> Option<&'static str> {
     if let Some(canonical) = canonical_keyword(keyword, "feff.inp") {
         return Some(canonical);
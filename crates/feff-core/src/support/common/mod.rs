@@ -1,3 +1,4 @@
+pub mod edgedb;
 pub mod getxk;
 pub mod isedge;
 pub mod itoken;
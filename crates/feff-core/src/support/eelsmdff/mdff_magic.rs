@@ -0,0 +1,433 @@
+use super::mdff_angularmesh::{AngularMeshConfig, AngularMeshError, mdff_angularmesh};
+use super::mdff_eels::{EnergyQMesh, MdffEelsConfig, MdffEelsError, SigmaTensorRow, mdff_eels};
+use super::mdff_euler::mdff_euler;
+use super::mdff_qmesh::{MdffQMeshConfig, MdffQMeshError, MdffQMeshPoint, mdff_qmesh};
+use num_complex::Complex64;
+use std::f64::consts::PI;
+
+const BEAM_ALONG_Z: [f64; 3] = [0.0, 0.0, 1.0];
+const ISOTROPY_TOLERANCE: f64 = 1.0e-9;
+const BISECTION_ITERATIONS: usize = 48;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MdffMagicConfig {
+    pub beam_energy_ev: f64,
+    pub energy_loss_ev: f64,
+    pub convergence_semiangle_rad: f64,
+    pub collection_min_rad: f64,
+    pub collection_max_rad: f64,
+    pub collection_steps: usize,
+    pub nqr: usize,
+    pub nqf: usize,
+    pub relativistic_q: bool,
+    pub h_on_sqrt_two_me: f64,
+    pub me_c2_ev: f64,
+    pub hbarc_ev: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MdffMagicPoint {
+    pub collection_semiangle_rad: f64,
+    pub sigma_parallel: f64,
+    pub sigma_perpendicular: f64,
+    pub anisotropy: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MdffMagicResult {
+    pub curve: Vec<MdffMagicPoint>,
+    pub magic_angle_rad: Option<f64>,
+    pub characteristic_angle_rad: f64,
+    pub probe_tensor: bool,
+}
+
+#[derive(Debug, Clone, thiserror::Error, PartialEq)]
+pub enum MdffMagicError {
+    #[error("collection sweep requires 0 <= min < max, got [{min}, {max}]")]
+    InvalidSweepRange { min: f64, max: f64 },
+    #[error("collection sweep requires at least 2 steps, got {steps}")]
+    InvalidSweepSteps { steps: usize },
+    #[error(
+        "energy loss {energy_loss_ev} eV must be positive and below beam energy {beam_energy_ev} eV"
+    )]
+    InvalidEnergyLoss {
+        energy_loss_ev: f64,
+        beam_energy_ev: f64,
+    },
+    #[error("sigma tensor contains non-finite components")]
+    NonFiniteTensor,
+    #[error("failed to build aperture mesh: {0}")]
+    AngularMesh(#[from] AngularMeshError),
+    #[error("failed to build q-mesh: {0}")]
+    QMesh(#[from] MdffQMeshError),
+    #[error("failed to evaluate MDFF cross section: {0}")]
+    Eels(#[from] MdffEelsError),
+}
+
+/// Sweeps the collection semiangle and locates the magic angle, i.e. the aperture at which the
+/// cross section for a beam parallel to the tensor z axis equals the one for a perpendicular beam.
+///
+/// When the supplied tensor has no uniaxial anisotropy the orientation difference vanishes for
+/// every aperture, so a unit uniaxial probe tensor is used instead; the magic condition depends
+/// only on the q-distribution inside the aperture and is unaffected by that substitution.
+pub fn mdff_magic_angle(
+    tensor: [[f64; 3]; 3],
+    config: MdffMagicConfig,
+) -> Result<MdffMagicResult, MdffMagicError> {
+    validate_config(&config)?;
+    if tensor.iter().flatten().any(|value| !value.is_finite()) {
+        return Err(MdffMagicError::NonFiniteTensor);
+    }
+
+    let trace = tensor[0][0].abs() + tensor[1][1].abs() + tensor[2][2].abs();
+    let uniaxial = tensor[2][2] - 0.5 * (tensor[0][0] + tensor[1][1]);
+    let probe_tensor = uniaxial.abs() <= ISOTROPY_TOLERANCE * trace.max(f64::MIN_POSITIVE);
+    let tensor = if probe_tensor {
+        [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 2.0]]
+    } else {
+        tensor
+    };
+
+    let step = (config.collection_max_rad - config.collection_min_rad)
+        / (config.collection_steps - 1) as f64;
+    let mut curve = Vec::with_capacity(config.collection_steps);
+    for index in 0..config.collection_steps {
+        let collection = (config.collection_min_rad + step * index as f64).max(step * 1.0e-3);
+        curve.push(mdff_magic_point(tensor, collection, &config)?);
+    }
+
+    let mut magic_angle_rad = None;
+    for window in curve.windows(2) {
+        let (left, right) = (window[0], window[1]);
+        if left.anisotropy == 0.0 {
+            magic_angle_rad = Some(left.collection_semiangle_rad);
+            break;
+        }
+        if left.anisotropy.signum() != right.anisotropy.signum() {
+            magic_angle_rad = Some(bisect_root(tensor, left, right, &config)?);
+            break;
+        }
+    }
+
+    Ok(MdffMagicResult {
+        curve,
+        magic_angle_rad,
+        characteristic_angle_rad: characteristic_angle(&config),
+        probe_tensor,
+    })
+}
+
+/// Evaluates the parallel/perpendicular cross sections for a single collection semiangle.
+pub fn mdff_magic_point(
+    tensor: [[f64; 3]; 3],
+    collection_semiangle_rad: f64,
+    config: &MdffMagicConfig,
+) -> Result<MdffMagicPoint, MdffMagicError> {
+    let q_rows = aperture_q_mesh(collection_semiangle_rad, config)?;
+
+    let sigma_parallel = integrated_cross_section(tensor, &q_rows, config)?;
+    let perpendicular_x = rotate_tensor(tensor, mdff_euler(0.0, PI / 2.0, 0.0));
+    let perpendicular_y = rotate_tensor(tensor, mdff_euler(PI / 2.0, PI / 2.0, 0.0));
+    let sigma_perpendicular = 0.5
+        * (integrated_cross_section(perpendicular_x, &q_rows, config)?
+            + integrated_cross_section(perpendicular_y, &q_rows, config)?);
+
+    let mean = 0.5 * (sigma_parallel.abs() + sigma_perpendicular.abs());
+    let anisotropy = if mean > 0.0 {
+        (sigma_parallel - sigma_perpendicular) / mean
+    } else {
+        0.0
+    };
+
+    Ok(MdffMagicPoint {
+        collection_semiangle_rad,
+        sigma_parallel,
+        sigma_perpendicular,
+        anisotropy,
+    })
+}
+
+fn validate_config(config: &MdffMagicConfig) -> Result<(), MdffMagicError> {
+    if !config.collection_min_rad.is_finite()
+        || !config.collection_max_rad.is_finite()
+        || config.collection_min_rad < 0.0
+        || config.collection_max_rad <= config.collection_min_rad
+    {
+        return Err(MdffMagicError::InvalidSweepRange {
+            min: config.collection_min_rad,
+            max: config.collection_max_rad,
+        });
+    }
+    if config.collection_steps < 2 {
+        return Err(MdffMagicError::InvalidSweepSteps {
+            steps: config.collection_steps,
+        });
+    }
+    if !config.energy_loss_ev.is_finite()
+        || config.energy_loss_ev <= 0.0
+        || config.energy_loss_ev >= config.beam_energy_ev
+    {
+        return Err(MdffMagicError::InvalidEnergyLoss {
+            energy_loss_ev: config.energy_loss_ev,
+            beam_energy_ev: config.beam_energy_ev,
+        });
+    }
+    Ok(())
+}
+
+fn bisect_root(
+    tensor: [[f64; 3]; 3],
+    mut left: MdffMagicPoint,
+    mut right: MdffMagicPoint,
+    config: &MdffMagicConfig,
+) -> Result<f64, MdffMagicError> {
+    for _ in 0..BISECTION_ITERATIONS {
+        let midpoint = 0.5 * (left.collection_semiangle_rad + right.collection_semiangle_rad);
+        let probe = mdff_magic_point(tensor, midpoint, config)?;
+        if probe.anisotropy == 0.0 {
+            return Ok(midpoint);
+        }
+        if probe.anisotropy.signum() == left.anisotropy.signum() {
+            left = probe;
+        } else {
+            right = probe;
+        }
+    }
+
+    let span = right.anisotropy - left.anisotropy;
+    if span.abs() <= f64::MIN_POSITIVE {
+        return Ok(0.5 * (left.collection_semiangle_rad + right.collection_semiangle_rad));
+    }
+    Ok(left.collection_semiangle_rad
+        - left.anisotropy * (right.collection_semiangle_rad - left.collection_semiangle_rad) / span)
+}
+
+fn aperture_q_mesh(
+    collection_semiangle_rad: f64,
+    config: &MdffMagicConfig,
+) -> Result<Vec<EnergyQMesh>, MdffMagicError> {
+    let detector = disk_mesh(collection_semiangle_rad, config.nqr, config.nqf)?;
+    let incident = if config.convergence_semiangle_rad > 0.0 {
+        disk_mesh(config.convergence_semiangle_rad, config.nqr, config.nqf)?
+    } else {
+        vec![MdffQMeshPoint {
+            theta_x: 0.0,
+            theta_y: 0.0,
+        }]
+    };
+
+    let mut points = Vec::with_capacity(detector.len() * incident.len());
+    for tilt in &incident {
+        for outgoing in &detector {
+            points.push(MdffQMeshPoint {
+                theta_x: outgoing.theta_x - tilt.theta_x,
+                theta_y: outgoing.theta_y - tilt.theta_y,
+            });
+        }
+    }
+
+    let q_mesh = mdff_qmesh(
+        &points,
+        MdffQMeshConfig {
+            beam_energy_ev: config.beam_energy_ev,
+            scattered_energy_ev: config.beam_energy_ev - config.energy_loss_ev,
+            beam_direction: BEAM_ALONG_Z,
+            relativistic_q: config.relativistic_q,
+            h_on_sqrt_two_me: config.h_on_sqrt_two_me,
+            me_c2_ev: config.me_c2_ev,
+        },
+    )?;
+
+    Ok(q_mesh
+        .rows
+        .iter()
+        .map(|row| EnergyQMesh {
+            q_vectors: vec![row.q_vector],
+            q_lengths_classical: vec![row.q_length_classical],
+        })
+        .collect())
+}
+
+fn disk_mesh(radius: f64, nqr: usize, nqf: usize) -> Result<Vec<MdffQMeshPoint>, MdffMagicError> {
+    let nqr = nqr.max(1);
+    let nqf = nqf.max(1);
+    let mesh = mdff_angularmesh(&AngularMeshConfig {
+        theta_x_center: 0.0,
+        theta_y_center: 0.0,
+        npos: nqf * nqr * nqr,
+        nqr,
+        nqf,
+        qmodus: 'U',
+        th0: radius,
+        thpart: radius / (2 * nqr) as f64,
+        acoll: radius,
+        aconv: 0.0,
+        legacy_manual_hack: false,
+    })?;
+
+    Ok(mesh
+        .theta_x
+        .into_iter()
+        .zip(mesh.theta_y)
+        .map(|(theta_x, theta_y)| MdffQMeshPoint { theta_x, theta_y })
+        .collect())
+}
+
+fn integrated_cross_section(
+    tensor: [[f64; 3]; 3],
+    q_rows: &[EnergyQMesh],
+    config: &MdffMagicConfig,
+) -> Result<f64, MdffMagicError> {
+    let sigma_rows = vec![
+        SigmaTensorRow {
+            energy_loss_ev: config.energy_loss_ev,
+            tensor,
+        };
+        q_rows.len()
+    ];
+    let spectrum = mdff_eels(
+        &sigma_rows,
+        q_rows,
+        &[Complex64::new(1.0, 0.0)],
+        MdffEelsConfig {
+            relativistic_q: config.relativistic_q,
+            hbarc_ev: config.hbarc_ev,
+        },
+    )?;
+
+    let total = spectrum.x.iter().map(|row| row[0].re).sum::<f64>();
+    Ok(total / q_rows.len().max(1) as f64)
+}
+
+fn rotate_tensor(tensor: [[f64; 3]; 3], rotation: [[f64; 3]; 3]) -> [[f64; 3]; 3] {
+    let mut rotated = [[0.0_f64; 3]; 3];
+    for (i, rotated_row) in rotated.iter_mut().enumerate() {
+        for (j, component) in rotated_row.iter_mut().enumerate() {
+            let mut sum = 0.0;
+            for (k, tensor_row) in tensor.iter().enumerate() {
+                for (l, value) in tensor_row.iter().enumerate() {
+                    sum += rotation[i][k] * value * rotation[j][l];
+                }
+            }
+            *component = sum;
+        }
+    }
+    rotated
+}
+
+fn characteristic_angle(config: &MdffMagicConfig) -> f64 {
+    let gamma = 1.0 + config.beam_energy_ev / config.me_c2_ev;
+    let beta_sq = 1.0 - 1.0 / (gamma * gamma);
+    config.energy_loss_ev / (gamma * config.me_c2_ev * beta_sq)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MdffMagicConfig, MdffMagicError, mdff_magic_angle, mdff_magic_point};
+    use crate::support::eelsmdff::mdff_wavelength::{
+        DEFAULT_H_ON_SQRT_TWO_ME_AU, DEFAULT_ME_C2_EV,
+    };
+
+    const HBARC_ATOMIC_EV_A0: f64 = 3727.3794066;
+    const UNIAXIAL: [[f64; 3]; 3] = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 3.0]];
+
+    fn config(relativistic_q: bool, convergence: f64) -> MdffMagicConfig {
+        MdffMagicConfig {
+            beam_energy_ev: 200_000.0,
+            energy_loss_ev: 284.0,
+            convergence_semiangle_rad: convergence,
+            collection_min_rad: 0.0,
+            collection_max_rad: 0.02,
+            collection_steps: 21,
+            nqr: 4,
+            nqf: 3,
+            relativistic_q,
+            h_on_sqrt_two_me: DEFAULT_H_ON_SQRT_TWO_ME_AU,
+            me_c2_ev: DEFAULT_ME_C2_EV,
+            hbarc_ev: HBARC_ATOMIC_EV_A0,
+        }
+    }
+
+    #[test]
+    fn non_relativistic_magic_angle_is_near_four_characteristic_angles() {
+        let result = mdff_magic_angle(UNIAXIAL, config(false, 0.0)).expect("sweep should run");
+        let magic = result.magic_angle_rad.expect("sweep should bracket a root");
+        let ratio = magic / result.characteristic_angle_rad;
+
+        assert!(!result.probe_tensor);
+        assert!(
+            (3.0..5.0).contains(&ratio),
+            "unexpected magic ratio {ratio}"
+        );
+        assert_eq!(result.curve.len(), 21);
+        assert!(result.curve[0].anisotropy > 0.0);
+        assert!(result.curve[20].anisotropy < 0.0);
+    }
+
+    #[test]
+    fn relativistic_q_shrinks_magic_angle() {
+        let classical = mdff_magic_angle(UNIAXIAL, config(false, 0.0))
+            .expect("classical sweep should run")
+            .magic_angle_rad
+            .expect("classical root");
+        let relativistic = mdff_magic_angle(UNIAXIAL, config(true, 0.0))
+            .expect("relativistic sweep should run")
+            .magic_angle_rad
+            .expect("relativistic root");
+
+        assert!(relativistic < classical);
+    }
+
+    #[test]
+    fn magic_angle_is_independent_of_uniaxial_tensor_strength() {
+        let weak = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.5]];
+        let first = mdff_magic_angle(UNIAXIAL, config(true, 0.001))
+            .expect("first sweep")
+            .magic_angle_rad
+            .expect("first root");
+        let second = mdff_magic_angle(weak, config(true, 0.001))
+            .expect("second sweep")
+            .magic_angle_rad
+            .expect("second root");
+
+        assert!((first - second).abs() <= 1.0e-6 * first);
+    }
+
+    #[test]
+    fn orientation_difference_vanishes_at_reported_root() {
+        let sweep_config = config(true, 0.0);
+        let magic = mdff_magic_angle(UNIAXIAL, sweep_config)
+            .expect("sweep should run")
+            .magic_angle_rad
+            .expect("root should exist");
+        let point = mdff_magic_point(UNIAXIAL, magic, &sweep_config).expect("point");
+
+        assert!(point.anisotropy.abs() < 1.0e-6);
+    }
+
+    #[test]
+    fn isotropic_tensor_falls_back_to_probe() {
+        let isotropic = [[2.0, 0.0, 0.0], [0.0, 2.0, 0.0], [0.0, 0.0, 2.0]];
+        let result = mdff_magic_angle(isotropic, config(false, 0.0)).expect("sweep should run");
+
+        assert!(result.probe_tensor);
+        assert!(result.magic_angle_rad.is_some());
+    }
+
+    #[test]
+    fn rejects_invalid_sweep_definitions() {
+        let mut invalid = config(false, 0.0);
+        invalid.collection_max_rad = 0.0;
+        assert!(matches!(
+            mdff_magic_angle(UNIAXIAL, invalid),
+            Err(MdffMagicError::InvalidSweepRange { .. })
+        ));
+
+        let mut invalid = config(false, 0.0);
+        invalid.energy_loss_ev = 250_000.0;
+        assert!(matches!(
+            mdff_magic_angle(UNIAXIAL, invalid),
+            Err(MdffMagicError::InvalidEnergyLoss { .. })
+        ));
+    }
+}
@@ -1,7 +1,8 @@
 use super::genfmtjas::{GenfmtJasConfig, genfmtjas};
 use super::genfmtsub::{GenfmtPathInput, GenfmtSubConfig, genfmt as genfmt_subroutine};
-use super::m_genfmt::GenfmtArtifacts;
+use super::m_genfmt::{GenfmtArtifacts, NrixsPathLegs};
 use super::regenf::artifacts_consumable;
+use num_complex::Complex64;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum GenfmtMode {
@@ -18,6 +19,12 @@ pub struct GenfmtRunConfig {
     pub iorder: i32,
     pub wnstar: bool,
     pub q_weights: Vec<f64>,
+    /// XSPH multipole matrix elements for the NRIXS mode, `rkk[iq][l][ie]`.
+    pub rkk: Vec<Vec<Vec<Complex64>>>,
+    /// Unit q directions for the NRIXS mode, `None` for a spherical average.
+    pub q_directions: Vec<Option<[f64; 3]>>,
+    /// Leg geometry of each path for the NRIXS angular factor.
+    pub path_legs: Vec<NrixsPathLegs>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -57,6 +64,9 @@ pub fn ffmod5(config: &GenfmtRunConfig, paths: &[GenfmtPathInput]) -> GenfmtRunO
                 critcw: config.critcw,
                 iorder: config.iorder,
                 q_weights: config.q_weights.clone(),
+                rkk: config.rkk.clone(),
+                q_directions: config.q_directions.clone(),
+                path_legs: config.path_legs.clone(),
             },
             paths,
         ),
@@ -109,6 +119,9 @@ mod tests {
                 iorder: 2,
                 wnstar: false,
                 q_weights: Vec::new(),
+                rkk: Vec::new(),
+                q_directions: Vec::new(),
+                path_legs: Vec::new(),
             },
             &sample_paths(),
         );
@@ -128,6 +141,9 @@ mod tests {
                 iorder: 2,
                 wnstar: true,
                 q_weights: Vec::new(),
+                rkk: Vec::new(),
+                q_directions: Vec::new(),
+                path_legs: Vec::new(),
             },
             &sample_paths(),
         );
@@ -150,6 +166,9 @@ mod tests {
                 iorder: 2,
                 wnstar: false,
                 q_weights: vec![1.0, 0.5, 0.5],
+                rkk: Vec::new(),
+                q_directions: Vec::new(),
+                path_legs: Vec::new(),
             },
             &sample_paths(),
         );
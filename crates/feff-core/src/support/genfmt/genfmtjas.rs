@@ -1,5 +1,9 @@
 use super::genfmtsub::{GenfmtPathInput, generated_path_records};
-use super::m_genfmt::GenfmtArtifacts;
+use super::m_genfmt::{
+    GeneratedPathRecord, GenfmtArtifacts, MAX_K_CHANNELS, NrixsPathAmplitude, NrixsPathLegs,
+};
+use super::mmtrjas::{MmtrjasInput, mmtrjas};
+use num_complex::Complex64;
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct GenfmtJasConfig {
@@ -7,6 +11,13 @@ pub struct GenfmtJasConfig {
     pub critcw: f64,
     pub iorder: i32,
     pub q_weights: Vec<f64>,
+    /// XSPH multipole matrix elements `rkk[iq][l][ie]` on the XSPH energy grid.
+    pub rkk: Vec<Vec<Vec<Complex64>>>,
+    /// Unit direction of each q; `None` integrates over q directions (the
+    /// spherical average) and missing entries do the same.
+    pub q_directions: Vec<Option<[f64; 3]>>,
+    /// Leg geometry per path; paths without an entry use the isotropic weight.
+    pub path_legs: Vec<NrixsPathLegs>,
 }
 
 pub fn genfmtjas(config: &GenfmtJasConfig, paths: &[GenfmtPathInput]) -> GenfmtArtifacts {
@@ -30,6 +41,7 @@ pub fn genfmtjas(config: &GenfmtJasConfig, paths: &[GenfmtPathInput]) -> GenfmtA
         ],
         list_rows: Vec::with_capacity(records.len()),
         nstar_rows: Vec::new(),
+        nrixs_amplitudes: nrixs_amplitudes(config, &records),
     };
 
     for record in records {
@@ -49,6 +61,126 @@ pub fn genfmtjas(config: &GenfmtJasConfig, paths: &[GenfmtPathInput]) -> GenfmtA
     artifacts
 }
 
+/// Per-path, per-q amplitudes
+/// `F(k) = ratio * sum_l t_l A_l(q, path) (-1)^l rkk_l(q, k)^2`.
+/// `t_l` closes the path in its own frame with the `mmtrjas` side matrices of
+/// the K-shell `m = 0` initial state and `A_l` rotates it onto `q` (see
+/// [`angular_factor`]); the square of `rkk` carries both the channel strength
+/// and the doubled central-atom phase, and `(-1)^l` is the parity of the
+/// outgoing and returning `l` waves. Channels above `MAX_K_CHANNELS` are dropped.
+fn nrixs_amplitudes(
+    config: &GenfmtJasConfig,
+    records: &[GeneratedPathRecord],
+) -> Vec<NrixsPathAmplitude> {
+    let q_count = config.rkk.len();
+    let channel_count = config
+        .rkk
+        .iter()
+        .map(Vec::len)
+        .max()
+        .unwrap_or(0)
+        .min(MAX_K_CHANNELS);
+    if q_count == 0 || channel_count == 0 {
+        return Vec::new();
+    }
+
+    let lind: Vec<usize> = (0..channel_count).collect();
+    let q_phases = vec![Complex64::new(1.0, 0.0); q_count];
+    let q_beta = vec![0.0; q_count];
+    let Ok(sides) = mmtrjas(MmtrjasInput {
+        mu_values: &[0],
+        lind: &lind,
+        q_phases: &q_phases,
+        q_beta: &q_beta,
+        eta_start: 0.0,
+        eta_end: 0.0,
+    }) else {
+        return Vec::new();
+    };
+    let terminations: Vec<Vec<Complex64>> = (0..q_count)
+        .map(|iq| {
+            (0..channel_count)
+                .map(|k| sides.left[iq][0][k] * sides.right[iq][0][k])
+                .collect()
+        })
+        .collect();
+
+    let mut amplitudes = Vec::with_capacity(records.len() * q_count);
+    for record in records {
+        let scale = record.cw_amplitude_ratio / 100.0;
+        let legs = config
+            .path_legs
+            .iter()
+            .find(|legs| legs.path_index == record.path_index);
+        for (iq, channels) in config.rkk.iter().enumerate() {
+            let q_direction = config.q_directions.get(iq).copied().flatten();
+            let channels = &channels[..channels.len().min(channel_count)];
+            let points = channels.iter().map(Vec::len).min().unwrap_or(0);
+            let amplitude = (0..points)
+                .map(|ie| {
+                    channels
+                        .iter()
+                        .enumerate()
+                        .map(|(l, rkk)| {
+                            let parity = if l % 2 == 0 { 1.0 } else { -1.0 };
+                            terminations[iq][l]
+                                * angular_factor(l, q_direction, legs)
+                                * rkk[ie]
+                                * rkk[ie]
+                                * parity
+                        })
+                        .sum::<Complex64>()
+                        * scale
+                })
+                .collect();
+            amplitudes.push(NrixsPathAmplitude {
+                path_index: record.path_index,
+                q_index: iq + 1,
+                amplitude,
+            });
+        }
+    }
+    amplitudes
+}
+
+/// `A_l = <P_l(q.R1) P_l(q.Rn)>` over the member paths, with `R1` and `Rn`
+/// the directions of the first and last scatterer: the `l` wave leaves along
+/// the first leg and returns along the last, each projected on `q`. Without
+/// a q direction the average over `q` leaves `<P_l(R1.Rn)> / (2l + 1)`, which
+/// is also the weight of a path without leg geometry (`R1 = Rn`).
+fn angular_factor(l: usize, q: Option<[f64; 3]>, legs: Option<&NrixsPathLegs>) -> f64 {
+    let isotropic = 1.0 / (2 * l + 1) as f64;
+    let Some(legs) = legs.filter(|legs| !legs.members.is_empty()) else {
+        return isotropic;
+    };
+    let dot = |a: &[f64; 3], b: &[f64; 3]| a.iter().zip(b).map(|(a, b)| a * b).sum::<f64>();
+    let total = legs
+        .members
+        .iter()
+        .map(|[first, last]| match &q {
+            Some(q) => legendre(l, dot(q, first)) * legendre(l, dot(q, last)),
+            None => legendre(l, dot(first, last)) * isotropic,
+        })
+        .sum::<f64>();
+    total / legs.members.len() as f64
+}
+
+/// Legendre polynomial `P_l(x)` by the Bonnet recurrence.
+fn legendre(l: usize, x: f64) -> f64 {
+    let x = x.clamp(-1.0, 1.0);
+    let (mut previous, mut current) = (1.0, x);
+    if l == 0 {
+        return previous;
+    }
+    for n in 1..l {
+        let n = n as f64;
+        let next = ((2.0 * n + 1.0) * x * current - n * previous) / (n + 1.0);
+        previous = current;
+        current = next;
+    }
+    current
+}
+
 fn normalized_q_weight(weights: &[f64]) -> f64 {
     if weights.is_empty() {
         return 1.0;
@@ -66,6 +198,7 @@ fn normalized_q_weight(weights: &[f64]) -> f64 {
 mod tests {
     use super::{GenfmtJasConfig, genfmtjas};
     use crate::support::genfmt::genfmtsub::GenfmtPathInput;
+    use num_complex::Complex64;
 
     #[test]
     fn genfmtjas_embeds_nrixs_header_fields() {
@@ -75,6 +208,9 @@ mod tests {
                 critcw: 3.0,
                 iorder: 2,
                 q_weights: vec![1.0, 0.5],
+                rkk: Vec::new(),
+                q_directions: Vec::new(),
+                path_legs: Vec::new(),
             },
             &[GenfmtPathInput {
                 path_index: 7,
@@ -105,6 +241,9 @@ mod tests {
                 critcw: 0.0,
                 iorder: 2,
                 q_weights: vec![1.0, 1.0],
+                rkk: Vec::new(),
+                q_directions: Vec::new(),
+                path_legs: Vec::new(),
             },
             &paths,
         );
@@ -115,6 +254,9 @@ mod tests {
                 critcw: 0.0,
                 iorder: 2,
                 q_weights: vec![0.2, 0.2],
+                rkk: Vec::new(),
+                q_directions: Vec::new(),
+                path_legs: Vec::new(),
             },
             &paths,
         );
@@ -149,6 +291,9 @@ mod tests {
                 critcw: 0.0,
                 iorder: 2,
                 q_weights: Vec::new(),
+                rkk: Vec::new(),
+                q_directions: Vec::new(),
+                path_legs: Vec::new(),
             },
             &[GenfmtPathInput {
                 path_index: 1,
@@ -161,4 +306,53 @@ mod tests {
 
         assert!(artifacts.list_rows[0].contains("100.000"));
     }
+
+    #[test]
+    fn genfmtjas_builds_per_q_path_amplitudes_from_rkk() {
+        let rkk = |value: Complex64| vec![value; 3];
+        let zero = Complex64::new(0.0, 0.0);
+        let artifacts = genfmtjas(
+            &GenfmtJasConfig {
+                version_tag: "10.0".to_string(),
+                critcw: 0.0,
+                iorder: 2,
+                q_weights: vec![1.0, 1.0],
+                rkk: vec![
+                    vec![rkk(Complex64::new(0.5, 0.0)), rkk(zero)],
+                    vec![rkk(zero), rkk(Complex64::new(0.5, 0.0))],
+                ],
+                q_directions: Vec::new(),
+                path_legs: Vec::new(),
+            },
+            &[
+                GenfmtPathInput {
+                    path_index: 1,
+                    nleg: 2,
+                    degeneracy: 4.0,
+                    reff: 2.5,
+                    amplitude: 1.0,
+                },
+                GenfmtPathInput {
+                    path_index: 2,
+                    nleg: 2,
+                    degeneracy: 4.0,
+                    reff: 3.5,
+                    amplitude: 0.5,
+                },
+            ],
+        );
+
+        assert_eq!(artifacts.nrixs_amplitudes.len(), 4);
+        let monopole = &artifacts.nrixs_amplitudes[0];
+        let dipole = &artifacts.nrixs_amplitudes[1];
+        assert_eq!((monopole.path_index, monopole.q_index), (1, 1));
+        assert_eq!((dipole.path_index, dipole.q_index), (1, 2));
+        assert_eq!(monopole.amplitude.len(), 3);
+        assert!(monopole.amplitude[0].re > 0.0, "even l keeps the sign");
+        assert!(dipole.amplitude[0].re < 0.0, "odd l flips the sign");
+
+        let weaker = &artifacts.nrixs_amplitudes[2];
+        assert_eq!(weaker.path_index, 2);
+        assert!((weaker.amplitude[0] / monopole.amplitude[0] - 0.5).norm() < 1.0e-12);
+    }
 }
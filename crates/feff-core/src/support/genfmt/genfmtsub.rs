@@ -60,6 +60,7 @@ pub fn genfmt(config: &GenfmtSubConfig, paths: &[GenfmtPathInput]) -> GenfmtArti
         ],
         list_rows: Vec::with_capacity(records.len()),
         nstar_rows: Vec::new(),
+        nrixs_amplitudes: Vec::new(),
     };
 
     for record in &records {
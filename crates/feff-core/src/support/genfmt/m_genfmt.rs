@@ -179,12 +179,31 @@ pub struct GeneratedPathRecord {
     pub cw_amplitude_ratio: f64,
 }
 
+/// NRIXS effective amplitude of one path for one momentum transfer, on the
+/// energy grid of the XSPH matrix elements it was built from.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NrixsPathAmplitude {
+    pub path_index: usize,
+    pub q_index: usize,
+    pub amplitude: Vec<Complex64>,
+}
+
+/// Leg geometry of one path for the NRIXS angular factor: for every member
+/// path the degeneracy counts, the unit vectors from the absorber to its first
+/// and to its last scatterer.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NrixsPathLegs {
+    pub path_index: usize,
+    pub members: Vec<[[f64; 3]; 2]>,
+}
+
 #[derive(Debug, Clone, PartialEq, Default)]
 pub struct GenfmtArtifacts {
     pub feff_header_lines: Vec<String>,
     pub list_header_lines: Vec<String>,
     pub list_rows: Vec<String>,
     pub nstar_rows: Vec<String>,
+    pub nrixs_amplitudes: Vec<NrixsPathAmplitude>,
 }
 
 impl GenfmtArtifacts {
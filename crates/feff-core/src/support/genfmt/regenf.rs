@@ -118,6 +118,7 @@ mod tests {
             list_header_lines: vec!["header".to_string()],
             list_rows: vec!["1 0.01 100.0 2.0 4 2.5".to_string()],
             nstar_rows: vec!["1 10.0".to_string()],
+            nrixs_amplitudes: Vec::new(),
         };
         assert!(artifacts_consumable(&valid));
 
@@ -126,6 +127,7 @@ mod tests {
             list_header_lines: vec!["header".to_string()],
             list_rows: vec!["bad".to_string()],
             nstar_rows: vec![],
+            nrixs_amplitudes: Vec::new(),
         };
         assert!(!artifacts_consumable(&invalid));
     }
@@ -0,0 +1,682 @@
+use super::m_pot_generator::XyzFormat;
+use crate::support::common::pertab::atsym;
+use crate::support::symmetry::SymmetryError;
+use crate::support::symmetry::operation::SymmetryOperation;
+use crate::support::symmetry::sites::{same_fractional_position, wrap_fractional};
+use crate::support::symmetry::space_groups::{operations_for_symbol, space_group_by_number};
+use std::collections::BTreeMap;
+
+const DISTANCE_TOLERANCE: f64 = 1.0e-6;
+
+#[derive(Debug, Clone, thiserror::Error, PartialEq, Eq)]
+pub enum CifError {
+    #[error("CIF source does not contain a data_ block")]
+    MissingDataBlock,
+    #[error("missing CIF item '{0}'")]
+    MissingItem(&'static str),
+    #[error("invalid number '{value}' for CIF item '{item}'")]
+    InvalidNumber { item: String, value: String },
+    #[error("unterminated ';' text field starting at line {0}")]
+    UnterminatedTextField(usize),
+    #[error("loop_ at line {line} has {values} values for {tags} tags")]
+    RaggedLoop {
+        line: usize,
+        tags: usize,
+        values: usize,
+    },
+    #[error(transparent)]
+    Symmetry(#[from] SymmetryError),
+    #[error("cannot determine the element of atom site '{0}'")]
+    UnknownElement(String),
+    #[error("CIF does not list any atom sites")]
+    NoSites,
+    #[error("cell parameters do not describe a valid unit cell")]
+    DegenerateCell,
+    #[error("absorber site {site} is out of range (sites={count})")]
+    AbsorberOutOfRange { site: usize, count: usize },
+}
+
+/// Unit-cell edge lengths (Angstrom) and angles (degrees).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CifCell {
+    pub a: f64,
+    pub b: f64,
+    pub c: f64,
+    pub alpha: f64,
+    pub beta: f64,
+    pub gamma: f64,
+}
+
+impl CifCell {
+    /// Row `i` is the Cartesian lattice vector `i` (a along x, b in the xy plane).
+    pub fn lattice_vectors(&self) -> Result<[[f64; 3]; 3], CifError> {
+        let (alpha, beta, gamma) = (
+            self.alpha.to_radians(),
+            self.beta.to_radians(),
+            self.gamma.to_radians(),
+        );
+        let cx = self.c * beta.cos();
+        let cy = self.c * (alpha.cos() - beta.cos() * gamma.cos()) / gamma.sin();
+        let cz_squared = self.c * self.c - cx * cx - cy * cy;
+        if self.a <= 0.0 || self.b <= 0.0 || self.c <= 0.0 || cz_squared <= 0.0 {
+            return Err(CifError::DegenerateCell);
+        }
+        Ok([
+            [self.a, 0.0, 0.0],
+            [self.b * gamma.cos(), self.b * gamma.sin(), 0.0],
+            [cx, cy, cz_squared.sqrt()],
+        ])
+    }
+
+    pub fn to_cartesian(&self, fractional: [f64; 3]) -> Result<[f64; 3], CifError> {
+        let vectors = self.lattice_vectors()?;
+        Ok(std::array::from_fn(|axis| {
+            (0..3).map(|row| fractional[row] * vectors[row][axis]).sum()
+        }))
+    }
+}
+
+/// One asymmetric-unit site from `_atom_site_*`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CifSite {
+    pub label: String,
+    pub atomic_number: i32,
+    pub fractional: [f64; 3],
+    pub occupancy: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CifStructure {
+    pub title: String,
+    pub space_group: Option<String>,
+    pub cell: CifCell,
+    pub operations: Vec<SymmetryOperation>,
+    pub sites: Vec<CifSite>,
+}
+
+impl CifStructure {
+    /// Reads the first data block of a CIF 1.1 file.
+    ///
+    /// Symmetry comes from `_symmetry_equiv_pos_as_xyz` or
+    /// `_space_group_symop_operation_xyz`. Without either loop the operations
+    /// are generated from the Hermann-Mauguin symbol or the International
+    /// Tables number; if neither is given only the identity is applied.
+    pub fn parse(source: &str) -> Result<Self, CifError> {
+        let block = CifBlock::parse(source)?;
+
+        let cell = CifCell {
+            a: block.required_number("_cell_length_a")?,
+            b: block.required_number("_cell_length_b")?,
+            c: block.required_number("_cell_length_c")?,
+            alpha: block.optional_number("_cell_angle_alpha")?.unwrap_or(90.0),
+            beta: block.optional_number("_cell_angle_beta")?.unwrap_or(90.0),
+            gamma: block.optional_number("_cell_angle_gamma")?.unwrap_or(90.0),
+        };
+        cell.lattice_vectors()?;
+
+        let mut operations = block
+            .loop_column("_symmetry_equiv_pos_as_xyz")
+            .or_else(|| block.loop_column("_space_group_symop_operation_xyz"))
+            .unwrap_or_default()
+            .into_iter()
+            .map(SymmetryOperation::parse_xyz)
+            .collect::<Result<Vec<_>, _>>()?;
+        let space_group = block
+            .item("_symmetry_space_group_name_h-m")
+            .or_else(|| block.item("_space_group_name_h-m_alt"))
+            .filter(|value| !is_unknown(value))
+            .map(str::to_string);
+        if operations.is_empty() {
+            operations = space_group_operations(&block, space_group.as_deref())?;
+        }
+
+        let labels = block.loop_column("_atom_site_label");
+        let types = block.loop_column("_atom_site_type_symbol");
+        let fract_x = block
+            .loop_column("_atom_site_fract_x")
+            .ok_or(CifError::MissingItem("_atom_site_fract_x"))?;
+        let fract_y = block
+            .loop_column("_atom_site_fract_y")
+            .ok_or(CifError::MissingItem("_atom_site_fract_y"))?;
+        let fract_z = block
+            .loop_column("_atom_site_fract_z")
+            .ok_or(CifError::MissingItem("_atom_site_fract_z"))?;
+        let occupancies = block.loop_column("_atom_site_occupancy");
+
+        let mut sites = Vec::with_capacity(fract_x.len());
+        for index in 0..fract_x.len() {
+            let label = labels
+                .as_ref()
+                .and_then(|column| column.get(index))
+                .or_else(|| types.as_ref().and_then(|column| column.get(index)))
+                .map(|value| value.to_string())
+                .unwrap_or_else(|| format!("site{}", index + 1));
+            let element_source = types
+                .as_ref()
+                .and_then(|column| column.get(index))
+                .copied()
+                .unwrap_or(label.as_str());
+            let atomic_number = element_atomic_number(element_source)
+                .ok_or_else(|| CifError::UnknownElement(label.clone()))?;
+            let fractional = [
+                parse_cif_number("_atom_site_fract_x", fract_x[index])?,
+                parse_cif_number("_atom_site_fract_y", fract_y[index])?,
+                parse_cif_number("_atom_site_fract_z", fract_z[index])?,
+            ];
+            let occupancy = match occupancies.as_ref().and_then(|column| column.get(index)) {
+                Some(value) if !is_unknown(value) => {
+                    parse_cif_number("_atom_site_occupancy", value)?
+                }
+                _ => 1.0,
+            };
+            sites.push(CifSite {
+                label,
+                atomic_number,
+                fractional,
+                occupancy,
+            });
+        }
+        if sites.is_empty() {
+            return Err(CifError::NoSites);
+        }
+
+        let title = block
+            .item("_chemical_formula_sum")
+            .or_else(|| block.item("_chemical_name_systematic"))
+            .filter(|value| !is_unknown(value))
+            .map(str::to_string)
+            .unwrap_or_else(|| block.name.clone());
+
+        Ok(Self {
+            title,
+            space_group,
+            cell,
+            operations,
+            sites,
+        })
+    }
+
+    /// Symmetry-expanded unit cell as `(site index, fractional position in [0, 1))`.
+    ///
+    /// Partially occupied sites that share a position keep only the species
+    /// with the largest occupancy, since FEFF clusters cannot mix species.
+    pub fn unit_cell_atoms(&self) -> Vec<(usize, [f64; 3])> {
+        let mut atoms: Vec<(usize, [f64; 3])> = Vec::new();
+        for (site_index, site) in self.sites.iter().enumerate() {
+            for operation in &self.operations {
+                let position = wrap_fractional(operation.apply(site.fractional));
+                match atoms
+                    .iter_mut()
+                    .find(|(_, existing)| same_fractional_position(*existing, position))
+                {
+                    Some(existing) => {
+                        if self.sites[existing.0].occupancy < site.occupancy {
+                            existing.0 = site_index;
+                        }
+                    }
+                    None => atoms.push((site_index, position)),
+                }
+            }
+        }
+        atoms
+    }
+
+    /// Builds a spherical cluster of `radius` Angstrom centred on asymmetric-unit
+    /// site `absorber_site` (1-based, in CIF order).
+    ///
+    /// The absorber is the first atom of the result and the rest are sorted by
+    /// distance, ready for `gen_pot_from_xyz` with absorber index 1.
+    pub fn build_cluster(&self, absorber_site: usize, radius: f64) -> Result<XyzFormat, CifError> {
+        let site = absorber_site
+            .checked_sub(1)
+            .and_then(|index| self.sites.get(index))
+            .ok_or(CifError::AbsorberOutOfRange {
+                site: absorber_site,
+                count: self.sites.len(),
+            })?;
+
+        let vectors = self.cell.lattice_vectors()?;
+        let origin = self.cell.to_cartesian(wrap_fractional(site.fractional))?;
+        let repeats = translation_repeats(&vectors, radius);
+
+        let mut cluster: BTreeMap<(i64, usize), (i32, [f64; 3])> = BTreeMap::new();
+        let mut order = 0_usize;
+        for (site_index, fractional) in self.unit_cell_atoms() {
+            for na in -repeats[0]..=repeats[0] {
+                for nb in -repeats[1]..=repeats[1] {
+                    for nc in -repeats[2]..=repeats[2] {
+                        let shifted = [
+                            fractional[0] + na as f64,
+                            fractional[1] + nb as f64,
+                            fractional[2] + nc as f64,
+                        ];
+                        let position = self.cell.to_cartesian(shifted)?;
+                        let relative: [f64; 3] =
+                            std::array::from_fn(|axis| position[axis] - origin[axis]);
+                        let distance = relative.iter().map(|v| v * v).sum::<f64>().sqrt();
+                        if distance > radius + DISTANCE_TOLERANCE {
+                            continue;
+                        }
+                        let key = ((distance / DISTANCE_TOLERANCE).round() as i64, order);
+                        order += 1;
+                        cluster.insert(key, (self.sites[site_index].atomic_number, relative));
+                    }
+                }
+            }
+        }
+
+        let mut atomic_numbers = Vec::with_capacity(cluster.len());
+        let mut xyz = Vec::with_capacity(cluster.len());
+        for (atomic_number, relative) in cluster.into_values() {
+            atomic_numbers.push(atomic_number);
+            xyz.push(relative.map(|value| if value.abs() < 1.0e-9 { 0.0 } else { value }));
+        }
+
+        Ok(XyzFormat {
+            title: self.title.clone(),
+            atomic_numbers,
+            xyz,
+            potential_indices: Vec::new(),
+            potential_numeric_labels: Vec::new(),
+            potential_string_labels: Vec::new(),
+        })
+    }
+}
+
+/// Resolves an element from a CIF type symbol or site label (`Cu`, `Cu2+`, `O1`).
+pub fn element_atomic_number(token: &str) -> Option<i32> {
+    let letters: String = token
+        .trim()
+        .chars()
+        .take_while(|ch| ch.is_ascii_alphabetic())
+        .collect();
+    let lookup = |symbol: &str| {
+        (1..=103_usize)
+            .find(|iz| atsym(*iz).is_some_and(|known| known.eq_ignore_ascii_case(symbol)))
+            .map(|iz| iz as i32)
+    };
+    letters
+        .get(..2)
+        .and_then(lookup)
+        .or_else(|| letters.get(..1).and_then(lookup))
+}
+
+#[derive(Debug, Default)]
+struct CifBlock {
+    name: String,
+    items: BTreeMap<String, String>,
+    loops: Vec<(Vec<String>, Vec<String>)>,
+}
+
+impl CifBlock {
+    fn parse(source: &str) -> Result<Self, CifError> {
+        let tokens = tokenize(source)?;
+        let mut block: Option<CifBlock> = None;
+        let mut index = 0;
+
+        while index < tokens.len() {
+            let (line, token) = (tokens[index].0, tokens[index].1.as_str());
+            let lower = token.to_ascii_lowercase();
+            if lower.starts_with("data_") {
+                if block.is_some() {
+                    break;
+                }
+                block = Some(CifBlock {
+                    name: token[5..].to_string(),
+                    ..CifBlock::default()
+                });
+                index += 1;
+                continue;
+            }
+            let Some(current) = block.as_mut() else {
+                index += 1;
+                continue;
+            };
+
+            if lower == "loop_" {
+                index += 1;
+                let mut tags = Vec::new();
+                while index < tokens.len() && tokens[index].1.starts_with('_') {
+                    tags.push(tokens[index].1.to_ascii_lowercase());
+                    index += 1;
+                }
+                let mut values = Vec::new();
+                while index < tokens.len() && !is_reserved(&tokens[index].1) {
+                    values.push(tokens[index].1.clone());
+                    index += 1;
+                }
+                if tags.is_empty() || values.len() % tags.len() != 0 {
+                    return Err(CifError::RaggedLoop {
+                        line,
+                        tags: tags.len(),
+                        values: values.len(),
+                    });
+                }
+                current.loops.push((tags, values));
+                continue;
+            }
+
+            if token.starts_with('_') {
+                let value = tokens
+                    .get(index + 1)
+                    .filter(|(_, value)| !is_reserved(value))
+                    .map(|(_, value)| value.clone())
+                    .unwrap_or_default();
+                current.items.insert(lower, value);
+                index += 2;
+                continue;
+            }
+
+            index += 1;
+        }
+
+        block.ok_or(CifError::MissingDataBlock)
+    }
+
+    fn item(&self, tag: &str) -> Option<&str> {
+        self.items.get(tag).map(String::as_str)
+    }
+
+    fn required_number(&self, tag: &'static str) -> Result<f64, CifError> {
+        self.optional_number(tag)?.ok_or(CifError::MissingItem(tag))
+    }
+
+    fn optional_number(&self, tag: &str) -> Result<Option<f64>, CifError> {
+        match self.item(tag) {
+            Some(value) if !is_unknown(value) => parse_cif_number(tag, value).map(Some),
+            _ => Ok(None),
+        }
+    }
+
+    fn loop_column(&self, tag: &str) -> Option<Vec<&str>> {
+        self.loops.iter().find_map(|(tags, values)| {
+            let column = tags.iter().position(|candidate| candidate == tag)?;
+            Some(
+                values
+                    .chunks(tags.len())
+                    .map(|row| row[column].as_str())
+                    .collect(),
+            )
+        })
+    }
+}
+
+fn tokenize(source: &str) -> Result<Vec<(usize, String)>, CifError> {
+    let mut tokens = Vec::new();
+    let mut text_field: Option<(usize, String)> = None;
+
+    for (index, line) in source.lines().enumerate() {
+        let line_number = index + 1;
+        if let Some((start, mut text)) = text_field.take() {
+            if line.starts_with(';') {
+                tokens.push((start, text.trim().to_string()));
+            } else {
+                text.push_str(line);
+                text.push('\n');
+                text_field = Some((start, text));
+            }
+            continue;
+        }
+        if let Some(rest) = line.strip_prefix(';') {
+            text_field = Some((line_number, format!("{rest}\n")));
+            continue;
+        }
+
+        let mut chars = line.char_indices().peekable();
+        while let Some((start, ch)) = chars.next() {
+            if ch.is_whitespace() {
+                continue;
+            }
+            if ch == '#' {
+                break;
+            }
+            if ch == '\'' || ch == '"' {
+                // A quote only closes when followed by whitespace or end of line.
+                let mut end = line.len();
+                while let Some((position, next)) = chars.next() {
+                    if next == ch && chars.peek().is_none_or(|(_, after)| after.is_whitespace()) {
+                        end = position;
+                        break;
+                    }
+                }
+                tokens.push((line_number, line[start + 1..end].to_string()));
+                continue;
+            }
+            let mut end = line.len();
+            while let Some((position, next)) = chars.peek().copied() {
+                if next.is_whitespace() {
+                    end = position;
+                    break;
+                }
+                chars.next();
+            }
+            tokens.push((line_number, line[start..end].to_string()));
+        }
+    }
+
+    match text_field {
+        Some((start, _)) => Err(CifError::UnterminatedTextField(start)),
+        None => Ok(tokens),
+    }
+}
+
+fn is_reserved(token: &str) -> bool {
+    let lower = token.to_ascii_lowercase();
+    token.starts_with('_')
+        || lower == "loop_"
+        || lower.starts_with("data_")
+        || lower.starts_with("save_")
+        || lower == "stop_"
+        || lower == "global_"
+}
+
+/// Operations implied by the space-group symbol, else by the table number.
+fn space_group_operations(
+    block: &CifBlock,
+    symbol: Option<&str>,
+) -> Result<Vec<SymmetryOperation>, CifError> {
+    if let Some(symbol) = symbol {
+        return Ok(operations_for_symbol(symbol)?);
+    }
+    let number = block
+        .item("_space_group_it_number")
+        .or_else(|| block.item("_symmetry_int_tables_number"))
+        .filter(|value| !is_unknown(value));
+    match number {
+        Some(value) => {
+            let group = value
+                .parse::<u16>()
+                .ok()
+                .and_then(space_group_by_number)
+                .ok_or_else(|| SymmetryError::UnknownSpaceGroup(value.to_string()))?;
+            Ok(group.operations()?)
+        }
+        None => Ok(vec![SymmetryOperation::identity()]),
+    }
+}
+
+fn is_unknown(value: &str) -> bool {
+    matches!(value, "?" | ".")
+}
+
+/// Parses a CIF number, dropping a standard uncertainty such as `3.615(2)`.
+fn parse_cif_number(tag: &str, value: &str) -> Result<f64, CifError> {
+    let numeric = value.split('(').next().unwrap_or(value);
+    numeric.parse::<f64>().map_err(|_| CifError::InvalidNumber {
+        item: tag.to_string(),
+        value: value.to_string(),
+    })
+}
+
+/// Lattice translations needed along each axis so every atom within `radius`
+/// of a point inside the cell is reached.
+pub(super) fn translation_repeats(vectors: &[[f64; 3]; 3], radius: f64) -> [i64; 3] {
+    let cross = |u: [f64; 3], v: [f64; 3]| {
+        [
+            u[1] * v[2] - u[2] * v[1],
+            u[2] * v[0] - u[0] * v[2],
+            u[0] * v[1] - u[1] * v[0],
+        ]
+    };
+    let dot = |u: [f64; 3], v: [f64; 3]| u[0] * v[0] + u[1] * v[1] + u[2] * v[2];
+    let volume = dot(vectors[0], cross(vectors[1], vectors[2])).abs();
+    std::array::from_fn(|axis| {
+        let face = cross(vectors[(axis + 1) % 3], vectors[(axis + 2) % 3]);
+        let height = volume / dot(face, face).sqrt();
+        (radius / height).ceil() as i64 + 1
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CifError, CifStructure, element_atomic_number};
+
+    const COPPER_CIF: &str = "\
+data_Cu
+_chemical_formula_sum 'Cu'
+_symmetry_space_group_name_H-M 'F m -3 m'
+_cell_length_a 3.6150(2)
+_cell_length_b 3.6150
+_cell_length_c 3.6150
+_cell_angle_alpha 90
+_cell_angle_beta 90
+_cell_angle_gamma 90
+loop_
+_symmetry_equiv_pos_as_xyz
+'x, y, z'
+'x, y+1/2, z+1/2'
+'x+1/2, y, z+1/2'
+'x+1/2, y+1/2, z'
+loop_
+_atom_site_label
+_atom_site_type_symbol
+_atom_site_fract_x
+_atom_site_fract_y
+_atom_site_fract_z
+_atom_site_occupancy
+Cu1 Cu 0.0 0.0 0.0 1.0
+";
+
+    const ROCKSALT_CIF: &str = "\
+# NaCl with an explicit text field
+data_NaCl
+_chemical_name_systematic
+;
+ Sodium chloride
+;
+_cell_length_a 5.64
+_cell_length_b 5.64
+_cell_length_c 5.64
+loop_
+_space_group_symop_operation_xyz
+x,y,z
+x,1/2+y,1/2+z
+1/2+x,y,1/2+z
+1/2+x,1/2+y,z
+loop_
+_atom_site_label
+_atom_site_fract_x
+_atom_site_fract_y
+_atom_site_fract_z
+Na1 0 0 0
+Cl1 0.5 0.5 0.5
+";
+
+    #[test]
+    fn parses_cell_symmetry_and_sites() {
+        let structure = CifStructure::parse(COPPER_CIF).expect("CIF should parse");
+        assert_eq!(structure.title, "Cu");
+        assert_eq!(structure.space_group.as_deref(), Some("F m -3 m"));
+        assert!((structure.cell.a - 3.615).abs() < 1.0e-12);
+        assert_eq!(structure.operations.len(), 4);
+        assert_eq!(structure.sites.len(), 1);
+        assert_eq!(structure.sites[0].atomic_number, 29);
+        assert_eq!(structure.unit_cell_atoms().len(), 4);
+    }
+
+    #[test]
+    fn space_group_symbol_or_number_supplies_missing_symmetry_loop() {
+        let without_loop = COPPER_CIF.replace(
+            "loop_\n_symmetry_equiv_pos_as_xyz\n'x, y, z'\n'x, y+1/2, z+1/2'\n'x+1/2, y, z+1/2'\n'x+1/2, y+1/2, z'\n",
+            "",
+        );
+        let structure = CifStructure::parse(&without_loop).expect("CIF should parse");
+        assert_eq!(structure.operations.len(), 192);
+        assert_eq!(structure.unit_cell_atoms().len(), 4);
+
+        let by_number = without_loop.replace(
+            "_symmetry_space_group_name_H-M 'F m -3 m'",
+            "_space_group_IT_number 225",
+        );
+        let structure = CifStructure::parse(&by_number).expect("CIF should parse");
+        assert_eq!(structure.operations.len(), 192);
+
+        let unknown = without_loop.replace("'F m -3 m'", "'X 9 9'");
+        assert!(matches!(
+            CifStructure::parse(&unknown),
+            Err(CifError::Symmetry(_))
+        ));
+    }
+
+    #[test]
+    fn fcc_cluster_has_twelve_nearest_neighbours() {
+        let structure = CifStructure::parse(COPPER_CIF).expect("CIF should parse");
+        let cluster = structure
+            .build_cluster(1, 2.6)
+            .expect("cluster should build");
+
+        assert_eq!(cluster.atom_count(), 13);
+        assert_eq!(cluster.xyz[0], [0.0, 0.0, 0.0]);
+        let nearest = 3.615 / 2.0_f64.sqrt();
+        for position in &cluster.xyz[1..] {
+            let distance = position.iter().map(|v| v * v).sum::<f64>().sqrt();
+            assert!((distance - nearest).abs() < 1.0e-9);
+        }
+    }
+
+    #[test]
+    fn rocksalt_cluster_is_centred_on_the_chosen_site() {
+        let structure = CifStructure::parse(ROCKSALT_CIF).expect("CIF should parse");
+        assert_eq!(structure.title, "Sodium chloride");
+
+        let cluster = structure
+            .build_cluster(2, 2.9)
+            .expect("cluster should build");
+        assert_eq!(cluster.atomic_numbers[0], 17);
+        assert_eq!(cluster.atom_count(), 7);
+        assert!(cluster.atomic_numbers[1..].iter().all(|z| *z == 11));
+    }
+
+    #[test]
+    fn partial_occupancy_keeps_the_majority_species() {
+        let source = COPPER_CIF.replace(
+            "Cu1 Cu 0.0 0.0 0.0 1.0\n",
+            "Cu1 Cu 0.0 0.0 0.0 0.3\nAu1 Au 0.0 0.0 0.0 0.7\n",
+        );
+        let structure = CifStructure::parse(&source).expect("CIF should parse");
+        let atoms = structure.unit_cell_atoms();
+        assert_eq!(atoms.len(), 4);
+        assert!(atoms.iter().all(|(site, _)| *site == 1));
+    }
+
+    #[test]
+    fn element_symbols_are_read_from_labels_and_type_symbols() {
+        assert_eq!(element_atomic_number("Cu2+"), Some(29));
+        assert_eq!(element_atomic_number("O1"), Some(8));
+        assert_eq!(element_atomic_number("Cl"), Some(17));
+        assert_eq!(element_atomic_number("1"), None);
+    }
+
+    #[test]
+    fn missing_cell_and_absorber_errors_are_reported() {
+        assert_eq!(
+            CifStructure::parse("data_x\nloop_\n_atom_site_fract_x\n0\n"),
+            Err(CifError::MissingItem("_cell_length_a"))
+        );
+        let structure = CifStructure::parse(COPPER_CIF).expect("CIF should parse");
+        assert_eq!(
+            structure.build_cluster(3, 3.0),
+            Err(CifError::AbsorberOutOfRange { site: 3, count: 1 })
+        );
+    }
+}
@@ -1,2 +1,4 @@
+pub mod cif;
 pub mod m_pot_generator;
 pub mod pot_generator_test;
+pub mod structure_files;
@@ -0,0 +1,934 @@
+use super::cif::{element_atomic_number, translation_repeats};
+use super::m_pot_generator::XyzFormat;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+const BOHR_RADIUS_ANGSTROM: f64 = 0.529_177_210_903;
+const DISTANCE_TOLERANCE: f64 = 1.0e-6;
+
+#[derive(Debug, Clone, thiserror::Error, PartialEq, Eq)]
+pub enum StructureImportError {
+    #[error("cannot determine the structure format of '{0}'")]
+    UnknownFormat(String),
+    #[error("{format}: unexpected end of input, expected {expected}")]
+    Truncated {
+        format: &'static str,
+        expected: &'static str,
+    },
+    #[error("{format}: invalid {field} '{value}' at line {line}")]
+    InvalidField {
+        format: &'static str,
+        field: &'static str,
+        value: String,
+        line: usize,
+    },
+    #[error("{format}: cannot determine the element of '{value}' at line {line}")]
+    UnknownElement {
+        format: &'static str,
+        value: String,
+        line: usize,
+    },
+    #[error("{format}: {reason}")]
+    Unsupported {
+        format: &'static str,
+        reason: String,
+    },
+    #[error("structure does not contain any atoms")]
+    NoAtoms,
+    #[error("lattice vectors do not describe a valid cell")]
+    DegenerateLattice,
+    #[error("absorber atom {atom} is out of range (atoms={count})")]
+    AbsorberOutOfRange { atom: usize, count: usize },
+}
+
+/// Structure file flavours understood by [`ImportedStructure::parse`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StructureFormat {
+    /// Plain XYZ; an extended-XYZ `Lattice=` comment is honoured when present.
+    Xyz,
+    ExtendedXyz,
+    /// VASP POSCAR/CONTCAR (VASP 5 species line, or VASP 4 with species in the title).
+    Poscar,
+    /// Quantum ESPRESSO pw.x input or output.
+    Espresso,
+}
+
+impl StructureFormat {
+    /// Guesses the format from the file name (`*.xyz`, `*.extxyz`, `POSCAR`,
+    /// `CONTCAR`, `*.vasp`, `*.pwi`, `*.pwo`). Generic `*.in` and `*.out`
+    /// names need an explicit format.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let name = path.file_name()?.to_str()?.to_ascii_lowercase();
+        if name.starts_with("poscar") || name.starts_with("contcar") {
+            return Some(Self::Poscar);
+        }
+        match name.rsplit_once('.').map(|(_, extension)| extension)? {
+            "xyz" => Some(Self::Xyz),
+            "extxyz" => Some(Self::ExtendedXyz),
+            "vasp" | "poscar" => Some(Self::Poscar),
+            "pwi" | "pwo" => Some(Self::Espresso),
+            _ => None,
+        }
+    }
+
+    pub fn parse(self, source: &str) -> Result<ImportedStructure, StructureImportError> {
+        match self {
+            Self::Xyz | Self::ExtendedXyz => ImportedStructure::parse_xyz(source),
+            Self::Poscar => ImportedStructure::parse_poscar(source),
+            Self::Espresso => ImportedStructure::parse_espresso(source),
+        }
+    }
+}
+
+/// Atoms in Cartesian Angstrom, with lattice vectors (rows) for periodic input.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportedStructure {
+    pub title: String,
+    pub atomic_numbers: Vec<i32>,
+    pub positions: Vec<[f64; 3]>,
+    pub lattice: Option<[[f64; 3]; 3]>,
+}
+
+impl ImportedStructure {
+    /// Reads the last frame of a (possibly multi-frame) XYZ or extended-XYZ file.
+    pub fn parse_xyz(source: &str) -> Result<Self, StructureImportError> {
+        Self::xyz_frames(source)?
+            .pop()
+            .ok_or(StructureImportError::NoAtoms)
+    }
+
+    /// Reads every frame of an XYZ trajectory, e.g. an MD run.
+    pub fn xyz_frames(source: &str) -> Result<Vec<Self>, StructureImportError> {
+        const FORMAT: &str = "XYZ";
+        let lines: Vec<&str> = source.lines().collect();
+        let mut frames = Vec::new();
+        let mut index = 0;
+        while index < lines.len() {
+            let header = lines[index].trim();
+            if header.is_empty() {
+                index += 1;
+                continue;
+            }
+            let count = header
+                .parse::<usize>()
+                .map_err(|_| invalid(FORMAT, "atom count", header, index + 1))?;
+            let comment = lines
+                .get(index + 1)
+                .ok_or(StructureImportError::Truncated {
+                    format: FORMAT,
+                    expected: "comment line",
+                })?;
+            let extended = ExtendedXyzComment::parse(comment, index + 2)?;
+
+            let mut atomic_numbers = Vec::with_capacity(count);
+            let mut positions = Vec::with_capacity(count);
+            for offset in 0..count {
+                let line_number = index + 3 + offset;
+                let line = lines
+                    .get(line_number - 1)
+                    .ok_or(StructureImportError::Truncated {
+                        format: FORMAT,
+                        expected: "atom line",
+                    })?;
+                let fields: Vec<&str> = line.split_whitespace().collect();
+                let species = fields
+                    .get(extended.species_column)
+                    .ok_or_else(|| invalid(FORMAT, "atom line", line.trim(), line_number))?;
+                atomic_numbers.push(species_atomic_number(FORMAT, species, line_number)?);
+                positions.push(read_vector(
+                    FORMAT,
+                    "position",
+                    &fields,
+                    extended.position_column,
+                    line_number,
+                )?);
+            }
+            if count == 0 {
+                return Err(StructureImportError::NoAtoms);
+            }
+            frames.push(Self {
+                title: extended.title,
+                atomic_numbers,
+                positions,
+                lattice: extended.lattice,
+            });
+            index += count + 2;
+        }
+        Ok(frames)
+    }
+
+    pub fn parse_poscar(source: &str) -> Result<Self, StructureImportError> {
+        const FORMAT: &str = "POSCAR";
+        let lines: Vec<&str> = source.lines().collect();
+        let line = |index: usize, expected: &'static str| {
+            lines
+                .get(index)
+                .copied()
+                .ok_or(StructureImportError::Truncated {
+                    format: FORMAT,
+                    expected,
+                })
+        };
+
+        let title = line(0, "title line")?.trim().to_string();
+        let scale_fields: Vec<&str> = line(1, "scaling factor")?.split_whitespace().collect();
+        let scale = read_number(FORMAT, "scaling factor", &scale_fields, 0, 2)?;
+        let mut lattice = [[0.0; 3]; 3];
+        for (axis, row) in lattice.iter_mut().enumerate() {
+            let fields: Vec<&str> = line(2 + axis, "lattice vector")?
+                .split_whitespace()
+                .collect();
+            *row = read_vector(FORMAT, "lattice vector", &fields, 0, 3 + axis)?;
+        }
+        // A negative scaling factor is the target cell volume.
+        let factor = if scale < 0.0 {
+            (-scale / determinant(&lattice).abs()).cbrt()
+        } else {
+            scale
+        };
+        lattice = lattice.map(|row| row.map(|value| value * factor));
+        if determinant(&lattice).abs() < 1.0e-12 {
+            return Err(StructureImportError::DegenerateLattice);
+        }
+
+        let mut cursor = 5;
+        let species_or_counts: Vec<&str> = line(cursor, "species or counts")?
+            .split_whitespace()
+            .collect();
+        let species: Vec<&str> = if species_or_counts
+            .first()
+            .is_some_and(|token| token.parse::<usize>().is_err())
+        {
+            cursor += 1;
+            species_or_counts
+        } else {
+            // VASP 4 files keep the species names in the title line.
+            title.split_whitespace().collect()
+        };
+        let count_fields: Vec<&str> = line(cursor, "atom counts")?.split_whitespace().collect();
+        let counts = count_fields
+            .iter()
+            .map(|value| {
+                value
+                    .parse::<usize>()
+                    .map_err(|_| invalid(FORMAT, "atom count", value, cursor + 1))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if species.len() < counts.len() {
+            return Err(StructureImportError::Unsupported {
+                format: FORMAT,
+                reason: "species names are missing (expected a VASP 5 species line)".to_string(),
+            });
+        }
+        cursor += 1;
+
+        let mut mode = line(cursor, "coordinate mode")?.trim();
+        if mode.starts_with(['S', 's']) {
+            cursor += 1;
+            mode = line(cursor, "coordinate mode")?.trim();
+        }
+        let cartesian = mode.starts_with(['C', 'c', 'K', 'k']);
+        cursor += 1;
+
+        let mut atomic_numbers = Vec::new();
+        let mut positions = Vec::new();
+        for (name, count) in species.iter().zip(&counts) {
+            let atomic_number = species_atomic_number(FORMAT, name, cursor)?;
+            for _ in 0..*count {
+                let fields: Vec<&str> = line(cursor, "atom position")?.split_whitespace().collect();
+                let coordinates = read_vector(FORMAT, "position", &fields, 0, cursor + 1)?;
+                let position = if cartesian {
+                    // Cartesian coordinates share the universal scaling factor.
+                    coordinates.map(|value| value * factor)
+                } else {
+                    fractional_to_cartesian(&lattice, coordinates)
+                };
+                atomic_numbers.push(atomic_number);
+                positions.push(position);
+                cursor += 1;
+            }
+        }
+        if atomic_numbers.is_empty() {
+            return Err(StructureImportError::NoAtoms);
+        }
+
+        Ok(Self {
+            title,
+            atomic_numbers,
+            positions,
+            lattice: Some(lattice),
+        })
+    }
+
+    /// Reads a pw.x input, or the final geometry of a pw.x output.
+    ///
+    /// The last `CELL_PARAMETERS`/`ATOMIC_POSITIONS` cards win, so relaxation
+    /// and MD outputs yield their latest step; SCF outputs fall back to the
+    /// printed crystal axes and `tau` positions.
+    pub fn parse_espresso(source: &str) -> Result<Self, StructureImportError> {
+        const FORMAT: &str = "Quantum ESPRESSO";
+        let lines: Vec<&str> = source.lines().collect();
+        let alat = espresso_alat(&lines);
+        let require_alat = || {
+            alat.ok_or_else(|| StructureImportError::Unsupported {
+                format: FORMAT,
+                reason: "alat units need celldm(1), A or a printed lattice parameter".to_string(),
+            })
+        };
+
+        let mut lattice: Option<[[f64; 3]; 3]> = None;
+        let mut positions_card: Option<(usize, String)> = None;
+        let mut printed_axes: [Option<[f64; 3]>; 3] = [None; 3];
+        let mut printed_atoms: Vec<(String, [f64; 3], usize)> = Vec::new();
+        let mut nat: Option<usize> = None;
+
+        for (index, raw) in lines.iter().enumerate() {
+            let line = raw.trim();
+            let upper = line.to_ascii_uppercase();
+            if upper.starts_with("CELL_PARAMETERS") {
+                let factor = match card_unit(&upper).as_deref() {
+                    Some("ANGSTROM") => 1.0,
+                    Some("BOHR") => BOHR_RADIUS_ANGSTROM,
+                    _ => card_alat(line)
+                        .map(|value| value * BOHR_RADIUS_ANGSTROM)
+                        .map_or_else(require_alat, Ok)?,
+                };
+                let mut rows = [[0.0; 3]; 3];
+                for (axis, row) in rows.iter_mut().enumerate() {
+                    let fields: Vec<&str> = lines
+                        .get(index + 1 + axis)
+                        .ok_or(StructureImportError::Truncated {
+                            format: FORMAT,
+                            expected: "CELL_PARAMETERS row",
+                        })?
+                        .split_whitespace()
+                        .collect();
+                    *row = read_vector(FORMAT, "cell vector", &fields, 0, index + 2 + axis)?
+                        .map(|value| value * factor);
+                }
+                lattice = Some(rows);
+            } else if upper.starts_with("ATOMIC_POSITIONS") {
+                positions_card = Some((index, card_unit(&upper).unwrap_or_else(|| "ALAT".into())));
+            } else if let Some(value) = line
+                .strip_prefix("number of atoms/cell")
+                .and_then(|rest| rest.trim().trim_start_matches('=').trim().parse().ok())
+            {
+                nat = Some(value);
+            } else if let Some(rest) = espresso_namelist_value(line, "nat") {
+                nat = rest.parse().ok().or(nat);
+            } else if let Some(axis) = ["a(1) =", "a(2) =", "a(3) ="]
+                .iter()
+                .position(|prefix| line.starts_with(prefix))
+            {
+                let fields = parenthesized_fields(line);
+                printed_axes[axis] =
+                    Some(read_vector(FORMAT, "crystal axis", &fields, 0, index + 1)?);
+            } else if line.contains("tau(") {
+                let species = line
+                    .split_whitespace()
+                    .nth(1)
+                    .unwrap_or_default()
+                    .to_string();
+                let fields =
+                    parenthesized_fields(line.split_once("tau(").map_or("", |(_, rest)| rest));
+                printed_atoms.push((
+                    species,
+                    read_vector(FORMAT, "tau", &fields, 0, index + 1)?,
+                    index + 1,
+                ));
+            }
+        }
+
+        if lattice.is_none() && printed_axes.iter().all(Option::is_some) {
+            let scale = require_alat()?;
+            lattice =
+                Some(printed_axes.map(|axis| axis.unwrap_or_default().map(|value| value * scale)));
+        }
+        if let Some(rows) = lattice
+            && determinant(&rows).abs() < 1.0e-12
+        {
+            return Err(StructureImportError::DegenerateLattice);
+        }
+
+        let mut atomic_numbers = Vec::new();
+        let mut positions = Vec::new();
+        if let Some((card_index, unit)) = positions_card {
+            let factor = match unit.as_str() {
+                "ANGSTROM" => Some(1.0),
+                "BOHR" => Some(BOHR_RADIUS_ANGSTROM),
+                "ALAT" => Some(require_alat()?),
+                "CRYSTAL" => None,
+                other => {
+                    return Err(StructureImportError::Unsupported {
+                        format: FORMAT,
+                        reason: format!("ATOMIC_POSITIONS unit '{}'", other.to_ascii_lowercase()),
+                    });
+                }
+            };
+            let crystal_lattice = match factor {
+                Some(_) => None,
+                None => Some(lattice.ok_or_else(|| StructureImportError::Unsupported {
+                    format: FORMAT,
+                    reason: "crystal coordinates need CELL_PARAMETERS".to_string(),
+                })?),
+            };
+            for (offset, raw) in lines.iter().enumerate().skip(card_index + 1) {
+                let fields: Vec<&str> = raw.split_whitespace().collect();
+                let line_number = offset + 1;
+                let is_atom_line = fields.len() >= 4
+                    && fields[1..4]
+                        .iter()
+                        .all(|value| value.parse::<f64>().is_ok());
+                if !is_atom_line || nat.is_some_and(|count| positions.len() == count) {
+                    break;
+                }
+                let coordinates = read_vector(FORMAT, "position", &fields, 1, line_number)?;
+                let position = match (factor, &crystal_lattice) {
+                    (Some(factor), _) => coordinates.map(|value| value * factor),
+                    (None, Some(rows)) => fractional_to_cartesian(rows, coordinates),
+                    (None, None) => unreachable!("crystal coordinates always carry a lattice"),
+                };
+                atomic_numbers.push(species_atomic_number(FORMAT, fields[0], line_number)?);
+                positions.push(position);
+            }
+        } else if !printed_atoms.is_empty() {
+            let scale = require_alat()?;
+            let count = nat.unwrap_or(printed_atoms.len()).min(printed_atoms.len());
+            for (species, tau, line_number) in printed_atoms.into_iter().take(count) {
+                atomic_numbers.push(species_atomic_number(FORMAT, &species, line_number)?);
+                positions.push(tau.map(|value| value * scale));
+            }
+        }
+        if atomic_numbers.is_empty() {
+            return Err(StructureImportError::NoAtoms);
+        }
+
+        let title = lines
+            .iter()
+            .find_map(|line| espresso_namelist_value(line.trim(), "prefix"))
+            .map(|value| value.trim_matches(['\'', '"']).to_string())
+            .unwrap_or_else(|| "Quantum ESPRESSO structure".to_string());
+        Ok(Self {
+            title,
+            atomic_numbers,
+            positions,
+            lattice,
+        })
+    }
+
+    /// Builds a cluster of `radius` Angstrom around atom `absorber` (1-based).
+    ///
+    /// Periodic structures are replicated with their lattice; molecular ones
+    /// are cut from the listed atoms. The absorber comes first and the rest are
+    /// sorted by distance, ready for `gen_pot_from_xyz` with absorber index 1.
+    pub fn build_cluster(
+        &self,
+        absorber: usize,
+        radius: f64,
+    ) -> Result<XyzFormat, StructureImportError> {
+        let origin = absorber
+            .checked_sub(1)
+            .and_then(|index| self.positions.get(index))
+            .copied()
+            .ok_or(StructureImportError::AbsorberOutOfRange {
+                atom: absorber,
+                count: self.positions.len(),
+            })?;
+
+        let repeats = match &self.lattice {
+            Some(lattice) => translation_repeats(lattice, radius),
+            None => [0; 3],
+        };
+        let lattice = self.lattice.unwrap_or([[0.0; 3]; 3]);
+
+        let mut cluster: BTreeMap<(i64, usize), (i32, [f64; 3])> = BTreeMap::new();
+        let mut order = 0_usize;
+        for (atomic_number, position) in self.atomic_numbers.iter().zip(&self.positions) {
+            for na in -repeats[0]..=repeats[0] {
+                for nb in -repeats[1]..=repeats[1] {
+                    for nc in -repeats[2]..=repeats[2] {
+                        let shift =
+                            fractional_to_cartesian(&lattice, [na as f64, nb as f64, nc as f64]);
+                        let relative: [f64; 3] =
+                            std::array::from_fn(|axis| position[axis] + shift[axis] - origin[axis]);
+                        let distance = relative.iter().map(|v| v * v).sum::<f64>().sqrt();
+                        if distance > radius + DISTANCE_TOLERANCE {
+                            continue;
+                        }
+                        let key = ((distance / DISTANCE_TOLERANCE).round() as i64, order);
+                        order += 1;
+                        cluster.insert(key, (*atomic_number, relative));
+                    }
+                }
+            }
+        }
+
+        let mut atomic_numbers = Vec::with_capacity(cluster.len());
+        let mut xyz = Vec::with_capacity(cluster.len());
+        for (atomic_number, relative) in cluster.into_values() {
+            atomic_numbers.push(atomic_number);
+            xyz.push(relative.map(|value| if value.abs() < 1.0e-9 { 0.0 } else { value }));
+        }
+
+        Ok(XyzFormat {
+            title: self.title.clone(),
+            atomic_numbers,
+            xyz,
+            potential_indices: Vec::new(),
+            potential_numeric_labels: Vec::new(),
+            potential_string_labels: Vec::new(),
+        })
+    }
+}
+
+/// The parts of an extended-XYZ comment line this reader uses.
+struct ExtendedXyzComment {
+    title: String,
+    lattice: Option<[[f64; 3]; 3]>,
+    species_column: usize,
+    position_column: usize,
+}
+
+impl ExtendedXyzComment {
+    fn parse(comment: &str, line_number: usize) -> Result<Self, StructureImportError> {
+        const FORMAT: &str = "extended XYZ";
+        let pairs = key_value_pairs(comment);
+        let mut parsed = Self {
+            title: comment.trim().to_string(),
+            lattice: None,
+            species_column: 0,
+            position_column: 1,
+        };
+        if pairs.is_empty() {
+            return Ok(parsed);
+        }
+
+        if let Some(value) = lookup(&pairs, "lattice") {
+            let fields: Vec<&str> = value.split_whitespace().collect();
+            if fields.len() != 9 {
+                return Err(invalid(FORMAT, "Lattice", value, line_number));
+            }
+            let mut rows = [[0.0; 3]; 3];
+            for (axis, row) in rows.iter_mut().enumerate() {
+                *row = read_vector(FORMAT, "Lattice", &fields, axis * 3, line_number)?;
+            }
+            if determinant(&rows).abs() < 1.0e-12 {
+                return Err(StructureImportError::DegenerateLattice);
+            }
+            parsed.lattice = Some(rows);
+        }
+        if let Some(value) = lookup(&pairs, "properties") {
+            let fields: Vec<&str> = value.split(':').collect();
+            let mut column = 0;
+            let mut species = None;
+            let mut position = None;
+            for property in fields.chunks(3) {
+                let [name, _, width] = property else {
+                    return Err(invalid(FORMAT, "Properties", value, line_number));
+                };
+                let width = width
+                    .parse::<usize>()
+                    .map_err(|_| invalid(FORMAT, "Properties", value, line_number))?;
+                match name.to_ascii_lowercase().as_str() {
+                    "species" => species = Some(column),
+                    "pos" => position = Some(column),
+                    _ => {}
+                }
+                column += width;
+            }
+            parsed.species_column = species.ok_or_else(|| StructureImportError::Unsupported {
+                format: FORMAT,
+                reason: "Properties has no species column".to_string(),
+            })?;
+            parsed.position_column = position.ok_or_else(|| StructureImportError::Unsupported {
+                format: FORMAT,
+                reason: "Properties has no pos column".to_string(),
+            })?;
+        }
+        let remainder: Vec<String> = pairs
+            .iter()
+            .filter(|(key, _)| {
+                !matches!(
+                    key.to_ascii_lowercase().as_str(),
+                    "lattice" | "properties" | "pbc"
+                )
+            })
+            .map(|(key, value)| {
+                if value.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}={}", key, value)
+                }
+            })
+            .collect();
+        parsed.title = lookup(&pairs, "comment")
+            .map(str::to_string)
+            .unwrap_or_else(|| remainder.join(" "));
+        Ok(parsed)
+    }
+}
+
+/// Splits `key=value key2="quoted value" flag` comment lines. Plain comments
+/// without any `=` yield no pairs.
+fn key_value_pairs(comment: &str) -> Vec<(String, String)> {
+    if !comment.contains('=') {
+        return Vec::new();
+    }
+    let mut pairs = Vec::new();
+    let mut chars = comment.trim().chars().peekable();
+    while chars.peek().is_some() {
+        while chars.next_if(|ch| ch.is_whitespace()).is_some() {}
+        let key: String =
+            std::iter::from_fn(|| chars.next_if(|ch| *ch != '=' && !ch.is_whitespace())).collect();
+        if key.is_empty() {
+            break;
+        }
+        let mut value = String::new();
+        if chars.next_if_eq(&'=').is_some() {
+            match chars.next_if(|ch| *ch == '"' || *ch == '\'') {
+                Some(quote) => {
+                    value = std::iter::from_fn(|| chars.next_if(|ch| *ch != quote)).collect();
+                    chars.next();
+                }
+                None => {
+                    value =
+                        std::iter::from_fn(|| chars.next_if(|ch| !ch.is_whitespace())).collect();
+                }
+            }
+        }
+        pairs.push((key, value));
+    }
+    pairs
+}
+
+fn lookup<'a>(pairs: &'a [(String, String)], key: &str) -> Option<&'a str> {
+    pairs
+        .iter()
+        .find(|(candidate, _)| candidate.eq_ignore_ascii_case(key))
+        .map(|(_, value)| value.as_str())
+}
+
+/// Lattice parameter in Angstrom from `celldm(1)` (Bohr), `A` (Angstrom) or
+/// the `lattice parameter (alat)` line of pw.x output.
+fn espresso_alat(lines: &[&str]) -> Option<f64> {
+    lines.iter().find_map(|raw| {
+        let line = raw.trim();
+        if let Some(rest) = line.strip_prefix("lattice parameter (alat)") {
+            let value = rest
+                .trim_start_matches([' ', '='])
+                .split_whitespace()
+                .next()?;
+            return fortran_number(value).map(|bohr| bohr * BOHR_RADIUS_ANGSTROM);
+        }
+        if let Some(value) = espresso_namelist_value(line, "celldm(1)") {
+            return fortran_number(&value).map(|bohr| bohr * BOHR_RADIUS_ANGSTROM);
+        }
+        espresso_namelist_value(line, "a").and_then(|value| fortran_number(&value))
+    })
+}
+
+/// The value assigned to `key` in a namelist line such as `nat = 2, ntyp = 1`.
+fn espresso_namelist_value(line: &str, key: &str) -> Option<String> {
+    line.split(',').find_map(|assignment| {
+        let (name, value) = assignment.split_once('=')?;
+        name.trim()
+            .eq_ignore_ascii_case(key)
+            .then(|| value.trim().to_string())
+    })
+}
+
+/// `CELL_PARAMETERS (alat= 10.2)` carries its own lattice parameter in Bohr.
+fn card_alat(line: &str) -> Option<f64> {
+    let (_, rest) = line.split_once("alat=")?;
+    fortran_number(rest.trim().trim_end_matches([')', '}']).trim())
+}
+
+/// Unit of a `CARD (unit)` or `CARD {unit}` header, upper-cased.
+fn card_unit(header: &str) -> Option<String> {
+    let unit: String = header
+        .split_whitespace()
+        .skip(1)
+        .collect::<Vec<_>>()
+        .join(" ")
+        .trim_matches(['(', ')', '{', '}'])
+        .split(['=', ' '])
+        .next()?
+        .to_string();
+    (!unit.is_empty()).then_some(unit)
+}
+
+/// Numbers inside the first `( ... )` of a pw.x output line.
+fn parenthesized_fields(line: &str) -> Vec<&str> {
+    line.split_once('(')
+        .and_then(|(_, rest)| rest.rsplit_once(')'))
+        .map(|(inside, _)| inside)
+        .unwrap_or_default()
+        .split_whitespace()
+        .filter(|token| token.parse::<f64>().is_ok())
+        .collect()
+}
+
+fn fortran_number(value: &str) -> Option<f64> {
+    value
+        .trim()
+        .trim_end_matches(',')
+        .replace(['d', 'D'], "e")
+        .parse()
+        .ok()
+}
+
+fn species_atomic_number(
+    format: &'static str,
+    species: &str,
+    line: usize,
+) -> Result<i32, StructureImportError> {
+    let atomic_number = match species.parse::<i32>() {
+        Ok(value) if (1..=103).contains(&value) => Some(value),
+        Ok(_) => None,
+        Err(_) => element_atomic_number(species),
+    };
+    atomic_number.ok_or_else(|| StructureImportError::UnknownElement {
+        format,
+        value: species.to_string(),
+        line,
+    })
+}
+
+fn read_number(
+    format: &'static str,
+    field: &'static str,
+    fields: &[&str],
+    index: usize,
+    line: usize,
+) -> Result<f64, StructureImportError> {
+    let value = fields.get(index).copied().unwrap_or_default();
+    fortran_number(value).ok_or_else(|| invalid(format, field, value, line))
+}
+
+fn read_vector(
+    format: &'static str,
+    field: &'static str,
+    fields: &[&str],
+    start: usize,
+    line: usize,
+) -> Result<[f64; 3], StructureImportError> {
+    Ok([
+        read_number(format, field, fields, start, line)?,
+        read_number(format, field, fields, start + 1, line)?,
+        read_number(format, field, fields, start + 2, line)?,
+    ])
+}
+
+fn invalid(
+    format: &'static str,
+    field: &'static str,
+    value: &str,
+    line: usize,
+) -> StructureImportError {
+    StructureImportError::InvalidField {
+        format,
+        field,
+        value: value.to_string(),
+        line,
+    }
+}
+
+fn fractional_to_cartesian(lattice: &[[f64; 3]; 3], fractional: [f64; 3]) -> [f64; 3] {
+    std::array::from_fn(|axis| (0..3).map(|row| fractional[row] * lattice[row][axis]).sum())
+}
+
+fn determinant(m: &[[f64; 3]; 3]) -> f64 {
+    m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+        - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+        + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ImportedStructure, StructureFormat, StructureImportError};
+    use std::path::Path;
+
+    fn assert_close(actual: [f64; 3], expected: [f64; 3]) {
+        for axis in 0..3 {
+            assert!(
+                (actual[axis] - expected[axis]).abs() < 1.0e-6,
+                "{:?} != {:?}",
+                actual,
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn formats_are_detected_from_file_names() {
+        assert_eq!(
+            StructureFormat::from_path(Path::new("snap.xyz")),
+            Some(StructureFormat::Xyz)
+        );
+        assert_eq!(
+            StructureFormat::from_path(Path::new("run/CONTCAR")),
+            Some(StructureFormat::Poscar)
+        );
+        assert_eq!(
+            StructureFormat::from_path(Path::new("relax.pwo")),
+            Some(StructureFormat::Espresso)
+        );
+        assert_eq!(StructureFormat::from_path(Path::new("notes.txt")), None);
+        assert_eq!(StructureFormat::from_path(Path::new("scf.in")), None);
+        assert_eq!(StructureFormat::from_path(Path::new("dmdw.out")), None);
+    }
+
+    #[test]
+    fn plain_xyz_keeps_the_last_frame_of_a_trajectory() {
+        let source = "2\nframe 1\nO 0.0 0.0 0.0\nH 0.0 0.0 0.96\n2\nframe 2\n8 0.0 0.0 0.0\nH 0.0 0.0 1.01\n";
+        let frames = ImportedStructure::xyz_frames(source).expect("xyz should parse");
+        assert_eq!(frames.len(), 2);
+
+        let structure = ImportedStructure::parse_xyz(source).expect("xyz should parse");
+        assert_eq!(structure.title, "frame 2");
+        assert_eq!(structure.atomic_numbers, vec![8, 1]);
+        assert_close(structure.positions[1], [0.0, 0.0, 1.01]);
+        assert!(structure.lattice.is_none());
+
+        let cluster = structure
+            .build_cluster(2, 5.0)
+            .expect("cluster should build");
+        assert_eq!(cluster.atomic_numbers, vec![1, 8]);
+        assert_close(cluster.xyz[1], [0.0, 0.0, -1.01]);
+    }
+
+    #[test]
+    fn extended_xyz_reads_lattice_and_property_columns() {
+        let source = "\
+2
+Lattice=\"3.615 0 0 0 3.615 0 0 0 3.615\" Properties=id:I:1:species:S:1:pos:R:3 pbc=\"T T T\" Time=10.0
+1 Cu 0.0 0.0 0.0
+2 Cu 1.8075 1.8075 0.0
+";
+        let structure = ImportedStructure::parse_xyz(source).expect("extxyz should parse");
+        assert_eq!(structure.atomic_numbers, vec![29, 29]);
+        assert_close(structure.positions[1], [1.8075, 1.8075, 0.0]);
+        assert_close(structure.lattice.expect("lattice")[2], [0.0, 0.0, 3.615]);
+        assert_eq!(structure.title, "Time=10.0");
+
+        let cluster = structure
+            .build_cluster(1, 2.6)
+            .expect("cluster should build");
+        // Simple tetragonal sub-lattice of fcc: 4 in-plane neighbours at a/sqrt(2).
+        assert_eq!(cluster.atom_count(), 5);
+    }
+
+    #[test]
+    fn poscar_reads_direct_and_cartesian_coordinates() {
+        let direct = "\
+NaCl
+5.64
+1.0 0.0 0.0
+0.0 1.0 0.0
+0.0 0.0 1.0
+Na Cl
+1 1
+Direct
+0.0 0.0 0.0
+0.5 0.5 0.5
+";
+        let structure = ImportedStructure::parse_poscar(direct).expect("POSCAR should parse");
+        assert_eq!(structure.atomic_numbers, vec![11, 17]);
+        assert_close(structure.positions[1], [2.82, 2.82, 2.82]);
+
+        let vasp4_cartesian = "\
+Na Cl
+-179.406144
+1.0 0.0 0.0
+0.0 1.0 0.0
+0.0 0.0 1.0
+1 1
+Selective dynamics
+Cartesian
+0.0 0.0 0.0 T T T
+0.5 0.5 0.5 F F F
+";
+        let structure =
+            ImportedStructure::parse_poscar(vasp4_cartesian).expect("POSCAR should parse");
+        assert_close(structure.lattice.expect("lattice")[0], [5.64, 0.0, 0.0]);
+        assert_close(structure.positions[1], [2.82, 2.82, 2.82]);
+    }
+
+    #[test]
+    fn espresso_output_uses_the_final_relaxed_geometry() {
+        let source = "\
+     lattice parameter (alat)  =      10.2000  a.u.
+     number of atoms/cell      =            2
+     crystal axes: (cart. coord. in units of alat)
+               a(1) = (  -0.500000   0.000000   0.500000 )
+               a(2) = (   0.000000   0.500000   0.500000 )
+               a(3) = (  -0.500000   0.500000   0.000000 )
+     site n.     atom                  positions (alat units)
+         1           Si  tau(   1) = (   0.0000000   0.0000000   0.0000000  )
+         2           Si  tau(   2) = (   0.2500000   0.2500000   0.2500000  )
+ATOMIC_POSITIONS (crystal)
+Si            0.0000000000        0.0000000000        0.0000000000
+Si            0.2600000000        0.2600000000        0.2600000000
+     End of BFGS Geometry Optimization
+";
+        let structure =
+            ImportedStructure::parse_espresso(source).expect("pw.x output should parse");
+        let alat = 10.2 * 0.529_177_210_903;
+        assert_eq!(structure.atomic_numbers, vec![14, 14]);
+        assert_close(
+            structure.positions[1],
+            [-0.26 * alat, 0.26 * alat, 0.26 * alat],
+        );
+
+        let scf_only = source.split("ATOMIC_POSITIONS").next().unwrap_or_default();
+        let structure =
+            ImportedStructure::parse_espresso(scf_only).expect("scf output should parse");
+        assert_close(
+            structure.positions[1],
+            [0.25 * alat, 0.25 * alat, 0.25 * alat],
+        );
+    }
+
+    #[test]
+    fn espresso_input_reads_cell_parameters_in_angstrom() {
+        let source = "\
+&CONTROL
+  prefix = 'zno'
+/
+&SYSTEM
+  ibrav = 0, nat = 2, ntyp = 2
+/
+CELL_PARAMETERS angstrom
+  3.25 0.0 0.0
+  -1.625 2.8145 0.0
+  0.0 0.0 5.2
+ATOMIC_POSITIONS {angstrom}
+Zn1 0.0 1.8763 0.0
+O   0.0 1.8763 1.98
+K_POINTS automatic
+4 4 4 0 0 0
+";
+        let structure = ImportedStructure::parse_espresso(source).expect("pw.x input should parse");
+        assert_eq!(structure.title, "zno");
+        assert_eq!(structure.atomic_numbers, vec![30, 8]);
+        assert_close(structure.positions[1], [0.0, 1.8763, 1.98]);
+        assert!(structure.lattice.is_some());
+    }
+
+    #[test]
+    fn malformed_structures_are_rejected() {
+        assert!(matches!(
+            ImportedStructure::parse_xyz("2\ncomment\nXx 0 0 0\nH 0 0 1\n"),
+            Err(StructureImportError::UnknownElement { line: 3, .. })
+        ));
+        assert!(matches!(
+            ImportedStructure::parse_xyz("3\ncomment\nO 0 0 0\n"),
+            Err(StructureImportError::Truncated { .. })
+        ));
+        let structure = ImportedStructure::parse_xyz("1\n\nO 0 0 0\n").expect("xyz should parse");
+        assert_eq!(
+            structure.build_cluster(2, 3.0),
+            Err(StructureImportError::AbsorberOutOfRange { atom: 2, count: 1 })
+        );
+    }
+}
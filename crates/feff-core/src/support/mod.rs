@@ -13,4 +13,5 @@ pub mod math;
 pub mod mkgtr;
 pub mod opconsat;
 pub mod rhorrp;
+pub mod symmetry;
 pub mod tdlda;
@@ -0,0 +1,386 @@
+use super::SymmetryError;
+use super::operation::SymmetryOperation;
+
+// Translations are kept in twelfths of a lattice vector so group closure is exact.
+const TWELFTHS: i32 = 12;
+const MAX_GROUP_ORDER: usize = 192;
+
+type Matrix = [[i32; 3]; 3];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Seitz {
+    rotation: Matrix,
+    translation: [i32; 3],
+}
+
+impl Seitz {
+    const IDENTITY: Self = Self {
+        rotation: [[1, 0, 0], [0, 1, 0], [0, 0, 1]],
+        translation: [0; 3],
+    };
+
+    fn compose(&self, other: &Self) -> Self {
+        let rotation = std::array::from_fn(|row| {
+            std::array::from_fn(|column| {
+                (0..3)
+                    .map(|inner| self.rotation[row][inner] * other.rotation[inner][column])
+                    .sum()
+            })
+        });
+        let translation = std::array::from_fn(|row| {
+            let rotated: i32 = (0..3)
+                .map(|inner| self.rotation[row][inner] * other.translation[inner])
+                .sum();
+            (rotated + self.translation[row]).rem_euclid(TWELFTHS)
+        });
+        Self {
+            rotation,
+            translation,
+        }
+    }
+
+    fn to_operation(self) -> SymmetryOperation {
+        SymmetryOperation {
+            rotation: self.rotation.map(|row| row.map(f64::from)),
+            translation: self
+                .translation
+                .map(|value| f64::from(value) / f64::from(TWELFTHS)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Axis {
+    X,
+    Y,
+    Z,
+    /// Face diagonal `'` (a-b for a z principal axis).
+    Prime,
+    /// Face diagonal `"` (a+b for a z principal axis).
+    DoublePrime,
+    /// Body diagonal `*`.
+    Body,
+}
+
+/// Expands a Hall symbol (e.g. `-P 2ybc`, `F 4d 2 3 -1d`, `P 31 2c (0 0 1)`)
+/// into the full list of operations, identity first.
+pub fn operations_from_hall(symbol: &str) -> Result<Vec<SymmetryOperation>, SymmetryError> {
+    let invalid = |reason: &str| SymmetryError::InvalidHallSymbol {
+        symbol: symbol.to_string(),
+        reason: reason.to_string(),
+    };
+
+    let (body, shift) = match symbol.split_once('(') {
+        Some((body, rest)) => {
+            let values = rest
+                .trim_end()
+                .trim_end_matches(')')
+                .split_whitespace()
+                .map(|token| token.parse::<i32>())
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|_| invalid("origin shift must be integers in twelfths"))?;
+            let shift: [i32; 3] = values
+                .try_into()
+                .map_err(|_| invalid("origin shift needs three components"))?;
+            (body, Some(shift))
+        }
+        None => (symbol, None),
+    };
+
+    let mut tokens = body.split_whitespace();
+    let lattice = tokens.next().ok_or_else(|| invalid("empty symbol"))?;
+    let (centrosymmetric, lattice) = match lattice.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, lattice),
+    };
+    let mut generators = centering_translations(lattice)
+        .ok_or_else(|| invalid("unknown lattice symbol"))?
+        .into_iter()
+        .map(|translation| Seitz {
+            rotation: Seitz::IDENTITY.rotation,
+            translation,
+        })
+        .collect::<Vec<_>>();
+    if centrosymmetric {
+        generators.push(Seitz {
+            rotation: negate(Seitz::IDENTITY.rotation),
+            translation: [0; 3],
+        });
+    }
+
+    let mut previous_order = 0;
+    let mut principal = Axis::Z;
+    for (position, token) in tokens.enumerate() {
+        let (generator, order, axis) = parse_matrix_symbol(token, position, previous_order)
+            .ok_or_else(|| invalid(&format!("cannot read rotation '{}'", token)))?;
+        let generator = orient(generator, order, axis, principal)
+            .ok_or_else(|| invalid(&format!("unsupported axis in '{}'", token)))?;
+        if position == 0 {
+            principal = axis;
+        }
+        previous_order = order;
+        generators.push(generator);
+    }
+
+    if let Some(shift) = shift {
+        generators = generators
+            .into_iter()
+            .map(|generator| shift_origin(generator, shift))
+            .collect();
+    }
+
+    let group = close_group(&generators).ok_or_else(|| invalid("group does not close"))?;
+    Ok(group.into_iter().map(Seitz::to_operation).collect())
+}
+
+fn centering_translations(lattice: &str) -> Option<Vec<[i32; 3]>> {
+    let vectors = match lattice.to_ascii_uppercase().as_str() {
+        "P" => vec![],
+        "A" => vec![[0, 6, 6]],
+        "B" => vec![[6, 0, 6]],
+        "C" => vec![[6, 6, 0]],
+        "I" => vec![[6, 6, 6]],
+        "R" => vec![[8, 4, 4], [4, 8, 8]],
+        "F" => vec![[0, 6, 6], [6, 0, 6], [6, 6, 0]],
+        _ => return None,
+    };
+    Some(vectors)
+}
+
+/// Reads one matrix symbol into a z-axis prototype plus its order and axis.
+/// Translations are returned along the prototype axis and re-oriented later.
+fn parse_matrix_symbol(
+    token: &str,
+    position: usize,
+    previous_order: i32,
+) -> Option<(HallMatrix, i32, Axis)> {
+    let (improper, rest) = match token.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, token),
+    };
+    let mut chars = rest.chars().peekable();
+    let order = chars.next()?.to_digit(10)? as i32;
+    if !matches!(order, 1 | 2 | 3 | 4 | 6) {
+        return None;
+    }
+    let screw = match chars.peek() {
+        Some(digit @ '1'..='5') => {
+            let value = digit.to_digit(10)? as i32;
+            chars.next();
+            value
+        }
+        _ => 0,
+    };
+
+    let mut axis = None;
+    let mut translation = [0; 3];
+    for symbol in chars {
+        match symbol {
+            'x' => axis = Some(Axis::X),
+            'y' => axis = Some(Axis::Y),
+            'z' => axis = Some(Axis::Z),
+            '\'' => axis = Some(Axis::Prime),
+            '"' => axis = Some(Axis::DoublePrime),
+            '*' => axis = Some(Axis::Body),
+            'a' => translation[0] += 6,
+            'b' => translation[1] += 6,
+            'c' => translation[2] += 6,
+            'n' => translation = add(translation, [6, 6, 6]),
+            'u' => translation[0] += 3,
+            'v' => translation[1] += 3,
+            'w' => translation[2] += 3,
+            'd' => translation = add(translation, [3, 3, 3]),
+            _ => return None,
+        }
+    }
+
+    let axis = match axis {
+        Some(axis) => axis,
+        None => match (position, order, previous_order) {
+            (0, _, _) => Axis::Z,
+            (1, 2, 2 | 4) => Axis::X,
+            (1, 2, 3 | 6) => Axis::Prime,
+            (2, 3, _) => Axis::Body,
+            (_, 1, _) => Axis::Z,
+            _ => return None,
+        },
+    };
+
+    Some((
+        HallMatrix {
+            improper,
+            screw,
+            translation,
+        },
+        order,
+        axis,
+    ))
+}
+
+#[derive(Debug, Clone, Copy)]
+struct HallMatrix {
+    improper: bool,
+    screw: i32,
+    translation: [i32; 3],
+}
+
+fn orient(matrix: HallMatrix, order: i32, axis: Axis, principal: Axis) -> Option<Seitz> {
+    let rotation = rotation_matrix(order, axis, principal)?;
+    let direction = match axis {
+        Axis::X => [1, 0, 0],
+        Axis::Y => [0, 1, 0],
+        Axis::Z => [0, 0, 1],
+        _ if matrix.screw == 0 => [0, 0, 0],
+        _ => return None,
+    };
+    let screw = direction.map(|component| component * matrix.screw * TWELFTHS / order.max(1));
+    let rotation = if matrix.improper {
+        negate(rotation)
+    } else {
+        rotation
+    };
+    Some(Seitz {
+        rotation,
+        translation: add(matrix.translation, screw).map(|value| value.rem_euclid(TWELFTHS)),
+    })
+}
+
+fn rotation_matrix(order: i32, axis: Axis, principal: Axis) -> Option<Matrix> {
+    let matrix = match (order, axis) {
+        (1, _) => Seitz::IDENTITY.rotation,
+        (2, Axis::X) => [[1, 0, 0], [0, -1, 0], [0, 0, -1]],
+        (3, Axis::X) => [[1, 0, 0], [0, 0, -1], [0, 1, -1]],
+        (4, Axis::X) => [[1, 0, 0], [0, 0, -1], [0, 1, 0]],
+        (6, Axis::X) => [[1, 0, 0], [0, 1, -1], [0, 1, 0]],
+        (2, Axis::Y) => [[-1, 0, 0], [0, 1, 0], [0, 0, -1]],
+        (3, Axis::Y) => [[-1, 0, 1], [0, 1, 0], [-1, 0, 0]],
+        (4, Axis::Y) => [[0, 0, 1], [0, 1, 0], [-1, 0, 0]],
+        (6, Axis::Y) => [[0, 0, 1], [0, 1, 0], [-1, 0, 1]],
+        (2, Axis::Z) => [[-1, 0, 0], [0, -1, 0], [0, 0, 1]],
+        (3, Axis::Z) => [[0, -1, 0], [1, -1, 0], [0, 0, 1]],
+        (4, Axis::Z) => [[0, -1, 0], [1, 0, 0], [0, 0, 1]],
+        (6, Axis::Z) => [[1, -1, 0], [1, 0, 0], [0, 0, 1]],
+        (2, Axis::Prime) => match principal {
+            Axis::X => [[-1, 0, 0], [0, 0, -1], [0, -1, 0]],
+            Axis::Y => [[0, 0, -1], [0, -1, 0], [-1, 0, 0]],
+            Axis::Z => [[0, -1, 0], [-1, 0, 0], [0, 0, -1]],
+            _ => return None,
+        },
+        (2, Axis::DoublePrime) => match principal {
+            Axis::X => [[-1, 0, 0], [0, 0, 1], [0, 1, 0]],
+            Axis::Y => [[0, 0, 1], [0, -1, 0], [1, 0, 0]],
+            Axis::Z => [[0, 1, 0], [1, 0, 0], [0, 0, -1]],
+            _ => return None,
+        },
+        (3, Axis::Body) => [[0, 0, 1], [1, 0, 0], [0, 1, 0]],
+        _ => return None,
+    };
+    Some(matrix)
+}
+
+fn shift_origin(generator: Seitz, shift: [i32; 3]) -> Seitz {
+    let rotated: [i32; 3] = std::array::from_fn(|row| {
+        (0..3)
+            .map(|column| generator.rotation[row][column] * shift[column])
+            .sum()
+    });
+    Seitz {
+        rotation: generator.rotation,
+        translation: std::array::from_fn(|axis| {
+            (generator.translation[axis] + shift[axis] - rotated[axis]).rem_euclid(TWELFTHS)
+        }),
+    }
+}
+
+fn close_group(generators: &[Seitz]) -> Option<Vec<Seitz>> {
+    let mut group = vec![Seitz::IDENTITY];
+    for generator in generators {
+        if !group.contains(generator) {
+            group.push(*generator);
+        }
+    }
+
+    let mut start = 0;
+    while start < group.len() {
+        let end = group.len();
+        for left in 0..end {
+            for right in 0..end {
+                if left < start && right < start {
+                    continue;
+                }
+                let product = group[left].compose(&group[right]);
+                if !group.contains(&product) {
+                    group.push(product);
+                    if group.len() > MAX_GROUP_ORDER {
+                        return None;
+                    }
+                }
+            }
+        }
+        start = end;
+    }
+    Some(group)
+}
+
+fn negate(matrix: Matrix) -> Matrix {
+    matrix.map(|row| row.map(|value| -value))
+}
+
+fn add(left: [i32; 3], right: [i32; 3]) -> [i32; 3] {
+    std::array::from_fn(|axis| left[axis] + right[axis])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::operations_from_hall;
+    use crate::support::symmetry::SymmetryError;
+    use crate::support::symmetry::operation::SymmetryOperation;
+
+    fn contains(operations: &[SymmetryOperation], xyz: &str) -> bool {
+        let expected = SymmetryOperation::parse_xyz(xyz).expect("reference operation");
+        operations
+            .iter()
+            .any(|operation| operation.equivalent_to(&expected))
+    }
+
+    #[test]
+    fn monoclinic_p21_c_has_screw_and_glide() {
+        let operations = operations_from_hall("-P 2ybc").expect("Hall symbol should expand");
+        assert_eq!(operations.len(), 4);
+        assert!(contains(&operations, "-x, y+1/2, -z+1/2"));
+        assert!(contains(&operations, "x, -y+1/2, z+1/2"));
+        assert!(contains(&operations, "-x, -y, -z"));
+    }
+
+    #[test]
+    fn hexagonal_and_cubic_groups_reach_full_order() {
+        assert_eq!(operations_from_hall("-P 6c 2c").expect("P63/mmc").len(), 24);
+        assert_eq!(operations_from_hall("-F 4 2 3").expect("Fm-3m").len(), 192);
+        assert_eq!(operations_from_hall("-R 3 2\"").expect("R-3m").len(), 36);
+        let diamond = operations_from_hall("-F 4vw 2vw 3").expect("Fd-3m");
+        assert_eq!(diamond.len(), 192);
+        assert!(contains(&diamond, "-x, -y, -z"));
+        let diamond_origin_1 = operations_from_hall("F 4d 2 3 -1d").expect("Fd-3m:1");
+        assert_eq!(diamond_origin_1.len(), 192);
+        assert!(contains(&diamond_origin_1, "-x+1/4, -y+1/4, -z+1/4"));
+    }
+
+    #[test]
+    fn origin_shift_moves_twofold_axes() {
+        let operations = operations_from_hall("P 31 2c (0 0 1)").expect("P3112");
+        assert_eq!(operations.len(), 6);
+        assert!(contains(&operations, "-y, x-y, z+1/3"));
+    }
+
+    #[test]
+    fn malformed_symbols_are_rejected() {
+        assert!(matches!(
+            operations_from_hall("Q 2"),
+            Err(SymmetryError::InvalidHallSymbol { .. })
+        ));
+        assert!(matches!(
+            operations_from_hall("P 5"),
+            Err(SymmetryError::InvalidHallSymbol { .. })
+        ));
+    }
+}
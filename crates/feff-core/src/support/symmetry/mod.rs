@@ -0,0 +1,15 @@
+pub mod hall;
+pub mod operation;
+pub mod point_group;
+pub mod sites;
+pub mod space_groups;
+
+#[derive(Debug, Clone, thiserror::Error, PartialEq, Eq)]
+pub enum SymmetryError {
+    #[error("invalid symmetry operation '{0}'")]
+    InvalidOperation(String),
+    #[error("invalid Hall symbol '{symbol}': {reason}")]
+    InvalidHallSymbol { symbol: String, reason: String },
+    #[error("unknown space group '{0}'")]
+    UnknownSpaceGroup(String),
+}
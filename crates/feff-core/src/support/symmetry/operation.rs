@@ -0,0 +1,179 @@
+use super::SymmetryError;
+
+const OPERATION_TOLERANCE: f64 = 1.0e-6;
+
+/// Affine operation on fractional coordinates, as written in `x,y,z` notation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SymmetryOperation {
+    pub rotation: [[f64; 3]; 3],
+    pub translation: [f64; 3],
+}
+
+impl SymmetryOperation {
+    pub fn identity() -> Self {
+        Self {
+            rotation: [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
+            translation: [0.0; 3],
+        }
+    }
+
+    /// Parses operations such as `-x+1/2, y, z-0.25` or `x-y,x,z+1/6`.
+    pub fn parse_xyz(text: &str) -> Result<Self, SymmetryError> {
+        let invalid = || SymmetryError::InvalidOperation(text.to_string());
+        let components: Vec<&str> = text.split(',').map(str::trim).collect();
+        if components.len() != 3 {
+            return Err(invalid());
+        }
+
+        let mut operation = Self {
+            rotation: [[0.0; 3]; 3],
+            translation: [0.0; 3],
+        };
+        for (row, component) in components.iter().enumerate() {
+            let compact: String = component
+                .chars()
+                .filter(|ch| !ch.is_whitespace())
+                .collect::<String>()
+                .to_ascii_lowercase();
+            if compact.is_empty() {
+                return Err(invalid());
+            }
+            for term in split_signed_terms(&compact) {
+                let (sign, body) = match term.strip_prefix('-') {
+                    Some(rest) => (-1.0, rest),
+                    None => (1.0, term.strip_prefix('+').unwrap_or(term)),
+                };
+                if body.is_empty() {
+                    return Err(invalid());
+                }
+                match body.chars().last() {
+                    Some(axis @ ('x' | 'y' | 'z')) => {
+                        let coefficient = &body[..body.len() - 1];
+                        let coefficient = coefficient.strip_suffix('*').unwrap_or(coefficient);
+                        let scale = if coefficient.is_empty() {
+                            1.0
+                        } else {
+                            parse_fraction(coefficient).ok_or_else(invalid)?
+                        };
+                        let column = (axis as u8 - b'x') as usize;
+                        operation.rotation[row][column] += sign * scale;
+                    }
+                    _ => {
+                        operation.translation[row] +=
+                            sign * parse_fraction(body).ok_or_else(invalid)?;
+                    }
+                }
+            }
+        }
+        Ok(operation)
+    }
+
+    pub fn apply(&self, fractional: [f64; 3]) -> [f64; 3] {
+        std::array::from_fn(|row| {
+            (0..3)
+                .map(|column| self.rotation[row][column] * fractional[column])
+                .sum::<f64>()
+                + self.translation[row]
+        })
+    }
+
+    /// `self ∘ other`: applies `other` first.
+    pub fn compose(&self, other: &Self) -> Self {
+        let rotation = std::array::from_fn(|row| {
+            std::array::from_fn(|column| {
+                (0..3)
+                    .map(|inner| self.rotation[row][inner] * other.rotation[inner][column])
+                    .sum()
+            })
+        });
+        let translation = std::array::from_fn(|row| {
+            (0..3)
+                .map(|inner| self.rotation[row][inner] * other.translation[inner])
+                .sum::<f64>()
+                + self.translation[row]
+        });
+        Self {
+            rotation,
+            translation,
+        }
+    }
+
+    /// Whether both operations agree up to a lattice translation.
+    pub fn equivalent_to(&self, other: &Self) -> bool {
+        let rotation_matches = self
+            .rotation
+            .iter()
+            .flatten()
+            .zip(other.rotation.iter().flatten())
+            .all(|(left, right)| (left - right).abs() < OPERATION_TOLERANCE);
+        rotation_matches
+            && self
+                .translation
+                .iter()
+                .zip(other.translation.iter())
+                .all(|(left, right)| {
+                    let delta = (left - right).rem_euclid(1.0);
+                    delta.min(1.0 - delta) < OPERATION_TOLERANCE
+                })
+    }
+}
+
+fn split_signed_terms(component: &str) -> Vec<&str> {
+    let mut terms = Vec::new();
+    let mut start = 0;
+    for (index, ch) in component.char_indices() {
+        if (ch == '+' || ch == '-') && index > start {
+            terms.push(&component[start..index]);
+            start = index;
+        }
+    }
+    terms.push(&component[start..]);
+    terms
+}
+
+fn parse_fraction(text: &str) -> Option<f64> {
+    match text.split_once('/') {
+        Some((numerator, denominator)) => {
+            let denominator = denominator.parse::<f64>().ok()?;
+            (denominator != 0.0).then_some(numerator.parse::<f64>().ok()? / denominator)
+        }
+        None => text.parse::<f64>().ok(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SymmetryOperation;
+    use crate::support::symmetry::SymmetryError;
+
+    #[test]
+    fn symmetry_operations_accept_coefficients_and_fractions() {
+        let operation = SymmetryOperation::parse_xyz("-x+1/2, x-y, z+0.25").expect("parse");
+        let image = operation.apply([0.1, 0.3, 0.5]);
+        for (actual, expected) in image.iter().zip([0.4, -0.2, 0.75]) {
+            assert!((actual - expected).abs() < 1.0e-12);
+        }
+        assert!(matches!(
+            SymmetryOperation::parse_xyz("x,y"),
+            Err(SymmetryError::InvalidOperation(_))
+        ));
+    }
+
+    #[test]
+    fn composition_matches_sequential_application() {
+        let screw = SymmetryOperation::parse_xyz("-x, y+1/2, -z").expect("parse");
+        let glide = SymmetryOperation::parse_xyz("x, -y+1/2, z+1/2").expect("parse");
+        let point = [0.12, 0.34, 0.56];
+
+        let composed = glide.compose(&screw).apply(point);
+        let sequential = glide.apply(screw.apply(point));
+        for (left, right) in composed.iter().zip(sequential.iter()) {
+            assert!((left - right).abs() < 1.0e-12);
+        }
+
+        let inversion = SymmetryOperation::parse_xyz("-x,-y,-z").expect("parse");
+        let shifted = SymmetryOperation::parse_xyz("-x+1,-y,-z-1").expect("parse");
+        assert!(inversion.equivalent_to(&shifted));
+        assert!(!inversion.equivalent_to(&screw));
+    }
+}
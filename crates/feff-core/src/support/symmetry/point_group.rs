@@ -0,0 +1,341 @@
+type Matrix = [[f64; 3]; 3];
+
+const ORTHOGONALITY_TOLERANCE: f64 = 1.0e-3;
+
+/// Point-group operations (Cartesian, about the origin) that map a cluster
+/// onto itself, with the Schoenflies symbol when the group is crystallographic.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PointGroup {
+    pub operations: Vec<Matrix>,
+    pub schoenflies: Option<&'static str>,
+}
+
+impl PointGroup {
+    /// Analyses atoms `(atomic number, Cartesian position)` centred on the
+    /// absorber at the origin. `tolerance` is the largest displacement (Angstrom)
+    /// still treated as a match.
+    pub fn of_cluster(atoms: &[(i32, [f64; 3])], tolerance: f64) -> Self {
+        let mut operations = vec![IDENTITY];
+        let Some((first, second)) = reference_pair(atoms, tolerance) else {
+            // Empty or linear clusters: only the identity is tested.
+            return Self::from_operations(operations);
+        };
+        let (z1, v1) = atoms[first];
+        let (z2, v2) = atoms[second];
+        let source = basis(v1, v2);
+
+        for (za, a) in atoms {
+            if *za != z1 || (norm(*a) - norm(v1)).abs() > tolerance {
+                continue;
+            }
+            for (zb, b) in atoms {
+                if *zb != z2
+                    || (norm(*b) - norm(v2)).abs() > tolerance
+                    || (dot(*a, *b) - dot(v1, v2)).abs() > tolerance * (norm(v1) + norm(v2))
+                {
+                    continue;
+                }
+                let target = basis(*a, *b);
+                for handedness in [1.0, -1.0] {
+                    let mut flipped = target;
+                    flipped[2] = flipped[2].map(|value| value * handedness);
+                    let Some(candidate) = map_basis(&source, &flipped) else {
+                        continue;
+                    };
+                    if is_orthogonal(&candidate)
+                        && maps_cluster_onto_itself(&candidate, atoms, tolerance)
+                        && !operations
+                            .iter()
+                            .any(|existing| same_matrix(existing, &candidate))
+                    {
+                        operations.push(candidate);
+                    }
+                }
+            }
+        }
+
+        Self::from_operations(operations)
+    }
+
+    fn from_operations(operations: Vec<Matrix>) -> Self {
+        let schoenflies = classify(&operations);
+        Self {
+            operations,
+            schoenflies,
+        }
+    }
+
+    pub fn order(&self) -> usize {
+        self.operations.len()
+    }
+
+    /// Whether two scattering paths (absorber-relative atom positions, in order)
+    /// are images of each other under the group, i.e. degenerate.
+    pub fn relates_paths(&self, left: &[[f64; 3]], right: &[[f64; 3]], tolerance: f64) -> bool {
+        left.len() == right.len()
+            && self.operations.iter().any(|operation| {
+                left.iter()
+                    .zip(right.iter())
+                    .all(|(from, to)| distance(apply(operation, *from), *to) <= tolerance)
+            })
+    }
+}
+
+const IDENTITY: Matrix = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+
+/// Nearest atom plus the nearest atom not collinear with it and the origin.
+fn reference_pair(atoms: &[(i32, [f64; 3])], tolerance: f64) -> Option<(usize, usize)> {
+    let mut by_distance: Vec<usize> = (0..atoms.len())
+        .filter(|index| norm(atoms[*index].1) > tolerance)
+        .collect();
+    by_distance.sort_by(|left, right| norm(atoms[*left].1).total_cmp(&norm(atoms[*right].1)));
+    let first = *by_distance.first()?;
+    let second = by_distance.iter().copied().find(|candidate| {
+        let crossed = cross(atoms[first].1, atoms[*candidate].1);
+        norm(crossed) > tolerance * norm(atoms[*candidate].1)
+    })?;
+    Some((first, second))
+}
+
+fn basis(first: [f64; 3], second: [f64; 3]) -> Matrix {
+    [first, second, cross(first, second)]
+}
+
+/// The linear map sending source rows onto target rows.
+fn map_basis(source: &Matrix, target: &Matrix) -> Option<Matrix> {
+    let inverse = invert(&transpose(source))?;
+    let target_columns = transpose(target);
+    Some(multiply(&target_columns, &inverse))
+}
+
+fn maps_cluster_onto_itself(operation: &Matrix, atoms: &[(i32, [f64; 3])], tolerance: f64) -> bool {
+    atoms.iter().all(|(atomic_number, position)| {
+        let image = apply(operation, *position);
+        atoms
+            .iter()
+            .any(|(other, target)| other == atomic_number && distance(image, *target) <= tolerance)
+    })
+}
+
+/// Names the 32 crystallographic point groups from their operation content.
+fn classify(operations: &[Matrix]) -> Option<&'static str> {
+    let order = operations.len();
+    let mut proper = 0;
+    let mut proper_orders = [0usize; 7];
+    let mut inversion = false;
+    let mut mirrors = 0;
+    let mut rotoinversion_4 = false;
+    for operation in operations {
+        let trace = (operation[0][0] + operation[1][1] + operation[2][2]).round() as i32;
+        if determinant(operation) > 0.0 {
+            proper += 1;
+            let rotation_order = match trace {
+                3 => 1,
+                -1 => 2,
+                0 => 3,
+                1 => 4,
+                2 => 6,
+                _ => return None,
+            };
+            proper_orders[rotation_order] += 1;
+        } else {
+            match trace {
+                -3 => inversion = true,
+                1 => mirrors += 1,
+                -1 => rotoinversion_4 = true,
+                0 | -2 => {}
+                _ => return None,
+            }
+        }
+    }
+    let highest = (1..=6).rev().find(|n| proper_orders[*n] > 0).unwrap_or(1);
+
+    let name = if proper_orders[3] >= 8 {
+        match (order, proper_orders[4] > 0, inversion) {
+            (48, _, _) => "Oh",
+            (24, true, _) => "O",
+            (24, false, true) => "Th",
+            (24, false, false) => "Td",
+            (12, _, _) => "T",
+            _ => return None,
+        }
+    } else {
+        match (highest, order) {
+            (6, 6) => "C6",
+            (6, 12) if proper == 12 => "D6",
+            (6, 12) if inversion => "C6h",
+            (6, 12) => "C6v",
+            (6, 24) => "D6h",
+            (4, 4) => "C4",
+            (4, 8) if proper == 8 => "D4",
+            (4, 8) if inversion => "C4h",
+            (4, 8) => "C4v",
+            (4, 16) => "D4h",
+            (3, 3) => "C3",
+            (3, 6) if proper == 6 => "D3",
+            (3, 6) if inversion => "S6",
+            (3, 6) if mirrors == 1 => "C3h",
+            (3, 6) => "C3v",
+            (3, 12) if inversion => "D3d",
+            (3, 12) => "D3h",
+            (2, 2) => "C2",
+            (2, 4) if proper == 4 => "D2",
+            (2, 4) if inversion => "C2h",
+            (2, 4) if rotoinversion_4 => "S4",
+            (2, 4) => "C2v",
+            (2, 8) if inversion => "D2h",
+            (2, 8) => "D2d",
+            (1, 1) => "C1",
+            (1, 2) if inversion => "Ci",
+            (1, 2) => "Cs",
+            _ => return None,
+        }
+    };
+    Some(name)
+}
+
+fn is_orthogonal(matrix: &Matrix) -> bool {
+    let product = multiply(matrix, &transpose(matrix));
+    same_matrix(&product, &IDENTITY)
+}
+
+fn same_matrix(left: &Matrix, right: &Matrix) -> bool {
+    left.iter()
+        .flatten()
+        .zip(right.iter().flatten())
+        .all(|(a, b)| (a - b).abs() < ORTHOGONALITY_TOLERANCE)
+}
+
+fn apply(matrix: &Matrix, vector: [f64; 3]) -> [f64; 3] {
+    std::array::from_fn(|row| dot(matrix[row], vector))
+}
+
+fn multiply(left: &Matrix, right: &Matrix) -> Matrix {
+    std::array::from_fn(|row| {
+        std::array::from_fn(|column| (0..3).map(|k| left[row][k] * right[k][column]).sum())
+    })
+}
+
+fn transpose(matrix: &Matrix) -> Matrix {
+    std::array::from_fn(|row| std::array::from_fn(|column| matrix[column][row]))
+}
+
+fn determinant(m: &Matrix) -> f64 {
+    m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+        - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+        + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
+}
+
+fn invert(m: &Matrix) -> Option<Matrix> {
+    let det = determinant(m);
+    if det.abs() < 1.0e-12 {
+        return None;
+    }
+    let cofactor =
+        |r0: usize, r1: usize, c0: usize, c1: usize| m[r0][c0] * m[r1][c1] - m[r0][c1] * m[r1][c0];
+    Some([
+        [
+            cofactor(1, 2, 1, 2) / det,
+            -cofactor(0, 2, 1, 2) / det,
+            cofactor(0, 1, 1, 2) / det,
+        ],
+        [
+            -cofactor(1, 2, 0, 2) / det,
+            cofactor(0, 2, 0, 2) / det,
+            -cofactor(0, 1, 0, 2) / det,
+        ],
+        [
+            cofactor(1, 2, 0, 1) / det,
+            -cofactor(0, 2, 0, 1) / det,
+            cofactor(0, 1, 0, 1) / det,
+        ],
+    ])
+}
+
+fn cross(u: [f64; 3], v: [f64; 3]) -> [f64; 3] {
+    [
+        u[1] * v[2] - u[2] * v[1],
+        u[2] * v[0] - u[0] * v[2],
+        u[0] * v[1] - u[1] * v[0],
+    ]
+}
+
+fn dot(u: [f64; 3], v: [f64; 3]) -> f64 {
+    u[0] * v[0] + u[1] * v[1] + u[2] * v[2]
+}
+
+fn norm(v: [f64; 3]) -> f64 {
+    dot(v, v).sqrt()
+}
+
+fn distance(u: [f64; 3], v: [f64; 3]) -> f64 {
+    norm([u[0] - v[0], u[1] - v[1], u[2] - v[2]])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PointGroup;
+
+    fn octahedron() -> Vec<(i32, [f64; 3])> {
+        let mut atoms = vec![(26, [0.0, 0.0, 0.0])];
+        for axis in 0..3 {
+            for sign in [1.0, -1.0] {
+                let mut position = [0.0; 3];
+                position[axis] = 2.0 * sign;
+                atoms.push((8, position));
+            }
+        }
+        atoms
+    }
+
+    #[test]
+    fn octahedral_environment_is_oh() {
+        let group = PointGroup::of_cluster(&octahedron(), 1.0e-3);
+        assert_eq!(group.order(), 48);
+        assert_eq!(group.schoenflies, Some("Oh"));
+    }
+
+    #[test]
+    fn distortions_lower_the_point_group() {
+        let mut elongated = octahedron();
+        elongated[5].1[2] = 2.3;
+        elongated[6].1[2] = -2.3;
+        assert_eq!(
+            PointGroup::of_cluster(&elongated, 1.0e-3).schoenflies,
+            Some("D4h")
+        );
+
+        let tetrahedron = vec![
+            (14, [0.0, 0.0, 0.0]),
+            (8, [1.0, 1.0, 1.0]),
+            (8, [-1.0, -1.0, 1.0]),
+            (8, [-1.0, 1.0, -1.0]),
+            (8, [1.0, -1.0, -1.0]),
+        ];
+        assert_eq!(
+            PointGroup::of_cluster(&tetrahedron, 1.0e-3).schoenflies,
+            Some("Td")
+        );
+
+        let water = vec![
+            (8, [0.0, 0.0, 0.0]),
+            (1, [0.76, 0.0, 0.59]),
+            (1, [-0.76, 0.0, 0.59]),
+        ];
+        assert_eq!(
+            PointGroup::of_cluster(&water, 1.0e-3).schoenflies,
+            Some("C2v")
+        );
+    }
+
+    #[test]
+    fn degenerate_paths_are_related_by_the_group() {
+        let group = PointGroup::of_cluster(&octahedron(), 1.0e-3);
+        let along_x = [[2.0, 0.0, 0.0]];
+        let along_z = [[0.0, 0.0, -2.0]];
+        let triangle_a = [[2.0, 0.0, 0.0], [0.0, 2.0, 0.0]];
+        let collinear = [[2.0, 0.0, 0.0], [-2.0, 0.0, 0.0]];
+        assert!(group.relates_paths(&along_x, &along_z, 1.0e-3));
+        assert!(!group.relates_paths(&triangle_a, &collinear, 1.0e-3));
+    }
+}
@@ -0,0 +1,107 @@
+use super::operation::SymmetryOperation;
+
+const POSITION_TOLERANCE: f64 = 1.0e-4;
+
+/// Symmetry-distinct images of `position` in the unit cell, wrapped to [0, 1).
+///
+/// The orbit length is the Wyckoff multiplicity of the site.
+pub fn site_orbit(operations: &[SymmetryOperation], position: [f64; 3]) -> Vec<[f64; 3]> {
+    let mut orbit: Vec<[f64; 3]> = Vec::new();
+    for operation in operations {
+        let image = wrap_fractional(operation.apply(position));
+        if !orbit
+            .iter()
+            .any(|existing| same_fractional_position(*existing, image))
+        {
+            orbit.push(image);
+        }
+    }
+    orbit
+}
+
+/// Operations that leave `position` fixed modulo a lattice translation.
+pub fn site_symmetry(
+    operations: &[SymmetryOperation],
+    position: [f64; 3],
+) -> Vec<SymmetryOperation> {
+    operations
+        .iter()
+        .filter(|operation| same_fractional_position(operation.apply(position), position))
+        .copied()
+        .collect()
+}
+
+/// Groups sites `(atomic number, fractional position)` into symmetry classes.
+///
+/// Entry `i` of the result is the index of the first site equivalent to site `i`,
+/// so inequivalent sites are exactly those that map to themselves.
+pub fn equivalent_site_classes(
+    operations: &[SymmetryOperation],
+    sites: &[(i32, [f64; 3])],
+) -> Vec<usize> {
+    let mut classes: Vec<usize> = Vec::with_capacity(sites.len());
+    for (index, (atomic_number, position)) in sites.iter().enumerate() {
+        let representative = (0..index)
+            .filter(|candidate| classes[*candidate] == *candidate)
+            .find(|candidate| {
+                let (other_number, other_position) = sites[*candidate];
+                other_number == *atomic_number
+                    && operations.iter().any(|operation| {
+                        same_fractional_position(operation.apply(other_position), *position)
+                    })
+            });
+        classes.push(representative.unwrap_or(index));
+    }
+    classes
+}
+
+pub fn wrap_fractional(position: [f64; 3]) -> [f64; 3] {
+    position.map(|value| {
+        let wrapped = value - value.floor();
+        if wrapped > 1.0 - POSITION_TOLERANCE {
+            0.0
+        } else {
+            wrapped
+        }
+    })
+}
+
+pub fn same_fractional_position(left: [f64; 3], right: [f64; 3]) -> bool {
+    left.iter().zip(right.iter()).all(|(a, b)| {
+        let delta = (a - b).rem_euclid(1.0);
+        delta.min(1.0 - delta) < POSITION_TOLERANCE
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{equivalent_site_classes, site_orbit, site_symmetry};
+    use crate::support::symmetry::space_groups::operations_for_symbol;
+
+    #[test]
+    fn wyckoff_multiplicities_follow_site_symmetry() {
+        let operations = operations_for_symbol("F m -3 m").expect("Fm-3m");
+        // 4a (m-3m), 8c (-43m), 24d (mmm.) and a general 192l position.
+        assert_eq!(site_orbit(&operations, [0.0, 0.0, 0.0]).len(), 4);
+        assert_eq!(site_orbit(&operations, [0.25, 0.25, 0.25]).len(), 8);
+        assert_eq!(site_orbit(&operations, [0.0, 0.25, 0.25]).len(), 24);
+        assert_eq!(site_orbit(&operations, [0.11, 0.23, 0.37]).len(), 192);
+        assert_eq!(site_symmetry(&operations, [0.0, 0.0, 0.0]).len(), 48);
+    }
+
+    #[test]
+    fn equivalent_sites_share_a_representative() {
+        let operations = operations_for_symbol("P 63/m m c").expect("P63/mmc");
+        let sites = [
+            (30, [1.0 / 3.0, 2.0 / 3.0, 0.25]),
+            (8, [1.0 / 3.0, 2.0 / 3.0, 0.62]),
+            (30, [2.0 / 3.0, 1.0 / 3.0, 0.75]),
+            (8, [2.0 / 3.0, 1.0 / 3.0, 0.12]),
+            (8, [0.0, 0.0, 0.0]),
+        ];
+        assert_eq!(
+            equivalent_site_classes(&operations, &sites),
+            vec![0, 1, 0, 1, 4]
+        );
+    }
+}
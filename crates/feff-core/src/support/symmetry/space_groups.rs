@@ -0,0 +1,420 @@
+use super::SymmetryError;
+use super::hall::operations_from_hall;
+use super::operation::SymmetryOperation;
+
+/// One of the 230 space groups in its standard setting: b-unique monoclinic
+/// axes, hexagonal axes for R groups and origin choice 2 where ITA offers two.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SpaceGroup {
+    pub number: u16,
+    pub hermann_mauguin: &'static str,
+    pub hall: &'static str,
+    /// Hall symbol for origin choice 1, for groups that have two origins.
+    pub hall_origin_1: Option<&'static str>,
+}
+
+impl SpaceGroup {
+    const fn new(
+        number: u16,
+        hermann_mauguin: &'static str,
+        hall: &'static str,
+        hall_origin_1: Option<&'static str>,
+    ) -> Self {
+        Self {
+            number,
+            hermann_mauguin,
+            hall,
+            hall_origin_1,
+        }
+    }
+
+    pub fn operations(&self) -> Result<Vec<SymmetryOperation>, SymmetryError> {
+        operations_from_hall(self.hall)
+    }
+
+    pub fn operations_origin_1(&self) -> Result<Vec<SymmetryOperation>, SymmetryError> {
+        operations_from_hall(self.hall_origin_1.unwrap_or(self.hall))
+    }
+}
+
+// Pre-2002 ITA symbols for the e-glide groups, still common in CIFs.
+const LEGACY_SYMBOLS: [(&str, u16); 5] = [
+    ("A b m 2", 39),
+    ("A b a 2", 41),
+    ("C m c a", 64),
+    ("C m m a", 67),
+    ("C c c a", 68),
+];
+
+pub const SPACE_GROUPS: [SpaceGroup; 230] = [
+    SpaceGroup::new(1, "P 1", "P 1", None),
+    SpaceGroup::new(2, "P -1", "-P 1", None),
+    SpaceGroup::new(3, "P 2", "P 2y", None),
+    SpaceGroup::new(4, "P 21", "P 2yb", None),
+    SpaceGroup::new(5, "C 2", "C 2y", None),
+    SpaceGroup::new(6, "P m", "P -2y", None),
+    SpaceGroup::new(7, "P c", "P -2yc", None),
+    SpaceGroup::new(8, "C m", "C -2y", None),
+    SpaceGroup::new(9, "C c", "C -2yc", None),
+    SpaceGroup::new(10, "P 2/m", "-P 2y", None),
+    SpaceGroup::new(11, "P 21/m", "-P 2yb", None),
+    SpaceGroup::new(12, "C 2/m", "-C 2y", None),
+    SpaceGroup::new(13, "P 2/c", "-P 2yc", None),
+    SpaceGroup::new(14, "P 21/c", "-P 2ybc", None),
+    SpaceGroup::new(15, "C 2/c", "-C 2yc", None),
+    SpaceGroup::new(16, "P 2 2 2", "P 2 2", None),
+    SpaceGroup::new(17, "P 2 2 21", "P 2c 2", None),
+    SpaceGroup::new(18, "P 21 21 2", "P 2 2ab", None),
+    SpaceGroup::new(19, "P 21 21 21", "P 2ac 2ab", None),
+    SpaceGroup::new(20, "C 2 2 21", "C 2c 2", None),
+    SpaceGroup::new(21, "C 2 2 2", "C 2 2", None),
+    SpaceGroup::new(22, "F 2 2 2", "F 2 2", None),
+    SpaceGroup::new(23, "I 2 2 2", "I 2 2", None),
+    SpaceGroup::new(24, "I 21 21 21", "I 2b 2c", None),
+    SpaceGroup::new(25, "P m m 2", "P 2 -2", None),
+    SpaceGroup::new(26, "P m c 21", "P 2c -2", None),
+    SpaceGroup::new(27, "P c c 2", "P 2 -2c", None),
+    SpaceGroup::new(28, "P m a 2", "P 2 -2a", None),
+    SpaceGroup::new(29, "P c a 21", "P 2c -2ac", None),
+    SpaceGroup::new(30, "P n c 2", "P 2 -2bc", None),
+    SpaceGroup::new(31, "P m n 21", "P 2ac -2", None),
+    SpaceGroup::new(32, "P b a 2", "P 2 -2ab", None),
+    SpaceGroup::new(33, "P n a 21", "P 2c -2n", None),
+    SpaceGroup::new(34, "P n n 2", "P 2 -2n", None),
+    SpaceGroup::new(35, "C m m 2", "C 2 -2", None),
+    SpaceGroup::new(36, "C m c 21", "C 2c -2", None),
+    SpaceGroup::new(37, "C c c 2", "C 2 -2c", None),
+    SpaceGroup::new(38, "A m m 2", "A 2 -2", None),
+    SpaceGroup::new(39, "A e m 2", "A 2 -2c", None),
+    SpaceGroup::new(40, "A m a 2", "A 2 -2a", None),
+    SpaceGroup::new(41, "A e a 2", "A 2 -2ac", None),
+    SpaceGroup::new(42, "F m m 2", "F 2 -2", None),
+    SpaceGroup::new(43, "F d d 2", "F 2 -2d", None),
+    SpaceGroup::new(44, "I m m 2", "I 2 -2", None),
+    SpaceGroup::new(45, "I b a 2", "I 2 -2c", None),
+    SpaceGroup::new(46, "I m a 2", "I 2 -2a", None),
+    SpaceGroup::new(47, "P m m m", "-P 2 2", None),
+    SpaceGroup::new(48, "P n n n", "-P 2ab 2bc", Some("P 2 2 -1n")),
+    SpaceGroup::new(49, "P c c m", "-P 2 2c", None),
+    SpaceGroup::new(50, "P b a n", "-P 2ab 2b", Some("P 2 2 -1ab")),
+    SpaceGroup::new(51, "P m m a", "-P 2a 2a", None),
+    SpaceGroup::new(52, "P n n a", "-P 2a 2bc", None),
+    SpaceGroup::new(53, "P m n a", "-P 2ac 2", None),
+    SpaceGroup::new(54, "P c c a", "-P 2a 2ac", None),
+    SpaceGroup::new(55, "P b a m", "-P 2 2ab", None),
+    SpaceGroup::new(56, "P c c n", "-P 2ab 2ac", None),
+    SpaceGroup::new(57, "P b c m", "-P 2c 2b", None),
+    SpaceGroup::new(58, "P n n m", "-P 2 2n", None),
+    SpaceGroup::new(59, "P m m n", "-P 2ab 2a", Some("P 2 2ab -1ab")),
+    SpaceGroup::new(60, "P b c n", "-P 2n 2ab", None),
+    SpaceGroup::new(61, "P b c a", "-P 2ac 2ab", None),
+    SpaceGroup::new(62, "P n m a", "-P 2ac 2n", None),
+    SpaceGroup::new(63, "C m c m", "-C 2c 2", None),
+    SpaceGroup::new(64, "C m c e", "-C 2ac 2", None),
+    SpaceGroup::new(65, "C m m m", "-C 2 2", None),
+    SpaceGroup::new(66, "C c c m", "-C 2 2c", None),
+    SpaceGroup::new(67, "C m m e", "-C 2a 2", None),
+    SpaceGroup::new(68, "C c c e", "-C 2a 2ac", Some("C 2 2 -1ac")),
+    SpaceGroup::new(69, "F m m m", "-F 2 2", None),
+    SpaceGroup::new(70, "F d d d", "-F 2uv 2vw", Some("F 2 2 -1d")),
+    SpaceGroup::new(71, "I m m m", "-I 2 2", None),
+    SpaceGroup::new(72, "I b a m", "-I 2 2c", None),
+    SpaceGroup::new(73, "I b c a", "-I 2b 2c", None),
+    SpaceGroup::new(74, "I m m a", "-I 2b 2", None),
+    SpaceGroup::new(75, "P 4", "P 4", None),
+    SpaceGroup::new(76, "P 41", "P 4w", None),
+    SpaceGroup::new(77, "P 42", "P 4c", None),
+    SpaceGroup::new(78, "P 43", "P 4cw", None),
+    SpaceGroup::new(79, "I 4", "I 4", None),
+    SpaceGroup::new(80, "I 41", "I 4bw", None),
+    SpaceGroup::new(81, "P -4", "P -4", None),
+    SpaceGroup::new(82, "I -4", "I -4", None),
+    SpaceGroup::new(83, "P 4/m", "-P 4", None),
+    SpaceGroup::new(84, "P 42/m", "-P 4c", None),
+    SpaceGroup::new(85, "P 4/n", "-P 4a", Some("P 4ab -1ab")),
+    SpaceGroup::new(86, "P 42/n", "-P 4bc", Some("P 4n -1n")),
+    SpaceGroup::new(87, "I 4/m", "-I 4", None),
+    SpaceGroup::new(88, "I 41/a", "-I 4ad", Some("I 4bw -1bw")),
+    SpaceGroup::new(89, "P 4 2 2", "P 4 2", None),
+    SpaceGroup::new(90, "P 4 21 2", "P 4ab 2ab", None),
+    SpaceGroup::new(91, "P 41 2 2", "P 4w 2c", None),
+    SpaceGroup::new(92, "P 41 21 2", "P 4abw 2nw", None),
+    SpaceGroup::new(93, "P 42 2 2", "P 4c 2", None),
+    SpaceGroup::new(94, "P 42 21 2", "P 4n 2n", None),
+    SpaceGroup::new(95, "P 43 2 2", "P 4cw 2c", None),
+    SpaceGroup::new(96, "P 43 21 2", "P 4nw 2abw", None),
+    SpaceGroup::new(97, "I 4 2 2", "I 4 2", None),
+    SpaceGroup::new(98, "I 41 2 2", "I 4bw 2bw", None),
+    SpaceGroup::new(99, "P 4 m m", "P 4 -2", None),
+    SpaceGroup::new(100, "P 4 b m", "P 4 -2ab", None),
+    SpaceGroup::new(101, "P 42 c m", "P 4c -2c", None),
+    SpaceGroup::new(102, "P 42 n m", "P 4n -2n", None),
+    SpaceGroup::new(103, "P 4 c c", "P 4 -2c", None),
+    SpaceGroup::new(104, "P 4 n c", "P 4 -2n", None),
+    SpaceGroup::new(105, "P 42 m c", "P 4c -2", None),
+    SpaceGroup::new(106, "P 42 b c", "P 4c -2ab", None),
+    SpaceGroup::new(107, "I 4 m m", "I 4 -2", None),
+    SpaceGroup::new(108, "I 4 c m", "I 4 -2c", None),
+    SpaceGroup::new(109, "I 41 m d", "I 4bw -2", None),
+    SpaceGroup::new(110, "I 41 c d", "I 4bw -2c", None),
+    SpaceGroup::new(111, "P -4 2 m", "P -4 2", None),
+    SpaceGroup::new(112, "P -4 2 c", "P -4 2c", None),
+    SpaceGroup::new(113, "P -4 21 m", "P -4 2ab", None),
+    SpaceGroup::new(114, "P -4 21 c", "P -4 2n", None),
+    SpaceGroup::new(115, "P -4 m 2", "P -4 -2", None),
+    SpaceGroup::new(116, "P -4 c 2", "P -4 -2c", None),
+    SpaceGroup::new(117, "P -4 b 2", "P -4 -2ab", None),
+    SpaceGroup::new(118, "P -4 n 2", "P -4 -2n", None),
+    SpaceGroup::new(119, "I -4 m 2", "I -4 -2", None),
+    SpaceGroup::new(120, "I -4 c 2", "I -4 -2c", None),
+    SpaceGroup::new(121, "I -4 2 m", "I -4 2", None),
+    SpaceGroup::new(122, "I -4 2 d", "I -4 2bw", None),
+    SpaceGroup::new(123, "P 4/m m m", "-P 4 2", None),
+    SpaceGroup::new(124, "P 4/m c c", "-P 4 2c", None),
+    SpaceGroup::new(125, "P 4/n b m", "-P 4a 2b", Some("P 4 2 -1ab")),
+    SpaceGroup::new(126, "P 4/n n c", "-P 4a 2bc", Some("P 4 2 -1n")),
+    SpaceGroup::new(127, "P 4/m b m", "-P 4 2ab", None),
+    SpaceGroup::new(128, "P 4/m n c", "-P 4 2n", None),
+    SpaceGroup::new(129, "P 4/n m m", "-P 4a 2a", Some("P 4ab 2ab -1ab")),
+    SpaceGroup::new(130, "P 4/n c c", "-P 4a 2ac", Some("P 4ab 2n -1ab")),
+    SpaceGroup::new(131, "P 42/m m c", "-P 4c 2", None),
+    SpaceGroup::new(132, "P 42/m c m", "-P 4c 2c", None),
+    SpaceGroup::new(133, "P 42/n b c", "-P 4ac 2b", Some("P 4n 2c -1n")),
+    SpaceGroup::new(134, "P 42/n n m", "-P 4ac 2bc", Some("P 4n 2 -1n")),
+    SpaceGroup::new(135, "P 42/m b c", "-P 4c 2ab", None),
+    SpaceGroup::new(136, "P 42/m n m", "-P 4n 2n", None),
+    SpaceGroup::new(137, "P 42/n m c", "-P 4ac 2a", Some("P 4n 2n -1n")),
+    SpaceGroup::new(138, "P 42/n c m", "-P 4ac 2ac", Some("P 4n 2ab -1n")),
+    SpaceGroup::new(139, "I 4/m m m", "-I 4 2", None),
+    SpaceGroup::new(140, "I 4/m c m", "-I 4 2c", None),
+    SpaceGroup::new(141, "I 41/a m d", "-I 4bd 2", Some("I 4bw 2bw -1bw")),
+    SpaceGroup::new(142, "I 41/a c d", "-I 4bd 2c", Some("I 4bw 2aw -1bw")),
+    SpaceGroup::new(143, "P 3", "P 3", None),
+    SpaceGroup::new(144, "P 31", "P 31", None),
+    SpaceGroup::new(145, "P 32", "P 32", None),
+    SpaceGroup::new(146, "R 3", "R 3", None),
+    SpaceGroup::new(147, "P -3", "-P 3", None),
+    SpaceGroup::new(148, "R -3", "-R 3", None),
+    SpaceGroup::new(149, "P 3 1 2", "P 3 2", None),
+    SpaceGroup::new(150, "P 3 2 1", "P 3 2\"", None),
+    SpaceGroup::new(151, "P 31 1 2", "P 31 2c (0 0 1)", None),
+    SpaceGroup::new(152, "P 31 2 1", "P 31 2\"", None),
+    SpaceGroup::new(153, "P 32 1 2", "P 32 2c (0 0 -1)", None),
+    SpaceGroup::new(154, "P 32 2 1", "P 32 2\"", None),
+    SpaceGroup::new(155, "R 3 2", "R 3 2\"", None),
+    SpaceGroup::new(156, "P 3 m 1", "P 3 -2\"", None),
+    SpaceGroup::new(157, "P 3 1 m", "P 3 -2", None),
+    SpaceGroup::new(158, "P 3 c 1", "P 3 -2\"c", None),
+    SpaceGroup::new(159, "P 3 1 c", "P 3 -2c", None),
+    SpaceGroup::new(160, "R 3 m", "R 3 -2\"", None),
+    SpaceGroup::new(161, "R 3 c", "R 3 -2\"c", None),
+    SpaceGroup::new(162, "P -3 1 m", "-P 3 2", None),
+    SpaceGroup::new(163, "P -3 1 c", "-P 3 2c", None),
+    SpaceGroup::new(164, "P -3 m 1", "-P 3 2\"", None),
+    SpaceGroup::new(165, "P -3 c 1", "-P 3 2\"c", None),
+    SpaceGroup::new(166, "R -3 m", "-R 3 2\"", None),
+    SpaceGroup::new(167, "R -3 c", "-R 3 2\"c", None),
+    SpaceGroup::new(168, "P 6", "P 6", None),
+    SpaceGroup::new(169, "P 61", "P 61", None),
+    SpaceGroup::new(170, "P 65", "P 65", None),
+    SpaceGroup::new(171, "P 62", "P 62", None),
+    SpaceGroup::new(172, "P 64", "P 64", None),
+    SpaceGroup::new(173, "P 63", "P 6c", None),
+    SpaceGroup::new(174, "P -6", "P -6", None),
+    SpaceGroup::new(175, "P 6/m", "-P 6", None),
+    SpaceGroup::new(176, "P 63/m", "-P 6c", None),
+    SpaceGroup::new(177, "P 6 2 2", "P 6 2", None),
+    SpaceGroup::new(178, "P 61 2 2", "P 61 2 (0 0 -1)", None),
+    SpaceGroup::new(179, "P 65 2 2", "P 65 2 (0 0 1)", None),
+    SpaceGroup::new(180, "P 62 2 2", "P 62 2c (0 0 1)", None),
+    SpaceGroup::new(181, "P 64 2 2", "P 64 2c (0 0 -1)", None),
+    SpaceGroup::new(182, "P 63 2 2", "P 6c 2c", None),
+    SpaceGroup::new(183, "P 6 m m", "P 6 -2", None),
+    SpaceGroup::new(184, "P 6 c c", "P 6 -2c", None),
+    SpaceGroup::new(185, "P 63 c m", "P 6c -2", None),
+    SpaceGroup::new(186, "P 63 m c", "P 6c -2c", None),
+    SpaceGroup::new(187, "P -6 m 2", "P -6 2", None),
+    SpaceGroup::new(188, "P -6 c 2", "P -6c 2", None),
+    SpaceGroup::new(189, "P -6 2 m", "P -6 -2", None),
+    SpaceGroup::new(190, "P -6 2 c", "P -6c -2c", None),
+    SpaceGroup::new(191, "P 6/m m m", "-P 6 2", None),
+    SpaceGroup::new(192, "P 6/m c c", "-P 6 2c", None),
+    SpaceGroup::new(193, "P 63/m c m", "-P 6c 2", None),
+    SpaceGroup::new(194, "P 63/m m c", "-P 6c 2c", None),
+    SpaceGroup::new(195, "P 2 3", "P 2 2 3", None),
+    SpaceGroup::new(196, "F 2 3", "F 2 2 3", None),
+    SpaceGroup::new(197, "I 2 3", "I 2 2 3", None),
+    SpaceGroup::new(198, "P 21 3", "P 2ac 2ab 3", None),
+    SpaceGroup::new(199, "I 21 3", "I 2b 2c 3", None),
+    SpaceGroup::new(200, "P m -3", "-P 2 2 3", None),
+    SpaceGroup::new(201, "P n -3", "-P 2ab 2bc 3", Some("P 2 2 3 -1n")),
+    SpaceGroup::new(202, "F m -3", "-F 2 2 3", None),
+    SpaceGroup::new(203, "F d -3", "-F 2uv 2vw 3", Some("F 2 2 3 -1d")),
+    SpaceGroup::new(204, "I m -3", "-I 2 2 3", None),
+    SpaceGroup::new(205, "P a -3", "-P 2ac 2ab 3", None),
+    SpaceGroup::new(206, "I a -3", "-I 2b 2c 3", None),
+    SpaceGroup::new(207, "P 4 3 2", "P 4 2 3", None),
+    SpaceGroup::new(208, "P 42 3 2", "P 4n 2 3", None),
+    SpaceGroup::new(209, "F 4 3 2", "F 4 2 3", None),
+    SpaceGroup::new(210, "F 41 3 2", "F 4d 2 3", None),
+    SpaceGroup::new(211, "I 4 3 2", "I 4 2 3", None),
+    SpaceGroup::new(212, "P 43 3 2", "P 4acd 2ab 3", None),
+    SpaceGroup::new(213, "P 41 3 2", "P 4bd 2ab 3", None),
+    SpaceGroup::new(214, "I 41 3 2", "I 4bd 2c 3", None),
+    SpaceGroup::new(215, "P -4 3 m", "P -4 2 3", None),
+    SpaceGroup::new(216, "F -4 3 m", "F -4 2 3", None),
+    SpaceGroup::new(217, "I -4 3 m", "I -4 2 3", None),
+    SpaceGroup::new(218, "P -4 3 n", "P -4n 2 3", None),
+    SpaceGroup::new(219, "F -4 3 c", "F -4c 2 3", None),
+    SpaceGroup::new(220, "I -4 3 d", "I -4bd 2c 3", None),
+    SpaceGroup::new(221, "P m -3 m", "-P 4 2 3", None),
+    SpaceGroup::new(222, "P n -3 n", "-P 4a 2bc 3", Some("P 4 2 3 -1n")),
+    SpaceGroup::new(223, "P m -3 n", "-P 4n 2 3", None),
+    SpaceGroup::new(224, "P n -3 m", "-P 4bc 2bc 3", Some("P 4n 2 3 -1n")),
+    SpaceGroup::new(225, "F m -3 m", "-F 4 2 3", None),
+    SpaceGroup::new(226, "F m -3 c", "-F 4c 2 3", None),
+    SpaceGroup::new(227, "F d -3 m", "-F 4vw 2vw 3", Some("F 4d 2 3 -1d")),
+    SpaceGroup::new(228, "F d -3 c", "-F 4ud 2vw 3", Some("F 4d 2 3 -1cd")),
+    SpaceGroup::new(229, "I m -3 m", "-I 4 2 3", None),
+    SpaceGroup::new(230, "I a -3 d", "-I 4bd 2c 3", None),
+];
+
+pub fn space_group_by_number(number: u16) -> Option<&'static SpaceGroup> {
+    number
+        .checked_sub(1)
+        .and_then(|index| SPACE_GROUPS.get(usize::from(index)))
+}
+
+/// Looks up a Hermann-Mauguin symbol such as `Fm-3m`, `P 1 21/c 1` or
+/// `F d -3 m :2`, ignoring spacing and case.
+pub fn find_space_group(symbol: &str) -> Option<&'static SpaceGroup> {
+    let (name, _) = split_setting(symbol);
+    let key = normalize_symbol(name);
+    if key.is_empty() {
+        return None;
+    }
+    SPACE_GROUPS
+        .iter()
+        .find(|group| normalize_symbol(group.hermann_mauguin) == key)
+        .or_else(|| {
+            LEGACY_SYMBOLS
+                .iter()
+                .find(|(legacy, _)| normalize_symbol(legacy) == key)
+                .and_then(|(_, number)| space_group_by_number(*number))
+        })
+}
+
+/// Operations for a Hermann-Mauguin symbol, honouring an `:1` origin-choice
+/// suffix. Rhombohedral-axis settings (`:R`) are not tabulated.
+pub fn operations_for_symbol(symbol: &str) -> Result<Vec<SymmetryOperation>, SymmetryError> {
+    let unknown = || SymmetryError::UnknownSpaceGroup(symbol.trim().to_string());
+    let (_, setting) = split_setting(symbol);
+    let group = find_space_group(symbol).ok_or_else(unknown)?;
+    match setting.map(str::to_ascii_uppercase).as_deref() {
+        None | Some("2") | Some("H") => group.operations(),
+        Some("1") => group.operations_origin_1(),
+        Some(_) => Err(unknown()),
+    }
+}
+
+fn split_setting(symbol: &str) -> (&str, Option<&str>) {
+    match symbol.split_once(':') {
+        Some((name, setting)) => (name, Some(setting.trim())),
+        None => (symbol, None),
+    }
+}
+
+fn normalize_symbol(symbol: &str) -> String {
+    let tokens: Vec<&str> = symbol
+        .split(|ch: char| ch.is_whitespace() || ch == '_')
+        .filter(|token| !token.is_empty())
+        .collect();
+    // Full monoclinic symbols (`P 1 21/c 1`) reduce to the short form.
+    let tokens = match tokens.as_slice() {
+        [lattice, "1", axis, "1"] => vec![*lattice, *axis],
+        _ => tokens,
+    };
+    tokens.concat().to_ascii_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SPACE_GROUPS, find_space_group, operations_for_symbol, space_group_by_number};
+    use crate::support::symmetry::SymmetryError;
+
+    fn point_group_order(number: u16) -> usize {
+        match number {
+            1 => 1,
+            2..=9 => 2,
+            10..=46 => 4,
+            47..=74 => 8,
+            75..=82 => 4,
+            83..=122 => 8,
+            123..=142 => 16,
+            143..=146 => 3,
+            147..=161 => 6,
+            162..=167 => 12,
+            168..=174 => 6,
+            175..=190 => 12,
+            191..=194 => 24,
+            195..=199 => 12,
+            200..=220 => 24,
+            _ => 48,
+        }
+    }
+
+    fn centering_multiplicity(hermann_mauguin: &str) -> usize {
+        match hermann_mauguin.chars().next() {
+            Some('P') => 1,
+            Some('A' | 'B' | 'C' | 'I') => 2,
+            Some('R') => 3,
+            Some('F') => 4,
+            _ => unreachable!("unexpected lattice symbol"),
+        }
+    }
+
+    #[test]
+    fn every_space_group_expands_to_its_crystallographic_order() {
+        for group in &SPACE_GROUPS {
+            let expected =
+                point_group_order(group.number) * centering_multiplicity(group.hermann_mauguin);
+            for operations in [group.operations(), group.operations_origin_1()] {
+                let operations = operations.unwrap_or_else(|error| {
+                    panic!("space group {} should expand: {}", group.number, error)
+                });
+                assert_eq!(
+                    operations.len(),
+                    expected,
+                    "space group {} ({})",
+                    group.number,
+                    group.hall
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn symbols_are_found_regardless_of_spacing_and_setting() {
+        assert_eq!(find_space_group("Fm-3m").map(|g| g.number), Some(225));
+        assert_eq!(find_space_group("P 1 21/c 1").map(|g| g.number), Some(14));
+        assert_eq!(find_space_group("F d -3 m :2").map(|g| g.number), Some(227));
+        assert_eq!(find_space_group("C m c a").map(|g| g.number), Some(64));
+        assert_eq!(
+            space_group_by_number(194).map(|g| g.hermann_mauguin),
+            Some("P 63/m m c")
+        );
+        assert_eq!(space_group_by_number(231), None);
+    }
+
+    #[test]
+    fn origin_choice_suffix_selects_the_hall_symbol() {
+        let origin_2 = operations_for_symbol("F d -3 m").expect("origin 2");
+        let origin_1 = operations_for_symbol("F d -3 m:1").expect("origin 1");
+        assert_eq!(origin_1.len(), origin_2.len());
+        assert_ne!(origin_1, origin_2);
+        assert_eq!(
+            operations_for_symbol("R -3 m :R"),
+            Err(SymmetryError::UnknownSpaceGroup("R -3 m :R".to_string()))
+        );
+    }
+}
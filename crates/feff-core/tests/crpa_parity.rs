@@ -1,3 +1,5 @@
+#![cfg(feature = "native")]
+
 use feff_core::domain::{ComputeArtifact, ComputeModule, ComputeRequest};
 use feff_core::modules::ModuleExecutor;
 use feff_core::modules::crpa::CrpaModule;
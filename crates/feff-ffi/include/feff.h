@@ -0,0 +1,184 @@
+/* feff-ffi C ABI. Generated by cbindgen from crates/feff-ffi; do not edit. */
+
+#ifndef FEFF_FFI_H
+#define FEFF_FFI_H
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// Result of every fallible call. Values match the `feff10-rs` exit codes.
+enum FeffStatus
+#if defined(__cplusplus) || __STDC_VERSION__ >= 202311L
+  : int32_t
+#endif // defined(__cplusplus) || __STDC_VERSION__ >= 202311L
+ {
+  FEFF_STATUS_SUCCESS = 0,
+  FEFF_STATUS_INPUT_VALIDATION_ERROR = 2,
+  FEFF_STATUS_IO_SYSTEM_ERROR = 3,
+  FEFF_STATUS_COMPUTATION_ERROR = 4,
+  FEFF_STATUS_INTERNAL_ERROR = 5,
+};
+#ifndef __cplusplus
+#if __STDC_VERSION__ >= 202311L
+typedef enum FeffStatus FeffStatus;
+#else
+typedef int32_t FeffStatus;
+#endif // __STDC_VERSION__ >= 202311L
+#endif // __cplusplus
+
+// `xmu.dat` column copied by [`feff_results_spectrum`].
+enum FeffSpectrumColumn
+#if defined(__cplusplus) || __STDC_VERSION__ >= 202311L
+  : int32_t
+#endif // defined(__cplusplus) || __STDC_VERSION__ >= 202311L
+ {
+  // Absolute energy in eV.
+  FEFF_SPECTRUM_COLUMN_ENERGY = 0,
+  // Wave number in 1/Angstrom.
+  FEFF_SPECTRUM_COLUMN_K = 1,
+  FEFF_SPECTRUM_COLUMN_MU = 2,
+  FEFF_SPECTRUM_COLUMN_MU0 = 3,
+  FEFF_SPECTRUM_COLUMN_CHI = 4,
+};
+#ifndef __cplusplus
+#if __STDC_VERSION__ >= 202311L
+typedef enum FeffSpectrumColumn FeffSpectrumColumn;
+#else
+typedef int32_t FeffSpectrumColumn;
+#endif // __STDC_VERSION__ >= 202311L
+#endif // __cplusplus
+
+// Opaque parsed input deck.
+typedef struct FeffDeck FeffDeck;
+
+// Opaque outputs of a run: the spectra, paths and LDOS it produced.
+typedef struct FeffResults FeffResults;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Code of the last error on this thread (e.g. `"INPUT.MISSING_REQUIRED_CARD"`),
+// or NULL after a successful call. Valid until the next call on this thread.
+const char *feff_last_error_code(void);
+
+// `ERROR: [CODE] message` line of the last error on this thread, or NULL
+// after a successful call. Valid until the next call on this thread.
+const char *feff_last_error_message(void);
+
+// Legacy FEFF class of a status (`"INPUT_FATAL"`, `"RUN_FATAL"`, ...), or
+// NULL for a value that is not a [`FeffStatus`]. The string is static.
+const char *feff_status_legacy_class(int32_t status);
+
+// Parses NUL-terminated `feff.inp` source into `*out_deck`.
+//
+// # Safety
+// `source` must be a valid NUL-terminated string and `out_deck` a valid
+// pointer to write the handle to.
+FeffStatus feff_deck_parse(const char *source, struct FeffDeck **out_deck);
+
+// Reads and parses the deck at `path` into `*out_deck`.
+//
+// # Safety
+// `path` must be a valid NUL-terminated string and `out_deck` a valid
+// pointer to write the handle to.
+FeffStatus feff_deck_read(const char *path, struct FeffDeck **out_deck);
+
+// Number of cards in `deck`, or 0 for NULL.
+//
+// # Safety
+// `deck` must be NULL or a handle from `feff_deck_parse`/`feff_deck_read`.
+size_t feff_deck_card_count(const struct FeffDeck *deck);
+
+// Releases a deck. NULL is ignored.
+//
+// # Safety
+// `deck` must be NULL or a handle not yet freed.
+void feff_deck_free(struct FeffDeck *deck);
+
+// Runs every module `deck` enables in memory, with `workers` threads
+// (0 or 1 for serial), and stores the outputs in `*out_results`. Nothing is
+// written to disk. `label` names the run in module outputs as a directory
+// name does for `feff_run_directory`; NULL keeps the default.
+//
+// # Safety
+// `deck` must be a live deck handle, `label` NULL or a valid NUL-terminated
+// string, and `out_results` a valid pointer to write the handle to.
+FeffStatus feff_deck_run(const struct FeffDeck *deck,
+                         const char *label,
+                         size_t workers,
+                         struct FeffResults **out_results);
+
+// Runs every module `feff.inp` in `directory` enables, as `feff10-rs run`
+// does, reading and writing files in `directory`. Modules the run manifest
+// (`feff10-run.json`) records as fresh are skipped. When `out_results` is
+// not NULL the outputs are read back into `*out_results`.
+//
+// # Safety
+// `directory` must be a valid NUL-terminated string; `out_results` must be
+// NULL or a valid pointer to write the handle to.
+FeffStatus feff_run_directory(const char *directory,
+                              size_t workers,
+                              struct FeffResults **out_results);
+
+// Runs one module (`"POT"`, `"XSPH"`, `"FMS"`, ...) in `directory`, reading
+// its inputs from and writing its outputs to that directory.
+//
+// # Safety
+// `directory` and `module` must be valid NUL-terminated strings.
+FeffStatus feff_run_module(const char *directory, const char *module, size_t workers);
+
+// Number of rows in the `xmu.dat` spectrum, or 0 when the run wrote none.
+//
+// # Safety
+// `results` must be NULL or a live results handle.
+size_t feff_results_spectrum_len(const struct FeffResults *results);
+
+// Copies the [`FeffSpectrumColumn`] `column` into `out`, which holds
+// `capacity` doubles and must fit `feff_results_spectrum_len(results)`.
+//
+// # Safety
+// `results` must be a live results handle and `out` must point to at
+// least `capacity` writable doubles.
+FeffStatus feff_results_spectrum(const struct FeffResults *results,
+                                 int32_t column,
+                                 double *out,
+                                 size_t capacity);
+
+// Number of rows in the `chi.dat` chi(k), or 0 when the run wrote none.
+//
+// # Safety
+// `results` must be NULL or a live results handle.
+size_t feff_results_chi_len(const struct FeffResults *results);
+
+// Copies the [`FeffChiColumn`] `column` of chi(k) into `out`, which holds
+// `capacity` doubles and must fit `feff_results_chi_len(results)`.
+//
+// # Safety
+// `results` must be a live results handle and `out` must point to at
+// least `capacity` writable doubles.
+FeffStatus feff_results_chi(const struct FeffResults *results,
+                            int32_t column,
+                            double *out,
+                            size_t capacity);
+
+// Number of scattering paths in `paths.dat`.
+//
+// # Safety
+// `results` must be NULL or a live results handle.
+size_t feff_results_path_count(const struct FeffResults *results);
+
+// Releases results. NULL is ignored.
+//
+// # Safety
+// `results` must be NULL or a handle not yet freed.
+void feff_results_free(struct FeffResults *results);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* FEFF_FFI_H */
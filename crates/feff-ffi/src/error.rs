@@ -0,0 +1,132 @@
+use feff_core::domain::{FeffError, FeffErrorCategory};
+use std::cell::RefCell;
+use std::ffi::{CString, c_char};
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::ptr;
+
+/// Result of every fallible call. Values match the `feff10-rs` exit codes.
+#[repr(i32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeffStatus {
+    Success = 0,
+    InputValidationError = 2,
+    IoSystemError = 3,
+    ComputationError = 4,
+    InternalError = 5,
+}
+
+impl From<FeffErrorCategory> for FeffStatus {
+    fn from(category: FeffErrorCategory) -> Self {
+        match category {
+            FeffErrorCategory::Success => Self::Success,
+            FeffErrorCategory::InputValidationError => Self::InputValidationError,
+            FeffErrorCategory::IoSystemError => Self::IoSystemError,
+            FeffErrorCategory::ComputationError => Self::ComputationError,
+            FeffErrorCategory::InternalError => Self::InternalError,
+        }
+    }
+}
+
+struct LastError {
+    code: CString,
+    message: CString,
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<LastError>> = const { RefCell::new(None) };
+}
+
+/// Runs `body`, recording its error (or panic) for this thread.
+pub(crate) fn guard(body: impl FnOnce() -> Result<(), FeffError>) -> FeffStatus {
+    let error = match catch_unwind(AssertUnwindSafe(body)) {
+        Ok(Ok(())) => {
+            LAST_ERROR.with(|last| last.borrow_mut().take());
+            return FeffStatus::Success;
+        }
+        Ok(Err(error)) => error,
+        Err(_) => FeffError::internal("SYS.FFI_PANIC", "feff-core panicked during the call"),
+    };
+    let status = FeffStatus::from(error.category());
+    LAST_ERROR.with(|last| {
+        *last.borrow_mut() = Some(LastError {
+            code: c_string(error.placeholder()),
+            message: c_string(&error.diagnostic_line()),
+        });
+    });
+    status
+}
+
+fn c_string(text: &str) -> CString {
+    CString::new(text.replace('\0', " ")).expect("interior NUL bytes were replaced")
+}
+
+/// Code of the last error on this thread (e.g. `"INPUT.MISSING_REQUIRED_CARD"`),
+/// or NULL after a successful call. Valid until the next call on this thread.
+#[unsafe(no_mangle)]
+pub extern "C" fn feff_last_error_code() -> *const c_char {
+    LAST_ERROR.with(|last| {
+        last.borrow()
+            .as_ref()
+            .map_or(ptr::null(), |error| error.code.as_ptr())
+    })
+}
+
+/// `ERROR: [CODE] message` line of the last error on this thread, or NULL
+/// after a successful call. Valid until the next call on this thread.
+#[unsafe(no_mangle)]
+pub extern "C" fn feff_last_error_message() -> *const c_char {
+    LAST_ERROR.with(|last| {
+        last.borrow()
+            .as_ref()
+            .map_or(ptr::null(), |error| error.message.as_ptr())
+    })
+}
+
+/// Legacy FEFF class of a status (`"INPUT_FATAL"`, `"RUN_FATAL"`, ...), or
+/// NULL for a value that is not a [`FeffStatus`]. The string is static.
+#[unsafe(no_mangle)]
+pub extern "C" fn feff_status_legacy_class(status: i32) -> *const c_char {
+    let class = match FeffErrorCategory::from_exit_code(status) {
+        Some(FeffErrorCategory::Success) => c"SUCCESS",
+        Some(FeffErrorCategory::InputValidationError) => c"INPUT_FATAL",
+        Some(FeffErrorCategory::IoSystemError) => c"IO_FATAL",
+        Some(FeffErrorCategory::ComputationError) => c"RUN_FATAL",
+        Some(FeffErrorCategory::InternalError) => c"SYS_FATAL",
+        None => return ptr::null(),
+    };
+    class.as_ptr()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FeffStatus, feff_status_legacy_class, guard};
+    use feff_core::domain::{FeffError, FeffErrorCategory};
+    use std::ffi::CStr;
+
+    #[test]
+    fn statuses_match_compatibility_placeholders() {
+        for category in [
+            FeffErrorCategory::Success,
+            FeffErrorCategory::InputValidationError,
+            FeffErrorCategory::IoSystemError,
+            FeffErrorCategory::ComputationError,
+            FeffErrorCategory::InternalError,
+        ] {
+            let placeholder = category.compatibility_placeholder();
+            assert_eq!(FeffStatus::from(category) as i32, placeholder.exit_code);
+            let class = unsafe { CStr::from_ptr(feff_status_legacy_class(placeholder.exit_code)) };
+            assert_eq!(class.to_str(), Ok(placeholder.legacy_class));
+        }
+        assert!(feff_status_legacy_class(1).is_null());
+    }
+
+    #[test]
+    fn guard_reports_panics_as_internal_errors() {
+        assert_eq!(
+            guard(|| Err(FeffError::computation("RUN.TEST", "failed"))),
+            FeffStatus::ComputationError
+        );
+        assert_eq!(guard(|| panic!("boom")), FeffStatus::InternalError);
+        assert_eq!(guard(|| Ok(())), FeffStatus::Success);
+    }
+}
@@ -0,0 +1,21 @@
+//! C ABI over `feff-core` for C and Fortran callers; `include/feff.h` is the
+//! header, generated with cbindgen from this crate.
+//!
+//! Every fallible function returns a [`FeffStatus`], whose values are the
+//! exit codes of `CompatibilityExitPlaceholder`, and records the error for
+//! [`feff_last_error_code`] / [`feff_last_error_message`] on the calling
+//! thread. Handles returned through `out` pointers are owned by the caller
+//! and released with the matching `*_free` function.
+
+mod error;
+mod run;
+
+pub use error::{
+    FeffStatus, feff_last_error_code, feff_last_error_message, feff_status_legacy_class,
+};
+pub use run::{
+    FeffChiColumn, FeffDeck, FeffResults, FeffSpectrumColumn, feff_deck_card_count, feff_deck_free,
+    feff_deck_parse, feff_deck_read, feff_deck_run, feff_results_chi, feff_results_chi_len,
+    feff_results_free, feff_results_path_count, feff_results_spectrum, feff_results_spectrum_len,
+    feff_run_directory, feff_run_module,
+};
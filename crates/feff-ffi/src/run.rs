@@ -0,0 +1,383 @@
+use crate::error::{FeffStatus, guard};
+use feff_core::domain::{
+    ComputeModule, ComputeResult, ExecutionControl, ExecutionMode, FeffError, FeffInput, InputDeck,
+};
+use feff_core::modules::cache::{directory_label, run_deck_directory};
+use feff_core::modules::{execute_runtime_module, runtime_request};
+use feff_core::parser::parse_input_deck;
+use feff_core::{Calculation, CalculationResults};
+use std::ffi::{CStr, c_char};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::ptr;
+
+/// Opaque parsed input deck.
+pub struct FeffDeck {
+    deck: InputDeck,
+}
+
+/// Opaque outputs of a run: the spectra, paths and LDOS it produced.
+pub struct FeffResults {
+    results: CalculationResults,
+}
+
+/// `xmu.dat` column copied by [`feff_results_spectrum`].
+#[repr(i32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeffSpectrumColumn {
+    /// Absolute energy in eV.
+    Energy = 0,
+    /// Wave number in 1/Angstrom.
+    K = 1,
+    Mu = 2,
+    Mu0 = 3,
+    Chi = 4,
+}
+
+impl FeffSpectrumColumn {
+    /// Columns arrive as plain integers so an out-of-range value from C is
+    /// an error rather than an invalid enum.
+    fn from_raw(column: i32) -> Option<Self> {
+        [Self::Energy, Self::K, Self::Mu, Self::Mu0, Self::Chi]
+            .into_iter()
+            .find(|candidate| *candidate as i32 == column)
+    }
+}
+
+/// `chi.dat` column copied by [`feff_results_chi`].
+#[repr(i32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeffChiColumn {
+    /// Wave number in 1/Angstrom.
+    K = 0,
+    Chi = 1,
+    Magnitude = 2,
+    Phase = 3,
+}
+
+impl FeffChiColumn {
+    fn from_raw(column: i32) -> Option<Self> {
+        [Self::K, Self::Chi, Self::Magnitude, Self::Phase]
+            .into_iter()
+            .find(|candidate| *candidate as i32 == column)
+    }
+}
+
+/// Parses NUL-terminated `feff.inp` source into `*out_deck`.
+///
+/// # Safety
+/// `source` must be a valid NUL-terminated string and `out_deck` a valid
+/// pointer to write the handle to.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn feff_deck_parse(
+    source: *const c_char,
+    out_deck: *mut *mut FeffDeck,
+) -> FeffStatus {
+    guard(|| {
+        let source = unsafe { c_str(source, "source") }?;
+        let deck = parse_input_deck(source)?;
+        unsafe { write_handle(out_deck, FeffDeck { deck }) }
+    })
+}
+
+/// Reads and parses the deck at `path` into `*out_deck`.
+///
+/// # Safety
+/// `path` must be a valid NUL-terminated string and `out_deck` a valid
+/// pointer to write the handle to.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn feff_deck_read(
+    path: *const c_char,
+    out_deck: *mut *mut FeffDeck,
+) -> FeffStatus {
+    guard(|| {
+        let path = Path::new(unsafe { c_str(path, "path") }?);
+        let source = fs::read_to_string(path).map_err(|source| {
+            FeffError::io_system(
+                "IO.FFI_DECK_READ",
+                format!("failed to read input deck '{}': {}", path.display(), source),
+            )
+        })?;
+        let deck = parse_input_deck(&source)?;
+        unsafe { write_handle(out_deck, FeffDeck { deck }) }
+    })
+}
+
+/// Number of cards in `deck`, or 0 for NULL.
+///
+/// # Safety
+/// `deck` must be NULL or a handle from `feff_deck_parse`/`feff_deck_read`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn feff_deck_card_count(deck: *const FeffDeck) -> usize {
+    unsafe { deck.as_ref() }.map_or(0, |deck| deck.deck.cards.len())
+}
+
+/// Releases a deck. NULL is ignored.
+///
+/// # Safety
+/// `deck` must be NULL or a handle not yet freed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn feff_deck_free(deck: *mut FeffDeck) {
+    if !deck.is_null() {
+        drop(unsafe { Box::from_raw(deck) });
+    }
+}
+
+/// Runs every module `deck` enables in memory, with `workers` threads
+/// (0 or 1 for serial), and stores the outputs in `*out_results`. Nothing is
+/// written to disk. `label` names the run in module outputs as a directory
+/// name does for `feff_run_directory`; NULL keeps the default.
+///
+/// # Safety
+/// `deck` must be a live deck handle, `label` NULL or a valid NUL-terminated
+/// string, and `out_results` a valid pointer to write the handle to.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn feff_deck_run(
+    deck: *const FeffDeck,
+    label: *const c_char,
+    workers: usize,
+    out_results: *mut *mut FeffResults,
+) -> FeffStatus {
+    guard(|| {
+        let deck = unsafe { deck.as_ref() }.ok_or_else(|| null_argument("deck"))?;
+        let mut calculation = Calculation::from_input(FeffInput::from_deck(&deck.deck)?)
+            .execution_mode(execution_mode(workers));
+        if !label.is_null() {
+            calculation = calculation.label(unsafe { c_str(label, "label") }?);
+        }
+        let results = calculation.run()?;
+        unsafe { write_handle(out_results, FeffResults { results }) }
+    })
+}
+
+/// Runs every module `feff.inp` in `directory` enables, as `feff10-rs run`
+/// does, reading and writing files in `directory`. Modules the run manifest
+/// (`feff10-run.json`) records as fresh are skipped. When `out_results` is
+/// not NULL the outputs are read back into `*out_results`.
+///
+/// # Safety
+/// `directory` must be a valid NUL-terminated string; `out_results` must be
+/// NULL or a valid pointer to write the handle to.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn feff_run_directory(
+    directory: *const c_char,
+    workers: usize,
+    out_results: *mut *mut FeffResults,
+) -> FeffStatus {
+    guard(|| {
+        let directory = PathBuf::from(unsafe { c_str(directory, "directory") }?);
+        let modules = run_deck_directory(
+            &directory,
+            execution_mode(workers),
+            &ExecutionControl::default(),
+        )?;
+        if out_results.is_null() {
+            return Ok(());
+        }
+        let results = CalculationResults::from_directory(directory, modules)?;
+        unsafe { write_handle(out_results, FeffResults { results }) }
+    })
+}
+
+/// Runs one module (`"POT"`, `"XSPH"`, `"FMS"`, ...) in `directory`, reading
+/// its inputs from and writing its outputs to that directory.
+///
+/// # Safety
+/// `directory` and `module` must be valid NUL-terminated strings.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn feff_run_module(
+    directory: *const c_char,
+    module: *const c_char,
+    workers: usize,
+) -> FeffStatus {
+    guard(|| {
+        let directory = Path::new(unsafe { c_str(directory, "directory") }?);
+        let module = unsafe { c_str(module, "module") }?.parse()?;
+        run_module(directory, module, execution_mode(workers))
+    })
+}
+
+/// Number of rows in the `xmu.dat` spectrum, or 0 when the run wrote none.
+///
+/// # Safety
+/// `results` must be NULL or a live results handle.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn feff_results_spectrum_len(results: *const FeffResults) -> usize {
+    unsafe { results.as_ref() }
+        .and_then(|results| results.results.spectrum.as_ref())
+        .map_or(0, |spectrum| spectrum.energy.len())
+}
+
+/// Copies the [`FeffSpectrumColumn`] `column` into `out`, which holds
+/// `capacity` doubles and must fit `feff_results_spectrum_len(results)`.
+///
+/// # Safety
+/// `results` must be a live results handle and `out` must point to at
+/// least `capacity` writable doubles.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn feff_results_spectrum(
+    results: *const FeffResults,
+    column: i32,
+    out: *mut f64,
+    capacity: usize,
+) -> FeffStatus {
+    guard(|| {
+        let results = unsafe { results.as_ref() }.ok_or_else(|| null_argument("results"))?;
+        let spectrum = results.results.spectrum.as_ref().ok_or_else(|| {
+            FeffError::input_validation("INPUT.FFI_NO_SPECTRUM", "the run wrote no xmu.dat")
+        })?;
+        let values = match FeffSpectrumColumn::from_raw(column) {
+            Some(FeffSpectrumColumn::Energy) => &spectrum.energy,
+            Some(FeffSpectrumColumn::K) => &spectrum.k,
+            Some(FeffSpectrumColumn::Mu) => &spectrum.mu,
+            Some(FeffSpectrumColumn::Mu0) => &spectrum.mu0,
+            Some(FeffSpectrumColumn::Chi) => &spectrum.chi,
+            None => {
+                return Err(FeffError::input_validation(
+                    "INPUT.FFI_SPECTRUM_COLUMN",
+                    format!("unknown spectrum column {}", column),
+                ));
+            }
+        };
+        unsafe { copy_column(values, out, capacity) }
+    })
+}
+
+/// Number of rows in the `chi.dat` chi(k), or 0 when the run wrote none.
+///
+/// # Safety
+/// `results` must be NULL or a live results handle.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn feff_results_chi_len(results: *const FeffResults) -> usize {
+    unsafe { results.as_ref() }
+        .and_then(|results| results.results.chi.as_ref())
+        .map_or(0, |chi| chi.k.len())
+}
+
+/// Copies the [`FeffChiColumn`] `column` of chi(k) into `out`, which holds
+/// `capacity` doubles and must fit `feff_results_chi_len(results)`.
+///
+/// # Safety
+/// `results` must be a live results handle and `out` must point to at
+/// least `capacity` writable doubles.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn feff_results_chi(
+    results: *const FeffResults,
+    column: i32,
+    out: *mut f64,
+    capacity: usize,
+) -> FeffStatus {
+    guard(|| {
+        let results = unsafe { results.as_ref() }.ok_or_else(|| null_argument("results"))?;
+        let chi = results.results.chi.as_ref().ok_or_else(|| {
+            FeffError::input_validation("INPUT.FFI_NO_CHI", "the run wrote no chi.dat")
+        })?;
+        let values = match FeffChiColumn::from_raw(column) {
+            Some(FeffChiColumn::K) => &chi.k,
+            Some(FeffChiColumn::Chi) => &chi.chi,
+            Some(FeffChiColumn::Magnitude) => &chi.magnitude,
+            Some(FeffChiColumn::Phase) => &chi.phase,
+            None => {
+                return Err(FeffError::input_validation(
+                    "INPUT.FFI_CHI_COLUMN",
+                    format!("unknown chi column {}", column),
+                ));
+            }
+        };
+        unsafe { copy_column(values, out, capacity) }
+    })
+}
+
+/// Number of scattering paths in `paths.dat`.
+///
+/// # Safety
+/// `results` must be NULL or a live results handle.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn feff_results_path_count(results: *const FeffResults) -> usize {
+    unsafe { results.as_ref() }.map_or(0, |results| results.results.paths.len())
+}
+
+/// Releases results. NULL is ignored.
+///
+/// # Safety
+/// `results` must be NULL or a handle not yet freed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn feff_results_free(results: *mut FeffResults) {
+    if !results.is_null() {
+        drop(unsafe { Box::from_raw(results) });
+    }
+}
+
+/// Runs `module` in `directory`; the directory name labels the run in
+/// module outputs, as in the CLI.
+fn run_module(
+    directory: &Path,
+    module: ComputeModule,
+    execution_mode: ExecutionMode,
+) -> ComputeResult<()> {
+    let request = runtime_request(directory_label(directory), module, directory)?
+        .with_execution_mode(execution_mode);
+    execute_runtime_module(module, &request).map(|_| ())
+}
+
+fn execution_mode(workers: usize) -> ExecutionMode {
+    if workers > 1 {
+        ExecutionMode::Threaded { workers }
+    } else {
+        ExecutionMode::Serial
+    }
+}
+
+/// # Safety
+/// `value` must be NULL or a valid NUL-terminated string that outlives the
+/// returned reference.
+unsafe fn c_str<'a>(value: *const c_char, name: &str) -> ComputeResult<&'a str> {
+    if value.is_null() {
+        return Err(null_argument(name));
+    }
+    unsafe { CStr::from_ptr(value) }.to_str().map_err(|_| {
+        FeffError::input_validation(
+            "INPUT.FFI_STRING",
+            format!("argument '{}' is not valid UTF-8", name),
+        )
+    })
+}
+
+/// Copies `values` into `out`, which holds `capacity` doubles.
+///
+/// # Safety
+/// `out` must be NULL or point to at least `capacity` writable doubles.
+unsafe fn copy_column(values: &[f64], out: *mut f64, capacity: usize) -> ComputeResult<()> {
+    if out.is_null() {
+        return Err(null_argument("out"));
+    }
+    if capacity < values.len() {
+        return Err(FeffError::input_validation(
+            "INPUT.FFI_BUFFER_TOO_SMALL",
+            format!(
+                "column has {} points but the buffer holds {}",
+                values.len(),
+                capacity
+            ),
+        ));
+    }
+    unsafe { ptr::copy_nonoverlapping(values.as_ptr(), out, values.len()) };
+    Ok(())
+}
+
+/// # Safety
+/// `out` must be NULL or valid for a pointer write.
+unsafe fn write_handle<T>(out: *mut *mut T, value: T) -> ComputeResult<()> {
+    if out.is_null() {
+        return Err(null_argument("out"));
+    }
+    unsafe { out.write(Box::into_raw(Box::new(value))) };
+    Ok(())
+}
+
+fn null_argument(name: &str) -> FeffError {
+    FeffError::input_validation(
+        "INPUT.FFI_NULL_ARGUMENT",
+        format!("argument '{}' is NULL", name),
+    )
+}
@@ -0,0 +1,186 @@
+use feff_ffi::{
+    FeffChiColumn, FeffSpectrumColumn, FeffStatus, feff_deck_card_count, feff_deck_free,
+    feff_deck_parse, feff_deck_run, feff_last_error_code, feff_last_error_message,
+    feff_results_chi, feff_results_chi_len, feff_results_free, feff_results_spectrum,
+    feff_results_spectrum_len, feff_run_directory, feff_run_module,
+};
+use std::ffi::{CStr, CString};
+use std::path::{Path, PathBuf};
+use std::ptr;
+use tempfile::TempDir;
+
+fn crate_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+}
+
+fn workflow_deck() -> String {
+    std::fs::read_to_string(
+        crate_dir().join("../../artifacts/fortran-baselines/FX-WORKFLOW-XAS-001/baseline/feff.inp"),
+    )
+    .expect("workflow deck should be readable")
+}
+
+fn c_path(path: &Path) -> CString {
+    CString::new(path.to_str().expect("utf-8 path")).expect("path without NUL")
+}
+
+fn last_error_code() -> Option<String> {
+    let code = feff_last_error_code();
+    (!code.is_null()).then(|| {
+        unsafe { CStr::from_ptr(code) }
+            .to_string_lossy()
+            .into_owned()
+    })
+}
+
+fn spectrum_column(results: *const feff_ffi::FeffResults, column: FeffSpectrumColumn) -> Vec<f64> {
+    let len = unsafe { feff_results_spectrum_len(results) };
+    let mut values = vec![0.0; len];
+    let status = unsafe { feff_results_spectrum(results, column as i32, values.as_mut_ptr(), len) };
+    assert_eq!(status, FeffStatus::Success);
+    values
+}
+
+fn chi_column(results: *const feff_ffi::FeffResults, column: FeffChiColumn) -> Vec<f64> {
+    let len = unsafe { feff_results_chi_len(results) };
+    let mut values = vec![0.0; len];
+    let status = unsafe { feff_results_chi(results, column as i32, values.as_mut_ptr(), len) };
+    assert_eq!(status, FeffStatus::Success);
+    values
+}
+
+#[test]
+fn header_matches_cbindgen_output() {
+    let expected = cbindgen::Builder::new()
+        .with_crate(crate_dir())
+        .with_config(
+            cbindgen::Config::from_file(crate_dir().join("cbindgen.toml"))
+                .expect("cbindgen.toml should load"),
+        )
+        .generate()
+        .expect("header should generate");
+    let mut generated = Vec::new();
+    expected.write(&mut generated);
+    let generated = String::from_utf8(generated).expect("header is utf-8");
+    let header = crate_dir().join("include/feff.h");
+    if std::env::var_os("FEFF_FFI_UPDATE_HEADER").is_some() {
+        std::fs::write(&header, &generated).expect("header should be written");
+    }
+    let committed = std::fs::read_to_string(&header).expect("include/feff.h should exist");
+    assert_eq!(
+        committed, generated,
+        "include/feff.h is stale; rerun this test with FEFF_FFI_UPDATE_HEADER=1"
+    );
+}
+
+#[test]
+fn deck_runs_in_memory_and_matches_a_directory_run() {
+    let source = CString::new(workflow_deck()).expect("deck without NUL");
+    let mut deck = ptr::null_mut();
+    assert_eq!(
+        unsafe { feff_deck_parse(source.as_ptr(), &mut deck) },
+        FeffStatus::Success
+    );
+    assert!(unsafe { feff_deck_card_count(deck) } > 0);
+
+    // Module outputs depend on the run label, so the in-memory run uses the
+    // name of the directory the on-disk run happens in.
+    let label = CString::new("ffi_run").expect("label without NUL");
+    let mut memory = ptr::null_mut();
+    assert_eq!(
+        unsafe { feff_deck_run(deck, label.as_ptr(), 1, &mut memory) },
+        FeffStatus::Success
+    );
+    unsafe { feff_deck_free(deck) };
+
+    let temp = TempDir::new().expect("tempdir should be created");
+    let run_dir = temp.path().join("ffi_run");
+    std::fs::create_dir(&run_dir).expect("run directory should be created");
+    std::fs::write(run_dir.join("feff.inp"), workflow_deck()).expect("deck should be staged");
+    let mut on_disk = ptr::null_mut();
+    assert_eq!(
+        unsafe { feff_run_directory(c_path(&run_dir).as_ptr(), 2, &mut on_disk) },
+        FeffStatus::Success
+    );
+    assert!(run_dir.join("xmu.dat").is_file());
+    assert!(run_dir.join("feff10-run.json").is_file());
+
+    let mu = spectrum_column(memory, FeffSpectrumColumn::Mu);
+    assert!(!mu.is_empty());
+    assert_eq!(mu, spectrum_column(on_disk, FeffSpectrumColumn::Mu));
+    assert_eq!(
+        spectrum_column(memory, FeffSpectrumColumn::Chi).len(),
+        mu.len()
+    );
+
+    let k = chi_column(memory, FeffChiColumn::K);
+    assert!(!k.is_empty());
+    assert!(k.windows(2).all(|pair| pair[0] < pair[1]));
+    assert_eq!(
+        chi_column(memory, FeffChiColumn::Chi),
+        chi_column(on_disk, FeffChiColumn::Chi)
+    );
+    let mut chi = vec![0.0; k.len()];
+    assert_eq!(
+        unsafe { feff_results_chi(memory, 4, chi.as_mut_ptr(), chi.len()) },
+        FeffStatus::InputValidationError
+    );
+    assert_eq!(last_error_code().as_deref(), Some("INPUT.FFI_CHI_COLUMN"));
+
+    let mut short = vec![0.0; mu.len() - 1];
+    let status = unsafe {
+        feff_results_spectrum(
+            memory,
+            FeffSpectrumColumn::Mu as i32,
+            short.as_mut_ptr(),
+            short.len(),
+        )
+    };
+    assert_eq!(status, FeffStatus::InputValidationError);
+    assert_eq!(
+        last_error_code().as_deref(),
+        Some("INPUT.FFI_BUFFER_TOO_SMALL")
+    );
+    unsafe {
+        feff_results_free(memory);
+        feff_results_free(on_disk);
+    }
+}
+
+#[test]
+fn errors_report_compatibility_status_and_diagnostic() {
+    let source = CString::new("TITLE no structure\n").expect("deck without NUL");
+    let mut deck = ptr::null_mut();
+    assert_eq!(
+        unsafe { feff_deck_parse(source.as_ptr(), &mut deck) },
+        FeffStatus::InputValidationError
+    );
+    assert!(deck.is_null());
+    assert_eq!(
+        last_error_code().as_deref(),
+        Some("INPUT.MISSING_REQUIRED_CARD")
+    );
+    let message = unsafe { CStr::from_ptr(feff_last_error_message()) };
+    assert!(message.to_string_lossy().starts_with("ERROR: [INPUT."));
+
+    let temp = TempDir::new().expect("tempdir should be created");
+    let module = CString::new("pot").expect("module name");
+    assert_eq!(
+        unsafe { feff_run_module(c_path(temp.path()).as_ptr(), module.as_ptr(), 1) },
+        FeffStatus::IoSystemError
+    );
+    let unknown = CString::new("NOPE").expect("module name");
+    assert_eq!(
+        unsafe { feff_run_module(c_path(temp.path()).as_ptr(), unknown.as_ptr(), 1) },
+        FeffStatus::InputValidationError
+    );
+    assert_eq!(last_error_code().as_deref(), Some("INPUT.UNKNOWN_MODULE"));
+    assert_eq!(
+        unsafe { feff_run_module(ptr::null(), module.as_ptr(), 1) },
+        FeffStatus::InputValidationError
+    );
+    assert_eq!(
+        last_error_code().as_deref(),
+        Some("INPUT.FFI_NULL_ARGUMENT")
+    );
+}
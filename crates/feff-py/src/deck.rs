@@ -0,0 +1,167 @@
+use crate::to_py_error;
+use feff_core::domain::{InputCard, InputDeck};
+use feff_core::parser::parse_input_deck;
+use feff_core::parser::writer::render_input_deck;
+use pyo3::prelude::*;
+
+/// A parsed `feff.inp`. Cards keep their order; edits replace or insert
+/// whole cards.
+#[pyclass(name = "InputDeck", module = "feff")]
+#[derive(Debug, Clone, Default)]
+pub struct PyInputDeck {
+    deck: InputDeck,
+}
+
+impl PyInputDeck {
+    pub(crate) fn inner(&self) -> &InputDeck {
+        &self.deck
+    }
+}
+
+#[pymethods]
+impl PyInputDeck {
+    #[new]
+    fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parses `feff.inp` source.
+    #[staticmethod]
+    fn parse(source: &str) -> PyResult<Self> {
+        let deck = parse_input_deck(source).map_err(to_py_error)?;
+        Ok(Self { deck })
+    }
+
+    #[getter]
+    fn cards(&self) -> Vec<PyInputCard> {
+        self.deck
+            .cards
+            .iter()
+            .cloned()
+            .map(PyInputCard::from)
+            .collect()
+    }
+
+    /// The first card named `keyword` (case-insensitive), if any.
+    fn card(&self, keyword: &str) -> Option<PyInputCard> {
+        self.deck
+            .cards
+            .iter()
+            .find(|card| card.keyword.eq_ignore_ascii_case(keyword))
+            .cloned()
+            .map(PyInputCard::from)
+    }
+
+    /// Replaces the values of the first `keyword` card, or adds the card
+    /// before `END` when the deck has none. `rows` replaces its continuation
+    /// lines (e.g. `ATOMS` rows) when given.
+    #[pyo3(signature = (keyword, values, rows = None))]
+    fn set_card(&mut self, keyword: &str, values: Vec<String>, rows: Option<Vec<Vec<String>>>) {
+        let card = self.deck.set_card(keyword, values);
+        if let Some(rows) = rows {
+            card.set_rows(rows);
+        }
+    }
+
+    /// Removes every `keyword` card and returns how many were removed.
+    fn remove_card(&mut self, keyword: &str) -> usize {
+        self.deck.remove_cards(keyword)
+    }
+
+    /// The deck rendered as `feff.inp` text.
+    fn __str__(&self) -> String {
+        render_input_deck(&self.deck)
+    }
+
+    fn __len__(&self) -> usize {
+        self.deck.cards.len()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("InputDeck({} cards)", self.deck.cards.len())
+    }
+}
+
+/// A copy of one card; edit the deck with `InputDeck.set_card`.
+#[pyclass(name = "InputCard", frozen, module = "feff")]
+#[derive(Debug, Clone)]
+pub struct PyInputCard {
+    card: InputCard,
+}
+
+impl From<InputCard> for PyInputCard {
+    fn from(card: InputCard) -> Self {
+        Self { card }
+    }
+}
+
+#[pymethods]
+impl PyInputCard {
+    #[getter]
+    fn keyword(&self) -> &str {
+        &self.card.keyword
+    }
+
+    #[getter]
+    fn values(&self) -> Vec<String> {
+        self.card.values.clone()
+    }
+
+    /// Values of each continuation line, e.g. one list per `ATOMS` row.
+    #[getter]
+    fn rows(&self) -> Vec<Vec<String>> {
+        self.card
+            .continuations
+            .iter()
+            .map(|row| row.values.clone())
+            .collect()
+    }
+
+    #[getter]
+    fn source_line(&self) -> usize {
+        self.card.source_line
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "InputCard({} {})",
+            self.card.keyword,
+            self.card.values.join(" ")
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PyInputDeck;
+    use feff_core::parser::parse_input_deck;
+
+    #[test]
+    fn set_card_replaces_existing_cards_and_inserts_new_ones_before_end() {
+        let deck =
+            parse_input_deck("TITLE Cu\nS02 1.0\nPOTENTIALS\n0 29 Cu\nATOMS\n0 0 0 0\nEND\n")
+                .expect("deck should parse");
+        let mut deck = PyInputDeck { deck };
+
+        deck.set_card("s02", vec!["0.9".to_string()], None);
+        deck.set_card(
+            "RPATH",
+            vec!["5.0".to_string()],
+            Some(vec![vec!["1".to_string(), "2".to_string()]]),
+        );
+
+        let keywords: Vec<String> = deck
+            .inner()
+            .cards
+            .iter()
+            .map(|card| card.keyword.clone())
+            .collect();
+        let rpath = keywords.len() - 2;
+        assert_eq!(keywords[1], "S02");
+        assert_eq!(keywords[rpath..], ["RPATH", "END"]);
+        assert_eq!(deck.inner().cards[1].values, ["0.9"]);
+        assert_eq!(deck.inner().cards[rpath].continuations[0].raw, "1 2");
+        assert_eq!(deck.remove_card("rpath"), 1);
+        assert_eq!(deck.remove_card("RPATH"), 0);
+    }
+}
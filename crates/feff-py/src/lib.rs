@@ -0,0 +1,201 @@
+//! Python bindings for `feff-core`, built with maturin as the `feff` module.
+//!
+//! The classes mirror the Rust types: [`deck::PyInputDeck`] wraps
+//! `InputDeck`, [`results::PyComputeModule`] wraps `ComputeModule` and
+//! `run` drives a `feff_core::Calculation`. Spectra, paths and LDOS come back
+//! as numpy arrays.
+
+mod deck;
+mod results;
+
+use deck::{PyInputCard, PyInputDeck};
+use feff_core::domain::{ExecutionMode, FeffInput};
+use feff_core::{Calculation, Spectroscopy};
+use pyo3::create_exception;
+use pyo3::exceptions::PyException;
+use pyo3::prelude::*;
+use results::{
+    PyCalculationResults, PyComputeModule, PyLocalDensityOfStates, PyScatteringPaths, PySpectrum,
+};
+
+create_exception!(
+    feff,
+    FeffError,
+    PyException,
+    "A FEFF input, I/O or computation error; the message starts with its code."
+);
+
+pub(crate) fn to_py_error(error: feff_core::domain::FeffError) -> PyErr {
+    FeffError::new_err(error.diagnostic_line())
+}
+
+/// Spectroscopy a run produces, as in `feff_core::Spectroscopy`.
+#[pyclass(name = "Spectroscopy", eq, eq_int, frozen, module = "feff")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PySpectroscopy {
+    Exafs,
+    Xanes,
+    Elnes,
+    Exelfs,
+    Rixs,
+    Xes,
+    Compton,
+}
+
+impl From<PySpectroscopy> for Spectroscopy {
+    fn from(spectroscopy: PySpectroscopy) -> Self {
+        match spectroscopy {
+            PySpectroscopy::Exafs => Self::Exafs,
+            PySpectroscopy::Xanes => Self::Xanes,
+            PySpectroscopy::Elnes => Self::Elnes,
+            PySpectroscopy::Exelfs => Self::Exelfs,
+            PySpectroscopy::Rixs => Self::Rixs,
+            PySpectroscopy::Xes => Self::Xes,
+            PySpectroscopy::Compton => Self::Compton,
+        }
+    }
+}
+
+/// Runs every module `deck` enables in memory and returns the typed results.
+///
+/// `workers` > 1 splits the energy loops of XSPH, FMS and LDOS and the
+/// q-point loops of EELS and COMPTON across threads; DEBYE splits its paths
+/// and spectrum points. The GIL is released while the modules run.
+#[pyfunction]
+#[pyo3(signature = (deck, spectroscopy = None, workers = 1, label = None))]
+fn run(
+    py: Python<'_>,
+    deck: &PyInputDeck,
+    spectroscopy: Option<PySpectroscopy>,
+    workers: usize,
+    label: Option<String>,
+) -> PyResult<PyCalculationResults> {
+    let input = FeffInput::from_deck(deck.inner()).map_err(to_py_error)?;
+    let mut calculation = Calculation::from_input(input);
+    if let Some(spectroscopy) = spectroscopy {
+        calculation = calculation.spectroscopy(spectroscopy.into());
+    }
+    if workers > 1 {
+        calculation = calculation.execution_mode(ExecutionMode::Threaded { workers });
+    }
+    if let Some(label) = label {
+        calculation = calculation.label(label);
+    }
+    let results = py.detach(|| calculation.run()).map_err(to_py_error)?;
+    Ok(PyCalculationResults::new(results))
+}
+
+#[pymodule]
+fn feff(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add("FeffError", module.py().get_type::<FeffError>())?;
+    module.add_class::<PyInputDeck>()?;
+    module.add_class::<PyInputCard>()?;
+    module.add_class::<PyComputeModule>()?;
+    module.add_class::<PySpectroscopy>()?;
+    module.add_class::<PyCalculationResults>()?;
+    module.add_class::<PySpectrum>()?;
+    module.add_class::<PyScatteringPaths>()?;
+    module.add_class::<PyLocalDensityOfStates>()?;
+    module.add_function(wrap_pyfunction!(run, module)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use pyo3::prelude::*;
+    use pyo3::types::{PyBytes, PyDict};
+    use std::path::Path;
+
+    fn workflow_deck() -> String {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("../..");
+        std::fs::read_to_string(
+            root.join("artifacts/fortran-baselines/FX-WORKFLOW-XAS-001/baseline/feff.inp"),
+        )
+        .expect("workflow deck should be readable")
+    }
+
+    fn feff_module(py: Python<'_>) -> Bound<'_, PyModule> {
+        let module = PyModule::new(py, "feff").expect("module should be created");
+        super::feff(&module).expect("module should initialise");
+        module
+    }
+
+    fn run_workflow<'py>(feff: &Bound<'py, PyModule>, workers: usize) -> Bound<'py, PyAny> {
+        let py = feff.py();
+        let deck = feff
+            .getattr("InputDeck")
+            .and_then(|class| class.call_method1("parse", (workflow_deck(),)))
+            .expect("deck should parse");
+        deck.call_method1("set_card", ("RPATH", vec!["4.0"]))
+            .expect("RPATH should be set");
+        let kwargs = PyDict::new(py);
+        kwargs.set_item("workers", workers).unwrap();
+        feff.getattr("run")
+            .and_then(|run| run.call((deck,), Some(&kwargs)))
+            .expect("run should succeed")
+    }
+
+    fn artifact(results: &Bound<'_, PyAny>, name: &str) -> Vec<u8> {
+        results
+            .call_method1("artifact", (name,))
+            .and_then(|bytes| Ok(bytes.cast_into::<PyBytes>()?.as_bytes().to_vec()))
+            .unwrap_or_else(|error| panic!("{name} should be returned: {error}"))
+    }
+
+    #[test]
+    fn run_matches_between_serial_and_threaded_workers() {
+        Python::initialize();
+        Python::attach(|py| {
+            let feff = feff_module(py);
+            let serial = run_workflow(&feff, 1);
+            let threaded = run_workflow(&feff, 4);
+
+            let modules: Vec<String> = serial
+                .getattr("modules")
+                .and_then(|modules| {
+                    modules
+                        .try_iter()?
+                        .map(|module| module?.getattr("name")?.extract())
+                        .collect()
+                })
+                .expect("module names");
+            assert_eq!(modules.first().map(String::as_str), Some("RDINP"));
+            assert!(modules.iter().any(|module| module == "DEBYE"));
+            for name in ["xmu.dat", "chi.dat", "paths.dat"] {
+                assert_eq!(artifact(&serial, name), artifact(&threaded, name), "{name}");
+            }
+            assert!(
+                serial
+                    .call_method1("artifact", ("missing.dat",))
+                    .unwrap()
+                    .is_none()
+            );
+            let legs: Vec<Vec<usize>> = serial
+                .getattr("paths")
+                .and_then(|paths| paths.getattr("legs")?.extract())
+                .expect("path legs");
+            assert!(!legs.is_empty());
+            assert!(legs.iter().all(|path| !path.is_empty()));
+        });
+    }
+
+    #[test]
+    fn errors_raise_feff_error_with_diagnostic_line() {
+        Python::initialize();
+        Python::attach(|py| {
+            let feff = feff_module(py);
+            let error = feff
+                .getattr("InputDeck")
+                .and_then(|class| class.call_method1("parse", ("TITLE empty\nEND\n",)))
+                .expect_err("a deck without atoms should not parse");
+
+            assert!(error.is_instance_of::<super::FeffError>(py));
+            assert!(
+                error
+                    .to_string()
+                    .contains("ERROR: [INPUT.MISSING_REQUIRED_CARD]"),
+                "{error}"
+            );
+        });
+    }
+}
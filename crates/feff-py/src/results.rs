@@ -0,0 +1,289 @@
+use feff_core::domain::ComputeModule;
+use feff_core::{CalculationResults, LocalDensityOfStates, ScatteringPath, Spectrum};
+use numpy::{PyArray1, PyArray2, PyArrayMethods};
+use pyo3::prelude::*;
+use pyo3::types::PyBytes;
+
+/// A FEFF module, as in `feff_core::domain::ComputeModule`.
+#[pyclass(name = "ComputeModule", eq, eq_int, frozen, module = "feff")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PyComputeModule {
+    Rdinp,
+    Pot,
+    Path,
+    Fms,
+    Xsph,
+    Band,
+    Ldos,
+    Rixs,
+    Crpa,
+    Compton,
+    Debye,
+    Dmdw,
+    Screen,
+    SelfEnergy,
+    Eels,
+    FullSpectrum,
+}
+
+impl From<ComputeModule> for PyComputeModule {
+    fn from(module: ComputeModule) -> Self {
+        match module {
+            ComputeModule::Rdinp => Self::Rdinp,
+            ComputeModule::Pot => Self::Pot,
+            ComputeModule::Path => Self::Path,
+            ComputeModule::Fms => Self::Fms,
+            ComputeModule::Xsph => Self::Xsph,
+            ComputeModule::Band => Self::Band,
+            ComputeModule::Ldos => Self::Ldos,
+            ComputeModule::Rixs => Self::Rixs,
+            ComputeModule::Crpa => Self::Crpa,
+            ComputeModule::Compton => Self::Compton,
+            ComputeModule::Debye => Self::Debye,
+            ComputeModule::Dmdw => Self::Dmdw,
+            ComputeModule::Screen => Self::Screen,
+            ComputeModule::SelfEnergy => Self::SelfEnergy,
+            ComputeModule::Eels => Self::Eels,
+            ComputeModule::FullSpectrum => Self::FullSpectrum,
+        }
+    }
+}
+
+impl From<PyComputeModule> for ComputeModule {
+    fn from(module: PyComputeModule) -> Self {
+        match module {
+            PyComputeModule::Rdinp => Self::Rdinp,
+            PyComputeModule::Pot => Self::Pot,
+            PyComputeModule::Path => Self::Path,
+            PyComputeModule::Fms => Self::Fms,
+            PyComputeModule::Xsph => Self::Xsph,
+            PyComputeModule::Band => Self::Band,
+            PyComputeModule::Ldos => Self::Ldos,
+            PyComputeModule::Rixs => Self::Rixs,
+            PyComputeModule::Crpa => Self::Crpa,
+            PyComputeModule::Compton => Self::Compton,
+            PyComputeModule::Debye => Self::Debye,
+            PyComputeModule::Dmdw => Self::Dmdw,
+            PyComputeModule::Screen => Self::Screen,
+            PyComputeModule::SelfEnergy => Self::SelfEnergy,
+            PyComputeModule::Eels => Self::Eels,
+            PyComputeModule::FullSpectrum => Self::FullSpectrum,
+        }
+    }
+}
+
+#[pymethods]
+impl PyComputeModule {
+    /// The FEFF name, e.g. `"XSPH"`.
+    #[getter]
+    fn name(&self) -> &'static str {
+        ComputeModule::from(*self).as_str()
+    }
+}
+
+/// Outputs of `feff.run`.
+#[pyclass(name = "CalculationResults", frozen, module = "feff")]
+#[derive(Debug)]
+pub struct PyCalculationResults {
+    results: CalculationResults,
+}
+
+impl PyCalculationResults {
+    pub(crate) fn new(results: CalculationResults) -> Self {
+        Self { results }
+    }
+}
+
+#[pymethods]
+impl PyCalculationResults {
+    /// Modules in the order they ran.
+    #[getter]
+    fn modules(&self) -> Vec<PyComputeModule> {
+        self.results
+            .modules
+            .iter()
+            .copied()
+            .map(PyComputeModule::from)
+            .collect()
+    }
+
+    /// `xmu.dat`, or `None` when no module wrote it.
+    #[getter]
+    fn spectrum(&self) -> Option<PySpectrum> {
+        self.results
+            .spectrum
+            .clone()
+            .map(|spectrum| PySpectrum { spectrum })
+    }
+
+    #[getter]
+    fn paths(&self) -> PyScatteringPaths {
+        PyScatteringPaths {
+            paths: self.results.paths.clone(),
+        }
+    }
+
+    /// One entry per `ldosNN.dat`.
+    #[getter]
+    fn ldos(&self) -> Vec<PyLocalDensityOfStates> {
+        self.results
+            .ldos
+            .iter()
+            .cloned()
+            .map(|ldos| PyLocalDensityOfStates { ldos })
+            .collect()
+    }
+
+    fn artifact_names(&self) -> Vec<String> {
+        self.results.artifact_names()
+    }
+
+    /// Contents of the artifact `name` (e.g. `"chi.dat"`), if a module wrote it.
+    fn artifact<'py>(&self, py: Python<'py>, name: &str) -> Option<Bound<'py, PyBytes>> {
+        self.results
+            .artifact(name)
+            .map(|bytes| PyBytes::new(py, &bytes))
+    }
+}
+
+/// `xmu.dat` columns as numpy arrays.
+#[pyclass(name = "Spectrum", frozen, module = "feff")]
+#[derive(Debug, Clone)]
+pub struct PySpectrum {
+    spectrum: Spectrum,
+}
+
+#[pymethods]
+impl PySpectrum {
+    /// Absolute energy in eV.
+    #[getter]
+    fn energy<'py>(&self, py: Python<'py>) -> Bound<'py, PyArray1<f64>> {
+        PyArray1::from_slice(py, &self.spectrum.energy)
+    }
+
+    #[getter]
+    fn k<'py>(&self, py: Python<'py>) -> Bound<'py, PyArray1<f64>> {
+        PyArray1::from_slice(py, &self.spectrum.k)
+    }
+
+    #[getter]
+    fn mu<'py>(&self, py: Python<'py>) -> Bound<'py, PyArray1<f64>> {
+        PyArray1::from_slice(py, &self.spectrum.mu)
+    }
+
+    #[getter]
+    fn mu0<'py>(&self, py: Python<'py>) -> Bound<'py, PyArray1<f64>> {
+        PyArray1::from_slice(py, &self.spectrum.mu0)
+    }
+
+    #[getter]
+    fn chi<'py>(&self, py: Python<'py>) -> Bound<'py, PyArray1<f64>> {
+        PyArray1::from_slice(py, &self.spectrum.chi)
+    }
+
+    fn __len__(&self) -> usize {
+        self.spectrum.energy.len()
+    }
+}
+
+/// `paths.dat` as one numpy array per column; `legs` stays a list of
+/// 1-based atom indices per path.
+#[pyclass(name = "ScatteringPaths", frozen, module = "feff")]
+#[derive(Debug, Clone)]
+pub struct PyScatteringPaths {
+    paths: Vec<ScatteringPath>,
+}
+
+impl PyScatteringPaths {
+    fn column<'py, T: numpy::Element>(
+        &self,
+        py: Python<'py>,
+        value: impl Fn(&ScatteringPath) -> T,
+    ) -> Bound<'py, PyArray1<T>> {
+        PyArray1::from_vec(py, self.paths.iter().map(value).collect())
+    }
+}
+
+#[pymethods]
+impl PyScatteringPaths {
+    #[getter]
+    fn index<'py>(&self, py: Python<'py>) -> Bound<'py, PyArray1<usize>> {
+        self.column(py, |path| path.index)
+    }
+
+    #[getter]
+    fn nleg<'py>(&self, py: Python<'py>) -> Bound<'py, PyArray1<usize>> {
+        self.column(py, |path| path.nleg)
+    }
+
+    #[getter]
+    fn degeneracy<'py>(&self, py: Python<'py>) -> Bound<'py, PyArray1<f64>> {
+        self.column(py, |path| path.degeneracy)
+    }
+
+    #[getter]
+    fn reff<'py>(&self, py: Python<'py>) -> Bound<'py, PyArray1<f64>> {
+        self.column(py, |path| path.reff)
+    }
+
+    #[getter]
+    fn amplitude<'py>(&self, py: Python<'py>) -> Bound<'py, PyArray1<f64>> {
+        self.column(py, |path| path.amplitude)
+    }
+
+    #[getter]
+    fn beta<'py>(&self, py: Python<'py>) -> Bound<'py, PyArray1<f64>> {
+        self.column(py, |path| path.beta)
+    }
+
+    #[getter]
+    fn eta<'py>(&self, py: Python<'py>) -> Bound<'py, PyArray1<f64>> {
+        self.column(py, |path| path.eta)
+    }
+
+    #[getter]
+    fn legs(&self) -> Vec<Vec<usize>> {
+        self.paths.iter().map(|path| path.legs.clone()).collect()
+    }
+
+    fn __len__(&self) -> usize {
+        self.paths.len()
+    }
+}
+
+/// `ldosNN.dat`: `energy` plus `(n, 4)` arrays of s, p, d and f densities.
+#[pyclass(name = "LocalDensityOfStates", frozen, module = "feff")]
+#[derive(Debug, Clone)]
+pub struct PyLocalDensityOfStates {
+    ldos: LocalDensityOfStates,
+}
+
+#[pymethods]
+impl PyLocalDensityOfStates {
+    #[getter]
+    fn ipot(&self) -> usize {
+        self.ldos.ipot
+    }
+
+    #[getter]
+    fn energy<'py>(&self, py: Python<'py>) -> Bound<'py, PyArray1<f64>> {
+        PyArray1::from_slice(py, &self.ldos.energy)
+    }
+
+    #[getter]
+    fn up<'py>(&self, py: Python<'py>) -> Bound<'py, PyArray2<f64>> {
+        channel_array(py, &self.ldos.up)
+    }
+
+    #[getter]
+    fn down<'py>(&self, py: Python<'py>) -> Bound<'py, PyArray2<f64>> {
+        channel_array(py, &self.ldos.down)
+    }
+}
+
+fn channel_array<'py>(py: Python<'py>, rows: &[[f64; 4]]) -> Bound<'py, PyArray2<f64>> {
+    let flat = rows.iter().flatten().copied().collect::<Vec<_>>();
+    PyArray1::from_vec(py, flat)
+        .reshape([rows.len(), 4])
+        .expect("ldos rows have four channels")
+}
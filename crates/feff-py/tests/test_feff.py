@@ -0,0 +1,67 @@
+"""Smoke tests for the numpy views; run with `maturin develop && pytest`."""
+
+from pathlib import Path
+
+import numpy as np
+import pytest
+
+import feff
+
+WORKFLOW_DECK = (
+    Path(__file__).resolve().parents[3]
+    / "artifacts/fortran-baselines/FX-WORKFLOW-XAS-001/baseline/feff.inp"
+)
+
+
+@pytest.fixture(scope="module")
+def results():
+    deck = feff.InputDeck.parse(WORKFLOW_DECK.read_text())
+    deck.set_card("RPATH", ["4.0"])
+    deck.set_card("LDOS", ["-20", "20", "0.5"])
+    return feff.run(deck, workers=2)
+
+
+def numeric_rows(text):
+    return [
+        [float(field.replace("D", "E")) for field in line.split()]
+        for line in text.splitlines()
+        if line.strip() and not line.lstrip().startswith("#")
+    ]
+
+
+def test_spectrum_columns_match_xmu_dat(results):
+    spectrum = results.spectrum
+    rows = np.array(numeric_rows(results.artifact("xmu.dat").decode()))
+
+    assert len(spectrum) == rows.shape[0]
+    for column, index in (("energy", 0), ("k", 2), ("mu", 3), ("mu0", 4), ("chi", 5)):
+        values = getattr(spectrum, column)
+        assert values.dtype == np.float64
+        np.testing.assert_array_equal(values, rows[:, index], err_msg=column)
+
+
+def test_path_columns_match_paths_dat(results):
+    paths = results.paths
+    text = results.artifact("paths.dat").decode()
+    rows = [line.split() for line in text.split("index nleg", 1)[1].splitlines()[1:]]
+    rows = [row for row in rows if row and row[0] != "0"]
+
+    assert len(paths) == len(rows) > 0
+    np.testing.assert_array_equal(paths.index, [int(row[0]) for row in rows])
+    np.testing.assert_array_equal(paths.nleg, [int(row[1]) for row in rows])
+    np.testing.assert_array_equal(paths.reff, [float(row[3]) for row in rows])
+    np.testing.assert_array_equal(paths.eta, [float(row[6]) for row in rows])
+    assert paths.reff.dtype == np.float64
+    assert paths.legs == [[int(atom) for atom in row[7].split(",")] for row in rows]
+
+
+def test_ldos_channels_reshape_to_rows_of_four(results):
+    assert [ldos.ipot for ldos in results.ldos] == [0, 1]
+    ldos = results.ldos[0]
+    rows = np.array(numeric_rows(results.artifact("ldos00.dat").decode()))
+
+    assert ldos.up.shape == (len(ldos.energy), 4)
+    assert ldos.down.shape == (len(ldos.energy), 4)
+    np.testing.assert_array_equal(ldos.energy, rows[:, 0])
+    np.testing.assert_array_equal(ldos.up, rows[:, 1:5])
+    np.testing.assert_array_equal(ldos.down, rows[:, 5:9])
@@ -0,0 +1,151 @@
+use crate::WasmError;
+use feff_core::domain::InputDeck;
+use feff_core::parser::lint::{self, lint_input_source};
+use feff_core::parser::parse_input_deck;
+use feff_core::parser::writer::render_input_deck;
+use wasm_bindgen::prelude::*;
+
+/// A parsed `feff.inp`. Cards keep their order; edits replace or insert
+/// whole cards.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Default)]
+pub struct Deck {
+    deck: InputDeck,
+}
+
+#[wasm_bindgen]
+impl Deck {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parses `feff.inp` source.
+    pub fn parse(source: &str) -> Result<Deck, WasmError> {
+        let deck = parse_input_deck(source)?;
+        Ok(Self { deck })
+    }
+
+    /// Keyword of every card, in deck order.
+    pub fn keywords(&self) -> Vec<String> {
+        self.deck
+            .cards
+            .iter()
+            .map(|card| card.keyword.clone())
+            .collect()
+    }
+
+    /// Values of the first `keyword` card (case-insensitive), if any.
+    pub fn values(&self, keyword: &str) -> Option<Vec<String>> {
+        self.deck
+            .cards
+            .iter()
+            .find(|card| card.keyword.eq_ignore_ascii_case(keyword))
+            .map(|card| card.values.clone())
+    }
+
+    /// Continuation lines of the first `keyword` card (e.g. `ATOMS` rows),
+    /// one string per row.
+    pub fn rows(&self, keyword: &str) -> Option<Vec<String>> {
+        self.deck
+            .cards
+            .iter()
+            .find(|card| card.keyword.eq_ignore_ascii_case(keyword))
+            .map(|card| {
+                card.continuations
+                    .iter()
+                    .map(|row| row.raw.clone())
+                    .collect()
+            })
+    }
+
+    /// Replaces the values of the first `keyword` card, or adds the card
+    /// before `END`. `rows`, split on whitespace, replaces its continuation
+    /// lines when given.
+    #[wasm_bindgen(js_name = setCard)]
+    pub fn set_card(&mut self, keyword: &str, values: Vec<String>, rows: Option<Vec<String>>) {
+        let card = self.deck.set_card(keyword, values);
+        if let Some(rows) = rows {
+            card.set_rows(
+                rows.iter()
+                    .map(|row| row.split_whitespace().map(str::to_string).collect())
+                    .collect(),
+            );
+        }
+    }
+
+    /// Removes every `keyword` card and returns how many were removed.
+    #[wasm_bindgen(js_name = removeCard)]
+    pub fn remove_card(&mut self, keyword: &str) -> usize {
+        self.deck.remove_cards(keyword)
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn length(&self) -> usize {
+        self.deck.cards.len()
+    }
+
+    /// The deck rendered as `feff.inp` text.
+    #[wasm_bindgen(js_name = toString)]
+    pub fn render(&self) -> String {
+        render_input_deck(&self.deck)
+    }
+}
+
+/// One problem `lint` found, positioned for an editor.
+#[wasm_bindgen]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    diagnostic: lint::Diagnostic,
+}
+
+#[wasm_bindgen]
+impl Diagnostic {
+    #[wasm_bindgen(getter)]
+    pub fn code(&self) -> String {
+        self.diagnostic.code.to_string()
+    }
+
+    /// `"error"`, `"warning"` or `"info"`.
+    #[wasm_bindgen(getter)]
+    pub fn severity(&self) -> String {
+        self.diagnostic.severity.as_str().to_string()
+    }
+
+    /// 1-based line.
+    #[wasm_bindgen(getter)]
+    pub fn line(&self) -> usize {
+        self.diagnostic.span.line
+    }
+
+    /// 1-based column the problem starts at.
+    #[wasm_bindgen(getter)]
+    pub fn column(&self) -> usize {
+        self.diagnostic.span.column
+    }
+
+    /// Column just past the end of the problem.
+    #[wasm_bindgen(getter, js_name = endColumn)]
+    pub fn end_column(&self) -> usize {
+        self.diagnostic.span.end_column
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn message(&self) -> String {
+        self.diagnostic.message.clone()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn fix(&self) -> String {
+        self.diagnostic.fix.clone()
+    }
+}
+
+/// Every problem in `source`, ordered by position, as `check` reports them.
+#[wasm_bindgen]
+pub fn lint(source: &str) -> Vec<Diagnostic> {
+    lint_input_source(source)
+        .into_iter()
+        .map(|diagnostic| Diagnostic { diagnostic })
+        .collect()
+}
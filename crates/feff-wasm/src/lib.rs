@@ -0,0 +1,56 @@
+//! WebAssembly facade over `feff-core` for in-browser deck editing and path
+//! viewing, built with `wasm-pack build crates/feff-wasm`.
+//!
+//! `feff-core` is built without its `native` feature, so nothing here reads
+//! the filesystem or spawns threads. [`Deck`] edits and lints a deck, and
+//! [`Workspace`] runs modules such as RDINP, PATH (GENFMT) and DEBYE (ff2x)
+//! one at a time against a `MemoryStore` the page fills and reads back.
+
+mod deck;
+mod workspace;
+
+pub use deck::{Deck, Diagnostic, lint};
+pub use workspace::{ScatteringPath, Spectrum, Workspace, modules};
+
+use feff_core::domain::FeffError;
+use wasm_bindgen::prelude::*;
+
+/// Error thrown to JavaScript as `FeffError`, with the code and exit status
+/// the CLI reports for the same failure.
+#[wasm_bindgen(js_name = FeffError)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WasmError {
+    code: String,
+    message: String,
+    exit_code: i32,
+}
+
+#[wasm_bindgen(js_class = FeffError)]
+impl WasmError {
+    /// Placeholder code, e.g. `"INPUT.MISSING_REQUIRED_CARD"`.
+    #[wasm_bindgen(getter)]
+    pub fn code(&self) -> String {
+        self.code.clone()
+    }
+
+    /// The `ERROR: [CODE] message` diagnostic line.
+    #[wasm_bindgen(getter)]
+    pub fn message(&self) -> String {
+        self.message.clone()
+    }
+
+    #[wasm_bindgen(getter, js_name = exitCode)]
+    pub fn exit_code(&self) -> i32 {
+        self.exit_code
+    }
+}
+
+impl From<FeffError> for WasmError {
+    fn from(error: FeffError) -> Self {
+        Self {
+            code: error.placeholder().to_string(),
+            message: error.diagnostic_line(),
+            exit_code: error.exit_code(),
+        }
+    }
+}
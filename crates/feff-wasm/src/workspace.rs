@@ -0,0 +1,204 @@
+use crate::WasmError;
+use feff_core::domain::{ArtifactStore, FeffError, MemoryStore, SharedStore};
+use feff_core::modules::rdinp::WORKFLOW_MODULE_ORDER;
+use feff_core::modules::{execute_runtime_module, runtime_request};
+use feff_core::{CalculationResults, calculation};
+use std::path::Path;
+use std::sync::Arc;
+use wasm_bindgen::prelude::*;
+
+/// In-memory directory the modules of a workspace read and write.
+const RUN_DIRECTORY: &str = "workspace";
+
+/// An in-memory run directory. The page writes the inputs a module needs
+/// (`feff.inp`, or e.g. a `phase.bin` from a native XSPH run), runs modules
+/// by name and reads their artifacts back.
+#[wasm_bindgen]
+#[derive(Debug)]
+pub struct Workspace {
+    store: Arc<MemoryStore>,
+    label: String,
+}
+
+#[wasm_bindgen]
+impl Workspace {
+    /// `label` names the run in module outputs, as the run directory name
+    /// does for the CLI; it defaults to `"workspace"`.
+    #[wasm_bindgen(constructor)]
+    pub fn new(label: Option<String>) -> Self {
+        Self {
+            store: Arc::new(MemoryStore::new()),
+            label: label.unwrap_or_else(|| RUN_DIRECTORY.to_string()),
+        }
+    }
+
+    #[wasm_bindgen(js_name = writeFile)]
+    pub fn write_file(&self, name: &str, bytes: &[u8]) {
+        self.store
+            .insert(Path::new(RUN_DIRECTORY).join(name), bytes);
+    }
+
+    #[wasm_bindgen(js_name = writeText)]
+    pub fn write_text(&self, name: &str, text: &str) {
+        self.write_file(name, text.as_bytes());
+    }
+
+    #[wasm_bindgen(js_name = readFile)]
+    pub fn read_file(&self, name: &str) -> Option<Vec<u8>> {
+        self.store.get(Path::new(RUN_DIRECTORY).join(name))
+    }
+
+    /// The artifact `name` as text; invalid UTF-8 is replaced.
+    #[wasm_bindgen(js_name = readText)]
+    pub fn read_text(&self, name: &str) -> Option<String> {
+        self.read_file(name)
+            .map(|bytes| String::from_utf8_lossy(&bytes).into_owned())
+    }
+
+    /// Names of every file in the workspace, sorted.
+    pub fn files(&self) -> Vec<String> {
+        self.store
+            .list_files(Path::new(RUN_DIRECTORY))
+            .unwrap_or_default()
+    }
+
+    /// Runs the module `name` (e.g. `"RDINP"`, case-insensitive) on the
+    /// workspace files and returns the names of the artifacts it wrote.
+    #[wasm_bindgen(js_name = runModule)]
+    pub fn run_module(&self, name: &str) -> Result<Vec<String>, WasmError> {
+        let module = name.parse()?;
+        let request = runtime_request(&self.label, module, Path::new(RUN_DIRECTORY))?
+            .with_store(SharedStore::new(self.store.clone()));
+        let artifacts = execute_runtime_module(module, &request)?;
+        Ok(artifacts
+            .into_iter()
+            .map(|artifact| artifact.relative_path.to_string_lossy().into_owned())
+            .collect())
+    }
+
+    /// Scattering paths listed in `paths.dat`, empty before PATH runs.
+    pub fn paths(&self) -> Result<Vec<ScatteringPath>, WasmError> {
+        Ok(self
+            .results()?
+            .paths
+            .into_iter()
+            .map(|path| ScatteringPath { path })
+            .collect())
+    }
+
+    /// The `xmu.dat` spectrum, once DEBYE (or another spectrum module) ran.
+    pub fn spectrum(&self) -> Result<Option<Spectrum>, WasmError> {
+        Ok(self
+            .results()?
+            .spectrum
+            .map(|spectrum| Spectrum { spectrum }))
+    }
+}
+
+impl Workspace {
+    fn results(&self) -> Result<CalculationResults, FeffError> {
+        CalculationResults::from_store(
+            SharedStore::new(self.store.clone()),
+            RUN_DIRECTORY,
+            Vec::new(),
+        )
+    }
+}
+
+/// Names of the modules `Workspace.runModule` accepts, in pipeline order.
+#[wasm_bindgen]
+pub fn modules() -> Vec<String> {
+    WORKFLOW_MODULE_ORDER
+        .into_iter()
+        .map(|module| module.as_str().to_string())
+        .collect()
+}
+
+/// One row of `paths.dat`.
+#[wasm_bindgen]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScatteringPath {
+    path: calculation::ScatteringPath,
+}
+
+#[wasm_bindgen]
+impl ScatteringPath {
+    #[wasm_bindgen(getter)]
+    pub fn index(&self) -> usize {
+        self.path.index
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn nleg(&self) -> usize {
+        self.path.nleg
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn degeneracy(&self) -> f64 {
+        self.path.degeneracy
+    }
+
+    /// Half the path length, in Angstrom.
+    #[wasm_bindgen(getter)]
+    pub fn reff(&self) -> f64 {
+        self.path.reff
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn amplitude(&self) -> f64 {
+        self.path.amplitude
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn beta(&self) -> f64 {
+        self.path.beta
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn eta(&self) -> f64 {
+        self.path.eta
+    }
+
+    /// 1-based atom indices of the legs.
+    #[wasm_bindgen(getter)]
+    pub fn legs(&self) -> Vec<usize> {
+        self.path.legs.clone()
+    }
+}
+
+/// `xmu.dat` columns as `Float64Array`s.
+#[wasm_bindgen]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Spectrum {
+    spectrum: calculation::Spectrum,
+}
+
+#[wasm_bindgen]
+impl Spectrum {
+    /// Absolute energy, in eV.
+    #[wasm_bindgen(getter)]
+    pub fn energy(&self) -> Vec<f64> {
+        self.spectrum.energy.clone()
+    }
+
+    /// Wave number, in 1/Angstrom.
+    #[wasm_bindgen(getter)]
+    pub fn k(&self) -> Vec<f64> {
+        self.spectrum.k.clone()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn mu(&self) -> Vec<f64> {
+        self.spectrum.mu.clone()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn mu0(&self) -> Vec<f64> {
+        self.spectrum.mu0.clone()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn chi(&self) -> Vec<f64> {
+        self.spectrum.chi.clone()
+    }
+}
@@ -0,0 +1,119 @@
+use feff_core::Calculation;
+use feff_wasm::{Deck, Diagnostic, Workspace, lint, modules};
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen_test::wasm_bindgen_test as test;
+
+// Runs natively with `cargo test` and under Node with `wasm-pack test --node`;
+// wasm32 has no filesystem, so fixtures are compiled in.
+const WORKFLOW_DECK: &str =
+    include_str!("../../../artifacts/fortran-baselines/FX-WORKFLOW-XAS-001/baseline/feff.inp");
+
+macro_rules! path_baseline {
+    ($name:literal) => {
+        include_bytes!(concat!(
+            "../../../artifacts/fortran-baselines/FX-PATH-001/baseline/",
+            $name
+        ))
+        .as_slice()
+    };
+}
+
+#[test]
+fn deck_edits_render_and_lint() {
+    let mut deck = Deck::parse(WORKFLOW_DECK).expect("workflow deck should parse");
+    let cards = deck.length();
+    assert_eq!(deck.values("S02"), None);
+    deck.set_card("s02", vec!["0.85".to_string()], None);
+    deck.set_card(
+        "RPATH",
+        vec!["4.5".to_string()],
+        Some(vec!["1  2".to_string()]),
+    );
+
+    assert_eq!(deck.length(), cards + 2);
+    assert_eq!(deck.values("S02"), Some(vec!["0.85".to_string()]));
+    assert_eq!(deck.rows("rpath"), Some(vec!["1 2".to_string()]));
+    let rendered = deck.render();
+    let reparsed = Deck::parse(&rendered).expect("rendered deck should parse");
+    assert_eq!(reparsed.keywords(), deck.keywords());
+    assert_eq!(deck.remove_card("RPATH"), 1);
+
+    let is_error = |diagnostic: &Diagnostic| diagnostic.severity() == "error";
+    assert!(!lint(WORKFLOW_DECK).iter().any(is_error));
+    // ATOMS uses potential 1, which POTENTIALS does not define.
+    let diagnostics = lint("POTENTIALS\n0 29 Cu\nATOMS\n0 0 0 1\nEND\n");
+    let undefined = diagnostics
+        .iter()
+        .find(|diagnostic| diagnostic.code() == "LINT.UNDEFINED_POTENTIAL")
+        .expect("undefined potential should be reported");
+    assert!(is_error(undefined));
+    assert_eq!((undefined.line(), undefined.column()), (4, 7));
+    assert!(undefined.column() < undefined.end_column());
+
+    let error = Deck::parse("TITLE no structure\n").expect_err("deck has no atoms");
+    assert_eq!(error.code(), "INPUT.MISSING_REQUIRED_CARD");
+    assert_eq!(error.exit_code(), 2);
+    assert!(error.message().starts_with("ERROR: [INPUT."));
+}
+
+#[test]
+fn path_runs_on_staged_artifacts() {
+    let workspace = Workspace::new(None);
+    assert!(workspace.paths().expect("empty workspace").is_empty());
+    workspace.write_file("paths.inp", path_baseline!("paths.inp"));
+    workspace.write_file("geom.dat", path_baseline!("geom.dat"));
+    workspace.write_file("global.inp", path_baseline!("global.inp"));
+    workspace.write_file("phase.bin", path_baseline!("phase.bin"));
+
+    let written = workspace.run_module("path").expect("PATH should run");
+    assert!(written.contains(&"paths.dat".to_string()));
+    assert!(workspace.files().contains(&"paths.dat".to_string()));
+
+    let paths = workspace.paths().expect("paths.dat should parse");
+    assert!(!paths.is_empty());
+    assert!(paths.iter().all(|path| path.reff() > 0.0));
+    assert!(
+        paths
+            .iter()
+            .all(|path| path.nleg() >= 2 && !path.legs().is_empty())
+    );
+
+    let error = workspace
+        .run_module("NOPE")
+        .expect_err("unknown module should fail");
+    assert_eq!(error.code(), "INPUT.UNKNOWN_MODULE");
+    assert!(modules().contains(&"DEBYE".to_string()));
+}
+
+#[test]
+fn workspace_runs_match_a_calculation() {
+    let expected = Calculation::from_source(WORKFLOW_DECK)
+        .expect("workflow deck should parse")
+        .label("workspace")
+        .run()
+        .expect("calculation should run");
+
+    let workspace = Workspace::new(None);
+    workspace.write_text("feff.inp", WORKFLOW_DECK);
+    for module in &expected.modules {
+        workspace
+            .run_module(module.as_str())
+            .unwrap_or_else(|error| panic!("{module} failed: {}", error.message()));
+    }
+
+    let spectrum = workspace
+        .spectrum()
+        .expect("xmu.dat should parse")
+        .expect("DEBYE writes xmu.dat");
+    let reference = expected.spectrum.as_ref().expect("calculation spectrum");
+    assert_eq!(spectrum.energy(), reference.energy);
+    assert_eq!(spectrum.mu(), reference.mu);
+    assert_eq!(
+        workspace.paths().expect("paths.dat should parse").len(),
+        expected.paths.len()
+    );
+    assert_eq!(
+        workspace.read_text("xmu.dat").map(String::into_bytes),
+        expected.artifact("xmu.dat")
+    );
+}